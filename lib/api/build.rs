@@ -92,6 +92,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("CreateCollection.optimizers_config", ""),
             ("CreateCollection.vectors_config", ""),
             ("CreateCollection.quantization_config", ""),
+            ("CreateCollection.mmap_hot_percent", "range(min = 1, max = 100)"),
             ("UpdateCollection.collection_name", "length(min = 1, max = 255)"),
             ("UpdateCollection.optimizers_config", ""),
             ("UpdateCollection.params", ""),
@@ -109,6 +110,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("ChangeAliases.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("ListCollectionAliasesRequest.collection_name", "length(min = 1, max = 255)"),
             ("HnswConfigDiff.ef_construct", "custom = \"crate::grpc::validate::validate_u64_range_min_4\""),
+            ("HnswConfigDiff.target_recall", "custom = \"crate::grpc::validate::validate_f64_range_1\""),
+            ("HnswConfigDiff.level_factor_multiplier", "custom = \"crate::grpc::validate::validate_f64_range_min_0\""),
             ("WalConfigDiff.wal_capacity_mb", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("OptimizersConfigDiff.deleted_threshold", "custom = \"crate::grpc::validate::validate_f64_range_1\""),
             ("OptimizersConfigDiff.vacuum_min_vector_number", "custom = \"crate::grpc::validate::validate_u64_range_min_100\""),
@@ -117,6 +120,13 @@ fn configure_validation(builder: Builder) -> Builder {
             ("VectorParams.size", "range(min = 1, max = 65536)"),
             ("VectorParams.hnsw_config", ""),
             ("VectorParams.quantization_config", ""),
+            ("VectorParams.index", ""),
+            ("VamanaIndexParams.max_degree", "custom = \"crate::grpc::validate::validate_u64_range_min_4\""),
+            ("VamanaIndexParams.build_list_size", "custom = \"crate::grpc::validate::validate_u64_range_min_4\""),
+            ("VamanaIndexParams.search_list_size", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("IvfIndexParams.pq_subvector_dim", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("IvfIndexParams.nprobe", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("VectorIndexParams.index", ""),
             ("VectorParamsMap.map", ""),
             ("VectorParamsDiff.hnsw_config", ""),
             ("VectorParamsDiff.quantization_config", ""),
@@ -135,6 +145,7 @@ fn configure_validation(builder: Builder) -> Builder {
             "ProductQuantization",
             "BinaryQuantization",
             "Disabled",
+            "HnswIndexParams",
             "QuantizationConfigDiff",
             "quantization_config_diff::Quantization",
             "Replica",
@@ -157,6 +168,9 @@ fn configure_validation(builder: Builder) -> Builder {
             ("GetPoints.collection_name", "length(min = 1, max = 255)"),
             ("SetPayloadPoints.collection_name", "length(min = 1, max = 255)"),
             ("DeletePayloadPoints.collection_name", "length(min = 1, max = 255)"),
+            ("RenamePayloadPoints.collection_name", "length(min = 1, max = 255)"),
+            ("RenamePayloadPoints.key", "length(min = 1)"),
+            ("RenamePayloadPoints.new_key", "length(min = 1)"),
             ("ClearPayloadPoints.collection_name", "length(min = 1, max = 255)"),
             ("UpdateBatchPoints.collection_name", "length(min = 1, max = 255)"),
             ("UpdateBatchPoints.operations", "length(min = 1)"),
@@ -164,12 +178,16 @@ fn configure_validation(builder: Builder) -> Builder {
             ("CreateFieldIndexCollection.field_name", "length(min = 1)"),
             ("DeleteFieldIndexCollection.collection_name", "length(min = 1, max = 255)"),
             ("DeleteFieldIndexCollection.field_name", "length(min = 1)"),
+            ("RebuildFieldIndexCollection.collection_name", "length(min = 1, max = 255)"),
+            ("RebuildFieldIndexCollection.field_name", "length(min = 1)"),
             // TODO(sparse) validate sparse vector for `SearchPoints`
             ("SearchPoints.collection_name", "length(min = 1, max = 255)"),
             ("SearchPoints.filter", ""),
             ("SearchPoints.limit", "range(min = 1)"),
             ("SearchPoints.params", ""),
             ("SearchPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("SearchPoints.tie_breaker", ""),
+            ("TieBreaker.key", "length(min = 1)"),
             ("SearchBatchPoints.collection_name", "length(min = 1, max = 255)"),
             ("SearchBatchPoints.search_points", ""),
             ("SearchBatchPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
@@ -186,6 +204,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("ScrollPoints.collection_name", "length(min = 1, max = 255)"),
             ("ScrollPoints.filter", ""),
             ("ScrollPoints.limit", "custom = \"crate::grpc::validate::validate_u32_range_min_1\""),
+            ("ScrollPoints.order_by", ""),
+            ("OrderBy.key", "length(min = 1)"),
             ("RecommendPoints.collection_name", "length(min = 1, max = 255)"),
             ("RecommendPoints.filter", ""),
             ("RecommendPoints.params", ""),
@@ -214,11 +234,18 @@ fn configure_validation(builder: Builder) -> Builder {
             ("DiscoverBatchPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("CountPoints.collection_name", "length(min = 1, max = 255)"),
             ("CountPoints.filter", ""),
+            ("FacetCounts.collection_name", "length(min = 1, max = 255)"),
+            ("FacetCounts.key", "length(min = 1)"),
+            ("FacetCounts.filter", ""),
+            ("FacetCounts.limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("FacetCounts.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("GeoPolygon.exterior", "custom = \"crate::grpc::validate::validate_geo_polygon_exterior\""),
             ("GeoPolygon.interiors", "custom = \"crate::grpc::validate::validate_geo_polygon_interiors\""),
             ("Filter.should", ""),
             ("Filter.must", ""),
             ("Filter.must_not", ""),
+            ("Filter.min_should", ""),
+            ("MinShould.conditions", ""),
             ("NestedCondition.filter", ""),
             ("Condition.condition_one_of", ""),
             ("Vectors.vectors_options", ""),
@@ -240,9 +267,11 @@ fn configure_validation(builder: Builder) -> Builder {
             ("DeleteVectorsInternal.delete_vectors", ""),
             ("SetPayloadPointsInternal.set_payload_points", ""),
             ("DeletePayloadPointsInternal.delete_payload_points", ""),
+            ("RenamePayloadPointsInternal.rename_payload_points", ""),
             ("ClearPayloadPointsInternal.clear_payload_points", ""),
             ("CreateFieldIndexCollectionInternal.create_field_index_collection", ""),
             ("DeleteFieldIndexCollectionInternal.delete_field_index_collection", ""),
+            ("RebuildFieldIndexCollectionInternal.rebuild_field_index_collection", ""),
             ("SearchPointsInternal.search_points", ""),
             ("SearchBatchPointsInternal.collection_name", "length(min = 1, max = 255)"),
             ("SearchBatchPointsInternal.search_points", ""),
@@ -256,6 +285,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("ScrollPointsInternal.scroll_points", ""),
             ("GetPointsInternal.get_points", ""),
             ("CountPointsInternal.count_points", ""),
+            ("FacetCountsInternal.facet_counts", ""),
             ("SyncPointsInternal.sync_points", ""),
             ("SyncPoints.collection_name", "length(min = 1, max = 255)"),
         ], &[])
@@ -282,6 +312,10 @@ fn configure_validation(builder: Builder) -> Builder {
             "ListFullSnapshotsRequest",
         ])
         .field_attribute("SnapshotDescription.creation_time", "#[serde(skip)]")
+        .field_attribute("DatetimeRange.lt", "#[serde(skip)]")
+        .field_attribute("DatetimeRange.gt", "#[serde(skip)]")
+        .field_attribute("DatetimeRange.gte", "#[serde(skip)]")
+        .field_attribute("DatetimeRange.lte", "#[serde(skip)]")
 }
 
 fn append_to_file(path: &str, line: &str) {
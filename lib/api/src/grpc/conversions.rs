@@ -1,7 +1,8 @@
 use std::collections::{HashMap, HashSet};
+use std::mem::size_of;
 use std::time::Instant;
 
-use chrono::{NaiveDateTime, Timelike};
+use chrono::{DateTime, NaiveDateTime, Timelike, Utc};
 use segment::data_types::text_index::TextIndexType;
 use segment::data_types::vectors::VectorElementType;
 use segment::types::default_quantization_ignore_value;
@@ -18,15 +19,19 @@ use crate::grpc::qdrant::value::Kind;
 use crate::grpc::qdrant::vectors::VectorsOptions;
 use crate::grpc::qdrant::with_payload_selector::SelectorOptions;
 use crate::grpc::qdrant::{
-    shard_key, with_vectors_selector, CollectionDescription, CollectionOperationResponse,
-    Condition, Distance, FieldCondition, Filter, GeoBoundingBox, GeoPoint, GeoPolygon, GeoRadius,
-    HasIdCondition, HealthCheckReply, HnswConfigDiff, IsEmptyCondition, IsNullCondition,
-    ListCollectionsResponse, ListValue, Match, NamedVectors, NestedCondition,
-    PayloadExcludeSelector, PayloadIncludeSelector, PayloadIndexParams, PayloadSchemaInfo,
-    PayloadSchemaType, PointId, ProductQuantization, QuantizationConfig, QuantizationSearchParams,
-    QuantizationType, Range, RepeatedIntegers, RepeatedStrings, ScalarQuantization, ScoredPoint,
-    SearchParams, ShardKey, Struct, TextIndexParams, TokenizerType, Value, ValuesCount, Vector,
-    Vectors, VectorsSelector, WithPayloadSelector, WithVectorsSelector,
+    facet_value, shard_key, start_from, with_vectors_selector, CollectionDescription,
+    CollectionOperationResponse, Condition, Datatype, DatetimeRange, Direction, Distance, FacetHit,
+    FacetValue, FieldCondition, Filter, FloatIndexParams, GeoBoundingBox, GeoPoint, GeoPolygon,
+    GeoRadius, HasIdCondition, HasIdRangeCondition, HasVectorCondition, HealthCheckReply,
+    HnswConfigDiff, IntegerIndexParams, IsEmptyCondition, IsNullCondition, KeywordIndexParams,
+    ListCollectionsResponse, ListValue, Match, MinShould, MultiVectorComparator, MultiVectorConfig,
+    NamedVectors, NestedCondition, OrderBy, PayloadExcludeSelector, PayloadIncludeSelector,
+    PayloadIndexParams, PayloadSchemaInfo, PayloadSchemaType, PointId, ProductQuantization,
+    QuantizationConfig, QuantizationSearchParams, QuantizationType, Range, RepeatedIntegers,
+    RepeatedStrings, ScalarQuantization, ScoredPoint, SearchParams, ShardKey, StartFrom,
+    StemmerLanguage, Struct, SynonymGroup, TextAnalyzerParams, TextIndexParams, TieBreaker,
+    TokenizerType, Value, ValuesCount, Vector, Vectors, VectorsSelector, WithPayloadSelector,
+    WithVectorsSelector,
 };
 
 pub fn payload_to_proto(payload: segment::types::Payload) -> HashMap<String, Value> {
@@ -181,37 +186,299 @@ impl From<segment::data_types::text_index::TokenizerType> for TokenizerType {
                 TokenizerType::Multilingual
             }
             segment::data_types::text_index::TokenizerType::Word => TokenizerType::Word,
+            segment::data_types::text_index::TokenizerType::Ngram => TokenizerType::Ngram,
+        }
+    }
+}
+
+impl From<segment::data_types::text_index::StemmerLanguage> for StemmerLanguage {
+    fn from(language: segment::data_types::text_index::StemmerLanguage) -> Self {
+        match language {
+            segment::data_types::text_index::StemmerLanguage::Arabic => StemmerLanguage::Arabic,
+            segment::data_types::text_index::StemmerLanguage::Danish => StemmerLanguage::Danish,
+            segment::data_types::text_index::StemmerLanguage::Dutch => StemmerLanguage::Dutch,
+            segment::data_types::text_index::StemmerLanguage::English => StemmerLanguage::English,
+            segment::data_types::text_index::StemmerLanguage::Finnish => StemmerLanguage::Finnish,
+            segment::data_types::text_index::StemmerLanguage::French => StemmerLanguage::French,
+            segment::data_types::text_index::StemmerLanguage::German => StemmerLanguage::German,
+            segment::data_types::text_index::StemmerLanguage::Greek => StemmerLanguage::Greek,
+            segment::data_types::text_index::StemmerLanguage::Hungarian => {
+                StemmerLanguage::Hungarian
+            }
+            segment::data_types::text_index::StemmerLanguage::Italian => StemmerLanguage::Italian,
+            segment::data_types::text_index::StemmerLanguage::Norwegian => {
+                StemmerLanguage::Norwegian
+            }
+            segment::data_types::text_index::StemmerLanguage::Portuguese => {
+                StemmerLanguage::Portuguese
+            }
+            segment::data_types::text_index::StemmerLanguage::Romanian => StemmerLanguage::Romanian,
+            segment::data_types::text_index::StemmerLanguage::Russian => StemmerLanguage::Russian,
+            segment::data_types::text_index::StemmerLanguage::Spanish => StemmerLanguage::Spanish,
+            segment::data_types::text_index::StemmerLanguage::Swedish => StemmerLanguage::Swedish,
+            segment::data_types::text_index::StemmerLanguage::Tamil => StemmerLanguage::Tamil,
+            segment::data_types::text_index::StemmerLanguage::Turkish => StemmerLanguage::Turkish,
+        }
+    }
+}
+
+impl TryFrom<StemmerLanguage> for segment::data_types::text_index::StemmerLanguage {
+    type Error = Status;
+    fn try_from(language: StemmerLanguage) -> Result<Self, Self::Error> {
+        match language {
+            StemmerLanguage::UnknownStemmerLanguage => {
+                Err(Status::invalid_argument("unknown stemmer language"))
+            }
+            StemmerLanguage::Arabic => Ok(segment::data_types::text_index::StemmerLanguage::Arabic),
+            StemmerLanguage::Danish => Ok(segment::data_types::text_index::StemmerLanguage::Danish),
+            StemmerLanguage::Dutch => Ok(segment::data_types::text_index::StemmerLanguage::Dutch),
+            StemmerLanguage::English => {
+                Ok(segment::data_types::text_index::StemmerLanguage::English)
+            }
+            StemmerLanguage::Finnish => {
+                Ok(segment::data_types::text_index::StemmerLanguage::Finnish)
+            }
+            StemmerLanguage::French => Ok(segment::data_types::text_index::StemmerLanguage::French),
+            StemmerLanguage::German => Ok(segment::data_types::text_index::StemmerLanguage::German),
+            StemmerLanguage::Greek => Ok(segment::data_types::text_index::StemmerLanguage::Greek),
+            StemmerLanguage::Hungarian => {
+                Ok(segment::data_types::text_index::StemmerLanguage::Hungarian)
+            }
+            StemmerLanguage::Italian => {
+                Ok(segment::data_types::text_index::StemmerLanguage::Italian)
+            }
+            StemmerLanguage::Norwegian => {
+                Ok(segment::data_types::text_index::StemmerLanguage::Norwegian)
+            }
+            StemmerLanguage::Portuguese => {
+                Ok(segment::data_types::text_index::StemmerLanguage::Portuguese)
+            }
+            StemmerLanguage::Romanian => {
+                Ok(segment::data_types::text_index::StemmerLanguage::Romanian)
+            }
+            StemmerLanguage::Russian => {
+                Ok(segment::data_types::text_index::StemmerLanguage::Russian)
+            }
+            StemmerLanguage::Spanish => {
+                Ok(segment::data_types::text_index::StemmerLanguage::Spanish)
+            }
+            StemmerLanguage::Swedish => {
+                Ok(segment::data_types::text_index::StemmerLanguage::Swedish)
+            }
+            StemmerLanguage::Tamil => Ok(segment::data_types::text_index::StemmerLanguage::Tamil),
+            StemmerLanguage::Turkish => {
+                Ok(segment::data_types::text_index::StemmerLanguage::Turkish)
+            }
+        }
+    }
+}
+
+impl From<segment::data_types::index::KeywordIndexParams> for PayloadIndexParams {
+    fn from(params: segment::data_types::index::KeywordIndexParams) -> Self {
+        PayloadIndexParams {
+            index_params: Some(IndexParams::KeywordIndexParams(KeywordIndexParams {
+                on_disk: params.on_disk,
+            })),
+        }
+    }
+}
+
+impl From<segment::data_types::index::IntegerIndexParams> for PayloadIndexParams {
+    fn from(params: segment::data_types::index::IntegerIndexParams) -> Self {
+        PayloadIndexParams {
+            index_params: Some(IndexParams::IntegerIndexParams(IntegerIndexParams {
+                on_disk: params.on_disk,
+            })),
+        }
+    }
+}
+
+impl From<segment::data_types::index::FloatIndexParams> for PayloadIndexParams {
+    fn from(params: segment::data_types::index::FloatIndexParams) -> Self {
+        PayloadIndexParams {
+            index_params: Some(IndexParams::FloatIndexParams(FloatIndexParams {
+                on_disk: params.on_disk,
+            })),
         }
     }
 }
 
 impl From<segment::data_types::text_index::TextIndexParams> for PayloadIndexParams {
     fn from(params: segment::data_types::text_index::TextIndexParams) -> Self {
-        let tokenizer = TokenizerType::from(params.tokenizer);
         PayloadIndexParams {
-            index_params: Some(IndexParams::TextIndexParams(TextIndexParams {
-                tokenizer: tokenizer as i32,
-                lowercase: params.lowercase,
-                min_token_len: params.min_token_len.map(|x| x as u64),
-                max_token_len: params.max_token_len.map(|x| x as u64),
-            })),
+            index_params: Some(IndexParams::TextIndexParams(params.into())),
+        }
+    }
+}
+
+impl From<segment::data_types::text_index::TextIndexParams> for TextIndexParams {
+    fn from(params: segment::data_types::text_index::TextIndexParams) -> Self {
+        let tokenizer = TokenizerType::from(params.tokenizer);
+        let analyzers = params
+            .analyzers
+            .iter()
+            .flatten()
+            .map(|(name, analyzer)| {
+                let analyzer_params = TextIndexParams::from(params.with_analyzer(analyzer));
+                (name.clone(), TextAnalyzerParams::from(analyzer_params))
+            })
+            .collect();
+        TextIndexParams {
+            tokenizer: tokenizer as i32,
+            lowercase: params.lowercase,
+            min_token_len: params.min_token_len.map(|x| x as u64),
+            max_token_len: params.max_token_len.map(|x| x as u64),
+            stemmer: params
+                .stemmer
+                .map(|language| StemmerLanguage::from(language) as i32),
+            stopwords: params
+                .stopwords
+                .map(|stopwords| stopwords.into_iter().collect())
+                .unwrap_or_default(),
+            synonyms: params
+                .synonyms
+                .map(|synonyms| {
+                    synonyms
+                        .into_iter()
+                        .map(|group| SynonymGroup {
+                            words: group.into_iter().collect(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            analyzers,
+            on_disk: params.on_disk,
+        }
+    }
+}
+
+impl From<TextIndexParams> for TextAnalyzerParams {
+    fn from(params: TextIndexParams) -> Self {
+        let TextIndexParams {
+            tokenizer,
+            lowercase,
+            min_token_len,
+            max_token_len,
+            stemmer,
+            stopwords,
+            synonyms,
+            analyzers: _,
+            on_disk: _,
+        } = params;
+        TextAnalyzerParams {
+            tokenizer,
+            lowercase,
+            min_token_len,
+            max_token_len,
+            stemmer,
+            stopwords,
+            synonyms,
+        }
+    }
+}
+
+impl From<segment::data_types::facets::FacetValue> for FacetValue {
+    fn from(value: segment::data_types::facets::FacetValue) -> Self {
+        let variant = match value {
+            segment::data_types::facets::FacetValue::Keyword(value) => {
+                facet_value::Variant::StringValue(value)
+            }
+            segment::data_types::facets::FacetValue::Int(value) => {
+                facet_value::Variant::IntegerValue(value)
+            }
+        };
+        FacetValue {
+            variant: Some(variant),
+        }
+    }
+}
+
+impl TryFrom<FacetValue> for segment::data_types::facets::FacetValue {
+    type Error = Status;
+
+    fn try_from(value: FacetValue) -> Result<Self, Self::Error> {
+        match value.variant {
+            Some(facet_value::Variant::StringValue(value)) => {
+                Ok(segment::data_types::facets::FacetValue::Keyword(value))
+            }
+            Some(facet_value::Variant::IntegerValue(value)) => {
+                Ok(segment::data_types::facets::FacetValue::Int(value))
+            }
+            None => Err(Status::invalid_argument("Malformed facet value")),
+        }
+    }
+}
+
+impl From<segment::data_types::facets::FacetValueHit> for FacetHit {
+    fn from(hit: segment::data_types::facets::FacetValueHit) -> Self {
+        FacetHit {
+            value: Some(hit.value.into()),
+            count: hit.count as u64,
         }
     }
 }
 
+impl TryFrom<FacetHit> for segment::data_types::facets::FacetValueHit {
+    type Error = Status;
+
+    fn try_from(hit: FacetHit) -> Result<Self, Self::Error> {
+        let value = hit
+            .value
+            .ok_or_else(|| Status::invalid_argument("Facet hit value is missing"))?;
+        Ok(segment::data_types::facets::FacetValueHit {
+            value: value.try_into()?,
+            count: hit.count as usize,
+        })
+    }
+}
+
+impl From<segment::types::PayloadSchemaType> for PayloadSchemaType {
+    fn from(schema_type: segment::types::PayloadSchemaType) -> Self {
+        match schema_type {
+            segment::types::PayloadSchemaType::Keyword => PayloadSchemaType::Keyword,
+            segment::types::PayloadSchemaType::Integer => PayloadSchemaType::Integer,
+            segment::types::PayloadSchemaType::Float => PayloadSchemaType::Float,
+            segment::types::PayloadSchemaType::Geo => PayloadSchemaType::Geo,
+            segment::types::PayloadSchemaType::Text => PayloadSchemaType::Text,
+            segment::types::PayloadSchemaType::Bool => PayloadSchemaType::Bool,
+            segment::types::PayloadSchemaType::Datetime => PayloadSchemaType::Datetime,
+            segment::types::PayloadSchemaType::Uuid => PayloadSchemaType::Uuid,
+        }
+    }
+}
+
+pub fn payload_schema_type_from_proto(
+    schema_type: i32,
+) -> Result<segment::types::PayloadSchemaType, Status> {
+    match PayloadSchemaType::from_i32(schema_type) {
+        Some(PayloadSchemaType::Keyword) => Ok(segment::types::PayloadSchemaType::Keyword),
+        Some(PayloadSchemaType::Integer) => Ok(segment::types::PayloadSchemaType::Integer),
+        Some(PayloadSchemaType::Float) => Ok(segment::types::PayloadSchemaType::Float),
+        Some(PayloadSchemaType::Geo) => Ok(segment::types::PayloadSchemaType::Geo),
+        Some(PayloadSchemaType::Text) => Ok(segment::types::PayloadSchemaType::Text),
+        Some(PayloadSchemaType::Bool) => Ok(segment::types::PayloadSchemaType::Bool),
+        Some(PayloadSchemaType::Datetime) => Ok(segment::types::PayloadSchemaType::Datetime),
+        Some(PayloadSchemaType::Uuid) => Ok(segment::types::PayloadSchemaType::Uuid),
+        Some(PayloadSchemaType::UnknownType) | None => Err(Status::invalid_argument(
+            "Malformed payload schema".to_string(),
+        )),
+    }
+}
+
 impl From<segment::types::PayloadIndexInfo> for PayloadSchemaInfo {
     fn from(schema: segment::types::PayloadIndexInfo) -> Self {
         PayloadSchemaInfo {
-            data_type: match schema.data_type {
-                segment::types::PayloadSchemaType::Keyword => PayloadSchemaType::Keyword,
-                segment::types::PayloadSchemaType::Integer => PayloadSchemaType::Integer,
-                segment::types::PayloadSchemaType::Float => PayloadSchemaType::Float,
-                segment::types::PayloadSchemaType::Geo => PayloadSchemaType::Geo,
-                segment::types::PayloadSchemaType::Text => PayloadSchemaType::Text,
-                segment::types::PayloadSchemaType::Bool => PayloadSchemaType::Bool,
-            }
-            .into(),
+            data_type: PayloadSchemaType::from(schema.data_type).into(),
             params: schema.params.map(|params| match params {
+                segment::types::PayloadSchemaParams::Keyword(keyword_index_params) => {
+                    keyword_index_params.into()
+                }
+                segment::types::PayloadSchemaParams::Integer(integer_index_params) => {
+                    integer_index_params.into()
+                }
+                segment::types::PayloadSchemaParams::Float(float_index_params) => {
+                    float_index_params.into()
+                }
                 segment::types::PayloadSchemaParams::Text(text_index_params) => {
                     text_index_params.into()
                 }
@@ -234,6 +501,34 @@ impl TryFrom<TokenizerType> for segment::data_types::text_index::TokenizerType {
                 Ok(segment::data_types::text_index::TokenizerType::Whitespace)
             }
             TokenizerType::Word => Ok(segment::data_types::text_index::TokenizerType::Word),
+            TokenizerType::Ngram => Ok(segment::data_types::text_index::TokenizerType::Ngram),
+        }
+    }
+}
+
+impl From<KeywordIndexParams> for segment::data_types::index::KeywordIndexParams {
+    fn from(params: KeywordIndexParams) -> Self {
+        segment::data_types::index::KeywordIndexParams {
+            r#type: Default::default(),
+            on_disk: params.on_disk,
+        }
+    }
+}
+
+impl From<IntegerIndexParams> for segment::data_types::index::IntegerIndexParams {
+    fn from(params: IntegerIndexParams) -> Self {
+        segment::data_types::index::IntegerIndexParams {
+            r#type: Default::default(),
+            on_disk: params.on_disk,
+        }
+    }
+}
+
+impl From<FloatIndexParams> for segment::data_types::index::FloatIndexParams {
+    fn from(params: FloatIndexParams) -> Self {
+        segment::data_types::index::FloatIndexParams {
+            r#type: Default::default(),
+            on_disk: params.on_disk,
         }
     }
 }
@@ -249,6 +544,82 @@ impl TryFrom<TextIndexParams> for segment::data_types::text_index::TextIndexPara
             lowercase: params.lowercase,
             min_token_len: params.min_token_len.map(|x| x as usize),
             max_token_len: params.max_token_len.map(|x| x as usize),
+            stemmer: params
+                .stemmer
+                .map(|language| {
+                    StemmerLanguage::from_i32(language)
+                        .map(|x| x.try_into())
+                        .unwrap_or_else(|| {
+                            Err(Status::invalid_argument("unknown stemmer language"))
+                        })
+                })
+                .transpose()?,
+            stopwords: (!params.stopwords.is_empty())
+                .then(|| params.stopwords.into_iter().collect()),
+            synonyms: (!params.synonyms.is_empty()).then(|| {
+                params
+                    .synonyms
+                    .into_iter()
+                    .map(|group| group.words.into_iter().collect())
+                    .collect()
+            }),
+            analyzers: (!params.analyzers.is_empty())
+                .then(|| {
+                    params
+                        .analyzers
+                        .into_iter()
+                        .map(|(name, analyzer)| Ok((name, analyzer.try_into()?)))
+                        .collect::<Result<_, Status>>()
+                })
+                .transpose()?,
+            on_disk: params.on_disk,
+        })
+    }
+}
+
+impl TryFrom<TextAnalyzerParams> for segment::data_types::text_index::TextAnalyzerParams {
+    type Error = Status;
+    fn try_from(params: TextAnalyzerParams) -> Result<Self, Self::Error> {
+        let TextAnalyzerParams {
+            tokenizer,
+            lowercase,
+            min_token_len,
+            max_token_len,
+            stemmer,
+            stopwords,
+            synonyms,
+        } = params;
+        let segment::data_types::text_index::TextIndexParams {
+            r#type: _,
+            tokenizer,
+            min_token_len,
+            max_token_len,
+            lowercase,
+            stemmer,
+            stopwords,
+            synonyms,
+            analyzers: _,
+            on_disk: _,
+        } = TextIndexParams {
+            tokenizer,
+            lowercase,
+            min_token_len,
+            max_token_len,
+            stemmer,
+            stopwords,
+            synonyms,
+            analyzers: Default::default(),
+            on_disk: None,
+        }
+        .try_into()?;
+        Ok(segment::data_types::text_index::TextAnalyzerParams {
+            tokenizer,
+            min_token_len,
+            max_token_len,
+            lowercase,
+            stemmer,
+            stopwords,
+            synonyms,
         })
     }
 }
@@ -261,6 +632,9 @@ impl TryFrom<PayloadIndexParams> for segment::data_types::text_index::TextIndexP
             Some(IndexParams::TextIndexParams(text_index_params)) => {
                 Ok(text_index_params.try_into()?)
             }
+            Some(_) => Err(Status::invalid_argument(
+                "Expected text index params".to_string(),
+            )),
         }
     }
 }
@@ -270,6 +644,15 @@ impl TryFrom<IndexParams> for segment::types::PayloadSchemaParams {
 
     fn try_from(value: IndexParams) -> Result<Self, Self::Error> {
         match value {
+            IndexParams::KeywordIndexParams(keyword_index_params) => Ok(
+                segment::types::PayloadSchemaParams::Keyword(keyword_index_params.into()),
+            ),
+            IndexParams::IntegerIndexParams(integer_index_params) => Ok(
+                segment::types::PayloadSchemaParams::Integer(integer_index_params.into()),
+            ),
+            IndexParams::FloatIndexParams(float_index_params) => Ok(
+                segment::types::PayloadSchemaParams::Float(float_index_params.into()),
+            ),
             IndexParams::TextIndexParams(text_index_params) => Ok(
                 segment::types::PayloadSchemaParams::Text(text_index_params.try_into()?),
             ),
@@ -281,26 +664,7 @@ impl TryFrom<PayloadSchemaInfo> for segment::types::PayloadIndexInfo {
     type Error = Status;
 
     fn try_from(schema: PayloadSchemaInfo) -> Result<Self, Self::Error> {
-        let data_type = match PayloadSchemaType::from_i32(schema.data_type) {
-            None => {
-                return Err(Status::invalid_argument(
-                    "Malformed payload schema".to_string(),
-                ))
-            }
-            Some(data_type) => match data_type {
-                PayloadSchemaType::Keyword => segment::types::PayloadSchemaType::Keyword,
-                PayloadSchemaType::Integer => segment::types::PayloadSchemaType::Integer,
-                PayloadSchemaType::Float => segment::types::PayloadSchemaType::Float,
-                PayloadSchemaType::Geo => segment::types::PayloadSchemaType::Geo,
-                PayloadSchemaType::Text => segment::types::PayloadSchemaType::Text,
-                PayloadSchemaType::Bool => segment::types::PayloadSchemaType::Bool,
-                PayloadSchemaType::UnknownType => {
-                    return Err(Status::invalid_argument(
-                        "Malformed payload schema".to_string(),
-                    ))
-                }
-            },
-        };
+        let data_type = payload_schema_type_from_proto(schema.data_type)?;
         let params = match schema.params {
             None => None,
             Some(PayloadIndexParams { index_params: None }) => None,
@@ -401,9 +765,12 @@ impl From<SearchParams> for segment::types::SearchParams {
     fn from(params: SearchParams) -> Self {
         Self {
             hnsw_ef: params.hnsw_ef.map(|x| x as usize),
+            ivf_nprobe: params.ivf_nprobe.map(|x| x as usize),
             exact: params.exact.unwrap_or(false),
             quantization: params.quantization.map(|q| q.into()),
             indexed_only: params.indexed_only.unwrap_or(false),
+            acorn: params.acorn.unwrap_or(false),
+            score_threshold: None,
         }
     }
 }
@@ -412,9 +779,11 @@ impl From<segment::types::SearchParams> for SearchParams {
     fn from(params: segment::types::SearchParams) -> Self {
         Self {
             hnsw_ef: params.hnsw_ef.map(|x| x as u64),
+            ivf_nprobe: params.ivf_nprobe.map(|x| x as u64),
             exact: Some(params.exact),
             quantization: params.quantization.map(|q| q.into()),
             indexed_only: Some(params.indexed_only),
+            acorn: Some(params.acorn),
         }
     }
 }
@@ -436,31 +805,71 @@ impl From<segment::data_types::vectors::Vector> for Vector {
             segment::data_types::vectors::Vector::Dense(vector) => Self {
                 data: vector,
                 indices: None,
+                vectors_count: None,
+                packed_data: None,
             },
             segment::data_types::vectors::Vector::Sparse(vector) => Self {
                 data: vector.values,
                 indices: Some(SparseIndices {
                     data: vector.indices,
                 }),
+                vectors_count: None,
+                packed_data: None,
             },
+            segment::data_types::vectors::Vector::MultiDense(vector) => {
+                let vectors_count = vector.vectors_count() as u32;
+                Self {
+                    data: vector.flattened_vectors,
+                    indices: None,
+                    vectors_count: Some(vectors_count),
+                    packed_data: None,
+                }
+            }
         }
     }
 }
 
 impl From<Vector> for segment::data_types::vectors::Vector {
     fn from(vector: Vector) -> Self {
-        match vector.indices {
-            None => segment::data_types::vectors::Vector::Dense(vector.data),
-            Some(indices) => segment::data_types::vectors::Vector::Sparse(
+        let Vector {
+            data,
+            indices,
+            vectors_count,
+            packed_data,
+        } = vector;
+        let data = match packed_data {
+            Some(packed_data) => unpack_vector_data(&packed_data),
+            None => data,
+        };
+        match (indices, vectors_count) {
+            (Some(indices), _) => segment::data_types::vectors::Vector::Sparse(
                 sparse::common::sparse_vector::SparseVector {
-                    values: vector.data,
+                    values: data,
                     indices: indices.data,
                 },
             ),
+            (None, Some(vectors_count)) => {
+                // Shape is checked by validation, see `Validate for Vector`
+                let dim = data.len() / (vectors_count as usize).max(1);
+                segment::data_types::vectors::Vector::MultiDense(
+                    segment::data_types::vectors::MultiDenseVector::new(data, dim),
+                )
+            }
+            (None, None) => segment::data_types::vectors::Vector::Dense(data),
         }
     }
 }
 
+/// Decode vector data packed as little-endian 32-bit floats
+///
+/// Length is checked by validation, see `Validate for Vector`
+fn unpack_vector_data(packed_data: &[u8]) -> Vec<VectorElementType> {
+    packed_data
+        .chunks_exact(size_of::<f32>())
+        .map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap()))
+        .collect()
+}
+
 impl From<HashMap<String, segment::data_types::vectors::Vector>> for NamedVectors {
     fn from(vectors: HashMap<String, segment::data_types::vectors::Vector>) -> Self {
         Self {
@@ -631,6 +1040,37 @@ impl TryFrom<ScalarQuantization> for segment::types::ScalarQuantization {
     }
 }
 
+impl From<segment::types::MultiVectorConfig> for MultiVectorConfig {
+    fn from(value: segment::types::MultiVectorConfig) -> Self {
+        MultiVectorConfig {
+            comparator: match value.comparator {
+                segment::types::MultiVectorComparator::MaxSim => {
+                    MultiVectorComparator::MaxSim as i32
+                }
+            },
+        }
+    }
+}
+
+impl TryFrom<MultiVectorConfig> for segment::types::MultiVectorConfig {
+    type Error = Status;
+
+    fn try_from(value: MultiVectorConfig) -> Result<Self, Self::Error> {
+        Ok(segment::types::MultiVectorConfig {
+            comparator: match MultiVectorComparator::from_i32(value.comparator) {
+                Some(MultiVectorComparator::MaxSim) => {
+                    segment::types::MultiVectorComparator::MaxSim
+                }
+                None => {
+                    return Err(Status::invalid_argument(
+                        "Unknown multivector comparator".to_string(),
+                    ))
+                }
+            },
+        })
+    }
+}
+
 impl From<segment::types::ProductQuantization> for ProductQuantization {
     fn from(value: segment::types::ProductQuantization) -> Self {
         let config = value.product;
@@ -770,6 +1210,10 @@ impl TryFrom<Filter> for segment::types::Filter {
             should: conditions_helper_from_grpc(value.should)?,
             must: conditions_helper_from_grpc(value.must)?,
             must_not: conditions_helper_from_grpc(value.must_not)?,
+            min_should: value
+                .min_should
+                .map(segment::types::MinShould::try_from)
+                .transpose()?,
         })
     }
 }
@@ -780,6 +1224,31 @@ impl From<segment::types::Filter> for Filter {
             should: conditions_helper_to_grpc(value.should),
             must: conditions_helper_to_grpc(value.must),
             must_not: conditions_helper_to_grpc(value.must_not),
+            min_should: value.min_should.map(MinShould::from),
+        }
+    }
+}
+
+impl TryFrom<MinShould> for segment::types::MinShould {
+    type Error = Status;
+
+    fn try_from(value: MinShould) -> Result<Self, Self::Error> {
+        Ok(Self {
+            conditions: value
+                .conditions
+                .into_iter()
+                .map(segment::types::Condition::try_from)
+                .collect::<Result<_, _>>()?,
+            min_count: value.min_count as usize,
+        })
+    }
+}
+
+impl From<segment::types::MinShould> for MinShould {
+    fn from(value: segment::types::MinShould) -> Self {
+        Self {
+            conditions: value.conditions.into_iter().map(Condition::from).collect(),
+            min_count: value.min_count as u64,
         }
     }
 }
@@ -808,6 +1277,12 @@ impl TryFrom<Condition> for segment::types::Condition {
                 ConditionOneOf::Nested(nested) => Ok(segment::types::Condition::Nested(
                     segment::types::NestedCondition::new(nested.try_into()?),
                 )),
+                ConditionOneOf::HasVector(has_vector) => {
+                    Ok(segment::types::Condition::HasVector(has_vector.into()))
+                }
+                ConditionOneOf::HasIdRange(has_id_range) => Ok(
+                    segment::types::Condition::HasIdRange(has_id_range.try_into()?),
+                ),
             };
         }
         Err(Status::invalid_argument("Malformed Condition type"))
@@ -823,6 +1298,12 @@ impl From<segment::types::Condition> for Condition {
             }
             segment::types::Condition::IsNull(is_null) => ConditionOneOf::IsNull(is_null.into()),
             segment::types::Condition::HasId(has_id) => ConditionOneOf::HasId(has_id.into()),
+            segment::types::Condition::HasVector(has_vector) => {
+                ConditionOneOf::HasVector(has_vector.into())
+            }
+            segment::types::Condition::HasIdRange(has_id_range) => {
+                ConditionOneOf::HasIdRange(has_id_range.into())
+            }
             segment::types::Condition::Filter(filter) => ConditionOneOf::Filter(filter.into()),
             segment::types::Condition::Nested(nested) => {
                 ConditionOneOf::Nested(nested.nested.into())
@@ -846,6 +1327,7 @@ impl TryFrom<NestedCondition> for segment::types::Nested {
             Some(filter) => Ok(Self {
                 key: value.key,
                 filter: filter.try_into()?,
+                count: value.count.map(Into::into),
             }),
         }
     }
@@ -856,6 +1338,7 @@ impl From<segment::types::Nested> for NestedCondition {
         Self {
             key: value.key,
             filter: Some(value.filter.into()),
+            count: value.count.map(Into::into),
         }
     }
 }
@@ -912,6 +1395,46 @@ impl From<segment::types::HasIdCondition> for HasIdCondition {
     }
 }
 
+impl From<HasVectorCondition> for segment::types::HasVectorCondition {
+    fn from(value: HasVectorCondition) -> Self {
+        Self {
+            has_vector: value.has_vector,
+        }
+    }
+}
+
+impl From<segment::types::HasVectorCondition> for HasVectorCondition {
+    fn from(value: segment::types::HasVectorCondition) -> Self {
+        Self {
+            has_vector: value.has_vector,
+        }
+    }
+}
+
+impl TryFrom<HasIdRangeCondition> for segment::types::HasIdRangeCondition {
+    type Error = Status;
+
+    fn try_from(value: HasIdRangeCondition) -> Result<Self, Self::Error> {
+        let HasIdRangeCondition { gte, lt } = value;
+        Ok(Self {
+            has_id_range: segment::types::IdRange {
+                gte: gte.map(|id| id.try_into()).transpose()?,
+                lt: lt.map(|id| id.try_into()).transpose()?,
+            },
+        })
+    }
+}
+
+impl From<segment::types::HasIdRangeCondition> for HasIdRangeCondition {
+    fn from(value: segment::types::HasIdRangeCondition) -> Self {
+        let segment::types::IdRange { gte, lt } = value.has_id_range;
+        Self {
+            gte: gte.map(|id| id.into()),
+            lt: lt.map(|id| id.into()),
+        }
+    }
+}
+
 impl TryFrom<FieldCondition> for segment::types::FieldCondition {
     type Error = Status;
 
@@ -924,8 +1447,21 @@ impl TryFrom<FieldCondition> for segment::types::FieldCondition {
             geo_radius,
             values_count,
             geo_polygon,
+            datetime_range,
         } = value;
 
+        let range = match (range, datetime_range) {
+            (Some(_), Some(_)) => {
+                return Err(Status::invalid_argument(
+                    "Only one of range and datetime_range can be specified",
+                ))
+            }
+            (Some(range), None) => Some(segment::types::RangeInterface::Float(range.into())),
+            (None, Some(datetime_range)) => Some(segment::types::RangeInterface::DateTime(
+                datetime_range.try_into()?,
+            )),
+            (None, None) => None,
+        };
         let geo_bounding_box =
             geo_bounding_box.map_or_else(|| Ok(None), |g| g.try_into().map(Some))?;
         let geo_radius = geo_radius.map_or_else(|| Ok(None), |g| g.try_into().map(Some))?;
@@ -933,7 +1469,7 @@ impl TryFrom<FieldCondition> for segment::types::FieldCondition {
         Ok(Self {
             key,
             r#match: r#match.map_or_else(|| Ok(None), |m| m.try_into().map(Some))?,
-            range: range.map(Into::into),
+            range,
             geo_bounding_box,
             geo_radius,
             geo_polygon,
@@ -954,17 +1490,23 @@ impl From<segment::types::FieldCondition> for FieldCondition {
             values_count,
         } = value;
 
+        let (range, datetime_range) = match range {
+            Some(segment::types::RangeInterface::Float(range)) => (Some(range.into()), None),
+            Some(segment::types::RangeInterface::DateTime(range)) => (None, Some(range.into())),
+            None => (None, None),
+        };
         let geo_bounding_box = geo_bounding_box.map(Into::into);
         let geo_radius = geo_radius.map(Into::into);
         let geo_polygon = geo_polygon.map(Into::into);
         Self {
             key,
             r#match: r#match.map(Into::into),
-            range: range.map(Into::into),
+            range,
             geo_bounding_box,
             geo_radius,
             geo_polygon,
             values_count: values_count.map(Into::into),
+            datetime_range,
         }
     }
 }
@@ -1099,6 +1641,34 @@ impl From<segment::types::Range> for Range {
     }
 }
 
+impl TryFrom<DatetimeRange> for segment::types::DatetimeRange {
+    type Error = Status;
+
+    fn try_from(value: DatetimeRange) -> Result<Self, Self::Error> {
+        let from_proto = |timestamp: Option<prost_types::Timestamp>| {
+            timestamp.map(date_time_from_proto).transpose()
+        };
+        Ok(Self {
+            lt: from_proto(value.lt)?,
+            gt: from_proto(value.gt)?,
+            gte: from_proto(value.gte)?,
+            lte: from_proto(value.lte)?,
+        })
+    }
+}
+
+impl From<segment::types::DatetimeRange> for DatetimeRange {
+    fn from(value: segment::types::DatetimeRange) -> Self {
+        let to_proto = |datetime: DateTime<Utc>| date_time_to_proto(datetime.naive_utc());
+        Self {
+            lt: value.lt.map(to_proto),
+            gt: value.gt.map(to_proto),
+            gte: value.gte.map(to_proto),
+            lte: value.lte.map(to_proto),
+        }
+    }
+}
+
 impl From<ValuesCount> for segment::types::ValuesCount {
     fn from(value: ValuesCount) -> Self {
         Self {
@@ -1121,16 +1691,157 @@ impl From<segment::types::ValuesCount> for ValuesCount {
     }
 }
 
+impl TryFrom<OrderBy> for segment::data_types::order_by::OrderBy {
+    type Error = Status;
+
+    fn try_from(value: OrderBy) -> Result<Self, Self::Error> {
+        let OrderBy {
+            key,
+            direction,
+            start_from,
+            distance_from,
+        } = value;
+        let direction = direction
+            .map(|direction| {
+                Direction::from_i32(direction)
+                    .map(segment::data_types::order_by::Direction::from)
+                    .ok_or_else(|| {
+                        Status::invalid_argument(format!("Unknown direction: {direction}"))
+                    })
+            })
+            .transpose()?;
+        let start_from = start_from
+            .and_then(|start_from| start_from.value)
+            .map(segment::data_types::order_by::StartFrom::try_from)
+            .transpose()?;
+        Ok(Self {
+            key,
+            direction,
+            start_from,
+            distance_from: distance_from.map(Into::into),
+        })
+    }
+}
+
+impl From<segment::data_types::order_by::OrderBy> for OrderBy {
+    fn from(value: segment::data_types::order_by::OrderBy) -> Self {
+        let segment::data_types::order_by::OrderBy {
+            key,
+            direction,
+            start_from,
+            distance_from,
+        } = value;
+        Self {
+            key,
+            direction: direction.map(|direction| Direction::from(direction) as i32),
+            start_from: start_from.map(|start_from| StartFrom {
+                value: Some(start_from.into()),
+            }),
+            distance_from: distance_from.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<TieBreaker> for segment::data_types::order_by::TieBreaker {
+    type Error = Status;
+
+    fn try_from(value: TieBreaker) -> Result<Self, Self::Error> {
+        let TieBreaker {
+            key,
+            direction,
+            distance_from,
+        } = value;
+        let direction = direction
+            .map(|direction| {
+                Direction::from_i32(direction)
+                    .map(segment::data_types::order_by::Direction::from)
+                    .ok_or_else(|| {
+                        Status::invalid_argument(format!("Unknown direction: {direction}"))
+                    })
+            })
+            .transpose()?;
+        Ok(Self {
+            key,
+            direction,
+            distance_from: distance_from.map(Into::into),
+        })
+    }
+}
+
+impl From<segment::data_types::order_by::TieBreaker> for TieBreaker {
+    fn from(value: segment::data_types::order_by::TieBreaker) -> Self {
+        let segment::data_types::order_by::TieBreaker {
+            key,
+            direction,
+            distance_from,
+        } = value;
+        Self {
+            key,
+            direction: direction.map(|direction| Direction::from(direction) as i32),
+            distance_from: distance_from.map(Into::into),
+        }
+    }
+}
+
+impl From<Direction> for segment::data_types::order_by::Direction {
+    fn from(value: Direction) -> Self {
+        match value {
+            Direction::Asc => Self::Asc,
+            Direction::Desc => Self::Desc,
+        }
+    }
+}
+
+impl From<segment::data_types::order_by::Direction> for Direction {
+    fn from(value: segment::data_types::order_by::Direction) -> Self {
+        match value {
+            segment::data_types::order_by::Direction::Asc => Self::Asc,
+            segment::data_types::order_by::Direction::Desc => Self::Desc,
+        }
+    }
+}
+
+impl TryFrom<start_from::Value> for segment::data_types::order_by::StartFrom {
+    type Error = Status;
+
+    fn try_from(value: start_from::Value) -> Result<Self, Self::Error> {
+        match value {
+            start_from::Value::Float(float) => Ok(Self::Float(float)),
+            start_from::Value::Integer(integer) => Ok(Self::Float(integer as f64)),
+            start_from::Value::Datetime(datetime) => segment::types::parse_datetime(&datetime)
+                .map(Self::Datetime)
+                .ok_or_else(|| Status::invalid_argument(format!("Malformed datetime: {datetime}"))),
+        }
+    }
+}
+
+impl From<segment::data_types::order_by::StartFrom> for start_from::Value {
+    fn from(value: segment::data_types::order_by::StartFrom) -> Self {
+        match value {
+            segment::data_types::order_by::StartFrom::Float(float) => Self::Float(float),
+            segment::data_types::order_by::StartFrom::Datetime(datetime) => {
+                Self::Datetime(datetime.to_rfc3339())
+            }
+        }
+    }
+}
+
 impl TryFrom<Match> for segment::types::Match {
     type Error = Status;
 
     fn try_from(value: Match) -> Result<Self, Self::Error> {
-        match value.match_value {
+        let Match {
+            analyzer,
+            match_value,
+        } = value;
+        match match_value {
             Some(mv) => Ok(match mv {
                 MatchValue::Keyword(kw) => kw.into(),
                 MatchValue::Integer(int) => int.into(),
                 MatchValue::Boolean(flag) => flag.into(),
-                MatchValue::Text(text) => segment::types::Match::Text(text.into()),
+                MatchValue::Text(text) => {
+                    segment::types::Match::Text(segment::types::MatchText { text, analyzer })
+                }
                 MatchValue::Keywords(kwds) => kwds.strings.into(),
                 MatchValue::Integers(ints) => ints.integers.into(),
                 MatchValue::ExceptIntegers(kwds) => {
@@ -1139,6 +1850,10 @@ impl TryFrom<Match> for segment::types::Match {
                 MatchValue::ExceptKeywords(ints) => {
                     segment::types::Match::Except(ints.strings.into())
                 }
+                MatchValue::Phrase(phrase) => {
+                    segment::types::Match::Phrase(segment::types::MatchPhrase { phrase, analyzer })
+                }
+                MatchValue::Prefix(prefix) => segment::types::Match::Prefix(prefix.into()),
             }),
             _ => Err(Status::invalid_argument("Malformed Match condition")),
         }
@@ -1147,14 +1862,23 @@ impl TryFrom<Match> for segment::types::Match {
 
 impl From<segment::types::Match> for Match {
     fn from(value: segment::types::Match) -> Self {
+        let mut analyzer = None;
         let match_value = match value {
             segment::types::Match::Value(value) => match value.value {
                 segment::types::ValueVariants::Keyword(kw) => MatchValue::Keyword(kw),
                 segment::types::ValueVariants::Integer(int) => MatchValue::Integer(int),
                 segment::types::ValueVariants::Bool(flag) => MatchValue::Boolean(flag),
             },
-            segment::types::Match::Text(segment::types::MatchText { text }) => {
-                MatchValue::Text(text)
+            segment::types::Match::Text(text) => {
+                analyzer = text.analyzer;
+                MatchValue::Text(text.text)
+            }
+            segment::types::Match::Phrase(phrase) => {
+                analyzer = phrase.analyzer;
+                MatchValue::Phrase(phrase.phrase)
+            }
+            segment::types::Match::Prefix(segment::types::MatchPrefix { prefix }) => {
+                MatchValue::Prefix(prefix)
             }
             segment::types::Match::Any(any) => match any.any {
                 segment::types::AnyVariants::Keywords(strings) => {
@@ -1174,6 +1898,7 @@ impl From<segment::types::Match> for Match {
             },
         };
         Self {
+            analyzer,
             match_value: Some(match_value),
         }
     }
@@ -1188,6 +1913,28 @@ impl From<HnswConfigDiff> for segment::types::HnswConfig {
             max_indexing_threads: hnsw_config.max_indexing_threads.unwrap_or_default() as usize,
             on_disk: hnsw_config.on_disk,
             payload_m: hnsw_config.payload_m.map(|x| x as usize),
+            payload_m_overrides: (!hnsw_config.payload_m_overrides.is_empty()).then(|| {
+                hnsw_config
+                    .payload_m_overrides
+                    .into_iter()
+                    .map(|(field, m)| (field, m as usize))
+                    .collect()
+            }),
+            seed: hnsw_config.seed,
+            extend_candidates: hnsw_config.extend_candidates,
+            keep_pruned_connections: hnsw_config.keep_pruned_connections,
+            compress_links: hnsw_config.compress_links,
+            target_recall: hnsw_config.target_recall,
+            level_factor_multiplier: hnsw_config.level_factor_multiplier,
+            ef_construct_levels: (!hnsw_config.ef_construct_levels.is_empty()).then(|| {
+                hnsw_config
+                    .ef_construct_levels
+                    .into_iter()
+                    .map(|ef| ef as usize)
+                    .collect()
+            }),
+            refine: hnsw_config.refine,
+            appendable: hnsw_config.appendable,
         }
     }
 }
@@ -1199,6 +1946,14 @@ pub fn date_time_to_proto(date_time: NaiveDateTime) -> prost_types::Timestamp {
     }
 }
 
+pub fn date_time_from_proto(timestamp: prost_types::Timestamp) -> Result<DateTime<Utc>, Status> {
+    u32::try_from(timestamp.nanos)
+        .ok()
+        .and_then(|nanos| NaiveDateTime::from_timestamp_opt(timestamp.seconds, nanos))
+        .map(|date_time| DateTime::from_naive_utc_and_offset(date_time, Utc))
+        .ok_or_else(|| Status::invalid_argument(format!("Malformed timestamp: {timestamp:?}")))
+}
+
 impl TryFrom<Distance> for segment::types::Distance {
     type Error = Status;
 
@@ -1213,6 +1968,7 @@ impl TryFrom<Distance> for segment::types::Distance {
             Distance::Euclid => segment::types::Distance::Euclid,
             Distance::Dot => segment::types::Distance::Dot,
             Distance::Manhattan => segment::types::Distance::Manhattan,
+            Distance::Hamming => segment::types::Distance::Hamming,
         })
     }
 }
@@ -1226,13 +1982,68 @@ pub fn from_grpc_dist(dist: i32) -> Result<segment::types::Distance, Status> {
     }
 }
 
+/// Convert gRPC datatype, where `Default` means that no datatype is set explicitly
+pub fn from_grpc_datatype(
+    datatype: i32,
+) -> Result<Option<segment::types::VectorStorageDatatype>, Status> {
+    match Datatype::from_i32(datatype) {
+        None => Err(Status::invalid_argument(format!(
+            "Malformed datatype parameter, unexpected value: {datatype}"
+        ))),
+        Some(Datatype::Default) => Ok(None),
+        Some(Datatype::Float32) => Ok(Some(segment::types::VectorStorageDatatype::Float32)),
+        Some(Datatype::Float16) => Ok(Some(segment::types::VectorStorageDatatype::Float16)),
+        Some(Datatype::Uint8) => Ok(Some(segment::types::VectorStorageDatatype::Uint8)),
+    }
+}
+
+impl From<segment::types::VectorStorageDatatype> for Datatype {
+    fn from(value: segment::types::VectorStorageDatatype) -> Self {
+        match value {
+            segment::types::VectorStorageDatatype::Float32 => Datatype::Float32,
+            segment::types::VectorStorageDatatype::Float16 => Datatype::Float16,
+            segment::types::VectorStorageDatatype::Uint8 => Datatype::Uint8,
+        }
+    }
+}
+
 pub fn into_named_vector_struct(
     vector_name: Option<String>,
     vector: Vec<VectorElementType>,
     indices: Option<SparseIndices>,
 ) -> Result<segment::data_types::vectors::NamedVectorStruct, Status> {
-    use segment::data_types::vectors::{NamedSparseVector, NamedVector, NamedVectorStruct};
+    into_named_multi_vector_struct(vector_name, vector, indices, None)
+}
+
+pub fn into_named_multi_vector_struct(
+    vector_name: Option<String>,
+    vector: Vec<VectorElementType>,
+    indices: Option<SparseIndices>,
+    vectors_count: Option<u32>,
+) -> Result<segment::data_types::vectors::NamedVectorStruct, Status> {
+    use segment::data_types::vectors::{
+        MultiDenseVector, NamedMultiDenseVector, NamedSparseVector, NamedVector, NamedVectorStruct,
+    };
     use sparse::common::sparse_vector::SparseVector;
+    if let Some(vectors_count) = vectors_count {
+        if indices.is_some() {
+            return Err(Status::invalid_argument(
+                "Sparse vector can not be a multivector",
+            ));
+        }
+        let vectors_count = vectors_count as usize;
+        if vectors_count == 0 || vector.is_empty() || vector.len() % vectors_count != 0 {
+            return Err(Status::invalid_argument(format!(
+                "Multivector data of length {} can not be split into {vectors_count} vectors",
+                vector.len(),
+            )));
+        }
+        let dim = vector.len() / vectors_count;
+        return Ok(NamedVectorStruct::MultiDense(NamedMultiDenseVector {
+            name: vector_name.unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_string()),
+            vector: MultiDenseVector::new(vector, dim),
+        }));
+    }
     Ok(match indices {
         Some(indices) => NamedVectorStruct::Sparse(NamedSparseVector {
             name: vector_name
@@ -21,6 +21,102 @@ pub struct VectorParams {
     /// If true - serve vectors from disk. If set to false, the vectors will be loaded in RAM.
     #[prost(bool, optional, tag = "5")]
     pub on_disk: ::core::option::Option<bool>,
+    /// Type of vector index. If omitted - HNSW index is used
+    #[prost(message, optional, tag = "6")]
+    #[validate]
+    pub index: ::core::option::Option<VectorIndexParams>,
+    /// If set - each point holds several vectors of this size, compared as a whole
+    #[prost(message, optional, tag = "7")]
+    pub multivector_config: ::core::option::Option<MultiVectorConfig>,
+    /// Element type, in which vectors are stored and scored. If omitted - Float32 is used
+    #[prost(enumeration = "Datatype", optional, tag = "8")]
+    pub datatype: ::core::option::Option<i32>,
+    /// If true - Cosine vectors are stored as is, scores are divided by cached vector norms
+    #[prost(bool, optional, tag = "9")]
+    pub keep_original: ::core::option::Option<bool>,
+    /// If not empty - per-dimension weights applied during scoring, only for Euclid and Dot distances
+    #[prost(float, repeated, tag = "10")]
+    pub dimension_weights: ::prost::alloc::vec::Vec<f32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MultiVectorConfig {
+    /// How to compare two multivectors
+    #[prost(enumeration = "MultiVectorComparator", tag = "1")]
+    pub comparator: i32,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HnswIndexParams {}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VamanaIndexParams {
+    /// Maximal number of edges per node in the index graph. Default: 64
+    #[prost(uint64, optional, tag = "1")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_4")]
+    pub max_degree: ::core::option::Option<u64>,
+    /// Size of the candidate list during the index building. Default: 100
+    #[prost(uint64, optional, tag = "2")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_4")]
+    pub build_list_size: ::core::option::Option<u64>,
+    /// Size of the candidate list during the search, if not set by search params. Default: 100
+    #[prost(uint64, optional, tag = "3")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub search_list_size: ::core::option::Option<u64>,
+    /// Minimal size (in KiloBytes) of vectors to search with the graph
+    #[prost(uint64, optional, tag = "4")]
+    pub full_scan_threshold: ::core::option::Option<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IvfIndexParams {
+    /// Number of inverted lists, vectors are clustered into. If 0 - square root of the number of vectors is used. Default: 0
+    #[prost(uint64, optional, tag = "1")]
+    pub num_lists: ::core::option::Option<u64>,
+    /// Number of vector dimensions, encoded into a single byte by product quantization. Default: 4
+    #[prost(uint64, optional, tag = "2")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub pq_subvector_dim: ::core::option::Option<u64>,
+    /// Number of nearest inverted lists to scan during the search, if not set by search params. Default: 16
+    #[prost(uint64, optional, tag = "3")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub nprobe: ::core::option::Option<u64>,
+    /// Minimal size (in KiloBytes) of vectors to search with the index
+    #[prost(uint64, optional, tag = "4")]
+    pub full_scan_threshold: ::core::option::Option<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VectorIndexParams {
+    #[prost(oneof = "vector_index_params::Index", tags = "1, 2, 3")]
+    #[validate]
+    pub index: ::core::option::Option<vector_index_params::Index>,
+}
+/// Nested message and enum types in `VectorIndexParams`.
+pub mod vector_index_params {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Index {
+        /// Filterable HNSW index, configured with `hnsw_config`
+        #[prost(message, tag = "1")]
+        Hnsw(super::HnswIndexParams),
+        /// Single-layer Vamana graph, served from disk
+        #[prost(message, tag = "2")]
+        Vamana(super::VamanaIndexParams),
+        /// Inverted lists of product-quantized vectors, for large rarely updated collections
+        #[prost(message, tag = "3")]
+        Ivf(super::IvfIndexParams),
+    }
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -205,6 +301,60 @@ pub struct HnswConfigDiff {
     /// Number of additional payload-aware links per node in the index graph. If not set - regular M parameter will be used.
     #[prost(uint64, optional, tag = "6")]
     pub payload_m: ::core::option::Option<u64>,
+    ///
+    /// Seed for the random level assignment of HNSW index building. If set, building the index for the same data produces the same graph.
+    /// Note: index building with a seed is single-threaded, to keep the insertion order deterministic.
+    #[prost(uint64, optional, tag = "7")]
+    pub seed: ::core::option::Option<u64>,
+    ///
+    /// Extend the candidate list with neighbours of the candidates before selecting links with the heuristic.
+    /// Improves recall on highly clustered data, at the cost of slower index building.
+    #[prost(bool, optional, tag = "8")]
+    pub extend_candidates: ::core::option::Option<bool>,
+    ///
+    /// Fill up the links of a node with candidates discarded by the heuristic, if it selected less than M of them.
+    #[prost(bool, optional, tag = "9")]
+    pub keep_pruned_connections: ::core::option::Option<bool>,
+    ///
+    /// Custom number of additional payload-aware links per node for specific payload fields. Overrides `payload_m` for the listed fields.
+    /// Value of 0 disables additional links for the field.
+    #[prost(map = "string, uint64", tag = "10")]
+    pub payload_m_overrides: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        u64,
+    >,
+    ///
+    /// Store HNSW links compressed and serve them from disk via mmap. Links of each node are sorted, delta-encoded and stored as varints.
+    /// Reduces memory and disk usage of the index, at the cost of slower search.
+    #[prost(bool, optional, tag = "11")]
+    pub compress_links: ::core::option::Option<bool>,
+    ///
+    /// Target recall of the search with default params. If set, `ef` used for searches without explicit `hnsw_ef`
+    /// is calibrated after the index is built, to reach this recall.
+    #[prost(double, optional, tag = "12")]
+    #[validate(custom = "crate::grpc::validate::validate_f64_range_1")]
+    pub target_recall: ::core::option::Option<f64>,
+    ///
+    /// Multiplier of the level generation factor `1/ln(m)`. Values below 1 flatten the hierarchy,
+    /// values above 1 make it deeper. Doesn't affect the number of links per node.
+    #[prost(double, optional, tag = "13")]
+    #[validate(custom = "crate::grpc::validate::validate_f64_range_min_0")]
+    pub level_factor_multiplier: ::core::option::Option<f64>,
+    ///
+    /// Number of neighbours to consider during the index building on each graph level, starting from level 0.
+    /// Levels above the end of the list use its last value. Overrides `ef_construct`. If empty - `ef_construct` is used on all levels.
+    #[prost(uint64, repeated, tag = "14")]
+    pub ef_construct_levels: ::prost::alloc::vec::Vec<u64>,
+    ///
+    /// Refine the graph after it is built: links of every point are selected once more from its neighbours and their neighbours.
+    /// Improves recall for the same `m`, at the cost of longer index building.
+    #[prost(bool, optional, tag = "15")]
+    pub refine: ::core::option::Option<bool>,
+    ///
+    /// Link new points into the built graph on upsert, instead of writing them into a separate unindexed segment.
+    /// Only applies to graphs and vectors kept in RAM, without quantization.
+    #[prost(bool, optional, tag = "16")]
+    pub appendable: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -219,6 +369,10 @@ pub struct SparseIndexConfig {
     /// Store inverted index on disk. If set to false, the index will be stored in RAM.
     #[prost(bool, optional, tag = "2")]
     pub on_disk: ::core::option::Option<bool>,
+    ///
+    /// Type, in which weights are stored in the index on disk. If omitted - Float32 is used.
+    #[prost(enumeration = "Datatype", optional, tag = "3")]
+    pub datatype: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -299,6 +453,16 @@ pub struct OptimizersConfigDiff {
     /// Max number of threads, which can be used for optimization. If 0 - `NUM_CPU - 1` will be used
     #[prost(uint64, optional, tag = "8")]
     pub max_optimization_threads: ::core::option::Option<u64>,
+    ///
+    /// Strategy of selecting segments to merge. Default is to merge the smallest segments,
+    /// as long as the merged segment fits into `max_segment_size`
+    #[prost(enumeration = "MergePolicy", optional, tag = "9")]
+    pub merge_policy: ::core::option::Option<i32>,
+    ///
+    /// Whether to rebuild segments with many deleted points, according to the vacuum thresholds.
+    /// Could be disabled for append-only collections, which never delete or update points.
+    #[prost(bool, optional, tag = "10")]
+    pub vacuum_enabled: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -446,6 +610,28 @@ pub struct CreateCollection {
     /// Configuration for sparse vectors
     #[prost(message, optional, tag = "16")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// Access pattern hint for memory-mapped files
+    #[prost(enumeration = "MmapAdvice", optional, tag = "17")]
+    pub mmap_advice: ::core::option::Option<i32>,
+    /// How memory-mapped files are loaded into RAM when segments are loaded
+    #[prost(enumeration = "MmapPrefault", optional, tag = "18")]
+    pub mmap_prefault: ::core::option::Option<i32>,
+    /// Percentage of the most frequently accessed vectors of memory-mapped storages to keep in RAM
+    #[prost(uint32, optional, tag = "19")]
+    #[validate(range(min = 1, max = 100))]
+    pub mmap_hot_percent: ::core::option::Option<u32>,
+    /// Declared types of payload fields, if not empty - payloads with other keys or types are rejected
+    #[prost(map = "string, enumeration(PayloadSchemaType)", tag = "20")]
+    pub strict_payload_schema: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        i32,
+    >,
+    /// Compression of persisted payload records
+    #[prost(enumeration = "PayloadCompression", optional, tag = "21")]
+    pub payload_compression: ::core::option::Option<i32>,
+    /// Backend of the persistent payload storage
+    #[prost(enumeration = "PayloadStorageBackend", optional, tag = "22")]
+    pub payload_storage_backend: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -539,6 +725,27 @@ pub struct CollectionParams {
     /// Configuration for sparse vectors
     #[prost(message, optional, tag = "10")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// Access pattern hint for memory-mapped files
+    #[prost(enumeration = "MmapAdvice", optional, tag = "11")]
+    pub mmap_advice: ::core::option::Option<i32>,
+    /// How memory-mapped files are loaded into RAM when segments are loaded
+    #[prost(enumeration = "MmapPrefault", optional, tag = "12")]
+    pub mmap_prefault: ::core::option::Option<i32>,
+    /// Percentage of the most frequently accessed vectors of memory-mapped storages to keep in RAM
+    #[prost(uint32, optional, tag = "13")]
+    pub mmap_hot_percent: ::core::option::Option<u32>,
+    /// Declared types of payload fields, if not empty - payloads with other keys or types are rejected
+    #[prost(map = "string, enumeration(PayloadSchemaType)", tag = "14")]
+    pub strict_payload_schema: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        i32,
+    >,
+    /// Compression of persisted payload records
+    #[prost(enumeration = "PayloadCompression", optional, tag = "15")]
+    pub payload_compression: ::core::option::Option<i32>,
+    /// Backend of the persistent payload storage
+    #[prost(enumeration = "PayloadStorageBackend", optional, tag = "16")]
+    pub payload_storage_backend: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -585,6 +792,30 @@ pub struct CollectionConfig {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KeywordIndexParams {
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "1")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IntegerIndexParams {
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "1")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FloatIndexParams {
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "1")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct TextIndexParams {
     /// Tokenizer type
     #[prost(enumeration = "TokenizerType", tag = "1")]
@@ -598,12 +829,64 @@ pub struct TextIndexParams {
     /// Maximal token length
     #[prost(uint64, optional, tag = "4")]
     pub max_token_len: ::core::option::Option<u64>,
+    /// If set - tokens are reduced to their stems by the Snowball stemmer of this language
+    #[prost(enumeration = "StemmerLanguage", optional, tag = "5")]
+    pub stemmer: ::core::option::Option<i32>,
+    /// Tokens, which are neither indexed nor used in queries
+    #[prost(string, repeated, tag = "6")]
+    pub stopwords: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Groups of interchangeable words, query token from a group also matches other words of the group
+    #[prost(message, repeated, tag = "7")]
+    pub synonyms: ::prost::alloc::vec::Vec<SynonymGroup>,
+    /// Additional named tokenization configurations, each one builds a separate full-text index of the field
+    #[prost(map = "string, message", tag = "8")]
+    pub analyzers: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        TextAnalyzerParams,
+    >,
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "9")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SynonymGroup {
+    /// Interchangeable words
+    #[prost(string, repeated, tag = "1")]
+    pub words: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TextAnalyzerParams {
+    /// Tokenizer type
+    #[prost(enumeration = "TokenizerType", tag = "1")]
+    pub tokenizer: i32,
+    /// If true - all tokens will be lowercase
+    #[prost(bool, optional, tag = "2")]
+    pub lowercase: ::core::option::Option<bool>,
+    /// Minimal token length
+    #[prost(uint64, optional, tag = "3")]
+    pub min_token_len: ::core::option::Option<u64>,
+    /// Maximal token length
+    #[prost(uint64, optional, tag = "4")]
+    pub max_token_len: ::core::option::Option<u64>,
+    /// If set - tokens are reduced to their stems by the Snowball stemmer of this language
+    #[prost(enumeration = "StemmerLanguage", optional, tag = "5")]
+    pub stemmer: ::core::option::Option<i32>,
+    /// Tokens, which are neither indexed nor used in queries
+    #[prost(string, repeated, tag = "6")]
+    pub stopwords: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Groups of interchangeable words
+    #[prost(message, repeated, tag = "7")]
+    pub synonyms: ::prost::alloc::vec::Vec<SynonymGroup>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PayloadIndexParams {
-    #[prost(oneof = "payload_index_params::IndexParams", tags = "1")]
+    #[prost(oneof = "payload_index_params::IndexParams", tags = "1, 2, 3, 4")]
     pub index_params: ::core::option::Option<payload_index_params::IndexParams>,
 }
 /// Nested message and enum types in `PayloadIndexParams`.
@@ -615,6 +898,15 @@ pub mod payload_index_params {
         /// Parameters for text index
         #[prost(message, tag = "1")]
         TextIndexParams(super::TextIndexParams),
+        /// Parameters for keyword index
+        #[prost(message, tag = "2")]
+        KeywordIndexParams(super::KeywordIndexParams),
+        /// Parameters for integer index
+        #[prost(message, tag = "3")]
+        IntegerIndexParams(super::IntegerIndexParams),
+        /// Parameters for float index
+        #[prost(message, tag = "4")]
+        FloatIndexParams(super::FloatIndexParams),
     }
 }
 #[derive(serde::Serialize)]
@@ -1010,6 +1302,7 @@ pub enum Distance {
     Euclid = 2,
     Dot = 3,
     Manhattan = 4,
+    Hamming = 5,
 }
 impl Distance {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1023,6 +1316,7 @@ impl Distance {
             Distance::Euclid => "Euclid",
             Distance::Dot => "Dot",
             Distance::Manhattan => "Manhattan",
+            Distance::Hamming => "Hamming",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1033,6 +1327,68 @@ impl Distance {
             "Euclid" => Some(Self::Euclid),
             "Dot" => Some(Self::Dot),
             "Manhattan" => Some(Self::Manhattan),
+            "Hamming" => Some(Self::Hamming),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum MultiVectorComparator {
+    /// Sum over query vectors of the best similarity with any stored vector
+    MaxSim = 0,
+}
+impl MultiVectorComparator {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            MultiVectorComparator::MaxSim => "MaxSim",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "MaxSim" => Some(Self::MaxSim),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Datatype {
+    Default = 0,
+    /// Single-precision floating point
+    Float32 = 1,
+    /// Half-precision floating point, halves memory usage
+    Float16 = 2,
+    /// Unsigned 8-bit integer, for vectors quantized by the client
+    Uint8 = 3,
+}
+impl Datatype {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Datatype::Default => "Default",
+            Datatype::Float32 => "Float32",
+            Datatype::Float16 => "Float16",
+            Datatype::Uint8 => "Uint8",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Default" => Some(Self::Default),
+            "Float32" => Some(Self::Float32),
+            "Float16" => Some(Self::Float16),
+            "Uint8" => Some(Self::Uint8),
             _ => None,
         }
     }
@@ -1084,6 +1440,8 @@ pub enum PayloadSchemaType {
     Geo = 4,
     Text = 5,
     Bool = 6,
+    Datetime = 7,
+    Uuid = 8,
 }
 impl PayloadSchemaType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1099,6 +1457,8 @@ impl PayloadSchemaType {
             PayloadSchemaType::Geo => "Geo",
             PayloadSchemaType::Text => "Text",
             PayloadSchemaType::Bool => "Bool",
+            PayloadSchemaType::Datetime => "Datetime",
+            PayloadSchemaType::Uuid => "Uuid",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1111,6 +1471,8 @@ impl PayloadSchemaType {
             "Geo" => Some(Self::Geo),
             "Text" => Some(Self::Text),
             "Bool" => Some(Self::Bool),
+            "Datetime" => Some(Self::Datetime),
+            "Uuid" => Some(Self::Uuid),
             _ => None,
         }
     }
@@ -1210,12 +1572,180 @@ impl ShardingMethod {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum MmapAdvice {
+    UnknownMmapAdvice = 0,
+    /// Expect page references in random order
+    Random = 1,
+    /// Expect page references in sequential order
+    Sequential = 2,
+    /// Expect access in the near future, read some pages ahead
+    WillNeed = 3,
+}
+impl MmapAdvice {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            MmapAdvice::UnknownMmapAdvice => "UnknownMmapAdvice",
+            MmapAdvice::Random => "Random",
+            MmapAdvice::Sequential => "Sequential",
+            MmapAdvice::WillNeed => "WillNeed",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "UnknownMmapAdvice" => Some(Self::UnknownMmapAdvice),
+            "Random" => Some(Self::Random),
+            "Sequential" => Some(Self::Sequential),
+            "WillNeed" => Some(Self::WillNeed),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum MmapPrefault {
+    UnknownMmapPrefault = 0,
+    /// Never read memory-mapped files in advance
+    Never = 1,
+    /// Read memory-mapped files into the page cache in advance
+    Populate = 2,
+    /// Read memory-mapped files and lock them in RAM
+    Lock = 3,
+}
+impl MmapPrefault {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            MmapPrefault::UnknownMmapPrefault => "UnknownMmapPrefault",
+            MmapPrefault::Never => "Never",
+            MmapPrefault::Populate => "Populate",
+            MmapPrefault::Lock => "Lock",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "UnknownMmapPrefault" => Some(Self::UnknownMmapPrefault),
+            "Never" => Some(Self::Never),
+            "Populate" => Some(Self::Populate),
+            "Lock" => Some(Self::Lock),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PayloadCompression {
+    UnknownPayloadCompression = 0,
+    /// Zstandard compression of each record
+    Zstd = 1,
+}
+impl PayloadCompression {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PayloadCompression::UnknownPayloadCompression => "UnknownPayloadCompression",
+            PayloadCompression::Zstd => "Zstd",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "UnknownPayloadCompression" => Some(Self::UnknownPayloadCompression),
+            "Zstd" => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PayloadStorageBackend {
+    UnknownPayloadStorageBackend = 0,
+    /// Payload is persisted in RocksDB
+    Rocksdb = 1,
+    /// Payload is persisted in append-only log files, read via memory mapping
+    Mmap = 2,
+}
+impl PayloadStorageBackend {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PayloadStorageBackend::UnknownPayloadStorageBackend => {
+                "UnknownPayloadStorageBackend"
+            }
+            PayloadStorageBackend::Rocksdb => "Rocksdb",
+            PayloadStorageBackend::Mmap => "Mmap",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "UnknownPayloadStorageBackend" => Some(Self::UnknownPayloadStorageBackend),
+            "Rocksdb" => Some(Self::Rocksdb),
+            "Mmap" => Some(Self::Mmap),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum MergePolicy {
+    /// Merge the smallest segments, as long as the merged segment fits into `max_segment_size`
+    MaxSegmentSizeBound = 0,
+    /// Merge the smallest segments until the amount of segments fits the target, regardless of `max_segment_size`
+    FewestSegments = 1,
+    /// Merge only segments of similar size, which fit into `max_segment_size` together
+    SizeTiered = 2,
+}
+impl MergePolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            MergePolicy::MaxSegmentSizeBound => "MaxSegmentSizeBound",
+            MergePolicy::FewestSegments => "FewestSegments",
+            MergePolicy::SizeTiered => "SizeTiered",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "MaxSegmentSizeBound" => Some(Self::MaxSegmentSizeBound),
+            "FewestSegments" => Some(Self::FewestSegments),
+            "SizeTiered" => Some(Self::SizeTiered),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum TokenizerType {
     Unknown = 0,
     Prefix = 1,
     Whitespace = 2,
     Word = 3,
     Multilingual = 4,
+    Ngram = 5,
 }
 impl TokenizerType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1229,6 +1759,7 @@ impl TokenizerType {
             TokenizerType::Whitespace => "Whitespace",
             TokenizerType::Word => "Word",
             TokenizerType::Multilingual => "Multilingual",
+            TokenizerType::Ngram => "Ngram",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1239,6 +1770,85 @@ impl TokenizerType {
             "Whitespace" => Some(Self::Whitespace),
             "Word" => Some(Self::Word),
             "Multilingual" => Some(Self::Multilingual),
+            "Ngram" => Some(Self::Ngram),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum StemmerLanguage {
+    UnknownStemmerLanguage = 0,
+    Arabic = 1,
+    Danish = 2,
+    Dutch = 3,
+    English = 4,
+    Finnish = 5,
+    French = 6,
+    German = 7,
+    Greek = 8,
+    Hungarian = 9,
+    Italian = 10,
+    Norwegian = 11,
+    Portuguese = 12,
+    Romanian = 13,
+    Russian = 14,
+    Spanish = 15,
+    Swedish = 16,
+    Tamil = 17,
+    Turkish = 18,
+}
+impl StemmerLanguage {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            StemmerLanguage::UnknownStemmerLanguage => "UnknownStemmerLanguage",
+            StemmerLanguage::Arabic => "Arabic",
+            StemmerLanguage::Danish => "Danish",
+            StemmerLanguage::Dutch => "Dutch",
+            StemmerLanguage::English => "English",
+            StemmerLanguage::Finnish => "Finnish",
+            StemmerLanguage::French => "French",
+            StemmerLanguage::German => "German",
+            StemmerLanguage::Greek => "Greek",
+            StemmerLanguage::Hungarian => "Hungarian",
+            StemmerLanguage::Italian => "Italian",
+            StemmerLanguage::Norwegian => "Norwegian",
+            StemmerLanguage::Portuguese => "Portuguese",
+            StemmerLanguage::Romanian => "Romanian",
+            StemmerLanguage::Russian => "Russian",
+            StemmerLanguage::Spanish => "Spanish",
+            StemmerLanguage::Swedish => "Swedish",
+            StemmerLanguage::Tamil => "Tamil",
+            StemmerLanguage::Turkish => "Turkish",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "UnknownStemmerLanguage" => Some(Self::UnknownStemmerLanguage),
+            "Arabic" => Some(Self::Arabic),
+            "Danish" => Some(Self::Danish),
+            "Dutch" => Some(Self::Dutch),
+            "English" => Some(Self::English),
+            "Finnish" => Some(Self::Finnish),
+            "French" => Some(Self::French),
+            "German" => Some(Self::German),
+            "Greek" => Some(Self::Greek),
+            "Hungarian" => Some(Self::Hungarian),
+            "Italian" => Some(Self::Italian),
+            "Norwegian" => Some(Self::Norwegian),
+            "Portuguese" => Some(Self::Portuguese),
+            "Romanian" => Some(Self::Romanian),
+            "Russian" => Some(Self::Russian),
+            "Spanish" => Some(Self::Spanish),
+            "Swedish" => Some(Self::Swedish),
+            "Tamil" => Some(Self::Tamil),
+            "Turkish" => Some(Self::Turkish),
             _ => None,
         }
     }
@@ -3195,6 +3805,12 @@ pub struct Vector {
     pub data: ::prost::alloc::vec::Vec<f32>,
     #[prost(message, optional, tag = "2")]
     pub indices: ::core::option::Option<SparseIndices>,
+    /// Number of vectors in a multivector, `data` holds them flattened
+    #[prost(uint32, optional, tag = "3")]
+    pub vectors_count: ::core::option::Option<u32>,
+    /// Vector data as packed little-endian 32-bit floats, alternative to `data`. Not supported for sparse vectors
+    #[prost(bytes = "vec", optional, tag = "4")]
+    pub packed_data: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
 }
 /// ---------------------------------------------
 /// ----------------- ShardKeySelector ----------
@@ -3370,7 +3986,7 @@ pub struct DeletePayloadPoints {
     /// Wait until the changes have been applied?
     #[prost(bool, optional, tag = "2")]
     pub wait: ::core::option::Option<bool>,
-    /// List of keys to delete
+    /// List of keys to delete, wildcard `*` matches any key of an object
     #[prost(string, repeated, tag = "3")]
     pub keys: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     /// Affected points
@@ -3387,6 +4003,36 @@ pub struct DeletePayloadPoints {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RenamePayloadPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// Payload key to rename, nested keys are separated by dots
+    #[prost(string, tag = "3")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// New payload key, existing value under this key is replaced
+    #[prost(string, tag = "4")]
+    #[validate(length(min = 1))]
+    pub new_key: ::prost::alloc::string::String,
+    /// Affected points
+    #[prost(message, optional, tag = "5")]
+    pub points_selector: ::core::option::Option<PointsSelector>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "6")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Option for custom sharding to specify used shard keys
+    #[prost(message, optional, tag = "7")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ClearPayloadPoints {
     /// name of the collection
     #[prost(string, tag = "1")]
@@ -3451,6 +4097,26 @@ pub struct DeleteFieldIndexCollection {
     #[prost(message, optional, tag = "4")]
     pub ordering: ::core::option::Option<WriteOrdering>,
 }
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RebuildFieldIndexCollection {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// Field name to rebuild
+    #[prost(string, tag = "3")]
+    #[validate(length(min = 1))]
+    pub field_name: ::prost::alloc::string::String,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "4")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+}
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -3598,6 +4264,16 @@ pub struct SearchParams {
     /// guarantee that all uploaded vectors will be included in search results
     #[prost(bool, optional, tag = "4")]
     pub indexed_only: ::core::option::Option<bool>,
+    ///
+    /// If enabled, filtered HNSW search also looks at the neighbours of points rejected by the filter.
+    /// Improves recall of searches with restrictive filters, at the cost of additional filter checks.
+    #[prost(bool, optional, tag = "5")]
+    pub acorn: ::core::option::Option<bool>,
+    ///
+    /// Params relevant to IVF index. Number of nearest inverted lists to scan.
+    /// Larger the value - more accurate the result, more time required for search.
+    #[prost(uint64, optional, tag = "6")]
+    pub ivf_nprobe: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3650,6 +4326,10 @@ pub struct SearchPoints {
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
     #[prost(message, optional, tag = "15")]
     pub sparse_indices: ::core::option::Option<SparseIndices>,
+    /// Order points with equal scores by a payload field
+    #[prost(message, optional, tag = "16")]
+    #[validate]
+    pub tie_breaker: ::core::option::Option<TieBreaker>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3749,6 +4429,63 @@ pub struct SearchPointGroups {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StartFrom {
+    #[prost(oneof = "start_from::Value", tags = "1, 2, 3")]
+    pub value: ::core::option::Option<start_from::Value>,
+}
+/// Nested message and enum types in `StartFrom`.
+pub mod start_from {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Value {
+        #[prost(double, tag = "1")]
+        Float(f64),
+        #[prost(int64, tag = "2")]
+        Integer(i64),
+        /// RFC 3339 datetime
+        #[prost(string, tag = "3")]
+        Datetime(::prost::alloc::string::String),
+    }
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderBy {
+    /// Payload key to order by
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Ascending or descending order
+    #[prost(enumeration = "Direction", optional, tag = "2")]
+    pub direction: ::core::option::Option<i32>,
+    /// Start from this value
+    #[prost(message, optional, tag = "3")]
+    pub start_from: ::core::option::Option<StartFrom>,
+    /// Order by distance in meters from this point, requires geo index
+    #[prost(message, optional, tag = "4")]
+    pub distance_from: ::core::option::Option<GeoPoint>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TieBreaker {
+    /// Payload key to order by
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Ascending or descending order
+    #[prost(enumeration = "Direction", optional, tag = "2")]
+    pub direction: ::core::option::Option<i32>,
+    /// Order by distance in meters from this point, requires geo index
+    #[prost(message, optional, tag = "3")]
+    pub distance_from: ::core::option::Option<GeoPoint>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ScrollPoints {
     #[prost(string, tag = "1")]
     #[validate(length(min = 1, max = 255))]
@@ -3776,6 +4513,10 @@ pub struct ScrollPoints {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "9")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Order the records by a payload field
+    #[prost(message, optional, tag = "10")]
+    #[validate]
+    pub order_by: ::core::option::Option<OrderBy>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4073,23 +4814,58 @@ pub struct DiscoverBatchPoints {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CountPoints {
+pub struct CountPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Filter conditions - return only those points that satisfy the specified conditions
+    #[prost(message, optional, tag = "2")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// If `true` - return exact count, if `false` - return approximate count
+    #[prost(bool, optional, tag = "3")]
+    pub exact: ::core::option::Option<bool>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "4")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "5")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetCounts {
     /// name of the collection
     #[prost(string, tag = "1")]
     #[validate(length(min = 1, max = 255))]
     pub collection_name: ::prost::alloc::string::String,
-    /// Filter conditions - return only those points that satisfy the specified conditions
-    #[prost(message, optional, tag = "2")]
+    /// Payload key of the facet, must be indexed with a keyword or integer index
+    #[prost(string, tag = "2")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Filter conditions - count only points that satisfy the specified conditions
+    #[prost(message, optional, tag = "3")]
     #[validate]
     pub filter: ::core::option::Option<Filter>,
-    /// If `true` - return exact count, if `false` - return approximate count
-    #[prost(bool, optional, tag = "3")]
+    /// Max number of values to return, values with the most points go first. Default is 10
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub limit: ::core::option::Option<u64>,
+    /// If `true` - return exact counts, if `false` - each shard only reports its top values
+    #[prost(bool, optional, tag = "5")]
     pub exact: ::core::option::Option<bool>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "6")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
     /// Options for specifying read consistency guarantees
-    #[prost(message, optional, tag = "4")]
+    #[prost(message, optional, tag = "7")]
     pub read_consistency: ::core::option::Option<ReadConsistency>,
     /// Specify in which shards to look for the points, if not specified - look in all shards
-    #[prost(message, optional, tag = "5")]
+    #[prost(message, optional, tag = "8")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
 }
 #[derive(serde::Serialize)]
@@ -4392,6 +5168,65 @@ pub struct ScrollResponse {
 pub struct CountResult {
     #[prost(uint64, tag = "1")]
     pub count: u64,
+    /// Bounds of the number of points, only present if the count is not exact
+    #[prost(message, optional, tag = "2")]
+    pub estimation: ::core::option::Option<CountEstimation>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CountEstimation {
+    /// Minimal possible number of points
+    #[prost(uint64, tag = "1")]
+    pub min: u64,
+    /// Expected number of points
+    #[prost(uint64, tag = "2")]
+    pub exp: u64,
+    /// Maximal possible number of points
+    #[prost(uint64, tag = "3")]
+    pub max: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetValue {
+    #[prost(oneof = "facet_value::Variant", tags = "1, 2")]
+    pub variant: ::core::option::Option<facet_value::Variant>,
+}
+/// Nested message and enum types in `FacetValue`.
+pub mod facet_value {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        /// String value of the payload field
+        #[prost(string, tag = "1")]
+        StringValue(::prost::alloc::string::String),
+        /// Integer value of the payload field
+        #[prost(int64, tag = "2")]
+        IntegerValue(i64),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetHit {
+    /// Value of the payload field
+    #[prost(message, optional, tag = "1")]
+    pub value: ::core::option::Option<FacetValue>,
+    /// Number of points with this value
+    #[prost(uint64, tag = "2")]
+    pub count: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub hits: ::prost::alloc::vec::Vec<FacetHit>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4406,6 +5241,9 @@ pub struct RetrievedPoint {
     /// Shard key
     #[prost(message, optional, tag = "5")]
     pub shard_key: ::core::option::Option<ShardKey>,
+    /// Value of the payload field, by which the points are ordered
+    #[prost(double, optional, tag = "6")]
+    pub order_value: ::core::option::Option<f64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4494,13 +5332,28 @@ pub struct Filter {
     #[prost(message, repeated, tag = "3")]
     #[validate]
     pub must_not: ::prost::alloc::vec::Vec<Condition>,
+    /// At least minimum amount of given conditions should match
+    #[prost(message, optional, tag = "4")]
+    #[validate]
+    pub min_should: ::core::option::Option<MinShould>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MinShould {
+    #[prost(message, repeated, tag = "1")]
+    #[validate]
+    pub conditions: ::prost::alloc::vec::Vec<Condition>,
+    #[prost(uint64, tag = "2")]
+    pub min_count: u64,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Condition {
-    #[prost(oneof = "condition::ConditionOneOf", tags = "1, 2, 3, 4, 5, 6")]
+    #[prost(oneof = "condition::ConditionOneOf", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
     #[validate]
     pub condition_one_of: ::core::option::Option<condition::ConditionOneOf>,
 }
@@ -4522,6 +5375,10 @@ pub mod condition {
         IsNull(super::IsNullCondition),
         #[prost(message, tag = "6")]
         Nested(super::NestedCondition),
+        #[prost(message, tag = "7")]
+        HasVector(super::HasVectorCondition),
+        #[prost(message, tag = "8")]
+        HasIdRange(super::HasIdRangeCondition),
     }
 }
 #[derive(serde::Serialize)]
@@ -4545,6 +5402,24 @@ pub struct HasIdCondition {
     #[prost(message, repeated, tag = "1")]
     pub has_id: ::prost::alloc::vec::Vec<PointId>,
 }
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HasVectorCondition {
+    #[prost(string, tag = "1")]
+    pub has_vector: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HasIdRangeCondition {
+    /// Point ID greater than or equal
+    #[prost(message, optional, tag = "1")]
+    pub gte: ::core::option::Option<PointId>,
+    /// Point ID less than
+    #[prost(message, optional, tag = "2")]
+    pub lt: ::core::option::Option<PointId>,
+}
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4557,6 +5432,9 @@ pub struct NestedCondition {
     #[prost(message, optional, tag = "2")]
     #[validate]
     pub filter: ::core::option::Option<Filter>,
+    /// Number of nested objects, which must match the filter. Default is at least one
+    #[prost(message, optional, tag = "3")]
+    pub count: ::core::option::Option<ValuesCount>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4582,12 +5460,18 @@ pub struct FieldCondition {
     /// Check if geo point is within a given polygon
     #[prost(message, optional, tag = "7")]
     pub geo_polygon: ::core::option::Option<GeoPolygon>,
+    /// Check if datetime lies in a given range
+    #[prost(message, optional, tag = "8")]
+    pub datetime_range: ::core::option::Option<DatetimeRange>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Match {
-    #[prost(oneof = "r#match::MatchValue", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
+    /// Name of the text index analyzer, used by text and phrase matching
+    #[prost(string, optional, tag = "11")]
+    pub analyzer: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(oneof = "r#match::MatchValue", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10")]
     pub match_value: ::core::option::Option<r#match::MatchValue>,
 }
 /// Nested message and enum types in `Match`.
@@ -4620,6 +5504,12 @@ pub mod r#match {
         /// Match any other value except those keywords
         #[prost(message, tag = "8")]
         ExceptKeywords(super::RepeatedStrings),
+        /// Match text, which contains tokens of the phrase one after another
+        #[prost(string, tag = "9")]
+        Phrase(::prost::alloc::string::String),
+        /// Match strings or text tokens, which start with the prefix
+        #[prost(string, tag = "10")]
+        Prefix(::prost::alloc::string::String),
     }
 }
 #[derive(serde::Serialize)]
@@ -4652,6 +5542,23 @@ pub struct Range {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DatetimeRange {
+    #[prost(message, optional, tag = "1")]
+    #[serde(skip)]
+    pub lt: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "2")]
+    #[serde(skip)]
+    pub gt: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "3")]
+    #[serde(skip)]
+    pub gte: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "4")]
+    #[serde(skip)]
+    pub lte: ::core::option::Option<::prost_types::Timestamp>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GeoBoundingBox {
     /// north-west corner
     #[prost(message, optional, tag = "1")]
@@ -4832,6 +5739,8 @@ pub enum FieldType {
     Geo = 3,
     Text = 4,
     Bool = 5,
+    Datetime = 6,
+    Uuid = 7,
 }
 impl FieldType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -4846,6 +5755,8 @@ impl FieldType {
             FieldType::Geo => "FieldTypeGeo",
             FieldType::Text => "FieldTypeText",
             FieldType::Bool => "FieldTypeBool",
+            FieldType::Datetime => "FieldTypeDatetime",
+            FieldType::Uuid => "FieldTypeUuid",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -4857,6 +5768,35 @@ impl FieldType {
             "FieldTypeGeo" => Some(Self::Geo),
             "FieldTypeText" => Some(Self::Text),
             "FieldTypeBool" => Some(Self::Bool),
+            "FieldTypeDatetime" => Some(Self::Datetime),
+            "FieldTypeUuid" => Some(Self::Uuid),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Direction {
+    Asc = 0,
+    Desc = 1,
+}
+impl Direction {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Direction::Asc => "Asc",
+            Direction::Desc => "Desc",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Asc" => Some(Self::Asc),
+            "Desc" => Some(Self::Desc),
             _ => None,
         }
     }
@@ -5213,6 +6153,33 @@ pub mod points_client {
             self.inner.unary(req, path, codec).await
         }
         ///
+        /// Rename payload key for points
+        pub async fn rename_payload(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RenamePayloadPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/RenamePayload",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "RenamePayload"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
         /// Remove all payload for specified points
         pub async fn clear_payload(
             &mut self,
@@ -5294,6 +6261,33 @@ pub mod points_client {
             self.inner.unary(req, path, codec).await
         }
         ///
+        /// Rebuild field index for collection, vector indexes are not affected
+        pub async fn rebuild_field_index(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RebuildFieldIndexCollection>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/RebuildFieldIndex",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "RebuildFieldIndex"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
         /// Retrieve closest points based on vector similarity and given filtering conditions
         pub async fn search(
             &mut self,
@@ -5657,6 +6651,15 @@ pub mod points_server {
             tonic::Status,
         >;
         ///
+        /// Rename payload key for points
+        async fn rename_payload(
+            &self,
+            request: tonic::Request<super::RenamePayloadPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        >;
+        ///
         /// Remove all payload for specified points
         async fn clear_payload(
             &self,
@@ -5684,6 +6687,15 @@ pub mod points_server {
             tonic::Status,
         >;
         ///
+        /// Rebuild field index for collection, vector indexes are not affected
+        async fn rebuild_field_index(
+            &self,
+            request: tonic::Request<super::RebuildFieldIndexCollection>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        >;
+        ///
         /// Retrieve closest points based on vector similarity and given filtering conditions
         async fn search(
             &self,
@@ -6209,7 +7221,99 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = DeletePayloadSvc(inner);
+                        let method = DeletePayloadSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/RenamePayload" => {
+                    #[allow(non_camel_case_types)]
+                    struct RenamePayloadSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::RenamePayloadPoints>
+                    for RenamePayloadSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RenamePayloadPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::rename_payload(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RenamePayloadSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/ClearPayload" => {
+                    #[allow(non_camel_case_types)]
+                    struct ClearPayloadSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::ClearPayloadPoints>
+                    for ClearPayloadSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ClearPayloadPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::clear_payload(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ClearPayloadSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6225,13 +7329,13 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/ClearPayload" => {
+                "/qdrant.Points/CreateFieldIndex" => {
                     #[allow(non_camel_case_types)]
-                    struct ClearPayloadSvc<T: Points>(pub Arc<T>);
+                    struct CreateFieldIndexSvc<T: Points>(pub Arc<T>);
                     impl<
                         T: Points,
-                    > tonic::server::UnaryService<super::ClearPayloadPoints>
-                    for ClearPayloadSvc<T> {
+                    > tonic::server::UnaryService<super::CreateFieldIndexCollection>
+                    for CreateFieldIndexSvc<T> {
                         type Response = super::PointsOperationResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -6239,11 +7343,11 @@ pub mod points_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ClearPayloadPoints>,
+                            request: tonic::Request<super::CreateFieldIndexCollection>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::clear_payload(&inner, request).await
+                                <T as Points>::create_field_index(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6255,7 +7359,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = ClearPayloadSvc(inner);
+                        let method = CreateFieldIndexSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6271,13 +7375,13 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/CreateFieldIndex" => {
+                "/qdrant.Points/DeleteFieldIndex" => {
                     #[allow(non_camel_case_types)]
-                    struct CreateFieldIndexSvc<T: Points>(pub Arc<T>);
+                    struct DeleteFieldIndexSvc<T: Points>(pub Arc<T>);
                     impl<
                         T: Points,
-                    > tonic::server::UnaryService<super::CreateFieldIndexCollection>
-                    for CreateFieldIndexSvc<T> {
+                    > tonic::server::UnaryService<super::DeleteFieldIndexCollection>
+                    for DeleteFieldIndexSvc<T> {
                         type Response = super::PointsOperationResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -6285,11 +7389,11 @@ pub mod points_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::CreateFieldIndexCollection>,
+                            request: tonic::Request<super::DeleteFieldIndexCollection>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::create_field_index(&inner, request).await
+                                <T as Points>::delete_field_index(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6301,7 +7405,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = CreateFieldIndexSvc(inner);
+                        let method = DeleteFieldIndexSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6317,13 +7421,13 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/DeleteFieldIndex" => {
+                "/qdrant.Points/RebuildFieldIndex" => {
                     #[allow(non_camel_case_types)]
-                    struct DeleteFieldIndexSvc<T: Points>(pub Arc<T>);
+                    struct RebuildFieldIndexSvc<T: Points>(pub Arc<T>);
                     impl<
                         T: Points,
-                    > tonic::server::UnaryService<super::DeleteFieldIndexCollection>
-                    for DeleteFieldIndexSvc<T> {
+                    > tonic::server::UnaryService<super::RebuildFieldIndexCollection>
+                    for RebuildFieldIndexSvc<T> {
                         type Response = super::PointsOperationResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -6331,11 +7435,11 @@ pub mod points_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::DeleteFieldIndexCollection>,
+                            request: tonic::Request<super::RebuildFieldIndexCollection>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::delete_field_index(&inner, request).await
+                                <T as Points>::rebuild_field_index(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6347,7 +7451,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = DeleteFieldIndexSvc(inner);
+                        let method = RebuildFieldIndexSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6998,6 +8102,17 @@ pub struct DeletePayloadPointsInternal {
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RenamePayloadPointsInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub rename_payload_points: ::core::option::Option<RenamePayloadPoints>,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ClearPayloadPointsInternal {
     #[prost(message, optional, tag = "1")]
     #[validate]
@@ -7035,6 +8150,19 @@ pub struct DeleteFieldIndexCollectionInternal {
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RebuildFieldIndexCollectionInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub rebuild_field_index_collection: ::core::option::Option<
+        RebuildFieldIndexCollection,
+    >,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SearchPointsInternal {
     #[prost(message, optional, tag = "1")]
     #[validate]
@@ -7223,6 +8351,17 @@ pub struct CountPointsInternal {
     #[prost(uint32, optional, tag = "2")]
     pub shard_id: ::core::option::Option<u32>,
 }
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetCountsInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub facet_counts: ::core::option::Option<FacetCounts>,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+}
 /// Generated client implementations.
 pub mod points_internal_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -7508,6 +8647,31 @@ pub mod points_internal_client {
                 .insert(GrpcMethod::new("qdrant.PointsInternal", "DeletePayload"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn rename_payload(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RenamePayloadPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/RenamePayload",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "RenamePayload"));
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn clear_payload(
             &mut self,
             request: impl tonic::IntoRequest<super::ClearPayloadPointsInternal>,
@@ -7583,6 +8747,31 @@ pub mod points_internal_client {
                 .insert(GrpcMethod::new("qdrant.PointsInternal", "DeleteFieldIndex"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn rebuild_field_index(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RebuildFieldIndexCollectionInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/RebuildFieldIndex",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "RebuildFieldIndex"));
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn search(
             &mut self,
             request: impl tonic::IntoRequest<super::SearchPointsInternal>,
@@ -7699,6 +8888,28 @@ pub mod points_internal_client {
                 .insert(GrpcMethod::new("qdrant.PointsInternal", "Count"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn facet(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FacetCountsInternal>,
+        ) -> std::result::Result<tonic::Response<super::FacetResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/Facet",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "Facet"));
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn recommend(
             &mut self,
             request: impl tonic::IntoRequest<super::RecommendPointsInternal>,
@@ -7810,6 +9021,13 @@ pub mod points_internal_server {
             tonic::Response<super::PointsOperationResponse>,
             tonic::Status,
         >;
+        async fn rename_payload(
+            &self,
+            request: tonic::Request<super::RenamePayloadPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        >;
         async fn clear_payload(
             &self,
             request: tonic::Request<super::ClearPayloadPointsInternal>,
@@ -7831,6 +9049,13 @@ pub mod points_internal_server {
             tonic::Response<super::PointsOperationResponse>,
             tonic::Status,
         >;
+        async fn rebuild_field_index(
+            &self,
+            request: tonic::Request<super::RebuildFieldIndexCollectionInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        >;
         async fn search(
             &self,
             request: tonic::Request<super::SearchPointsInternal>,
@@ -7857,6 +9082,10 @@ pub mod points_internal_server {
             &self,
             request: tonic::Request<super::CountPointsInternal>,
         ) -> std::result::Result<tonic::Response<super::CountResponse>, tonic::Status>;
+        async fn facet(
+            &self,
+            request: tonic::Request<super::FacetCountsInternal>,
+        ) -> std::result::Result<tonic::Response<super::FacetResponse>, tonic::Status>;
         async fn recommend(
             &self,
             request: tonic::Request<super::RecommendPointsInternal>,
@@ -8317,6 +9546,52 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/RenamePayload" => {
+                    #[allow(non_camel_case_types)]
+                    struct RenamePayloadSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::RenamePayloadPointsInternal>
+                    for RenamePayloadSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RenamePayloadPointsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::rename_payload(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RenamePayloadSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.PointsInternal/ClearPayload" => {
                     #[allow(non_camel_case_types)]
                     struct ClearPayloadSvc<T: PointsInternal>(pub Arc<T>);
@@ -8463,6 +9738,56 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/RebuildFieldIndex" => {
+                    #[allow(non_camel_case_types)]
+                    struct RebuildFieldIndexSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<
+                        super::RebuildFieldIndexCollectionInternal,
+                    > for RebuildFieldIndexSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::RebuildFieldIndexCollectionInternal,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::rebuild_field_index(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RebuildFieldIndexSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.PointsInternal/Search" => {
                     #[allow(non_camel_case_types)]
                     struct SearchSvc<T: PointsInternal>(pub Arc<T>);
@@ -8694,6 +10019,52 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/Facet" => {
+                    #[allow(non_camel_case_types)]
+                    struct FacetSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::FacetCountsInternal>
+                    for FacetSvc<T> {
+                        type Response = super::FacetResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FacetCountsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::facet(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = FacetSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.PointsInternal/Recommend" => {
                     #[allow(non_camel_case_types)]
                     struct RecommendSvc<T: PointsInternal>(pub Arc<T>);
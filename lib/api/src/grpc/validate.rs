@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::mem::size_of;
 
 use common::validation::{validate_move_shard_different_peers, validate_range_generic};
 use validator::{Validate, ValidationError, ValidationErrors};
@@ -94,6 +95,17 @@ impl Validate for crate::grpc::qdrant::quantization_config::Quantization {
     }
 }
 
+impl Validate for crate::grpc::qdrant::vector_index_params::Index {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        use crate::grpc::qdrant::vector_index_params::Index;
+        match self {
+            Index::Hnsw(hnsw) => hnsw.validate(),
+            Index::Vamana(vamana) => vamana.validate(),
+            Index::Ivf(ivf) => ivf.validate(),
+        }
+    }
+}
+
 impl Validate for crate::grpc::qdrant::quantization_config_diff::Quantization {
     fn validate(&self) -> Result<(), ValidationErrors> {
         use crate::grpc::qdrant::quantization_config_diff::Quantization;
@@ -165,6 +177,8 @@ impl Validate for crate::grpc::qdrant::condition::ConditionOneOf {
             ConditionOneOf::Filter(filter) => filter.validate(),
             ConditionOneOf::IsEmpty(_) => Ok(()),
             ConditionOneOf::HasId(_) => Ok(()),
+            ConditionOneOf::HasVector(_) => Ok(()),
+            ConditionOneOf::HasIdRange(_) => Ok(()),
             ConditionOneOf::IsNull(_) => Ok(()),
         }
     }
@@ -177,7 +191,8 @@ impl Validate for crate::grpc::qdrant::FieldCondition {
             && self.geo_bounding_box.is_none()
             && self.geo_radius.is_none()
             && self.geo_polygon.is_none()
-            && self.values_count.is_none();
+            && self.values_count.is_none()
+            && self.datetime_range.is_none();
 
         if all_fields_none {
             let mut errors = ValidationErrors::new();
@@ -194,14 +209,44 @@ impl Validate for crate::grpc::qdrant::FieldCondition {
 
 impl Validate for crate::grpc::qdrant::Vector {
     fn validate(&self) -> Result<(), ValidationErrors> {
+        let data_len = match &self.packed_data {
+            Some(packed_data) => validate_packed_vector_data(self, packed_data)?,
+            None => self.data.len(),
+        };
+
         if let Some(indices) = &self.indices {
             sparse::common::sparse_vector::validate_sparse_vector_impl(&indices.data, &self.data)
+        } else if let Some(vectors_count) = self.vectors_count {
+            validate_multi_vector_shape(vectors_count, data_len)
         } else {
             Ok(())
         }
     }
 }
 
+/// Validate packed vector data can be decoded into floats, returns the number of floats.
+fn validate_packed_vector_data(
+    vector: &crate::grpc::qdrant::Vector,
+    packed_data: &[u8],
+) -> Result<usize, ValidationErrors> {
+    let error = if !vector.data.is_empty() {
+        Some("must not be used together with `data`")
+    } else if vector.indices.is_some() {
+        Some("is not supported for sparse vectors")
+    } else if packed_data.len() % size_of::<f32>() != 0 {
+        Some("length must be a multiple of 4 bytes")
+    } else {
+        None
+    };
+
+    if let Some(message) = error {
+        let mut errors = ValidationErrors::new();
+        errors.add("packed_data", ValidationError::new(message));
+        return Err(errors);
+    }
+    Ok(packed_data.len() / size_of::<f32>())
+}
+
 impl Validate for super::qdrant::vectors::VectorsOptions {
     fn validate(&self) -> Result<(), ValidationErrors> {
         match self {
@@ -222,6 +267,23 @@ impl Validate for super::qdrant::query_enum::Query {
     }
 }
 
+/// Validate flattened multivector data can be split into `vectors_count` non-empty vectors.
+fn validate_multi_vector_shape(
+    vectors_count: u32,
+    data_len: usize,
+) -> Result<(), ValidationErrors> {
+    let vectors_count = vectors_count as usize;
+    if vectors_count == 0 || data_len == 0 || data_len % vectors_count != 0 {
+        let mut errors = ValidationErrors::new();
+        errors.add(
+            "vectors_count",
+            ValidationError::new("must evenly split data into non-empty vectors"),
+        );
+        return Err(errors);
+    }
+    Ok(())
+}
+
 /// Validate the value is in `[1, ]` or `None`.
 pub fn validate_u64_range_min_1(value: &Option<u64>) -> Result<(), ValidationError> {
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(1), None))
@@ -262,6 +324,11 @@ pub fn validate_f64_range_1(value: &Option<f64>) -> Result<(), ValidationError>
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(0.0), Some(1.0)))
 }
 
+/// Validate the value is in `[0.0, ]` or `None`.
+pub fn validate_f64_range_min_0(value: &Option<f64>) -> Result<(), ValidationError> {
+    value.map_or(Ok(()), |v| validate_range_generic(v, Some(0.0), None))
+}
+
 /// Validate the value is in `[1.0, ]` or `None`.
 pub fn validate_f64_range_min_1(value: &Option<f64>) -> Result<(), ValidationError> {
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(1.0), None))
@@ -328,7 +395,7 @@ mod tests {
 
     use crate::grpc::qdrant::{
         CreateCollection, CreateFieldIndexCollection, GeoLineString, GeoPoint, GeoPolygon,
-        SearchPoints, UpdateCollection,
+        SearchPoints, SparseIndices, UpdateCollection, Vector,
     };
 
     #[test]
@@ -442,6 +509,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_packed_vector() {
+        let packed_data: Vec<u8> = [1.0f32, -2.5, 3.25, 0.0]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+
+        let vector = Vector {
+            packed_data: Some(packed_data.clone()),
+            vectors_count: Some(2),
+            ..Default::default()
+        };
+        assert!(vector.validate().is_ok());
+        let vector: segment::data_types::vectors::Vector = vector.into();
+        let multi_vector: segment::data_types::vectors::MultiDenseVector =
+            vector.try_into().unwrap();
+        assert_eq!(multi_vector.flattened_vectors, vec![1.0, -2.5, 3.25, 0.0]);
+
+        let bad_vector = Vector {
+            packed_data: Some(packed_data[..7].to_vec()),
+            ..Default::default()
+        };
+        assert!(bad_vector.validate().is_err());
+
+        let bad_vector = Vector {
+            data: vec![1.0],
+            packed_data: Some(packed_data.clone()),
+            ..Default::default()
+        };
+        assert!(bad_vector.validate().is_err());
+
+        let bad_vector = Vector {
+            packed_data: Some(packed_data),
+            indices: Some(SparseIndices {
+                data: vec![0, 1, 2, 3],
+            }),
+            ..Default::default()
+        };
+        assert!(bad_vector.validate().is_err());
+    }
+
     #[test]
     fn test_geo_polygon() {
         let bad_polygon = GeoPolygon {
@@ -10,7 +10,7 @@ use collection::operations::point_ops::{
 };
 use collection::operations::types::{CoreSearchRequestBatch, SearchRequestInternal, VectorParams};
 use collection::operations::CollectionUpdateOperations;
-use collection::optimizers_builder::OptimizersConfig;
+use collection::optimizers_builder::{MergePolicy, OptimizersConfig};
 use collection::shards::local_shard::LocalShard;
 use collection::shards::shard_trait::ShardOperation;
 use criterion::{criterion_group, criterion_main, Criterion};
@@ -65,6 +65,11 @@ fn batch_search_bench(c: &mut Criterion) {
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            index: None,
+            multivector_config: None,
+            datatype: None,
+            keep_original: None,
+            dimension_weights: None,
         }
         .into(),
         ..CollectionParams::empty()
@@ -75,12 +80,14 @@ fn batch_search_bench(c: &mut Criterion) {
         optimizer_config: OptimizersConfig {
             deleted_threshold: 0.9,
             vacuum_min_vector_number: 1000,
+            vacuum_enabled: true,
             default_segment_number: 2,
             max_segment_size: Some(100_000),
             memmap_threshold: Some(100_000),
             indexing_threshold: Some(50_000),
             flush_interval_sec: 30,
             max_optimization_threads: 2,
+            merge_policy: MergePolicy::MaxSegmentSizeBound,
         },
         wal_config,
         hnsw_config: Default::default(),
@@ -142,6 +149,7 @@ fn batch_search_bench(c: &mut Criterion) {
                             with_payload: None,
                             with_vector: None,
                             score_threshold: None,
+                            tie_breaker: None,
                         };
                         let result = shard
                             .core_search(
@@ -175,6 +183,7 @@ fn batch_search_bench(c: &mut Criterion) {
                             with_payload: None,
                             with_vector: None,
                             score_threshold: None,
+                            tie_breaker: None,
                         };
                         searches.push(search_query.into());
                     }
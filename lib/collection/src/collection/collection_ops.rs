@@ -217,6 +217,18 @@ impl Collection {
         Ok(())
     }
 
+    /// Read memory-mapped vector storages and indexes of local shards into the page cache
+    ///
+    /// Only data of this peer is loaded, remote replicas are not affected.
+    pub async fn warmup(&self, vector_name: Option<&str>) -> CollectionResult<()> {
+        let shard_holder = self.shards_holder.read().await;
+        let warmups = shard_holder
+            .all_shards()
+            .map(|replica_set| replica_set.warmup(vector_name));
+        future::try_join_all(warmups).await?;
+        Ok(())
+    }
+
     pub async fn info(
         &self,
         shard_selection: &ShardSelectorInternal,
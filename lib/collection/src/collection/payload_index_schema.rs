@@ -5,7 +5,7 @@ use segment::types::{PayloadFieldSchema, PayloadKeyType};
 use serde::{Deserialize, Serialize};
 
 use crate::collection::Collection;
-use crate::operations::types::{CollectionResult, UpdateResult};
+use crate::operations::types::{CollectionError, CollectionResult, UpdateResult};
 use crate::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
 use crate::save_on_disk::SaveOnDisk;
 
@@ -76,4 +76,20 @@ impl Collection {
 
         Ok(result)
     }
+
+    /// Check that the payload field has an index in the collection
+    pub fn check_payload_index_exists(&self, field_name: &str) -> CollectionResult<()> {
+        if self
+            .payload_index_schema
+            .read()
+            .schema
+            .contains_key(field_name)
+        {
+            Ok(())
+        } else {
+            Err(CollectionError::bad_request(format!(
+                "Field `{field_name}` is not indexed"
+            )))
+        }
+    }
 }
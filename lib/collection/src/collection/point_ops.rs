@@ -1,12 +1,15 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use futures::{future, TryFutureExt, TryStreamExt as _};
 use itertools::Itertools as _;
+use segment::data_types::facets::{merge_facet_counts, top_facet_hits};
 use segment::types::{ShardKey, WithPayload, WithPayloadInterface};
 use validator::Validate as _;
 
 use super::Collection;
 use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::payload_schema::check_operation_payloads;
 use crate::operations::point_ops::WriteOrdering;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::*;
@@ -88,6 +91,15 @@ impl Collection {
         shard_keys_selection: Option<ShardKey>,
     ) -> CollectionResult<UpdateResult> {
         operation.validate()?;
+        if let Some(schema) = &self
+            .collection_config
+            .read()
+            .await
+            .params
+            .strict_payload_schema
+        {
+            check_operation_payloads(schema, &operation)?;
+        }
         let _update_lock = self.updates_lock.read().await;
 
         let mut results = {
@@ -160,8 +172,15 @@ impl Collection {
             });
         }
 
+        let order_by = request.order_by.as_ref();
+        if order_by.is_some() && offset.is_some() {
+            return Err(CollectionError::BadRequest {
+                description: "Offset can't be used together with `order_by`, use `order_by.start_from` instead".to_string(),
+            });
+        }
+
         // Needed to return next page offset.
-        let limit = limit + 1;
+        let limit = if order_by.is_none() { limit + 1 } else { limit };
         let retrieved_points: Vec<_> = {
             let shards_holder = self.shards_holder.read().await;
             let target_shards = shards_holder.select_shards(shard_selection)?;
@@ -174,6 +193,7 @@ impl Collection {
                         &with_payload_interface,
                         &with_vector,
                         request.filter.as_ref(),
+                        order_by,
                         read_consistency,
                         shard_selection.is_shard_id(),
                     )
@@ -190,6 +210,26 @@ impl Collection {
 
             future::try_join_all(scroll_futures).await?
         };
+        if let Some(order_by) = order_by {
+            // Pagination is done by values of the field, with `start_from`
+            let points = retrieved_points
+                .into_iter()
+                .flatten()
+                .sorted_by(|point, other| {
+                    let value = point.order_value.unwrap_or_default();
+                    let other_value = other.order_value.unwrap_or_default();
+                    order_by
+                        .cmp_values(value, other_value)
+                        .then_with(|| point.id.cmp(&other.id))
+                })
+                .take(limit)
+                .collect();
+            return Ok(ScrollResult {
+                points,
+                next_page_offset: None,
+            });
+        }
+
         let mut points: Vec<_> = retrieved_points
             .into_iter()
             .flatten()
@@ -233,12 +273,59 @@ impl Collection {
             .collect();
 
         let mut count = 0;
+        let mut estimation: Option<CountEstimation> = None;
 
         while let Some(response) = requests.try_next().await? {
             count += response.count;
+            if !request.exact {
+                let shard_estimation = response
+                    .estimation
+                    .unwrap_or(CountEstimation::exact(response.count));
+                estimation = Some(match estimation {
+                    Some(estimation) => estimation + shard_estimation,
+                    None => shard_estimation,
+                });
+            }
+        }
+
+        Ok(CountResult { count, estimation })
+    }
+
+    pub async fn facet(
+        &self,
+        request: FacetRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        let shards_holder = self.shards_holder.read().await;
+        let shards = shards_holder.select_shards(shard_selection)?;
+
+        let request = Arc::new(request);
+        let mut requests: futures::stream::FuturesUnordered<_> = shards
+            .into_iter()
+            // `facet` requests received through internal gRPC *always* have `shard_selection`
+            .map(|(shard, _shard_key)| {
+                shard.facet(
+                    request.clone(),
+                    read_consistency,
+                    shard_selection.is_shard_id(),
+                    timeout,
+                )
+            })
+            .collect();
+
+        let mut shards_counts = Vec::new();
+        while let Some(response) = requests.try_next().await? {
+            shards_counts.extend(response.hits.into_iter().map(|hit| (hit.value, hit.count)));
         }
+        let counts = merge_facet_counts(shards_counts);
 
-        Ok(CountResult { count })
+        // Response for a single shard is merged with other shards by the peer, which sent the request
+        let limit = (!shard_selection.is_shard_id()).then_some(request.limit);
+        Ok(FacetResponse {
+            hits: top_facet_hits(counts, limit),
+        })
     }
 
     pub async fn retrieve(
@@ -1,10 +1,15 @@
 use std::collections::HashMap;
+use std::mem;
 use std::sync::Arc;
 use std::time::Duration;
 
 use futures::{future, TryFutureExt};
+use segment::data_types::order_by::{OrderValue, TieBreaker};
 use segment::spaces::tools;
-use segment::types::{ExtendedPointId, Order, ScoredPoint, WithPayloadInterface, WithVector};
+use segment::types::{
+    ExtendedPointId, Order, PayloadContainer, PayloadSchemaType, ScoredPoint, WithPayloadInterface,
+    WithVector,
+};
 
 use super::Collection;
 use crate::operations::consistency_params::ReadConsistency;
@@ -32,6 +37,29 @@ impl Collection {
         Ok(results.into_iter().next().unwrap())
     }
 
+    /// Strategies, which the query planner chooses for the request in each segment
+    ///
+    /// Only shards of this peer are planned, remote replicas are not requested.
+    pub async fn search_plans(
+        &self,
+        request: &CoreSearchRequest,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<Vec<SegmentSearchPlan>> {
+        let shard_holder = self.shards_holder.read().await;
+        let mut plans = Vec::new();
+        for (replica_set, _) in shard_holder.select_shards(shard_selection)? {
+            let shard_id = replica_set.shard_id;
+            plans.extend(
+                replica_set
+                    .search_plans(request)
+                    .await?
+                    .into_iter()
+                    .map(|plan| SegmentSearchPlan { shard_id, plan }),
+            );
+        }
+        Ok(plans)
+    }
+
     pub async fn core_search_batch(
         &self,
         request: CoreSearchRequestBatch,
@@ -154,8 +182,119 @@ impl Collection {
             future::try_join_all(all_searches).await?
         };
 
-        self.merge_from_shards(all_searches_res, request, !shard_selection.is_shard_id())
-            .await
+        let is_client_request = !shard_selection.is_shard_id();
+        let mut results = self
+            .merge_from_shards(all_searches_res, Arc::clone(&request), is_client_request)
+            .await?;
+
+        if is_client_request {
+            for (result, search) in results.iter_mut().zip(request.searches.iter()) {
+                if let Some(tie_breaker) = &search.tie_breaker {
+                    *result = self
+                        .break_ties(
+                            mem::take(result),
+                            search,
+                            tie_breaker,
+                            read_consistency,
+                            shard_selection,
+                        )
+                        .await?;
+                }
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Order points with equal scores by the payload field of the `tie_breaker`
+    ///
+    /// Takes all candidates from the shards, ordered by score, and cuts them to the requested
+    /// page after the ties are resolved.
+    async fn break_ties(
+        &self,
+        mut points: Vec<ScoredPoint>,
+        request: &CoreSearchRequest,
+        tie_breaker: &TieBreaker,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let key = &tie_breaker.key;
+        let field_type = self
+            .payload_index_schema
+            .read()
+            .schema
+            .get(key)
+            .map(|field_schema| field_schema.kind());
+        if tie_breaker.distance_from.is_some() {
+            if field_type != Some(PayloadSchemaType::Geo) {
+                return Err(CollectionError::bad_input(format!(
+                    "Tie breaker by distance requires geo index on field `{key}`"
+                )));
+            }
+        } else if !matches!(
+            field_type,
+            Some(
+                PayloadSchemaType::Integer | PayloadSchemaType::Float | PayloadSchemaType::Datetime
+            )
+        ) {
+            return Err(CollectionError::bad_input(format!(
+                "Tie breaker requires integer, float or datetime index on field `{key}`"
+            )));
+        }
+
+        // Points after the page can only get into it if they have the same score as the last one
+        let page_end = request.limit + request.offset;
+        let last_score = page_end
+            .checked_sub(1)
+            .and_then(|last| points.get(last))
+            .map(|point| point.score);
+        if let Some(last_score) = last_score {
+            let ties_count = points[page_end..]
+                .iter()
+                .take_while(|point| point.score == last_score)
+                .count();
+            points.truncate(page_end + ties_count);
+        }
+
+        let retrieve_request = PointRequestInternal {
+            ids: points.iter().map(|point| point.id).collect(),
+            with_payload: Some(WithPayloadInterface::Fields(vec![key.clone()])),
+            with_vector: WithVector::Bool(false),
+        };
+        let order_values: HashMap<ExtendedPointId, OrderValue> = self
+            .retrieve(retrieve_request, read_consistency, shard_selection)
+            .await?
+            .into_iter()
+            .filter_map(|record| {
+                let payload = record.payload?;
+                let order_value = tie_breaker.order_value(payload.get_value(key))?;
+                Some((record.id, order_value))
+            })
+            .collect();
+
+        // Points are already ordered by score, so only runs of equal scores are sorted
+        let mut start = 0;
+        while start < points.len() {
+            let score = points[start].score;
+            let end = start
+                + points[start..]
+                    .iter()
+                    .take_while(|point| point.score == score)
+                    .count();
+            points[start..end].sort_by(|point, other| {
+                tie_breaker.cmp_values(
+                    order_values.get(&point.id).copied(),
+                    order_values.get(&other.id).copied(),
+                )
+            });
+            start = end;
+        }
+
+        Ok(points
+            .into_iter()
+            .skip(request.offset)
+            .take(request.limit)
+            .collect())
     }
 
     pub(crate) async fn fill_search_result_with_payload(
@@ -240,17 +379,21 @@ impl Collection {
                     | QueryEnum::RecommendBestScore(_) => Order::LargeBetter,
                 };
 
+                // Ties are resolved later, so all candidates are kept for them
+                let break_ties = is_client_request && request.tie_breaker.is_some();
+                let top = if break_ties {
+                    res.len()
+                } else {
+                    request.limit + request.offset
+                };
+
                 let mut top_res = match order {
-                    Order::LargeBetter => {
-                        tools::peek_top_largest_iterable(res, request.limit + request.offset)
-                    }
-                    Order::SmallBetter => {
-                        tools::peek_top_smallest_iterable(res, request.limit + request.offset)
-                    }
+                    Order::LargeBetter => tools::peek_top_largest_iterable(res, top),
+                    Order::SmallBetter => tools::peek_top_smallest_iterable(res, top),
                 };
                 // Remove `offset` from top result only for client requests
                 // to avoid applying `offset` twice in distributed mode.
-                if is_client_request && request.offset > 0 {
+                if is_client_request && !break_ties && request.offset > 0 {
                     if top_res.len() >= request.offset {
                         // Panics if the end point > length of the vector.
                         top_res.drain(..request.offset);
@@ -9,7 +9,7 @@ use tokio::fs;
 use super::Collection;
 use crate::collection::CollectionVersion;
 use crate::config::{CollectionConfig, ShardingMethod};
-use crate::operations::snapshot_ops::{self, SnapshotDescription};
+use crate::operations::snapshot_ops::{self, SegmentDescription, SnapshotDescription};
 use crate::operations::types::{CollectionError, CollectionResult, NodeType};
 use crate::shards::local_shard::LocalShard;
 use crate::shards::remote_shard::RemoteShard;
@@ -328,6 +328,50 @@ impl Collection {
             .await
     }
 
+    pub async fn list_shard_segments(
+        &self,
+        shard_id: ShardId,
+    ) -> CollectionResult<Vec<SegmentDescription>> {
+        self.shards_holder
+            .read()
+            .await
+            .list_shard_segments(shard_id)
+            .await
+    }
+
+    pub async fn create_segment_snapshot(
+        &self,
+        shard_id: ShardId,
+        segment_id: &str,
+        temp_dir: &Path,
+    ) -> CollectionResult<SnapshotDescription> {
+        self.shards_holder
+            .read()
+            .await
+            .create_segment_snapshot(
+                &self.snapshots_path,
+                &self.name(),
+                shard_id,
+                segment_id,
+                temp_dir,
+            )
+            .await
+    }
+
+    pub async fn restore_segment_snapshot(
+        &self,
+        shard_id: ShardId,
+        segment_id: &str,
+        snapshot_path: &Path,
+        force: bool,
+    ) -> CollectionResult<()> {
+        self.shards_holder
+            .read()
+            .await
+            .restore_segment_snapshot(snapshot_path, shard_id, segment_id, force)
+            .await
+    }
+
     pub async fn assert_shard_exists(&self, shard_id: ShardId) -> CollectionResult<()> {
         self.shards_holder
             .read()
@@ -26,6 +26,7 @@ use crate::collection_manager::optimizers::segment_optimizer::{
 };
 use crate::config::CollectionParams;
 use crate::operations::types::{VectorParams, VectorsConfig};
+use crate::optimizers_builder::MergePolicy;
 
 pub fn empty_segment(path: &Path) -> Segment {
     build_simple_segment(path, 4, Distance::Dot).unwrap()
@@ -204,6 +205,7 @@ pub(crate) fn get_merge_optimizer(
 ) -> MergeOptimizer {
     MergeOptimizer::new(
         5,
+        MergePolicy::default(),
         OptimizerThresholds {
             max_segment_size: 100_000,
             memmap_threshold: 1000000,
@@ -218,6 +220,11 @@ pub(crate) fn get_merge_optimizer(
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: None,
+                index: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
             }),
             ..CollectionParams::empty()
         },
@@ -246,6 +253,11 @@ pub(crate) fn get_indexing_optimizer(
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: None,
+                index: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
             }),
             ..CollectionParams::empty()
         },
@@ -6,10 +6,13 @@ use std::sync::Arc;
 
 use parking_lot::{RwLock, RwLockUpgradableReadGuard};
 use segment::common::operation_error::{OperationResult, SegmentFailedState};
+use segment::data_types::facets::{merge_facet_counts, FacetValue};
 use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::order_by::{OrderBy, OrderValue};
 use segment::data_types::vectors::{QueryVector, Vector};
 use segment::entry::entry_point::SegmentEntry;
 use segment::index::field_index::CardinalityEstimation;
+use segment::index::query_planner::SearchPlan;
 use segment::telemetry::SegmentTelemetry;
 use segment::types::{
     Condition, Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
@@ -296,6 +299,20 @@ impl SegmentEntry for ProxySegment {
         Ok(wrapped_results)
     }
 
+    fn search_plan(
+        &self,
+        vector_name: &str,
+        filter: Option<&Filter>,
+        params: Option<&SearchParams>,
+    ) -> OperationResult<Option<SearchPlan>> {
+        // Write segment is small and always searched without index,
+        // only the search in the wrapped segment follows a plan
+        self.wrapped_segment
+            .get()
+            .read()
+            .search_plan(vector_name, filter, params)
+    }
+
     fn upsert_point(
         &mut self,
         op_num: SeqNumberType,
@@ -506,6 +523,44 @@ impl SegmentEntry for ProxySegment {
         read_points
     }
 
+    fn read_ordered_filtered<'a>(
+        &'a self,
+        limit: Option<usize>,
+        filter: Option<&'a Filter>,
+        order_by: &'a OrderBy,
+    ) -> OperationResult<Vec<(OrderValue, PointIdType)>> {
+        let deleted_points = self.deleted_points.read();
+        let mut read_points = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .read_ordered_filtered(limit, filter, order_by)?
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment.get().read().read_ordered_filtered(
+                limit,
+                Some(&wrapped_filter),
+                order_by,
+            )?
+        };
+        let mut write_segment_points = self
+            .write_segment
+            .get()
+            .read()
+            .read_ordered_filtered(limit, filter, order_by)?;
+        read_points.append(&mut write_segment_points);
+        read_points.sort_unstable_by(|(value, id), (other_value, other_id)| {
+            order_by
+                .cmp_values(*value, *other_value)
+                .then_with(|| id.cmp(other_id))
+        });
+        if let Some(limit) = limit {
+            read_points.truncate(limit);
+        }
+        Ok(read_points)
+    }
+
     /// Read points in [from; to) range
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType> {
         let deleted_points = self.deleted_points.read();
@@ -577,6 +632,37 @@ impl SegmentEntry for ProxySegment {
         }
     }
 
+    fn facet(
+        &self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HashMap<FacetValue, usize>> {
+        let deleted_points = self.deleted_points.read();
+        let wrapped_counts = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .facet(key, filter, is_stopped)?
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment
+                .get()
+                .read()
+                .facet(key, Some(&wrapped_filter), is_stopped)?
+        };
+        let write_counts = self
+            .write_segment
+            .get()
+            .read()
+            .facet(key, filter, is_stopped)?;
+
+        Ok(merge_facet_counts(
+            wrapped_counts.into_iter().chain(write_counts),
+        ))
+    }
+
     fn segment_type(&self) -> SegmentType {
         SegmentType::Special
     }
@@ -763,6 +849,20 @@ impl SegmentEntry for ProxySegment {
         self.write_segment.get().read().vector_dims()
     }
 
+    fn available_vectors_size_in_bytes(&self, vector_name: &str) -> OperationResult<usize> {
+        let wrapped_size = self
+            .wrapped_segment
+            .get()
+            .read()
+            .available_vectors_size_in_bytes(vector_name)?;
+        let write_size = self
+            .write_segment
+            .get()
+            .read()
+            .available_vectors_size_in_bytes(vector_name)?;
+        Ok(wrapped_size + write_size)
+    }
+
     fn take_snapshot(
         &self,
         temp_path: &Path,
@@ -1331,6 +1431,10 @@ mod tests {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
+                        keep_original: None,
+                        dimension_weights: None,
                     },
                 ),
                 (
@@ -1341,11 +1445,16 @@ mod tests {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
+                        keep_original: None,
+                        dimension_weights: None,
                     },
                 ),
             ]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
         let mut original_segment = build_segment(dir.path(), &config, true).unwrap();
         let write_segment = build_segment(dir.path(), &config, true).unwrap();
@@ -85,6 +85,15 @@ impl LockedSegment {
         }
     }
 
+    /// Name of the segment directory, which identifies the segment within the shard
+    pub fn name(&self) -> String {
+        let data_path = self.get().read().data_path();
+        data_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
     /// Consume the LockedSegment and drop the underlying segment data.
     /// Operation fails if the segment is used by other thread for longer than `timeout`.
     pub fn drop_data(self) -> OperationResult<()> {
@@ -233,6 +242,14 @@ impl<'s> SegmentHolder {
         self.segments.get(&id)
     }
 
+    /// Find segment by the name of its directory
+    pub fn get_by_name(&self, name: &str) -> Option<(SegmentId, &LockedSegment)> {
+        self.segments
+            .iter()
+            .find(|(_, segment)| segment.name() == name)
+            .map(|(id, segment)| (*id, segment))
+    }
+
     pub fn appendable_segments(&self) -> Vec<SegmentId> {
         self.segments
             .iter()
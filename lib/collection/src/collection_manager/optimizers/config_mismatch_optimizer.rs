@@ -16,6 +16,7 @@ use crate::collection_manager::optimizers::segment_optimizer::{
 };
 use crate::config::CollectionParams;
 use crate::operations::config_diff::DiffConfig;
+use crate::operations::types::VectorIndexParams;
 
 /// Looks for segments having a mismatch between configured and actual parameters
 ///
@@ -82,7 +83,7 @@ impl ConfigMismatchOptimizer {
             .collection_params
             .vectors
             .get_params(vector_name)
-            .and_then(|vector_params| vector_params.hnsw_config)
+            .and_then(|vector_params| vector_params.hnsw_config.clone())
             .map(|vector_hnsw| vector_hnsw.update(target_hnsw_collection))
             .and_then(|hnsw| match hnsw {
                 Ok(hnsw) => Some(hnsw),
@@ -99,6 +100,16 @@ impl ConfigMismatchOptimizer {
         }
     }
 
+    /// Target index params, if the vector is configured to use another index instead of HNSW
+    fn get_required_index_params(&self, vector_name: &str) -> Option<&VectorIndexParams> {
+        self.collection_params
+            .vectors
+            .get_params(vector_name)?
+            .index
+            .as_ref()
+            .filter(|index| !matches!(index, VectorIndexParams::Hnsw))
+    }
+
     fn worst_segment(
         &self,
         segments: LockedSegmentHolder,
@@ -128,8 +139,8 @@ impl ConfigMismatchOptimizer {
                     return None; // Never optimize already optimized segment
                 }
 
-                if self.collection_params.on_disk_payload
-                    != segment_config.payload_storage_type.is_on_disk()
+                if self.collection_params.payload_storage_type()
+                    != segment_config.payload_storage_type
                 {
                     return Some((*idx, vector_size)); // Skip segments with payload mismatch
                 }
@@ -141,15 +152,34 @@ impl ConfigMismatchOptimizer {
                         .iter()
                         .any(|(vector_name, vector_data)| {
                             // Check HNSW mismatch
-                            match &vector_data.index {
-                                Indexes::Plain {} => {}
-                                Indexes::Hnsw(effective_hnsw) => {
+                            let target_index = self.get_required_index_params(vector_name);
+                            match (&vector_data.index, target_index) {
+                                (Indexes::Plain {}, _) => {}
+                                (Indexes::Hnsw(effective_hnsw), None) => {
                                     // Select segment if we have an HNSW mismatch that requires rebuild
                                     let target_hnsw = self.get_required_hnsw_config(vector_name);
                                     if effective_hnsw.mismatch_requires_rebuild(&target_hnsw) {
                                         return true;
                                     }
                                 }
+                                (
+                                    Indexes::Vamana(effective_vamana),
+                                    Some(VectorIndexParams::Vamana(target_vamana)),
+                                ) => {
+                                    if effective_vamana.mismatch_requires_rebuild(target_vamana) {
+                                        return true;
+                                    }
+                                }
+                                (
+                                    Indexes::Ivf(effective_ivf),
+                                    Some(VectorIndexParams::Ivf(target_ivf)),
+                                ) => {
+                                    if effective_ivf.mismatch_requires_rebuild(target_ivf) {
+                                        return true;
+                                    }
+                                }
+                                // Select segment if the type of index was changed
+                                _ => return true,
                             }
 
                             if let Some(is_required_on_disk) =
@@ -316,6 +346,11 @@ mod tests {
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: None,
+                index: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
             }),
             ..CollectionParams::empty()
         };
@@ -337,6 +372,16 @@ mod tests {
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            payload_m_overrides: None,
+            seed: None,
+            extend_candidates: None,
+            keep_pruned_connections: None,
+            compress_links: None,
+            target_recall: None,
+            level_factor_multiplier: None,
+            ef_construct_levels: None,
+            refine: None,
+            appendable: None,
         };
 
         // Optimizers used in test
@@ -445,9 +490,14 @@ mod tests {
                     VectorParams {
                         size: vector1_dim.try_into().unwrap(),
                         distance: Distance::Dot,
-                        hnsw_config: Some(hnsw_config_vector1),
+                        hnsw_config: Some(hnsw_config_vector1.clone()),
                         quantization_config: None,
                         on_disk: None,
+                        index: None,
+                        multivector_config: None,
+                        datatype: None,
+                        keep_original: None,
+                        dimension_weights: None,
                     },
                 ),
                 (
@@ -458,6 +508,11 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: None,
                         on_disk: None,
+                        index: None,
+                        multivector_config: None,
+                        datatype: None,
+                        keep_original: None,
+                        dimension_weights: None,
                     },
                 ),
             ])),
@@ -487,6 +542,16 @@ mod tests {
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            payload_m_overrides: None,
+            seed: None,
+            extend_candidates: None,
+            keep_pruned_connections: None,
+            compress_links: None,
+            target_recall: None,
+            level_factor_multiplier: None,
+            ef_construct_levels: None,
+            refine: None,
+            appendable: None,
         };
 
         // Optimizers used in test
@@ -524,7 +589,7 @@ mod tests {
         assert_eq!(suggested_to_optimize.len(), 0);
 
         // Create changed HNSW config for vector2, update it in the optimizer
-        let mut hnsw_config_vector2 = hnsw_config_vector1;
+        let mut hnsw_config_vector2 = hnsw_config_vector1.clone();
         hnsw_config_vector2.m = hnsw_config_vector1.m.map(|m| m / 2);
         hnsw_config_vector2.ef_construct = None;
         match config_mismatch_optimizer.collection_params.vectors {
@@ -533,7 +598,7 @@ mod tests {
                 map.get_mut("vector2")
                     .unwrap()
                     .hnsw_config
-                    .replace(hnsw_config_vector2);
+                    .replace(hnsw_config_vector2.clone());
             }
         }
 
@@ -558,12 +623,22 @@ mod tests {
             .for_each(|segment| {
                 assert_eq!(
                     segment.config().vector_data["vector1"].index,
-                    Indexes::Hnsw(hnsw_config_vector1.update(&hnsw_config_collection).unwrap()),
+                    Indexes::Hnsw(
+                        hnsw_config_vector1
+                            .clone()
+                            .update(&hnsw_config_collection)
+                            .unwrap()
+                    ),
                     "HNSW config of vector1 is not what we expect",
                 );
                 assert_eq!(
                     segment.config().vector_data["vector2"].index,
-                    Indexes::Hnsw(hnsw_config_vector2.update(&hnsw_config_collection).unwrap()),
+                    Indexes::Hnsw(
+                        hnsw_config_vector2
+                            .clone()
+                            .update(&hnsw_config_collection)
+                            .unwrap()
+                    ),
                     "HNSW config of vector2 is not what we expect",
                 );
             });
@@ -613,6 +688,11 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: Some(quantization_config_vector1.clone()),
                         on_disk: None,
+                        index: None,
+                        multivector_config: None,
+                        datatype: None,
+                        keep_original: None,
+                        dimension_weights: None,
                     },
                 ),
                 (
@@ -623,6 +703,11 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: None,
                         on_disk: None,
+                        index: None,
+                        multivector_config: None,
+                        datatype: None,
+                        keep_original: None,
+                        dimension_weights: None,
                     },
                 ),
             ])),
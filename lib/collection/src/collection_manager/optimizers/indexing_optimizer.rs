@@ -349,6 +349,11 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: None,
                         on_disk: None,
+                        index: None,
+                        multivector_config: None,
+                        datatype: None,
+                        keep_original: None,
+                        dimension_weights: None,
                     },
                 )
             })
@@ -464,6 +469,11 @@ mod tests {
                     hnsw_config: None,
                     quantization_config: None,
                     on_disk: None,
+                    index: None,
+                    multivector_config: None,
+                    datatype: None,
+                    keep_original: None,
+                    dimension_weights: None,
                 }),
                 ..CollectionParams::empty()
             },
@@ -720,6 +730,11 @@ mod tests {
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: Some(false),
+                index: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
             }),
             ..CollectionParams::empty()
         };
@@ -741,6 +756,16 @@ mod tests {
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            payload_m_overrides: None,
+            seed: None,
+            extend_candidates: None,
+            keep_pruned_connections: None,
+            compress_links: None,
+            target_recall: None,
+            level_factor_multiplier: None,
+            ef_construct_levels: None,
+            refine: None,
+            appendable: None,
         };
 
         {
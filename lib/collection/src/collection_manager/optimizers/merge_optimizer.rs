@@ -16,15 +16,23 @@ use crate::collection_manager::optimizers::segment_optimizer::{
     OptimizerThresholds, SegmentOptimizer,
 };
 use crate::config::CollectionParams;
+use crate::optimizers_builder::MergePolicy;
 
 const BYTES_IN_KB: usize = 1024;
 
-/// Optimizer that tries to reduce number of segments until it fits configured value.
-/// It merges 3 smallest segments into a single large segment.
+/// Sizes of segments in the same tier differ less than this number of times
+const SIZE_TIER_FACTOR: usize = 4;
+
+/// Minimal number of segments to merge.
 /// Merging 3 segments instead of 2 guarantees that after the optimization the number of segments
 /// will be less than before.
+const MIN_SEGMENTS_TO_MERGE: usize = 3;
+
+/// Optimizer that tries to reduce number of segments until it fits configured value.
+/// It merges at least 3 segments into a single large segment, segments are selected by `MergePolicy`.
 pub struct MergeOptimizer {
     max_segments: usize,
+    merge_policy: MergePolicy,
     thresholds_config: OptimizerThresholds,
     segments_path: PathBuf,
     collection_temp_dir: PathBuf,
@@ -38,6 +46,7 @@ impl MergeOptimizer {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         max_segments: usize,
+        merge_policy: MergePolicy,
         thresholds_config: OptimizerThresholds,
         segments_path: PathBuf,
         collection_temp_dir: PathBuf,
@@ -47,6 +56,7 @@ impl MergeOptimizer {
     ) -> Self {
         MergeOptimizer {
             max_segments,
+            merge_policy,
             thresholds_config,
             segments_path,
             collection_temp_dir,
@@ -56,6 +66,30 @@ impl MergeOptimizer {
             telemetry_durations_aggregator: OperationDurationsAggregator::new(),
         }
     }
+
+    /// Select the smallest of the given segments, as long as their total size is below `max_size`
+    ///
+    /// * `segments` - segment ids with sizes in bytes, sorted by size
+    fn select_smallest<'a>(
+        segments: impl Iterator<Item = &'a (SegmentId, usize)>,
+        max_size: usize,
+        max_candidates: usize,
+    ) -> Vec<SegmentId> {
+        segments
+            .scan(0, |size_sum, (sid, size)| {
+                *size_sum += size; // produce a cumulative sum of segment sizes starting from smallest
+                Some((*sid, *size_sum))
+            })
+            .take_while(|(_, size)| *size < max_size)
+            .take(max_candidates)
+            .map(|x| x.0)
+            .collect()
+    }
+}
+
+/// Size tier of a segment, sizes of segments in the same tier differ less than `SIZE_TIER_FACTOR` times
+fn size_tier(size: usize) -> u32 {
+    (size / BYTES_IN_KB).max(1).ilog(SIZE_TIER_FACTOR)
 }
 
 impl SegmentOptimizer for MergeOptimizer {
@@ -104,12 +138,13 @@ impl SegmentOptimizer for MergeOptimizer {
         if raw_segments.len() <= self.max_segments {
             return vec![];
         }
-        let max_candidates = raw_segments.len() - self.max_segments + 2;
-
-        // Find at least top-3 smallest segments to join.
-        // We need 3 segments because in this case we can guarantee that total segments number will be less
+        let max_candidates = raw_segments.len() - self.max_segments + MIN_SEGMENTS_TO_MERGE - 1;
+        let max_size = self
+            .thresholds_config
+            .max_segment_size
+            .saturating_mul(BYTES_IN_KB);
 
-        let candidates: Vec<_> = raw_segments
+        let segment_sizes: Vec<_> = raw_segments
             .iter()
             .cloned()
             .filter_map(|(idx, segment)| {
@@ -128,22 +163,35 @@ impl SegmentOptimizer for MergeOptimizer {
                 ))
             })
             .sorted_by_key(|(_, size)| *size)
-            .scan(0, |size_sum, (sid, size)| {
-                *size_sum += size; // produce a cumulative sum of segment sizes starting from smallest
-                Some((sid, *size_sum))
-            })
-            .take_while(|(_, size)| {
-                *size
-                    < self
-                        .thresholds_config
-                        .max_segment_size
-                        .saturating_mul(BYTES_IN_KB)
-            })
-            .take(max_candidates)
-            .map(|x| x.0)
             .collect();
 
-        if candidates.len() < 3 {
+        // Find at least top-3 smallest segments to join.
+        // We need 3 segments because in this case we can guarantee that total segments number will be less
+        let candidates = match self.merge_policy {
+            MergePolicy::MaxSegmentSizeBound => {
+                Self::select_smallest(segment_sizes.iter(), max_size, max_candidates)
+            }
+            MergePolicy::FewestSegments => segment_sizes
+                .iter()
+                .take(max_candidates)
+                .map(|(sid, _)| *sid)
+                .collect(),
+            // Smallest tier, which has enough segments to merge
+            MergePolicy::SizeTiered => segment_sizes
+                .iter()
+                .map(|(_, size)| size_tier(*size))
+                .dedup()
+                .map(|tier| {
+                    let tier_segments = segment_sizes
+                        .iter()
+                        .filter(|(_, size)| size_tier(*size) == tier);
+                    Self::select_smallest(tier_segments, max_size, max_candidates)
+                })
+                .find(|candidates| candidates.len() >= MIN_SEGMENTS_TO_MERGE)
+                .unwrap_or_default(),
+        };
+
+        if candidates.len() < MIN_SEGMENTS_TO_MERGE {
             return vec![];
         }
         log::debug!("Merge candidates: {:?}", candidates);
@@ -205,6 +253,52 @@ mod tests {
         assert_eq!(check_result.len(), 3);
     }
 
+    #[test]
+    fn test_merge_policies() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+        let mut holder = SegmentHolder::default();
+        let dim = 256;
+
+        let small_segments = [
+            holder.add(random_segment(dir.path(), 100, 3, dim)),
+            holder.add(random_segment(dir.path(), 100, 10, dim)),
+        ];
+        let similar_segments = [
+            holder.add(random_segment(dir.path(), 100, 20, dim)),
+            holder.add(random_segment(dir.path(), 100, 20, dim)),
+            holder.add(random_segment(dir.path(), 100, 20, dim)),
+        ];
+        let large_segment = holder.add(random_segment(dir.path(), 100, 100, dim));
+
+        let locked_holder = Arc::new(RwLock::new(holder));
+
+        let mut merge_optimizer = get_merge_optimizer(dir.path(), temp_dir.path(), dim);
+        merge_optimizer.max_segments = 3;
+        merge_optimizer.thresholds_config.max_segment_size = 70;
+
+        let check_policy = |merge_optimizer: &mut MergeOptimizer, merge_policy| {
+            merge_optimizer.merge_policy = merge_policy;
+            merge_optimizer.check_condition(locked_holder.clone(), &Default::default())
+        };
+
+        // Smallest segments, which fit into the max segment size together
+        let candidates = check_policy(&mut merge_optimizer, MergePolicy::MaxSegmentSizeBound);
+        assert_eq!(candidates.len(), 4);
+        assert!(small_segments.iter().all(|sid| candidates.contains(sid)));
+
+        // Smallest segments, regardless of the max segment size
+        let candidates = check_policy(&mut merge_optimizer, MergePolicy::FewestSegments);
+        assert_eq!(candidates.len(), 5);
+        assert!(!candidates.contains(&large_segment));
+
+        // Small segments are of different tiers, so only similar ones are merged
+        let candidates = check_policy(&mut merge_optimizer, MergePolicy::SizeTiered);
+        assert_eq!(candidates.len(), 3);
+        assert!(similar_segments.iter().all(|sid| candidates.contains(sid)));
+    }
+
     #[test]
     fn test_merge_optimizer() {
         let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
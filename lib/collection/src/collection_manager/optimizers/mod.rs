@@ -4,6 +4,7 @@ use std::sync::Arc;
 use chrono::{DateTime, Utc};
 use parking_lot::Mutex;
 use schemars::JsonSchema;
+use segment::common::build_progress::BuildProgress;
 use serde::{Deserialize, Serialize};
 
 use super::holders::segment_holder::SegmentId;
@@ -79,6 +80,8 @@ pub struct Tracker {
     pub start_at: DateTime<Utc>,
     /// Latest state of the optimizer
     pub state: Arc<Mutex<TrackerState>>,
+    /// Progress of the index build of the optimizer
+    pub progress: BuildProgress,
 }
 
 impl Tracker {
@@ -88,6 +91,7 @@ impl Tracker {
             name: name.into(),
             segment_ids,
             state: Default::default(),
+            progress: Default::default(),
             start_at: Utc::now(),
         }
     }
@@ -106,6 +110,10 @@ impl Tracker {
             status: state.status.clone(),
             start_at: self.start_at,
             end_at: state.end_at,
+            progress: self
+                .progress
+                .is_started()
+                .then(|| TrackerProgress::from(&self.progress)),
         }
     }
 }
@@ -123,6 +131,30 @@ pub struct TrackerTelemetry {
     pub start_at: DateTime<Utc>,
     /// End time of the optimizer
     pub end_at: Option<DateTime<Utc>>,
+    /// Progress of the index build, if the optimizer builds an index
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<TrackerProgress>,
+}
+
+/// Progress of the index build, used in telemetry
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, PartialEq)]
+pub struct TrackerProgress {
+    /// Number of points linked into the index so far
+    pub points_done: usize,
+    /// Total number of points to link into the index
+    pub points_total: usize,
+    /// Estimated time left until the index is built, in seconds
+    pub eta_sec: Option<f64>,
+}
+
+impl From<&BuildProgress> for TrackerProgress {
+    fn from(progress: &BuildProgress) -> Self {
+        Self {
+            points_done: progress.done(),
+            points_total: progress.total(),
+            eta_sec: progress.eta().map(|eta| eta.as_secs_f64()),
+        }
+    }
 }
 
 /// Handle to an optimizer tracker, allows updating its state
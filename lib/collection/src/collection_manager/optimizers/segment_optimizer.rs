@@ -1,32 +1,36 @@
 use std::collections::{HashMap, HashSet};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use itertools::Itertools;
 use parking_lot::{Mutex, RwLock, RwLockUpgradableReadGuard};
+use segment::common::build_progress::BuildProgress;
 use segment::common::operation_error::check_process_stopped;
 use segment::common::operation_time_statistics::{
     OperationDurationStatistics, OperationDurationsAggregator, ScopeDurationMeasurer,
 };
 use segment::common::version::StorageVersion;
 use segment::entry::entry_point::SegmentEntry;
+use segment::index::hnsw_index::max_rayon_threads;
 use segment::index::sparse_index::sparse_index_config::SparseIndexType;
 use segment::segment::{Segment, SegmentVersion};
 use segment::segment_constructor::build_segment;
 use segment::segment_constructor::segment_builder::SegmentBuilder;
 use segment::types::{
-    HnswConfig, Indexes, PayloadFieldSchema, PayloadKeyType, PayloadStorageType, PointIdType,
-    QuantizationConfig, SegmentConfig, VectorStorageType, VECTOR_ELEMENT_SIZE,
+    HnswConfig, Indexes, PayloadFieldSchema, PayloadKeyType, PointIdType, QuantizationConfig,
+    SegmentConfig, VectorStorageType, VECTOR_ELEMENT_SIZE,
 };
 
 use crate::collection_manager::holders::proxy_segment::ProxySegment;
 use crate::collection_manager::holders::segment_holder::{
     LockedSegment, LockedSegmentHolder, SegmentId,
 };
+use crate::common::optimization_budget::OptimizationResources;
 use crate::config::CollectionParams;
 use crate::operations::config_diff::DiffConfig;
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::types::{CollectionError, CollectionResult, VectorIndexParams};
+use crate::optimizers_builder::BUILD_CHECKPOINTS_PATH;
 
 const BYTES_IN_KB: usize = 1024;
 
@@ -55,6 +59,11 @@ pub trait SegmentOptimizer {
     /// Get temp path, where optimized segments could be temporary stored
     fn temp_path(&self) -> &Path;
 
+    /// Get path, where checkpoints of vector index builds are kept over restarts
+    fn build_checkpoints_path(&self) -> PathBuf {
+        self.temp_path().join(BUILD_CHECKPOINTS_PATH)
+    }
+
     /// Get basic segment config
     fn collection_params(&self) -> CollectionParams;
 
@@ -78,17 +87,48 @@ pub trait SegmentOptimizer {
 
     fn get_telemetry_counter(&self) -> Arc<Mutex<OperationDurationsAggregator>>;
 
+    /// Estimate resources, required to optimize the given segments
+    ///
+    /// Memory is estimated by the size of vectors, which are copied into the optimized segment,
+    /// as they are stored by the vector storages of the given segments.
+    /// Threads are estimated by the number of threads, used for index building.
+    fn estimate_resources(
+        &self,
+        segments: &LockedSegmentHolder,
+        ids: &[SegmentId],
+    ) -> OptimizationResources {
+        let segments = segments.read();
+        let memory_bytes = ids
+            .iter()
+            .filter_map(|id| segments.get(*id))
+            .map(|segment| {
+                let segment = segment.get();
+                let read_segment = segment.read();
+                read_segment
+                    .vector_dims()
+                    .into_keys()
+                    .map(|vector_name| {
+                        read_segment
+                            .available_vectors_size_in_bytes(&vector_name)
+                            .unwrap_or(0)
+                    })
+                    .sum::<usize>()
+            })
+            .sum();
+        OptimizationResources {
+            threads: max_rayon_threads(self.hnsw_config().max_indexing_threads),
+            memory_bytes,
+        }
+    }
+
     /// Build temp segment
     fn temp_segment(&self, save_version: bool) -> CollectionResult<LockedSegment> {
         let collection_params = self.collection_params();
         let config = SegmentConfig {
             vector_data: collection_params.into_base_vector_data()?,
             sparse_vector_data: collection_params.into_sparse_vector_data()?,
-            payload_storage_type: if collection_params.on_disk_payload {
-                PayloadStorageType::OnDisk
-            } else {
-                PayloadStorageType::InMemory
-            },
+            payload_storage_type: collection_params.payload_storage_type(),
+            payload_compression: collection_params.payload_compression,
         };
         Ok(LockedSegment::new(build_segment(
             self.collection_path(),
@@ -163,15 +203,29 @@ pub trait SegmentOptimizer {
             let collection_hnsw = self.hnsw_config();
             let collection_quantization = self.quantization_config();
             vector_data.iter_mut().for_each(|(vector_name, config)| {
-                // Assign HNSW index
-                let param_hnsw = collection_params
+                let param_index = collection_params
                     .vectors
                     .get_params(vector_name)
-                    .and_then(|params| params.hnsw_config);
-                let vector_hnsw = param_hnsw
-                    .and_then(|c| c.update(collection_hnsw).ok())
-                    .unwrap_or_else(|| collection_hnsw.clone());
-                config.index = Indexes::Hnsw(vector_hnsw);
+                    .and_then(|params| params.index.clone());
+                config.index = match param_index {
+                    // Assign Vamana index
+                    Some(VectorIndexParams::Vamana(vector_vamana)) => {
+                        Indexes::Vamana(vector_vamana)
+                    }
+                    // Assign IVF index
+                    Some(VectorIndexParams::Ivf(vector_ivf)) => Indexes::Ivf(vector_ivf),
+                    // Assign HNSW index
+                    Some(VectorIndexParams::Hnsw) | None => {
+                        let param_hnsw = collection_params
+                            .vectors
+                            .get_params(vector_name)
+                            .and_then(|params| params.hnsw_config.clone());
+                        let vector_hnsw = param_hnsw
+                            .and_then(|c| c.update(collection_hnsw).ok())
+                            .unwrap_or_else(|| collection_hnsw.clone());
+                        Indexes::Hnsw(vector_hnsw)
+                    }
+                };
 
                 // Assign quantization config
                 let param_quantization = collection_params
@@ -238,11 +292,8 @@ pub trait SegmentOptimizer {
         let optimized_config = SegmentConfig {
             vector_data,
             sparse_vector_data,
-            payload_storage_type: if collection_params.on_disk_payload {
-                PayloadStorageType::OnDisk
-            } else {
-                PayloadStorageType::InMemory
-            },
+            payload_storage_type: collection_params.payload_storage_type(),
+            payload_compression: collection_params.payload_compression,
         };
 
         Ok(SegmentBuilder::new(
@@ -337,6 +388,7 @@ pub trait SegmentOptimizer {
     /// * `proxy_deleted_points` - Holds a set of points, deleted while optimization was running
     /// * `proxy_deleted_indexes` - Holds a set of Indexes, deleted while optimization was running
     /// * `proxy_created_indexes` - Holds a set of Indexes, created while optimization was running
+    /// * `progress` - progress of the index build
     /// * `stopped` - flag to check if optimization was cancelled by external thread
     ///
     /// # Result
@@ -348,9 +400,12 @@ pub trait SegmentOptimizer {
         proxy_deleted_points: Arc<RwLock<HashSet<PointIdType>>>,
         proxy_deleted_indexes: Arc<RwLock<HashSet<PayloadKeyType>>>,
         proxy_created_indexes: Arc<RwLock<HashMap<PayloadKeyType, PayloadFieldSchema>>>,
+        progress: &BuildProgress,
         stopped: &AtomicBool,
     ) -> CollectionResult<Segment> {
         let mut segment_builder = self.optimized_segment_builder(optimizing_segments)?;
+        segment_builder.progress = progress.clone();
+        segment_builder.build_checkpoint_dir = Some(self.build_checkpoints_path());
 
         self.check_cancellation(stopped)?;
 
@@ -430,6 +485,17 @@ pub trait SegmentOptimizer {
         segments: LockedSegmentHolder,
         ids: Vec<SegmentId>,
         stopped: &AtomicBool,
+    ) -> CollectionResult<bool> {
+        self.optimize_with_progress(segments, ids, &BuildProgress::default(), stopped)
+    }
+
+    /// Same as `optimize`, but reports progress of the index build into `progress`
+    fn optimize_with_progress(
+        &self,
+        segments: LockedSegmentHolder,
+        ids: Vec<SegmentId>,
+        progress: &BuildProgress,
+        stopped: &AtomicBool,
     ) -> CollectionResult<bool> {
         check_process_stopped(stopped)?;
 
@@ -526,6 +592,7 @@ pub trait SegmentOptimizer {
             proxy_deleted_points.clone(),
             proxy_deleted_indexes.clone(),
             proxy_created_indexes.clone(),
+            progress,
             stopped,
         ) {
             Ok(segment) => segment,
@@ -580,7 +647,8 @@ pub trait SegmentOptimizer {
                 )?;
             }
 
-            optimized_segment.prefault_mmap_pages();
+            self.collection_params()
+                .load_mmap_pages(&optimized_segment, true);
 
             let (_, proxies) = write_segments_guard.swap(optimized_segment, &proxy_ids);
 
@@ -332,6 +332,7 @@ impl SegmentsSearcher {
                             }
                         },
                         shard_key: None,
+                        order_value: None,
                     },
                 );
                 point_version.insert(id, version);
@@ -371,6 +372,7 @@ struct BatchSearchParams<'a> {
     pub with_vector: WithVector,
     pub top: usize,
     pub params: Option<&'a SearchParams>,
+    pub score_threshold: Option<ScoreType>,
 }
 
 /// Returns suggested search sampling size for a given number of points and required limit.
@@ -437,14 +439,22 @@ fn search_in_segment(
             .as_ref()
             .unwrap_or(&WithPayloadInterface::Bool(false));
 
+        let search_type = search_query.query.as_ref().into();
+        // Custom scores are not comparable with the distance-based threshold
+        let score_threshold = match search_type {
+            SearchType::Nearest => search_query.score_threshold,
+            SearchType::RecommendBestScore | SearchType::Discover | SearchType::Context => None,
+        };
+
         let params = BatchSearchParams {
-            search_type: search_query.query.as_ref().into(),
+            search_type,
             vector_name: search_query.query.get_vector_name(),
             filter: search_query.filter.as_ref(),
             with_payload: WithPayload::from(with_payload_interface),
             with_vector: search_query.with_vector.clone().unwrap_or_default(),
             top: search_query.limit + search_query.offset,
             params: search_query.params.as_ref(),
+            score_threshold,
         };
 
         let query = search_query.query.clone().into();
@@ -532,6 +542,19 @@ fn execute_batch_search(
         let batch_len = vectors_batch.len();
         return Ok((vec![vec![]; batch_len], vec![false; batch_len]));
     }
+    // Let the index skip points, which would be cut off by the score threshold anyway
+    let params_with_threshold = search_params.score_threshold.and_then(|score_threshold| {
+        let distance = segment_config
+            .vector_data
+            .get(search_params.vector_name)?
+            .distance;
+        Some(SearchParams {
+            score_threshold: Some(distance.preprocess_score_threshold(score_threshold)),
+            ..search_params.params.copied().unwrap_or_default()
+        })
+    });
+    let params = params_with_threshold.as_ref().or(search_params.params);
+
     let vectors_batch = &vectors_batch.iter().collect_vec();
     let res = read_segment.search_batch(
         search_params.vector_name,
@@ -540,7 +563,7 @@ fn execute_batch_search(
         &search_params.with_vector,
         search_params.filter,
         top,
-        search_params.params,
+        params,
         is_stopped,
     )?;
 
@@ -608,7 +631,7 @@ fn get_hnsw_ef_construct(config: &SegmentConfig, vector_name: &str) -> Option<us
         .vector_data
         .get(vector_name)
         .and_then(|config| match &config.index {
-            Indexes::Plain {} => None,
+            Indexes::Plain {} | Indexes::Vamana(_) | Indexes::Ivf(_) => None,
             Indexes::Hnsw(hnsw) => Some(hnsw),
         })
         .map(|hnsw| hnsw.ef_construct)
@@ -673,6 +696,7 @@ mod tests {
             limit: 5,
             score_threshold: None,
             offset: 0,
+            tie_breaker: None,
         };
 
         let batch_request = CoreSearchRequestBatch {
@@ -727,6 +751,7 @@ mod tests {
                 filter: None,
                 params: None,
                 score_threshold: None,
+                tie_breaker: None,
             };
             let req2 = SearchRequestInternal {
                 vector: random_vector(&mut rnd, 4).into(),
@@ -737,6 +762,7 @@ mod tests {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                tie_breaker: None,
             };
 
             let batch_request = CoreSearchRequestBatch {
@@ -4,6 +4,7 @@ use std::collections::{HashMap, HashSet};
 
 use parking_lot::{RwLock, RwLockWriteGuard};
 use segment::common::operation_error::{OperationError, OperationResult};
+use segment::common::utils::is_wildcard_path;
 use segment::data_types::named_vectors::NamedVectors;
 use segment::entry::entry_point::SegmentEntry;
 use segment::types::{
@@ -160,12 +161,26 @@ pub(crate) fn delete_payload(
     points: &[PointIdType],
     keys: &[PayloadKeyType],
 ) -> CollectionResult<usize> {
+    let (wildcard_keys, keys): (Vec<_>, Vec<_>) =
+        keys.iter().partition(|key| is_wildcard_path(key));
+
     let updated_points =
         segments.apply_points_to_appendable(op_num, points, |id, write_segment| {
             let mut res = true;
-            for key in keys {
+            for key in &keys {
                 res &= write_segment.delete_payload(op_num, id, key)?;
             }
+            if !wildcard_keys.is_empty() {
+                let mut payload = write_segment.payload(id)?;
+                let mut is_removed = false;
+                for key in &wildcard_keys {
+                    is_removed |= !payload.remove(key).is_empty();
+                }
+                if is_removed {
+                    // Full payload is assigned to update indexes of all matched keys
+                    res &= write_segment.set_full_payload(op_num, id, &payload)?;
+                }
+            }
             Ok(res)
         })?;
 
@@ -183,6 +198,38 @@ pub(crate) fn delete_payload_by_filter(
     delete_payload(segments, op_num, &affected_points, keys)
 }
 
+pub(crate) fn rename_payload(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    points: &[PointIdType],
+    key: PayloadKeyTypeRef,
+    new_key: PayloadKeyTypeRef,
+) -> CollectionResult<usize> {
+    let updated_points =
+        segments.apply_points_to_appendable(op_num, points, |id, write_segment| {
+            let mut payload = write_segment.payload(id)?;
+            if !payload.rename(key, new_key) {
+                return Ok(false);
+            }
+            // Full payload is assigned to update indexes of both keys
+            write_segment.set_full_payload(op_num, id, &payload)
+        })?;
+
+    check_unprocessed_points(points, &updated_points)?;
+    Ok(updated_points.len())
+}
+
+pub(crate) fn rename_payload_by_filter(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    filter: &Filter,
+    key: PayloadKeyTypeRef,
+    new_key: PayloadKeyTypeRef,
+) -> CollectionResult<usize> {
+    let affected_points = points_by_filter(segments, filter)?;
+    rename_payload(segments, op_num, &affected_points, key, new_key)
+}
+
 pub(crate) fn clear_payload(
     segments: &SegmentHolder,
     op_num: SeqNumberType,
@@ -237,6 +284,24 @@ pub(crate) fn delete_field_index(
         .map_err(Into::into)
 }
 
+/// Drop and build again the index of the field in segments, where the field is indexed.
+/// Schema of the field and vector indexes of segments stay untouched.
+pub(crate) fn rebuild_field_index(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    field_name: PayloadKeyTypeRef,
+) -> CollectionResult<usize> {
+    segments
+        .apply_segments(|write_segment| {
+            let Some(field_schema) = write_segment.get_indexed_fields().remove(field_name) else {
+                return Ok(false);
+            };
+            write_segment.delete_field_index(op_num, field_name)?;
+            write_segment.create_field_index(op_num, field_name, Some(&field_schema))
+        })
+        .map_err(Into::into)
+}
+
 ///
 /// Returns
 /// - Ok(true) if the operation was successful and point replaced existing value
@@ -507,6 +572,17 @@ pub(crate) fn process_payload_operation(
                 })
             }
         }
+        PayloadOps::RenamePayload(rp) => {
+            if let Some(points) = rp.points {
+                rename_payload(&segments.read(), op_num, &points, &rp.key, &rp.new_key)
+            } else if let Some(filter) = rp.filter {
+                rename_payload_by_filter(&segments.read(), op_num, &filter, &rp.key, &rp.new_key)
+            } else {
+                Err(CollectionError::BadRequest {
+                    description: "No points or filter specified".to_string(),
+                })
+            }
+        }
     }
 }
 
@@ -525,6 +601,9 @@ pub(crate) fn process_field_index_operation(
         FieldIndexOperations::DeleteIndex(field_name) => {
             delete_field_index(&segments.read(), op_num, field_name)
         }
+        FieldIndexOperations::RebuildIndex(field_name) => {
+            rebuild_field_index(&segments.read(), op_num, field_name)
+        }
     }
 }
 
@@ -2,6 +2,7 @@ pub mod batching;
 pub mod fetch_vectors;
 pub mod file_utils;
 pub mod is_ready;
+pub mod optimization_budget;
 pub mod retrieve_request_trait;
 pub mod stoppable_task;
 pub mod stoppable_task_async;
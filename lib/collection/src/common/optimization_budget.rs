@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+/// Resources, required by a single optimization
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OptimizationResources {
+    /// Number of threads, used to build indexes of the optimized segment
+    pub threads: usize,
+    /// Size of the data, copied into the optimized segment, in bytes
+    pub memory_bytes: usize,
+}
+
+#[derive(Debug, Default)]
+struct UsedResources {
+    resources: OptimizationResources,
+    optimizations: usize,
+}
+
+/// Limit of resources, which concurrent optimizations of all collections on the node may use
+///
+/// Collections schedule their optimizations independently, so without a shared limit
+/// multiple large optimizations may run at the same time and exhaust memory of the node.
+///
+/// An optimization, which requires more than the whole budget, is started only when no other
+/// optimization is running, so that large segments are still optimized eventually.
+#[derive(Debug, Default)]
+pub struct OptimizationBudget {
+    /// Maximal total number of threads. If `None` - not limited
+    max_threads: Option<usize>,
+    /// Maximal total memory in bytes. If `None` - not limited
+    max_memory_bytes: Option<usize>,
+    used: Mutex<UsedResources>,
+}
+
+impl OptimizationBudget {
+    pub fn new(max_threads: Option<usize>, max_memory_bytes: Option<usize>) -> Self {
+        Self {
+            max_threads,
+            max_memory_bytes,
+            used: Default::default(),
+        }
+    }
+
+    /// Reserve resources for an optimization
+    ///
+    /// Returns `None` if the budget is exhausted, the optimization should be retried later.
+    /// Resources are released when the returned permit is dropped.
+    pub fn try_acquire(
+        self: &Arc<Self>,
+        required: OptimizationResources,
+    ) -> Option<OptimizationPermit> {
+        let mut used = self.used.lock();
+
+        let fits = |used: usize, required: usize, max: Option<usize>| {
+            max.map_or(true, |max| used.saturating_add(required) <= max)
+        };
+        let is_idle = used.optimizations == 0;
+        if !is_idle
+            && !(fits(used.resources.threads, required.threads, self.max_threads)
+                && fits(
+                    used.resources.memory_bytes,
+                    required.memory_bytes,
+                    self.max_memory_bytes,
+                ))
+        {
+            return None;
+        }
+
+        used.resources.threads += required.threads;
+        used.resources.memory_bytes += required.memory_bytes;
+        used.optimizations += 1;
+
+        Some(OptimizationPermit {
+            budget: self.clone(),
+            resources: required,
+        })
+    }
+
+    /// Resources, reserved by running optimizations
+    pub fn used(&self) -> OptimizationResources {
+        self.used.lock().resources
+    }
+}
+
+/// Resources, reserved for a running optimization, released on drop
+#[derive(Debug)]
+pub struct OptimizationPermit {
+    budget: Arc<OptimizationBudget>,
+    resources: OptimizationResources,
+}
+
+impl Drop for OptimizationPermit {
+    fn drop(&mut self) {
+        let mut used = self.budget.used.lock();
+        used.resources.threads -= self.resources.threads;
+        used.resources.memory_bytes -= self.resources.memory_bytes;
+        used.optimizations -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resources(threads: usize, memory_bytes: usize) -> OptimizationResources {
+        OptimizationResources {
+            threads,
+            memory_bytes,
+        }
+    }
+
+    #[test]
+    fn test_optimization_budget() {
+        let budget = Arc::new(OptimizationBudget::new(Some(4), Some(1000)));
+
+        let first = budget.try_acquire(resources(2, 600)).unwrap();
+        // Memory is exhausted
+        assert!(budget.try_acquire(resources(1, 500)).is_none());
+        let second = budget.try_acquire(resources(2, 400)).unwrap();
+        // Threads are exhausted
+        assert!(budget.try_acquire(resources(1, 0)).is_none());
+        assert_eq!(budget.used(), resources(4, 1000));
+
+        drop(first);
+        assert_eq!(budget.used(), resources(2, 400));
+        drop(second);
+
+        // Oversized optimization is allowed, if nothing else is running
+        let oversized = budget.try_acquire(resources(8, 5000)).unwrap();
+        assert!(budget.try_acquire(resources(0, 0)).is_none());
+        drop(oversized);
+        assert_eq!(budget.used(), resources(0, 0));
+
+        // Unlimited budget
+        let budget = Arc::new(OptimizationBudget::default());
+        let _permits: Vec<_> = (0..10)
+            .map(|_| budget.try_acquire(resources(8, 5000)).unwrap())
+            .collect();
+    }
+}
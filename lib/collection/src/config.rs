@@ -6,19 +6,22 @@ use std::path::Path;
 
 use atomicwrites::AtomicFile;
 use atomicwrites::OverwriteBehavior::AllowOverwrite;
+use memory::madvise;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
 use segment::index::sparse_index::sparse_index_config::{SparseIndexConfig, SparseIndexType};
+use segment::segment::Segment;
 use segment::types::{
-    Distance, HnswConfig, Indexes, QuantizationConfig, SparseVectorDataConfig, VectorDataConfig,
-    VectorStorageType,
+    Distance, HnswConfig, Indexes, PayloadCompression, PayloadStorageType, QuantizationConfig,
+    SparseVectorDataConfig, VectorDataConfig, VectorStorageType,
 };
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 use wal::WalOptions;
 
 use crate::operations::config_diff::{DiffConfig, QuantizationConfigDiff};
+use crate::operations::payload_schema::StrictPayloadSchema;
 use crate::operations::types::{
     CollectionError, CollectionResult, SparseVectorParams, SparseVectorsConfig, VectorParams,
     VectorParamsDiff, VectorsConfig, VectorsConfigDiff,
@@ -63,6 +66,51 @@ pub enum ShardingMethod {
     Custom,
 }
 
+/// Hint for the OS about the access pattern to memory-mapped files
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum MmapAdvice {
+    /// Expect page references in random order
+    Random,
+    /// Expect page references in sequential order
+    Sequential,
+    /// Expect access in the near future, so it might be a good idea to read some pages ahead
+    WillNeed,
+}
+
+impl From<MmapAdvice> for madvise::Advice {
+    fn from(advice: MmapAdvice) -> Self {
+        match advice {
+            MmapAdvice::Random => madvise::Advice::Random,
+            MmapAdvice::Sequential => madvise::Advice::Sequential,
+            MmapAdvice::WillNeed => madvise::Advice::WillNeed,
+        }
+    }
+}
+
+/// How memory-mapped files are loaded into RAM when segments are loaded
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum MmapPrefault {
+    /// Never read memory-mapped files in advance, pages are loaded on first access
+    Never,
+    /// Read memory-mapped files into the page cache in advance
+    Populate,
+    /// Read memory-mapped files and lock them in RAM, so pages are never evicted
+    Lock,
+}
+
+/// Backend of the persistent payload storage
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadStorageBackend {
+    /// Payload is persisted in RocksDB
+    #[default]
+    Rocksdb,
+    /// Payload is persisted in append-only log files, which are read via memory mapping
+    Mmap,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub struct CollectionParams {
@@ -104,6 +152,36 @@ pub struct CollectionParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[validate]
     pub sparse_vectors: Option<BTreeMap<String, SparseVectorParams>>,
+    /// Access pattern hint for memory-mapped files of the collection.
+    /// If none - value of the `storage.mmap_advice` service setting is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mmap_advice: Option<MmapAdvice>,
+    /// How memory-mapped files of the collection are loaded into RAM when segments are loaded.
+    /// If none - files are read in advance only if vector data is expected to fit into RAM.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mmap_prefault: Option<MmapPrefault>,
+    /// Percentage of the most frequently accessed vectors of memory-mapped storages to keep in RAM.
+    /// Hot vectors are re-selected in background, the rest is read from disk.
+    /// If none - vectors are only cached by the OS page cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 100))]
+    pub mmap_hot_percent: Option<u32>,
+    /// Declared types of payload fields.
+    /// If set - upserts and payload updates with undeclared top-level keys or values of other types
+    /// are rejected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strict_payload_schema: Option<StrictPayloadSchema>,
+    /// Compression of payload records, persisted on disk.
+    /// Saves disk space at the cost of CPU time for reading and writing payloads.
+    /// If none - records are not compressed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_compression: Option<PayloadCompression>,
+    /// Backend of the persistent payload storage.
+    /// `mmap` stores payload in append-only log files without RocksDB and reads it via memory
+    /// mapping, in this case `on_disk_payload` is ignored.
+    /// If none - payload is stored in RocksDB.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_storage_backend: Option<PayloadStorageBackend>,
 }
 
 impl Anonymize for CollectionParams {
@@ -117,6 +195,17 @@ impl Anonymize for CollectionParams {
             read_fan_out_factor: self.read_fan_out_factor,
             on_disk_payload: self.on_disk_payload,
             sparse_vectors: self.sparse_vectors.anonymize(),
+            mmap_advice: self.mmap_advice,
+            mmap_prefault: self.mmap_prefault,
+            mmap_hot_percent: self.mmap_hot_percent,
+            strict_payload_schema: self.strict_payload_schema.as_ref().map(|schema| {
+                schema
+                    .iter()
+                    .map(|(key, schema_type)| (key.anonymize(), *schema_type))
+                    .collect()
+            }),
+            payload_compression: self.payload_compression,
+            payload_storage_backend: self.payload_storage_backend,
         }
     }
 }
@@ -194,6 +283,41 @@ impl CollectionParams {
             read_fan_out_factor: None,
             on_disk_payload: default_on_disk_payload(),
             sparse_vectors: None,
+            mmap_advice: None,
+            mmap_prefault: None,
+            mmap_hot_percent: None,
+            strict_payload_schema: None,
+            payload_compression: None,
+            payload_storage_backend: None,
+        }
+    }
+
+    /// Type of payload storage, used for segments of the collection
+    pub fn payload_storage_type(&self) -> PayloadStorageType {
+        match self.payload_storage_backend.unwrap_or_default() {
+            PayloadStorageBackend::Mmap => PayloadStorageType::Mmap,
+            PayloadStorageBackend::Rocksdb if self.on_disk_payload => PayloadStorageType::OnDisk,
+            PayloadStorageBackend::Rocksdb => PayloadStorageType::InMemory,
+        }
+    }
+
+    /// Apply memory-mapped files configuration of the collection to a loaded segment
+    ///
+    /// `prefault_by_default` defines whether files are read in advance if `mmap_prefault` is not set.
+    pub fn load_mmap_pages(&self, segment: &Segment, prefault_by_default: bool) {
+        if let Some(advice) = self.mmap_advice {
+            segment.madvise_mmap_pages(advice.into());
+        }
+
+        match self.mmap_prefault {
+            None if prefault_by_default => segment.prefault_mmap_pages(),
+            None | Some(MmapPrefault::Never) => {}
+            Some(MmapPrefault::Populate) => segment.prefault_mmap_pages(),
+            Some(MmapPrefault::Lock) => segment.lock_mmap_pages(),
+        }
+
+        if let Some(percent) = self.mmap_hot_percent {
+            segment.set_hot_tier_ratio(Some(percent as f32 / 100.0));
         }
     }
 
@@ -337,6 +461,10 @@ impl CollectionParams {
                         } else {
                             VectorStorageType::Memory
                         },
+                        multivector_config: params.multivector_config,
+                        datatype: params.datatype,
+                        keep_original: params.keep_original,
+                        dimension_weights: params.dimension_weights.clone(),
                     },
                 )
             })
@@ -362,6 +490,7 @@ impl CollectionParams {
                                     .index
                                     .and_then(|index| index.full_scan_threshold),
                                 index_type: SparseIndexType::MutableRam,
+                                datatype: params.index.and_then(|index| index.datatype),
                             },
                         },
                     )
@@ -90,6 +90,7 @@ fn discovery_into_core_search(
     let filter = {
         let not_ids = Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![Condition::HasId(HasIdCondition {
                 has_id: referenced_ids.into_iter().collect(),
@@ -111,6 +112,7 @@ fn discovery_into_core_search(
         with_payload: request.with_payload,
         with_vector: request.with_vector,
         score_threshold: None,
+        tie_breaker: None,
     };
 
     Ok(core_search)
@@ -164,6 +164,7 @@ impl From<SearchGroupsRequestInternal> for GroupRequest {
             with_payload,
             with_vector,
             score_threshold,
+            tie_breaker: None,
         };
 
         GroupRequest {
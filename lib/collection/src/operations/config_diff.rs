@@ -1,8 +1,11 @@
+use std::collections::BTreeMap;
 use std::num::NonZeroU32;
 
 use merge::Merge;
 use schemars::JsonSchema;
-use segment::types::{BinaryQuantization, HnswConfig, ProductQuantization, ScalarQuantization};
+use segment::types::{
+    BinaryQuantization, HnswConfig, PayloadKeyType, ProductQuantization, ScalarQuantization,
+};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -10,7 +13,7 @@ use validator::{Validate, ValidationErrors};
 
 use crate::config::{CollectionParams, WalConfig};
 use crate::operations::types::CollectionResult;
-use crate::optimizers_builder::OptimizersConfig;
+use crate::optimizers_builder::{MergePolicy, OptimizersConfig};
 
 // Structures for partial update of collection params
 // TODO: make auto-generated somehow...
@@ -37,20 +40,7 @@ pub trait DiffConfig<T: DeserializeOwned + Serialize> {
     }
 }
 
-#[derive(
-    Debug,
-    Default,
-    Deserialize,
-    Serialize,
-    JsonSchema,
-    Validate,
-    Copy,
-    Clone,
-    PartialEq,
-    Eq,
-    Merge,
-    Hash,
-)]
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Merge)]
 #[serde(rename_all = "snake_case")]
 pub struct HnswConfigDiff {
     /// Number of edges per node in the index graph. Larger the value - more accurate the search, more space required.
@@ -80,8 +70,95 @@ pub struct HnswConfigDiff {
     /// Custom M param for additional payload-aware HNSW links. If not set, default M will be used.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub payload_m: Option<usize>,
+    /// Custom M param of payload-aware HNSW links for specific payload fields.
+    /// Overrides `payload_m` for the listed fields. Value of 0 disables additional links for the field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_m_overrides: Option<BTreeMap<PayloadKeyType, usize>>,
+    /// Seed for the random level assignment of HNSW index building. If set, building the index
+    /// for the same data produces the same graph. Note: index building with a seed is
+    /// single-threaded, to keep the insertion order deterministic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Extend the candidate list with neighbours of the candidates before selecting links
+    /// with the heuristic. Improves recall on highly clustered data, at the cost of slower
+    /// index building.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extend_candidates: Option<bool>,
+    /// Fill up the links of a node with candidates discarded by the heuristic, if it selected
+    /// less than M of them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_pruned_connections: Option<bool>,
+    /// Store HNSW links compressed and serve them from disk via mmap. Links of each node are
+    /// sorted, delta-encoded and stored as varints. Reduces memory and disk usage of the index,
+    /// at the cost of slower search.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compress_links: Option<bool>,
+    /// Target recall of the search with default params. If set, `ef` used for searches without
+    /// explicit `hnsw_ef` is calibrated after the index is built, to reach this recall.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub target_recall: Option<f64>,
+    /// Multiplier of the level generation factor `1/ln(m)`. Values below 1 flatten the hierarchy,
+    /// values above 1 make it deeper. Doesn't affect the number of links per node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0.0))]
+    pub level_factor_multiplier: Option<f64>,
+    /// Number of neighbours to consider during the index building on each graph level, starting
+    /// from level 0. Levels above the end of the list use its last value. Overrides `ef_construct`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ef_construct_levels: Option<Vec<usize>>,
+    /// Refine the graph after it is built: links of every point are selected once more from its
+    /// neighbours and their neighbours. Improves recall for the same `m`, at the cost of longer
+    /// index building.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refine: Option<bool>,
+    /// Link new points into the built graph on upsert, instead of writing them into a separate
+    /// unindexed segment. Only applies to graphs and vectors kept in RAM, without quantization.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub appendable: Option<bool>,
+}
+
+impl std::hash::Hash for HnswConfigDiff {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self {
+            m,
+            ef_construct,
+            full_scan_threshold,
+            max_indexing_threads,
+            on_disk,
+            payload_m,
+            payload_m_overrides,
+            seed,
+            extend_candidates,
+            keep_pruned_connections,
+            compress_links,
+            target_recall,
+            level_factor_multiplier,
+            ef_construct_levels,
+            refine,
+            appendable,
+        } = self;
+        m.hash(state);
+        ef_construct.hash(state);
+        full_scan_threshold.hash(state);
+        max_indexing_threads.hash(state);
+        on_disk.hash(state);
+        payload_m.hash(state);
+        payload_m_overrides.hash(state);
+        seed.hash(state);
+        extend_candidates.hash(state);
+        keep_pruned_connections.hash(state);
+        compress_links.hash(state);
+        target_recall.map(f64::to_bits).hash(state);
+        level_factor_multiplier.map(f64::to_bits).hash(state);
+        ef_construct_levels.hash(state);
+        refine.hash(state);
+        appendable.hash(state);
+    }
 }
 
+impl Eq for HnswConfigDiff {}
+
 #[derive(
     Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Merge, PartialEq, Eq, Hash,
 )]
@@ -112,9 +189,14 @@ pub struct CollectionParamsDiff {
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Merge)]
 pub struct OptimizersConfigDiff {
     /// The minimal fraction of deleted vectors in a segment, required to perform segment optimization
+    #[validate(range(min = 0.0, max = 1.0))]
     pub deleted_threshold: Option<f64>,
     /// The minimal number of vectors in a segment, required to perform segment optimization
+    #[validate(range(min = 100))]
     pub vacuum_min_vector_number: Option<usize>,
+    /// Whether to rebuild segments with many deleted points, according to the thresholds above.
+    /// Could be disabled for append-only collections, which never delete or update points.
+    pub vacuum_enabled: Option<bool>,
     /// Target amount of segments optimizer will try to keep.
     /// Real amount of segments may vary depending on multiple parameters:
     ///  - Amount of stored points
@@ -156,18 +238,23 @@ pub struct OptimizersConfigDiff {
     pub flush_interval_sec: Option<u64>,
     /// Maximum available threads for optimization workers
     pub max_optimization_threads: Option<usize>,
+    /// Strategy of selecting segments to merge. Default is to merge the smallest segments,
+    /// as long as the merged segment fits into `max_segment_size`
+    pub merge_policy: Option<MergePolicy>,
 }
 
 impl std::hash::Hash for OptimizersConfigDiff {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.deleted_threshold.map(f64::to_le_bytes).hash(state);
         self.vacuum_min_vector_number.hash(state);
+        self.vacuum_enabled.hash(state);
         self.default_segment_number.hash(state);
         self.max_segment_size.hash(state);
         self.memmap_threshold.hash(state);
         self.indexing_threshold.hash(state);
         self.flush_interval_sec.hash(state);
         self.max_optimization_threads.hash(state);
+        self.merge_policy.hash(state);
     }
 }
 
@@ -176,12 +263,14 @@ impl PartialEq for OptimizersConfigDiff {
         self.deleted_threshold.map(f64::to_le_bytes)
             == other.deleted_threshold.map(f64::to_le_bytes)
             && self.vacuum_min_vector_number == other.vacuum_min_vector_number
+            && self.vacuum_enabled == other.vacuum_enabled
             && self.default_segment_number == other.default_segment_number
             && self.max_segment_size == other.max_segment_size
             && self.memmap_threshold == other.memmap_threshold
             && self.indexing_threshold == other.indexing_threshold
             && self.flush_interval_sec == other.flush_interval_sec
             && self.max_optimization_threads == other.max_optimization_threads
+            && self.merge_policy == other.merge_policy
     }
 }
 
@@ -339,6 +428,11 @@ mod tests {
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: None,
+                index: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
             }
             .into(),
             ..CollectionParams::empty()
@@ -366,22 +460,57 @@ mod tests {
         assert_eq!(new_config.m, 32)
     }
 
+    #[test]
+    fn test_hnsw_payload_m_overrides_update() {
+        let base_config = HnswConfig::default();
+        let update: HnswConfigDiff =
+            serde_json::from_str(r#"{ "payload_m_overrides": { "tenant": 4 } }"#).unwrap();
+        let new_config = update.update(&base_config).unwrap();
+        assert_eq!(
+            new_config.payload_m_overrides,
+            Some(BTreeMap::from([("tenant".to_string(), 4)])),
+        );
+        assert!(base_config.mismatch_requires_rebuild(&new_config));
+    }
+
     #[test]
     fn test_optimizer_update() {
         let base_config = OptimizersConfig {
             deleted_threshold: 0.9,
             vacuum_min_vector_number: 1000,
+            vacuum_enabled: true,
             default_segment_number: 10,
             max_segment_size: None,
             memmap_threshold: None,
             indexing_threshold: Some(50_000),
             flush_interval_sec: 30,
             max_optimization_threads: 1,
+            merge_policy: MergePolicy::default(),
         };
         let update: OptimizersConfigDiff =
             serde_json::from_str(r#"{ "indexing_threshold": 10000 }"#).unwrap();
         let new_config = update.update(&base_config).unwrap();
-        assert_eq!(new_config.indexing_threshold, Some(10000))
+        assert_eq!(new_config.indexing_threshold, Some(10000));
+        assert_eq!(new_config.merge_policy, MergePolicy::MaxSegmentSizeBound);
+
+        let update: OptimizersConfigDiff =
+            serde_json::from_str(r#"{ "merge_policy": "size_tiered" }"#).unwrap();
+        let new_config = update.update(&new_config).unwrap();
+        assert_eq!(new_config.merge_policy, MergePolicy::SizeTiered);
+        assert_eq!(new_config.indexing_threshold, Some(10000));
+
+        let update: OptimizersConfigDiff = serde_json::from_str(
+            r#"{ "deleted_threshold": 0.5, "vacuum_min_vector_number": 500, "vacuum_enabled": false }"#,
+        )
+        .unwrap();
+        let new_config = update.update(&new_config).unwrap();
+        assert_eq!(new_config.deleted_threshold, 0.5);
+        assert_eq!(new_config.vacuum_min_vector_number, 500);
+        assert!(!new_config.vacuum_enabled);
+
+        let update: OptimizersConfigDiff =
+            serde_json::from_str(r#"{ "deleted_threshold": 1.5 }"#).unwrap();
+        assert!(update.validate().is_err());
     }
 
     #[test]
@@ -4,7 +4,8 @@ use std::time::Duration;
 
 use api::grpc::conversions::{
     convert_shard_key_from_grpc, convert_shard_key_from_grpc_opt, convert_shard_key_to_grpc,
-    from_grpc_dist, payload_to_proto, proto_to_payloads,
+    from_grpc_datatype, from_grpc_dist, payload_schema_type_from_proto, payload_to_proto,
+    proto_to_payloads,
 };
 use api::grpc::qdrant::quantization_config_diff::Quantization;
 use api::grpc::qdrant::update_collection_cluster_setup_request::{
@@ -14,7 +15,7 @@ use api::grpc::qdrant::{CreateShardKey, SearchPoints};
 use common::types::ScoreType;
 use itertools::Itertools;
 use segment::data_types::vectors::{Named, NamedQuery, Vector, VectorStruct, DEFAULT_VECTOR_NAME};
-use segment::types::{Distance, QuantizationConfig};
+use segment::types::{Distance, IvfConfig, PayloadCompression, QuantizationConfig, VamanaConfig};
 use segment::vector_storage::query::context_query::{ContextPair, ContextQuery};
 use segment::vector_storage::query::discovery_query::DiscoveryQuery;
 use segment::vector_storage::query::reco_query::RecoQuery;
@@ -24,12 +25,12 @@ use super::consistency_params::ReadConsistency;
 use super::types::{
     BaseGroupRequest, ContextExamplePair, CoreSearchRequest, DiscoverRequestInternal, GroupsResult,
     PointGroup, QueryEnum, RecommendExample, RecommendGroupsRequestInternal, RecommendStrategy,
-    SearchGroupsRequestInternal, SparseIndexParams, SparseVectorParams, VectorParamsDiff,
-    VectorsConfigDiff,
+    SearchGroupsRequestInternal, SparseIndexParams, SparseVectorParams, VectorIndexParams,
+    VectorParamsDiff, VectorsConfigDiff,
 };
 use crate::config::{
     default_replication_factor, default_write_consistency_factor, CollectionConfig,
-    CollectionParams, ShardingMethod, WalConfig,
+    CollectionParams, MmapAdvice, MmapPrefault, PayloadStorageBackend, ShardingMethod, WalConfig,
 };
 use crate::lookup::types::WithLookupInterface;
 use crate::lookup::WithLookup;
@@ -42,6 +43,7 @@ use crate::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, QuantizationConfigDiff,
     WalConfigDiff,
 };
+use crate::operations::payload_schema::StrictPayloadSchema;
 use crate::operations::point_ops::PointsSelector::PointIdsSelector;
 use crate::operations::point_ops::{
     Batch, FilterSelector, PointIdsList, PointStruct, PointsSelector, WriteOrdering,
@@ -49,12 +51,12 @@ use crate::operations::point_ops::{
 use crate::operations::shard_key_selector::ShardKeySelector;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{
-    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionStatus, CountResult,
-    LocalShardInfo, LookupLocation, OptimizersStatus, RecommendRequestInternal, Record,
-    RemoteShardInfo, SearchRequestInternal, ShardTransferInfo, UpdateResult, UpdateStatus,
-    VectorParams, VectorsConfig,
+    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionStatus, CountEstimation,
+    CountResult, FacetResponse, LocalShardInfo, LookupLocation, OptimizersStatus,
+    RecommendRequestInternal, Record, RemoteShardInfo, SearchRequestInternal, ShardTransferInfo,
+    UpdateResult, UpdateStatus, VectorParams, VectorsConfig,
 };
-use crate::optimizers_builder::OptimizersConfig;
+use crate::optimizers_builder::{default_vacuum_enabled, MergePolicy, OptimizersConfig};
 use crate::shards::remote_shard::{CollectionCoreSearchRequest, CollectionSearchRequest};
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::transfer::ShardTransferMethod;
@@ -77,6 +79,135 @@ pub fn sharding_method_from_proto(sharding_method: i32) -> Result<ShardingMethod
     }
 }
 
+pub fn mmap_advice_to_proto(mmap_advice: MmapAdvice) -> i32 {
+    match mmap_advice {
+        MmapAdvice::Random => api::grpc::qdrant::MmapAdvice::Random as i32,
+        MmapAdvice::Sequential => api::grpc::qdrant::MmapAdvice::Sequential as i32,
+        MmapAdvice::WillNeed => api::grpc::qdrant::MmapAdvice::WillNeed as i32,
+    }
+}
+
+pub fn mmap_advice_from_proto(mmap_advice: i32) -> Result<MmapAdvice, Status> {
+    match api::grpc::qdrant::MmapAdvice::from_i32(mmap_advice) {
+        Some(api::grpc::qdrant::MmapAdvice::Random) => Ok(MmapAdvice::Random),
+        Some(api::grpc::qdrant::MmapAdvice::Sequential) => Ok(MmapAdvice::Sequential),
+        Some(api::grpc::qdrant::MmapAdvice::WillNeed) => Ok(MmapAdvice::WillNeed),
+        Some(api::grpc::qdrant::MmapAdvice::UnknownMmapAdvice) | None => Err(
+            Status::invalid_argument(format!("Cannot convert mmap advice: {mmap_advice}")),
+        ),
+    }
+}
+
+pub fn mmap_prefault_to_proto(mmap_prefault: MmapPrefault) -> i32 {
+    match mmap_prefault {
+        MmapPrefault::Never => api::grpc::qdrant::MmapPrefault::Never as i32,
+        MmapPrefault::Populate => api::grpc::qdrant::MmapPrefault::Populate as i32,
+        MmapPrefault::Lock => api::grpc::qdrant::MmapPrefault::Lock as i32,
+    }
+}
+
+pub fn mmap_prefault_from_proto(mmap_prefault: i32) -> Result<MmapPrefault, Status> {
+    match api::grpc::qdrant::MmapPrefault::from_i32(mmap_prefault) {
+        Some(api::grpc::qdrant::MmapPrefault::Never) => Ok(MmapPrefault::Never),
+        Some(api::grpc::qdrant::MmapPrefault::Populate) => Ok(MmapPrefault::Populate),
+        Some(api::grpc::qdrant::MmapPrefault::Lock) => Ok(MmapPrefault::Lock),
+        Some(api::grpc::qdrant::MmapPrefault::UnknownMmapPrefault) | None => Err(
+            Status::invalid_argument(format!("Cannot convert mmap prefault: {mmap_prefault}")),
+        ),
+    }
+}
+
+pub fn payload_compression_to_proto(payload_compression: PayloadCompression) -> i32 {
+    match payload_compression {
+        PayloadCompression::Zstd => api::grpc::qdrant::PayloadCompression::Zstd as i32,
+    }
+}
+
+pub fn payload_compression_from_proto(
+    payload_compression: i32,
+) -> Result<PayloadCompression, Status> {
+    match api::grpc::qdrant::PayloadCompression::from_i32(payload_compression) {
+        Some(api::grpc::qdrant::PayloadCompression::Zstd) => Ok(PayloadCompression::Zstd),
+        Some(api::grpc::qdrant::PayloadCompression::UnknownPayloadCompression) | None => {
+            Err(Status::invalid_argument(format!(
+                "Cannot convert payload compression: {payload_compression}"
+            )))
+        }
+    }
+}
+
+pub fn payload_storage_backend_to_proto(payload_storage_backend: PayloadStorageBackend) -> i32 {
+    match payload_storage_backend {
+        PayloadStorageBackend::Rocksdb => api::grpc::qdrant::PayloadStorageBackend::Rocksdb as i32,
+        PayloadStorageBackend::Mmap => api::grpc::qdrant::PayloadStorageBackend::Mmap as i32,
+    }
+}
+
+pub fn payload_storage_backend_from_proto(
+    payload_storage_backend: i32,
+) -> Result<PayloadStorageBackend, Status> {
+    match api::grpc::qdrant::PayloadStorageBackend::from_i32(payload_storage_backend) {
+        Some(api::grpc::qdrant::PayloadStorageBackend::Rocksdb) => {
+            Ok(PayloadStorageBackend::Rocksdb)
+        }
+        Some(api::grpc::qdrant::PayloadStorageBackend::Mmap) => Ok(PayloadStorageBackend::Mmap),
+        Some(api::grpc::qdrant::PayloadStorageBackend::UnknownPayloadStorageBackend) | None => {
+            Err(Status::invalid_argument(format!(
+                "Cannot convert payload storage backend: {payload_storage_backend}"
+            )))
+        }
+    }
+}
+
+pub fn merge_policy_to_proto(merge_policy: MergePolicy) -> i32 {
+    match merge_policy {
+        MergePolicy::MaxSegmentSizeBound => {
+            api::grpc::qdrant::MergePolicy::MaxSegmentSizeBound as i32
+        }
+        MergePolicy::FewestSegments => api::grpc::qdrant::MergePolicy::FewestSegments as i32,
+        MergePolicy::SizeTiered => api::grpc::qdrant::MergePolicy::SizeTiered as i32,
+    }
+}
+
+pub fn merge_policy_from_proto(merge_policy: i32) -> Result<MergePolicy, Status> {
+    match api::grpc::qdrant::MergePolicy::from_i32(merge_policy) {
+        Some(api::grpc::qdrant::MergePolicy::MaxSegmentSizeBound) => {
+            Ok(MergePolicy::MaxSegmentSizeBound)
+        }
+        Some(api::grpc::qdrant::MergePolicy::FewestSegments) => Ok(MergePolicy::FewestSegments),
+        Some(api::grpc::qdrant::MergePolicy::SizeTiered) => Ok(MergePolicy::SizeTiered),
+        None => Err(Status::invalid_argument(format!(
+            "Cannot convert merge policy: {merge_policy}"
+        ))),
+    }
+}
+
+pub fn strict_payload_schema_to_proto(schema: StrictPayloadSchema) -> HashMap<String, i32> {
+    schema
+        .into_iter()
+        .map(|(key, schema_type)| {
+            (
+                key,
+                api::grpc::qdrant::PayloadSchemaType::from(schema_type) as i32,
+            )
+        })
+        .collect()
+}
+
+/// Empty schema in gRPC means that payloads are not validated
+pub fn strict_payload_schema_from_proto(
+    schema: HashMap<String, i32>,
+) -> Result<Option<StrictPayloadSchema>, Status> {
+    if schema.is_empty() {
+        return Ok(None);
+    }
+    schema
+        .into_iter()
+        .map(|(key, schema_type)| Ok((key, payload_schema_type_from_proto(schema_type)?)))
+        .collect::<Result<_, Status>>()
+        .map(Some)
+}
+
 pub fn write_ordering_to_proto(ordering: WriteOrdering) -> api::grpc::qdrant::WriteOrdering {
     api::grpc::qdrant::WriteOrdering {
         r#type: match ordering {
@@ -138,6 +269,7 @@ pub fn try_record_from_grpc(
         payload,
         vector,
         shard_key: convert_shard_key_from_grpc_opt(point.shard_key),
+        order_value: point.order_value,
     })
 }
 
@@ -230,6 +362,28 @@ impl From<api::grpc::qdrant::HnswConfigDiff> for HnswConfigDiff {
             max_indexing_threads: value.max_indexing_threads.map(|v| v as usize),
             on_disk: value.on_disk,
             payload_m: value.payload_m.map(|v| v as usize),
+            payload_m_overrides: (!value.payload_m_overrides.is_empty()).then(|| {
+                value
+                    .payload_m_overrides
+                    .into_iter()
+                    .map(|(field, m)| (field, m as usize))
+                    .collect()
+            }),
+            seed: value.seed,
+            extend_candidates: value.extend_candidates,
+            keep_pruned_connections: value.keep_pruned_connections,
+            compress_links: value.compress_links,
+            target_recall: value.target_recall,
+            level_factor_multiplier: value.level_factor_multiplier,
+            ef_construct_levels: (!value.ef_construct_levels.is_empty()).then(|| {
+                value
+                    .ef_construct_levels
+                    .into_iter()
+                    .map(|ef| ef as usize)
+                    .collect()
+            }),
+            refine: value.refine,
+            appendable: value.appendable,
         }
     }
 }
@@ -243,6 +397,26 @@ impl From<HnswConfigDiff> for api::grpc::qdrant::HnswConfigDiff {
             max_indexing_threads: value.max_indexing_threads.map(|v| v as u64),
             on_disk: value.on_disk,
             payload_m: value.payload_m.map(|v| v as u64),
+            payload_m_overrides: value
+                .payload_m_overrides
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(field, m)| (field, m as u64))
+                .collect(),
+            seed: value.seed,
+            extend_candidates: value.extend_candidates,
+            keep_pruned_connections: value.keep_pruned_connections,
+            compress_links: value.compress_links,
+            target_recall: value.target_recall,
+            level_factor_multiplier: value.level_factor_multiplier,
+            ef_construct_levels: value
+                .ef_construct_levels
+                .unwrap_or_default()
+                .into_iter()
+                .map(|ef| ef as u64)
+                .collect(),
+            refine: value.refine,
+            appendable: value.appendable,
         }
     }
 }
@@ -282,9 +456,11 @@ impl TryFrom<api::grpc::qdrant::CollectionParamsDiff> for CollectionParamsDiff {
     }
 }
 
-impl From<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfigDiff {
-    fn from(value: api::grpc::qdrant::OptimizersConfigDiff) -> Self {
-        Self {
+impl TryFrom<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfigDiff {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::OptimizersConfigDiff) -> Result<Self, Self::Error> {
+        Ok(Self {
             deleted_threshold: value.deleted_threshold,
             vacuum_min_vector_number: value.vacuum_min_vector_number.map(|v| v as usize),
             default_segment_number: value.default_segment_number.map(|v| v as usize),
@@ -293,7 +469,12 @@ impl From<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfigDiff {
             indexing_threshold: value.indexing_threshold.map(|v| v as usize),
             flush_interval_sec: value.flush_interval_sec,
             max_optimization_threads: value.max_optimization_threads.map(|v| v as usize),
-        }
+            merge_policy: value
+                .merge_policy
+                .map(merge_policy_from_proto)
+                .transpose()?,
+            vacuum_enabled: value.vacuum_enabled,
+        })
     }
 }
 
@@ -378,6 +559,22 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     write_consistency_factor: Some(config.params.write_consistency_factor.get()),
                     read_fan_out_factor: config.params.read_fan_out_factor,
                     sharding_method: config.params.sharding_method.map(sharding_method_to_proto),
+                    mmap_advice: config.params.mmap_advice.map(mmap_advice_to_proto),
+                    mmap_prefault: config.params.mmap_prefault.map(mmap_prefault_to_proto),
+                    mmap_hot_percent: config.params.mmap_hot_percent,
+                    strict_payload_schema: config
+                        .params
+                        .strict_payload_schema
+                        .map(strict_payload_schema_to_proto)
+                        .unwrap_or_default(),
+                    payload_compression: config
+                        .params
+                        .payload_compression
+                        .map(payload_compression_to_proto),
+                    payload_storage_backend: config
+                        .params
+                        .payload_storage_backend
+                        .map(payload_storage_backend_to_proto),
                     sparse_vectors_config: config.params.sparse_vectors.map(|sparse_vectors| {
                         api::grpc::qdrant::SparseVectorConfig {
                             map: sparse_vectors
@@ -396,6 +593,28 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     max_indexing_threads: Some(config.hnsw_config.max_indexing_threads as u64),
                     on_disk: config.hnsw_config.on_disk,
                     payload_m: config.hnsw_config.payload_m.map(|v| v as u64),
+                    payload_m_overrides: config
+                        .hnsw_config
+                        .payload_m_overrides
+                        .iter()
+                        .flatten()
+                        .map(|(field, m)| (field.clone(), *m as u64))
+                        .collect(),
+                    seed: config.hnsw_config.seed,
+                    extend_candidates: config.hnsw_config.extend_candidates,
+                    keep_pruned_connections: config.hnsw_config.keep_pruned_connections,
+                    compress_links: config.hnsw_config.compress_links,
+                    target_recall: config.hnsw_config.target_recall,
+                    level_factor_multiplier: config.hnsw_config.level_factor_multiplier,
+                    ef_construct_levels: config
+                        .hnsw_config
+                        .ef_construct_levels
+                        .iter()
+                        .flatten()
+                        .map(|ef| *ef as u64)
+                        .collect(),
+                    refine: config.hnsw_config.refine,
+                    appendable: config.hnsw_config.appendable,
                 }),
                 optimizer_config: Some(api::grpc::qdrant::OptimizersConfigDiff {
                     deleted_threshold: Some(config.optimizer_config.deleted_threshold),
@@ -415,6 +634,8 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     max_optimization_threads: Some(
                         config.optimizer_config.max_optimization_threads as u64,
                     ),
+                    merge_policy: Some(merge_policy_to_proto(config.optimizer_config.merge_policy)),
+                    vacuum_enabled: Some(config.optimizer_config.vacuum_enabled),
                 }),
                 wal_config: Some(api::grpc::qdrant::WalConfigDiff {
                     wal_capacity_mb: Some(config.wal_config.wal_capacity_mb as u64),
@@ -439,6 +660,7 @@ impl From<Record> for api::grpc::qdrant::RetrievedPoint {
             payload: record.payload.map(payload_to_proto).unwrap_or_default(),
             vectors,
             shard_key: record.shard_key.map(convert_shard_key_to_grpc),
+            order_value: record.order_value,
         }
     }
 }
@@ -456,9 +678,13 @@ impl TryFrom<i32> for CollectionStatus {
     }
 }
 
-impl From<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfig {
-    fn from(optimizer_config: api::grpc::qdrant::OptimizersConfigDiff) -> Self {
-        Self {
+impl TryFrom<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfig {
+    type Error = Status;
+
+    fn try_from(
+        optimizer_config: api::grpc::qdrant::OptimizersConfigDiff,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
             deleted_threshold: optimizer_config.deleted_threshold.unwrap_or_default(),
             vacuum_min_vector_number: optimizer_config
                 .vacuum_min_vector_number
@@ -472,7 +698,15 @@ impl From<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfig {
             max_optimization_threads: optimizer_config
                 .max_optimization_threads
                 .unwrap_or_default() as usize,
-        }
+            merge_policy: optimizer_config
+                .merge_policy
+                .map(merge_policy_from_proto)
+                .transpose()?
+                .unwrap_or_default(),
+            vacuum_enabled: optimizer_config
+                .vacuum_enabled
+                .unwrap_or_else(default_vacuum_enabled),
+        })
     }
 }
 
@@ -541,10 +775,97 @@ impl TryFrom<api::grpc::qdrant::VectorParams> for VectorParams {
                 .map(grpc_to_segment_quantization_config)
                 .transpose()?,
             on_disk: vector_params.on_disk,
+            index: vector_params.index.map(TryInto::try_into).transpose()?,
+            multivector_config: vector_params
+                .multivector_config
+                .map(TryInto::try_into)
+                .transpose()?,
+            datatype: vector_params
+                .datatype
+                .map(from_grpc_datatype)
+                .transpose()?
+                .flatten(),
+            keep_original: vector_params.keep_original,
+            dimension_weights: (!vector_params.dimension_weights.is_empty())
+                .then_some(vector_params.dimension_weights),
         })
     }
 }
 
+impl TryFrom<api::grpc::qdrant::VectorIndexParams> for VectorIndexParams {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::VectorIndexParams) -> Result<Self, Self::Error> {
+        let index = value
+            .index
+            .ok_or_else(|| Status::invalid_argument("VectorIndexParams must contain index"))?;
+        match index {
+            api::grpc::qdrant::vector_index_params::Index::Hnsw(_) => Ok(VectorIndexParams::Hnsw),
+            api::grpc::qdrant::vector_index_params::Index::Vamana(vamana) => {
+                let default = VamanaConfig::default();
+                Ok(VectorIndexParams::Vamana(VamanaConfig {
+                    max_degree: vamana
+                        .max_degree
+                        .map_or(default.max_degree, |value| value as usize),
+                    build_list_size: vamana
+                        .build_list_size
+                        .map_or(default.build_list_size, |value| value as usize),
+                    search_list_size: vamana
+                        .search_list_size
+                        .map_or(default.search_list_size, |value| value as usize),
+                    full_scan_threshold: vamana
+                        .full_scan_threshold
+                        .map_or(default.full_scan_threshold, |value| value as usize),
+                }))
+            }
+            api::grpc::qdrant::vector_index_params::Index::Ivf(ivf) => {
+                let default = IvfConfig::default();
+                Ok(VectorIndexParams::Ivf(IvfConfig {
+                    num_lists: ivf
+                        .num_lists
+                        .map_or(default.num_lists, |value| value as usize),
+                    pq_subvector_dim: ivf
+                        .pq_subvector_dim
+                        .map_or(default.pq_subvector_dim, |value| value as usize),
+                    nprobe: ivf.nprobe.map_or(default.nprobe, |value| value as usize),
+                    full_scan_threshold: ivf
+                        .full_scan_threshold
+                        .map_or(default.full_scan_threshold, |value| value as usize),
+                }))
+            }
+        }
+    }
+}
+
+impl From<VectorIndexParams> for api::grpc::qdrant::VectorIndexParams {
+    fn from(value: VectorIndexParams) -> Self {
+        let index = match value {
+            VectorIndexParams::Hnsw => api::grpc::qdrant::vector_index_params::Index::Hnsw(
+                api::grpc::qdrant::HnswIndexParams {},
+            ),
+            VectorIndexParams::Vamana(vamana) => {
+                api::grpc::qdrant::vector_index_params::Index::Vamana(
+                    api::grpc::qdrant::VamanaIndexParams {
+                        max_degree: Some(vamana.max_degree as u64),
+                        build_list_size: Some(vamana.build_list_size as u64),
+                        search_list_size: Some(vamana.search_list_size as u64),
+                        full_scan_threshold: Some(vamana.full_scan_threshold as u64),
+                    },
+                )
+            }
+            VectorIndexParams::Ivf(ivf) => api::grpc::qdrant::vector_index_params::Index::Ivf(
+                api::grpc::qdrant::IvfIndexParams {
+                    num_lists: Some(ivf.num_lists as u64),
+                    pq_subvector_dim: Some(ivf.pq_subvector_dim as u64),
+                    nprobe: Some(ivf.nprobe as u64),
+                    full_scan_threshold: Some(ivf.full_scan_threshold as u64),
+                },
+            ),
+        };
+        Self { index: Some(index) }
+    }
+}
+
 impl TryFrom<api::grpc::qdrant::VectorParamsDiff> for VectorParamsDiff {
     type Error = Status;
 
@@ -560,16 +881,28 @@ impl TryFrom<api::grpc::qdrant::VectorParamsDiff> for VectorParamsDiff {
     }
 }
 
-impl From<api::grpc::qdrant::SparseVectorParams> for SparseVectorParams {
-    fn from(sparse_vector_params: api::grpc::qdrant::SparseVectorParams) -> Self {
-        Self {
+impl TryFrom<api::grpc::qdrant::SparseVectorParams> for SparseVectorParams {
+    type Error = Status;
+
+    fn try_from(
+        sparse_vector_params: api::grpc::qdrant::SparseVectorParams,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
             index: sparse_vector_params
                 .index
-                .map(|index_config| SparseIndexParams {
-                    full_scan_threshold: index_config.full_scan_threshold.map(|v| v as usize),
-                    on_disk: index_config.on_disk,
-                }),
-        }
+                .map(|index_config| {
+                    Ok::<_, Status>(SparseIndexParams {
+                        full_scan_threshold: index_config.full_scan_threshold.map(|v| v as usize),
+                        on_disk: index_config.on_disk,
+                        datatype: index_config
+                            .datatype
+                            .map(from_grpc_datatype)
+                            .transpose()?
+                            .flatten(),
+                    })
+                })
+                .transpose()?,
+        })
     }
 }
 
@@ -580,6 +913,9 @@ impl From<SparseVectorParams> for api::grpc::qdrant::SparseVectorParams {
                 api::grpc::qdrant::SparseIndexConfig {
                     full_scan_threshold: index_config.full_scan_threshold.map(|v| v as u64),
                     on_disk: index_config.on_disk,
+                    datatype: index_config
+                        .datatype
+                        .map(|datatype| api::grpc::qdrant::Datatype::from(datatype) as i32),
                 }
             }),
         }
@@ -639,13 +975,18 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                             ),
                         },
                     },
-                    sparse_vectors: params.sparse_vectors_config.map(|sparse_vectors| {
-                        sparse_vectors
-                            .map
-                            .into_iter()
-                            .map(|(name, sparse_vector_params)| (name, sparse_vector_params.into()))
-                            .collect()
-                    }),
+                    sparse_vectors: params
+                        .sparse_vectors_config
+                        .map(|sparse_vectors| {
+                            sparse_vectors
+                                .map
+                                .into_iter()
+                                .map(|(name, sparse_vector_params)| {
+                                    Ok((name, sparse_vector_params.try_into()?))
+                                })
+                                .collect::<Result<_, Status>>()
+                        })
+                        .transpose()?,
                     shard_number: NonZeroU32::new(params.shard_number)
                         .ok_or_else(|| Status::invalid_argument("`shard_number` cannot be zero"))?,
                     on_disk_payload: params.on_disk_payload,
@@ -671,6 +1012,23 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                         .sharding_method
                         .map(sharding_method_from_proto)
                         .transpose()?,
+                    mmap_advice: params.mmap_advice.map(mmap_advice_from_proto).transpose()?,
+                    mmap_prefault: params
+                        .mmap_prefault
+                        .map(mmap_prefault_from_proto)
+                        .transpose()?,
+                    mmap_hot_percent: params.mmap_hot_percent,
+                    strict_payload_schema: strict_payload_schema_from_proto(
+                        params.strict_payload_schema,
+                    )?,
+                    payload_compression: params
+                        .payload_compression
+                        .map(payload_compression_from_proto)
+                        .transpose()?,
+                    payload_storage_backend: params
+                        .payload_storage_backend
+                        .map(payload_storage_backend_from_proto)
+                        .transpose()?,
                 },
             },
             hnsw_config: match config.hnsw_config {
@@ -679,7 +1037,7 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
             },
             optimizer_config: match config.optimizer_config {
                 None => return Err(Status::invalid_argument("Malformed OptimizerConfig type")),
-                Some(optimizer_config) => optimizer_config.into(),
+                Some(optimizer_config) => optimizer_config.try_into()?,
             },
             wal_config: match config.wal_config {
                 None => return Err(Status::invalid_argument("Malformed WalConfig type")),
@@ -880,6 +1238,7 @@ impl From<api::grpc::qdrant::CountResult> for CountResult {
     fn from(value: api::grpc::qdrant::CountResult) -> Self {
         Self {
             count: value.count as usize,
+            estimation: value.estimation.map(CountEstimation::from),
         }
     }
 }
@@ -888,10 +1247,46 @@ impl From<CountResult> for api::grpc::qdrant::CountResult {
     fn from(value: CountResult) -> Self {
         Self {
             count: value.count as u64,
+            estimation: value
+                .estimation
+                .map(api::grpc::qdrant::CountEstimation::from),
         }
     }
 }
 
+impl From<api::grpc::qdrant::CountEstimation> for CountEstimation {
+    fn from(value: api::grpc::qdrant::CountEstimation) -> Self {
+        Self {
+            min: value.min as usize,
+            exp: value.exp as usize,
+            max: value.max as usize,
+        }
+    }
+}
+
+impl From<CountEstimation> for api::grpc::qdrant::CountEstimation {
+    fn from(value: CountEstimation) -> Self {
+        Self {
+            min: value.min as u64,
+            exp: value.exp as u64,
+            max: value.max as u64,
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::FacetResponse> for FacetResponse {
+    type Error = Status;
+    fn try_from(value: api::grpc::qdrant::FacetResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            hits: value
+                .hits
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
 impl TryFrom<api::grpc::qdrant::SearchPoints> for CoreSearchRequest {
     type Error = Status;
     fn try_from(value: api::grpc::qdrant::SearchPoints) -> Result<Self, Self::Error> {
@@ -910,6 +1305,7 @@ impl TryFrom<api::grpc::qdrant::SearchPoints> for CoreSearchRequest {
             timeout: _,
             shard_key_selector: _,
             sparse_indices,
+            tie_breaker,
         } = value;
 
         let vector_struct =
@@ -924,6 +1320,7 @@ impl TryFrom<api::grpc::qdrant::SearchPoints> for CoreSearchRequest {
             with_payload: with_payload.map(TryInto::try_into).transpose()?,
             with_vector: with_vectors.map(Into::into),
             score_threshold: score_threshold.map(|s| s as ScoreType),
+            tie_breaker: tie_breaker.map(TryInto::try_into).transpose()?,
         })
     }
 }
@@ -940,6 +1337,8 @@ impl<'a> From<CollectionSearchRequest<'a>> for api::grpc::qdrant::SearchPoints {
                     data: vector.indices,
                 }),
             ),
+            // Plain search requests have no multivector shape, only core search forwards it
+            Vector::MultiDense(vector) => (vector.flattened_vectors, None),
         };
         Self {
             collection_name: collection_id,
@@ -959,6 +1358,7 @@ impl<'a> From<CollectionSearchRequest<'a>> for api::grpc::qdrant::SearchPoints {
             timeout: None,
             shard_key_selector: None,
             sparse_indices,
+            tie_breaker: request.tie_breaker.clone().map(|t| t.into()),
         }
     }
 }
@@ -1099,10 +1499,11 @@ impl TryFrom<api::grpc::qdrant::CoreSearchPoints> for CoreSearchRequest {
             .map(|query| {
                 Ok(match query {
                     api::grpc::qdrant::query_enum::Query::NearestNeighbors(vector) => {
-                        QueryEnum::Nearest(api::grpc::conversions::into_named_vector_struct(
+                        QueryEnum::Nearest(api::grpc::conversions::into_named_multi_vector_struct(
                             value.vector_name,
                             vector.data,
                             vector.indices.clone(),
+                            vector.vectors_count,
                         )?)
                     }
                     api::grpc::qdrant::query_enum::Query::RecommendBestScore(query) => {
@@ -1164,6 +1565,8 @@ impl TryFrom<api::grpc::qdrant::CoreSearchPoints> for CoreSearchRequest {
                     .unwrap_or_default(),
             ),
             score_threshold: value.score_threshold,
+            // Ties are resolved by the peer, which received the request from the client
+            tie_breaker: None,
         })
     }
 }
@@ -1190,6 +1593,10 @@ impl TryFrom<api::grpc::qdrant::SearchPoints> for SearchRequestInternal {
                     .unwrap_or_default(),
             ),
             score_threshold: value.score_threshold,
+            tie_breaker: value
+                .tie_breaker
+                .map(|tie_breaker| tie_breaker.try_into())
+                .transpose()?,
         })
     }
 }
@@ -1213,6 +1620,7 @@ impl TryFrom<api::grpc::qdrant::SearchPointGroups> for SearchGroupsRequestIntern
             timeout: None,
             shard_key_selector: None,
             sparse_indices: value.sparse_indices,
+            tie_breaker: None,
         };
 
         let SearchRequestInternal {
@@ -1224,6 +1632,7 @@ impl TryFrom<api::grpc::qdrant::SearchPointGroups> for SearchGroupsRequestIntern
             with_payload,
             with_vector,
             score_threshold,
+            tie_breaker: _,
         } = search_points.try_into()?;
 
         Ok(SearchGroupsRequestInternal {
@@ -1333,6 +1742,7 @@ impl From<api::grpc::qdrant::Vector> for RecommendExample {
         match vector {
             Vector::Dense(vector) => Self::Dense(vector),
             Vector::Sparse(vector) => Self::Sparse(vector),
+            Vector::MultiDense(vector) => Self::MultiDense(vector),
         }
     }
 }
@@ -1479,11 +1889,19 @@ impl From<VectorParams> for api::grpc::qdrant::VectorParams {
                 Distance::Euclid => api::grpc::qdrant::Distance::Euclid,
                 Distance::Dot => api::grpc::qdrant::Distance::Dot,
                 Distance::Manhattan => api::grpc::qdrant::Distance::Manhattan,
+                Distance::Hamming => api::grpc::qdrant::Distance::Hamming,
             }
             .into(),
             hnsw_config: value.hnsw_config.map(Into::into),
             quantization_config: value.quantization_config.map(Into::into),
             on_disk: value.on_disk,
+            index: value.index.map(Into::into),
+            multivector_config: value.multivector_config.map(Into::into),
+            datatype: value
+                .datatype
+                .map(|datatype| api::grpc::qdrant::Datatype::from(datatype) as i32),
+            keep_original: value.keep_original,
+            dimension_weights: value.dimension_weights.unwrap_or_default(),
         }
     }
 }
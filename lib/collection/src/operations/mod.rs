@@ -4,6 +4,7 @@ pub mod consistency_params;
 pub mod conversions;
 pub mod operation_effect;
 pub mod payload_ops;
+pub mod payload_schema;
 pub mod point_ops;
 pub mod shard_key_selector;
 pub mod shard_selector_internal;
@@ -36,6 +37,8 @@ pub enum FieldIndexOperations {
     CreateIndex(CreateIndex),
     /// Delete index for the field
     DeleteIndex(String),
+    /// Drop and build again index for the field, keeping its schema
+    RebuildIndex(String),
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -86,6 +89,7 @@ impl FieldIndexOperations {
         match self {
             FieldIndexOperations::CreateIndex(_) => true,
             FieldIndexOperations::DeleteIndex(_) => false,
+            FieldIndexOperations::RebuildIndex(_) => true,
         }
     }
 }
@@ -95,6 +99,7 @@ impl Validate for FieldIndexOperations {
         match self {
             FieldIndexOperations::CreateIndex(create_index) => create_index.validate(),
             FieldIndexOperations::DeleteIndex(_) => Ok(()),
+            FieldIndexOperations::RebuildIndex(_) => Ok(()),
         }
     }
 }
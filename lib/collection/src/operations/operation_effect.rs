@@ -127,6 +127,15 @@ impl EstimateOperationEffectArea for PayloadOps {
                     OperationEffectArea::Empty
                 }
             }
+            PayloadOps::RenamePayload(rename_payload) => {
+                if let Some(points) = &rename_payload.points {
+                    OperationEffectArea::Points(points.clone())
+                } else if let Some(filter) = &rename_payload.filter {
+                    OperationEffectArea::Filter(filter.clone())
+                } else {
+                    OperationEffectArea::Empty
+                }
+            }
         }
     }
 }
@@ -1,8 +1,9 @@
 use schemars::JsonSchema;
+use segment::common::utils::is_wildcard_path;
 use segment::types::{Filter, Payload, PayloadKeyType, PointIdType};
 use serde;
 use serde::{Deserialize, Serialize};
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 use super::{split_iter_by_shard, OperationToShard, SplitByShard};
 use crate::hash_ring::HashRing;
@@ -76,7 +77,8 @@ impl TryFrom<SetPayloadShadow> for SetPayload {
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 #[serde(try_from = "DeletePayloadShadow")]
 pub struct DeletePayload {
-    /// List of payload keys to remove from payload
+    /// List of payload keys to remove from payload.
+    /// Wildcard `*` matches any key of an object, e.g. `metadata.tmp.*`
     pub keys: Vec<PayloadKeyType>,
     /// Deletes values from each point in this list
     pub points: Option<Vec<PointIdType>>,
@@ -93,7 +95,8 @@ pub struct DeletePayload {
 /// as individual shard does not need to know about shard key
 #[derive(Debug, Deserialize, Serialize, Validate, Clone)]
 pub struct DeletePayloadOp {
-    /// List of payload keys to remove from payload
+    /// List of payload keys to remove from payload.
+    /// Wildcard `*` matches any key of an object, e.g. `metadata.tmp.*`
     pub keys: Vec<PayloadKeyType>,
     /// Deletes values from each point in this list
     pub points: Option<Vec<PointIdType>>,
@@ -126,6 +129,98 @@ impl TryFrom<DeletePayloadShadow> for DeletePayload {
     }
 }
 
+/// This data structure is used in API interface and applied across multiple shards
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(try_from = "RenamePayloadShadow")]
+#[validate(schema(function = "validate_rename_payload"))]
+pub struct RenamePayload {
+    /// Payload key to rename, nested keys are separated by dots
+    #[validate(length(min = 1))]
+    pub key: PayloadKeyType,
+    /// New payload key, existing value under this key is replaced
+    #[validate(length(min = 1))]
+    pub new_key: PayloadKeyType,
+    /// Renames key of each point in this list
+    pub points: Option<Vec<PointIdType>>,
+    /// Renames key of points that satisfy this filter condition
+    pub filter: Option<Filter>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// This data structure is used inside shard operations queue
+/// and supposed to be written into WAL of individual shard.
+///
+/// Unlike `RenamePayload` it does not contain `shard_key` field
+/// as individual shard does not need to know about shard key
+#[derive(Debug, Deserialize, Serialize, Validate, Clone)]
+#[validate(schema(function = "validate_rename_payload_op"))]
+pub struct RenamePayloadOp {
+    /// Payload key to rename, nested keys are separated by dots
+    pub key: PayloadKeyType,
+    /// New payload key, existing value under this key is replaced
+    pub new_key: PayloadKeyType,
+    /// Renames key of each point in this list
+    pub points: Option<Vec<PointIdType>>,
+    /// Renames key of points that satisfy this filter condition
+    pub filter: Option<Filter>,
+}
+
+#[derive(Deserialize)]
+struct RenamePayloadShadow {
+    pub key: PayloadKeyType,
+    pub new_key: PayloadKeyType,
+    pub points: Option<Vec<PointIdType>>,
+    pub filter: Option<Filter>,
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+impl TryFrom<RenamePayloadShadow> for RenamePayload {
+    type Error = PointsSelectorValidationError;
+
+    fn try_from(value: RenamePayloadShadow) -> Result<Self, Self::Error> {
+        if value.points.is_some() || value.filter.is_some() {
+            Ok(RenamePayload {
+                key: value.key,
+                new_key: value.new_key,
+                points: value.points,
+                filter: value.filter,
+                shard_key: value.shard_key,
+            })
+        } else {
+            Err(PointsSelectorValidationError)
+        }
+    }
+}
+
+fn validate_rename_payload(operation: &RenamePayload) -> Result<(), ValidationError> {
+    validate_rename_keys(&operation.key, &operation.new_key)
+}
+
+fn validate_rename_payload_op(operation: &RenamePayloadOp) -> Result<(), ValidationError> {
+    validate_rename_keys(&operation.key, &operation.new_key)
+}
+
+/// Validate that keys of the rename are different paths of object keys
+///
+/// Wildcards are rejected, as they may match multiple values, which can't be moved to one key.
+fn validate_rename_keys(key: &str, new_key: &str) -> Result<(), ValidationError> {
+    if key.contains('[') || new_key.contains('[') {
+        return Err(ValidationError::new(
+            "array notation is not supported in renamed keys",
+        ));
+    }
+    if is_wildcard_path(key) || is_wildcard_path(new_key) {
+        return Err(ValidationError::new(
+            "wildcards are not supported in renamed keys",
+        ));
+    }
+    if key == new_key {
+        return Err(ValidationError::new("new key must differ from the key"));
+    }
+    Ok(())
+}
+
 /// Define operations description for point payloads manipulation
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -140,6 +235,8 @@ pub enum PayloadOps {
     ClearPayloadByFilter(Filter),
     /// Overwrite full payload with given keys
     OverwritePayload(SetPayloadOp),
+    /// Move payload value to another key
+    RenamePayload(RenamePayloadOp),
 }
 
 impl PayloadOps {
@@ -150,6 +247,7 @@ impl PayloadOps {
             PayloadOps::ClearPayload { .. } => false,
             PayloadOps::ClearPayloadByFilter(_) => false,
             PayloadOps::OverwritePayload(_) => true,
+            PayloadOps::RenamePayload(_) => true,
         }
     }
 }
@@ -162,6 +260,7 @@ impl Validate for PayloadOps {
             PayloadOps::ClearPayload { .. } => Ok(()),
             PayloadOps::ClearPayloadByFilter(_) => Ok(()),
             PayloadOps::OverwritePayload(operation) => operation.validate(),
+            PayloadOps::RenamePayload(operation) => operation.validate(),
         }
     }
 }
@@ -181,6 +280,28 @@ impl SplitByShard for PayloadOps {
             PayloadOps::OverwritePayload(operation) => operation
                 .split_by_shard(ring)
                 .map(PayloadOps::OverwritePayload),
+            PayloadOps::RenamePayload(operation) => operation
+                .split_by_shard(ring)
+                .map(PayloadOps::RenamePayload),
+        }
+    }
+}
+
+impl SplitByShard for RenamePayloadOp {
+    fn split_by_shard(self, ring: &HashRing<ShardId>) -> OperationToShard<Self> {
+        match (&self.points, &self.filter) {
+            (Some(_), _) => {
+                split_iter_by_shard(self.points.unwrap(), |id| *id, ring).map(|points| {
+                    RenamePayloadOp {
+                        points: Some(points),
+                        key: self.key.clone(),
+                        new_key: self.new_key.clone(),
+                        filter: self.filter.clone(),
+                    }
+                })
+            }
+            (None, Some(_)) => OperationToShard::to_all(self),
+            (None, None) => OperationToShard::to_none(),
         }
     }
 }
@@ -293,4 +414,27 @@ mod tests {
             _ => panic!("Wrong operation"),
         }
     }
+
+    #[test]
+    fn test_rename_payload_validation() {
+        let rename = |key: &str, new_key: &str| RenamePayload {
+            key: key.to_string(),
+            new_key: new_key.to_string(),
+            points: Some(vec![1.into()]),
+            filter: None,
+            shard_key: None,
+        };
+
+        assert!(rename("metadata.tmp", "metadata.old").validate().is_ok());
+        assert!(rename("metadata.tmp", "metadata.tmp").validate().is_err());
+        assert!(rename("metadata.tags[]", "tags").validate().is_err());
+        assert!(rename("metadata.*", "old").validate().is_err());
+        assert!(rename("metadata.tmp", "old.*").validate().is_err());
+
+        let operation: PayloadOps = serde_json::from_str(
+            r#"{"rename_payload": {"key": "metadata.*.name", "new_key": "name", "points": [1]}}"#,
+        )
+        .unwrap();
+        assert!(operation.validate().is_err());
+    }
 }
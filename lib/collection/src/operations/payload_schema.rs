@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+
+use segment::types::{
+    parse_datetime, parse_uuid, GeoPoint, Payload, PayloadContainer, PayloadKeyType,
+    PayloadSchemaType,
+};
+use serde_json::Value;
+
+use super::payload_ops::PayloadOps;
+use super::point_ops::{PointInsertOperationsInternal, PointOperations};
+use super::types::{CollectionError, CollectionResult};
+use super::CollectionUpdateOperations;
+
+/// Declared types of payload fields.
+/// Payloads with undeclared top-level keys or values of other types are rejected.
+pub type StrictPayloadSchema = BTreeMap<PayloadKeyType, PayloadSchemaType>;
+
+/// Check payloads, assigned by the `operation`, against the strict payload schema
+///
+/// Returns `BadInput` error, which lists all mismatches as `key: reason` pairs.
+pub fn check_operation_payloads(
+    schema: &StrictPayloadSchema,
+    operation: &CollectionUpdateOperations,
+) -> CollectionResult<()> {
+    let mut violations = vec![];
+    match operation {
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(points)) => {
+            match points {
+                PointInsertOperationsInternal::PointsBatch(batch) => {
+                    let payloads = batch.payloads.iter().flatten();
+                    for (id, payload) in batch.ids.iter().zip(payloads) {
+                        if let Some(payload) = payload {
+                            violations.extend(
+                                payload_violations(schema, payload)
+                                    .map(|(key, reason)| format!("point {id}: {key}: {reason}")),
+                            );
+                        }
+                    }
+                }
+                PointInsertOperationsInternal::PointsList(points) => {
+                    for point in points {
+                        if let Some(payload) = &point.payload {
+                            let id = point.id;
+                            violations.extend(
+                                payload_violations(schema, payload)
+                                    .map(|(key, reason)| format!("point {id}: {key}: {reason}")),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        CollectionUpdateOperations::PayloadOperation(
+            PayloadOps::SetPayload(operation) | PayloadOps::OverwritePayload(operation),
+        ) => {
+            violations.extend(
+                payload_violations(schema, &operation.payload)
+                    .map(|(key, reason)| format!("{key}: {reason}")),
+            );
+        }
+        CollectionUpdateOperations::PayloadOperation(PayloadOps::RenamePayload(operation)) => {
+            let top_level_key = operation.new_key.split('.').next().unwrap_or_default();
+            if !is_declared(schema, top_level_key) {
+                violations.push(format!("{}: unexpected key", operation.new_key));
+            }
+        }
+        _ => {}
+    }
+
+    if violations.is_empty() {
+        return Ok(());
+    }
+    Err(CollectionError::bad_input(format!(
+        "Payload doesn't match strict payload schema of the collection: [{}]",
+        violations.join("; ")
+    )))
+}
+
+/// Find mismatches of the `payload` and the `schema` as `(key, reason)` pairs
+fn payload_violations<'a>(
+    schema: &'a StrictPayloadSchema,
+    payload: &'a Payload,
+) -> impl Iterator<Item = (&'a str, String)> + 'a {
+    let unexpected_keys = payload
+        .0
+        .keys()
+        .filter(move |key| !is_declared(schema, key))
+        .map(|key| (key.as_str(), "unexpected key".to_string()));
+
+    let wrong_types = schema.iter().filter_map(move |(key, schema_type)| {
+        payload
+            .get_value(key)
+            .into_iter()
+            .find_map(|value| find_wrong_value(value, *schema_type))
+            .map(|value| {
+                let reason = format!("expected {}, got {value}", schema_type_name(*schema_type));
+                (key.as_str(), reason)
+            })
+    });
+
+    unexpected_keys.chain(wrong_types)
+}
+
+/// Top-level `key` is declared by itself or as a beginning of a nested key
+fn is_declared(schema: &StrictPayloadSchema, key: &str) -> bool {
+    schema.keys().any(|declared| {
+        declared.strip_prefix(key).map_or(false, |rest| {
+            rest.is_empty() || rest.starts_with(['.', '['])
+        })
+    })
+}
+
+/// Find a value, which doesn't have the `schema_type`
+///
+/// Elements of an array are checked one by one, nested arrays never match.
+/// Null values are treated as missing and match any type.
+fn find_wrong_value(value: &Value, schema_type: PayloadSchemaType) -> Option<&Value> {
+    match value {
+        Value::Null => None,
+        Value::Array(values) => values.iter().find(|value| {
+            matches!(value, Value::Array(_)) || find_wrong_value(value, schema_type).is_some()
+        }),
+        _ => {
+            let is_of_type = match schema_type {
+                PayloadSchemaType::Keyword | PayloadSchemaType::Text => value.is_string(),
+                PayloadSchemaType::Integer => value.is_i64(),
+                PayloadSchemaType::Float => value.is_number(),
+                PayloadSchemaType::Bool => value.is_boolean(),
+                PayloadSchemaType::Geo => serde_json::from_value::<GeoPoint>(value.clone()).is_ok(),
+                PayloadSchemaType::Datetime => value.as_str().and_then(parse_datetime).is_some(),
+                PayloadSchemaType::Uuid => value.as_str().and_then(parse_uuid).is_some(),
+            };
+            (!is_of_type).then_some(value)
+        }
+    }
+}
+
+fn schema_type_name(schema_type: PayloadSchemaType) -> &'static str {
+    match schema_type {
+        PayloadSchemaType::Keyword => "keyword",
+        PayloadSchemaType::Integer => "integer",
+        PayloadSchemaType::Float => "float",
+        PayloadSchemaType::Geo => "geo",
+        PayloadSchemaType::Text => "text",
+        PayloadSchemaType::Bool => "bool",
+        PayloadSchemaType::Datetime => "datetime",
+        PayloadSchemaType::Uuid => "uuid",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_payload_violations() {
+        let schema: StrictPayloadSchema = [
+            ("city".to_string(), PayloadSchemaType::Keyword),
+            ("price".to_string(), PayloadSchemaType::Float),
+            ("location".to_string(), PayloadSchemaType::Geo),
+            ("meta.created".to_string(), PayloadSchemaType::Datetime),
+        ]
+        .into_iter()
+        .collect();
+
+        let payload: Payload = json!({
+            "city": ["Berlin", "Moscow"],
+            "price": 10,
+            "location": {"lon": 13.4, "lat": 52.5},
+            "meta": {"created": "2024-01-01T00:00:00Z"},
+        })
+        .into();
+        assert_eq!(payload_violations(&schema, &payload).count(), 0);
+
+        let payload: Payload = json!({
+            "city": ["Berlin", 1],
+            "price": null,
+            "color": "red",
+        })
+        .into();
+        let violations: Vec<_> = payload_violations(&schema, &payload).collect();
+        assert_eq!(
+            violations,
+            vec![
+                ("color", "unexpected key".to_string()),
+                ("city", "expected keyword, got 1".to_string()),
+            ]
+        );
+    }
+}
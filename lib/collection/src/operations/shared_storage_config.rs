@@ -1,6 +1,8 @@
 use std::num::NonZeroUsize;
+use std::sync::Arc;
 use std::time::Duration;
 
+use crate::common::optimization_budget::OptimizationBudget;
 use crate::operations::types::NodeType;
 
 /// Default timeout for search requests.
@@ -24,6 +26,8 @@ pub struct SharedStorageConfig {
     pub is_distributed: bool,
     pub incoming_shard_transfers_limit: Option<usize>,
     pub outgoing_shard_transfers_limit: Option<usize>,
+    /// Resources, available to optimizations of all collections on the node
+    pub optimization_budget: Arc<OptimizationBudget>,
 }
 
 impl Default for SharedStorageConfig {
@@ -38,6 +42,7 @@ impl Default for SharedStorageConfig {
             is_distributed: false,
             incoming_shard_transfers_limit: DEFAULT_IO_SHARD_TRANSFER_LIMIT,
             outgoing_shard_transfers_limit: DEFAULT_IO_SHARD_TRANSFER_LIMIT,
+            optimization_budget: Default::default(),
         }
     }
 }
@@ -54,6 +59,7 @@ impl SharedStorageConfig {
         is_distributed: bool,
         incoming_shard_transfers_limit: Option<usize>,
         outgoing_shard_transfers_limit: Option<usize>,
+        optimization_budget: Arc<OptimizationBudget>,
     ) -> Self {
         let update_queue_size = update_queue_size.unwrap_or(match node_type {
             NodeType::Normal => DEFAULT_UPDATE_QUEUE_SIZE,
@@ -69,6 +75,7 @@ impl SharedStorageConfig {
             is_distributed,
             incoming_shard_transfers_limit,
             outgoing_shard_transfers_limit,
+            optimization_budget,
         }
     }
 }
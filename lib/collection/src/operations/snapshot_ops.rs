@@ -4,6 +4,7 @@ use std::time::SystemTime;
 use api::grpc::conversions::date_time_to_proto;
 use chrono::NaiveDateTime;
 use schemars::JsonSchema;
+use segment::types::SegmentType;
 use serde::{Deserialize, Serialize};
 use url::Url;
 use validator::Validate;
@@ -188,3 +189,23 @@ impl TryFrom<api::grpc::qdrant::ShardSnapshotLocation> for ShardSnapshotLocation
         Ok(location)
     }
 }
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize, schemars::JsonSchema)]
+pub struct SegmentSnapshotRecover {
+    pub location: ShardSnapshotLocation,
+
+    /// Restore the snapshot, even if updates of the segment, which are newer than the snapshot,
+    /// are no longer in WAL and would be lost. Allows to replace a damaged segment.
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Segment of a local shard, which could be snapshotted and restored separately
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct SegmentDescription {
+    /// Identifier of the segment within the shard
+    pub id: String,
+    pub segment_type: SegmentType,
+    pub num_points: usize,
+    pub is_appendable: bool,
+}
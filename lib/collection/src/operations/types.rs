@@ -14,14 +14,19 @@ use merge::Merge;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::common::operation_error::OperationError;
+use segment::data_types::facets::FacetValueHit;
 use segment::data_types::groups::GroupId;
+use segment::data_types::order_by::{OrderBy, OrderValue, TieBreaker};
 use segment::data_types::vectors::{
-    DenseVector, Named, NamedQuery, NamedVectorStruct, QueryVector, Vector, VectorElementType,
-    VectorRef, VectorStruct, DEFAULT_VECTOR_NAME,
+    DenseVector, MultiDenseVector, Named, NamedQuery, NamedVectorStruct, QueryVector, Vector,
+    VectorElementType, VectorRef, VectorStruct, DEFAULT_VECTOR_NAME,
 };
+use segment::index::field_index::CardinalityEstimation;
+use segment::index::query_planner::SearchPlan;
 use segment::types::{
-    Distance, Filter, Payload, PayloadIndexInfo, PayloadKeyType, PointIdType, QuantizationConfig,
-    ScoredPoint, SearchParams, SeqNumberType, ShardKey, WithPayloadInterface, WithVector,
+    Distance, Filter, IvfConfig, MultiVectorConfig, Payload, PayloadIndexInfo, PayloadKeyType,
+    PointIdType, QuantizationConfig, ScoredPoint, SearchParams, SeqNumberType, ShardKey,
+    VamanaConfig, VectorStorageDatatype, WithPayloadInterface, WithVector,
 };
 use segment::vector_storage::query::context_query::ContextQuery;
 use segment::vector_storage::query::discovery_query::DiscoveryQuery;
@@ -90,6 +95,9 @@ pub struct Record {
     /// Shard Key
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKey>,
+    /// Value of the payload field, by which the points are ordered
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order_value: Option<OrderValue>,
 }
 
 /// Current statistics and configuration of the collection
@@ -281,6 +289,10 @@ pub struct ScrollRequestInternal {
     /// Whether to return the point vector with the result?
     #[serde(default, alias = "with_vectors")]
     pub with_vector: WithVector,
+    /// Order the records by a payload field, which must be indexed with an integer, float or
+    /// datetime index. Can't be used together with `offset`, use `start_from` for pagination.
+    #[validate]
+    pub order_by: Option<OrderBy>,
 }
 
 impl Default for ScrollRequestInternal {
@@ -291,6 +303,7 @@ impl Default for ScrollRequestInternal {
             filter: None,
             with_payload: Some(WithPayloadInterface::Bool(true)),
             with_vector: WithVector::Bool(false),
+            order_by: None,
         }
     }
 }
@@ -349,6 +362,11 @@ pub struct SearchRequestInternal {
     /// Score of the returned result might be higher or smaller than the threshold depending on the
     /// Distance function used. E.g. for cosine similarity only higher scores will be returned.
     pub score_threshold: Option<ScoreType>,
+    /// Order points with equal scores by a payload field, which must be indexed with an integer,
+    /// float or datetime index. Points without a value of the field go last.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub tie_breaker: Option<TieBreaker>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -414,6 +432,8 @@ pub struct CoreSearchRequest {
     /// Whether to return the point vector with the result?
     pub with_vector: Option<WithVector>,
     pub score_threshold: Option<ScoreType>,
+    /// Order points with equal scores by a payload field
+    pub tie_breaker: Option<TieBreaker>,
 }
 
 #[derive(Debug, Clone)]
@@ -421,6 +441,22 @@ pub struct CoreSearchRequestBatch {
     pub searches: Vec<CoreSearchRequest>,
 }
 
+/// Strategy of the search in a single segment, chosen by the query planner
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct SegmentSearchPlan {
+    /// Shard, which contains the segment
+    pub shard_id: ShardId,
+    #[serde(flatten)]
+    pub plan: SearchPlan,
+}
+
+/// Details of the search execution, returned on request
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct SearchDebugInfo {
+    /// Strategies of the search in segments of this peer
+    pub search_plans: Vec<SegmentSearchPlan>,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 pub struct SearchGroupsRequest {
     #[serde(flatten)]
@@ -491,6 +527,7 @@ pub enum RecommendExample {
     PointId(PointIdType),
     Dense(DenseVector),
     Sparse(SparseVector),
+    MultiDense(MultiDenseVector),
 }
 
 impl RecommendExample {
@@ -508,6 +545,7 @@ impl Validate for RecommendExample {
             RecommendExample::PointId(_) => Ok(()),
             RecommendExample::Dense(_) => Ok(()),
             RecommendExample::Sparse(sparse) => sparse.validate(),
+            RecommendExample::MultiDense(_) => Ok(()),
         }
     }
 }
@@ -842,11 +880,102 @@ pub const fn default_exact_count() -> bool {
     true
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetRequest {
+    #[serde(flatten)]
+    #[validate]
+    pub facet_request: FacetRequestInternal,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// Facet Request
+/// Counts the number of points with each value of a payload field.
+/// The field must be indexed with a keyword or integer index.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetRequestInternal {
+    /// Payload key to count values of
+    pub key: PayloadKeyType,
+    /// Max number of values to return, values with the most points go first. Default: 10
+    #[serde(default = "default_facet_limit")]
+    #[validate(range(min = 1))]
+    pub limit: usize,
+    /// Count only points which satisfy this conditions
+    #[validate]
+    pub filter: Option<Filter>,
+    /// If true, count points of every value exactly.
+    /// If false, each shard only reports its top values, so counts might be underestimated
+    /// in collections with many shards. Default: false
+    #[serde(default)]
+    pub exact: bool,
+}
+
+pub const fn default_facet_limit() -> usize {
+    10
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetResponse {
+    /// Values with number of points, ordered from the most frequent one
+    pub hits: Vec<FacetValueHit>,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct CountResult {
     /// Number of points which satisfy the conditions
     pub count: usize,
+    /// Bounds of the number of points which satisfy the conditions.
+    /// Only present if the count is not exact.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimation: Option<CountEstimation>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct CountEstimation {
+    /// Minimal possible number of points
+    pub min: usize,
+    /// Expected number of points
+    pub exp: usize,
+    /// Maximal possible number of points
+    pub max: usize,
+}
+
+impl CountEstimation {
+    pub const fn exact(count: usize) -> Self {
+        Self {
+            min: count,
+            exp: count,
+            max: count,
+        }
+    }
+}
+
+impl From<CardinalityEstimation> for CountEstimation {
+    fn from(estimation: CardinalityEstimation) -> Self {
+        Self {
+            min: estimation.min,
+            exp: estimation.exp,
+            max: estimation.max,
+        }
+    }
+}
+
+impl std::ops::Add for CountEstimation {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            min: self.min + other.min,
+            exp: self.exp + other.exp,
+            max: self.max + other.max,
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone)]
@@ -1014,12 +1143,19 @@ impl From<OperationError> for CollectionError {
                 error: format!("{err}"),
                 backtrace: None,
             },
+            OperationError::DataCorruption { .. } => Self::ServiceError {
+                error: format!("{err}"),
+                backtrace: None,
+            },
             OperationError::ValidationError { .. } => Self::BadInput {
                 description: format!("{err}"),
             },
             OperationError::WrongSparse => Self::BadInput {
                 description: "Conversion between sparse and regular vectors failed".to_string(),
             },
+            OperationError::WrongMulti => Self::BadInput {
+                description: "Conversion between multi and regular vectors failed".to_string(),
+            },
         }
     }
 }
@@ -1219,7 +1355,8 @@ impl Record {
 }
 
 /// Params of single vector data storage
-#[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+#[validate(schema(function = "validate_vector_params_datatype"))]
 #[serde(rename_all = "snake_case")]
 pub struct VectorParams {
     /// Size of a vectors used
@@ -1243,6 +1380,84 @@ pub struct VectorParams {
     /// Default: false
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub on_disk: Option<bool>,
+    /// Type of vector index, built for indexed segments. If none - HNSW index is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub index: Option<VectorIndexParams>,
+    /// If set, each point holds several vectors of `size`, which are compared as a whole.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multivector_config: Option<MultiVectorConfig>,
+    /// Element type, in which vectors are stored and scored. If none - `float32` is used.
+    /// Vectors are still sent and received as `float32` through the API.
+    /// Multivectors are only supported with `float32`, `uint8` is not supported with `Cosine` distance.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datatype: Option<VectorStorageDatatype>,
+    /// If true, `Cosine` vectors are stored as they were uploaded, instead of being normalized.
+    /// Scores are divided by cached vector norms, so original vectors are returned with `with_vector`.
+    /// Only supported with `float32` datatype, not supported for multivectors.
+    /// Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_original: Option<bool>,
+    /// Per-dimension weights, applied to each term of the distance during scoring.
+    /// Must contain exactly `size` non-negative values.
+    /// Only supported with `Euclid` and `Dot` distances for `float32` single vectors.
+    /// Quantization is not used for weighted vectors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimension_weights: Option<Vec<f32>>,
+}
+
+impl std::hash::Hash for VectorParams {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self {
+            size,
+            distance,
+            hnsw_config,
+            quantization_config,
+            on_disk,
+            index,
+            multivector_config,
+            datatype,
+            keep_original,
+            dimension_weights,
+        } = self;
+        size.hash(state);
+        distance.hash(state);
+        hnsw_config.hash(state);
+        quantization_config.hash(state);
+        on_disk.hash(state);
+        index.hash(state);
+        multivector_config.hash(state);
+        datatype.hash(state);
+        keep_original.hash(state);
+        // Float weights are not hashable, equal params still have equal number of weights
+        dimension_weights.as_ref().map(Vec::len).hash(state);
+    }
+}
+
+impl Eq for VectorParams {}
+
+/// Type of vector index
+#[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum VectorIndexParams {
+    /// Filterable HNSW index, configured with `hnsw_config`
+    Hnsw,
+    /// Single-layer Vamana graph, served from disk. Suitable for collections much larger than RAM,
+    /// if vectors are stored on disk as well. Does not speed up searches with restrictive filters.
+    Vamana(VamanaConfig),
+    /// Inverted lists of product-quantized vectors. Requires little memory, suitable for very large
+    /// collections, which are rarely updated. Only nearest neighbour search is accelerated.
+    Ivf(IvfConfig),
+}
+
+impl Validate for VectorIndexParams {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            VectorIndexParams::Hnsw => Ok(()),
+            VectorIndexParams::Vamana(vamana) => vamana.validate(),
+            VectorIndexParams::Ivf(ivf) => ivf.validate(),
+        }
+    }
 }
 
 /// Validate the value is in `[1, 65536]` or `None`.
@@ -1252,6 +1467,71 @@ pub fn validate_nonzerou64_range_min_1_max_65536(
     validate_range_generic(value.get(), Some(1), Some(65536))
 }
 
+/// Validate that multivectors are only stored with the default datatype.
+fn validate_vector_params_datatype(params: &VectorParams) -> Result<(), ValidationError> {
+    let datatype = params.datatype.unwrap_or_default();
+    if params.multivector_config.is_some() && datatype != VectorStorageDatatype::Float32 {
+        return Err(ValidationError::new(
+            "multivectors only support float32 datatype",
+        ));
+    }
+    // Normalized vectors can't be represented with integer elements
+    if params.distance == Distance::Cosine && datatype == VectorStorageDatatype::Uint8 {
+        return Err(ValidationError::new(
+            "uint8 datatype is not supported with Cosine distance",
+        ));
+    }
+    // Binary vectors are packed into bytes by the client
+    if params.distance == Distance::Hamming && datatype != VectorStorageDatatype::Uint8 {
+        return Err(ValidationError::new(
+            "Hamming distance is only supported with uint8 datatype",
+        ));
+    }
+    if params.keep_original == Some(true) {
+        if params.distance != Distance::Cosine {
+            return Err(ValidationError::new(
+                "keep_original is only supported with Cosine distance",
+            ));
+        }
+        if datatype != VectorStorageDatatype::Float32 || params.multivector_config.is_some() {
+            return Err(ValidationError::new(
+                "keep_original is only supported for float32 single vectors",
+            ));
+        }
+    }
+    if let Some(weights) = &params.dimension_weights {
+        if !matches!(params.distance, Distance::Euclid | Distance::Dot) {
+            return Err(ValidationError::new(
+                "dimension_weights are only supported with Euclid and Dot distances",
+            ));
+        }
+        if datatype != VectorStorageDatatype::Float32 || params.multivector_config.is_some() {
+            return Err(ValidationError::new(
+                "dimension_weights are only supported for float32 single vectors",
+            ));
+        }
+        if matches!(params.index, Some(VectorIndexParams::Ivf(_))) {
+            return Err(ValidationError::new(
+                "dimension_weights are not supported with IVF index",
+            ));
+        }
+        if weights.len() as u64 != params.size.get() {
+            return Err(ValidationError::new(
+                "dimension_weights must contain a weight for each dimension",
+            ));
+        }
+        if weights
+            .iter()
+            .any(|weight| !weight.is_finite() || *weight < 0.0)
+        {
+            return Err(ValidationError::new(
+                "dimension_weights must be finite non-negative numbers",
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// Is considered empty if `None` or if diff has no field specified
 fn is_hnsw_diff_empty(hnsw_config: &Option<HnswConfigDiff>) -> bool {
     hnsw_config
@@ -1295,6 +1575,10 @@ pub struct SparseIndexParams {
     /// Store index on disk. If set to false, the index will be stored in RAM. Default: false
     #[serde(skip_serializing_if = "Option::is_none")]
     pub on_disk: Option<bool>,
+    /// Type, in which weights are stored in the index on disk. If none - `float32` is used.
+    /// `float16` and `uint8` reduce posting list size at the cost of weight precision.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub datatype: Option<VectorStorageDatatype>,
 }
 
 impl Anonymize for SparseIndexParams {
@@ -1302,6 +1586,7 @@ impl Anonymize for SparseIndexParams {
         SparseIndexParams {
             full_scan_threshold: self.full_scan_threshold,
             on_disk: self.on_disk,
+            datatype: self.datatype,
         }
     }
 }
@@ -1311,6 +1596,7 @@ impl SparseIndexParams {
         SparseIndexParams {
             full_scan_threshold,
             on_disk,
+            datatype: None,
         }
     }
 
@@ -1321,6 +1607,9 @@ impl SparseIndexParams {
         if let Some(on_disk) = other.on_disk {
             self.on_disk = Some(on_disk);
         }
+        if let Some(datatype) = other.datatype {
+            self.datatype = Some(datatype);
+        }
     }
 }
 
@@ -1716,6 +2005,7 @@ impl From<SearchRequestInternal> for CoreSearchRequest {
             with_payload: request.with_payload,
             with_vector: request.with_vector,
             score_threshold: request.score_threshold,
+            tie_breaker: request.tie_breaker,
         }
     }
 }
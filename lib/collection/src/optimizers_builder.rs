@@ -1,5 +1,6 @@
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
 use schemars::JsonSchema;
 use segment::common::cpu::get_num_cpus;
@@ -19,6 +20,27 @@ const DEFAULT_MAX_SEGMENT_PER_CPU_KB: usize = 200_000;
 pub const DEFAULT_INDEXING_THRESHOLD_KB: usize = 20_000;
 const SEGMENTS_PATH: &str = "segments";
 const TEMP_SEGMENTS_PATH: &str = "temp_segments";
+/// Directory inside of temp segments, which keeps vector index build checkpoints over restarts
+pub const BUILD_CHECKPOINTS_PATH: &str = "hnsw_build_checkpoints";
+/// Build checkpoints, not modified for this long, are not expected to be resumed anymore
+const BUILD_CHECKPOINT_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Strategy of selecting segments to merge, once there are more segments than the target amount
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MergePolicy {
+    /// Merge the smallest segments, as long as the merged segment fits into `max_segment_size`
+    #[default]
+    MaxSegmentSizeBound,
+    /// Merge the smallest segments until the amount of segments fits the target,
+    /// even if the merged segment exceeds `max_segment_size`.
+    /// Minimizes the number of segments to search in, at the cost of rewriting large segments.
+    FewestSegments,
+    /// Merge only segments of similar size, which fit into `max_segment_size` together.
+    /// Each vector is rewritten a logarithmic number of times, which minimizes write amplification
+    /// under steady ingestion, at the cost of keeping more segments.
+    SizeTiered,
+}
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
 pub struct OptimizersConfig {
@@ -28,6 +50,10 @@ pub struct OptimizersConfig {
     /// The minimal number of vectors in a segment, required to perform segment optimization
     #[validate(range(min = 100))]
     pub vacuum_min_vector_number: usize,
+    /// Whether to rebuild segments with many deleted points, according to the thresholds above.
+    /// Could be disabled for append-only collections, which never delete or update points.
+    #[serde(default = "default_vacuum_enabled")]
+    pub vacuum_enabled: bool,
     /// Target amount of segments optimizer will try to keep.
     /// Real amount of segments may vary depending on multiple parameters:
     ///  - Amount of stored points
@@ -73,6 +99,10 @@ pub struct OptimizersConfig {
     pub flush_interval_sec: u64,
     /// Maximum available threads for optimization workers
     pub max_optimization_threads: usize,
+    /// Strategy of selecting segments to merge. Default is to merge the smallest segments,
+    /// as long as the merged segment fits into `max_segment_size`
+    #[serde(default)]
+    pub merge_policy: MergePolicy,
 }
 
 impl OptimizersConfig {
@@ -81,12 +111,14 @@ impl OptimizersConfig {
         Self {
             deleted_threshold: 0.1,
             vacuum_min_vector_number: 1000,
+            vacuum_enabled: true,
             default_segment_number: 0,
             max_segment_size: None,
             memmap_threshold: None,
             indexing_threshold: Some(100_000),
             flush_interval_sec: 60,
             max_optimization_threads: 0,
+            merge_policy: MergePolicy::default(),
         }
     }
 
@@ -111,20 +143,61 @@ impl OptimizersConfig {
     }
 }
 
+pub const fn default_vacuum_enabled() -> bool {
+    true
+}
+
+/// Remove leftovers of interrupted optimizations
+///
+/// Build checkpoints are kept, so that restarted optimizations could resume building the index.
 pub fn clear_temp_segments(shard_path: &Path) {
     let temp_segments_path = shard_path.join(TEMP_SEGMENTS_PATH);
-    if temp_segments_path.exists() {
-        log::debug!("Removing temp_segments directory: {:?}", temp_segments_path);
-        if let Err(err) = std::fs::remove_dir_all(&temp_segments_path) {
+    if !temp_segments_path.exists() {
+        return;
+    }
+    log::debug!("Removing temp_segments directory: {:?}", temp_segments_path);
+    let entries = match std::fs::read_dir(&temp_segments_path) {
+        Ok(entries) => entries,
+        Err(err) => {
             log::warn!(
-                "Failed to remove temp_segments directory: {:?}, error: {:?}",
+                "Failed to read temp_segments directory: {:?}, error: {:?}",
                 temp_segments_path,
                 err
             );
+            return;
+        }
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let result = if entry.file_name() == BUILD_CHECKPOINTS_PATH {
+            clear_stale_build_checkpoints(&path)
+        } else if path.is_dir() {
+            std::fs::remove_dir_all(&path)
+        } else {
+            std::fs::remove_file(&path)
+        };
+        if let Err(err) = result {
+            log::warn!(
+                "Failed to remove temp segment data: {:?}, error: {:?}",
+                path,
+                err
+            );
         }
     }
 }
 
+fn clear_stale_build_checkpoints(checkpoints_path: &Path) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(checkpoints_path)? {
+        let entry = entry?;
+        let age = entry.metadata()?.modified()?.elapsed().unwrap_or_default();
+        if age > BUILD_CHECKPOINT_MAX_AGE {
+            log::debug!("Removing stale build checkpoint: {:?}", entry.path());
+            std::fs::remove_file(entry.path())?;
+        }
+    }
+    Ok(())
+}
+
 pub fn build_optimizers(
     shard_path: &Path,
     collection_params: &CollectionParams,
@@ -152,9 +225,10 @@ pub fn build_optimizers(
         max_segment_size: optimizers_config.get_max_segment_size(),
     };
 
-    Arc::new(vec![
+    let mut optimizers: Vec<Arc<Optimizer>> = vec![
         Arc::new(MergeOptimizer::new(
             optimizers_config.get_number_segments(),
+            optimizers_config.merge_policy,
             threshold_config.clone(),
             segments_path.clone(),
             temp_segments_path.clone(),
@@ -170,7 +244,9 @@ pub fn build_optimizers(
             hnsw_config.clone(),
             quantization_config.clone(),
         )),
-        Arc::new(VacuumOptimizer::new(
+    ];
+    if optimizers_config.vacuum_enabled {
+        optimizers.push(Arc::new(VacuumOptimizer::new(
             optimizers_config.deleted_threshold,
             optimizers_config.vacuum_min_vector_number,
             threshold_config.clone(),
@@ -179,14 +255,16 @@ pub fn build_optimizers(
             collection_params.clone(),
             hnsw_config.clone(),
             quantization_config.clone(),
-        )),
-        Arc::new(ConfigMismatchOptimizer::new(
-            threshold_config,
-            segments_path,
-            temp_segments_path,
-            collection_params.clone(),
-            hnsw_config.clone(),
-            quantization_config.clone(),
-        )),
-    ])
+        )));
+    }
+    optimizers.push(Arc::new(ConfigMismatchOptimizer::new(
+        threshold_config,
+        segments_path,
+        temp_segments_path,
+        collection_params.clone(),
+        hnsw_config.clone(),
+        quantization_config.clone(),
+    )));
+
+    Arc::new(optimizers)
 }
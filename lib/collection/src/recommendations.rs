@@ -48,6 +48,12 @@ fn avg_vectors<'a>(vectors: impl Iterator<Item = VectorRef<'a>>) -> CollectionRe
                 sparse_count += 1;
                 avg_sparse = vector.combine_aggregate(&avg_sparse, |v1, v2| v1 + v2);
             }
+            VectorRef::MultiDense(_) => {
+                return Err(CollectionError::bad_input(
+                    "Multivectors can not be averaged, use `best_score` strategy instead"
+                        .to_owned(),
+                ));
+            }
         }
     }
 
@@ -320,6 +326,7 @@ fn recommend_by_avg_vector(
         )),
         filter: Some(Filter {
             should: None,
+            min_should: None,
             must: filter.clone().map(|filter| vec![Condition::Filter(filter)]),
             must_not: Some(vec![Condition::HasId(HasIdCondition {
                 has_id: reference_vectors_ids.iter().cloned().collect(),
@@ -331,6 +338,7 @@ fn recommend_by_avg_vector(
         limit,
         score_threshold,
         offset: offset.unwrap_or_default(),
+        tie_breaker: None,
     })
 }
 
@@ -383,6 +391,7 @@ fn recommend_by_best_score(
         query,
         filter: Some(Filter {
             should: None,
+            min_should: None,
             must: filter.map(|filter| vec![Condition::Filter(filter)]),
             must_not: Some(vec![Condition::HasId(HasIdCondition {
                 has_id: reference_vectors_ids.into_iter().collect(),
@@ -394,6 +403,7 @@ fn recommend_by_best_score(
         with_payload,
         with_vector,
         score_threshold,
+        tie_breaker: None,
     }
 }
 
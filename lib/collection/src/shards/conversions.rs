@@ -5,15 +5,16 @@ use api::grpc::qdrant::{
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollection,
     DeleteFieldIndexCollectionInternal, DeletePayloadPoints, DeletePayloadPointsInternal,
     DeletePointVectors, DeletePoints, DeletePointsInternal, DeleteVectorsInternal, PointVectors,
-    PointsIdsList, PointsSelector, SetPayloadPoints, SetPayloadPointsInternal, SyncPoints,
-    SyncPointsInternal, UpdatePointVectors, UpdateVectorsInternal, UpsertPoints,
-    UpsertPointsInternal, VectorsSelector,
+    PointsIdsList, PointsSelector, RebuildFieldIndexCollection,
+    RebuildFieldIndexCollectionInternal, RenamePayloadPoints, RenamePayloadPointsInternal,
+    SetPayloadPoints, SetPayloadPointsInternal, SyncPoints, SyncPointsInternal, UpdatePointVectors,
+    UpdateVectorsInternal, UpsertPoints, UpsertPointsInternal, VectorsSelector,
 };
 use segment::types::{Filter, PayloadFieldSchema, PayloadSchemaParams, PointIdType, ScoredPoint};
 use tonic::Status;
 
 use crate::operations::conversions::write_ordering_to_proto;
-use crate::operations::payload_ops::{DeletePayloadOp, SetPayloadOp};
+use crate::operations::payload_ops::{DeletePayloadOp, RenamePayloadOp, SetPayloadOp};
 use crate::operations::point_ops::{
     PointInsertOperationsInternal, PointSyncOperation, WriteOrdering,
 };
@@ -257,6 +258,39 @@ pub fn internal_delete_payload(
     }
 }
 
+pub fn internal_rename_payload(
+    shard_id: Option<ShardId>,
+    collection_name: String,
+    rename_payload: RenamePayloadOp,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+) -> RenamePayloadPointsInternal {
+    let points_selector = if let Some(points) = rename_payload.points {
+        Some(PointsSelector {
+            points_selector_one_of: Some(PointsSelectorOneOf::Points(PointsIdsList {
+                ids: points.into_iter().map(|id| id.into()).collect(),
+            })),
+        })
+    } else {
+        rename_payload.filter.map(|filter| PointsSelector {
+            points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
+        })
+    };
+
+    RenamePayloadPointsInternal {
+        shard_id,
+        rename_payload_points: Some(RenamePayloadPoints {
+            collection_name,
+            wait: Some(wait),
+            key: rename_payload.key,
+            new_key: rename_payload.new_key,
+            points_selector,
+            ordering: ordering.map(write_ordering_to_proto),
+            shard_key_selector: None,
+        }),
+    }
+}
+
 pub fn internal_clear_payload(
     shard_id: Option<ShardId>,
     collection_name: String,
@@ -331,10 +365,28 @@ pub fn internal_create_index(
                     segment::types::PayloadSchemaType::Bool => {
                         api::grpc::qdrant::FieldType::Bool as i32
                     }
+                    segment::types::PayloadSchemaType::Datetime => {
+                        api::grpc::qdrant::FieldType::Datetime as i32
+                    }
+                    segment::types::PayloadSchemaType::Uuid => {
+                        api::grpc::qdrant::FieldType::Uuid as i32
+                    }
                 },
                 None,
             ),
             PayloadFieldSchema::FieldParams(field_params) => match field_params {
+                PayloadSchemaParams::Keyword(keyword_index_params) => (
+                    api::grpc::qdrant::FieldType::Keyword as i32,
+                    Some(keyword_index_params.into()),
+                ),
+                PayloadSchemaParams::Integer(integer_index_params) => (
+                    api::grpc::qdrant::FieldType::Integer as i32,
+                    Some(integer_index_params.into()),
+                ),
+                PayloadSchemaParams::Float(float_index_params) => (
+                    api::grpc::qdrant::FieldType::Float as i32,
+                    Some(float_index_params.into()),
+                ),
                 PayloadSchemaParams::Text(text_index_params) => (
                     api::grpc::qdrant::FieldType::Text as i32,
                     Some(text_index_params.into()),
@@ -375,6 +427,24 @@ pub fn internal_delete_index(
     }
 }
 
+pub fn internal_rebuild_index(
+    shard_id: Option<ShardId>,
+    collection_name: String,
+    rebuild_index: String,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+) -> RebuildFieldIndexCollectionInternal {
+    RebuildFieldIndexCollectionInternal {
+        shard_id,
+        rebuild_field_index_collection: Some(RebuildFieldIndexCollection {
+            collection_name,
+            wait: Some(wait),
+            field_name: rebuild_index,
+            ordering: ordering.map(write_ordering_to_proto),
+        }),
+    }
+}
+
 pub fn try_scored_point_from_grpc(
     point: api::grpc::qdrant::ScoredPoint,
     with_payload: bool,
@@ -3,14 +3,17 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::data_types::order_by::OrderBy;
+use segment::index::query_planner::SearchPlan;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
 use tokio::runtime::Handle;
 
 use crate::operations::types::{
-    CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CollectionError, CollectionInfo, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
+    CountRequestInternal, CountResult, FacetRequestInternal, FacetResponse, PointRequestInternal,
+    Record, UpdateResult,
 };
 use crate::operations::CollectionUpdateOperations;
 use crate::shards::shard_trait::ShardOperation;
@@ -41,6 +44,16 @@ impl DummyShard {
         self.dummy()
     }
 
+    pub async fn warmup(&self, _vector_name: Option<&str>) -> CollectionResult<()> {
+        // Dummy shard has no data to read
+        Ok(())
+    }
+
+    pub fn search_plans(&self, _request: &CoreSearchRequest) -> CollectionResult<Vec<SearchPlan>> {
+        // Dummy shard has no segments to plan the search in
+        Ok(vec![])
+    }
+
     pub fn get_telemetry_data(&self) -> LocalShardTelemetry {
         LocalShardTelemetry {
             variant_name: Some("dummy shard".into()),
@@ -72,6 +85,7 @@ impl ShardOperation for DummyShard {
         _: &WithPayloadInterface,
         _: &WithVector,
         _: Option<&Filter>,
+        _: Option<&OrderBy>,
         _: &Handle,
     ) -> CollectionResult<Vec<Record>> {
         self.dummy()
@@ -94,6 +108,15 @@ impl ShardOperation for DummyShard {
         self.dummy()
     }
 
+    async fn facet(
+        &self,
+        _: Arc<FacetRequestInternal>,
+        _: &Handle,
+        _: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        self.dummy()
+    }
+
     async fn retrieve(
         &self,
         _: Arc<PointRequestInternal>,
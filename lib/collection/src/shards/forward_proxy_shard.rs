@@ -3,6 +3,8 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::data_types::order_by::OrderBy;
+use segment::index::query_planner::SearchPlan;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
     WithVector,
@@ -13,8 +15,9 @@ use tokio::sync::Mutex;
 use super::update_tracker::UpdateTracker;
 use crate::operations::point_ops::{PointOperations, PointStruct, PointSyncOperation};
 use crate::operations::types::{
-    CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CollectionError, CollectionInfo, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
+    CountRequestInternal, CountResult, FacetRequestInternal, FacetResponse, PointRequestInternal,
+    Record, UpdateResult,
 };
 use crate::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
 use crate::shards::local_shard::LocalShard;
@@ -92,6 +95,7 @@ impl ForwardProxyShard {
                 &WithPayloadInterface::Bool(true),
                 &true.into(),
                 None,
+                None,
                 runtime_handle,
             )
             .await?;
@@ -150,6 +154,14 @@ impl ForwardProxyShard {
         self.wrapped_shard.on_optimizer_config_update().await
     }
 
+    pub async fn warmup(&self, vector_name: Option<&str>) -> CollectionResult<()> {
+        self.wrapped_shard.warmup(vector_name).await
+    }
+
+    pub fn search_plans(&self, request: &CoreSearchRequest) -> CollectionResult<Vec<SearchPlan>> {
+        self.wrapped_shard.search_plans(request)
+    }
+
     pub fn get_telemetry_data(&self) -> LocalShardTelemetry {
         self.wrapped_shard.get_telemetry_data()
     }
@@ -187,6 +199,7 @@ impl ShardOperation for ForwardProxyShard {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<Record>> {
         let local_shard = &self.wrapped_shard;
@@ -197,6 +210,7 @@ impl ShardOperation for ForwardProxyShard {
                 with_payload_interface,
                 with_vector,
                 filter,
+                order_by,
                 search_runtime_handle,
             )
             .await
@@ -223,6 +237,18 @@ impl ShardOperation for ForwardProxyShard {
         local_shard.count(request).await
     }
 
+    async fn facet(
+        &self,
+        request: Arc<FacetRequestInternal>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .facet(request, search_runtime_handle, timeout)
+            .await
+    }
+
     async fn retrieve(
         &self,
         request: Arc<PointRequestInternal>,
@@ -9,21 +9,24 @@ use arc_swap::ArcSwap;
 use common::panic;
 use indicatif::{ProgressBar, ProgressStyle};
 use itertools::Itertools;
+use memory::mmap_ops::PrefaultMmapPages;
 use parking_lot::{Mutex as ParkingMutex, RwLock};
 use segment::data_types::vectors::VectorElementType;
 use segment::entry::entry_point::SegmentEntry as _;
 use segment::index::field_index::CardinalityEstimation;
+use segment::index::query_planner::SearchPlan;
 use segment::segment::Segment;
 use segment::segment_constructor::{build_segment, load_segment};
 use segment::types::{
-    CompressionRatio, Filter, PayloadIndexInfo, PayloadKeyType, PayloadStorageType, PointIdType,
-    QuantizationConfig, SegmentConfig, SegmentType,
+    CompressionRatio, Filter, PayloadIndexInfo, PayloadKeyType, PointIdType, QuantizationConfig,
+    SegmentConfig, SegmentType,
 };
 use segment::utils::mem::Mem;
 use tokio::fs::{copy, create_dir_all, remove_dir_all};
 use tokio::runtime::Handle;
 use tokio::sync::mpsc::Sender;
 use tokio::sync::{mpsc, oneshot, Mutex, RwLock as TokioRwLock};
+use uuid::Uuid;
 use wal::{Wal, WalOptions};
 
 use super::update_tracker::UpdateTracker;
@@ -31,11 +34,12 @@ use crate::collection_manager::collection_updater::CollectionUpdater;
 use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder};
 use crate::collection_manager::optimizers::TrackerLog;
 use crate::common::file_utils::move_dir;
-use crate::config::CollectionConfig;
+use crate::config::{CollectionConfig, CollectionParams};
 use crate::operations::shared_storage_config::SharedStorageConfig;
+use crate::operations::snapshot_ops::SegmentDescription;
 use crate::operations::types::{
     check_sparse_compatible_with_segment_config, CollectionError, CollectionInfoInternal,
-    CollectionResult, CollectionStatus, OptimizersStatus,
+    CollectionResult, CollectionStatus, CoreSearchRequest, OptimizersStatus,
 };
 use crate::operations::CollectionUpdateOperations;
 use crate::optimizers_builder::{build_optimizers, clear_temp_segments};
@@ -198,6 +202,7 @@ impl LocalShard {
                         let mut res = load_segment(&segments_path)?;
                         if let Some(segment) = &mut res {
                             segment.check_consistency_and_repair()?;
+                            segment.verify_checksums_in_background();
                         } else {
                             std::fs::remove_dir_all(&segments_path).map_err(|err| {
                                 CollectionError::service_error(format!(
@@ -224,22 +229,7 @@ impl LocalShard {
                 continue;
             };
 
-            collection_config_read
-                .params
-                .vectors
-                .check_compatible_with_segment_config(&segment.config().vector_data, true)?;
-            collection_config_read
-                .params
-                .sparse_vectors
-                .as_ref()
-                .map(|sparse_vectors| {
-                    check_sparse_compatible_with_segment_config(
-                        sparse_vectors,
-                        &segment.config().sparse_vector_data,
-                        true,
-                    )
-                })
-                .unwrap_or(Ok(()))?;
+            Self::check_segment_compatibility(&collection_config_read.params, &segment)?;
 
             segment_holder.add(segment);
         }
@@ -286,17 +276,39 @@ impl LocalShard {
         // even to store half of the vector data.
         let do_mmap_prefault = available_memory_bytes * 2 > vectors_size_bytes;
 
-        if do_mmap_prefault {
-            for (_, segment) in collection.segments.read().iter() {
-                if let LockedSegment::Original(segment) = segment {
-                    segment.read().prefault_mmap_pages();
-                }
+        // Explicit collection settings take precedence over the heuristic
+        let collection_params = collection.collection_config.read().await.params.clone();
+
+        for (_, segment) in collection.segments.read().iter() {
+            if let LockedSegment::Original(segment) = segment {
+                collection_params.load_mmap_pages(&segment.read(), do_mmap_prefault);
             }
         }
 
         Ok(collection)
     }
 
+    /// Check that configuration of the segment is compatible with the collection
+    fn check_segment_compatibility(
+        collection_params: &CollectionParams,
+        segment: &Segment,
+    ) -> CollectionResult<()> {
+        collection_params
+            .vectors
+            .check_compatible_with_segment_config(&segment.config().vector_data, true)?;
+        collection_params
+            .sparse_vectors
+            .as_ref()
+            .map(|sparse_vectors| {
+                check_sparse_compatible_with_segment_config(
+                    sparse_vectors,
+                    &segment.config().sparse_vector_data,
+                    true,
+                )
+            })
+            .unwrap_or(Ok(()))
+    }
+
     pub fn shard_path(&self) -> PathBuf {
         self.path.clone()
     }
@@ -371,11 +383,8 @@ impl LocalShard {
             let segment_config = SegmentConfig {
                 vector_data: vector_params.clone(),
                 sparse_vector_data: sparse_vector_params.clone(),
-                payload_storage_type: if config.params.on_disk_payload {
-                    PayloadStorageType::OnDisk
-                } else {
-                    PayloadStorageType::InMemory
-                },
+                payload_storage_type: config.params.payload_storage_type(),
+                payload_compression: config.params.payload_compression,
             };
             let segment = thread::Builder::new()
                 .name(format!("shard-build-{collection_id}-{id}"))
@@ -501,6 +510,38 @@ impl LocalShard {
         Ok(())
     }
 
+    /// Read memory-mapped vector storages and indexes of the shard into the page cache
+    ///
+    /// Only files of the given vector are read, if `vector_name` is specified.
+    pub async fn warmup(&self, vector_name: Option<&str>) -> CollectionResult<()> {
+        let mut tasks = Vec::new();
+        for (_, segment) in self.segments.read().iter() {
+            // Proxy segments only exist during optimization, their data is about to be replaced
+            if let LockedSegment::Original(segment) = segment {
+                tasks.extend(segment.read().vector_mmap_pages(vector_name)?);
+            }
+        }
+
+        tokio::task::spawn_blocking(move || tasks.iter().for_each(PrefaultMmapPages::exec)).await?;
+
+        Ok(())
+    }
+
+    /// Strategies, which the query planner chooses for the request in each segment of the shard
+    ///
+    /// Segments without a vector index, which supports planning, are skipped.
+    pub fn search_plans(&self, request: &CoreSearchRequest) -> CollectionResult<Vec<SearchPlan>> {
+        let mut plans = Vec::new();
+        for (_, segment) in self.segments.read().iter() {
+            plans.extend(segment.get().read().search_plan(
+                request.query.get_vector_name(),
+                request.filter.as_ref(),
+                request.params.as_ref(),
+            )?);
+        }
+        Ok(plans)
+    }
+
     pub async fn on_optimizer_config_update(&self) -> CollectionResult<()> {
         let config = self.collection_config.read().await;
         let mut update_handler = self.update_handler.lock().await;
@@ -663,6 +704,209 @@ impl LocalShard {
         Ok(())
     }
 
+    /// List segments of the shard, which could be snapshotted separately
+    pub fn list_segments(&self) -> Vec<SegmentDescription> {
+        self.segments
+            .read()
+            .iter()
+            .map(|(_id, segment)| {
+                let info = segment.get().read().info();
+                SegmentDescription {
+                    id: segment.name(),
+                    segment_type: info.segment_type,
+                    num_points: info.num_points,
+                    is_appendable: info.is_appendable,
+                }
+            })
+            .collect()
+    }
+
+    /// Create snapshot of a single segment into `target_path`
+    ///
+    /// Returns path of the created segment archive.
+    pub async fn create_segment_snapshot(
+        &self,
+        segment_name: &str,
+        temp_path: &Path,
+        target_path: &Path,
+    ) -> CollectionResult<PathBuf> {
+        // Make sure that all submitted by this point updates have made it to the segments
+        let (tx, rx) = oneshot::channel();
+        let plunger = UpdateSignal::Plunger(tx);
+        self.update_sender.load().send(plunger).await?;
+        rx.await?;
+
+        let segments = self.segments.clone();
+        let segment_name = segment_name.to_owned();
+        let temp_path = temp_path.to_owned();
+        let target_path = target_path.to_owned();
+
+        tokio::task::spawn_blocking(move || -> CollectionResult<_> {
+            let segments_read = segments.read();
+
+            let (_, segment) = segments_read
+                .get_by_name(&segment_name)
+                .ok_or_else(|| segment_not_found_error(&segment_name))?;
+
+            // Proxy segment doesn't contain all changes of the wrapped segment
+            if let LockedSegment::Proxy(_) = segment {
+                return Err(segment_is_optimized_error(&segment_name));
+            }
+
+            let segment_arc = segment.get();
+            let snapshot_path = segment_arc.read().take_snapshot(&temp_path, &target_path)?;
+            Ok(snapshot_path)
+        })
+        .await?
+    }
+
+    /// Restore a single segment of the shard from the segment archive
+    ///
+    /// The restored segment replaces the segment with the given name, if it is present in the
+    /// shard, otherwise it is added to the shard. Points, which are also stored in other segments,
+    /// are deduplicated by version, so that more recent updates of those points are preserved.
+    ///
+    /// Operations from WAL, which are newer than the snapshot, are re-applied to the restored
+    /// segment, so snapshots older than the segment they replace could be restored as well.
+    /// If WAL is already truncated past the snapshot, the operations in between are lost,
+    /// so such a restore is rejected, unless `force` is set.
+    pub async fn restore_segment_snapshot(
+        &self,
+        segment_name: &str,
+        snapshot_path: &Path,
+        force: bool,
+    ) -> CollectionResult<()> {
+        let collection_params = self.collection_config.read().await.params.clone();
+
+        let segments = self.segments.clone();
+        let wal = self.wal.clone();
+        let segment_name = segment_name.to_owned();
+        let snapshot_path = snapshot_path.to_owned();
+        let shard_path = self.path.clone();
+
+        tokio::task::spawn_blocking(move || -> CollectionResult<_> {
+            // Unpack the archive next to the shard, so that the segment is moved atomically
+            let restore_dir = tempfile::Builder::new()
+                .prefix("segment-restore-")
+                .tempdir_in(&shard_path)?;
+            let restored_name = Uuid::new_v4().to_string();
+            let restore_path = restore_dir.path().join(&restored_name);
+            Segment::restore_snapshot_into(&snapshot_path, &restore_path)?;
+
+            // Validate the segment before it is moved into the shard, so that invalid data never
+            // appears in the segments directory. Temporary directory is removed on error.
+            {
+                let segment = load_segment(&restore_path)?.ok_or_else(|| {
+                    CollectionError::bad_input(format!(
+                        "Invalid segment snapshot {}",
+                        snapshot_path.display(),
+                    ))
+                })?;
+                // Restored files are about to replace live data, so corruption must be detected first
+                segment.verify_checksums()?;
+                Self::check_segment_compatibility(&collection_params, &segment)?;
+            }
+
+            let segment_path = Self::segments_path(&shard_path).join(&restored_name);
+            std::fs::rename(&restore_path, &segment_path)?;
+
+            let segment = match load_segment(&segment_path) {
+                Ok(Some(segment)) => segment,
+                Ok(None) => {
+                    std::fs::remove_dir_all(&segment_path)?;
+                    return Err(CollectionError::service_error(format!(
+                        "Failed to load restored segment {}",
+                        segment_path.display(),
+                    )));
+                }
+                Err(err) => {
+                    std::fs::remove_dir_all(&segment_path)?;
+                    return Err(err.into());
+                }
+            };
+
+            let snapshot_version = segment.version();
+
+            // Hold WAL lock, so that no new operations are written until they are replayed
+            let mut wal_lock = wal.lock();
+
+            let (restored_id, replaced_segments) = {
+                let mut segments_write = segments.write();
+
+                let existing = segments_write.get_by_name(&segment_name);
+                let (replace_ids, current_version) = match existing {
+                    Some((_, LockedSegment::Proxy(_))) => {
+                        segment.drop_data()?;
+                        return Err(segment_is_optimized_error(&segment_name));
+                    }
+                    Some((segment_id, LockedSegment::Original(original))) => {
+                        (vec![segment_id], Some(original.read().version()))
+                    }
+                    None => (vec![], None),
+                };
+
+                // Operations between the snapshot and the first WAL entry can't be re-applied.
+                // They don't affect the segment only if it is not newer than the snapshot.
+                let first_wal_index = wal_lock.first_index();
+                let is_wal_truncated = snapshot_version + 1 < first_wal_index;
+                let is_up_to_date =
+                    current_version.map_or(false, |version| version <= snapshot_version);
+                if is_wal_truncated && !is_up_to_date {
+                    if !force {
+                        segment.drop_data()?;
+                        return Err(CollectionError::bad_input(format!(
+                            "Snapshot of segment {segment_name} has version {snapshot_version}, \
+                             but WAL only contains operations since {first_wal_index}, \
+                             newer updates of the segment would be lost",
+                        )));
+                    }
+                    log::warn!(
+                        "Forced restore of segment {segment_name} from snapshot version \
+                         {snapshot_version}, operations before {first_wal_index} are lost",
+                    );
+                }
+
+                segments_write.swap(segment, &replace_ids)
+            };
+
+            let deduplicated = segments.read().deduplicate_points()?;
+            if deduplicated > 0 {
+                log::debug!("Deduplicated {deduplicated} points of restored segment");
+            }
+
+            // Re-apply operations, which are not contained in the snapshot.
+            // Points of other segments are skipped by version, if already applied.
+            let replay_from = (snapshot_version + 1).max(wal_lock.first_index());
+            for (op_num, operation) in wal_lock.read(replay_from) {
+                match CollectionUpdater::update(&segments, op_num, operation) {
+                    Err(
+                        err @ (CollectionError::ServiceError { .. }
+                        | CollectionError::OutOfMemory { .. }),
+                    ) => return Err(err),
+                    Err(err) => log::debug!("Skipped WAL operation {op_num} on restore: {err}"),
+                    Ok(_) => (),
+                }
+            }
+
+            // WAL must not be acknowledged past the restored data, so that operations are
+            // still replayed on the restored segment after restart
+            let flushed_version = segments.read().flush_all(true)?;
+            let restored_version = segments
+                .read()
+                .get(restored_id)
+                .map_or(flushed_version, |segment| segment.get().read().version());
+            wal_lock.ack(flushed_version.min(restored_version))?;
+            drop(wal_lock);
+
+            for replaced_segment in replaced_segments {
+                replaced_segment.drop_data()?;
+            }
+
+            Ok(())
+        })
+        .await?
+    }
+
     pub fn estimate_cardinality<'a>(
         &'a self,
         filter: Option<&'a Filter>,
@@ -837,3 +1081,15 @@ impl Drop for LocalShard {
         })
     }
 }
+
+fn segment_not_found_error(segment_name: &str) -> CollectionError {
+    CollectionError::NotFound {
+        what: format!("segment {segment_name}"),
+    }
+}
+
+fn segment_is_optimized_error(segment_name: &str) -> CollectionError {
+    CollectionError::bad_request(format!(
+        "Segment {segment_name} is being optimized, try again later"
+    ))
+}
@@ -1,9 +1,12 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
 use futures::future::try_join_all;
 use itertools::Itertools;
+use segment::data_types::facets::{merge_facet_counts, top_facet_hits};
+use segment::data_types::order_by::{OrderBy, OrderValue};
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -14,8 +17,8 @@ use crate::collection_manager::segments_searcher::SegmentsSearcher;
 use crate::common::stopping_guard::StoppingGuard;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, QueryEnum, Record, UpdateResult,
-    UpdateStatus,
+    CountRequestInternal, CountResult, FacetRequestInternal, FacetResponse, PointRequestInternal,
+    QueryEnum, Record, UpdateResult, UpdateStatus,
 };
 use crate::operations::CollectionUpdateOperations;
 use crate::optimizers_builder::DEFAULT_INDEXING_THRESHOLD_KB;
@@ -100,6 +103,127 @@ impl LocalShard {
             .collect();
         Ok(top_results)
     }
+
+    async fn scroll_by_id(
+        &self,
+        offset: Option<ExtendedPointId>,
+        limit: usize,
+        with_payload_interface: &WithPayloadInterface,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<Record>> {
+        // ToDo: Make faster points selection with a set
+        let segments = self.segments();
+        let read_handles: Vec<_> = {
+            let segments_guard = segments.read();
+            segments_guard
+                .iter()
+                .map(|(_, segment)| {
+                    let segment = segment.clone();
+                    let filter = filter.cloned();
+                    search_runtime_handle.spawn_blocking(move || {
+                        segment
+                            .get()
+                            .read()
+                            .read_filtered(offset, Some(limit), filter.as_ref())
+                    })
+                })
+                .collect()
+        };
+        let all_points = try_join_all(read_handles).await?;
+
+        let point_ids = all_points
+            .into_iter()
+            .flatten()
+            .sorted()
+            .dedup()
+            .take(limit)
+            .collect_vec();
+
+        let with_payload = WithPayload::from(with_payload_interface);
+        let mut points =
+            SegmentsSearcher::retrieve(segments, &point_ids, &with_payload, with_vector)?;
+        points.sort_by_key(|point| point.id);
+
+        Ok(points)
+    }
+
+    async fn scroll_by_field(
+        &self,
+        limit: usize,
+        with_payload_interface: &WithPayloadInterface,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        order_by: &OrderBy,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<Record>> {
+        let segments = self.segments();
+        let read_handles: Vec<_> = {
+            let segments_guard = segments.read();
+            segments_guard
+                .iter()
+                .map(|(_, segment)| {
+                    let segment = segment.clone();
+                    let filter = filter.cloned();
+                    let order_by = order_by.clone();
+                    search_runtime_handle.spawn_blocking(move || {
+                        segment.get().read().read_ordered_filtered(
+                            Some(limit),
+                            filter.as_ref(),
+                            &order_by,
+                        )
+                    })
+                })
+                .collect()
+        };
+        let all_points = try_join_all(read_handles)
+            .await?
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Same point might be found in several segments, keep its first value in the order
+        let mut order_values: HashMap<ExtendedPointId, OrderValue> = HashMap::new();
+        for (value, point_id) in all_points.into_iter().flatten() {
+            order_values
+                .entry(point_id)
+                .and_modify(|current| {
+                    if order_by.cmp_values(value, *current).is_lt() {
+                        *current = value;
+                    }
+                })
+                .or_insert(value);
+        }
+        let ordered_points = order_values
+            .into_iter()
+            .sorted_unstable_by(|(id, value), (other_id, other_value)| {
+                order_by
+                    .cmp_values(*value, *other_value)
+                    .then_with(|| id.cmp(other_id))
+            })
+            .take(limit)
+            .collect_vec();
+
+        let point_ids = ordered_points.iter().map(|(id, _)| *id).collect_vec();
+        let with_payload = WithPayload::from(with_payload_interface);
+        let mut records: HashMap<_, _> =
+            SegmentsSearcher::retrieve(segments, &point_ids, &with_payload, with_vector)?
+                .into_iter()
+                .map(|record| (record.id, record))
+                .collect();
+
+        // Retrieved records are not ordered, restore the order of the values
+        let ordered_records = ordered_points
+            .into_iter()
+            .filter_map(|(point_id, value)| {
+                let mut record = records.remove(&point_id)?;
+                record.order_value = Some(value);
+                Some(record)
+            })
+            .collect();
+
+        Ok(ordered_records)
+    }
 }
 #[async_trait]
 impl ShardOperation for LocalShard {
@@ -153,42 +277,33 @@ impl ShardOperation for LocalShard {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<Record>> {
-        // ToDo: Make faster points selection with a set
-        let segments = self.segments();
-        let read_handles: Vec<_> = {
-            let segments_guard = segments.read();
-            segments_guard
-                .iter()
-                .map(|(_, segment)| {
-                    let segment = segment.clone();
-                    let filter = filter.cloned();
-                    search_runtime_handle.spawn_blocking(move || {
-                        segment
-                            .get()
-                            .read()
-                            .read_filtered(offset, Some(limit), filter.as_ref())
-                    })
-                })
-                .collect()
-        };
-        let all_points = try_join_all(read_handles).await?;
-
-        let point_ids = all_points
-            .into_iter()
-            .flatten()
-            .sorted()
-            .dedup()
-            .take(limit)
-            .collect_vec();
-
-        let with_payload = WithPayload::from(with_payload_interface);
-        let mut points =
-            SegmentsSearcher::retrieve(segments, &point_ids, &with_payload, with_vector)?;
-        points.sort_by_key(|point| point.id);
-
-        Ok(points)
+        match order_by {
+            None => {
+                self.scroll_by_id(
+                    offset,
+                    limit,
+                    with_payload_interface,
+                    with_vector,
+                    filter,
+                    search_runtime_handle,
+                )
+                .await
+            }
+            Some(order_by) => {
+                self.scroll_by_field(
+                    limit,
+                    with_payload_interface,
+                    with_vector,
+                    filter,
+                    order_by,
+                    search_runtime_handle,
+                )
+                .await
+            }
+        }
     }
 
     /// Collect overview information about the shard
@@ -207,13 +322,70 @@ impl ShardOperation for LocalShard {
     }
 
     async fn count(&self, request: Arc<CountRequestInternal>) -> CollectionResult<CountResult> {
-        let total_count = if request.exact {
+        if request.exact {
             let all_points = self.read_filtered(request.filter.as_ref())?;
-            all_points.len()
+            Ok(CountResult {
+                count: all_points.len(),
+                estimation: None,
+            })
         } else {
-            self.estimate_cardinality(request.filter.as_ref())?.exp
+            let estimation = self.estimate_cardinality(request.filter.as_ref())?;
+            Ok(CountResult {
+                count: estimation.exp,
+                estimation: Some(estimation.into()),
+            })
+        }
+    }
+
+    async fn facet(
+        &self,
+        request: Arc<FacetRequestInternal>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        let is_stopped = StoppingGuard::new();
+
+        let read_handles: Vec<_> = {
+            let segments_guard = self.segments.read();
+            segments_guard
+                .iter()
+                .map(|(_, segment)| {
+                    let segment = segment.clone();
+                    let request = request.clone();
+                    let is_stopped = is_stopped.get_is_stopped();
+                    search_runtime_handle.spawn_blocking(move || {
+                        segment.get().read().facet(
+                            &request.key,
+                            request.filter.as_ref(),
+                            &is_stopped,
+                        )
+                    })
+                })
+                .collect()
         };
-        Ok(CountResult { count: total_count })
+
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+
+        let segments_counts = tokio::time::timeout(timeout, try_join_all(read_handles))
+            .await
+            .map_err(|_| {
+                log::debug!("Facet timeout reached: {} seconds", timeout.as_secs());
+                // StoppingGuard takes care of setting is_stopped to true
+                CollectionError::timeout(timeout.as_secs() as usize, "Facet")
+            })??;
+
+        let mut shard_counts = Vec::with_capacity(segments_counts.len());
+        for segment_counts in segments_counts {
+            shard_counts.extend(segment_counts?);
+        }
+        let counts = merge_facet_counts(shard_counts);
+
+        // Other shards may have more points of values, which are not in the top of this shard,
+        // so exact request needs all of them
+        let limit = (!request.exact).then_some(request.limit);
+        Ok(FacetResponse {
+            hits: top_facet_hits(counts, limit),
+        })
     }
 
     async fn retrieve(
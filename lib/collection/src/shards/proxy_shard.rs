@@ -5,6 +5,8 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::data_types::order_by::OrderBy;
+use segment::index::query_planner::SearchPlan;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
     WithVector,
@@ -18,8 +20,9 @@ use crate::operations::operation_effect::{
     EstimateOperationEffectArea, OperationEffectArea, PointsOperationEffect,
 };
 use crate::operations::types::{
-    CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CollectionError, CollectionInfo, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
+    CountRequestInternal, CountResult, FacetRequestInternal, FacetResponse, PointRequestInternal,
+    Record, UpdateResult,
 };
 use crate::operations::CollectionUpdateOperations;
 use crate::shards::local_shard::LocalShard;
@@ -117,6 +120,14 @@ impl ProxyShard {
         Ok(())
     }
 
+    pub async fn warmup(&self, vector_name: Option<&str>) -> CollectionResult<()> {
+        self.wrapped_shard.warmup(vector_name).await
+    }
+
+    pub fn search_plans(&self, request: &CoreSearchRequest) -> CollectionResult<Vec<SearchPlan>> {
+        self.wrapped_shard.search_plans(request)
+    }
+
     pub fn get_telemetry_data(&self) -> LocalShardTelemetry {
         self.wrapped_shard.get_telemetry_data()
     }
@@ -180,6 +191,7 @@ impl ShardOperation for ProxyShard {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<Record>> {
         let local_shard = &self.wrapped_shard;
@@ -190,6 +202,7 @@ impl ShardOperation for ProxyShard {
                 with_payload_interface,
                 with_vector,
                 filter,
+                order_by,
                 search_runtime_handle,
             )
             .await
@@ -220,6 +233,19 @@ impl ShardOperation for ProxyShard {
         local_shard.count(request).await
     }
 
+    /// Forward read-only `facet` to `wrapped_shard`
+    async fn facet(
+        &self,
+        request: Arc<FacetRequestInternal>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .facet(request, search_runtime_handle, timeout)
+            .await
+    }
+
     /// Forward read-only `retrieve` to `wrapped_shard`
     async fn retrieve(
         &self,
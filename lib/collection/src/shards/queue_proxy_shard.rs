@@ -4,6 +4,8 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::data_types::order_by::OrderBy;
+use segment::index::query_planner::SearchPlan;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -15,8 +17,9 @@ use super::transfer::driver::MAX_RETRY_COUNT;
 use super::update_tracker::UpdateTracker;
 use crate::operations::point_ops::WriteOrdering;
 use crate::operations::types::{
-    CollectionInfo, CollectionResult, CoreSearchRequestBatch, CountRequestInternal, CountResult,
-    PointRequestInternal, Record, UpdateResult,
+    CollectionInfo, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
+    CountRequestInternal, CountResult, FacetRequestInternal, FacetResponse, PointRequestInternal,
+    Record, UpdateResult,
 };
 use crate::operations::CollectionUpdateOperations;
 use crate::shards::local_shard::LocalShard;
@@ -103,6 +106,23 @@ impl QueueProxyShard {
             .await
     }
 
+    pub async fn warmup(&self, vector_name: Option<&str>) -> CollectionResult<()> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .warmup(vector_name)
+            .await
+    }
+
+    pub fn search_plans(&self, request: &CoreSearchRequest) -> CollectionResult<Vec<SearchPlan>> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .search_plans(request)
+    }
+
     pub fn get_telemetry_data(&self) -> LocalShardTelemetry {
         self.inner
             .as_ref()
@@ -172,6 +192,7 @@ impl ShardOperation for QueueProxyShard {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<Record>> {
         self.inner
@@ -183,6 +204,7 @@ impl ShardOperation for QueueProxyShard {
                 with_payload_interface,
                 with_vector,
                 filter,
+                order_by,
                 search_runtime_handle,
             )
             .await
@@ -218,6 +240,20 @@ impl ShardOperation for QueueProxyShard {
             .await
     }
 
+    /// Forward read-only `facet` to `wrapped_shard`
+    async fn facet(
+        &self,
+        request: Arc<FacetRequestInternal>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .facet(request, search_runtime_handle, timeout)
+            .await
+    }
+
     /// Forward read-only `retrieve` to `wrapped_shard`
     async fn retrieve(
         &self,
@@ -401,6 +437,7 @@ impl ShardOperation for Inner {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<Record>> {
         let local_shard = &self.wrapped_shard;
@@ -411,6 +448,7 @@ impl ShardOperation for Inner {
                 with_payload_interface,
                 with_vector,
                 filter,
+                order_by,
                 search_runtime_handle,
             )
             .await
@@ -441,6 +479,19 @@ impl ShardOperation for Inner {
         local_shard.count(request).await
     }
 
+    /// Forward read-only `facet` to `wrapped_shard`
+    async fn facet(
+        &self,
+        request: Arc<FacetRequestInternal>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .facet(request, search_runtime_handle, timeout)
+            .await
+    }
+
     /// Forward read-only `retrieve` to `wrapped_shard`
     async fn retrieve(
         &self,
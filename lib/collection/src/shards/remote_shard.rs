@@ -10,10 +10,10 @@ use api::grpc::qdrant::shard_snapshot_location::Location;
 use api::grpc::qdrant::shard_snapshots_client::ShardSnapshotsClient;
 use api::grpc::qdrant::{
     CollectionOperationResponse, CoreSearchBatchPointsInternal, CountPoints, CountPointsInternal,
-    GetCollectionInfoRequest, GetCollectionInfoRequestInternal, GetPoints, GetPointsInternal,
-    HealthCheckRequest, InitiateShardTransferRequest, RecoverShardSnapshotRequest,
-    RecoverSnapshotResponse, ScrollPoints, ScrollPointsInternal, ShardSnapshotLocation,
-    WaitForShardStateRequest,
+    FacetCounts, FacetCountsInternal, GetCollectionInfoRequest, GetCollectionInfoRequestInternal,
+    GetPoints, GetPointsInternal, HealthCheckRequest, InitiateShardTransferRequest,
+    RecoverShardSnapshotRequest, RecoverSnapshotResponse, ScrollPoints, ScrollPointsInternal,
+    ShardSnapshotLocation, WaitForShardStateRequest,
 };
 use api::grpc::transport_channel_pool::{AddTimeout, MAX_GRPC_CHANNEL_TIMEOUT};
 use async_trait::async_trait;
@@ -21,6 +21,7 @@ use parking_lot::Mutex;
 use segment::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
 };
+use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -40,8 +41,8 @@ use crate::operations::point_ops::{PointOperations, WriteOrdering};
 use crate::operations::snapshot_ops::SnapshotPriority;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, SearchRequestInternal,
-    UpdateResult,
+    CountRequestInternal, CountResult, FacetRequestInternal, FacetResponse, PointRequestInternal,
+    Record, SearchRequestInternal, UpdateResult,
 };
 use crate::operations::vector_ops::VectorOperations;
 use crate::operations::{CollectionUpdateOperations, FieldIndexOperations};
@@ -49,8 +50,8 @@ use crate::shards::channel_service::ChannelService;
 use crate::shards::conversions::{
     internal_clear_payload, internal_clear_payload_by_filter, internal_create_index,
     internal_delete_index, internal_delete_payload, internal_delete_points,
-    internal_delete_points_by_filter, internal_set_payload, internal_sync_points,
-    internal_upsert_points, try_scored_point_from_grpc,
+    internal_delete_points_by_filter, internal_rebuild_index, internal_rename_payload,
+    internal_set_payload, internal_sync_points, internal_upsert_points, try_scored_point_from_grpc,
 };
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_trait::ShardOperation;
@@ -367,6 +368,22 @@ impl RemoteShard {
                     .await?
                     .into_inner()
                 }
+                PayloadOps::RenamePayload(rename_payload) => {
+                    let request = &internal_rename_payload(
+                        shard_id,
+                        collection_name,
+                        rename_payload,
+                        wait,
+                        ordering,
+                    );
+                    self.with_points_client(|mut client| async move {
+                        client
+                            .rename_payload(tonic::Request::new(request.clone()))
+                            .await
+                    })
+                    .await?
+                    .into_inner()
+                }
                 PayloadOps::ClearPayload { points } => {
                     let request =
                         &internal_clear_payload(shard_id, collection_name, points, wait, ordering);
@@ -445,6 +462,22 @@ impl RemoteShard {
                     .await?
                     .into_inner()
                 }
+                FieldIndexOperations::RebuildIndex(rebuild_index) => {
+                    let request = &internal_rebuild_index(
+                        shard_id,
+                        collection_name,
+                        rebuild_index,
+                        wait,
+                        ordering,
+                    );
+                    self.with_points_client(|mut client| async move {
+                        client
+                            .rebuild_field_index(tonic::Request::new(request.clone()))
+                            .await
+                    })
+                    .await?
+                    .into_inner()
+                }
             },
         };
         match point_operation_response.result {
@@ -556,6 +589,7 @@ impl ShardOperation for RemoteShard {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<Record>> {
         let scroll_points = ScrollPoints {
@@ -567,6 +601,7 @@ impl ShardOperation for RemoteShard {
             with_vectors: Some(with_vector.clone().into()),
             read_consistency: None,
             shard_key_selector: None,
+            order_by: order_by.map(|o| o.clone().into()),
         };
         let request = &ScrollPointsInternal {
             scroll_points: Some(scroll_points),
@@ -694,6 +729,43 @@ impl ShardOperation for RemoteShard {
         )
     }
 
+    async fn facet(
+        &self,
+        request: Arc<FacetRequestInternal>,
+        _search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        let facet_counts = FacetCounts {
+            collection_name: self.collection_id.clone(),
+            key: request.key.clone(),
+            filter: request.filter.clone().map(|f| f.into()),
+            limit: Some(request.limit as u64),
+            exact: Some(request.exact),
+            timeout: timeout.map(|t| t.as_secs()),
+            read_consistency: None,
+            shard_key_selector: None,
+        };
+
+        let request = &FacetCountsInternal {
+            facet_counts: Some(facet_counts),
+            shard_id: Some(self.id),
+        };
+        let facet_response = self
+            .with_points_client(|mut client| async move {
+                let mut request = tonic::Request::new(request.clone());
+
+                if let Some(timeout) = timeout {
+                    request.set_timeout(timeout);
+                }
+
+                client.facet(request).await
+            })
+            .await?
+            .into_inner();
+
+        Ok(facet_response.try_into()?)
+    }
+
     async fn retrieve(
         &self,
         request: Arc<PointRequestInternal>,
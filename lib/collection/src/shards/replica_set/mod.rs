@@ -12,6 +12,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use schemars::JsonSchema;
+use segment::index::query_planner::SearchPlan;
 use serde::{Deserialize, Serialize};
 use tokio::runtime::Handle;
 use tokio::sync::{Mutex, RwLock};
@@ -22,7 +23,7 @@ use super::transfer::ShardTransfer;
 use super::CollectionId;
 use crate::config::CollectionConfig;
 use crate::operations::shared_storage_config::SharedStorageConfig;
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::types::{CollectionError, CollectionResult, CoreSearchRequest};
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::channel_service::ChannelService;
 use crate::shards::dummy_shard::DummyShard;
@@ -672,6 +673,29 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Read memory-mapped data of the local replica into the page cache, if it is present
+    pub(crate) async fn warmup(&self, vector_name: Option<&str>) -> CollectionResult<()> {
+        let read_local = self.local.read().await;
+        if let Some(shard) = &*read_local {
+            shard.warmup(vector_name).await
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Query planner decisions for the request in segments of the local replica, if it is present
+    pub(crate) async fn search_plans(
+        &self,
+        request: &CoreSearchRequest,
+    ) -> CollectionResult<Vec<SearchPlan>> {
+        let read_local = self.local.read().await;
+        if let Some(shard) = &*read_local {
+            shard.search_plans(request)
+        } else {
+            Ok(vec![])
+        }
+    }
+
     /// Check if the are any locally disabled peers
     /// And if so, report them to the consensus
     pub fn sync_local_state<F>(&self, get_shard_transfers: F) -> CollectionResult<()>
@@ -2,6 +2,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use futures::FutureExt as _;
+use segment::data_types::order_by::OrderBy;
 use segment::types::*;
 
 use super::ShardReplicaSet;
@@ -17,18 +18,21 @@ impl ShardReplicaSet {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
         read_consistency: Option<ReadConsistency>,
         local_only: bool,
     ) -> CollectionResult<Vec<Record>> {
         let with_payload_interface = Arc::new(with_payload_interface.clone());
         let with_vector = Arc::new(with_vector.clone());
         let filter = filter.map(|filter| Arc::new(filter.clone()));
+        let order_by = order_by.map(|order_by| Arc::new(order_by.clone()));
 
         self.execute_and_resolve_read_operation(
             |shard| {
                 let with_payload_interface = with_payload_interface.clone();
                 let with_vector = with_vector.clone();
                 let filter = filter.clone();
+                let order_by = order_by.clone();
                 let search_runtime = self.search_runtime.clone();
 
                 async move {
@@ -39,6 +43,7 @@ impl ShardReplicaSet {
                             &with_payload_interface,
                             &with_vector,
                             filter.as_deref(),
+                            order_by.as_deref(),
                             &search_runtime,
                         )
                         .await
@@ -87,6 +92,26 @@ impl ShardReplicaSet {
         .await
     }
 
+    pub async fn facet(
+        &self,
+        request: Arc<FacetRequestInternal>,
+        read_consistency: Option<ReadConsistency>,
+        local_only: bool,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        self.execute_and_resolve_read_operation(
+            |shard| {
+                let request = Arc::clone(&request);
+                let search_runtime = self.search_runtime.clone();
+
+                async move { shard.facet(request, &search_runtime, timeout).await }.boxed()
+            },
+            read_consistency,
+            local_only,
+        )
+        .await
+    }
+
     pub async fn retrieve(
         &self,
         request: Arc<PointRequestInternal>,
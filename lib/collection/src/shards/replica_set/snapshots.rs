@@ -1,7 +1,8 @@
 use std::ops::Deref as _;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use super::{ReplicaSetState, ReplicaState, ShardReplicaSet, REPLICA_STATE_FILE};
+use crate::operations::snapshot_ops::SegmentDescription;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::dummy_shard::DummyShard;
@@ -32,6 +33,62 @@ impl ShardReplicaSet {
         Ok(())
     }
 
+    pub async fn list_local_segments(&self) -> CollectionResult<Vec<SegmentDescription>> {
+        let local_read = self.local.read().await;
+
+        match &*local_read {
+            Some(Shard::Local(local)) => Ok(local.list_segments()),
+            _ => Err(self.not_local_shard_error()),
+        }
+    }
+
+    /// Create snapshot of a single segment of the local shard into `target_path`
+    ///
+    /// Returns path of the created segment archive.
+    pub async fn create_segment_snapshot(
+        &self,
+        segment_id: &str,
+        temp_path: &Path,
+        target_path: &Path,
+    ) -> CollectionResult<PathBuf> {
+        let local_read = self.local.read().await;
+
+        match &*local_read {
+            Some(Shard::Local(local)) => {
+                local
+                    .create_segment_snapshot(segment_id, temp_path, target_path)
+                    .await
+            }
+            _ => Err(self.not_local_shard_error()),
+        }
+    }
+
+    /// Restore a single segment of the local shard from the segment archive
+    pub async fn restore_segment_snapshot(
+        &self,
+        segment_id: &str,
+        snapshot_path: &Path,
+        force: bool,
+    ) -> CollectionResult<()> {
+        let local_read = self.local.read().await;
+
+        match &*local_read {
+            Some(Shard::Local(local)) => {
+                local
+                    .restore_segment_snapshot(segment_id, snapshot_path, force)
+                    .await
+            }
+            _ => Err(self.not_local_shard_error()),
+        }
+    }
+
+    fn not_local_shard_error(&self) -> CollectionError {
+        CollectionError::bad_input(format!(
+            "Shard {} is not a local shard on this peer",
+            self.shard_id,
+        ))
+    }
+
     pub fn restore_snapshot(
         snapshot_path: &Path,
         this_peer_id: PeerId,
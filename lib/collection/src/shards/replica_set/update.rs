@@ -364,7 +364,7 @@ mod tests {
     use super::*;
     use crate::config::*;
     use crate::operations::types::{VectorParams, VectorsConfig};
-    use crate::optimizers_builder::OptimizersConfig;
+    use crate::optimizers_builder::{MergePolicy, OptimizersConfig};
     use crate::shards::replica_set::{AbortShardTransfer, ChangePeerState};
 
     #[tokio::test]
@@ -388,12 +388,14 @@ mod tests {
     const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
         deleted_threshold: 0.9,
         vacuum_min_vector_number: 1000,
+        vacuum_enabled: true,
         default_segment_number: 2,
         max_segment_size: None,
         memmap_threshold: None,
         indexing_threshold: Some(50_000),
         flush_interval_sec: 30,
         max_optimization_threads: 2,
+        merge_policy: MergePolicy::MaxSegmentSizeBound,
     };
 
     async fn new_shard_replica_set(collection_dir: &TempDir) -> ShardReplicaSet {
@@ -412,6 +414,11 @@ mod tests {
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: None,
+                index: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
             }),
             shard_number: NonZeroU32::new(4).unwrap(),
             replication_factor: NonZeroU32::new(3).unwrap(),
@@ -1,10 +1,11 @@
 use std::collections::{HashMap, HashSet};
 use std::hash;
 
+use segment::data_types::facets::{top_facet_hits, FacetValue};
 use segment::types::{Payload, ScoredPoint};
 use tinyvec::TinyVec;
 
-use crate::operations::types::{CountResult, Record};
+use crate::operations::types::{CountResult, FacetResponse, Record};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum ResolveCondition {
@@ -18,25 +19,47 @@ pub trait Resolve: Sized {
 
 impl Resolve for CountResult {
     fn resolve(records: Vec<Self>, condition: ResolveCondition) -> Self {
-        match condition {
-            ResolveCondition::All => Self {
-                count: records
-                    .iter()
-                    .map(|result| result.count)
-                    .min()
-                    .unwrap_or_default(),
-            },
+        let result = match condition {
+            ResolveCondition::All => records.into_iter().min_by_key(|result| result.count),
             ResolveCondition::Majority => {
-                let mut counts = records
-                    .iter()
-                    .map(|result| result.count)
-                    .collect::<Vec<_>>();
-                counts.sort_unstable();
-                let middle = counts.len() / 2;
-                Self {
-                    count: counts.get(middle).copied().unwrap_or_default(),
-                }
+                let mut records = records;
+                records.sort_unstable_by_key(|result| result.count);
+                let middle = records.len() / 2;
+                records.into_iter().nth(middle)
             }
+        };
+
+        result.unwrap_or(Self {
+            count: 0,
+            estimation: None,
+        })
+    }
+}
+
+impl Resolve for FacetResponse {
+    fn resolve(responses: Vec<Self>, condition: ResolveCondition) -> Self {
+        let replicas = responses.len();
+
+        let mut value_counts: HashMap<FacetValue, Vec<usize>> = HashMap::new();
+        for response in responses {
+            for hit in response.hits {
+                value_counts.entry(hit.value).or_default().push(hit.count);
+            }
+        }
+
+        let counts = value_counts.into_iter().filter_map(|(value, mut counts)| {
+            // Replicas, which did not report the value, have no points with it
+            counts.resize(replicas, 0);
+            counts.sort_unstable();
+            let count = match condition {
+                ResolveCondition::All => counts[0],
+                ResolveCondition::Majority => counts[replicas / 2],
+            };
+            (count > 0).then_some((value, count))
+        });
+
+        Self {
+            hits: top_facet_hits(counts, None),
         }
     }
 }
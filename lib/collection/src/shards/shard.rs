@@ -2,8 +2,10 @@ use core::marker::{Send, Sync};
 use std::future::{self, Future};
 use std::path::Path;
 
+use segment::index::query_planner::SearchPlan;
+
 use super::update_tracker::UpdateTracker;
-use crate::operations::types::CollectionResult;
+use crate::operations::types::{CollectionResult, CoreSearchRequest};
 use crate::shards::dummy_shard::DummyShard;
 use crate::shards::forward_proxy_shard::ForwardProxyShard;
 use crate::shards::local_shard::LocalShard;
@@ -119,6 +121,26 @@ impl Shard {
         }
     }
 
+    pub async fn warmup(&self, vector_name: Option<&str>) -> CollectionResult<()> {
+        match self {
+            Shard::Local(local_shard) => local_shard.warmup(vector_name).await,
+            Shard::Proxy(proxy_shard) => proxy_shard.warmup(vector_name).await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.warmup(vector_name).await,
+            Shard::QueueProxy(proxy_shard) => proxy_shard.warmup(vector_name).await,
+            Shard::Dummy(dummy_shard) => dummy_shard.warmup(vector_name).await,
+        }
+    }
+
+    pub fn search_plans(&self, request: &CoreSearchRequest) -> CollectionResult<Vec<SearchPlan>> {
+        match self {
+            Shard::Local(local_shard) => local_shard.search_plans(request),
+            Shard::Proxy(proxy_shard) => proxy_shard.search_plans(request),
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.search_plans(request),
+            Shard::QueueProxy(proxy_shard) => proxy_shard.search_plans(request),
+            Shard::Dummy(dummy_shard) => dummy_shard.search_plans(request),
+        }
+    }
+
     pub fn is_update_in_progress(&self) -> bool {
         self.update_tracker()
             .map_or(false, UpdateTracker::is_update_in_progress)
@@ -16,7 +16,7 @@ use crate::hash_ring::HashRing;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::snapshot_ops::{
-    get_snapshot_description, list_snapshots_in_directory, SnapshotDescription,
+    get_snapshot_description, list_snapshots_in_directory, SegmentDescription, SnapshotDescription,
 };
 use crate::operations::types::{CollectionError, CollectionResult, ShardTransferInfo};
 use crate::operations::{OperationToShard, SplitByShard};
@@ -887,6 +887,107 @@ impl ShardHolder {
             .await
     }
 
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    pub async fn list_shard_segments(
+        &self,
+        shard_id: ShardId,
+    ) -> CollectionResult<Vec<SegmentDescription>> {
+        let shard = self
+            .get_shard(&shard_id)
+            .ok_or_else(|| shard_not_found_error(shard_id))?;
+
+        shard.list_local_segments().await
+    }
+
+    /// Create snapshot of a single segment of the local shard
+    ///
+    /// Segment snapshots are stored together with snapshots of the shard, so they are listed,
+    /// downloaded and deleted the same way.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    pub async fn create_segment_snapshot(
+        &self,
+        snapshots_path: &Path,
+        collection_name: &str,
+        shard_id: ShardId,
+        segment_id: &str,
+        temp_dir: &Path,
+    ) -> CollectionResult<SnapshotDescription> {
+        // - `snapshot_temp_dir` and `snapshot_target_dir` are handled by `tempfile`
+        //   and would be deleted, if future is cancelled
+
+        let shard = self
+            .get_shard(&shard_id)
+            .ok_or_else(|| shard_not_found_error(shard_id))?;
+
+        let snapshot_file_name = format!(
+            "{collection_name}-shard-{shard_id}-segment-{segment_id}-{}.snapshot",
+            chrono::Utc::now().format("%Y-%m-%d-%H-%M-%S"),
+        );
+
+        let snapshot_path =
+            self.shard_snapshot_path_unchecked(snapshots_path, shard_id, &snapshot_file_name)?;
+
+        let snapshot_temp_dir = tempfile::Builder::new()
+            .prefix(&format!("{snapshot_file_name}-temp-"))
+            .tempdir_in(temp_dir)?;
+
+        let snapshot_target_dir = tempfile::Builder::new()
+            .prefix(&format!("{snapshot_file_name}-target-"))
+            .tempdir_in(temp_dir)?;
+
+        // Segment snapshot is already a single archive, so it is not archived again
+        let segment_archive = shard
+            .create_segment_snapshot(
+                segment_id,
+                snapshot_temp_dir.path(),
+                snapshot_target_dir.path(),
+            )
+            .await?;
+
+        if let Some(snapshot_dir) = snapshot_path.parent() {
+            if !snapshot_dir.exists() {
+                std::fs::create_dir_all(snapshot_dir)?;
+            }
+        }
+
+        // Remove partially moved `snapshot_path`, if `move_file` fails
+        let snapshot_file = tempfile::TempPath::from_path(&snapshot_path);
+
+        move_file(&segment_archive, &snapshot_path).await?;
+
+        // We successfully moved `snapshot_path`, so we `keep` it
+        snapshot_file.keep()?;
+
+        get_snapshot_description(&snapshot_path).await
+    }
+
+    /// Restore a single segment of the local shard from the segment snapshot
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. Segment is restored in a blocking task, which is not
+    /// interrupted, if future is cancelled.
+    pub async fn restore_segment_snapshot(
+        &self,
+        snapshot_path: &Path,
+        shard_id: ShardId,
+        segment_id: &str,
+        force: bool,
+    ) -> CollectionResult<()> {
+        let shard = self
+            .get_shard(&shard_id)
+            .ok_or_else(|| shard_not_found_error(shard_id))?;
+
+        shard
+            .restore_segment_snapshot(segment_id, snapshot_path, force)
+            .await
+    }
+
     /// # Cancel safety
     ///
     /// This method is cancel safe.
@@ -2,6 +2,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -9,7 +10,7 @@ use tokio::runtime::Handle;
 
 use crate::operations::types::{
     CollectionInfo, CollectionResult, CoreSearchRequestBatch, CountRequestInternal, CountResult,
-    PointRequestInternal, Record, UpdateResult,
+    FacetRequestInternal, FacetResponse, PointRequestInternal, Record, UpdateResult,
 };
 use crate::operations::CollectionUpdateOperations;
 
@@ -29,6 +30,7 @@ pub trait ShardOperation {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<Record>>;
 
@@ -43,6 +45,13 @@ pub trait ShardOperation {
 
     async fn count(&self, request: Arc<CountRequestInternal>) -> CollectionResult<CountResult>;
 
+    async fn facet(
+        &self,
+        request: Arc<FacetRequestInternal>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse>;
+
     async fn retrieve(
         &self,
         request: Arc<PointRequestInternal>,
@@ -69,6 +69,7 @@ impl Anonymize for TrackerTelemetry {
             status: self.status.clone(),
             start_at: self.start_at.anonymize(),
             end_at: self.end_at.anonymize(),
+            progress: self.progress.clone(),
         }
     }
 }
@@ -17,6 +17,7 @@ use crate::collection_manager::fixtures::{
 };
 use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder, SegmentId};
 use crate::collection_manager::optimizers::TrackerStatus;
+use crate::common::optimization_budget::{OptimizationBudget, OptimizationResources};
 use crate::update_handler::{Optimizer, UpdateHandler};
 
 #[tokio::test]
@@ -49,14 +50,17 @@ async fn test_optimization_process() {
 
     let optimizers_log = Arc::new(Mutex::new(Default::default()));
     let segments: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
-    let handles = UpdateHandler::launch_optimization(
+    let optimization_budget = Arc::new(OptimizationBudget::default());
+    let (handles, postponed) = UpdateHandler::launch_optimization(
         optimizers.clone(),
         optimizers_log.clone(),
         segments.clone(),
+        &optimization_budget,
         |_| {},
     );
 
     assert_eq!(handles.len(), 2);
+    assert!(!postponed);
 
     let join_res = join_all(handles.into_iter().map(|x| x.join_handle).collect_vec()).await;
 
@@ -70,10 +74,11 @@ async fn test_optimization_process() {
         assert_eq!(log[1].status, TrackerStatus::Done);
     }
 
-    let handles_2 = UpdateHandler::launch_optimization(
+    let (handles_2, _) = UpdateHandler::launch_optimization(
         optimizers.clone(),
         optimizers_log.clone(),
         segments.clone(),
+        &optimization_budget,
         |_| {},
     );
 
@@ -114,10 +119,11 @@ async fn test_cancel_optimization() {
 
     let optimizers_log = Arc::new(Mutex::new(Default::default()));
     let segments: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
-    let handles = UpdateHandler::launch_optimization(
+    let (handles, _) = UpdateHandler::launch_optimization(
         optimizers.clone(),
         optimizers_log.clone(),
         segments.clone(),
+        &Arc::new(OptimizationBudget::default()),
         |_| {},
     );
 
@@ -153,6 +159,47 @@ async fn test_cancel_optimization() {
     }
 }
 
+#[tokio::test]
+async fn test_optimization_budget_postpones_optimizations() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let mut holder = SegmentHolder::default();
+    let dim = 256;
+
+    for _ in 0..3 {
+        holder.add(random_segment(dir.path(), 100, 1000, dim));
+    }
+
+    let indexing_optimizer: Arc<Optimizer> =
+        Arc::new(get_indexing_optimizer(dir.path(), temp_dir.path(), dim));
+
+    let optimizers = Arc::new(vec![indexing_optimizer]);
+
+    let optimizers_log = Arc::new(Mutex::new(Default::default()));
+    let segments: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
+
+    // Budget is smaller than any optimization, so only one of them may run at a time
+    let optimization_budget = Arc::new(OptimizationBudget::new(None, Some(1)));
+    let (handles, postponed) = UpdateHandler::launch_optimization(
+        optimizers.clone(),
+        optimizers_log.clone(),
+        segments.clone(),
+        &optimization_budget,
+        |_| {},
+    );
+
+    assert_eq!(handles.len(), 1);
+    assert!(postponed);
+    assert!(optimization_budget.used().memory_bytes > 0);
+
+    let join_handles = handles.into_iter().filter_map(|h| h.stop()).collect_vec();
+    join_all(join_handles).await;
+
+    // Resources are released, once the optimization is finished
+    assert_eq!(optimization_budget.used(), OptimizationResources::default());
+}
+
 #[test]
 fn check_version_upgrade() {
     assert!(!Collection::can_upgrade_storage(
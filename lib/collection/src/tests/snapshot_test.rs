@@ -9,7 +9,7 @@ use crate::collection::{Collection, RequestShardTransfer};
 use crate::config::{CollectionConfig, CollectionParams, WalConfig};
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{NodeType, VectorParams, VectorsConfig};
-use crate::optimizers_builder::OptimizersConfig;
+use crate::optimizers_builder::{MergePolicy, OptimizersConfig};
 use crate::shards::channel_service::ChannelService;
 use crate::shards::collection_shard_distribution::CollectionShardDistribution;
 use crate::shards::replica_set::{AbortShardTransfer, ChangePeerState};
@@ -17,12 +17,14 @@ use crate::shards::replica_set::{AbortShardTransfer, ChangePeerState};
 pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
     deleted_threshold: 0.9,
     vacuum_min_vector_number: 1000,
+    vacuum_enabled: true,
     default_segment_number: 2,
     max_segment_size: None,
     memmap_threshold: None,
     indexing_threshold: Some(50_000),
     flush_interval_sec: 30,
     max_optimization_threads: 2,
+    merge_policy: MergePolicy::MaxSegmentSizeBound,
 };
 
 pub fn dummy_on_replica_failure() -> ChangePeerState {
@@ -54,6 +56,11 @@ async fn _test_snapshot_collection(node_type: NodeType) {
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            index: None,
+            multivector_config: None,
+            datatype: None,
+            keep_original: None,
+            dimension_weights: None,
         }),
         shard_number: NonZeroU32::new(4).unwrap(),
         replication_factor: NonZeroU32::new(3).unwrap(),
@@ -85,6 +85,7 @@ fn validate_error_sparse_vector_search_request_internal() {
         with_payload: None,
         with_vector: None,
         score_threshold: None,
+        tie_breaker: None,
     });
 }
 
@@ -1,18 +1,25 @@
 use std::num::NonZeroU64;
+use std::path::Path;
 use std::sync::Arc;
 
-use segment::types::{Distance, PayloadFieldSchema, PayloadSchemaType};
+use segment::types::{
+    Distance, ExtendedPointId, PayloadFieldSchema, PayloadSchemaType, WithPayloadInterface,
+    WithVector,
+};
 use tempfile::Builder;
 use tokio::runtime::Handle;
 use tokio::sync::RwLock;
 
 use crate::config::{CollectionConfig, CollectionParams, WalConfig};
 use crate::operations::point_ops::{PointOperations, PointStruct};
-use crate::operations::types::{VectorParams, VectorsConfig};
+use crate::operations::types::{
+    CollectionError, PointRequestInternal, VectorParams, VectorsConfig,
+};
 use crate::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
 use crate::shards::local_shard::LocalShard;
 use crate::shards::shard_trait::ShardOperation;
 use crate::tests::snapshot_test::TEST_OPTIMIZERS_CONFIG;
+use crate::wal::SerdeWal;
 
 fn create_collection_config() -> CollectionConfig {
     let wal_config = WalConfig {
@@ -27,6 +34,11 @@ fn create_collection_config() -> CollectionConfig {
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            index: None,
+            multivector_config: None,
+            datatype: None,
+            keep_original: None,
+            dimension_weights: None,
         }),
         ..CollectionParams::empty()
     };
@@ -180,3 +192,269 @@ async fn test_delete_from_indexed_payload() {
     assert_eq!(number_of_indexed_points, 4);
     assert_eq!(number_of_indexed_points_after_load, 3);
 }
+
+async fn available_point_ids(shard: &LocalShard) -> Vec<ExtendedPointId> {
+    let request = PointRequestInternal {
+        ids: (1..=5).map(ExtendedPointId::from).collect(),
+        with_payload: Some(WithPayloadInterface::Bool(false)),
+        with_vector: WithVector::Bool(false),
+    };
+    shard
+        .retrieve(Arc::new(request), &false.into(), &false.into())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|record| record.id)
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_restore_segment_snapshot_keeps_newer_updates() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let snapshots_dir = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+
+    let config = create_collection_config();
+
+    let collection_name = "test".to_string();
+
+    let current_runtime: Handle = Handle::current();
+
+    let shard = LocalShard::build(
+        0,
+        collection_name.clone(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        current_runtime.clone(),
+    )
+    .await
+    .unwrap();
+
+    shard.update(upsert_operation(), true).await.unwrap();
+
+    let segment = shard
+        .list_segments()
+        .into_iter()
+        .max_by_key(|segment| segment.num_points)
+        .unwrap();
+    assert_eq!(segment.num_points, 5);
+
+    let snapshot_path = shard
+        .create_segment_snapshot(&segment.id, snapshots_dir.path(), snapshots_dir.path())
+        .await
+        .unwrap();
+
+    // Snapshot of the current state of the segment could be restored
+    shard
+        .restore_segment_snapshot(&segment.id, &snapshot_path, false)
+        .await
+        .unwrap();
+    assert_eq!(available_point_ids(&shard).await.len(), 5);
+
+    shard.update(delete_point_operation(4), true).await.unwrap();
+
+    // Restored segment has a new name
+    let segment = shard
+        .list_segments()
+        .into_iter()
+        .max_by_key(|segment| segment.num_points)
+        .unwrap();
+    assert_eq!(segment.num_points, 4);
+
+    // Snapshot, taken before the delete, must not bring the deleted point back,
+    // the delete is re-applied from WAL
+    shard
+        .restore_segment_snapshot(&segment.id, &snapshot_path, false)
+        .await
+        .unwrap();
+
+    let point_ids = available_point_ids(&shard).await;
+    assert_eq!(point_ids.len(), 4);
+    assert!(!point_ids.contains(&4.into()));
+
+    drop(shard);
+
+    let shard = LocalShard::load(
+        0,
+        collection_name,
+        collection_dir.path(),
+        Arc::new(RwLock::new(config)),
+        Arc::new(Default::default()),
+        current_runtime,
+    )
+    .await
+    .unwrap();
+
+    let point_ids = available_point_ids(&shard).await;
+    assert_eq!(point_ids.len(), 4);
+    assert!(!point_ids.contains(&4.into()));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_restore_segment_snapshot_with_truncated_wal() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let snapshots_dir = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+
+    let config = create_collection_config();
+
+    let collection_name = "test".to_string();
+
+    let current_runtime: Handle = Handle::current();
+
+    let shard = LocalShard::build(
+        0,
+        collection_name.clone(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        current_runtime.clone(),
+    )
+    .await
+    .unwrap();
+
+    shard.update(upsert_operation(), true).await.unwrap();
+
+    let segment = shard
+        .list_segments()
+        .into_iter()
+        .max_by_key(|segment| segment.num_points)
+        .unwrap();
+
+    let snapshot_path = shard
+        .create_segment_snapshot(&segment.id, snapshots_dir.path(), snapshots_dir.path())
+        .await
+        .unwrap();
+
+    shard.update(delete_point_operation(4), true).await.unwrap();
+    let last_operation_id = shard
+        .update(delete_point_operation(5), true)
+        .await
+        .unwrap()
+        .operation_id
+        .unwrap();
+
+    drop(shard);
+
+    // Truncate WAL, so that only the last delete is left
+    let mut wal: SerdeWal<CollectionUpdateOperations> = SerdeWal::new(
+        LocalShard::wal_path(collection_dir.path())
+            .to_str()
+            .unwrap(),
+        (&config.wal_config).into(),
+    )
+    .unwrap();
+    wal.ack(last_operation_id).unwrap();
+    drop(wal);
+
+    let shard = LocalShard::load(
+        0,
+        collection_name,
+        collection_dir.path(),
+        Arc::new(RwLock::new(config)),
+        Arc::new(Default::default()),
+        current_runtime,
+    )
+    .await
+    .unwrap();
+
+    let segment = shard
+        .list_segments()
+        .into_iter()
+        .max_by_key(|segment| segment.num_points)
+        .unwrap();
+    let segments_count = segment_dirs_count(collection_dir.path());
+
+    // Delete of point 4 can't be re-applied from WAL, so the snapshot is rejected
+    let result = shard
+        .restore_segment_snapshot(&segment.id, &snapshot_path, false)
+        .await;
+    assert!(matches!(result, Err(CollectionError::BadInput { .. })));
+    assert_eq!(available_point_ids(&shard).await.len(), 3);
+    assert_eq!(segment_dirs_count(collection_dir.path()), segments_count);
+
+    // Forced restore loses the delete of point 4, but operations left in WAL are re-applied
+    shard
+        .restore_segment_snapshot(&segment.id, &snapshot_path, true)
+        .await
+        .unwrap();
+
+    let point_ids = available_point_ids(&shard).await;
+    assert_eq!(point_ids.len(), 4);
+    assert!(point_ids.contains(&4.into()));
+    assert!(!point_ids.contains(&5.into()));
+    assert_eq!(segment_dirs_count(collection_dir.path()), segments_count);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_restore_incompatible_segment_snapshot() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let other_collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let snapshots_dir = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+
+    let config = create_collection_config();
+    let mut other_config = config.clone();
+    if let VectorsConfig::Single(params) = &mut other_config.params.vectors {
+        params.distance = Distance::Euclid;
+    }
+
+    let current_runtime: Handle = Handle::current();
+
+    let shard = LocalShard::build(
+        0,
+        "test".to_string(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config)),
+        Arc::new(Default::default()),
+        current_runtime.clone(),
+    )
+    .await
+    .unwrap();
+    shard.update(upsert_operation(), true).await.unwrap();
+
+    let other_shard = LocalShard::build(
+        0,
+        "other".to_string(),
+        other_collection_dir.path(),
+        Arc::new(RwLock::new(other_config)),
+        Arc::new(Default::default()),
+        current_runtime,
+    )
+    .await
+    .unwrap();
+    other_shard.update(upsert_operation(), true).await.unwrap();
+
+    let other_segment = other_shard
+        .list_segments()
+        .into_iter()
+        .max_by_key(|segment| segment.num_points)
+        .unwrap();
+    let snapshot_path = other_shard
+        .create_segment_snapshot(
+            &other_segment.id,
+            snapshots_dir.path(),
+            snapshots_dir.path(),
+        )
+        .await
+        .unwrap();
+
+    let segment = shard
+        .list_segments()
+        .into_iter()
+        .max_by_key(|segment| segment.num_points)
+        .unwrap();
+    let segments_count = segment_dirs_count(collection_dir.path());
+
+    // Invalid segment is rejected before it is moved into the shard
+    let result = shard
+        .restore_segment_snapshot(&segment.id, &snapshot_path, true)
+        .await;
+    assert!(result.is_err());
+    assert_eq!(segment_dirs_count(collection_dir.path()), segments_count);
+    assert_eq!(available_point_ids(&shard).await.len(), 5);
+}
+
+fn segment_dirs_count(shard_path: &Path) -> usize {
+    std::fs::read_dir(LocalShard::segments_path(shard_path))
+        .unwrap()
+        .count()
+}
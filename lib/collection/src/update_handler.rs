@@ -17,9 +17,10 @@ use tokio::time::error::Elapsed;
 use tokio::time::{timeout, Duration};
 
 use crate::collection_manager::collection_updater::CollectionUpdater;
-use crate::collection_manager::holders::segment_holder::LockedSegmentHolder;
+use crate::collection_manager::holders::segment_holder::{LockedSegment, LockedSegmentHolder};
 use crate::collection_manager::optimizers::segment_optimizer::SegmentOptimizer;
 use crate::collection_manager::optimizers::{Tracker, TrackerLog, TrackerStatus};
+use crate::common::optimization_budget::OptimizationBudget;
 use crate::common::stoppable_task::{spawn_stoppable, StoppableTaskHandle};
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{CollectionError, CollectionResult};
@@ -141,6 +142,7 @@ impl UpdateHandler {
             self.wal.clone(),
             self.optimization_handles.clone(),
             self.optimizers_log.clone(),
+            self.shared_storage_config.optimization_budget.clone(),
             self.max_optimization_threads,
         )));
         self.update_worker = Some(self.runtime_handle.spawn(Self::update_worker_fn(
@@ -216,14 +218,15 @@ impl UpdateHandler {
     }
 
     /// Checks conditions for all optimizers until there is no suggested segment
-    /// Starts a task for each optimization
-    /// Returns handles for started tasks
+    /// Starts a task for each optimization, if it fits into the optimization budget of the node
+    /// Returns handles for started tasks and whether some optimization was postponed
     pub(crate) fn launch_optimization<F>(
         optimizers: Arc<Vec<Arc<Optimizer>>>,
         optimizers_log: Arc<Mutex<TrackerLog>>,
         segments: LockedSegmentHolder,
+        optimization_budget: &Arc<OptimizationBudget>,
         callback: F,
-    ) -> Vec<StoppableTaskHandle<bool>>
+    ) -> (Vec<StoppableTaskHandle<bool>>, bool)
     where
         F: FnOnce(bool),
         F: Send + 'static,
@@ -239,6 +242,16 @@ impl UpdateHandler {
                     break;
                 }
 
+                let required_resources =
+                    optimizer.estimate_resources(&segments, &nonoptimal_segment_ids);
+                let Some(permit) = optimization_budget.try_acquire(required_resources) else {
+                    debug!(
+                        "Optimization of segments {nonoptimal_segment_ids:?} is postponed, \
+                         budget of the node is exhausted"
+                    );
+                    return (handles, true);
+                };
+
                 let optimizer = optimizer.clone();
                 let optimizers_log = optimizers_log.clone();
                 let segments = segments.clone();
@@ -251,13 +264,22 @@ impl UpdateHandler {
                     {
                         let segments = segments.clone();
                         move |stopped| {
+                            // Resources are reserved until the optimization is finished
+                            let _permit = permit;
+
                             // Track optimizer status
                             let tracker = Tracker::start(optimizer.as_ref().name(), nsi.clone());
                             let tracker_handle = tracker.handle();
+                            let progress = tracker.progress.clone();
                             optimizers_log.lock().register(tracker);
 
                             // Optimize and handle result
-                            match optimizer.as_ref().optimize(segments.clone(), nsi, stopped) {
+                            match optimizer.as_ref().optimize_with_progress(
+                                segments.clone(),
+                                nsi,
+                                &progress,
+                                stopped,
+                            ) {
                                 // Perform some actions when optimization if finished
                                 Ok(result) => {
                                     tracker_handle.update(TrackerStatus::Done);
@@ -310,20 +332,23 @@ impl UpdateHandler {
                 handles.push(handle);
             }
         }
-        handles
+        (handles, false)
     }
 
+    /// Returns whether some optimization was postponed due to the exhausted optimization budget
     pub(crate) async fn process_optimization(
         optimizers: Arc<Vec<Arc<Optimizer>>>,
         segments: LockedSegmentHolder,
         optimization_handles: Arc<TokioMutex<Vec<StoppableTaskHandle<bool>>>>,
         optimizers_log: Arc<Mutex<TrackerLog>>,
+        optimization_budget: &Arc<OptimizationBudget>,
         sender: Sender<OptimizerSignal>,
-    ) {
-        let mut new_handles = Self::launch_optimization(
+    ) -> bool {
+        let (mut new_handles, postponed) = Self::launch_optimization(
             optimizers.clone(),
             optimizers_log,
             segments.clone(),
+            optimization_budget,
             move |_optimization_result| {
                 // After optimization is finished, we still need to check if there are
                 // some further optimizations possible.
@@ -334,6 +359,7 @@ impl UpdateHandler {
         );
         let mut handles = optimization_handles.lock().await;
         handles.append(&mut new_handles);
+        postponed
     }
 
     /// Cleanup finalized optimization task handles
@@ -372,8 +398,11 @@ impl UpdateHandler {
         wal: LockedWal,
         optimization_handles: Arc<TokioMutex<Vec<StoppableTaskHandle<bool>>>>,
         optimizers_log: Arc<Mutex<TrackerLog>>,
+        optimization_budget: Arc<OptimizationBudget>,
         max_handles: usize,
     ) {
+        // Optimizations, postponed due to the exhausted budget, are retried on clean up interval
+        let mut postponed = false;
         loop {
             let receiver = timeout(OPTIMIZER_CLEANUP_INTERVAL, receiver.recv());
             let result = receiver.await;
@@ -381,36 +410,39 @@ impl UpdateHandler {
             // Always clean up on any signal
             Self::cleanup_optimization_handles(optimization_handles.clone()).await;
 
-            match result {
+            let signal = match result {
                 // Channel closed or stop signal
                 Ok(None | Some(OptimizerSignal::Stop)) => break,
+                // Clean up interval, retry postponed optimizations
+                Err(Elapsed { .. }) if postponed => OptimizerSignal::Nop,
                 // Clean up interval
                 Err(Elapsed { .. }) => continue,
                 // Optimizer signal
-                Ok(Some(signal @ (OptimizerSignal::Nop | OptimizerSignal::Operation(_)))) => {
-                    // If not forcing with Nop, wait on next signal if we have too many handles
-                    if signal != OptimizerSignal::Nop
-                        && optimization_handles.lock().await.len() >= max_handles
-                    {
-                        continue;
-                    }
+                Ok(Some(signal @ (OptimizerSignal::Nop | OptimizerSignal::Operation(_)))) => signal,
+            };
 
-                    if Self::try_recover(segments.clone(), wal.clone())
-                        .await
-                        .is_err()
-                    {
-                        continue;
-                    }
-                    Self::process_optimization(
-                        optimizers.clone(),
-                        segments.clone(),
-                        optimization_handles.clone(),
-                        optimizers_log.clone(),
-                        sender.clone(),
-                    )
-                    .await;
-                }
+            // If not forcing with Nop, wait on next signal if we have too many handles
+            if signal != OptimizerSignal::Nop
+                && optimization_handles.lock().await.len() >= max_handles
+            {
+                continue;
             }
+
+            if Self::try_recover(segments.clone(), wal.clone())
+                .await
+                .is_err()
+            {
+                continue;
+            }
+            postponed = Self::process_optimization(
+                optimizers.clone(),
+                segments.clone(),
+                optimization_handles.clone(),
+                optimizers_log.clone(),
+                &optimization_budget,
+                sender.clone(),
+            )
+            .await;
         }
     }
 
@@ -543,6 +575,28 @@ impl UpdateHandler {
             if let Err(err) = wal.lock().ack(ack) {
                 segments.write().report_optimizer_error(err);
             }
+
+            Self::update_hot_tiers(&segments);
+        }
+    }
+
+    /// Move the most frequently accessed vectors of memory-mapped storages into RAM,
+    /// and release vectors which are not accessed often anymore
+    fn update_hot_tiers(segments: &LockedSegmentHolder) {
+        let original_segments: Vec<_> = segments
+            .read()
+            .iter()
+            .filter_map(|(_, segment)| match segment {
+                LockedSegment::Original(segment) => Some(segment.clone()),
+                LockedSegment::Proxy(_) => None,
+            })
+            .collect();
+
+        for segment in original_segments {
+            // Copy hot vectors under the shared lock, so searches are not blocked
+            if segment.read().prepare_hot_tier() {
+                segment.write().apply_hot_tier();
+            }
         }
     }
 
@@ -70,6 +70,7 @@ async fn test_collection_updater_with_shards(shard_number: u32) {
         limit: 3,
         offset: None,
         score_threshold: None,
+        tie_breaker: None,
     };
 
     let search_res = collection
@@ -134,6 +135,7 @@ async fn test_collection_search_with_payload_and_vector_with_shards(shard_number
         limit: 3,
         offset: None,
         score_threshold: None,
+        tie_breaker: None,
     };
 
     let search_res = collection
@@ -410,6 +412,7 @@ async fn test_read_api_with_shards(shard_number: u32) {
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: false.into(),
+                order_by: None,
             },
             None,
             &ShardSelectorInternal::All,
@@ -466,6 +469,7 @@ async fn test_collection_delete_points_by_filter_with_shards(shard_number: u32)
     let to_be_deleted: HashSet<PointIdType> = vec![0.into(), 3.into()].into_iter().collect();
     let delete_filter = segment::types::Filter {
         should: None,
+        min_should: None,
         must: Some(vec![Condition::HasId(HasIdCondition::from(to_be_deleted))]),
         must_not: None,
     };
@@ -493,6 +497,7 @@ async fn test_collection_delete_points_by_filter_with_shards(shard_number: u32)
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Bool(false)),
                 with_vector: false.into(),
+                order_by: None,
             },
             None,
             &ShardSelectorInternal::All,
@@ -7,7 +7,7 @@ use std::sync::Arc;
 use collection::collection::{Collection, RequestShardTransfer};
 use collection::config::{CollectionConfig, CollectionParams, WalConfig};
 use collection::operations::types::{CollectionError, VectorParams};
-use collection::optimizers_builder::OptimizersConfig;
+use collection::optimizers_builder::{MergePolicy, OptimizersConfig};
 use collection::shards::channel_service::ChannelService;
 use collection::shards::collection_shard_distribution::CollectionShardDistribution;
 use collection::shards::replica_set::{AbortShardTransfer, ChangePeerState, ReplicaState};
@@ -25,12 +25,14 @@ pub const REST_PORT: u16 = 6333;
 pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
     deleted_threshold: 0.9,
     vacuum_min_vector_number: 1000,
+    vacuum_enabled: true,
     default_segment_number: 2,
     max_segment_size: None,
     memmap_threshold: None,
     indexing_threshold: Some(50_000),
     flush_interval_sec: 30,
     max_optimization_threads: 2,
+    merge_policy: MergePolicy::MaxSegmentSizeBound,
 };
 
 #[cfg(test)]
@@ -48,6 +50,11 @@ pub async fn simple_collection_fixture(collection_path: &Path, shard_number: u32
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            index: None,
+            multivector_config: None,
+            datatype: None,
+            keep_original: None,
+            dimension_weights: None,
         }
         .into(),
         shard_number: NonZeroU32::new(shard_number).expect("Shard number can not be zero"),
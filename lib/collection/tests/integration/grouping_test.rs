@@ -41,6 +41,7 @@ mod group_by {
             with_payload: None,
             with_vector: None,
             score_threshold: None,
+            tie_breaker: None,
         });
 
         let request = GroupRequest::with_limit_from_request(source, "docId".to_string(), 3);
@@ -199,6 +200,7 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                tie_breaker: None,
             }),
             "docId".to_string(),
             3,
@@ -231,6 +233,7 @@ mod group_by {
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: Some(WithVector::Bool(true)),
                 score_threshold: None,
+                tie_breaker: None,
             }),
             "docId".to_string(),
             3,
@@ -269,6 +272,7 @@ mod group_by {
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: Some(WithVector::Bool(true)),
                 score_threshold: None,
+                tie_breaker: None,
             }),
             "other_stuff".to_string(),
             3,
@@ -305,6 +309,7 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                tie_breaker: None,
             }),
             "docId".to_string(),
             0,
@@ -337,6 +342,7 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                tie_breaker: None,
             }),
             "docId".to_string(),
             3,
@@ -369,6 +375,7 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                tie_breaker: None,
             }),
             "docId".to_string(),
             3,
@@ -405,6 +412,7 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                tie_breaker: None,
             }),
             "docId".to_string(),
             400,
@@ -459,6 +467,7 @@ mod group_by_builder {
             with_payload: None,
             with_vector: None,
             score_threshold: None,
+            tie_breaker: None,
         });
 
         let request = GroupRequest::with_limit_from_request(source_request, "docId".to_string(), 3);
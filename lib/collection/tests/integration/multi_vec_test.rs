@@ -43,6 +43,11 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         hnsw_config: None,
         quantization_config: None,
         on_disk: None,
+        index: None,
+        multivector_config: None,
+        datatype: None,
+        keep_original: None,
+        dimension_weights: None,
     };
     let vector_params2 = VectorParams {
         size: NonZeroU64::new(4).unwrap(),
@@ -50,6 +55,11 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         hnsw_config: None,
         quantization_config: None,
         on_disk: None,
+        index: None,
+        multivector_config: None,
+        datatype: None,
+        keep_original: None,
+        dimension_weights: None,
     };
 
     let mut vectors_config = BTreeMap::new();
@@ -128,6 +138,7 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
         with_vector: Some(true.into()),
         params: None,
         score_threshold: None,
+        tie_breaker: None,
     };
 
     let result = collection
@@ -161,6 +172,7 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
         with_vector: Some(true.into()),
         params: None,
         score_threshold: None,
+        tie_breaker: None,
     };
 
     let result = collection
@@ -190,6 +202,7 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
         with_vector: Some(true.into()),
         params: None,
         score_threshold: None,
+        tie_breaker: None,
     };
 
     let result = collection
@@ -34,6 +34,11 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            index: None,
+            multivector_config: None,
+            datatype: None,
+            keep_original: None,
+            dimension_weights: None,
         }),
         ..CollectionParams::empty()
     };
@@ -151,6 +156,7 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
         with_vector: Some(WithVector::Bool(true)),
         params: None,
         score_threshold: None,
+        tie_breaker: None,
     };
 
     let reference_result = collection
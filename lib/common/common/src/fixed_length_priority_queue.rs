@@ -12,6 +12,9 @@ use serde::{Deserialize, Serialize};
 pub struct FixedLengthPriorityQueue<T: Ord> {
     heap: BinaryHeap<Reverse<T>>,
     length: NonZeroUsize,
+    /// Elements lower than threshold are never kept
+    #[serde(default)]
+    threshold: Option<T>,
 }
 
 impl<T: Ord> Default for FixedLengthPriorityQueue<T> {
@@ -24,12 +27,51 @@ impl<T: Ord> FixedLengthPriorityQueue<T> {
     /// Creates a new queue with the given length
     /// Panics if length is 0
     pub fn new(length: usize) -> Self {
+        Self::with_threshold(length, None)
+    }
+
+    /// Creates a new queue with the given length, which rejects elements lower than `threshold`
+    /// Panics if length is 0
+    pub fn with_threshold(length: usize, threshold: Option<T>) -> Self {
         let heap = BinaryHeap::with_capacity(length + 1);
         let length = NonZeroUsize::new(length).expect("length must be > 0");
-        FixedLengthPriorityQueue::<T> { heap, length }
+        FixedLengthPriorityQueue::<T> {
+            heap,
+            length,
+            threshold,
+        }
+    }
+
+    pub fn threshold(&self) -> Option<&T> {
+        self.threshold.as_ref()
+    }
+
+    /// Checks if the value would be kept by the queue, without modifying it
+    ///
+    /// Allows to skip preparation of values, which are going to be rejected anyway.
+    pub fn accepts(&self, value: &T) -> bool {
+        if self
+            .threshold
+            .as_ref()
+            .map_or(false, |threshold| value < threshold)
+        {
+            return false;
+        }
+        if self.heap.len() < self.length.into() {
+            return true;
+        }
+        self.top().map_or(true, |top| top < value)
     }
 
+    /// Pushes the value into the queue
+    ///
+    /// Returns the element, which did not fit into the queue: either the removed lowest element
+    /// or the value itself, if it is not better than the lowest element or lower than the threshold.
     pub fn push(&mut self, value: T) -> Option<T> {
+        if !self.accepts(&value) {
+            return Some(value);
+        }
+
         if self.heap.len() < self.length.into() {
             self.heap.push(Reverse(value));
             return None;
@@ -37,12 +79,31 @@ impl<T: Ord> FixedLengthPriorityQueue<T> {
 
         let mut x = self.heap.peek_mut().unwrap();
         let mut value = Reverse(value);
-        if x.0 < value.0 {
-            std::mem::swap(&mut *x, &mut value);
-        }
+        std::mem::swap(&mut *x, &mut value);
         Some(value.0)
     }
 
+    /// Consumes the queue and iterates over its elements from the largest to the smallest,
+    /// skipping the ones lower than the threshold
+    ///
+    /// Elements are checked against the threshold on push, so this only makes a difference
+    /// if the threshold was raised after some of them were pushed.
+    pub fn top_with_threshold(self) -> impl Iterator<Item = T> {
+        let threshold = self.threshold;
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(x)| x)
+            .take_while(move |x| threshold.as_ref().map_or(true, |threshold| x >= threshold))
+    }
+
+    /// Raises or removes the threshold
+    ///
+    /// Elements already in the queue are kept, see [`Self::top_with_threshold`].
+    pub fn set_threshold(&mut self, threshold: Option<T>) {
+        self.threshold = threshold;
+    }
+
     pub fn into_vec(self) -> Vec<T> {
         self.heap
             .into_sorted_vec()
@@ -61,6 +122,11 @@ impl<T: Ord> FixedLengthPriorityQueue<T> {
         self.heap.peek().map(|x| &x.0)
     }
 
+    /// Returns maximal length of the queue
+    pub fn capacity(&self) -> usize {
+        self.length.into()
+    }
+
     /// Returns actual length of the queue
     pub fn len(&self) -> usize {
         self.heap.len()
@@ -117,3 +183,27 @@ impl<T: Ord> IntoIterator for FixedLengthPriorityQueue<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_with_threshold() {
+        let mut queue = FixedLengthPriorityQueue::with_threshold(3, Some(10));
+
+        assert_eq!(queue.push(5), Some(5));
+        assert!(queue.is_empty());
+
+        for value in [10, 30, 20, 40] {
+            queue.push(value);
+        }
+        assert_eq!(queue.top(), Some(&20));
+        assert!(!queue.accepts(&15));
+        assert_eq!(queue.push(15), Some(15));
+        assert_eq!(queue.push(35), Some(20));
+
+        queue.set_threshold(Some(36));
+        assert_eq!(queue.top_with_threshold().collect::<Vec<_>>(), vec![40]);
+    }
+}
@@ -48,6 +48,9 @@ pub enum Advice {
 
     /// See [`memmap2::Advice::Sequential`].
     Sequential,
+
+    /// See [`memmap2::Advice::WillNeed`].
+    WillNeed,
 }
 
 #[cfg(unix)]
@@ -57,6 +60,7 @@ impl From<Advice> for memmap2::Advice {
             Advice::Normal => memmap2::Advice::Normal,
             Advice::Random => memmap2::Advice::Random,
             Advice::Sequential => memmap2::Advice::Sequential,
+            Advice::WillNeed => memmap2::Advice::WillNeed,
         }
     }
 }
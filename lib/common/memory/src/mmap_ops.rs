@@ -65,6 +65,16 @@ impl PrefaultMmapPages {
     pub fn exec(&self) {
         prefault_mmap_pages(self.mmap.as_ref(), self.path.as_deref());
     }
+
+    /// Advise OS how the memory map will be accessed
+    pub fn madvise(&self, advice: madvise::Advice) -> io::Result<()> {
+        madvise::madvise(self.mmap.as_ref(), advice)
+    }
+
+    /// Read the memory map and lock its pages in RAM, so they are not evicted from the page cache
+    pub fn lock(&self) {
+        lock_mmap_pages(self.mmap.as_ref(), self.path.as_deref());
+    }
 }
 
 fn prefault_mmap_pages<T>(mmap: &T, path: Option<&Path>)
@@ -91,6 +101,37 @@ where
         instant.elapsed()
     );
 }
+
+fn lock_mmap_pages(mmap: &Mmap, path: Option<&Path>) {
+    #[cfg(unix)]
+    {
+        let separator = path.map_or("", |_| " "); // space if `path` is `Some` or nothing
+        let display_path = path.unwrap_or(Path::new("")); // path if `path` is `Some` or nothing
+
+        log::trace!("Locking mmap{separator}{display_path:?} in RAM...");
+
+        let instant = time::Instant::now();
+
+        // `mlock` populates all pages of the mapping before locking them
+        if let Err(err) = mmap.lock() {
+            log::warn!(
+                "Failed to lock mmap{separator}{display_path:?} in RAM, prefault instead: {err}"
+            );
+            prefault_mmap_pages(mmap, path);
+            return;
+        }
+
+        log::trace!(
+            "Locking mmap{separator}{display_path:?} in RAM took {:?}",
+            instant.elapsed()
+        );
+    }
+
+    // Locking is not supported on this platform, just read the pages into the page cache
+    #[cfg(not(unix))]
+    prefault_mmap_pages(mmap, path);
+}
+
 pub fn transmute_from_u8<T>(v: &[u8]) -> &T {
     debug_assert_eq!(v.len(), size_of::<T>());
     unsafe { &*(v.as_ptr() as *const T) }
@@ -12,6 +12,7 @@ use segment::index::hnsw_index::graph_links::GraphLinksRam;
 use segment::index::hnsw_index::point_scorer::FilteredScorer;
 use segment::spaces::metric::Metric;
 use segment::spaces::simple::{CosineMetric, DotProductMetric};
+use segment::vector_storage::DEFAULT_STOPPED;
 
 const NUM_VECTORS: usize = 5_000;
 const DIM: usize = 16;
@@ -36,7 +37,9 @@ fn build_index<TMetric: Metric>(
         let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
         let level = graph_layers_builder.get_random_layer(&mut rng);
         graph_layers_builder.set_levels(idx, level);
-        graph_layers_builder.link_new_point(idx, scorer);
+        graph_layers_builder
+            .link_new_point(idx, scorer, &DEFAULT_STOPPED)
+            .unwrap();
     }
     (
         vector_holder,
@@ -57,7 +60,9 @@ fn hnsw_build_asymptotic(c: &mut Criterion) {
             let query = random_vector(&mut rng, DIM);
             let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
             let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
-            graph_layers.search(TOP, EF, scorer, None);
+            graph_layers
+                .search(TOP, EF, scorer, None, &DEFAULT_STOPPED)
+                .unwrap();
         })
     });
 
@@ -66,7 +71,9 @@ fn hnsw_build_asymptotic(c: &mut Criterion) {
         let query = random_vector(&mut rng, DIM);
         let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
         let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
-        graph_layers.search(TOP, EF, scorer, None);
+        graph_layers
+            .search(TOP, EF, scorer, None, &DEFAULT_STOPPED)
+            .unwrap();
     }
 
     let (vector_holder, graph_layers) = build_index::<CosineMetric>(NUM_VECTORS * 10);
@@ -77,7 +84,9 @@ fn hnsw_build_asymptotic(c: &mut Criterion) {
             let query = random_vector(&mut rng, DIM);
             let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
             let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
-            graph_layers.search(TOP, EF, scorer, None);
+            graph_layers
+                .search(TOP, EF, scorer, None, &DEFAULT_STOPPED)
+                .unwrap();
         })
     });
 
@@ -100,7 +109,9 @@ fn hnsw_build_asymptotic(c: &mut Criterion) {
         let query = random_vector(&mut rng, DIM);
         let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
         let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
-        graph_layers.search(TOP, EF, scorer, None);
+        graph_layers
+            .search(TOP, EF, scorer, None, &DEFAULT_STOPPED)
+            .unwrap();
     }
 }
 
@@ -9,6 +9,7 @@ use segment::fixtures::index_fixtures::{FakeFilterContext, TestRawScorerProducer
 use segment::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
 use segment::index::hnsw_index::point_scorer::FilteredScorer;
 use segment::spaces::simple::CosineMetric;
+use segment::vector_storage::DEFAULT_STOPPED;
 
 const NUM_VECTORS: usize = 10000;
 const DIM: usize = 32;
@@ -33,7 +34,9 @@ fn hnsw_benchmark(c: &mut Criterion) {
                 let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
                 let level = graph_layers_builder.get_random_layer(&mut rng);
                 graph_layers_builder.set_levels(idx, level);
-                graph_layers_builder.link_new_point(idx, scorer);
+                graph_layers_builder
+                    .link_new_point(idx, scorer, &DEFAULT_STOPPED)
+                    .unwrap();
             }
         })
     });
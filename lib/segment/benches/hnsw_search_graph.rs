@@ -10,6 +10,7 @@ use segment::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
 use segment::index::hnsw_index::graph_links::GraphLinksRam;
 use segment::index::hnsw_index::point_scorer::FilteredScorer;
 use segment::spaces::simple::CosineMetric;
+use segment::vector_storage::DEFAULT_STOPPED;
 
 const NUM_VECTORS: usize = 100000;
 const DIM: usize = 64;
@@ -34,7 +35,9 @@ fn hnsw_benchmark(c: &mut Criterion) {
         let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
         let level = graph_layers_builder.get_random_layer(&mut rng);
         graph_layers_builder.set_levels(idx, level);
-        graph_layers_builder.link_new_point(idx, scorer);
+        graph_layers_builder
+            .link_new_point(idx, scorer, &DEFAULT_STOPPED)
+            .unwrap();
     }
     let graph_layers = graph_layers_builder
         .into_graph_layers::<GraphLinksRam>(None)
@@ -47,7 +50,9 @@ fn hnsw_benchmark(c: &mut Criterion) {
             let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
             let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
 
-            graph_layers.search(TOP, EF, scorer, None);
+            graph_layers
+                .search(TOP, EF, scorer, None, &DEFAULT_STOPPED)
+                .unwrap();
         })
     });
 
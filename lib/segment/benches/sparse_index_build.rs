@@ -1,6 +1,7 @@
 #[cfg(not(target_os = "windows"))]
 mod prof;
 
+use std::collections::HashMap;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
@@ -44,6 +45,7 @@ fn sparse_vector_index_build_benchmark(c: &mut Criterion) {
     let payload_index = StructPayloadIndex::open(
         wrapped_payload_storage,
         id_tracker.clone(),
+        HashMap::new(),
         payload_dir.path(),
         true,
     )
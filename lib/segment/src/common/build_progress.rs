@@ -0,0 +1,85 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+/// Shared progress of a long running index build
+///
+/// Cloned handles refer to the same progress, so it can be updated by the building threads
+/// and read by an observer at the same time.
+#[derive(Clone, Debug, Default)]
+pub struct BuildProgress {
+    inner: Arc<BuildProgressInner>,
+}
+
+#[derive(Debug, Default)]
+struct BuildProgressInner {
+    done: AtomicUsize,
+    total: AtomicUsize,
+    started_at: Mutex<Option<Instant>>,
+}
+
+impl BuildProgress {
+    /// (Re)start tracking of a build, which is going to process `total` items
+    pub fn start(&self, total: usize) {
+        self.inner.done.store(0, Ordering::Relaxed);
+        self.inner.total.store(total, Ordering::Relaxed);
+        *self.inner.started_at.lock() = Some(Instant::now());
+    }
+
+    /// Mark `count` more items as processed
+    pub fn inc(&self, count: usize) {
+        self.inner.done.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn done(&self) -> usize {
+        self.inner.done.load(Ordering::Relaxed)
+    }
+
+    pub fn total(&self) -> usize {
+        self.inner.total.load(Ordering::Relaxed)
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.inner.started_at.lock().is_some()
+    }
+
+    /// Estimated time left, extrapolated from the rate of processing so far
+    pub fn eta(&self) -> Option<Duration> {
+        let started_at = (*self.inner.started_at.lock())?;
+        let done = self.done();
+        let total = self.total();
+        if done == 0 {
+            return None;
+        }
+        let left = total.saturating_sub(done);
+        Some(started_at.elapsed().mul_f64(left as f64 / done as f64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_progress() {
+        let progress = BuildProgress::default();
+        assert!(!progress.is_started());
+        assert_eq!(progress.eta(), None);
+
+        progress.start(10);
+        let handle = progress.clone();
+        handle.inc(4);
+        assert_eq!(progress.done(), 4);
+        assert_eq!(progress.total(), 10);
+        assert!(progress.eta().is_some());
+
+        handle.inc(6);
+        assert_eq!(progress.eta(), Some(Duration::ZERO));
+
+        progress.start(5);
+        assert_eq!(progress.done(), 0);
+        assert_eq!(progress.eta(), None);
+    }
+}
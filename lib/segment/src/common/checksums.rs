@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::hash::Hasher;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+use io::file_operations::{atomic_save_json, read_json};
+use seahash::SeaHasher;
+use serde::{Deserialize, Serialize};
+
+use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
+
+pub const CHECKSUMS_FILE: &str = "checksums.json";
+
+const READ_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Checksums of segment files, which are not modified after the segment is built.
+///
+/// Saved on flush of non-appendable segments and verified in background after segment load,
+/// so corrupted files are reported instead of producing garbage search results.
+/// Files rewritten in place, e.g. by a repair of the HNSW graph, must have their checksums
+/// recomputed.
+///
+/// Payload indexes are not covered: their data is kept in RocksDB, which checksums its own
+/// blocks, and on-disk field index files are rebuilt from it each time the segment is loaded.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct SegmentChecksums {
+    /// Checksum of each file by its path relative to the segment directory
+    files: BTreeMap<String, u64>,
+}
+
+impl SegmentChecksums {
+    pub fn get_path(segment_path: &Path) -> PathBuf {
+        segment_path.join(CHECKSUMS_FILE)
+    }
+
+    pub fn check_exists(segment_path: &Path) -> bool {
+        Self::get_path(segment_path).exists()
+    }
+
+    /// Compute checksums of the given files. Files must be located inside of the segment directory.
+    pub fn compute(segment_path: &Path, files: &[PathBuf]) -> OperationResult<Self> {
+        let mut checksums = BTreeMap::new();
+        for file in files {
+            let relative_path = file.strip_prefix(segment_path).map_err(|_| {
+                OperationError::service_error(format!(
+                    "File {} is not located in segment {}",
+                    file.display(),
+                    segment_path.display(),
+                ))
+            })?;
+            checksums.insert(
+                relative_path.to_string_lossy().into_owned(),
+                file_checksum(file, &AtomicBool::new(false))?,
+            );
+        }
+        Ok(Self { files: checksums })
+    }
+
+    pub fn save(&self, segment_path: &Path) -> OperationResult<()> {
+        Ok(atomic_save_json(&Self::get_path(segment_path), self)?)
+    }
+
+    pub fn load(segment_path: &Path) -> OperationResult<Self> {
+        Ok(read_json(&Self::get_path(segment_path))?)
+    }
+
+    /// Verify files of the segment against the saved checksums.
+    ///
+    /// Segments without saved checksums, e.g. created by older versions, are not verified.
+    /// Returns `Cancelled` error if `stopped` is set before all files are read.
+    pub fn verify(segment_path: &Path, stopped: &AtomicBool) -> OperationResult<()> {
+        if !Self::check_exists(segment_path) {
+            return Ok(());
+        }
+
+        let checksums = Self::load(segment_path).map_err(|err| OperationError::DataCorruption {
+            description: format!(
+                "Failed to read checksums of segment {}: {err}",
+                segment_path.display(),
+            ),
+        })?;
+
+        for (relative_path, expected) in &checksums.files {
+            let file = segment_path.join(relative_path);
+            if !file.exists() {
+                return Err(OperationError::DataCorruption {
+                    description: format!("File {} is missing", file.display()),
+                });
+            }
+
+            let actual = file_checksum(&file, stopped)?;
+            if actual != *expected {
+                return Err(OperationError::DataCorruption {
+                    description: format!(
+                        "Checksum mismatch of file {}: expected {expected:016x}, got {actual:016x}",
+                        file.display(),
+                    ),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn file_checksum(path: &Path, stopped: &AtomicBool) -> OperationResult<u64> {
+    let mut file = File::open(path)?;
+    let mut hasher = SeaHasher::new();
+    let mut buffer = vec![0; READ_BUFFER_SIZE];
+    loop {
+        check_process_stopped(stopped)?;
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.write(&buffer[..read]);
+    }
+    Ok(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::OpenOptions;
+    use std::io::{Seek, SeekFrom, Write};
+
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_verify_checksums() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let data_dir = dir.path().join("vector_storage");
+        std::fs::create_dir_all(&data_dir).unwrap();
+        let data_path = data_dir.join("matrix.dat");
+        std::fs::write(&data_path, vec![7u8; 3 * READ_BUFFER_SIZE + 5]).unwrap();
+
+        let stopped = AtomicBool::new(false);
+
+        // No checksums saved yet
+        SegmentChecksums::verify(dir.path(), &stopped).unwrap();
+
+        let checksums = SegmentChecksums::compute(dir.path(), &[data_path.clone()]).unwrap();
+        checksums.save(dir.path()).unwrap();
+        assert_eq!(SegmentChecksums::load(dir.path()).unwrap(), checksums);
+        SegmentChecksums::verify(dir.path(), &stopped).unwrap();
+
+        let err = SegmentChecksums::verify(dir.path(), &AtomicBool::new(true)).unwrap_err();
+        assert!(matches!(err, OperationError::Cancelled { .. }));
+
+        // Flip a single byte
+        let mut file = OpenOptions::new().write(true).open(&data_path).unwrap();
+        file.seek(SeekFrom::Start(READ_BUFFER_SIZE as u64 + 1))
+            .unwrap();
+        file.write_all(&[8]).unwrap();
+        drop(file);
+
+        let err = SegmentChecksums::verify(dir.path(), &stopped).unwrap_err();
+        assert!(matches!(err, OperationError::DataCorruption { .. }));
+
+        std::fs::remove_file(&data_path).unwrap();
+        let err = SegmentChecksums::verify(dir.path(), &stopped).unwrap_err();
+        assert!(matches!(err, OperationError::DataCorruption { .. }));
+    }
+}
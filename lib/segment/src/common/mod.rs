@@ -1,5 +1,7 @@
 pub mod anonymize;
 pub mod arc_atomic_ref_cell_iterator;
+pub mod build_progress;
+pub mod checksums;
 pub mod cpu;
 pub mod error_logging;
 pub mod mmap_type;
@@ -175,6 +177,9 @@ fn check_vector_against_config(
 ) -> OperationResult<()> {
     match vector {
         VectorRef::Dense(vector) => {
+            if vector_config.multivector_config.is_some() {
+                return Err(OperationError::WrongMulti);
+            }
             // Check dimensionality
             let dim = vector_config.size;
             if vector.len() != dim {
@@ -186,6 +191,20 @@ fn check_vector_against_config(
             Ok(())
         }
         VectorRef::Sparse(_) => Err(OperationError::WrongSparse),
+        VectorRef::MultiDense(vector) => {
+            if vector_config.multivector_config.is_none() {
+                return Err(OperationError::WrongMulti);
+            }
+            // Check dimensionality of each vector
+            let dim = vector_config.size;
+            if vector.dim != dim {
+                return Err(OperationError::WrongVector {
+                    expected_dim: dim,
+                    received_dim: vector.dim,
+                });
+            }
+            Ok(())
+        }
     }
 }
 
@@ -196,6 +215,7 @@ fn check_sparse_vector_against_config(
     match vector {
         VectorRef::Dense(_) => Err(OperationError::WrongSparse),
         VectorRef::Sparse(_vector) => Ok(()), // TODO(sparse) check vector by config
+        VectorRef::MultiDense(_) => Err(OperationError::WrongSparse),
     }
 }
 
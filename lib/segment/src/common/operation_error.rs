@@ -44,6 +44,9 @@ pub enum OperationError {
     },
     #[error("Inconsistent storage: {description}")]
     InconsistentStorage { description: String },
+    /// Stored data does not match its checksum, the file was corrupted after it was written.
+    #[error("Data corruption detected: {description}")]
+    DataCorruption { description: String },
     #[error("Out of memory, free: {free}, {description}")]
     OutOfMemory { description: String, free: u64 },
     #[error("Operation cancelled: {description}")]
@@ -52,6 +55,8 @@ pub enum OperationError {
     ValidationError { description: String },
     #[error("Wrong usage of sparse vectors")]
     WrongSparse,
+    #[error("Wrong usage of multivectors")]
+    WrongMulti,
 }
 
 impl OperationError {
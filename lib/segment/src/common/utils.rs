@@ -1,14 +1,20 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
+use atomic_refcell::AtomicRefCell;
 use serde_json::Value;
 
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::vectors::Vector;
 use crate::index::field_index::FieldIndex;
 use crate::types::PayloadKeyType;
+use crate::vector_storage::VectorStorageEnum;
 
 pub type IndexesMap = HashMap<PayloadKeyType, Vec<FieldIndex>>;
 
+/// Vector storages of the segment by vector name
+pub type VectorStoragesMap = HashMap<String, Arc<AtomicRefCell<VectorStorageEnum>>>;
+
 /// Avoids allocating Vec with a single element
 #[derive(Debug)]
 pub enum MultiValue<T> {
@@ -285,8 +291,37 @@ fn delete_array_path(
     MultiValue::default()
 }
 
+/// Path element, which matches any key of an object
+pub const PATH_WILDCARD: &str = "*";
+
+/// Check if a path contains wildcard elements, e.g. `metadata.tmp.*`
+pub fn is_wildcard_path(path: &str) -> bool {
+    path.split('.').any(|element| element == PATH_WILDCARD)
+}
+
+/// Remove values of all keys of the JSON map, which match the rest of the path
+fn remove_wildcard_values(
+    rest_of_path: Option<&str>,
+    json_map: &mut serde_json::Map<String, Value>,
+) -> MultiValue<Value> {
+    let mut values = MultiValue::default();
+    match rest_of_path {
+        None => values.extend(std::mem::take(json_map).into_iter().map(|(_, value)| value)),
+        Some(rest_path) => {
+            for value in json_map.values_mut() {
+                if let Value::Object(map) = value {
+                    values.extend(remove_value_from_json_map(rest_path, map));
+                }
+            }
+        }
+    }
+    values
+}
+
 /// Remove value at a given JSON path from JSON map
 ///
+/// Wildcard path element `*` matches all keys of an object, e.g. `a.*.b` or `a.*`.
+///
 /// performance: the function could be improved by using the Entry API instead of BTreeMap.get_mut
 pub fn remove_value_from_json_map(
     path: &str,
@@ -294,6 +329,8 @@ pub fn remove_value_from_json_map(
 ) -> MultiValue<Value> {
     // check if leaf path element
     match path.split_once('.') {
+        Some((PATH_WILDCARD, rest_path)) => remove_wildcard_values(Some(rest_path), json_map),
+        None if path == PATH_WILDCARD => remove_wildcard_values(None, json_map),
         Some((element, rest_path)) => {
             // check if targeting array
             match parse_array_path(element) {
@@ -323,6 +360,32 @@ pub fn remove_value_from_json_map(
     }
 }
 
+/// Set value at a given JSON path of object keys, creating missing intermediate objects
+///
+/// Array notation is not supported. Intermediate values, which are not objects, are replaced.
+pub fn set_value_to_json_map(
+    path: &str,
+    json_map: &mut serde_json::Map<String, Value>,
+    value: Value,
+) {
+    match path.split_once('.') {
+        Some((element, rest_path)) => {
+            let entry = json_map
+                .entry(element)
+                .or_insert_with(|| Value::Object(Default::default()));
+            if !entry.is_object() {
+                *entry = Value::Object(Default::default());
+            }
+            if let Value::Object(map) = entry {
+                set_value_to_json_map(rest_path, map, value);
+            }
+        }
+        None => {
+            json_map.insert(path.to_string(), value);
+        }
+    }
+}
+
 /// Check if a path is included in a list of patterns
 ///
 /// Basically, it checks if either the pattern or path is a prefix of the other.
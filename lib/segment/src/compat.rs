@@ -52,6 +52,10 @@ impl From<SegmentConfigV5> for SegmentConfig {
                     storage_type: (old_data.on_disk == Some(true))
                         .then_some(VectorStorageType::Mmap)
                         .unwrap_or_else(|| old_segment.storage_type.into()),
+                    multivector_config: None,
+                    datatype: None,
+                    keep_original: None,
+                    dimension_weights: None,
                 };
 
                 (vector_name, new_data)
@@ -62,6 +66,7 @@ impl From<SegmentConfigV5> for SegmentConfig {
             vector_data,
             sparse_vector_data: Default::default(),
             payload_storage_type: old_segment.payload_storage_type,
+            payload_compression: None,
         }
     }
 }
@@ -150,6 +155,16 @@ mod tests {
                             max_indexing_threads: 0,
                             on_disk: None,
                             payload_m: Some(10),
+                            payload_m_overrides: None,
+                            seed: None,
+                            extend_candidates: None,
+                            keep_pruned_connections: None,
+                            compress_links: None,
+                            target_recall: None,
+                            level_factor_multiplier: None,
+                            ef_construct_levels: None,
+                            refine: None,
+                            appendable: None,
                         }),
                         quantization_config: None,
                         on_disk: None,
@@ -181,6 +196,16 @@ mod tests {
                 max_indexing_threads: 0,
                 on_disk: None,
                 payload_m: None,
+                payload_m_overrides: None,
+                seed: None,
+                extend_candidates: None,
+                keep_pruned_connections: None,
+                compress_links: None,
+                target_recall: None,
+                level_factor_multiplier: None,
+                ef_construct_levels: None,
+                refine: None,
+                appendable: None,
             }),
             storage_type: StorageTypeV5::InMemory,
             payload_storage_type: PayloadStorageType::default(),
@@ -192,14 +217,18 @@ mod tests {
         eprintln!("new = {:#?}", new_segment);
 
         match &new_segment.vector_data.get("vec1").unwrap().index {
-            Indexes::Plain { .. } => panic!("expected HNSW index"),
+            Indexes::Plain { .. } | Indexes::Vamana(_) | Indexes::Ivf(_) => {
+                panic!("expected HNSW index")
+            }
             Indexes::Hnsw(hnsw) => {
                 assert_eq!(hnsw.m, 20);
             }
         }
 
         match &new_segment.vector_data.get("vec2").unwrap().index {
-            Indexes::Plain { .. } => panic!("expected HNSW index"),
+            Indexes::Plain { .. } | Indexes::Vamana(_) | Indexes::Ivf(_) => {
+                panic!("expected HNSW index")
+            }
             Indexes::Hnsw(hnsw) => {
                 assert_eq!(hnsw.m, 25);
             }
@@ -256,6 +285,16 @@ mod tests {
                 max_indexing_threads: 0,
                 on_disk: None,
                 payload_m: None,
+                payload_m_overrides: None,
+                seed: None,
+                extend_candidates: None,
+                keep_pruned_connections: None,
+                compress_links: None,
+                target_recall: None,
+                level_factor_multiplier: None,
+                ef_construct_levels: None,
+                refine: None,
+                appendable: None,
             }),
             storage_type: StorageTypeV5::InMemory,
             payload_storage_type: PayloadStorageType::default(),
@@ -0,0 +1,89 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::types::IntPayloadType;
+
+#[derive(
+    Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
+#[serde(untagged)]
+pub enum FacetValue {
+    Keyword(String),
+    Int(IntPayloadType),
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct FacetValueHit {
+    pub value: FacetValue,
+    pub count: usize,
+}
+
+impl FacetValueHit {
+    /// Values with more points go first, values with the same number of points are ordered by value
+    fn cmp_rank(&self, other: &Self) -> Ordering {
+        other
+            .count
+            .cmp(&self.count)
+            .then_with(|| self.value.cmp(&other.value))
+    }
+}
+
+/// Select `limit` values with the most points, or all values if `limit` is `None`
+pub fn top_facet_hits(
+    counts: impl IntoIterator<Item = (FacetValue, usize)>,
+    limit: Option<usize>,
+) -> Vec<FacetValueHit> {
+    let mut hits: Vec<_> = counts
+        .into_iter()
+        .map(|(value, count)| FacetValueHit { value, count })
+        .collect();
+    hits.sort_unstable_by(FacetValueHit::cmp_rank);
+    if let Some(limit) = limit {
+        hits.truncate(limit);
+    }
+    hits
+}
+
+/// Sum counts of the same values
+pub fn merge_facet_counts(
+    counts: impl IntoIterator<Item = (FacetValue, usize)>,
+) -> HashMap<FacetValue, usize> {
+    let mut merged = HashMap::new();
+    for (value, count) in counts {
+        *merged.entry(value).or_insert(0) += count;
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_facet_hits() {
+        let counts = merge_facet_counts([
+            (FacetValue::Keyword("b".to_string()), 2),
+            (FacetValue::Keyword("a".to_string()), 1),
+            (FacetValue::Keyword("c".to_string()), 3),
+            (FacetValue::Keyword("a".to_string()), 1),
+        ]);
+
+        let hits = top_facet_hits(counts, Some(2));
+        assert_eq!(
+            hits,
+            vec![
+                FacetValueHit {
+                    value: FacetValue::Keyword("c".to_string()),
+                    count: 3,
+                },
+                FacetValueHit {
+                    value: FacetValue::Keyword("a".to_string()),
+                    count: 2,
+                },
+            ]
+        );
+    }
+}
@@ -1,5 +1,9 @@
+pub mod facets;
 pub mod groups;
+pub mod index;
 pub mod named_vectors;
+pub mod order_by;
+pub mod primitive;
 pub mod text_index;
 pub mod tiny_map;
 pub mod vectors;
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use sparse::common::sparse_vector::SparseVector;
 
 use super::tiny_map;
-use super::vectors::{DenseVector, Vector, VectorElementType, VectorRef};
+use super::vectors::{DenseVector, MultiDenseVector, Vector, VectorElementType, VectorRef};
 use crate::common::operation_error::OperationError;
 use crate::types::Distance;
 
@@ -14,6 +14,7 @@ type CowKey<'a> = Cow<'a, str>;
 pub enum CowVector<'a> {
     Dense(Cow<'a, [VectorElementType]>),
     Sparse(Cow<'a, SparseVector>),
+    MultiDense(Cow<'a, MultiDenseVector>),
 }
 
 impl<'a> Default for CowVector<'a> {
@@ -34,6 +35,7 @@ impl<'a> CowVector<'a> {
         match self {
             CowVector::Dense(v) => Vector::Dense(v.into_owned()),
             CowVector::Sparse(v) => Vector::Sparse(v.into_owned()),
+            CowVector::MultiDense(v) => Vector::MultiDense(v.into_owned()),
         }
     }
 
@@ -41,6 +43,7 @@ impl<'a> CowVector<'a> {
         match self {
             CowVector::Dense(v) => VectorRef::Dense(v.as_ref()),
             CowVector::Sparse(v) => VectorRef::Sparse(v.as_ref()),
+            CowVector::MultiDense(v) => VectorRef::MultiDense(v.as_ref()),
         }
     }
 }
@@ -50,6 +53,7 @@ impl<'a> From<Vector> for CowVector<'a> {
         match v {
             Vector::Dense(v) => CowVector::Dense(Cow::Owned(v)),
             Vector::Sparse(v) => CowVector::Sparse(Cow::Owned(v)),
+            Vector::MultiDense(v) => CowVector::MultiDense(Cow::Owned(v)),
         }
     }
 }
@@ -60,6 +64,18 @@ impl<'a> From<SparseVector> for CowVector<'a> {
     }
 }
 
+impl<'a> From<MultiDenseVector> for CowVector<'a> {
+    fn from(v: MultiDenseVector) -> Self {
+        CowVector::MultiDense(Cow::Owned(v))
+    }
+}
+
+impl<'a> From<&'a MultiDenseVector> for CowVector<'a> {
+    fn from(v: &'a MultiDenseVector) -> Self {
+        CowVector::MultiDense(Cow::Borrowed(v))
+    }
+}
+
 impl<'a> From<DenseVector> for CowVector<'a> {
     fn from(v: DenseVector) -> Self {
         CowVector::Dense(Cow::Owned(v))
@@ -78,6 +94,12 @@ impl<'a> From<&'a [VectorElementType]> for CowVector<'a> {
     }
 }
 
+impl<'a> From<Cow<'a, [VectorElementType]>> for CowVector<'a> {
+    fn from(v: Cow<'a, [VectorElementType]>) -> Self {
+        CowVector::Dense(v)
+    }
+}
+
 impl<'a> TryFrom<CowVector<'a>> for SparseVector {
     type Error = OperationError;
 
@@ -85,6 +107,7 @@ impl<'a> TryFrom<CowVector<'a>> for SparseVector {
         match value {
             CowVector::Dense(_) => Err(OperationError::WrongSparse),
             CowVector::Sparse(v) => Ok(v.into_owned()),
+            CowVector::MultiDense(_) => Err(OperationError::WrongSparse),
         }
     }
 }
@@ -96,6 +119,7 @@ impl<'a> TryFrom<CowVector<'a>> for DenseVector {
         match value {
             CowVector::Dense(v) => Ok(v.into_owned()),
             CowVector::Sparse(_) => Err(OperationError::WrongSparse),
+            CowVector::MultiDense(_) => Err(OperationError::WrongMulti),
         }
     }
 }
@@ -105,6 +129,7 @@ impl<'a> From<VectorRef<'a>> for CowVector<'a> {
         match v {
             VectorRef::Dense(v) => CowVector::Dense(Cow::Borrowed(v)),
             VectorRef::Sparse(v) => CowVector::Sparse(Cow::Borrowed(v)),
+            VectorRef::MultiDense(v) => CowVector::MultiDense(Cow::Borrowed(v)),
         }
     }
 }
@@ -117,6 +142,7 @@ impl<'a> NamedVectors<'a> {
             match value {
                 VectorRef::Dense(v) => CowVector::Dense(Cow::Borrowed(v)),
                 VectorRef::Sparse(v) => CowVector::Sparse(Cow::Borrowed(v)),
+                VectorRef::MultiDense(v) => CowVector::MultiDense(Cow::Borrowed(v)),
             },
         );
         Self { map }
@@ -155,6 +181,7 @@ impl<'a> NamedVectors<'a> {
             match vector {
                 Vector::Dense(v) => CowVector::Dense(Cow::Owned(v)),
                 Vector::Sparse(v) => CowVector::Sparse(Cow::Owned(v)),
+                Vector::MultiDense(v) => CowVector::MultiDense(Cow::Owned(v)),
             },
         );
     }
@@ -165,6 +192,7 @@ impl<'a> NamedVectors<'a> {
             match vector {
                 VectorRef::Dense(v) => CowVector::Dense(Cow::Borrowed(v)),
                 VectorRef::Sparse(v) => CowVector::Sparse(Cow::Borrowed(v)),
+                VectorRef::MultiDense(v) => CowVector::MultiDense(Cow::Borrowed(v)),
             },
         );
     }
@@ -200,21 +228,31 @@ impl<'a> NamedVectors<'a> {
         self.map.get(key).map(|v| v.as_vec_ref())
     }
 
+    /// Preprocess vectors by the distance of their name, `None` distance keeps dense vectors as is
     pub fn preprocess<F>(&mut self, distance_map: F)
     where
-        F: Fn(&str) -> Distance,
+        F: Fn(&str) -> Option<Distance>,
     {
         for (name, vector) in self.map.iter_mut() {
             let distance = distance_map(name);
             match vector {
                 CowVector::Dense(v) => {
-                    let preprocessed_vector = distance.preprocess_vector(v.to_vec());
-                    *vector = CowVector::Dense(Cow::Owned(preprocessed_vector))
+                    if let Some(distance) = distance {
+                        let preprocessed_vector = distance.preprocess_vector(v.to_vec());
+                        *vector = CowVector::Dense(Cow::Owned(preprocessed_vector))
+                    }
                 }
                 CowVector::Sparse(v) => {
                     // sort by indices to enable faster dot product and overlap checks
                     v.to_mut().sort_by_indices();
                 }
+                CowVector::MultiDense(v) => {
+                    if let Some(distance) = distance {
+                        let preprocessed_vector =
+                            distance.preprocess_multi_vector(v.as_ref().clone());
+                        *vector = CowVector::MultiDense(Cow::Owned(preprocessed_vector))
+                    }
+                }
             }
         }
     }
@@ -0,0 +1,205 @@
+use std::cmp::Ordering;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use validator::Validate;
+
+use crate::types::{
+    parse_datetime, DateTimePayloadType, FloatPayloadType, GeoPoint, PayloadKeyType, Range,
+};
+
+/// Value of the payload field, by which points are ordered.
+/// Datetime values are represented as timestamps in microseconds,
+/// geo points are represented as distances in meters from the origin of ordering.
+pub type OrderValue = FloatPayloadType;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl Direction {
+    /// Compare values in the order of the direction
+    pub fn cmp_values(self, value: OrderValue, other: OrderValue) -> Ordering {
+        let ordering = value.total_cmp(&other);
+        match self {
+            Direction::Asc => ordering,
+            Direction::Desc => ordering.reverse(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq)]
+#[serde(untagged)]
+pub enum StartFrom {
+    Float(FloatPayloadType),
+    Datetime(DateTimePayloadType),
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct OrderBy {
+    /// Payload key to order by
+    #[validate(length(min = 1))]
+    pub key: PayloadKeyType,
+    /// Direction of ordering: `asc` or `desc`. Default is ascending.
+    pub direction: Option<Direction>,
+    /// Which payload value to start scrolling from. Default is the lowest value for `asc` and the highest for `desc`
+    pub start_from: Option<StartFrom>,
+    /// Order by distance in meters from this point, instead of values of the field.
+    /// The field must be indexed with a geo index.
+    pub distance_from: Option<GeoPoint>,
+}
+
+impl OrderBy {
+    pub fn direction(&self) -> Direction {
+        self.direction.unwrap_or_default()
+    }
+
+    /// Range of values, which are traversed in the order of the `direction`
+    pub fn value_range(&self) -> Range {
+        let start_from = self.start_from.map(|start_from| match start_from {
+            StartFrom::Float(value) => value,
+            StartFrom::Datetime(datetime) => datetime.timestamp_micros() as OrderValue,
+        });
+        match self.direction() {
+            Direction::Asc => Range {
+                gte: start_from,
+                ..Default::default()
+            },
+            Direction::Desc => Range {
+                lte: start_from,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Compare values in the order of the `direction`
+    pub fn cmp_values(&self, value: OrderValue, other: OrderValue) -> Ordering {
+        self.direction().cmp_values(value, other)
+    }
+}
+
+/// Secondary order of search results, applied to the points with equal scores
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct TieBreaker {
+    /// Payload key to order by
+    #[validate(length(min = 1))]
+    pub key: PayloadKeyType,
+    /// Direction of ordering: `asc` or `desc`. Default is ascending.
+    pub direction: Option<Direction>,
+    /// Order by distance in meters from this point, instead of values of the field
+    pub distance_from: Option<GeoPoint>,
+}
+
+impl TieBreaker {
+    pub fn direction(&self) -> Direction {
+        self.direction.unwrap_or_default()
+    }
+
+    /// Compare optional values in the order of the `direction`, missing values go last
+    pub fn cmp_values(&self, value: Option<OrderValue>, other: Option<OrderValue>) -> Ordering {
+        match (value, other) {
+            (Some(value), Some(other)) => self.direction().cmp_values(value, other),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+
+    /// Select the first value of the field in the order of the `direction`
+    ///
+    /// Numbers are used as is, datetime strings are converted to timestamps in microseconds.
+    /// If `distance_from` is set, only geo points are used, converted to distances in meters.
+    pub fn order_value<'a>(
+        &self,
+        values: impl IntoIterator<Item = &'a Value>,
+    ) -> Option<OrderValue> {
+        values
+            .into_iter()
+            .filter_map(|value| match (&self.distance_from, value) {
+                (Some(origin), Value::Object(_)) => GeoPoint::deserialize(value)
+                    .ok()
+                    .map(|point| origin.distance(&point)),
+                (Some(_), _) => None,
+                (None, Value::Number(number)) => number.as_f64(),
+                (None, Value::String(string)) => {
+                    parse_datetime(string).map(|datetime| datetime.timestamp_micros() as OrderValue)
+                }
+                (None, _) => None,
+            })
+            .min_by(|value, other| self.direction().cmp_values(*value, *other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_tie_breaker_order_value() {
+        let values = [
+            json!(3),
+            json!("2024-01-01T00:00:00Z"),
+            json!(1.5),
+            json!("not a date"),
+            json!(true),
+        ];
+
+        let asc = TieBreaker {
+            key: "price".to_string(),
+            direction: None,
+            distance_from: None,
+        };
+        assert_eq!(asc.order_value(&values), Some(1.5));
+        assert_eq!(asc.order_value(&[]), None);
+
+        let desc = TieBreaker {
+            key: "price".to_string(),
+            direction: Some(Direction::Desc),
+            distance_from: None,
+        };
+        let timestamp = parse_datetime("2024-01-01T00:00:00Z")
+            .unwrap()
+            .timestamp_micros() as OrderValue;
+        assert_eq!(desc.order_value(&values), Some(timestamp));
+
+        assert_eq!(desc.cmp_values(Some(1.0), Some(2.0)), Ordering::Greater);
+        assert_eq!(desc.cmp_values(None, Some(2.0)), Ordering::Greater);
+        assert_eq!(asc.cmp_values(Some(1.0), None), Ordering::Less);
+    }
+
+    #[test]
+    fn test_tie_breaker_geo_distance() {
+        let origin = GeoPoint { lon: 0.0, lat: 0.0 };
+        let far = GeoPoint { lon: 0.5, lat: 0.5 };
+        let values = [
+            json!({"lon": 0.5, "lat": 0.5}),
+            json!({"lon": 0.1, "lat": 0.0}),
+            json!(3),
+            json!({"lon": 500.0, "lat": 0.0}),
+        ];
+
+        let nearest = TieBreaker {
+            key: "location".to_string(),
+            direction: None,
+            distance_from: Some(origin.clone()),
+        };
+        let near_distance = origin.distance(&GeoPoint { lon: 0.1, lat: 0.0 });
+        assert_eq!(nearest.order_value(&values), Some(near_distance));
+        assert_eq!(nearest.order_value(&values[2..]), None);
+
+        let farthest = TieBreaker {
+            direction: Some(Direction::Desc),
+            ..nearest
+        };
+        assert_eq!(farthest.order_value(&values), Some(origin.distance(&far)));
+    }
+}
@@ -0,0 +1,67 @@
+use std::borrow::Cow;
+
+use half::slice::HalfFloatSliceExt;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::data_types::vectors::{VectorElementType, VectorElementTypeByte, VectorElementTypeHalf};
+use crate::types::VectorStorageDatatype;
+
+/// Element type, in which dense vectors are kept by a vector storage
+///
+/// Vectors always enter and leave storages as `VectorElementType`,
+/// conversion to the storage element type is done at the storage boundary.
+pub trait PrimitiveVectorElement:
+    Copy + Clone + Default + Serialize + DeserializeOwned + Send + Sync + 'static
+{
+    fn datatype() -> VectorStorageDatatype;
+
+    fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]>;
+
+    fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]>;
+}
+
+impl PrimitiveVectorElement for VectorElementType {
+    fn datatype() -> VectorStorageDatatype {
+        VectorStorageDatatype::Float32
+    }
+
+    fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]> {
+        vector
+    }
+
+    fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]> {
+        vector
+    }
+}
+
+impl PrimitiveVectorElement for VectorElementTypeHalf {
+    fn datatype() -> VectorStorageDatatype {
+        VectorStorageDatatype::Float16
+    }
+
+    fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]> {
+        let mut converted = vec![VectorElementTypeHalf::default(); vector.len()];
+        converted.convert_from_f32_slice(&vector);
+        Cow::Owned(converted)
+    }
+
+    fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]> {
+        Cow::Owned(vector.to_f32_vec())
+    }
+}
+
+impl PrimitiveVectorElement for VectorElementTypeByte {
+    fn datatype() -> VectorStorageDatatype {
+        VectorStorageDatatype::Uint8
+    }
+
+    fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]> {
+        // Float to integer casts are saturating, out of range values are clamped to `0..=255`
+        Cow::Owned(vector.iter().map(|&x| x.round() as Self).collect())
+    }
+
+    fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]> {
+        Cow::Owned(vector.iter().map(|&x| VectorElementType::from(x)).collect())
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -15,6 +17,33 @@ pub enum TokenizerType {
     #[default]
     Word,
     Multilingual,
+    /// Splits words into all their substrings with length from `min_token_len` to `max_token_len`,
+    /// 3 characters by default. Enables matching of arbitrary substrings of words.
+    Ngram,
+}
+
+/// Language of the Snowball stemmer, which reduces words to their stems
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StemmerLanguage {
+    Arabic,
+    Danish,
+    Dutch,
+    English,
+    Finnish,
+    French,
+    German,
+    Greek,
+    Hungarian,
+    Italian,
+    Norwegian,
+    Portuguese,
+    Romanian,
+    Russian,
+    Spanish,
+    Swedish,
+    Tamil,
+    Turkish,
 }
 
 #[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
@@ -41,4 +70,86 @@ pub struct TextIndexParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     /// If true, lowercase all tokens. Default: true
     pub lowercase: Option<bool>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// If set, tokens are reduced to their stems by the Snowball stemmer of this language.
+    /// Not applied with `prefix` and `ngram` tokenizers.
+    pub stemmer: Option<StemmerLanguage>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Tokens, which are neither indexed nor used in queries.
+    /// Compared with tokens after lowercasing, before stemming.
+    pub stopwords: Option<BTreeSet<String>>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Groups of interchangeable words. Query token from a group also matches documents,
+    /// which contain any other word of the group. Not applied to phrase queries.
+    /// Compared with tokens after lowercasing, before stemming.
+    pub synonyms: Option<Vec<BTreeSet<String>>>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Additional named configurations of tokenization. Each analyzer builds a separate full-text
+    /// index of the same field, which is used by text conditions with the name of the analyzer.
+    pub analyzers: Option<BTreeMap<String, TextAnalyzerParams>>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// If true, store the index on disk. Default: false.
+    pub on_disk: Option<bool>,
+}
+
+impl TextIndexParams {
+    /// Parameters of the full-text index, built with the given analyzer
+    pub fn with_analyzer(&self, analyzer: &TextAnalyzerParams) -> TextIndexParams {
+        let TextAnalyzerParams {
+            tokenizer,
+            min_token_len,
+            max_token_len,
+            lowercase,
+            stemmer,
+            stopwords,
+            synonyms,
+        } = analyzer.clone();
+        TextIndexParams {
+            r#type: self.r#type,
+            tokenizer,
+            min_token_len,
+            max_token_len,
+            lowercase,
+            stemmer,
+            stopwords,
+            synonyms,
+            analyzers: None,
+            on_disk: self.on_disk,
+        }
+    }
+}
+
+/// Tokenization of the additional full-text index of the field, see `TextIndexParams`
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct TextAnalyzerParams {
+    #[serde(default)]
+    pub tokenizer: TokenizerType,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_token_len: Option<usize>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_token_len: Option<usize>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// If true, lowercase all tokens. Default: true
+    pub lowercase: Option<bool>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// If set, tokens are reduced to their stems by the Snowball stemmer of this language.
+    pub stemmer: Option<StemmerLanguage>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Tokens, which are neither indexed nor used in queries.
+    pub stopwords: Option<BTreeSet<String>>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Groups of interchangeable words.
+    pub synonyms: Option<Vec<BTreeSet<String>>>,
 }
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sparse::common::sparse_vector::SparseVector;
@@ -17,12 +19,14 @@ use crate::vector_storage::query::reco_query::RecoQuery;
 pub enum Vector {
     Dense(DenseVector),
     Sparse(SparseVector),
+    MultiDense(MultiDenseVector),
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum VectorRef<'a> {
     Dense(&'a [VectorElementType]),
     Sparse(&'a SparseVector),
+    MultiDense(&'a MultiDenseVector),
 }
 
 impl Vector {
@@ -30,6 +34,7 @@ impl Vector {
         match self {
             Vector::Dense(v) => VectorRef::Dense(v.as_slice()),
             Vector::Sparse(v) => VectorRef::Sparse(v),
+            Vector::MultiDense(v) => VectorRef::MultiDense(v),
         }
     }
 }
@@ -39,6 +44,7 @@ impl Validate for Vector {
         match self {
             Vector::Dense(_) => Ok(()),
             Vector::Sparse(v) => v.validate(),
+            Vector::MultiDense(_) => Ok(()),
         }
     }
 }
@@ -48,6 +54,7 @@ impl<'a> VectorRef<'a> {
         match self {
             VectorRef::Dense(v) => Vector::Dense(v.to_vec()),
             VectorRef::Sparse(v) => Vector::Sparse(v.clone()),
+            VectorRef::MultiDense(v) => Vector::MultiDense(v.clone()),
         }
     }
 }
@@ -59,6 +66,7 @@ impl<'a> TryFrom<VectorRef<'a>> for &'a [VectorElementType] {
         match value {
             VectorRef::Dense(v) => Ok(v),
             VectorRef::Sparse(_) => Err(OperationError::WrongSparse),
+            VectorRef::MultiDense(_) => Err(OperationError::WrongMulti),
         }
     }
 }
@@ -70,6 +78,19 @@ impl<'a> TryFrom<VectorRef<'a>> for &'a SparseVector {
         match value {
             VectorRef::Dense(_) => Err(OperationError::WrongSparse),
             VectorRef::Sparse(v) => Ok(v),
+            VectorRef::MultiDense(_) => Err(OperationError::WrongSparse),
+        }
+    }
+}
+
+impl<'a> TryFrom<VectorRef<'a>> for &'a MultiDenseVector {
+    type Error = OperationError;
+
+    fn try_from(value: VectorRef<'a>) -> Result<Self, Self::Error> {
+        match value {
+            VectorRef::Dense(_) => Err(OperationError::WrongMulti),
+            VectorRef::Sparse(_) => Err(OperationError::WrongSparse),
+            VectorRef::MultiDense(v) => Ok(v),
         }
     }
 }
@@ -80,6 +101,7 @@ impl From<NamedVectorStruct> for Vector {
             NamedVectorStruct::Default(v) => Vector::Dense(v),
             NamedVectorStruct::Dense(v) => Vector::Dense(v.vector),
             NamedVectorStruct::Sparse(v) => Vector::Sparse(v.vector),
+            NamedVectorStruct::MultiDense(v) => Vector::MultiDense(v.vector),
         }
     }
 }
@@ -91,6 +113,7 @@ impl TryFrom<Vector> for DenseVector {
         match value {
             Vector::Dense(v) => Ok(v),
             Vector::Sparse(_) => Err(OperationError::WrongSparse),
+            Vector::MultiDense(_) => Err(OperationError::WrongMulti),
         }
     }
 }
@@ -102,6 +125,19 @@ impl TryFrom<Vector> for SparseVector {
         match value {
             Vector::Dense(_) => Err(OperationError::WrongSparse),
             Vector::Sparse(v) => Ok(v),
+            Vector::MultiDense(_) => Err(OperationError::WrongSparse),
+        }
+    }
+}
+
+impl TryFrom<Vector> for MultiDenseVector {
+    type Error = OperationError;
+
+    fn try_from(value: Vector) -> Result<Self, Self::Error> {
+        match value {
+            Vector::Dense(_) => Err(OperationError::WrongMulti),
+            Vector::Sparse(_) => Err(OperationError::WrongSparse),
+            Vector::MultiDense(v) => Ok(v),
         }
     }
 }
@@ -124,6 +160,12 @@ impl<'a> From<&'a SparseVector> for VectorRef<'a> {
     }
 }
 
+impl<'a> From<&'a MultiDenseVector> for VectorRef<'a> {
+    fn from(val: &'a MultiDenseVector) -> Self {
+        VectorRef::MultiDense(val)
+    }
+}
+
 impl From<DenseVector> for Vector {
     fn from(val: DenseVector) -> Self {
         Vector::Dense(val)
@@ -136,11 +178,18 @@ impl From<SparseVector> for Vector {
     }
 }
 
+impl From<MultiDenseVector> for Vector {
+    fn from(val: MultiDenseVector) -> Self {
+        Vector::MultiDense(val)
+    }
+}
+
 impl<'a> From<&'a Vector> for VectorRef<'a> {
     fn from(val: &'a Vector) -> Self {
         match val {
             Vector::Dense(v) => VectorRef::Dense(v.as_slice()),
             Vector::Sparse(v) => VectorRef::Sparse(v),
+            Vector::MultiDense(v) => VectorRef::MultiDense(v),
         }
     }
 }
@@ -148,17 +197,101 @@ impl<'a> From<&'a Vector> for VectorRef<'a> {
 /// Type of vector element.
 pub type VectorElementType = f32;
 
+/// Type of vector element, stored with half precision.
+pub type VectorElementTypeHalf = half::f16;
+
+/// Type of vector element, stored as unsigned byte.
+pub type VectorElementTypeByte = u8;
+
 pub const DEFAULT_VECTOR_NAME: &str = "";
 
 /// Type for dense vector
 pub type DenseVector = Vec<VectorElementType>;
 
+/// Multiple dense vectors of the same dimensionality, representing a single point
+///
+/// Vectors are stored one after another in a single flat array.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "Vec<DenseVector>", into = "Vec<DenseVector>")]
+pub struct MultiDenseVector {
+    pub flattened_vectors: DenseVector,
+    pub dim: usize,
+}
+
+impl MultiDenseVector {
+    pub fn new(flattened_vectors: DenseVector, dim: usize) -> Self {
+        debug_assert!(dim > 0 && flattened_vectors.len() % dim == 0);
+        Self {
+            flattened_vectors,
+            dim,
+        }
+    }
+
+    /// Create from a list of vectors, which must be non-empty and of the same dimensionality
+    pub fn try_from_matrix(matrix: Vec<DenseVector>) -> Result<Self, OperationError> {
+        let dim = matrix.first().map_or(0, |vector| vector.len());
+        if dim == 0 {
+            return Err(OperationError::ValidationError {
+                description: "multivector must contain at least one non-empty vector".to_string(),
+            });
+        }
+        if matrix.iter().any(|vector| vector.len() != dim) {
+            return Err(OperationError::ValidationError {
+                description: "all vectors of a multivector must have the same dimensionality"
+                    .to_string(),
+            });
+        }
+        Ok(Self::new(matrix.concat(), dim))
+    }
+
+    pub fn multi_vectors(&self) -> impl Iterator<Item = &[VectorElementType]> + Clone + Send + '_ {
+        self.flattened_vectors.chunks_exact(self.dim.max(1))
+    }
+
+    pub fn vectors_count(&self) -> usize {
+        self.flattened_vectors.len() / self.dim.max(1)
+    }
+
+    pub fn to_matrix(&self) -> Vec<DenseVector> {
+        self.multi_vectors().map(|vector| vector.to_vec()).collect()
+    }
+}
+
+impl TryFrom<Vec<DenseVector>> for MultiDenseVector {
+    type Error = OperationError;
+
+    fn try_from(matrix: Vec<DenseVector>) -> Result<Self, Self::Error> {
+        Self::try_from_matrix(matrix)
+    }
+}
+
+impl From<MultiDenseVector> for Vec<DenseVector> {
+    fn from(value: MultiDenseVector) -> Self {
+        value.to_matrix()
+    }
+}
+
+impl JsonSchema for MultiDenseVector {
+    fn schema_name() -> String {
+        "MultiDenseVector".to_string()
+    }
+
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        <Vec<DenseVector>>::json_schema(gen)
+    }
+}
+
 impl<'a> VectorRef<'a> {
     // Cannot use `ToOwned` trait because of `Borrow` implementation for `Vector`
     pub fn to_owned(self) -> Vector {
         match self {
             VectorRef::Dense(v) => Vector::Dense(v.to_vec()),
             VectorRef::Sparse(v) => Vector::Sparse(v.clone()),
+            VectorRef::MultiDense(v) => Vector::MultiDense(v.clone()),
         }
     }
 
@@ -166,6 +299,7 @@ impl<'a> VectorRef<'a> {
         match self {
             VectorRef::Dense(v) => v.len(),
             VectorRef::Sparse(v) => v.indices.len(),
+            VectorRef::MultiDense(v) => v.dim,
         }
     }
 
@@ -181,6 +315,7 @@ impl<'a> TryInto<&'a [VectorElementType]> for &'a Vector {
         match self {
             Vector::Dense(v) => Ok(v),
             Vector::Sparse(_) => Err(OperationError::WrongSparse),
+            Vector::MultiDense(_) => Err(OperationError::WrongMulti),
         }
     }
 }
@@ -192,6 +327,7 @@ impl<'a> TryInto<&'a SparseVector> for &'a Vector {
         match self {
             Vector::Dense(_) => Err(OperationError::WrongSparse),
             Vector::Sparse(v) => Ok(v),
+            Vector::MultiDense(_) => Err(OperationError::WrongSparse),
         }
     }
 }
@@ -220,6 +356,7 @@ impl VectorStruct {
             VectorStruct::Multi(vectors) => vectors.values().all(|v| match v {
                 Vector::Dense(vector) => vector.is_empty(),
                 Vector::Sparse(vector) => vector.indices.is_empty(),
+                Vector::MultiDense(vector) => vector.flattened_vectors.is_empty(),
             }),
         }
     }
@@ -294,6 +431,16 @@ pub struct NamedSparseVector {
     pub vector: SparseVector,
 }
 
+/// Multivector data with name
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct NamedMultiDenseVector {
+    /// Name of vector data
+    pub name: String,
+    /// Vector data
+    pub vector: MultiDenseVector,
+}
+
 /// Vector data separator for named and unnamed modes
 /// Unnamed mode:
 ///
@@ -316,6 +463,7 @@ pub enum NamedVectorStruct {
     Default(DenseVector),
     Dense(NamedVector),
     Sparse(NamedSparseVector),
+    MultiDense(NamedMultiDenseVector),
 }
 
 impl From<DenseVector> for NamedVectorStruct {
@@ -336,6 +484,12 @@ impl From<NamedSparseVector> for NamedVectorStruct {
     }
 }
 
+impl From<NamedMultiDenseVector> for NamedVectorStruct {
+    fn from(v: NamedMultiDenseVector) -> Self {
+        NamedVectorStruct::MultiDense(v)
+    }
+}
+
 pub trait Named {
     fn get_name(&self) -> &str;
 }
@@ -346,6 +500,7 @@ impl Named for NamedVectorStruct {
             NamedVectorStruct::Default(_) => DEFAULT_VECTOR_NAME,
             NamedVectorStruct::Dense(v) => &v.name,
             NamedVectorStruct::Sparse(v) => &v.name,
+            NamedVectorStruct::MultiDense(v) => &v.name,
         }
     }
 }
@@ -355,6 +510,9 @@ impl NamedVectorStruct {
         match vector {
             Vector::Dense(vector) => NamedVectorStruct::Dense(NamedVector { name, vector }),
             Vector::Sparse(vector) => NamedVectorStruct::Sparse(NamedSparseVector { name, vector }),
+            Vector::MultiDense(vector) => {
+                NamedVectorStruct::MultiDense(NamedMultiDenseVector { name, vector })
+            }
         }
     }
 
@@ -363,6 +521,7 @@ impl NamedVectorStruct {
             NamedVectorStruct::Default(v) => v.as_slice().into(),
             NamedVectorStruct::Dense(v) => v.vector.as_slice().into(),
             NamedVectorStruct::Sparse(v) => (&v.vector).into(),
+            NamedVectorStruct::MultiDense(v) => (&v.vector).into(),
         }
     }
 
@@ -371,6 +530,7 @@ impl NamedVectorStruct {
             NamedVectorStruct::Default(v) => v.into(),
             NamedVectorStruct::Dense(v) => v.vector.into(),
             NamedVectorStruct::Sparse(v) => v.vector.into(),
+            NamedVectorStruct::MultiDense(v) => v.vector.into(),
         }
     }
 }
@@ -381,6 +541,7 @@ impl Validate for NamedVectorStruct {
             NamedVectorStruct::Default(_) => Ok(()),
             NamedVectorStruct::Dense(_) => Ok(()),
             NamedVectorStruct::Sparse(v) => v.validate(),
+            NamedVectorStruct::MultiDense(_) => Ok(()),
         }
     }
 }
@@ -487,3 +648,9 @@ impl From<SparseVector> for QueryVector {
         Self::Nearest(Vector::Sparse(vec))
     }
 }
+
+impl From<MultiDenseVector> for QueryVector {
+    fn from(vec: MultiDenseVector) -> Self {
+        Self::Nearest(Vector::MultiDense(vec))
+    }
+}
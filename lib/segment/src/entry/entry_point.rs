@@ -3,9 +3,12 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 
 use crate::common::operation_error::{OperationResult, SegmentFailedState};
+use crate::data_types::facets::FacetValue;
 use crate::data_types::named_vectors::NamedVectors;
+use crate::data_types::order_by::{OrderBy, OrderValue};
 use crate::data_types::vectors::{QueryVector, Vector};
 use crate::index::field_index::CardinalityEstimation;
+use crate::index::query_planner::SearchPlan;
 use crate::telemetry::SegmentTelemetry;
 use crate::types::{
     Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
@@ -50,6 +53,15 @@ pub trait SegmentEntry {
         is_stopped: &AtomicBool,
     ) -> OperationResult<Vec<Vec<ScoredPoint>>>;
 
+    /// Strategy, which the search in the vector index would use with the given filter and params.
+    /// `None` if the index doesn't choose its strategy with the query planner.
+    fn search_plan(
+        &self,
+        vector_name: &str,
+        filter: Option<&Filter>,
+        params: Option<&SearchParams>,
+    ) -> OperationResult<Option<SearchPlan>>;
+
     fn upsert_point(
         &mut self,
         op_num: SeqNumberType,
@@ -121,6 +133,15 @@ pub trait SegmentEntry {
         filter: Option<&'a Filter>,
     ) -> Vec<PointIdType>;
 
+    /// Read points, which satisfy filtering condition, in the order of payload values of
+    /// `order_by.key`. Each point is returned once, with its first value in that order.
+    fn read_ordered_filtered<'a>(
+        &'a self,
+        limit: Option<usize>,
+        filter: Option<&'a Filter>,
+        order_by: &'a OrderBy,
+    ) -> OperationResult<Vec<(OrderValue, PointIdType)>>;
+
     /// Read points in [from; to) range
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType>;
 
@@ -130,10 +151,21 @@ pub trait SegmentEntry {
     /// Estimate available point count in this segment for given filter.
     fn estimate_point_count<'a>(&'a self, filter: Option<&'a Filter>) -> CardinalityEstimation;
 
+    /// Count points with each value of an indexed payload field, which satisfy the filter.
+    fn facet(
+        &self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HashMap<FacetValue, usize>>;
+
     fn vector_dim(&self, vector_name: &str) -> OperationResult<usize>;
 
     fn vector_dims(&self) -> HashMap<String, usize>;
 
+    /// Size of the available vectors in bytes, as stored by the vector storage
+    fn available_vectors_size_in_bytes(&self, vector_name: &str) -> OperationResult<usize>;
+
     /// Number of available points
     ///
     /// - excludes soft deleted points
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
@@ -40,6 +41,7 @@ pub fn fixture_open_sparse_index<I: InvertedIndex>(
     let payload_index = StructPayloadIndex::open(
         wrapped_payload_storage,
         id_tracker.clone(),
+        HashMap::new(),
         payload_dir,
         true,
     )?;
@@ -5,7 +5,7 @@ use rand::{Rng, SeedableRng};
 
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
-use crate::types::{PointIdType, SeqNumberType};
+use crate::types::{IdRange, PointIdType, SeqNumberType};
 
 /// Sampling randomness seed
 ///
@@ -59,6 +59,17 @@ pub trait IdTracker {
         external_id: Option<PointIdType>,
     ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_>;
 
+    /// Iterate over IDs in a given range, in the order of external IDs
+    fn iter_range(
+        &self,
+        range: IdRange,
+    ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
+        Box::new(
+            self.iter_from(range.gte)
+                .take_while(move |(external_id, _)| range.lt.map_or(true, |lt| *external_id < lt)),
+        )
+    }
+
     /// Iterate over internal IDs (offsets)
     ///
     /// - excludes removed points
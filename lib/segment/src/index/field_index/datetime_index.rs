@@ -0,0 +1,196 @@
+use std::sync::Arc;
+
+use common::types::PointOffsetType;
+use parking_lot::RwLock;
+use rocksdb::DB;
+use serde_json::Value;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::Flusher;
+use crate::index::field_index::numeric_index::NumericIndex;
+use crate::index::field_index::{
+    CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex, PrimaryCondition, ValueIndexer,
+};
+use crate::telemetry::PayloadIndexTelemetry;
+use crate::types::{
+    parse_datetime, DatetimeRange, FieldCondition, IntPayloadType, PayloadKeyType, Range,
+    RangeInterface,
+};
+
+/// Index over RFC 3339 datetime strings.
+///
+/// Values are stored in a numeric index as timestamps in microseconds since the Unix epoch.
+pub struct DatetimeIndex {
+    index: NumericIndex<IntPayloadType>,
+}
+
+impl DatetimeIndex {
+    pub fn new(db: Arc<RwLock<DB>>, field: &str, is_appendable: bool) -> Self {
+        Self {
+            index: NumericIndex::new(db, field, is_appendable),
+        }
+    }
+
+    pub fn recreate(&self) -> OperationResult<()> {
+        self.index.recreate()
+    }
+
+    /// Timestamps of the point values, in microseconds
+    pub fn get_values(&self, idx: PointOffsetType) -> Option<&[IntPayloadType]> {
+        self.index.get_values(idx)
+    }
+
+    /// Timestamps in the `range` with their points, ordered by timestamp
+    pub fn stream_range(
+        &self,
+        range: &Range,
+    ) -> Box<dyn DoubleEndedIterator<Item = (IntPayloadType, PointOffsetType)> + '_> {
+        self.index.stream_range(range)
+    }
+
+    pub fn get_telemetry_data(&self) -> PayloadIndexTelemetry {
+        self.index.get_telemetry_data()
+    }
+
+    pub fn values_count(&self, point_id: PointOffsetType) -> usize {
+        self.index.values_count(point_id)
+    }
+
+    pub fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
+        self.index.values_is_empty(point_id)
+    }
+}
+
+impl PayloadFieldIndex for DatetimeIndex {
+    fn count_indexed_points(&self) -> usize {
+        self.index.count_indexed_points()
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        self.index.load()
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        PayloadFieldIndex::clear(self.index)
+    }
+
+    fn flusher(&self) -> Flusher {
+        self.index.flusher()
+    }
+
+    fn filter(
+        &self,
+        condition: &FieldCondition,
+    ) -> OperationResult<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
+        match &condition.range {
+            Some(RangeInterface::DateTime(range)) => {
+                Ok(self.index.range_filter(&range.to_timestamp_range()))
+            }
+            _ => Err(OperationError::service_error(
+                "failed to get condition range",
+            )),
+        }
+    }
+
+    fn estimate_cardinality(
+        &self,
+        condition: &FieldCondition,
+    ) -> OperationResult<CardinalityEstimation> {
+        match &condition.range {
+            Some(RangeInterface::DateTime(range)) => Ok(self
+                .index
+                .range_cardinality(&range.to_timestamp_range())
+                .with_primary_clause(PrimaryCondition::Condition(condition.clone()))),
+            _ => Err(OperationError::service_error(
+                "failed to estimate cardinality",
+            )),
+        }
+    }
+
+    fn payload_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        // Numeric index produces blocks over timestamps, convert them back into datetime ranges
+        Box::new(self.index.payload_blocks(threshold, key).map(|mut block| {
+            if let Some(RangeInterface::Float(range)) = &block.condition.range {
+                block.condition.range = Some(RangeInterface::DateTime(
+                    DatetimeRange::from_timestamp_range(range),
+                ));
+            }
+            block
+        }))
+    }
+}
+
+impl ValueIndexer<IntPayloadType> for DatetimeIndex {
+    fn add_many(
+        &mut self,
+        id: PointOffsetType,
+        values: Vec<IntPayloadType>,
+    ) -> OperationResult<()> {
+        self.index.add_many(id, values)
+    }
+
+    fn get_value(&self, value: &Value) -> Option<IntPayloadType> {
+        if let Value::String(datetime) = value {
+            return parse_datetime(datetime).map(|datetime| datetime.timestamp_micros());
+        }
+        None
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        self.index.remove_point(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
+    use crate::common::utils::MultiValue;
+    use crate::types::Range;
+
+    #[test]
+    fn test_datetime_range_filter() {
+        let temp_dir = Builder::new()
+            .prefix("test_datetime_index")
+            .tempdir()
+            .unwrap();
+        let db = open_db_with_existing_cf(temp_dir.path()).unwrap();
+        let mut index = DatetimeIndex::new(db, "test", true);
+        index.recreate().unwrap();
+
+        let values = [
+            json!("2014-01-01T00:00:00Z"),
+            json!("2015-06-01T12:30:00+02:00"),
+            json!(["2016-01-01T00:00:00Z", "2020-01-01T00:00:00Z"]),
+            json!("not a datetime"),
+        ];
+        for (idx, value) in values.iter().enumerate() {
+            index
+                .add_point(idx as PointOffsetType, &MultiValue::Single(Some(value)))
+                .unwrap();
+        }
+        assert_eq!(index.count_indexed_points(), 3);
+        assert_eq!(index.values_count(2), 2);
+
+        let range = DatetimeRange {
+            gte: parse_datetime("2015-01-01T00:00:00Z"),
+            lt: parse_datetime("2017-01-01T00:00:00Z"),
+            ..Default::default()
+        };
+        let condition = FieldCondition::new_range("test".to_string(), range);
+        let mut points = index.filter(&condition).unwrap().collect::<Vec<_>>();
+        points.sort_unstable();
+        points.dedup();
+        assert_eq!(points, vec![1, 2]);
+
+        let float_condition = FieldCondition::new_range("test".to_string(), Range::default());
+        assert!(index.filter(&float_condition).is_err());
+    }
+}
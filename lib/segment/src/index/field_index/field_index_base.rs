@@ -7,13 +7,16 @@ use crate::common::operation_error::OperationResult;
 use crate::common::utils::MultiValue;
 use crate::common::Flusher;
 use crate::index::field_index::binary_index::BinaryIndex;
+use crate::index::field_index::datetime_index::DatetimeIndex;
 use crate::index::field_index::full_text_index::text_index::FullTextIndex;
 use crate::index::field_index::geo_index::GeoMapIndex;
+use crate::index::field_index::null_index::NullIndex;
 use crate::index::field_index::numeric_index::NumericIndex;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    FieldCondition, FloatPayloadType, IntPayloadType, Match, MatchText, PayloadKeyType,
+    parse_uuid, AnyVariants, FieldCondition, FloatPayloadType, IntPayloadType, Match, MatchAny,
+    MatchPhrase, MatchPrefix, MatchText, MatchValue, PayloadKeyType, UuidIntType, ValueVariants,
 };
 
 pub trait PayloadFieldIndex {
@@ -119,9 +122,12 @@ pub enum FieldIndex {
     IntMapIndex(MapIndex<IntPayloadType>),
     KeywordIndex(MapIndex<SmolStr>),
     FloatIndex(NumericIndex<FloatPayloadType>),
+    DatetimeIndex(DatetimeIndex),
+    UuidIndex(MapIndex<UuidIntType>),
     GeoIndex(GeoMapIndex),
     FullTextIndex(FullTextIndex),
     BinaryIndex(BinaryIndex),
+    NullIndex(NullIndex),
 }
 
 impl FieldIndex {
@@ -142,10 +148,31 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(_) => None,
             FieldIndex::KeywordIndex(_) => None,
             FieldIndex::FloatIndex(_) => None,
+            FieldIndex::DatetimeIndex(_) => None,
+            FieldIndex::UuidIndex(uuid_index) => {
+                // Compare parsed UUIDs, so that matching does not depend on string formatting
+                let payload_uuids = ValueIndexer::get_values(uuid_index, payload_value);
+                match &condition.r#match {
+                    Some(Match::Value(MatchValue {
+                        value: ValueVariants::Keyword(keyword),
+                    })) => Some(
+                        parse_uuid(keyword).map_or(false, |uuid| payload_uuids.contains(&uuid)),
+                    ),
+                    Some(Match::Any(MatchAny {
+                        any: AnyVariants::Keywords(keywords),
+                    })) => Some(keywords.iter().any(|keyword| {
+                        parse_uuid(keyword).map_or(false, |uuid| payload_uuids.contains(&uuid))
+                    })),
+                    _ => None,
+                }
+            }
             FieldIndex::GeoIndex(_) => None,
             FieldIndex::BinaryIndex(_) => None,
+            FieldIndex::NullIndex(_) => None,
             FieldIndex::FullTextIndex(full_text_index) => match &condition.r#match {
-                Some(Match::Text(MatchText { text })) => {
+                Some(Match::Text(MatchText { text, analyzer }))
+                    if full_text_index.has_analyzer(analyzer.as_deref()) =>
+                {
                     let query = full_text_index.parse_query(text);
                     for value in full_text_index.get_values(payload_value) {
                         let document = full_text_index.parse_document(&value);
@@ -155,6 +182,28 @@ impl FieldIndex {
                     }
                     Some(false)
                 }
+                Some(Match::Phrase(MatchPhrase { phrase, analyzer }))
+                    if full_text_index.has_analyzer(analyzer.as_deref()) =>
+                {
+                    let query = full_text_index.parse_phrase_query(phrase);
+                    for value in full_text_index.get_values(payload_value) {
+                        let document = full_text_index.parse_document(&value);
+                        if query.check_match(&document) {
+                            return Some(true);
+                        }
+                    }
+                    Some(false)
+                }
+                Some(Match::Prefix(MatchPrefix { prefix }))
+                    if full_text_index.has_analyzer(None) =>
+                {
+                    Some(
+                        full_text_index
+                            .get_values(payload_value)
+                            .iter()
+                            .any(|value| full_text_index.check_prefix(value, prefix)),
+                    )
+                }
                 _ => None,
             },
         }
@@ -166,8 +215,11 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(payload_field_index) => payload_field_index,
             FieldIndex::KeywordIndex(payload_field_index) => payload_field_index,
             FieldIndex::FloatIndex(payload_field_index) => payload_field_index,
+            FieldIndex::DatetimeIndex(payload_field_index) => payload_field_index,
+            FieldIndex::UuidIndex(payload_field_index) => payload_field_index,
             FieldIndex::GeoIndex(payload_field_index) => payload_field_index,
             FieldIndex::BinaryIndex(payload_field_index) => payload_field_index,
+            FieldIndex::NullIndex(payload_field_index) => payload_field_index,
             FieldIndex::FullTextIndex(payload_field_index) => payload_field_index,
         }
     }
@@ -179,8 +231,11 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::KeywordIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::FloatIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::DatetimeIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::UuidIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::GeoIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::BinaryIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::NullIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::FullTextIndex(ref mut payload_field_index) => payload_field_index,
         }
     }
@@ -191,8 +246,11 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::KeywordIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::FloatIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::DatetimeIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::UuidIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::GeoIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::BinaryIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::NullIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::FullTextIndex(ref mut payload_field_index) => payload_field_index.load(),
         }
     }
@@ -203,8 +261,11 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(index) => index.clear(),
             FieldIndex::KeywordIndex(index) => index.clear(),
             FieldIndex::FloatIndex(index) => index.clear(),
+            FieldIndex::DatetimeIndex(index) => index.clear(),
+            FieldIndex::UuidIndex(index) => index.clear(),
             FieldIndex::GeoIndex(index) => index.clear(),
             FieldIndex::BinaryIndex(index) => index.clear(),
+            FieldIndex::NullIndex(index) => index.clear(),
             FieldIndex::FullTextIndex(index) => index.clear(),
         }
     }
@@ -215,8 +276,11 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(index) => index.recreate(),
             FieldIndex::KeywordIndex(index) => index.recreate(),
             FieldIndex::FloatIndex(index) => index.recreate(),
+            FieldIndex::DatetimeIndex(index) => index.recreate(),
+            FieldIndex::UuidIndex(index) => index.recreate(),
             FieldIndex::GeoIndex(index) => index.recreate(),
             FieldIndex::BinaryIndex(index) => index.recreate(),
+            FieldIndex::NullIndex(index) => index.recreate(),
             FieldIndex::FullTextIndex(index) => index.recreate(),
         }
     }
@@ -271,12 +335,21 @@ impl FieldIndex {
             FieldIndex::FloatIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
+            FieldIndex::DatetimeIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
+            FieldIndex::UuidIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
             FieldIndex::GeoIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
             FieldIndex::BinaryIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
+            FieldIndex::NullIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
             FieldIndex::FullTextIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
@@ -289,8 +362,11 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(index) => index.remove_point(point_id),
             FieldIndex::KeywordIndex(index) => index.remove_point(point_id),
             FieldIndex::FloatIndex(index) => index.remove_point(point_id),
+            FieldIndex::DatetimeIndex(index) => index.remove_point(point_id),
+            FieldIndex::UuidIndex(index) => index.remove_point(point_id),
             FieldIndex::GeoIndex(index) => index.remove_point(point_id),
             FieldIndex::BinaryIndex(index) => index.remove_point(point_id),
+            FieldIndex::NullIndex(index) => index.remove_point(point_id),
             FieldIndex::FullTextIndex(index) => index.remove_point(point_id),
         }
     }
@@ -301,8 +377,11 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(index) => index.get_telemetry_data(),
             FieldIndex::KeywordIndex(index) => index.get_telemetry_data(),
             FieldIndex::FloatIndex(index) => index.get_telemetry_data(),
+            FieldIndex::DatetimeIndex(index) => index.get_telemetry_data(),
+            FieldIndex::UuidIndex(index) => index.get_telemetry_data(),
             FieldIndex::GeoIndex(index) => index.get_telemetry_data(),
             FieldIndex::BinaryIndex(index) => index.get_telemetry_data(),
+            FieldIndex::NullIndex(index) => index.get_telemetry_data(),
             FieldIndex::FullTextIndex(index) => index.get_telemetry_data(),
         }
     }
@@ -313,8 +392,11 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(index) => index.values_count(point_id),
             FieldIndex::KeywordIndex(index) => index.values_count(point_id),
             FieldIndex::FloatIndex(index) => index.values_count(point_id),
+            FieldIndex::DatetimeIndex(index) => index.values_count(point_id),
+            FieldIndex::UuidIndex(index) => index.values_count(point_id),
             FieldIndex::GeoIndex(index) => index.values_count(point_id),
             FieldIndex::BinaryIndex(index) => index.values_count(point_id),
+            FieldIndex::NullIndex(index) => index.values_count(point_id),
             FieldIndex::FullTextIndex(index) => index.values_count(point_id),
         }
     }
@@ -325,8 +407,11 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(index) => index.values_is_empty(point_id),
             FieldIndex::KeywordIndex(index) => index.values_is_empty(point_id),
             FieldIndex::FloatIndex(index) => index.values_is_empty(point_id),
+            FieldIndex::DatetimeIndex(index) => index.values_is_empty(point_id),
+            FieldIndex::UuidIndex(index) => index.values_is_empty(point_id),
             FieldIndex::GeoIndex(index) => index.values_is_empty(point_id),
             FieldIndex::BinaryIndex(index) => index.values_is_empty(point_id),
+            FieldIndex::NullIndex(index) => index.values_is_empty(point_id),
             FieldIndex::FullTextIndex(index) => index.values_is_empty(point_id),
         }
     }
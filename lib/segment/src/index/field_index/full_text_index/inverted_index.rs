@@ -1,24 +1,48 @@
-use std::collections::{BTreeSet, HashMap};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::ops::Bound;
 
 use common::types::PointOffsetType;
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
-use super::posting_list::PostingList;
+use super::mmap_inverted_index::MmapInvertedIndex;
+use super::mutable_inverted_index::MutableInvertedIndex;
+use super::posting_list::PostingListView;
 use super::postings_iterator::intersect_postings_iterator;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition, PrimaryCondition};
+use crate::index::query_estimator::{combine_must_estimations, combine_should_estimations};
 use crate::types::{FieldCondition, Match, MatchText, PayloadKeyType};
 
 pub type TokenId = u32;
 
+/// Placeholder in token sequences, which never matches a phrase token.
+/// Separates different values of a document and stands for tokens missing in the vocabulary.
+pub const SEQUENCE_GAP: TokenId = TokenId::MAX;
+
 #[derive(Default, Serialize, Deserialize, Debug, Clone)]
 pub struct Document {
     tokens: Vec<TokenId>,
+    /// Tokens in the order of their appearance, used to match phrases.
+    /// `None` for documents stored before token sequences were kept.
+    #[serde(default)]
+    sequence: Option<Vec<TokenId>>,
 }
 
 impl Document {
     pub fn new(mut tokens: Vec<TokenId>) -> Self {
         tokens.sort_unstable();
-        Self { tokens }
+        Self {
+            tokens,
+            sequence: None,
+        }
+    }
+
+    pub fn with_sequence(tokens: Vec<TokenId>, sequence: Vec<TokenId>) -> Self {
+        Self {
+            sequence: Some(sequence),
+            ..Self::new(tokens)
+        }
     }
 
     pub fn len(&self) -> usize {
@@ -33,14 +57,38 @@ impl Document {
         &self.tokens
     }
 
+    pub fn sequence(&self) -> Option<&[TokenId]> {
+        self.sequence.as_deref()
+    }
+
     pub fn check(&self, token: TokenId) -> bool {
         self.tokens.binary_search(&token).is_ok()
     }
+
+    /// Check that tokens of the `phrase` appear in the document one after another
+    ///
+    /// Documents without a stored token sequence can't be checked and always pass.
+    pub fn check_phrase(&self, phrase: &[TokenId]) -> bool {
+        match &self.sequence {
+            Some(sequence) => {
+                phrase.is_empty()
+                    || sequence
+                        .windows(phrase.len())
+                        .any(|window| window == phrase)
+            }
+            None => true,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct ParsedQuery {
     pub tokens: Vec<Option<TokenId>>,
+    /// Query tokens, which have synonyms, together with the synonym tokens.
+    /// Document must contain at least one token of each group.
+    pub synonyms: Vec<Vec<TokenId>>,
+    /// If set, tokens must also follow one another in the document, in this order
+    pub phrase: Option<Vec<TokenId>>,
 }
 
 impl ParsedQuery {
@@ -53,85 +101,82 @@ impl ParsedQuery {
             .iter()
             // unwrap crash safety: all tokens exist in the vocabulary if it passes the above check
             .all(|query_token| document.check(query_token.unwrap()))
+            && self
+                .synonyms
+                .iter()
+                .all(|group| group.iter().any(|&token| document.check(token)))
+            && self
+                .phrase
+                .as_ref()
+                .map_or(true, |phrase| document.check_phrase(phrase))
     }
 }
 
-#[derive(Default)]
-pub struct InvertedIndex {
-    postings: Vec<Option<PostingList>>,
-    pub vocab: HashMap<String, TokenId>,
-    pub point_to_docs: Vec<Option<Document>>,
-    pub points_count: usize,
+/// Query, which matches documents with any token starting with the prefix
+#[derive(Debug)]
+pub struct ParsedPrefixQuery {
+    /// Sorted ids of the vocabulary tokens, which start with the prefix
+    pub tokens: Vec<TokenId>,
 }
 
-impl InvertedIndex {
-    pub fn new() -> InvertedIndex {
-        Default::default()
+impl ParsedPrefixQuery {
+    pub fn check_match(&self, document: &Document) -> bool {
+        document
+            .tokens()
+            .iter()
+            .any(|token| self.tokens.binary_search(token).is_ok())
     }
+}
 
-    pub fn document_from_tokens(&mut self, tokens: &BTreeSet<String>) -> Document {
-        let mut document_tokens = vec![];
-        for token in tokens {
-            // check if in vocab
-            let vocab_idx = match self.vocab.get(token) {
-                Some(&idx) => idx,
-                None => {
-                    let next_token_id = self.vocab.len() as TokenId;
-                    self.vocab.insert(token.to_string(), next_token_id);
-                    next_token_id
-                }
-            };
-            document_tokens.push(vocab_idx);
-        }
+pub enum InvertedIndex {
+    Mutable(MutableInvertedIndex),
+    Mmap(MmapInvertedIndex),
+}
 
-        Document::new(document_tokens)
+impl InvertedIndex {
+    /// Sorted to allow lookup of the tokens by prefix
+    pub fn vocab(&self) -> &BTreeMap<String, TokenId> {
+        match self {
+            InvertedIndex::Mutable(index) => &index.vocab,
+            InvertedIndex::Mmap(index) => &index.vocab,
+        }
     }
 
-    pub fn index_document(&mut self, idx: PointOffsetType, document: Document) {
-        self.points_count += 1;
-        if self.point_to_docs.len() <= idx as usize {
-            self.point_to_docs
-                .resize_with(idx as usize + 1, Default::default);
+    pub fn points_count(&self) -> usize {
+        match self {
+            InvertedIndex::Mutable(index) => index.points_count,
+            InvertedIndex::Mmap(index) => index.points_count,
         }
+    }
 
-        for token_idx in document.tokens() {
-            let token_idx_usize = *token_idx as usize;
-            if self.postings.len() <= token_idx_usize {
-                self.postings
-                    .resize_with(token_idx_usize + 1, Default::default);
-            }
-            let posting = self
-                .postings
-                .get_mut(token_idx_usize)
-                .expect("posting must exist even if with None");
-            match posting {
-                None => *posting = Some(PostingList::new(idx)),
-                Some(vec) => vec.insert(idx),
-            }
+    fn posting(&self, token_id: TokenId) -> Option<PostingListView<'_>> {
+        match self {
+            InvertedIndex::Mutable(index) => index.posting(token_id),
+            InvertedIndex::Mmap(index) => index.posting(token_id),
         }
-        self.point_to_docs[idx as usize] = Some(document);
     }
 
-    pub fn remove_document(&mut self, idx: PointOffsetType) -> Option<Document> {
-        if self.point_to_docs.len() <= idx as usize {
-            return None; // Already removed or never actually existed
+    pub fn get_doc(&self, idx: PointOffsetType) -> Option<Cow<'_, Document>> {
+        match self {
+            InvertedIndex::Mutable(index) => index.get_doc(idx).map(Cow::Borrowed),
+            InvertedIndex::Mmap(index) => index.get_doc(idx).map(Cow::Owned),
         }
+    }
 
-        let removed_doc = match std::mem::take(&mut self.point_to_docs[idx as usize]) {
-            Some(doc) => doc,
-            None => return None,
-        };
-
-        self.points_count -= 1;
+    /// Number of tokens in the document of the point
+    pub fn values_count(&self, idx: PointOffsetType) -> usize {
+        match self {
+            InvertedIndex::Mutable(index) => index.get_doc(idx).map_or(0, Document::len),
+            InvertedIndex::Mmap(index) => index.values_count(idx),
+        }
+    }
 
-        for removed_token in removed_doc.tokens() {
-            // unwrap safety: posting list exists and contains the document id
-            let posting = self.postings.get_mut(*removed_token as usize).unwrap();
-            if let Some(vec) = posting {
-                vec.remove(idx);
-            }
+    /// Remove document of the point, returns `false` if there was no document
+    pub fn remove_document(&mut self, idx: PointOffsetType) -> bool {
+        match self {
+            InvertedIndex::Mutable(index) => index.remove_document(idx).is_some(),
+            InvertedIndex::Mmap(index) => index.remove_document(idx),
         }
-        Some(removed_doc)
     }
 
     pub fn filter(&self, query: &ParsedQuery) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
@@ -142,7 +187,7 @@ impl InvertedIndex {
                 None => None,
                 // if a ParsedQuery token was given an index, then it must exist in the vocabulary
                 // dictionary. Posting list entry can be None but it exists.
-                Some(idx) => self.postings.get(idx as usize).unwrap().as_ref(),
+                Some(idx) => self.posting(idx),
             })
             .collect();
         if postings_opt.is_none() {
@@ -150,11 +195,113 @@ impl InvertedIndex {
             return Box::new(vec![].into_iter());
         }
         let postings = postings_opt.unwrap();
-        if postings.is_empty() {
+        let candidates = match (postings.is_empty(), query.synonyms.split_first()) {
             // Empty request -> no matches
-            return Box::new(vec![].into_iter());
+            (true, None) => return Box::new(vec![].into_iter()),
+            (true, Some((first_group, other_groups))) => {
+                let candidates = self.union_postings_iterator(first_group);
+                self.filter_synonyms(candidates, other_groups.to_vec())
+            }
+            (false, _) => {
+                let candidates = intersect_postings_iterator(postings);
+                self.filter_synonyms(candidates, query.synonyms.clone())
+            }
+        };
+        match query.phrase.clone() {
+            None => candidates,
+            Some(phrase) => Box::new(candidates.filter(move |&idx| {
+                self.get_doc(idx)
+                    .map_or(false, |document| document.check_phrase(&phrase))
+            })),
+        }
+    }
+
+    /// Points, which contain any of the `tokens`
+    fn union_postings_iterator(
+        &self,
+        tokens: &[TokenId],
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let postings: Vec<_> = tokens
+            .iter()
+            .filter_map(|&token_id| self.posting(token_id))
+            .collect();
+        Box::new(
+            postings
+                .into_iter()
+                .flat_map(|posting| posting.iter())
+                .unique(),
+        )
+    }
+
+    /// Keep points, which contain at least one token of each synonym group
+    fn filter_synonyms<'a>(
+        &'a self,
+        candidates: Box<dyn Iterator<Item = PointOffsetType> + 'a>,
+        synonyms: Vec<Vec<TokenId>>,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + 'a> {
+        if synonyms.is_empty() {
+            return candidates;
         }
-        intersect_postings_iterator(postings)
+        Box::new(candidates.filter(move |&idx| {
+            synonyms.iter().all(|group| {
+                group.iter().any(|&token_id| {
+                    self.posting(token_id)
+                        .map_or(false, |posting| posting.contains(&idx))
+                })
+            })
+        }))
+    }
+
+    /// Find all tokens of the vocabulary, which start with the given prefix
+    pub fn parse_prefix(&self, prefix: &str) -> ParsedPrefixQuery {
+        let mut tokens: Vec<TokenId> = self
+            .vocab()
+            .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            .take_while(|(token, _)| token.starts_with(prefix))
+            .map(|(_, &token_id)| token_id)
+            .collect();
+        tokens.sort_unstable();
+        ParsedPrefixQuery { tokens }
+    }
+
+    fn prefix_postings<'a>(
+        &'a self,
+        query: &'a ParsedPrefixQuery,
+    ) -> impl Iterator<Item = PostingListView<'a>> + 'a {
+        query
+            .tokens
+            .iter()
+            .filter_map(|&token_id| self.posting(token_id))
+    }
+
+    pub fn filter_prefix(
+        &self,
+        query: &ParsedPrefixQuery,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let postings: Vec<_> = self.prefix_postings(query).collect();
+        Box::new(
+            postings
+                .into_iter()
+                .flat_map(|posting| posting.iter())
+                .unique(),
+        )
+    }
+
+    pub fn estimate_prefix_cardinality(
+        &self,
+        query: &ParsedPrefixQuery,
+        condition: &FieldCondition,
+    ) -> CardinalityEstimation {
+        let estimations: Vec<_> = self
+            .prefix_postings(query)
+            .map(|posting| CardinalityEstimation::exact(posting.len()))
+            .collect();
+        let estimation = if estimations.is_empty() {
+            CardinalityEstimation::exact(0)
+        } else {
+            combine_should_estimations(&estimations, self.points_count())
+        };
+        estimation.with_primary_clause(PrimaryCondition::Condition(condition.clone()))
     }
 
     pub fn estimate_cardinality(
@@ -168,7 +315,7 @@ impl InvertedIndex {
             .map(|&vocab_idx| match vocab_idx {
                 None => None,
                 // unwrap safety: same as in filter()
-                Some(idx) => self.postings.get(idx as usize).unwrap().as_ref(),
+                Some(idx) => self.posting(idx),
             })
             .collect();
         if postings_opt.is_none() {
@@ -181,7 +328,7 @@ impl InvertedIndex {
             };
         }
         let postings = postings_opt.unwrap();
-        if postings.is_empty() {
+        if postings.is_empty() && query.synonyms.is_empty() {
             // Empty request -> no matches
             return CardinalityEstimation {
                 primary_clauses: vec![PrimaryCondition::Condition(condition.clone())],
@@ -190,10 +337,54 @@ impl InvertedIndex {
                 max: 0,
             };
         }
+        if query.synonyms.is_empty() {
+            return self.estimate_postings_cardinality(&postings, query, condition);
+        }
+
+        // Each synonym group matches points, which contain any of its tokens
+        let mut estimations: Vec<_> = query
+            .synonyms
+            .iter()
+            .map(|group| {
+                let group_estimations: Vec<_> = group
+                    .iter()
+                    .filter_map(|&token_id| self.posting(token_id))
+                    .map(|posting| CardinalityEstimation::exact(posting.len()))
+                    .collect();
+                if group_estimations.is_empty() {
+                    CardinalityEstimation::exact(0)
+                } else {
+                    combine_should_estimations(&group_estimations, self.points_count())
+                }
+            })
+            .collect();
+        if !postings.is_empty() {
+            estimations.push(self.estimate_postings_cardinality(&postings, query, condition));
+        }
+        let estimation = combine_must_estimations(&estimations, self.points_count());
+        CardinalityEstimation {
+            primary_clauses: vec![PrimaryCondition::Condition(condition.clone())],
+            ..estimation
+        }
+    }
+
+    /// Estimate number of points, which contain all tokens of the `postings`
+    fn estimate_postings_cardinality(
+        &self,
+        postings: &[PostingListView],
+        query: &ParsedQuery,
+        condition: &FieldCondition,
+    ) -> CardinalityEstimation {
         // Smallest posting is the largest possible cardinality
         let smallest_posting = postings.iter().map(|posting| posting.len()).min().unwrap();
 
-        return if postings.len() == 1 {
+        // Tokens of a phrase may be present, but not next to each other
+        let is_phrase = query
+            .phrase
+            .as_ref()
+            .map_or(false, |phrase| phrase.len() > 1);
+
+        return if postings.len() == 1 && !is_phrase {
             CardinalityEstimation {
                 primary_clauses: vec![PrimaryCondition::Condition(condition.clone())],
                 min: smallest_posting,
@@ -203,9 +394,9 @@ impl InvertedIndex {
         } else {
             let expected_frac: f64 = postings
                 .iter()
-                .map(|posting| posting.len() as f64 / self.points_count as f64)
+                .map(|posting| posting.len() as f64 / self.points_count() as f64)
                 .product();
-            let exp = (expected_frac * self.points_count as f64) as usize;
+            let exp = (expected_frac * self.points_count() as f64) as usize;
             CardinalityEstimation {
                 primary_clauses: vec![PrimaryCondition::Condition(condition.clone())],
                 min: 0, // ToDo: make better estimation
@@ -223,25 +414,16 @@ impl InvertedIndex {
         // It might be very hard to predict possible combinations of conditions,
         // so we only build it for individual tokens
         Box::new(
-            self.vocab
+            self.vocab()
                 .iter()
-                .filter(|(_token, &posting_idx)| self.postings[posting_idx as usize].is_some())
-                .filter(move |(_token, &posting_idx)| {
-                    // unwrap crash safety: all tokens that passes the first filter should have postings
-                    self.postings[posting_idx as usize].as_ref().unwrap().len() >= threshold
-                })
-                .map(|(token, &posting_idx)| {
-                    (
-                        token,
-                        // same as the above case
-                        self.postings[posting_idx as usize].as_ref().unwrap(),
-                    )
-                })
+                .filter_map(|(token, &posting_idx)| Some((token, self.posting(posting_idx)?)))
+                .filter(move |(_token, posting)| posting.len() >= threshold)
                 .map(move |(token, posting)| PayloadBlockCondition {
                     condition: FieldCondition {
                         key: key.clone(),
                         r#match: Some(Match::Text(MatchText {
                             text: token.clone(),
+                            analyzer: None,
                         })),
                         range: None,
                         geo_bounding_box: None,
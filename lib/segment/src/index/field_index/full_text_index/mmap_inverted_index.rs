@@ -0,0 +1,224 @@
+use std::collections::BTreeMap;
+use std::fs::{create_dir_all, remove_dir_all};
+use std::iter;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use bitvec::vec::BitVec;
+use common::types::PointOffsetType;
+
+use super::inverted_index::{Document, TokenId};
+use super::mutable_inverted_index::MutableInvertedIndex;
+use super::posting_list::{PostingList, PostingListView};
+use crate::common::operation_error::OperationResult;
+use crate::index::field_index::mmap_array::MmapArray;
+
+const POSTING_OFFSETS_FILE: &str = "posting_offsets.bin";
+const POSTINGS_FILE: &str = "postings.bin";
+const DOCUMENT_OFFSETS_FILE: &str = "document_offsets.bin";
+const DOCUMENT_TOKENS_FILE: &str = "document_tokens.bin";
+const SEQUENCE_OFFSETS_FILE: &str = "sequence_offsets.bin";
+const SEQUENCES_FILE: &str = "sequences.bin";
+
+/// Memory mapped postings and documents
+struct MmapInvertedIndexStorage {
+    /// Offsets of the points of each token in `postings`, with one extra offset at the end
+    posting_offsets: MmapArray<u64>,
+    postings: MmapArray<PointOffsetType>,
+    /// Offsets of the tokens of each point in `document_tokens`, with one extra offset at the end
+    document_offsets: MmapArray<u64>,
+    /// Sorted tokens of each point
+    document_tokens: MmapArray<TokenId>,
+    /// Offsets of the token sequence of each point in `sequences`, with one extra offset at the end
+    sequence_offsets: MmapArray<u64>,
+    sequences: MmapArray<TokenId>,
+}
+
+/// Immutable inverted index, which keeps postings and documents in memory mapped files.
+///
+/// Only the vocabulary and a few counters per token and per point are kept in RAM.
+/// RocksDB remains the source of truth: the files are rebuilt from it on each load.
+pub struct MmapInvertedIndex {
+    path: PathBuf,
+    /// Sorted to allow lookup of the tokens by prefix
+    pub vocab: BTreeMap<String, TokenId>,
+    storage: Option<MmapInvertedIndexStorage>,
+    /// Number of not removed points of each token
+    posting_lens: Vec<u32>,
+    /// Points, which have a not removed document
+    present: BitVec,
+    /// Points, which have a stored token sequence
+    has_sequence: BitVec,
+    pub points_count: usize,
+}
+
+impl MmapInvertedIndex {
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_owned(),
+            vocab: Default::default(),
+            storage: None,
+            posting_lens: vec![],
+            present: Default::default(),
+            has_sequence: Default::default(),
+            points_count: 0,
+        }
+    }
+
+    /// Write postings and documents of the `index` into memory mapped files,
+    /// only its vocabulary is kept in RAM
+    pub fn load_from(&mut self, index: MutableInvertedIndex) -> OperationResult<()> {
+        let MutableInvertedIndex {
+            postings,
+            vocab,
+            point_to_docs,
+            points_count,
+        } = index;
+
+        create_dir_all(&self.path)?;
+        let storage = MmapInvertedIndexStorage {
+            posting_offsets: MmapArray::create(
+                &self.path.join(POSTING_OFFSETS_FILE),
+                offsets(
+                    postings
+                        .iter()
+                        .map(|posting| posting.as_ref().map_or(0, PostingList::len)),
+                ),
+            )?,
+            postings: MmapArray::create(
+                &self.path.join(POSTINGS_FILE),
+                postings.iter().flatten().flat_map(PostingList::iter),
+            )?,
+            document_offsets: MmapArray::create(
+                &self.path.join(DOCUMENT_OFFSETS_FILE),
+                offsets(
+                    point_to_docs
+                        .iter()
+                        .map(|document| document.as_ref().map_or(0, Document::len)),
+                ),
+            )?,
+            document_tokens: MmapArray::create(
+                &self.path.join(DOCUMENT_TOKENS_FILE),
+                point_to_docs
+                    .iter()
+                    .flatten()
+                    .flat_map(|document| document.tokens().iter().copied()),
+            )?,
+            sequence_offsets: MmapArray::create(
+                &self.path.join(SEQUENCE_OFFSETS_FILE),
+                offsets(point_to_docs.iter().map(|document| {
+                    document
+                        .as_ref()
+                        .and_then(Document::sequence)
+                        .map_or(0, <[TokenId]>::len)
+                })),
+            )?,
+            sequences: MmapArray::create(
+                &self.path.join(SEQUENCES_FILE),
+                point_to_docs
+                    .iter()
+                    .flatten()
+                    .filter_map(Document::sequence)
+                    .flat_map(|sequence| sequence.iter().copied()),
+            )?,
+        };
+
+        self.posting_lens = postings
+            .iter()
+            .map(|posting| posting.as_ref().map_or(0, |posting| posting.len() as u32))
+            .collect();
+        self.present = point_to_docs.iter().map(Option::is_some).collect();
+        self.has_sequence = point_to_docs
+            .iter()
+            .map(|document| document.as_ref().and_then(Document::sequence).is_some())
+            .collect();
+        self.vocab = vocab;
+        self.points_count = points_count;
+        self.storage = Some(storage);
+        Ok(())
+    }
+
+    /// Remove memory mapped files of the index
+    pub fn remove_files(&mut self) -> OperationResult<()> {
+        self.storage = None;
+        if self.path.exists() {
+            remove_dir_all(&self.path)?;
+        }
+        Ok(())
+    }
+
+    fn is_present(&self, idx: PointOffsetType) -> bool {
+        self.present
+            .get(idx as usize)
+            .map_or(false, |present| *present)
+    }
+
+    pub fn posting(&self, token_id: TokenId) -> Option<PostingListView<'_>> {
+        let storage = self.storage.as_ref()?;
+        let range = offsets_range(storage.posting_offsets.as_slice(), token_id as usize)?;
+        Some(PostingListView::new(
+            &storage.postings.as_slice()[range],
+            Some(self.present.as_bitslice()),
+            self.posting_lens[token_id as usize] as usize,
+        ))
+    }
+
+    fn document_tokens(&self, idx: PointOffsetType) -> Option<&[TokenId]> {
+        if !self.is_present(idx) {
+            return None;
+        }
+        let storage = self.storage.as_ref()?;
+        let range = offsets_range(storage.document_offsets.as_slice(), idx as usize)?;
+        Some(&storage.document_tokens.as_slice()[range])
+    }
+
+    pub fn get_doc(&self, idx: PointOffsetType) -> Option<Document> {
+        let tokens = self.document_tokens(idx)?.to_vec();
+        if !self.has_sequence[idx as usize] {
+            return Some(Document::new(tokens));
+        }
+        let storage = self.storage.as_ref()?;
+        let range = offsets_range(storage.sequence_offsets.as_slice(), idx as usize)?;
+        let sequence = storage.sequences.as_slice()[range].to_vec();
+        Some(Document::with_sequence(tokens, sequence))
+    }
+
+    pub fn values_count(&self, idx: PointOffsetType) -> usize {
+        self.document_tokens(idx).map_or(0, <[TokenId]>::len)
+    }
+
+    /// Mark document of the point as removed, files of the index are not changed
+    pub fn remove_document(&mut self, idx: PointOffsetType) -> bool {
+        let Some(storage) = &self.storage else {
+            return false;
+        };
+        if !self.is_present(idx) {
+            return false;
+        }
+        let Some(range) = offsets_range(storage.document_offsets.as_slice(), idx as usize) else {
+            return false;
+        };
+        for &token_id in &storage.document_tokens.as_slice()[range] {
+            self.posting_lens[token_id as usize] -= 1;
+        }
+        self.present.set(idx as usize, false);
+        self.points_count -= 1;
+        true
+    }
+}
+
+/// Offsets of consecutive lists, concatenated in a single array, starting with 0
+fn offsets(lengths: impl Iterator<Item = usize>) -> impl Iterator<Item = u64> {
+    iter::once(0).chain(lengths.scan(0u64, |offset, len| {
+        *offset += len as u64;
+        Some(*offset)
+    }))
+}
+
+/// Range of the list with the given index in the concatenated array
+fn offsets_range(offsets: &[u64], idx: usize) -> Option<Range<usize>> {
+    if idx + 1 >= offsets.len() {
+        return None;
+    }
+    Some(offsets[idx] as usize..offsets[idx + 1] as usize)
+}
@@ -1,4 +1,6 @@
 mod inverted_index;
+mod mmap_inverted_index;
+mod mutable_inverted_index;
 mod posting_list;
 mod postings_iterator;
 pub mod text_index;
@@ -0,0 +1,114 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use common::types::PointOffsetType;
+
+use super::inverted_index::{Document, TokenId, SEQUENCE_GAP};
+use super::posting_list::{PostingList, PostingListView};
+
+/// Inverted index, which keeps postings and documents in RAM
+#[derive(Default)]
+pub struct MutableInvertedIndex {
+    pub(super) postings: Vec<Option<PostingList>>,
+    /// Sorted to allow lookup of the tokens by prefix
+    pub vocab: BTreeMap<String, TokenId>,
+    pub point_to_docs: Vec<Option<Document>>,
+    pub points_count: usize,
+}
+
+impl MutableInvertedIndex {
+    pub fn new() -> MutableInvertedIndex {
+        Default::default()
+    }
+
+    /// Build a document, adding its tokens to the vocabulary
+    ///
+    /// `sequence` lists tokens in the order of appearance, empty strings are gaps between values.
+    pub fn document_from_tokens(
+        &mut self,
+        tokens: &BTreeSet<String>,
+        sequence: Option<&[String]>,
+    ) -> Document {
+        let mut document_tokens = vec![];
+        for token in tokens {
+            // check if in vocab
+            let vocab_idx = match self.vocab.get(token) {
+                Some(&idx) => idx,
+                None => {
+                    let next_token_id = self.vocab.len() as TokenId;
+                    self.vocab.insert(token.to_string(), next_token_id);
+                    next_token_id
+                }
+            };
+            document_tokens.push(vocab_idx);
+        }
+
+        match sequence {
+            Some(sequence) => {
+                let sequence = sequence
+                    .iter()
+                    .map(|token| self.vocab.get(token).copied().unwrap_or(SEQUENCE_GAP))
+                    .collect();
+                Document::with_sequence(document_tokens, sequence)
+            }
+            None => Document::new(document_tokens),
+        }
+    }
+
+    pub fn index_document(&mut self, idx: PointOffsetType, document: Document) {
+        self.points_count += 1;
+        if self.point_to_docs.len() <= idx as usize {
+            self.point_to_docs
+                .resize_with(idx as usize + 1, Default::default);
+        }
+
+        for token_idx in document.tokens() {
+            let token_idx_usize = *token_idx as usize;
+            if self.postings.len() <= token_idx_usize {
+                self.postings
+                    .resize_with(token_idx_usize + 1, Default::default);
+            }
+            let posting = self
+                .postings
+                .get_mut(token_idx_usize)
+                .expect("posting must exist even if with None");
+            match posting {
+                None => *posting = Some(PostingList::new(idx)),
+                Some(vec) => vec.insert(idx),
+            }
+        }
+        self.point_to_docs[idx as usize] = Some(document);
+    }
+
+    pub fn remove_document(&mut self, idx: PointOffsetType) -> Option<Document> {
+        if self.point_to_docs.len() <= idx as usize {
+            return None; // Already removed or never actually existed
+        }
+
+        let removed_doc = match std::mem::take(&mut self.point_to_docs[idx as usize]) {
+            Some(doc) => doc,
+            None => return None,
+        };
+
+        self.points_count -= 1;
+
+        for removed_token in removed_doc.tokens() {
+            // unwrap safety: posting list exists and contains the document id
+            let posting = self.postings.get_mut(*removed_token as usize).unwrap();
+            if let Some(vec) = posting {
+                vec.remove(idx);
+            }
+        }
+        Some(removed_doc)
+    }
+
+    pub fn posting(&self, token_id: TokenId) -> Option<PostingListView<'_>> {
+        self.postings
+            .get(token_id as usize)?
+            .as_ref()
+            .map(PostingList::view)
+    }
+
+    pub fn get_doc(&self, idx: PointOffsetType) -> Option<&Document> {
+        self.point_to_docs.get(idx as usize)?.as_ref()
+    }
+}
@@ -1,3 +1,4 @@
+use bitvec::slice::BitSlice;
 use common::types::PointOffsetType;
 
 #[derive(Clone, Debug, Default)]
@@ -38,6 +39,49 @@ impl PostingList {
     pub fn iter(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
         self.list.iter().copied()
     }
+
+    pub fn view(&self) -> PostingListView<'_> {
+        PostingListView::new(&self.list, None, self.list.len())
+    }
+}
+
+/// Read-only view of a posting list, stored in memory or in a memory mapped file
+#[derive(Clone, Copy, Debug)]
+pub struct PostingListView<'a> {
+    /// Sorted ids of the points
+    list: &'a [PointOffsetType],
+    /// If set, only points with the set bit are considered present in the list
+    present: Option<&'a BitSlice>,
+    len: usize,
+}
+
+impl<'a> PostingListView<'a> {
+    /// `len` is the number of present points of the `list`
+    pub fn new(list: &'a [PointOffsetType], present: Option<&'a BitSlice>, len: usize) -> Self {
+        Self { list, present, len }
+    }
+
+    fn is_present(&self, idx: PointOffsetType) -> bool {
+        self.present.map_or(true, |present| {
+            present.get(idx as usize).map_or(false, |bit| *bit)
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn contains(&self, val: &PointOffsetType) -> bool {
+        self.list.binary_search(val).is_ok() && self.is_present(*val)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = PointOffsetType> + 'a {
+        let view = *self;
+        self.list
+            .iter()
+            .copied()
+            .filter(move |&idx| view.is_present(idx))
+    }
 }
 
 impl IntoIterator for PostingList {
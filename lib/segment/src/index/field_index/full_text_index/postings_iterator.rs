@@ -1,9 +1,9 @@
 use common::types::PointOffsetType;
 
-use super::posting_list::PostingList;
+use super::posting_list::PostingListView;
 
 pub fn intersect_postings_iterator<'a>(
-    mut postings: Vec<&'a PostingList>,
+    mut postings: Vec<PostingListView<'a>>,
 ) -> Box<dyn Iterator<Item = PointOffsetType> + 'a> {
     let smallest_posting_idx = postings
         .iter()
@@ -23,6 +23,7 @@ pub fn intersect_postings_iterator<'a>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::index::field_index::full_text_index::posting_list::PostingList;
 
     #[test]
     fn test_postings_iterator() {
@@ -44,7 +45,7 @@ mod tests {
         p3.insert(6);
         p3.insert(7);
 
-        let postings = vec![&p1, &p2, &p3];
+        let postings = vec![p1.view(), p2.view(), p3.view()];
         let merged = intersect_postings_iterator(postings);
 
         let res = merged.collect::<Vec<_>>();
@@ -158,6 +158,11 @@ fn test_prefix_search() {
         min_token_len: None,
         max_token_len: None,
         lowercase: None,
+        stemmer: None,
+        stopwords: None,
+        synonyms: None,
+        analyzers: None,
+        on_disk: None,
     };
 
     let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
@@ -178,7 +183,7 @@ fn test_prefix_search() {
 
     for idx in res.iter() {
         let doc = index.get_doc(*idx).unwrap();
-        assert!(query.check_match(doc));
+        assert!(query.check_match(&doc));
     }
 
     assert_eq!(res.len(), 3);
@@ -189,7 +194,7 @@ fn test_prefix_search() {
 
     for idx in [1, 2, 3] {
         let doc = index.get_doc(idx).unwrap();
-        assert!(!query.check_match(doc));
+        assert!(!query.check_match(&doc));
     }
 
     assert_eq!(res.len(), 0);
@@ -1,4 +1,6 @@
+use std::borrow::Cow;
 use std::collections::{BTreeSet, HashSet};
+use std::path::Path;
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
@@ -12,8 +14,10 @@ use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::common::Flusher;
 use crate::data_types::text_index::TextIndexParams;
 use crate::index::field_index::full_text_index::inverted_index::{
-    Document, InvertedIndex, ParsedQuery,
+    Document, InvertedIndex, ParsedPrefixQuery, ParsedQuery, TokenId, SEQUENCE_GAP,
 };
+use crate::index::field_index::full_text_index::mmap_inverted_index::MmapInvertedIndex;
+use crate::index::field_index::full_text_index::mutable_inverted_index::MutableInvertedIndex;
 use crate::index::field_index::full_text_index::tokenizers::Tokenizer;
 use crate::index::field_index::{
     CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex, ValueIndexer,
@@ -25,6 +29,8 @@ pub struct FullTextIndex {
     inverted_index: InvertedIndex,
     db_wrapper: DatabaseColumnWrapper,
     config: TextIndexParams,
+    /// Name of the analyzer, which configures this index. `None` for the main index of the field.
+    analyzer: Option<String>,
 }
 
 impl FullTextIndex {
@@ -36,55 +42,100 @@ impl FullTextIndex {
         bincode::deserialize(data).unwrap()
     }
 
-    fn serialize_document_tokens(&self, tokens: BTreeSet<String>) -> OperationResult<Vec<u8>> {
+    fn serialize_document_tokens(
+        &self,
+        tokens: BTreeSet<String>,
+        sequence: Vec<String>,
+    ) -> OperationResult<Vec<u8>> {
         #[derive(Serialize)]
         struct StoredDocument {
             tokens: BTreeSet<String>,
+            sequence: Vec<String>,
         }
-        let doc = StoredDocument { tokens };
+        let doc = StoredDocument { tokens, sequence };
         serde_cbor::to_vec(&doc).map_err(|e| {
             OperationError::service_error(format!("Failed to serialize document: {e}"))
         })
     }
 
-    fn deserialize_document(data: &[u8], index: &mut InvertedIndex) -> OperationResult<Document> {
+    fn deserialize_document(
+        data: &[u8],
+        index: &mut MutableInvertedIndex,
+    ) -> OperationResult<Document> {
         #[derive(Deserialize)]
         struct StoredDocument {
             tokens: BTreeSet<String>,
+            // Not present in documents stored by older versions
+            #[serde(default)]
+            sequence: Option<Vec<String>>,
         }
         serde_cbor::from_slice::<StoredDocument>(data)
             .map_err(|e| {
                 OperationError::service_error(format!("Failed to deserialize document: {e}"))
             })
-            .map(|doc| index.document_from_tokens(&doc.tokens))
+            .map(|doc| index.document_from_tokens(&doc.tokens, doc.sequence.as_deref()))
     }
 
-    fn storage_cf_name(field: &str) -> String {
-        format!("{field}_fts")
+    fn storage_cf_name(field: &str, analyzer: Option<&str>) -> String {
+        match analyzer {
+            None => format!("{field}_fts"),
+            Some(analyzer) => format!("{field}_fts.{analyzer}"),
+        }
     }
 
     pub fn new(db: Arc<RwLock<DB>>, config: TextIndexParams, field: &str) -> Self {
-        let store_cf_name = Self::storage_cf_name(field);
+        Self::new_with_analyzer(db, config, field, None)
+    }
+
+    /// Additional index of the field, which is used by conditions with the name of the analyzer
+    pub fn new_with_analyzer(
+        db: Arc<RwLock<DB>>,
+        config: TextIndexParams,
+        field: &str,
+        analyzer: Option<String>,
+    ) -> Self {
+        let store_cf_name = Self::storage_cf_name(field, analyzer.as_deref());
         let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
         FullTextIndex {
-            inverted_index: InvertedIndex::new(),
+            inverted_index: InvertedIndex::Mutable(MutableInvertedIndex::new()),
             db_wrapper,
             config,
+            analyzer,
         }
     }
 
-    pub fn get_doc(&self, idx: PointOffsetType) -> Option<&Document> {
-        match self.inverted_index.point_to_docs.get(idx as usize) {
-            Some(Some(doc)) => Some(doc),
-            _ => None,
+    /// Immutable index, which keeps postings in memory mapped files at `path`
+    pub fn new_mmap(
+        db: Arc<RwLock<DB>>,
+        config: TextIndexParams,
+        field: &str,
+        analyzer: Option<String>,
+        path: &Path,
+    ) -> Self {
+        let store_cf_name = Self::storage_cf_name(field, analyzer.as_deref());
+        let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
+        FullTextIndex {
+            inverted_index: InvertedIndex::Mmap(MmapInvertedIndex::new(path)),
+            db_wrapper,
+            config,
+            analyzer,
         }
     }
 
+    /// Whether the index serves conditions, which select the given analyzer
+    pub fn has_analyzer(&self, analyzer: Option<&str>) -> bool {
+        self.analyzer.as_deref() == analyzer
+    }
+
+    pub fn get_doc(&self, idx: PointOffsetType) -> Option<Cow<'_, Document>> {
+        self.inverted_index.get_doc(idx)
+    }
+
     pub fn get_telemetry_data(&self) -> PayloadIndexTelemetry {
         PayloadIndexTelemetry {
             field_name: None,
-            points_values_count: self.inverted_index.points_count,
-            points_count: self.inverted_index.points_count,
+            points_values_count: self.inverted_index.points_count(),
+            points_count: self.inverted_index.points_count(),
             histogram_bucket_size: None,
         }
     }
@@ -95,22 +146,114 @@ impl FullTextIndex {
 
     pub fn parse_query(&self, text: &str) -> ParsedQuery {
         let mut tokens = HashSet::new();
-        Tokenizer::tokenize_query(text, &self.config, |token| {
-            tokens.insert(self.inverted_index.vocab.get(token).copied());
+        let mut synonyms = HashSet::new();
+        Tokenizer::tokenize_query_with_synonyms(text, &self.config, |token, token_synonyms| {
+            let token_id = self.inverted_index.vocab().get(token).copied();
+            if token_synonyms.is_empty() {
+                tokens.insert(token_id);
+                return;
+            }
+            // Token, which is missing in the vocabulary, may still match by its synonyms
+            let mut group: Vec<TokenId> = token_id
+                .into_iter()
+                .chain(
+                    token_synonyms
+                        .iter()
+                        .filter_map(|synonym| self.inverted_index.vocab().get(synonym).copied()),
+                )
+                .collect();
+            group.sort_unstable();
+            group.dedup();
+            synonyms.insert(group);
         });
         ParsedQuery {
             tokens: tokens.into_iter().collect(),
+            synonyms: synonyms.into_iter().collect(),
+            phrase: None,
         }
     }
 
+    /// Parse query, which also requires tokens to follow one another in the given order
+    pub fn parse_phrase_query(&self, phrase: &str) -> ParsedQuery {
+        let mut tokens = HashSet::new();
+        let mut phrase_tokens = vec![];
+        Tokenizer::tokenize_query(phrase, &self.config, |token| {
+            let token_id = self.inverted_index.vocab().get(token).copied();
+            tokens.insert(token_id);
+            phrase_tokens.push(token_id.unwrap_or(SEQUENCE_GAP));
+        });
+        ParsedQuery {
+            tokens: tokens.into_iter().collect(),
+            synonyms: vec![],
+            phrase: Some(phrase_tokens),
+        }
+    }
+
+    /// Parse query, which matches documents with any token starting with the `prefix`
+    ///
+    /// Prefix is not tokenized or stemmed, only lowercased if configured.
+    pub fn parse_prefix_query(&self, prefix: &str) -> ParsedPrefixQuery {
+        self.inverted_index
+            .parse_prefix(&self.normalize_prefix(prefix))
+    }
+
+    fn normalize_prefix<'a>(&self, prefix: &'a str) -> Cow<'a, str> {
+        if self.config.lowercase.unwrap_or(true) {
+            Cow::Owned(prefix.to_lowercase())
+        } else {
+            Cow::Borrowed(prefix)
+        }
+    }
+
+    /// Check if any token of the `text` starts with the `prefix`
+    pub fn check_prefix(&self, text: &str, prefix: &str) -> bool {
+        let prefix = self.normalize_prefix(prefix);
+        let mut found = false;
+        Tokenizer::tokenize_doc(text, &self.config, |token| {
+            found |= token.starts_with(prefix.as_ref());
+        });
+        found
+    }
+
     pub fn parse_document(&self, text: &str) -> Document {
         let mut document_tokens = vec![];
         Tokenizer::tokenize_doc(text, &self.config, |token| {
-            if let Some(token_id) = self.inverted_index.vocab.get(token) {
+            if let Some(token_id) = self.inverted_index.vocab().get(token) {
                 document_tokens.push(*token_id);
             }
         });
-        Document::new(document_tokens)
+        let mut sequence = vec![];
+        Tokenizer::tokenize_query(text, &self.config, |token| {
+            let token_id = self.inverted_index.vocab().get(token).copied();
+            sequence.push(token_id.unwrap_or(SEQUENCE_GAP));
+        });
+        Document::with_sequence(document_tokens, sequence)
+    }
+
+    /// Parse text or phrase condition into a query, `None` for other conditions and conditions
+    /// of other analyzers
+    fn parse_condition(&self, condition: &FieldCondition) -> Option<ParsedQuery> {
+        match &condition.r#match {
+            Some(Match::Text(text_match)) if self.has_analyzer(text_match.analyzer.as_deref()) => {
+                Some(self.parse_query(&text_match.text))
+            }
+            Some(Match::Phrase(phrase_match))
+                if self.has_analyzer(phrase_match.analyzer.as_deref()) =>
+            {
+                Some(self.parse_phrase_query(&phrase_match.phrase))
+            }
+            _ => None,
+        }
+    }
+
+    /// Parse prefix condition into a query, prefix conditions are served by the main index only
+    fn parse_prefix_condition(&self, condition: &FieldCondition) -> Option<ParsedPrefixQuery> {
+        match &condition.r#match {
+            Some(Match::Prefix(prefix_match)) if self.has_analyzer(None) => {
+                Some(self.parse_prefix_query(&prefix_match.prefix))
+            }
+            _ => None,
+        }
     }
 
     #[cfg(test)]
@@ -121,11 +264,11 @@ impl FullTextIndex {
 
     pub fn values_count(&self, point_id: PointOffsetType) -> usize {
         // Maybe we want number of documents in the future?
-        self.get_doc(point_id).map(|x| x.len()).unwrap_or(0)
+        self.inverted_index.values_count(point_id)
     }
 
     pub fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
-        self.get_doc(point_id).map(|x| x.is_empty()).unwrap_or(true)
+        self.values_count(point_id) == 0
     }
 }
 
@@ -136,18 +279,35 @@ impl ValueIndexer<String> for FullTextIndex {
         }
 
         let mut tokens: BTreeSet<String> = BTreeSet::new();
+        // Tokens in order of appearance, with values separated by empty gap tokens
+        let mut sequence: Vec<String> = vec![];
 
         for value in values {
             Tokenizer::tokenize_doc(&value, &self.config, |token| {
                 tokens.insert(token.to_owned());
             });
+            if !sequence.is_empty() {
+                sequence.push(String::new());
+            }
+            Tokenizer::tokenize_query(&value, &self.config, |token| {
+                sequence.push(token.to_owned());
+            });
         }
 
-        let document = self.inverted_index.document_from_tokens(&tokens);
-        self.inverted_index.index_document(idx, document);
+        match &mut self.inverted_index {
+            InvertedIndex::Mutable(index) => {
+                let document = index.document_from_tokens(&tokens, Some(&sequence));
+                index.index_document(idx, document);
+            }
+            InvertedIndex::Mmap(_) => {
+                return Err(OperationError::service_error(
+                    "Can't add values to immutable full-text index",
+                ));
+            }
+        }
 
         let db_idx = Self::store_key(&idx);
-        let db_document = self.serialize_document_tokens(tokens)?;
+        let db_document = self.serialize_document_tokens(tokens, sequence)?;
 
         self.db_wrapper.put(db_idx, db_document)?;
 
@@ -162,9 +322,7 @@ impl ValueIndexer<String> for FullTextIndex {
     }
 
     fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
-        let removed_doc = self.inverted_index.remove_document(id);
-
-        if removed_doc.is_none() {
+        if !self.inverted_index.remove_document(id) {
             return Ok(());
         }
 
@@ -177,7 +335,7 @@ impl ValueIndexer<String> for FullTextIndex {
 
 impl PayloadFieldIndex for FullTextIndex {
     fn count_indexed_points(&self) -> usize {
-        self.inverted_index.points_count
+        self.inverted_index.points_count()
     }
 
     fn load(&mut self) -> OperationResult<bool> {
@@ -185,15 +343,24 @@ impl PayloadFieldIndex for FullTextIndex {
             return Ok(false);
         };
 
+        let mut mutable_index = MutableInvertedIndex::new();
         for (key, value) in self.db_wrapper.lock_db().iter()? {
             let idx = Self::restore_key(&key);
-            let document = Self::deserialize_document(&value, &mut self.inverted_index)?;
-            self.inverted_index.index_document(idx, document);
+            let document = Self::deserialize_document(&value, &mut mutable_index)?;
+            mutable_index.index_document(idx, document);
+        }
+
+        match &mut self.inverted_index {
+            InvertedIndex::Mutable(index) => *index = mutable_index,
+            InvertedIndex::Mmap(index) => index.load_from(mutable_index)?,
         }
         Ok(true)
     }
 
-    fn clear(self) -> OperationResult<()> {
+    fn clear(mut self) -> OperationResult<()> {
+        if let InvertedIndex::Mmap(index) = &mut self.inverted_index {
+            index.remove_files()?;
+        }
         self.db_wrapper.remove_column_family()
     }
 
@@ -205,10 +372,12 @@ impl PayloadFieldIndex for FullTextIndex {
         &self,
         condition: &FieldCondition,
     ) -> OperationResult<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
-        if let Some(Match::Text(text_match)) = &condition.r#match {
-            let parsed_query = self.parse_query(&text_match.text);
+        if let Some(parsed_query) = self.parse_condition(condition) {
             return Ok(self.inverted_index.filter(&parsed_query));
         }
+        if let Some(parsed_query) = self.parse_prefix_condition(condition) {
+            return Ok(self.inverted_index.filter_prefix(&parsed_query));
+        }
         Err(OperationError::service_error("failed to filter"))
     }
 
@@ -216,12 +385,16 @@ impl PayloadFieldIndex for FullTextIndex {
         &self,
         condition: &FieldCondition,
     ) -> OperationResult<CardinalityEstimation> {
-        if let Some(Match::Text(text_match)) = &condition.r#match {
-            let parsed_query = self.parse_query(&text_match.text);
+        if let Some(parsed_query) = self.parse_condition(condition) {
             return Ok(self
                 .inverted_index
                 .estimate_cardinality(&parsed_query, condition));
         }
+        if let Some(parsed_query) = self.parse_prefix_condition(condition) {
+            return Ok(self
+                .inverted_index
+                .estimate_prefix_cardinality(&parsed_query, condition));
+        }
         Err(OperationError::service_error(
             "failed to estimate cardinality",
         ))
@@ -232,25 +405,31 @@ impl PayloadFieldIndex for FullTextIndex {
         threshold: usize,
         key: PayloadKeyType,
     ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        // Blocks of the main index already cover the field
+        if self.analyzer.is_some() {
+            return Box::new(std::iter::empty());
+        }
         self.inverted_index.payload_blocks(threshold, key)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
     use tempfile::Builder;
 
     use super::*;
     use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
     use crate::common::utils::MultiValue;
-    use crate::data_types::text_index::{TextIndexType, TokenizerType};
-    use crate::types::MatchText;
+    use crate::data_types::text_index::{TextAnalyzerParams, TextIndexType, TokenizerType};
+    use crate::types::{MatchPhrase, MatchPrefix, MatchText};
 
     fn filter_request(text: &str) -> FieldCondition {
         FieldCondition {
             key: "text".to_owned(),
             r#match: Some(Match::Text(MatchText {
                 text: text.to_owned(),
+                analyzer: None,
             })),
             range: None,
             geo_bounding_box: None,
@@ -280,6 +459,11 @@ mod tests {
             min_token_len: None,
             max_token_len: None,
             lowercase: None,
+            stemmer: None,
+            stopwords: None,
+            synonyms: None,
+            analyzers: None,
+            on_disk: None,
         };
 
         {
@@ -350,4 +534,357 @@ mod tests {
             assert_eq!(search_res, vec![0, 1, 3, 4]);
         }
     }
+
+    #[test]
+    fn test_phrase_matching() {
+        let payloads: Vec<_> = vec![
+            serde_json::json!("The giant computer was silent."),
+            serde_json::json!("A computer, giant and silent."),
+            serde_json::json!(["Giant", "computer"]),
+        ];
+
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let config = TextIndexParams::default();
+
+        let phrase_request = |phrase: &str| FieldCondition {
+            r#match: Some(Match::Phrase(MatchPhrase {
+                phrase: phrase.to_owned(),
+                analyzer: None,
+            })),
+            ..filter_request(phrase)
+        };
+
+        {
+            let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+            let mut index = FullTextIndex::new(db, config.clone(), "text");
+            index.recreate().unwrap();
+
+            for (idx, payload) in payloads.iter().enumerate() {
+                index
+                    .add_point(idx as PointOffsetType, &MultiValue::one(payload))
+                    .unwrap();
+            }
+
+            let search_res: Vec<_> = index
+                .filter(&filter_request("giant computer"))
+                .unwrap()
+                .collect();
+            assert_eq!(search_res, vec![0, 1, 2]);
+
+            // Words are adjacent only in the first document, the last one has them in separate values
+            let search_res: Vec<_> = index
+                .filter(&phrase_request("giant computer"))
+                .unwrap()
+                .collect();
+            assert_eq!(search_res, vec![0]);
+
+            let search_res: Vec<_> = index
+                .filter(&phrase_request("computer giant"))
+                .unwrap()
+                .collect();
+            assert!(search_res.is_empty());
+
+            index.flusher()().unwrap();
+        }
+
+        {
+            let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+            let mut index = FullTextIndex::new(db, config, "text");
+            assert!(index.load().unwrap());
+
+            let search_res: Vec<_> = index
+                .filter(&phrase_request("giant computer was"))
+                .unwrap()
+                .collect();
+            assert_eq!(search_res, vec![0]);
+        }
+    }
+
+    #[test]
+    fn test_prefix_matching() {
+        let payloads: Vec<_> = vec![
+            serde_json::json!("Qdrant is a vector database"),
+            serde_json::json!("Quadratic equations"),
+            serde_json::json!(["Vectors", "QDRANT cloud"]),
+        ];
+
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let mut index = FullTextIndex::new(db, TextIndexParams::default(), "text");
+        index.recreate().unwrap();
+
+        for (idx, payload) in payloads.iter().enumerate() {
+            index
+                .add_point(idx as PointOffsetType, &MultiValue::one(payload))
+                .unwrap();
+        }
+
+        let prefix_request = |prefix: &str| FieldCondition {
+            r#match: Some(Match::Prefix(MatchPrefix {
+                prefix: prefix.to_owned(),
+            })),
+            ..filter_request(prefix)
+        };
+
+        let search_res: Vec<_> = index
+            .filter(&prefix_request("Qdr"))
+            .unwrap()
+            .sorted()
+            .collect();
+        assert_eq!(search_res, vec![0, 2]);
+
+        let search_res: Vec<_> = index
+            .filter(&prefix_request("vec"))
+            .unwrap()
+            .sorted()
+            .collect();
+        assert_eq!(search_res, vec![0, 2]);
+
+        let search_res: Vec<_> = index
+            .filter(&prefix_request("q"))
+            .unwrap()
+            .sorted()
+            .collect();
+        assert_eq!(search_res, vec![0, 1, 2]);
+
+        assert!(index
+            .filter(&prefix_request("database!"))
+            .unwrap()
+            .next()
+            .is_none());
+
+        let estimation = index.estimate_cardinality(&prefix_request("q")).unwrap();
+        assert!(estimation.min <= 3 && estimation.max >= 3);
+    }
+
+    #[test]
+    fn test_synonyms_matching() {
+        let payloads: Vec<_> = vec![
+            serde_json::json!("A red car"),
+            serde_json::json!("A red automobile"),
+            serde_json::json!("A blue auto"),
+            serde_json::json!("A red bicycle"),
+        ];
+
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let config = TextIndexParams {
+            synonyms: Some(vec![["car", "automobile", "auto"]
+                .into_iter()
+                .map(String::from)
+                .collect()]),
+            ..Default::default()
+        };
+        let mut index = FullTextIndex::new(db, config, "text");
+        index.recreate().unwrap();
+
+        for (idx, payload) in payloads.iter().enumerate() {
+            index
+                .add_point(idx as PointOffsetType, &MultiValue::one(payload))
+                .unwrap();
+        }
+
+        let search_res: Vec<_> = index
+            .filter(&filter_request("car"))
+            .unwrap()
+            .sorted()
+            .collect();
+        assert_eq!(search_res, vec![0, 1, 2]);
+
+        let search_res: Vec<_> = index
+            .filter(&filter_request("red auto"))
+            .unwrap()
+            .sorted()
+            .collect();
+        assert_eq!(search_res, vec![0, 1]);
+
+        for (idx, payload) in payloads.iter().enumerate() {
+            let document = index.get_doc(idx as PointOffsetType).unwrap();
+            let expected = search_res.contains(&(idx as PointOffsetType));
+            assert_eq!(
+                index.parse_query("red auto").check_match(&document),
+                expected,
+                "{payload}"
+            );
+        }
+
+        let estimation = index
+            .estimate_cardinality(&filter_request("red auto"))
+            .unwrap();
+        assert!(estimation.min <= 2 && estimation.max >= 2);
+    }
+
+    #[test]
+    fn test_analyzer_indexes() {
+        let payloads: Vec<_> = vec![
+            serde_json::json!("Database of vectors"),
+            serde_json::json!("Vectorized data"),
+            serde_json::json!("A base of data"),
+        ];
+
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let config = TextIndexParams {
+            analyzers: Some(
+                [(
+                    "ngram".to_owned(),
+                    TextAnalyzerParams {
+                        tokenizer: TokenizerType::Ngram,
+                        ..Default::default()
+                    },
+                )]
+                .into(),
+            ),
+            ..Default::default()
+        };
+        let ngram_config = config.with_analyzer(&config.analyzers.as_ref().unwrap()["ngram"]);
+
+        let mut word_index = FullTextIndex::new(db.clone(), config.clone(), "text");
+        let mut ngram_index =
+            FullTextIndex::new_with_analyzer(db, ngram_config, "text", Some("ngram".to_owned()));
+        word_index.recreate().unwrap();
+        ngram_index.recreate().unwrap();
+
+        for (idx, payload) in payloads.iter().enumerate() {
+            let value = MultiValue::one(payload);
+            word_index
+                .add_point(idx as PointOffsetType, &value)
+                .unwrap();
+            ngram_index
+                .add_point(idx as PointOffsetType, &value)
+                .unwrap();
+        }
+
+        let ngram_request = |text: &str| FieldCondition {
+            r#match: Some(Match::Text(MatchText {
+                text: text.to_owned(),
+                analyzer: Some("ngram".to_owned()),
+            })),
+            ..filter_request(text)
+        };
+
+        // Each condition is served by the index of its analyzer only
+        assert!(word_index.filter(&ngram_request("base")).is_err());
+        assert!(ngram_index.filter(&filter_request("base")).is_err());
+        let prefix_request = FieldCondition {
+            r#match: Some(Match::Prefix(MatchPrefix {
+                prefix: "base".to_owned(),
+            })),
+            ..filter_request("base")
+        };
+        assert!(ngram_index.filter(&prefix_request).is_err());
+
+        let search_res: Vec<_> = word_index
+            .filter(&filter_request("base"))
+            .unwrap()
+            .sorted()
+            .collect();
+        assert_eq!(search_res, vec![2]);
+
+        let search_res: Vec<_> = ngram_index
+            .filter(&ngram_request("base"))
+            .unwrap()
+            .sorted()
+            .collect();
+        assert_eq!(search_res, vec![0, 2]);
+
+        let search_res: Vec<_> = ngram_index
+            .filter(&ngram_request("vector"))
+            .unwrap()
+            .sorted()
+            .collect();
+        assert_eq!(search_res, vec![0, 1]);
+
+        assert_eq!(ngram_index.payload_blocks(1, "text".to_owned()).count(), 0);
+    }
+
+    #[test]
+    fn test_mmap_full_text_index() {
+        let payloads: Vec<_> = vec![
+            serde_json::json!("The giant computer was silent."),
+            serde_json::json!("A computer, giant and silent."),
+            serde_json::json!(["Giant", "computer"]),
+            serde_json::json!("Quiet chambers of Multivac"),
+        ];
+
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let index_path = temp_dir.path().join("text_fts");
+        let config = TextIndexParams {
+            on_disk: Some(true),
+            ..Default::default()
+        };
+
+        {
+            let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+            let mut index = FullTextIndex::new(db, config.clone(), "text");
+            index.recreate().unwrap();
+            for (idx, payload) in payloads.iter().enumerate() {
+                index
+                    .add_point(idx as PointOffsetType, &MultiValue::one(payload))
+                    .unwrap();
+            }
+            index.flusher()().unwrap();
+        }
+
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let mut index = FullTextIndex::new_mmap(db, config, "text", None, &index_path);
+        assert!(index.load().unwrap());
+        assert!(index_path.exists());
+        assert_eq!(index.count_indexed_points(), payloads.len());
+
+        let phrase_request = FieldCondition {
+            r#match: Some(Match::Phrase(MatchPhrase {
+                phrase: "giant computer".to_owned(),
+                analyzer: None,
+            })),
+            ..filter_request("giant computer")
+        };
+        let prefix_request = FieldCondition {
+            r#match: Some(Match::Prefix(MatchPrefix {
+                prefix: "multi".to_owned(),
+            })),
+            ..filter_request("multi")
+        };
+
+        let search_res: Vec<_> = index
+            .filter(&filter_request("giant computer"))
+            .unwrap()
+            .collect();
+        assert_eq!(search_res, vec![0, 1, 2]);
+        let search_res: Vec<_> = index.filter(&phrase_request).unwrap().collect();
+        assert_eq!(search_res, vec![0]);
+        let search_res: Vec<_> = index.filter(&prefix_request).unwrap().collect();
+        assert_eq!(search_res, vec![3]);
+
+        let document = index.get_doc(0).unwrap();
+        assert!(index
+            .parse_phrase_query("giant computer")
+            .check_match(&document));
+        assert_eq!(index.values_count(2), 2);
+
+        // Values can't be added, but points can be removed
+        assert!(index
+            .add_point(4, &MultiValue::one(&serde_json::json!("giant")))
+            .is_err());
+        index.remove_point(1).unwrap();
+        assert_eq!(index.count_indexed_points(), payloads.len() - 1);
+        assert!(index.get_doc(1).is_none());
+        assert!(index.values_is_empty(1));
+
+        let search_res: Vec<_> = index
+            .filter(&filter_request("giant computer"))
+            .unwrap()
+            .collect();
+        assert_eq!(search_res, vec![0, 2]);
+        let estimation = index
+            .estimate_cardinality(&filter_request("giant"))
+            .unwrap();
+        assert!(estimation.min <= 2 && estimation.max >= 2);
+        let blocks: Vec<_> = index.payload_blocks(2, "text".to_owned()).collect();
+        assert!(blocks.iter().all(|block| block.cardinality == 2));
+        assert!(!blocks.is_empty());
+
+        index.clear().unwrap();
+        assert!(!index_path.exists());
+    }
 }
@@ -1,6 +1,9 @@
+use std::borrow::Cow;
+
 use charabia::Tokenize;
+use rust_stemmers::{Algorithm, Stemmer};
 
-use crate::data_types::text_index::{TextIndexParams, TokenizerType};
+use crate::data_types::text_index::{StemmerLanguage, TextIndexParams, TokenizerType};
 
 struct WhiteSpaceTokenizer;
 
@@ -62,6 +65,74 @@ impl PrefixTokenizer {
     }
 }
 
+/// Default length of n-grams of the `ngram` tokenizer
+const DEFAULT_NGRAM_LEN: usize = 3;
+
+struct NgramTokenizer;
+
+impl NgramTokenizer {
+    /// Byte offsets of the word's chars, followed by the word length
+    fn char_boundaries(word: &str) -> Vec<usize> {
+        word.char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(word.len()))
+            .collect()
+    }
+
+    /// Docs. tokens (2..=3): "hello" -> ["he", "el", "ll", "lo", "hel", "ell", "llo"]
+    ///
+    /// Words, which are shorter than `min_ngram`, are kept as is.
+    fn tokenize<C: FnMut(&str)>(text: &str, min_ngram: usize, max_ngram: usize, mut callback: C) {
+        text.split(|c| !char::is_alphanumeric(c))
+            .filter(|token| !token.is_empty())
+            .for_each(|word| {
+                let boundaries = Self::char_boundaries(word);
+                let chars_count = boundaries.len() - 1;
+                if chars_count < min_ngram {
+                    callback(word);
+                    return;
+                }
+                for n in min_ngram..=max_ngram.min(chars_count) {
+                    for start in 0..=chars_count - n {
+                        callback(&word[boundaries[start]..boundaries[start + n]]);
+                    }
+                }
+            });
+    }
+
+    /// Query words are split into n-grams of the maximal length only.
+    /// E.g.
+    ///
+    /// Query tokens (2..=3): "ell"   -> ["ell"]
+    /// Query tokens (2..=3): "hello" -> ["hel", "ell", "llo"]
+    ///
+    /// Documents should contain all n-grams of the word, so matching of words, which are longer
+    /// than the maximal n-gram, is approximate.
+    fn tokenize_query<C: FnMut(&str)>(text: &str, max_ngram: usize, mut callback: C) {
+        text.split(|c| !char::is_alphanumeric(c))
+            .filter(|token| !token.is_empty())
+            .for_each(|word| {
+                let boundaries = Self::char_boundaries(word);
+                let chars_count = boundaries.len() - 1;
+                if chars_count <= max_ngram {
+                    callback(word);
+                    return;
+                }
+                for start in 0..=chars_count - max_ngram {
+                    callback(&word[boundaries[start]..boundaries[start + max_ngram]]);
+                }
+            });
+    }
+}
+
+fn ngram_bounds(config: &TextIndexParams) -> (usize, usize) {
+    let min_ngram = config.min_token_len.unwrap_or(DEFAULT_NGRAM_LEN);
+    let max_ngram = config
+        .max_token_len
+        .unwrap_or_else(|| min_ngram.max(DEFAULT_NGRAM_LEN));
+    (min_ngram, max_ngram)
+}
+
 struct MultilingualTokenizer;
 
 impl MultilingualTokenizer {
@@ -74,6 +145,105 @@ impl MultilingualTokenizer {
     }
 }
 
+fn stemmer_algorithm(language: StemmerLanguage) -> Algorithm {
+    match language {
+        StemmerLanguage::Arabic => Algorithm::Arabic,
+        StemmerLanguage::Danish => Algorithm::Danish,
+        StemmerLanguage::Dutch => Algorithm::Dutch,
+        StemmerLanguage::English => Algorithm::English,
+        StemmerLanguage::Finnish => Algorithm::Finnish,
+        StemmerLanguage::French => Algorithm::French,
+        StemmerLanguage::German => Algorithm::German,
+        StemmerLanguage::Greek => Algorithm::Greek,
+        StemmerLanguage::Hungarian => Algorithm::Hungarian,
+        StemmerLanguage::Italian => Algorithm::Italian,
+        StemmerLanguage::Norwegian => Algorithm::Norwegian,
+        StemmerLanguage::Portuguese => Algorithm::Portuguese,
+        StemmerLanguage::Romanian => Algorithm::Romanian,
+        StemmerLanguage::Russian => Algorithm::Russian,
+        StemmerLanguage::Spanish => Algorithm::Spanish,
+        StemmerLanguage::Swedish => Algorithm::Swedish,
+        StemmerLanguage::Tamil => Algorithm::Tamil,
+        StemmerLanguage::Turkish => Algorithm::Turkish,
+    }
+}
+
+/// Applies length limits, lowercasing, stopwords and stemming to tokens
+struct TokenFilter<'a> {
+    config: &'a TextIndexParams,
+    stemmer: Option<Stemmer>,
+}
+
+impl<'a> TokenFilter<'a> {
+    fn new(config: &'a TextIndexParams) -> Self {
+        // Stems of prefixes and n-grams are meaningless, so such tokens are kept as is
+        let stemmer = match config.tokenizer {
+            TokenizerType::Prefix | TokenizerType::Ngram => None,
+            _ => config
+                .stemmer
+                .map(|language| Stemmer::create(stemmer_algorithm(language))),
+        };
+        Self { config, stemmer }
+    }
+
+    fn lowercase<'b>(&self, token: &'b str) -> Cow<'b, str> {
+        if self.config.lowercase.unwrap_or(true) {
+            Cow::Owned(token.to_lowercase())
+        } else {
+            Cow::Borrowed(token)
+        }
+    }
+
+    fn apply<C: FnMut(&str)>(&self, token: &str, mut callback: C) {
+        let config = self.config;
+        if config
+            .min_token_len
+            .map(|min_len| token.len() < min_len && token.chars().count() < min_len)
+            .unwrap_or(false)
+        {
+            return;
+        }
+        if config
+            .max_token_len
+            .map(|max_len| token.len() > max_len && token.chars().count() > max_len)
+            .unwrap_or(false)
+        {
+            return;
+        }
+        let token = self.lowercase(token);
+        if config
+            .stopwords
+            .as_ref()
+            .map(|stopwords| stopwords.contains(token.as_ref()))
+            .unwrap_or(false)
+        {
+            return;
+        }
+        match &self.stemmer {
+            Some(stemmer) => callback(&stemmer.stem(&token)),
+            None => callback(&token),
+        }
+    }
+
+    /// Filtered tokens of the words, which are synonyms of the `token`
+    fn synonyms(&self, token: &str) -> Vec<String> {
+        let Some(synonym_groups) = &self.config.synonyms else {
+            return vec![];
+        };
+        let token = self.lowercase(token);
+        let mut synonyms = vec![];
+        for group in synonym_groups {
+            if !group.contains(token.as_ref()) {
+                continue;
+            }
+            for word in group.iter().filter(|word| **word != token) {
+                self.apply(word, |synonym| synonyms.push(synonym.to_owned()));
+            }
+        }
+        synonyms
+    }
+}
+
 pub struct Tokenizer;
 
 impl Tokenizer {
@@ -81,27 +251,8 @@ impl Tokenizer {
         config: &'a TextIndexParams,
         mut callback: C,
     ) -> impl FnMut(&str) + 'a {
-        move |token: &str| {
-            if config
-                .min_token_len
-                .map(|min_len| token.len() < min_len && token.chars().count() < min_len)
-                .unwrap_or(false)
-            {
-                return;
-            }
-            if config
-                .max_token_len
-                .map(|max_len| token.len() > max_len && token.chars().count() > max_len)
-                .unwrap_or(false)
-            {
-                return;
-            }
-            if config.lowercase.unwrap_or(true) {
-                callback(&token.to_lowercase());
-            } else {
-                callback(token);
-            }
-        }
+        let token_filter = TokenFilter::new(config);
+        move |token: &str| token_filter.apply(token, &mut callback)
     }
 
     pub fn tokenize_doc<C: FnMut(&str)>(text: &str, config: &TextIndexParams, mut callback: C) {
@@ -116,11 +267,18 @@ impl Tokenizer {
                 config.max_token_len.unwrap_or(usize::MAX),
                 token_filter,
             ),
+            TokenizerType::Ngram => {
+                let (min_ngram, max_ngram) = ngram_bounds(config);
+                NgramTokenizer::tokenize(text, min_ngram, max_ngram, token_filter)
+            }
         }
     }
 
-    pub fn tokenize_query<C: FnMut(&str)>(text: &str, config: &TextIndexParams, mut callback: C) {
-        let token_filter = Self::doc_token_filter(config, &mut callback);
+    fn tokenize_query_with_filter<F: FnMut(&str)>(
+        text: &str,
+        config: &TextIndexParams,
+        token_filter: F,
+    ) {
         match config.tokenizer {
             TokenizerType::Whitespace => WhiteSpaceTokenizer::tokenize(text, token_filter),
             TokenizerType::Word => WordTokenizer::tokenize(text, token_filter),
@@ -130,8 +288,30 @@ impl Tokenizer {
                 config.max_token_len.unwrap_or(usize::MAX),
                 token_filter,
             ),
+            TokenizerType::Ngram => {
+                let (_, max_ngram) = ngram_bounds(config);
+                NgramTokenizer::tokenize_query(text, max_ngram, token_filter)
+            }
         }
     }
+
+    pub fn tokenize_query<C: FnMut(&str)>(text: &str, config: &TextIndexParams, mut callback: C) {
+        let token_filter = Self::doc_token_filter(config, &mut callback);
+        Self::tokenize_query_with_filter(text, config, token_filter);
+    }
+
+    /// Tokenize query, passing each token to the `callback` together with tokens of its synonyms
+    pub fn tokenize_query_with_synonyms<C: FnMut(&str, &[String])>(
+        text: &str,
+        config: &TextIndexParams,
+        mut callback: C,
+    ) {
+        let token_filter = TokenFilter::new(config);
+        Self::tokenize_query_with_filter(text, config, |token| {
+            let synonyms = token_filter.synonyms(token);
+            token_filter.apply(token, |token| callback(token, &synonyms));
+        });
+    }
 }
 
 #[cfg(test)]
@@ -188,6 +368,23 @@ mod tests {
         assert_eq!(tokens.get(1), Some(&"мир".to_owned()));
     }
 
+    #[test]
+    fn test_ngram_tokenizer() {
+        let text = "hello, мир, a!";
+        let mut tokens = Vec::new();
+        NgramTokenizer::tokenize(text, 2, 3, |token| tokens.push(token.to_owned()));
+        assert_eq!(
+            tokens,
+            ["he", "el", "ll", "lo", "hel", "ell", "llo", "ми", "ир", "мир", "a"]
+        );
+
+        let mut query_tokens = Vec::new();
+        NgramTokenizer::tokenize_query("ell мир hello", 3, |token| {
+            query_tokens.push(token.to_owned())
+        });
+        assert_eq!(query_tokens, ["ell", "мир", "hel", "ell", "llo"]);
+    }
+
     #[cfg(feature = "multiling-japanese")]
     #[test]
     fn test_multilingual_tokenizer_japanese() {
@@ -254,6 +451,11 @@ mod tests {
                 min_token_len: Some(1),
                 max_token_len: Some(4),
                 lowercase: Some(true),
+                stemmer: None,
+                stopwords: None,
+                synonyms: None,
+                analyzers: None,
+                on_disk: None,
             },
             |token| tokens.push(token.to_owned()),
         );
@@ -267,4 +469,67 @@ mod tests {
         assert_eq!(tokens.get(5), Some(&"ми".to_owned()));
         assert_eq!(tokens.get(6), Some(&"мир".to_owned()));
     }
+
+    #[test]
+    fn test_tokenizer_stemmer_and_stopwords() {
+        let text = "The runners are running to the Houses";
+        let config = TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Word,
+            min_token_len: None,
+            max_token_len: None,
+            lowercase: Some(true),
+            stemmer: Some(StemmerLanguage::English),
+            stopwords: Some(["the", "are", "to"].into_iter().map(String::from).collect()),
+            synonyms: None,
+            analyzers: None,
+            on_disk: None,
+        };
+
+        let mut tokens = Vec::new();
+        Tokenizer::tokenize_doc(text, &config, |token| tokens.push(token.to_owned()));
+        eprintln!("tokens = {tokens:#?}");
+        assert_eq!(tokens, ["runner", "run", "hous"]);
+
+        let mut query_tokens = Vec::new();
+        Tokenizer::tokenize_query("RUN house", &config, |token| {
+            query_tokens.push(token.to_owned())
+        });
+        assert_eq!(query_tokens, ["run", "hous"]);
+    }
+
+    #[test]
+    fn test_tokenizer_synonyms() {
+        let config = TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Word,
+            min_token_len: None,
+            max_token_len: None,
+            lowercase: Some(true),
+            stemmer: Some(StemmerLanguage::English),
+            stopwords: None,
+            synonyms: Some(vec![["car", "automobiles", "auto"]
+                .into_iter()
+                .map(String::from)
+                .collect()]),
+            analyzers: None,
+            on_disk: None,
+        };
+
+        let mut query_tokens = Vec::new();
+        Tokenizer::tokenize_query_with_synonyms("Red CARS car", &config, |token, synonyms| {
+            query_tokens.push((token.to_owned(), synonyms.to_vec()))
+        });
+        assert_eq!(
+            query_tokens,
+            [
+                ("red".to_owned(), vec![]),
+                ("car".to_owned(), vec![]),
+                (
+                    "car".to_owned(),
+                    vec!["auto".to_owned(), "automobil".to_owned()]
+                ),
+            ]
+        );
+    }
 }
@@ -1,16 +1,25 @@
+use std::fmt::{Display, Write};
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::Arc;
 
 use parking_lot::RwLock;
 use rocksdb::DB;
 
 use super::binary_index::BinaryIndex;
+use super::datetime_index::DatetimeIndex;
+use crate::data_types::text_index::TextIndexParams;
 use crate::index::field_index::full_text_index::text_index::FullTextIndex;
 use crate::index::field_index::geo_index::GeoMapIndex;
+use crate::index::field_index::histogram::Numericable;
 use crate::index::field_index::map_index::MapIndex;
-use crate::index::field_index::numeric_index::NumericIndex;
+use crate::index::field_index::null_index::NullIndex;
+use crate::index::field_index::numeric_index::{Encodable, NumericIndex};
 use crate::index::field_index::FieldIndex;
 use crate::types::{
     FloatPayloadType, IntPayloadType, PayloadFieldSchema, PayloadSchemaParams, PayloadSchemaType,
+    UuidIntType,
 };
 
 /// Selects index types based on field type
@@ -19,8 +28,13 @@ pub fn index_selector(
     payload_schema: &PayloadFieldSchema,
     db: Arc<RwLock<DB>>,
     is_appendable: bool,
+    path: &Path,
 ) -> Vec<FieldIndex> {
-    match payload_schema {
+    // Every indexed field also tracks empty and null values
+    let null_index = FieldIndex::NullIndex(NullIndex::new(db.clone(), field));
+    // Indexes of appendable segments are always kept in RAM
+    let on_disk_path = (payload_schema.is_on_disk() && !is_appendable).then_some(path);
+    let mut indexes = match payload_schema {
         PayloadFieldSchema::FieldType(payload_type) => match payload_type {
             PayloadSchemaType::Keyword => {
                 vec![FieldIndex::KeywordIndex(MapIndex::new(
@@ -49,11 +63,128 @@ pub fn index_selector(
                 field,
             ))],
             PayloadSchemaType::Bool => vec![FieldIndex::BinaryIndex(BinaryIndex::new(db, field))],
+            PayloadSchemaType::Datetime => vec![FieldIndex::DatetimeIndex(DatetimeIndex::new(
+                db,
+                field,
+                is_appendable,
+            ))],
+            PayloadSchemaType::Uuid => vec![FieldIndex::UuidIndex(MapIndex::<UuidIntType>::new(
+                db,
+                field,
+                is_appendable,
+            ))],
         },
         PayloadFieldSchema::FieldParams(payload_params) => match payload_params {
-            PayloadSchemaParams::Text(text_index_params) => vec![FieldIndex::FullTextIndex(
-                FullTextIndex::new(db, text_index_params.clone(), field),
-            )],
+            PayloadSchemaParams::Keyword(_) => vec![FieldIndex::KeywordIndex(new_map_index(
+                db,
+                field,
+                is_appendable,
+                on_disk_path,
+            ))],
+            PayloadSchemaParams::Integer(_) => vec![
+                FieldIndex::IntMapIndex(new_map_index(
+                    db.clone(),
+                    field,
+                    is_appendable,
+                    on_disk_path,
+                )),
+                FieldIndex::IntIndex(new_numeric_index::<IntPayloadType>(
+                    db,
+                    field,
+                    is_appendable,
+                    on_disk_path,
+                )),
+            ],
+            PayloadSchemaParams::Float(_) => {
+                vec![FieldIndex::FloatIndex(
+                    new_numeric_index::<FloatPayloadType>(db, field, is_appendable, on_disk_path),
+                )]
+            }
+            PayloadSchemaParams::Text(text_index_params) => {
+                new_full_text_indexes(db, text_index_params, field, on_disk_path)
+            }
         },
+    };
+    indexes.push(null_index);
+    indexes
+}
+
+/// Main full-text index of the field, followed by the indexes of additional analyzers
+fn new_full_text_indexes(
+    db: Arc<RwLock<DB>>,
+    params: &TextIndexParams,
+    field: &str,
+    on_disk_path: Option<&Path>,
+) -> Vec<FieldIndex> {
+    let main_index = match on_disk_path {
+        Some(path) => FullTextIndex::new_mmap(
+            db.clone(),
+            params.clone(),
+            field,
+            None,
+            &field_index_path(path, field, "fts"),
+        ),
+        None => FullTextIndex::new(db.clone(), params.clone(), field),
+    };
+    let mut indexes = vec![FieldIndex::FullTextIndex(main_index)];
+    indexes.extend(params.analyzers.iter().flatten().map(|(name, analyzer)| {
+        let analyzer_params = params.with_analyzer(analyzer);
+        let analyzer_index = match on_disk_path {
+            Some(path) => FullTextIndex::new_mmap(
+                db.clone(),
+                analyzer_params,
+                field,
+                Some(name.clone()),
+                &field_index_path(&field_index_path(path, field, "fts_analyzers"), name, "fts"),
+            ),
+            None => FullTextIndex::new_with_analyzer(
+                db.clone(),
+                analyzer_params,
+                field,
+                Some(name.clone()),
+            ),
+        };
+        FieldIndex::FullTextIndex(analyzer_index)
+    }));
+    indexes
+}
+
+fn new_map_index<N: Hash + Eq + Clone + Display + FromStr + Ord + Default>(
+    db: Arc<RwLock<DB>>,
+    field: &str,
+    is_appendable: bool,
+    on_disk_path: Option<&Path>,
+) -> MapIndex<N> {
+    match on_disk_path {
+        Some(path) => MapIndex::new_mmap(db, field, &field_index_path(path, field, "map")),
+        None => MapIndex::new(db, field, is_appendable),
+    }
+}
+
+fn new_numeric_index<T: Encodable + Numericable>(
+    db: Arc<RwLock<DB>>,
+    field: &str,
+    is_appendable: bool,
+    on_disk_path: Option<&Path>,
+) -> NumericIndex<T> {
+    match on_disk_path {
+        Some(path) => NumericIndex::new_mmap(db, field, &field_index_path(path, field, "numeric")),
+        None => NumericIndex::new(db, field, is_appendable),
+    }
+}
+
+/// Directory for on-disk files of the field index.
+///
+/// Field name is escaped, so that any field name makes a single valid file name.
+fn field_index_path(path: &Path, field: &str, suffix: &str) -> PathBuf {
+    let mut name = String::with_capacity(field.len() + suffix.len() + 1);
+    for byte in field.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-' {
+            name.push(byte as char);
+        } else {
+            // Writing into a string never fails
+            let _ = write!(name, "%{byte:02X}");
+        }
     }
+    path.join(format!("{name}_{suffix}"))
 }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fmt::Display;
 use std::hash::Hash;
 use std::iter;
@@ -15,8 +15,10 @@ use super::MapIndex;
 use crate::common::operation_error::OperationResult;
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 
-pub struct ImmutableMapIndex<N: Hash + Eq + Clone + Display + FromStr> {
+pub struct ImmutableMapIndex<N: Hash + Eq + Clone + Display + FromStr + Ord> {
     value_to_points: HashMap<N, Range<u32>>,
+    /// Keys of `value_to_points` in sorted order, used for range lookups
+    sorted_values: BTreeSet<N>,
     value_to_points_container: Vec<PointOffsetType>,
     point_to_values: Vec<Range<u32>>,
     point_to_values_container: Vec<N>,
@@ -26,12 +28,13 @@ pub struct ImmutableMapIndex<N: Hash + Eq + Clone + Display + FromStr> {
     db_wrapper: DatabaseColumnWrapper,
 }
 
-impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
+impl<N: Hash + Eq + Clone + Display + FromStr + Ord + Default> ImmutableMapIndex<N> {
     pub fn new(db: Arc<RwLock<DB>>, field_name: &str) -> Self {
         let store_cf_name = MapIndex::<N>::storage_cf_name(field_name);
         let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
         Self {
             value_to_points: Default::default(),
+            sorted_values: Default::default(),
             value_to_points_container: Default::default(),
             point_to_values: Default::default(),
             point_to_values_container: Default::default(),
@@ -107,6 +110,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
 
         if self.shrink_value_range(value) {
             self.value_to_points.remove(value);
+            self.sorted_values.remove(value);
         }
     }
 
@@ -151,6 +155,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
 
         let mut mutable = MutableMapIndex {
             map: Default::default(),
+            sorted_values: Default::default(),
             point_to_values: Vec::new(),
             indexed_points: 0,
             values_count: 0,
@@ -159,6 +164,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
         let result = mutable.load_from_db()?;
         let MutableMapIndex {
             map,
+            sorted_values,
             point_to_values,
             indexed_points,
             values_count,
@@ -168,6 +174,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
         self.indexed_points = indexed_points;
         self.values_count = values_count;
         self.value_to_points.clear();
+        self.sorted_values = sorted_values;
         self.value_to_points_container.clear();
         self.point_to_values.clear();
         self.point_to_values_container.clear();
@@ -237,4 +244,8 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
     pub fn get_values_iterator(&self) -> Box<dyn Iterator<Item = &N> + '_> {
         Box::new(self.value_to_points.keys())
     }
+
+    pub fn get_sorted_values(&self) -> &BTreeSet<N> {
+        &self.sorted_values
+    }
 }
@@ -0,0 +1,278 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Display;
+use std::fs::{create_dir_all, remove_dir_all};
+use std::hash::Hash;
+use std::iter;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use bitvec::vec::BitVec;
+use common::types::PointOffsetType;
+use parking_lot::RwLock;
+use rocksdb::DB;
+
+use super::mutable_map_index::MutableMapIndex;
+use super::MapIndex;
+use crate::common::operation_error::OperationResult;
+use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::index::field_index::mmap_array::MmapArray;
+
+const VALUE_OFFSETS_FILE: &str = "value_offsets.bin";
+const VALUE_POINTS_FILE: &str = "value_points.bin";
+const POINT_OFFSETS_FILE: &str = "point_offsets.bin";
+const POINT_VALUES_FILE: &str = "point_values.bin";
+
+/// Memory mapped values-to-points and points-to-values maps
+struct MmapMapStorage {
+    /// Offsets of the points of each value in `value_points`, with one extra offset at the end
+    value_offsets: MmapArray<u32>,
+    value_points: MmapArray<PointOffsetType>,
+    /// Offsets of the values of each point in `point_values`, with one extra offset at the end
+    point_offsets: MmapArray<u32>,
+    /// Ids of the values of each point, id is a position in the sorted list of unique values
+    point_values: MmapArray<u32>,
+}
+
+/// Immutable map index, which keeps the point lists in memory mapped files.
+///
+/// Only unique values and a few counters are cached in RAM, so the index is suitable for fields
+/// with a small number of distinct values over a large number of points, such as tenant ids.
+/// RocksDB remains the source of truth: the files are rebuilt from it on each load.
+pub struct MmapMapIndex<N: Hash + Eq + Clone + Display + FromStr + Ord> {
+    path: PathBuf,
+    /// Unique values in sorted order, position of the value is its id
+    values: Vec<N>,
+    value_to_id: HashMap<N, u32>,
+    /// Values, which still have points, used for range lookups
+    sorted_values: BTreeSet<N>,
+    /// Number of not deleted points of each value
+    value_points_count: Vec<u32>,
+    storage: Option<MmapMapStorage>,
+    deleted: BitVec,
+    /// Amount of point which have at least one indexed payload value
+    indexed_points: usize,
+    values_count: usize,
+    db_wrapper: DatabaseColumnWrapper,
+}
+
+impl<N: Hash + Eq + Clone + Display + FromStr + Ord + Default> MmapMapIndex<N> {
+    pub fn new(db: Arc<RwLock<DB>>, field_name: &str, path: &Path) -> Self {
+        let store_cf_name = MapIndex::<N>::storage_cf_name(field_name);
+        let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
+        Self {
+            path: path.to_owned(),
+            values: Default::default(),
+            value_to_id: Default::default(),
+            sorted_values: Default::default(),
+            value_points_count: Default::default(),
+            storage: None,
+            deleted: Default::default(),
+            indexed_points: 0,
+            values_count: 0,
+            db_wrapper,
+        }
+    }
+
+    pub fn get_db_wrapper(&self) -> &DatabaseColumnWrapper {
+        &self.db_wrapper
+    }
+
+    pub fn load_from_db(&mut self) -> OperationResult<bool> {
+        // Load data from db into `MutableMapIndex` first and write it into memory mapped files
+
+        let mut mutable = MutableMapIndex {
+            map: Default::default(),
+            sorted_values: Default::default(),
+            point_to_values: Vec::new(),
+            indexed_points: 0,
+            values_count: 0,
+            db_wrapper: self.db_wrapper.clone(),
+        };
+        if !mutable.load_from_db()? {
+            return Ok(false);
+        }
+        let MutableMapIndex {
+            map,
+            sorted_values,
+            point_to_values,
+            indexed_points,
+            values_count,
+            ..
+        } = mutable;
+
+        let values: Vec<N> = sorted_values.iter().cloned().collect();
+        let value_to_id: HashMap<N, u32> = values
+            .iter()
+            .enumerate()
+            .map(|(id, value)| (value.clone(), id as u32))
+            .collect();
+        let value_points: Vec<&BTreeSet<PointOffsetType>> =
+            values.iter().map(|value| &map[value]).collect();
+
+        create_dir_all(&self.path)?;
+        let storage = MmapMapStorage {
+            value_offsets: MmapArray::create(
+                &self.path.join(VALUE_OFFSETS_FILE),
+                offsets(value_points.iter().map(|points| points.len())),
+            )?,
+            value_points: MmapArray::create(
+                &self.path.join(VALUE_POINTS_FILE),
+                value_points
+                    .iter()
+                    .flat_map(|points| points.iter().copied()),
+            )?,
+            point_offsets: MmapArray::create(
+                &self.path.join(POINT_OFFSETS_FILE),
+                offsets(point_to_values.iter().map(|values| values.len())),
+            )?,
+            point_values: MmapArray::create(
+                &self.path.join(POINT_VALUES_FILE),
+                point_to_values
+                    .iter()
+                    .flat_map(|values| values.iter().map(|value| value_to_id[value])),
+            )?,
+        };
+
+        self.value_points_count = value_points
+            .iter()
+            .map(|points| points.len() as u32)
+            .collect();
+        self.values = values;
+        self.value_to_id = value_to_id;
+        self.sorted_values = sorted_values;
+        self.deleted = BitVec::repeat(false, point_to_values.len());
+        self.storage = Some(storage);
+        self.indexed_points = indexed_points;
+        self.values_count = values_count;
+
+        Ok(true)
+    }
+
+    /// Remove memory mapped files of the index
+    pub fn remove_files(&mut self) -> OperationResult<()> {
+        self.storage = None;
+        if self.path.exists() {
+            remove_dir_all(&self.path)?;
+        }
+        Ok(())
+    }
+
+    fn point_values_range(&self, idx: PointOffsetType) -> Option<Range<usize>> {
+        let storage = self.storage.as_ref()?;
+        let offsets = storage.point_offsets.as_slice();
+        let idx = idx as usize;
+        if idx + 1 >= offsets.len() {
+            return None;
+        }
+        if self.deleted[idx] {
+            return Some(0..0);
+        }
+        Some(offsets[idx] as usize..offsets[idx + 1] as usize)
+    }
+
+    pub fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
+        let Some(removed_values_range) = self.point_values_range(idx) else {
+            return Ok(());
+        };
+        self.deleted.set(idx as usize, true);
+
+        if !removed_values_range.is_empty() {
+            self.indexed_points -= 1;
+        }
+        self.values_count -= removed_values_range.len();
+
+        let Some(storage) = &self.storage else {
+            return Ok(());
+        };
+        for &value_id in &storage.point_values.as_slice()[removed_values_range] {
+            let value = &self.values[value_id as usize];
+            let points_count = &mut self.value_points_count[value_id as usize];
+            *points_count -= 1;
+            if *points_count == 0 {
+                self.sorted_values.remove(value);
+            }
+            // update db
+            let key = MapIndex::encode_db_record(value, idx);
+            self.db_wrapper.remove(key)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_values(&self, idx: PointOffsetType) -> Option<impl Iterator<Item = &N> + '_> {
+        let range = self.point_values_range(idx)?;
+        let point_values = self.storage.as_ref()?.point_values.as_slice();
+        Some(
+            point_values[range]
+                .iter()
+                .map(|&value_id| &self.values[value_id as usize]),
+        )
+    }
+
+    pub fn values_count(&self, idx: PointOffsetType) -> usize {
+        self.point_values_range(idx).map_or(0, |range| range.len())
+    }
+
+    pub fn get_indexed_points(&self) -> usize {
+        self.indexed_points
+    }
+
+    pub fn get_values_count(&self) -> usize {
+        self.values_count
+    }
+
+    pub fn get_unique_values_count(&self) -> usize {
+        self.sorted_values.len()
+    }
+
+    pub fn get_points_with_value_count<Q>(&self, value: &Q) -> Option<usize>
+    where
+        Q: ?Sized,
+        N: std::borrow::Borrow<Q>,
+        Q: Hash + Eq,
+    {
+        self.value_to_id
+            .get(value)
+            .map(|&value_id| self.value_points_count[value_id as usize] as usize)
+    }
+
+    pub fn get_iterator<Q>(&self, value: &Q) -> Box<dyn Iterator<Item = PointOffsetType> + '_>
+    where
+        Q: ?Sized,
+        N: std::borrow::Borrow<Q>,
+        Q: Hash + Eq,
+    {
+        match (self.value_to_id.get(value), &self.storage) {
+            (Some(&value_id), Some(storage)) => {
+                let offsets = storage.value_offsets.as_slice();
+                let range =
+                    offsets[value_id as usize] as usize..offsets[value_id as usize + 1] as usize;
+                Box::new(
+                    storage.value_points.as_slice()[range]
+                        .iter()
+                        .copied()
+                        .filter(|&idx| !self.deleted[idx as usize]),
+                )
+            }
+            _ => Box::new(iter::empty::<PointOffsetType>()),
+        }
+    }
+
+    pub fn get_values_iterator(&self) -> Box<dyn Iterator<Item = &N> + '_> {
+        Box::new(self.sorted_values.iter())
+    }
+
+    pub fn get_sorted_values(&self) -> &BTreeSet<N> {
+        &self.sorted_values
+    }
+}
+
+/// Convert lengths of consecutive lists into offsets of the lists, with one extra offset at the end
+fn offsets(lengths: impl Iterator<Item = usize>) -> impl Iterator<Item = u32> {
+    iter::once(0).chain(lengths.scan(0u32, |offset, len| {
+        *offset += len as u32;
+        Some(*offset)
+    }))
+}
@@ -1,21 +1,28 @@
 pub mod immutable_map_index;
+pub mod mmap_map_index;
 pub mod mutable_map_index;
 
+use std::collections::{BTreeSet, HashMap};
 use std::fmt::Display;
 use std::hash::Hash;
+use std::ops::Bound;
+use std::path::Path;
 use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
 use immutable_map_index::ImmutableMapIndex;
 use itertools::Itertools;
+use mmap_map_index::MmapMapIndex;
 use mutable_map_index::MutableMapIndex;
 use parking_lot::RwLock;
 use rocksdb::DB;
 use serde_json::Value;
 use smol_str::SmolStr;
+use uuid::Uuid;
 
-use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::common::Flusher;
 use crate::index::field_index::stat_tools::number_of_selected_points;
@@ -25,16 +32,17 @@ use crate::index::field_index::{
 use crate::index::query_estimator::combine_should_estimations;
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    AnyVariants, FieldCondition, IntPayloadType, Match, MatchAny, MatchExcept, MatchValue,
-    PayloadKeyType, ValueVariants,
+    parse_uuid, AnyVariants, FieldCondition, IntPayloadType, Match, MatchAny, MatchExcept,
+    MatchPrefix, MatchValue, PayloadKeyType, UuidIntType, ValueVariants,
 };
 
-pub enum MapIndex<N: Hash + Eq + Clone + Display + FromStr> {
+pub enum MapIndex<N: Hash + Eq + Clone + Display + FromStr + Ord> {
     Mutable(MutableMapIndex<N>),
     Immutable(ImmutableMapIndex<N>),
+    Mmap(MmapMapIndex<N>),
 }
 
-impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
+impl<N: Hash + Eq + Clone + Display + FromStr + Ord + Default> MapIndex<N> {
     pub fn new(db: Arc<RwLock<DB>>, field_name: &str, is_appendable: bool) -> Self {
         if is_appendable {
             MapIndex::Mutable(MutableMapIndex::new(db, field_name))
@@ -43,10 +51,16 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         }
     }
 
+    /// Create immutable index, which keeps point lists in memory mapped files under `path`
+    pub fn new_mmap(db: Arc<RwLock<DB>>, field_name: &str, path: &Path) -> Self {
+        MapIndex::Mmap(MmapMapIndex::new(db, field_name, path))
+    }
+
     fn get_db_wrapper(&self) -> &DatabaseColumnWrapper {
         match self {
             MapIndex::Mutable(index) => index.get_db_wrapper(),
             MapIndex::Immutable(index) => index.get_db_wrapper(),
+            MapIndex::Mmap(index) => index.get_db_wrapper(),
         }
     }
 
@@ -54,13 +68,30 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.load_from_db(),
             MapIndex::Immutable(index) => index.load_from_db(),
+            MapIndex::Mmap(index) => index.load_from_db(),
         }
     }
 
-    pub fn get_values(&self, idx: PointOffsetType) -> Option<&[N]> {
+    pub fn get_values(&self, idx: PointOffsetType) -> Option<Box<dyn Iterator<Item = &N> + '_>> {
         match self {
-            MapIndex::Mutable(index) => index.get_values(idx),
-            MapIndex::Immutable(index) => index.get_values(idx),
+            MapIndex::Mutable(index) => Some(Box::new(index.get_values(idx)?.iter())),
+            MapIndex::Immutable(index) => Some(Box::new(index.get_values(idx)?.iter())),
+            MapIndex::Mmap(index) => Some(Box::new(index.get_values(idx)?)),
+        }
+    }
+
+    /// Check if any value of the point satisfies `check_fn`
+    pub fn check_values_any(&self, idx: PointOffsetType, check_fn: impl Fn(&N) -> bool) -> bool {
+        match self {
+            MapIndex::Mutable(index) => index
+                .get_values(idx)
+                .map_or(false, |values| values.iter().any(check_fn)),
+            MapIndex::Immutable(index) => index
+                .get_values(idx)
+                .map_or(false, |values| values.iter().any(check_fn)),
+            MapIndex::Mmap(index) => index
+                .get_values(idx)
+                .map_or(false, |mut values| values.any(check_fn)),
         }
     }
 
@@ -68,6 +99,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.get_indexed_points(),
             MapIndex::Immutable(index) => index.get_indexed_points(),
+            MapIndex::Mmap(index) => index.get_indexed_points(),
         }
     }
 
@@ -75,6 +107,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.get_values_count(),
             MapIndex::Immutable(index) => index.get_values_count(),
+            MapIndex::Mmap(index) => index.get_values_count(),
         }
     }
 
@@ -82,6 +115,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.get_unique_values_count(),
             MapIndex::Immutable(index) => index.get_unique_values_count(),
+            MapIndex::Mmap(index) => index.get_unique_values_count(),
         }
     }
 
@@ -94,6 +128,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.get_points_with_value_count(value),
             MapIndex::Immutable(index) => index.get_points_with_value_count(value),
+            MapIndex::Mmap(index) => index.get_points_with_value_count(value),
         }
     }
 
@@ -106,6 +141,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.get_iterator(value),
             MapIndex::Immutable(index) => index.get_iterator(value),
+            MapIndex::Mmap(index) => index.get_iterator(value),
         }
     }
 
@@ -113,6 +149,15 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.get_values_iterator(),
             MapIndex::Immutable(index) => index.get_values_iterator(),
+            MapIndex::Mmap(index) => index.get_values_iterator(),
+        }
+    }
+
+    fn get_sorted_values(&self) -> &BTreeSet<N> {
+        match self {
+            MapIndex::Mutable(index) => index.get_sorted_values(),
+            MapIndex::Immutable(index) => index.get_sorted_values(),
+            MapIndex::Mmap(index) => index.get_sorted_values(),
         }
     }
 
@@ -128,6 +173,13 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         self.get_db_wrapper().flusher()
     }
 
+    fn clear_storage(mut self) -> OperationResult<()> {
+        if let MapIndex::Mmap(index) = &mut self {
+            index.remove_files()?;
+        }
+        self.get_db_wrapper().recreate_column_family()
+    }
+
     fn match_cardinality<Q>(&self, value: &Q) -> CardinalityEstimation
     where
         Q: ?Sized,
@@ -170,13 +222,46 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
     }
 
     pub fn values_count(&self, point_id: PointOffsetType) -> usize {
-        self.get_values(point_id).map(|x| x.len()).unwrap_or(0)
+        match self {
+            MapIndex::Mutable(index) => index.get_values(point_id).map_or(0, |x| x.len()),
+            MapIndex::Immutable(index) => index.get_values(point_id).map_or(0, |x| x.len()),
+            MapIndex::Mmap(index) => index.values_count(point_id),
+        }
     }
 
     pub fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
-        self.get_values(point_id)
-            .map(|x| x.is_empty())
-            .unwrap_or(true)
+        self.values_count(point_id) == 0
+    }
+
+    /// Number of points with each value.
+    ///
+    /// If `points` are given, only these points are counted.
+    pub fn count_per_value(
+        &self,
+        points: Option<&[PointOffsetType]>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HashMap<&N, usize>> {
+        let mut counts = HashMap::new();
+        match points {
+            None => {
+                for value in self.get_values_iterator() {
+                    check_process_stopped(is_stopped)?;
+                    let count = self.get_points_with_value_count(value).unwrap_or(0);
+                    if count > 0 {
+                        counts.insert(value, count);
+                    }
+                }
+            }
+            Some(points) => {
+                for &point in points {
+                    check_process_stopped(is_stopped)?;
+                    for value in self.get_values(point).into_iter().flatten() {
+                        *counts.entry(value).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+        Ok(counts)
     }
 
     /// Estimates cardinality for `except` clause
@@ -307,6 +392,30 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
     }
 }
 
+impl MapIndex<SmolStr> {
+    /// Iterate over indexed values, which start with the given prefix
+    fn get_prefix_values_iterator<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Iterator<Item = &'a SmolStr> + 'a {
+        self.get_sorted_values()
+            .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            .take_while(move |value| value.starts_with(prefix))
+    }
+
+    fn prefix_cardinality(&self, prefix: &str) -> CardinalityEstimation {
+        let estimations = self
+            .get_prefix_values_iterator(prefix)
+            .map(|value| self.match_cardinality(value.as_str()))
+            .collect::<Vec<_>>();
+        if estimations.is_empty() {
+            CardinalityEstimation::exact(0)
+        } else {
+            combine_should_estimations(&estimations, self.get_indexed_points())
+        }
+    }
+}
+
 impl PayloadFieldIndex for MapIndex<SmolStr> {
     fn count_indexed_points(&self) -> usize {
         self.get_indexed_points()
@@ -317,7 +426,7 @@ impl PayloadFieldIndex for MapIndex<SmolStr> {
     }
 
     fn clear(self) -> OperationResult<()> {
-        self.get_db_wrapper().recreate_column_family()
+        self.clear_storage()
     }
 
     fn flusher(&self) -> Flusher {
@@ -352,6 +461,11 @@ impl PayloadFieldIndex for MapIndex<SmolStr> {
             Some(Match::Except(MatchExcept {
                 except: AnyVariants::Keywords(keywords),
             })) => Ok(self.except_iterator(keywords)),
+            Some(Match::Prefix(MatchPrefix { prefix })) => Ok(Box::new(
+                self.get_prefix_values_iterator(prefix)
+                    .flat_map(|value| self.get_iterator(value.as_str()))
+                    .unique(),
+            )),
             _ => Err(OperationError::service_error("failed to filter")),
         }
     }
@@ -398,6 +512,9 @@ impl PayloadFieldIndex for MapIndex<SmolStr> {
             Some(Match::Except(MatchExcept {
                 except: AnyVariants::Keywords(keywords),
             })) => Ok(self.except_cardinality::<str, &str>(keywords.iter().map(|k| k.as_str()))),
+            Some(Match::Prefix(MatchPrefix { prefix })) => Ok(self
+                .prefix_cardinality(prefix)
+                .with_primary_clause(PrimaryCondition::Condition(condition.clone()))),
             _ => Err(OperationError::service_error(
                 "failed to estimate cardinality",
             )),
@@ -431,7 +548,7 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
     }
 
     fn clear(self) -> OperationResult<()> {
-        self.get_db_wrapper().recreate_column_family()
+        self.clear_storage()
     }
 
     fn flusher(&self) -> Flusher {
@@ -538,11 +655,127 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
     }
 }
 
+impl PayloadFieldIndex for MapIndex<UuidIntType> {
+    fn count_indexed_points(&self) -> usize {
+        self.get_indexed_points()
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        self.load_from_db()
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        self.clear_storage()
+    }
+
+    fn flusher(&self) -> Flusher {
+        MapIndex::flusher(self)
+    }
+
+    fn filter<'a>(
+        &'a self,
+        condition: &'a FieldCondition,
+    ) -> OperationResult<Box<dyn Iterator<Item = PointOffsetType> + 'a>> {
+        match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Keyword(keyword),
+            })) => match parse_uuid(keyword) {
+                Some(uuid) => Ok(self.get_iterator(&uuid)),
+                None => Ok(Box::new(vec![].into_iter())),
+            },
+            Some(Match::Any(MatchAny { any: any_variant })) => match any_variant {
+                AnyVariants::Keywords(keywords) => Ok(Box::new(
+                    keywords
+                        .iter()
+                        .filter_map(|keyword| parse_uuid(keyword))
+                        .unique()
+                        .flat_map(|uuid| self.get_iterator(&uuid))
+                        .unique(),
+                )),
+                AnyVariants::Integers(integers) => {
+                    if integers.is_empty() {
+                        Ok(Box::new(vec![].into_iter()))
+                    } else {
+                        Err(OperationError::service_error("failed to filter"))
+                    }
+                }
+            },
+            _ => Err(OperationError::service_error("failed to filter")),
+        }
+    }
+
+    fn estimate_cardinality(
+        &self,
+        condition: &FieldCondition,
+    ) -> OperationResult<CardinalityEstimation> {
+        match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Keyword(keyword),
+            })) => {
+                let estimation = match parse_uuid(keyword) {
+                    Some(uuid) => self.match_cardinality(&uuid),
+                    None => CardinalityEstimation::exact(0),
+                };
+                Ok(estimation.with_primary_clause(PrimaryCondition::Condition(condition.clone())))
+            }
+            Some(Match::Any(MatchAny { any: any_variant })) => match any_variant {
+                AnyVariants::Keywords(keywords) => {
+                    let estimations = keywords
+                        .iter()
+                        .filter_map(|keyword| parse_uuid(keyword))
+                        .unique()
+                        .map(|uuid| self.match_cardinality(&uuid))
+                        .collect::<Vec<_>>();
+                    let estimation = if estimations.is_empty() {
+                        CardinalityEstimation::exact(0)
+                    } else {
+                        combine_should_estimations(&estimations, self.get_indexed_points())
+                    };
+                    Ok(estimation
+                        .with_primary_clause(PrimaryCondition::Condition(condition.clone())))
+                }
+                AnyVariants::Integers(integers) => {
+                    if integers.is_empty() {
+                        Ok(CardinalityEstimation::exact(0)
+                            .with_primary_clause(PrimaryCondition::Condition(condition.clone())))
+                    } else {
+                        Err(OperationError::service_error(
+                            "failed to estimate cardinality",
+                        ))
+                    }
+                }
+            },
+            _ => Err(OperationError::service_error(
+                "failed to estimate cardinality",
+            )),
+        }
+    }
+
+    fn payload_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        Box::new(
+            self.get_values_iterator()
+                .map(|value| (value, self.get_points_with_value_count(value).unwrap_or(0)))
+                .filter(move |(_value, count)| *count >= threshold)
+                .map(move |(value, count)| PayloadBlockCondition {
+                    condition: FieldCondition::new_match(
+                        key.clone(),
+                        Uuid::from_u128(*value).to_string().into(),
+                    ),
+                    cardinality: count,
+                }),
+        )
+    }
+}
+
 impl ValueIndexer<String> for MapIndex<SmolStr> {
     fn add_many(&mut self, id: PointOffsetType, values: Vec<String>) -> OperationResult<()> {
         match self {
             MapIndex::Mutable(index) => index.add_many_to_map(id, values),
-            MapIndex::Immutable(_) => Err(OperationError::service_error(
+            MapIndex::Immutable(_) | MapIndex::Mmap(_) => Err(OperationError::service_error(
                 "Can't add values to immutable map index",
             )),
         }
@@ -559,6 +792,7 @@ impl ValueIndexer<String> for MapIndex<SmolStr> {
         match self {
             MapIndex::Mutable(index) => index.remove_point(id),
             MapIndex::Immutable(index) => index.remove_point(id),
+            MapIndex::Mmap(index) => index.remove_point(id),
         }
     }
 }
@@ -571,7 +805,7 @@ impl ValueIndexer<IntPayloadType> for MapIndex<IntPayloadType> {
     ) -> OperationResult<()> {
         match self {
             MapIndex::Mutable(index) => index.add_many_to_map(id, values),
-            MapIndex::Immutable(_) => Err(OperationError::service_error(
+            MapIndex::Immutable(_) | MapIndex::Mmap(_) => Err(OperationError::service_error(
                 "Can't add values to immutable map index",
             )),
         }
@@ -588,6 +822,33 @@ impl ValueIndexer<IntPayloadType> for MapIndex<IntPayloadType> {
         match self {
             MapIndex::Mutable(index) => index.remove_point(id),
             MapIndex::Immutable(index) => index.remove_point(id),
+            MapIndex::Mmap(index) => index.remove_point(id),
+        }
+    }
+}
+
+impl ValueIndexer<UuidIntType> for MapIndex<UuidIntType> {
+    fn add_many(&mut self, id: PointOffsetType, values: Vec<UuidIntType>) -> OperationResult<()> {
+        match self {
+            MapIndex::Mutable(index) => index.add_many_to_map(id, values),
+            MapIndex::Immutable(_) | MapIndex::Mmap(_) => Err(OperationError::service_error(
+                "Can't add values to immutable map index",
+            )),
+        }
+    }
+
+    fn get_value(&self, value: &Value) -> Option<UuidIntType> {
+        if let Value::String(keyword) = value {
+            return parse_uuid(keyword);
+        }
+        None
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        match self {
+            MapIndex::Mutable(index) => index.remove_point(id),
+            MapIndex::Immutable(index) => index.remove_point(id),
+            MapIndex::Mmap(index) => index.remove_point(id),
         }
     }
 }
@@ -606,7 +867,7 @@ mod tests {
 
     const FIELD_NAME: &str = "test";
 
-    fn save_map_index<N: Hash + Eq + Clone + Display + FromStr + Debug + Default>(
+    fn save_map_index<N: Hash + Eq + Clone + Display + FromStr + Ord + Debug + Default>(
         data: &[Vec<N>],
         path: &Path,
     ) {
@@ -624,7 +885,7 @@ mod tests {
         index.flusher()().unwrap();
     }
 
-    fn load_map_index<N: Hash + Eq + Clone + Display + FromStr + Debug + Default>(
+    fn load_map_index<N: Hash + Eq + Clone + Display + FromStr + Ord + Debug + Default>(
         data: &[Vec<N>],
         path: &Path,
     ) -> MapIndex<N> {
@@ -632,13 +893,8 @@ mod tests {
             MapIndex::<N>::new(open_db_with_existing_cf(path).unwrap(), FIELD_NAME, true);
         index.load_from_db().unwrap();
         for (idx, values) in data.iter().enumerate() {
-            let index_values: HashSet<N> = HashSet::from_iter(
-                index
-                    .get_values(idx as PointOffsetType)
-                    .unwrap()
-                    .iter()
-                    .cloned(),
-            );
+            let index_values: HashSet<N> =
+                HashSet::from_iter(index.get_values(idx as PointOffsetType).unwrap().cloned());
             let check_values: HashSet<N> = HashSet::from_iter(values.iter().cloned());
             assert_eq!(index_values, check_values);
         }
@@ -666,6 +922,23 @@ mod tests {
             .equals_min_exp_max(&CardinalityEstimation::exact(0)));
     }
 
+    #[test]
+    fn test_count_per_value() {
+        let data = vec![vec![1, 2], vec![1], vec![2, 3], vec![1]];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index(&data, temp_dir.path());
+        let mut index = load_map_index(&data, temp_dir.path());
+        index.remove_point(3).unwrap();
+
+        let is_stopped = AtomicBool::new(false);
+        let counts = index.count_per_value(None, &is_stopped).unwrap();
+        assert_eq!(counts, HashMap::from([(&1, 2), (&2, 2), (&3, 1)]));
+
+        let counts = index.count_per_value(Some(&[0, 2]), &is_stopped).unwrap();
+        assert_eq!(counts, HashMap::from([(&1, 1), (&2, 2), (&3, 1)]));
+    }
+
     #[test]
     fn test_string_disk_map_index() {
         let data = vec![
@@ -702,6 +975,81 @@ mod tests {
             .equals_min_exp_max(&CardinalityEstimation::exact(0)));
     }
 
+    #[test]
+    fn test_prefix_match() {
+        let data = vec![
+            vec![String::from("qdrant"), String::from("rust")],
+            vec![String::from("qdr")],
+            vec![String::from("quadrant")],
+            vec![String::from("qd")],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index(&data, temp_dir.path());
+
+        let mut index = MapIndex::<SmolStr>::new(
+            open_db_with_existing_cf(temp_dir.path()).unwrap(),
+            FIELD_NAME,
+            false,
+        );
+        index.load_from_db().unwrap();
+
+        let condition = FieldCondition::new_match(
+            FIELD_NAME,
+            Match::Prefix(MatchPrefix {
+                prefix: "qdr".to_owned(),
+            }),
+        );
+
+        let mut points: Vec<_> = index.filter(&condition).unwrap().collect();
+        points.sort_unstable();
+        assert_eq!(points, vec![0, 1]);
+
+        let estimation = index.estimate_cardinality(&condition).unwrap();
+        assert!(estimation.min <= 2 && estimation.max >= 2);
+
+        index.remove_point(1).unwrap();
+        let points: Vec<_> = index.filter(&condition).unwrap().collect();
+        assert_eq!(points, vec![0]);
+    }
+
+    #[test]
+    fn test_uuid_match() {
+        let uuids = [
+            "a1a2a3a4-b1b2-c1c2-d1d2-d3d4d5d6d7d8",
+            "550e8400-e29b-41d4-a716-446655440000",
+        ];
+        let data = vec![
+            vec![parse_uuid(uuids[0]).unwrap()],
+            vec![parse_uuid(uuids[0]).unwrap(), parse_uuid(uuids[1]).unwrap()],
+            vec![parse_uuid(uuids[1]).unwrap()],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index(&data, temp_dir.path());
+        let index = load_map_index(&data, temp_dir.path());
+
+        // Match is independent of UUID string formatting
+        let condition =
+            FieldCondition::new_match(FIELD_NAME, uuids[0].to_uppercase().replace('-', "").into());
+        let mut points: Vec<_> = index.filter(&condition).unwrap().collect();
+        points.sort_unstable();
+        assert_eq!(points, vec![0, 1]);
+
+        let condition = FieldCondition::new_match(
+            FIELD_NAME,
+            Match::Any(MatchAny {
+                any: AnyVariants::Keywords(vec![uuids[1].to_owned(), "not-a-uuid".to_owned()]),
+            }),
+        );
+        let mut points: Vec<_> = index.filter(&condition).unwrap().collect();
+        points.sort_unstable();
+        assert_eq!(points, vec![1, 2]);
+
+        let estimation = index.estimate_cardinality(&condition).unwrap();
+        assert!(estimation.min <= 2 && estimation.max >= 2);
+    }
+
     #[test]
     fn test_empty_index() {
         let data: Vec<Vec<String>> = vec![];
@@ -715,4 +1063,55 @@ mod tests {
             .except_cardinality::<str, &str>(vec![].into_iter())
             .equals_min_exp_max(&CardinalityEstimation::exact(0)));
     }
+
+    #[test]
+    fn test_mmap_map_index() {
+        let data = vec![
+            vec![String::from("tenant_a")],
+            vec![String::from("tenant_b"), String::from("tenant_a")],
+            vec![],
+            vec![String::from("tenant_b")],
+            vec![String::from("tenant_c")],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index(&data, temp_dir.path());
+
+        let mmap_dir = Builder::new().prefix("mmap_dir").tempdir().unwrap();
+        let mut index = MapIndex::<SmolStr>::new_mmap(
+            open_db_with_existing_cf(temp_dir.path()).unwrap(),
+            FIELD_NAME,
+            mmap_dir.path(),
+        );
+        assert!(index.load().unwrap());
+        assert_eq!(index.count_indexed_points(), 4);
+        assert_eq!(index.values_count(1), 2);
+        assert!(index.values_is_empty(2));
+        assert!(index.check_values_any(3, |value| value == "tenant_b"));
+
+        let condition = FieldCondition::new_match(FIELD_NAME, "tenant_a".to_owned().into());
+        let mut points: Vec<_> = index.filter(&condition).unwrap().collect();
+        points.sort_unstable();
+        assert_eq!(points, vec![0, 1]);
+
+        index.remove_point(1).unwrap();
+        index.remove_point(4).unwrap();
+        let points: Vec<_> = index.filter(&condition).unwrap().collect();
+        assert_eq!(points, vec![0]);
+        assert_eq!(index.count_indexed_points(), 2);
+        assert!(index.values_is_empty(1));
+        assert_eq!(index.get_unique_values_count(), 2);
+        assert_eq!(index.estimate_cardinality(&condition).unwrap().exp, 1,);
+        index.flusher()().unwrap();
+        drop(index);
+
+        // Removed points are removed from the db as well
+        let mut index = MapIndex::<SmolStr>::new_mmap(
+            open_db_with_existing_cf(temp_dir.path()).unwrap(),
+            FIELD_NAME,
+            mmap_dir.path(),
+        );
+        assert!(index.load().unwrap());
+        assert_eq!(index.count_indexed_points(), 2);
+    }
 }
@@ -1,3 +1,4 @@
+use std::collections::hash_map::Entry;
 use std::collections::{BTreeSet, HashMap};
 use std::fmt::Display;
 use std::hash::Hash;
@@ -13,8 +14,10 @@ use super::MapIndex;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 
-pub struct MutableMapIndex<N: Hash + Eq + Clone + Display + FromStr> {
+pub struct MutableMapIndex<N: Hash + Eq + Clone + Display + FromStr + Ord> {
     pub(super) map: HashMap<N, BTreeSet<PointOffsetType>>,
+    /// Keys of the `map` in sorted order, used for range lookups
+    pub(super) sorted_values: BTreeSet<N>,
     pub(super) point_to_values: Vec<Vec<N>>,
     /// Amount of point which have at least one indexed payload value
     pub(super) indexed_points: usize,
@@ -22,12 +25,13 @@ pub struct MutableMapIndex<N: Hash + Eq + Clone + Display + FromStr> {
     pub(super) db_wrapper: DatabaseColumnWrapper,
 }
 
-impl<N: Hash + Eq + Clone + Display + FromStr + Default> MutableMapIndex<N> {
+impl<N: Hash + Eq + Clone + Display + FromStr + Ord + Default> MutableMapIndex<N> {
     pub fn new(db: Arc<RwLock<DB>>, field_name: &str) -> Self {
         let store_cf_name = MapIndex::<N>::storage_cf_name(field_name);
         let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
         Self {
             map: Default::default(),
+            sorted_values: Default::default(),
             point_to_values: Vec::new(),
             indexed_points: 0,
             values_count: 0,
@@ -55,6 +59,9 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MutableMapIndex<N> {
         self.point_to_values[idx as usize] = Vec::with_capacity(values.len());
         for value in values {
             let entry = self.map.entry(value.into());
+            if let Entry::Vacant(vacant) = &entry {
+                self.sorted_values.insert(vacant.key().clone());
+            }
             self.point_to_values[idx as usize].push(entry.key().clone());
             let db_record = MapIndex::encode_db_record(entry.key(), idx);
             entry.or_default().insert(idx);
@@ -110,6 +117,9 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MutableMapIndex<N> {
             self.values_count += 1;
 
             let entry = self.map.entry(value);
+            if let Entry::Vacant(vacant) = &entry {
+                self.sorted_values.insert(vacant.key().clone());
+            }
             self.point_to_values[idx as usize].push(entry.key().clone());
             entry.or_default().insert(idx);
         }
@@ -156,4 +166,8 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MutableMapIndex<N> {
     pub fn get_values_iterator(&self) -> Box<dyn Iterator<Item = &N> + '_> {
         Box::new(self.map.keys())
     }
+
+    pub fn get_sorted_values(&self) -> &BTreeSet<N> {
+        &self.sorted_values
+    }
 }
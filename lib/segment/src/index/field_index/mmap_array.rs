@@ -0,0 +1,48 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+
+use memmap2::Mmap;
+use memory::mmap_ops;
+
+use crate::common::operation_error::OperationResult;
+
+/// Read-only array of plain values in a memory mapped file.
+///
+/// Used by on-disk payload indexes: the file is written once, when the index is loaded,
+/// and afterwards values are only read through the page cache.
+pub struct MmapArray<T: Copy> {
+    mmap: Mmap,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Copy> MmapArray<T> {
+    /// Write `values` into the file at `path`, replacing its content, and map the file into memory
+    pub fn create(path: &Path, values: impl IntoIterator<Item = T>) -> OperationResult<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        for value in values {
+            writer.write_all(mmap_ops::transmute_to_u8(&value))?;
+        }
+        writer.flush()?;
+        drop(writer);
+
+        let mmap = mmap_ops::open_read_mmap(path)?;
+        Ok(Self {
+            mmap,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        mmap_ops::transmute_from_u8_to_slice(&self.mmap)
+    }
+
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
+    }
+}
@@ -2,8 +2,9 @@ use std::collections::HashSet;
 
 use common::types::PointOffsetType;
 
-use crate::types::{FieldCondition, IsEmptyCondition, IsNullCondition};
+use crate::types::{FieldCondition, IdRange, IsEmptyCondition, IsNullCondition};
 
+pub mod datetime_index;
 mod field_index_base;
 pub mod full_text_index;
 pub mod geo_hash;
@@ -11,6 +12,8 @@ pub mod geo_index;
 mod histogram;
 pub mod index_selector;
 pub mod map_index;
+mod mmap_array;
+pub mod null_index;
 pub mod numeric_index;
 mod stat_tools;
 
@@ -28,6 +31,7 @@ pub enum PrimaryCondition {
     IsEmpty(IsEmptyCondition),
     IsNull(IsNullCondition),
     Ids(HashSet<PointOffsetType>),
+    IdRange(IdRange),
 }
 
 #[derive(Debug, Clone)]
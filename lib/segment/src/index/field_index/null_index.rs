@@ -0,0 +1,306 @@
+use std::sync::Arc;
+
+use common::types::PointOffsetType;
+use parking_lot::RwLock;
+use rocksdb::DB;
+use serde_json::Value;
+
+use super::{CardinalityEstimation, FieldIndex, PayloadBlockCondition, PayloadFieldIndex};
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::common::utils::MultiValue;
+use crate::common::Flusher;
+use crate::telemetry::PayloadIndexTelemetry;
+use crate::types::{FieldCondition, PayloadKeyType, ValuesCount};
+
+/// Field has at least one non-empty value
+const HAS_VALUES: u8 = 0b0000_0001;
+/// Field is null
+const IS_NULL: u8 = 0b0000_0010;
+/// Field is a single payload value, its number of values is known
+const IS_SINGLE: u8 = 0b0000_0100;
+/// Field path selects multiple payload values, e.g. `a[].b`
+const IS_MULTIPLE: u8 = 0b0000_1000;
+
+/// Tracks which points have non-empty or null values of the field, and how many values they have.
+///
+/// Used to answer `is_empty`, `is_null` and `values_count` conditions without reading payload storage.
+/// Points, which were never added to the index, are considered empty and not null.
+pub struct NullIndex {
+    flags: Vec<u8>,
+    values_counts: Vec<u32>,
+    has_values_count: usize,
+    is_null_count: usize,
+    db_wrapper: DatabaseColumnWrapper,
+}
+
+impl NullIndex {
+    pub fn new(db: Arc<RwLock<DB>>, field_name: &str) -> Self {
+        let store_cf_name = Self::storage_cf_name(field_name);
+        let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
+        Self {
+            flags: Vec::new(),
+            values_counts: Vec::new(),
+            has_values_count: 0,
+            is_null_count: 0,
+            db_wrapper,
+        }
+    }
+
+    /// Select null index among all indexes of the field
+    pub fn find(indexes: &[FieldIndex]) -> Option<&NullIndex> {
+        indexes.iter().find_map(|index| match index {
+            FieldIndex::NullIndex(null_index) => Some(null_index),
+            _ => None,
+        })
+    }
+
+    fn storage_cf_name(field: &str) -> String {
+        format!("{field}_null")
+    }
+
+    pub fn recreate(&self) -> OperationResult<()> {
+        self.db_wrapper.recreate_column_family()
+    }
+
+    fn set(&mut self, id: PointOffsetType, flags: u8, values_count: u32) {
+        let idx = id as usize;
+        if idx >= self.flags.len() {
+            if flags == 0 {
+                return;
+            }
+            self.flags.resize(idx + 1, 0);
+            self.values_counts.resize(idx + 1, 0);
+        }
+
+        let old_flags = std::mem::replace(&mut self.flags[idx], flags);
+        self.values_counts[idx] = values_count;
+
+        match (old_flags & HAS_VALUES != 0, flags & HAS_VALUES != 0) {
+            (false, true) => self.has_values_count += 1,
+            (true, false) => self.has_values_count -= 1,
+            _ => {}
+        }
+
+        match (old_flags & IS_NULL != 0, flags & IS_NULL != 0) {
+            (false, true) => self.is_null_count += 1,
+            (true, false) => self.is_null_count -= 1,
+            _ => {}
+        }
+    }
+
+    fn get_flags(&self, point_id: PointOffsetType) -> u8 {
+        self.flags.get(point_id as usize).copied().unwrap_or(0)
+    }
+
+    pub fn add_point(
+        &mut self,
+        id: PointOffsetType,
+        payload: &MultiValue<&Value>,
+    ) -> OperationResult<()> {
+        let mut flags = 0;
+        if !payload.check_is_empty() {
+            flags |= HAS_VALUES;
+        }
+        if payload.check_is_null() {
+            flags |= IS_NULL;
+        }
+
+        let mut values_count = 0;
+        match payload {
+            MultiValue::Single(Some(value)) => {
+                flags |= IS_SINGLE;
+                values_count = ValuesCount::count_values(value) as u32;
+            }
+            MultiValue::Multiple(values) if !values.is_empty() => flags |= IS_MULTIPLE,
+            _ => {}
+        }
+
+        self.set(id, flags, values_count);
+        if flags == 0 {
+            // Same as a point without the field at all
+            self.db_wrapper.remove(id.to_be_bytes())
+        } else {
+            let mut value = [0; 5];
+            value[0] = flags;
+            value[1..].copy_from_slice(&values_count.to_be_bytes());
+            self.db_wrapper.put(id.to_be_bytes(), value)
+        }
+    }
+
+    pub fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        self.set(id, 0, 0);
+        self.db_wrapper.remove(id.to_be_bytes())
+    }
+
+    /// Check if the field of the point is not empty
+    pub fn values_has_values(&self, point_id: PointOffsetType) -> bool {
+        self.get_flags(point_id) & HAS_VALUES != 0
+    }
+
+    /// Check if the field of the point is null
+    pub fn values_is_null(&self, point_id: PointOffsetType) -> bool {
+        self.get_flags(point_id) & IS_NULL != 0
+    }
+
+    /// Check if the field path selects multiple payload values of the point.
+    /// Number of values is not tracked for such points.
+    pub fn values_is_multiple(&self, point_id: PointOffsetType) -> bool {
+        self.get_flags(point_id) & IS_MULTIPLE != 0
+    }
+
+    /// Number of values of the field, as counted by `values_count` condition.
+    ///
+    /// Returns `None` if the point has no single value of the field.
+    pub fn get_values_count(&self, point_id: PointOffsetType) -> Option<usize> {
+        (self.get_flags(point_id) & IS_SINGLE != 0)
+            .then(|| self.values_counts[point_id as usize] as usize)
+    }
+
+    pub fn values_count(&self, point_id: PointOffsetType) -> usize {
+        usize::from(self.values_has_values(point_id))
+    }
+
+    pub fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
+        !self.values_has_values(point_id)
+    }
+
+    /// Number of points with non-empty field
+    pub fn has_values_count(&self) -> usize {
+        self.has_values_count
+    }
+
+    /// Number of points with null value
+    pub fn is_null_count(&self) -> usize {
+        self.is_null_count
+    }
+
+    pub fn iter_is_null(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
+        self.flags
+            .iter()
+            .enumerate()
+            .filter(|(_, flags)| *flags & IS_NULL != 0)
+            .map(|(idx, _)| idx as PointOffsetType)
+    }
+
+    pub fn get_telemetry_data(&self) -> PayloadIndexTelemetry {
+        PayloadIndexTelemetry {
+            field_name: None,
+            points_count: self.has_values_count,
+            points_values_count: self.has_values_count,
+            histogram_bucket_size: None,
+        }
+    }
+}
+
+impl PayloadFieldIndex for NullIndex {
+    fn count_indexed_points(&self) -> usize {
+        self.has_values_count
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        if !self.db_wrapper.has_column_family()? {
+            return Ok(false);
+        }
+
+        for (key, value) in self.db_wrapper.lock_db().iter()? {
+            let idx = PointOffsetType::from_be_bytes(key.as_ref().try_into().unwrap());
+
+            debug_assert_eq!(value.len(), 5);
+
+            let values_count = u32::from_be_bytes(value[1..5].try_into().unwrap());
+            self.set(idx, value[0], values_count);
+        }
+        Ok(true)
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        self.db_wrapper.remove_column_family()
+    }
+
+    fn flusher(&self) -> Flusher {
+        self.db_wrapper.flusher()
+    }
+
+    fn filter<'a>(
+        &'a self,
+        _condition: &'a FieldCondition,
+    ) -> OperationResult<Box<dyn Iterator<Item = PointOffsetType> + 'a>> {
+        Err(OperationError::service_error("failed to filter"))
+    }
+
+    fn estimate_cardinality(
+        &self,
+        _condition: &FieldCondition,
+    ) -> OperationResult<CardinalityEstimation> {
+        Err(OperationError::service_error(
+            "failed to estimate cardinality",
+        ))
+    }
+
+    fn payload_blocks(
+        &self,
+        _threshold: usize,
+        _key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        Box::new(std::iter::empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
+
+    #[test]
+    fn test_null_index_load() {
+        let tmp_dir = Builder::new().prefix("test_null_index").tempdir().unwrap();
+        let db = open_db_with_existing_cf(tmp_dir.path()).unwrap();
+
+        let mut index = NullIndex::new(db.clone(), "field");
+        index.recreate().unwrap();
+
+        let values = [
+            json!(1),
+            json!(null),
+            json!([]),
+            json!([null, 2]),
+            json!("value"),
+        ];
+        for (idx, value) in values.iter().enumerate() {
+            index
+                .add_point(idx as PointOffsetType, &MultiValue::one(value))
+                .unwrap();
+        }
+        index.remove_point(4).unwrap();
+
+        let multiple_values = [json!([1, 2]), json!(3)];
+        index
+            .add_point(5, &MultiValue::Multiple(multiple_values.iter().collect()))
+            .unwrap();
+        index.flusher()().unwrap();
+
+        let mut index = NullIndex::new(db, "field");
+        assert!(index.load().unwrap());
+
+        let has_values: Vec<_> = (0..7).map(|id| index.values_has_values(id)).collect();
+        assert_eq!(
+            has_values,
+            vec![true, false, false, true, false, true, false]
+        );
+        assert_eq!(index.has_values_count(), 3);
+
+        assert_eq!(index.iter_is_null().collect::<Vec<_>>(), vec![1]);
+        assert_eq!(index.is_null_count(), 1);
+
+        let values_counts: Vec<_> = (0..7).map(|id| index.get_values_count(id)).collect();
+        assert_eq!(
+            values_counts,
+            vec![Some(1), Some(0), Some(0), Some(2), None, None, None]
+        );
+        assert!(index.values_is_multiple(5));
+    }
+}
@@ -225,14 +225,15 @@ impl<T: Encodable + Numericable> ImmutableNumericIndex<T> {
         self.map.len()
     }
 
-    pub(super) fn values_range(
+    /// Values with their points in the given range, ordered by value
+    pub(super) fn orderable_values_range(
         &self,
         start_bound: Bound<NumericIndexKey<T>>,
         end_bound: Bound<NumericIndexKey<T>>,
-    ) -> impl Iterator<Item = PointOffsetType> + '_ {
+    ) -> impl DoubleEndedIterator<Item = (T, PointOffsetType)> + '_ {
         self.map
             .values_range(start_bound, end_bound)
-            .map(|NumericIndexKey { idx, .. }| idx)
+            .map(|NumericIndexKey { key, idx, .. }| (key, idx))
     }
 
     pub(super) fn load(&mut self) -> OperationResult<bool> {
@@ -0,0 +1,287 @@
+use std::fs::{create_dir_all, remove_dir_all};
+use std::ops::{Bound, Range};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use bitvec::vec::BitVec;
+use common::types::PointOffsetType;
+use parking_lot::RwLock;
+use rocksdb::DB;
+
+use super::immutable_numeric_index::NumericIndexKey;
+use super::mutable_numeric_index::MutableNumericIndex;
+use super::{Encodable, NumericIndex, HISTOGRAM_MAX_BUCKET_SIZE, HISTOGRAM_PRECISION};
+use crate::common::operation_error::OperationResult;
+use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::index::field_index::histogram::{Histogram, Numericable, Point};
+use crate::index::field_index::mmap_array::MmapArray;
+
+const PAIR_VALUES_FILE: &str = "pair_values.bin";
+const PAIR_POINTS_FILE: &str = "pair_points.bin";
+const POINT_OFFSETS_FILE: &str = "point_offsets.bin";
+const POINT_VALUES_FILE: &str = "point_values.bin";
+
+/// Memory mapped sorted list of (value, point) pairs and points-to-values map
+struct MmapNumericStorage<T: Copy> {
+    /// Values of the pairs, sorted in the order of [`NumericIndexKey`]
+    pair_values: MmapArray<T>,
+    /// Points of the pairs
+    pair_points: MmapArray<PointOffsetType>,
+    /// Offsets of the values of each point in `point_values`, with one extra offset at the end
+    point_offsets: MmapArray<u32>,
+    point_values: MmapArray<T>,
+}
+
+impl<T: Encodable + Numericable> MmapNumericStorage<T> {
+    fn pairs_len(&self) -> usize {
+        self.pair_values.len()
+    }
+
+    fn get_pair(&self, index: usize) -> NumericIndexKey<T> {
+        NumericIndexKey::new(
+            self.pair_values.as_slice()[index],
+            self.pair_points.as_slice()[index],
+        )
+    }
+
+    /// Position of the first pair, for which `pred` is false.
+    /// Pairs, for which `pred` is true, must precede all other pairs.
+    fn partition_point(&self, pred: impl Fn(&NumericIndexKey<T>) -> bool) -> usize {
+        let (mut low, mut high) = (0, self.pairs_len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if pred(&self.get_pair(mid)) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
+    fn find_start_index(&self, bound: &Bound<NumericIndexKey<T>>) -> usize {
+        match bound {
+            Bound::Included(bound) => self.partition_point(|key| key < bound),
+            Bound::Excluded(bound) => self.partition_point(|key| key <= bound),
+            Bound::Unbounded => 0,
+        }
+    }
+
+    fn find_end_index(&self, bound: &Bound<NumericIndexKey<T>>) -> usize {
+        match bound {
+            Bound::Included(bound) => self.partition_point(|key| key <= bound),
+            Bound::Excluded(bound) => self.partition_point(|key| key < bound),
+            Bound::Unbounded => self.pairs_len(),
+        }
+    }
+}
+
+/// Immutable numeric index, which keeps sorted values in memory mapped files.
+///
+/// Only the histogram and deletion flags are kept in RAM.
+/// RocksDB remains the source of truth: the files are rebuilt from it on each load.
+pub struct MmapNumericIndex<T: Encodable + Numericable> {
+    path: PathBuf,
+    storage: Option<MmapNumericStorage<T>>,
+    /// Deletion flags of the sorted pairs
+    deleted_pairs: BitVec,
+    deleted_pairs_count: usize,
+    deleted_points: BitVec,
+    db_wrapper: DatabaseColumnWrapper,
+    pub(super) histogram: Histogram<T>,
+    pub(super) points_count: usize,
+    pub(super) max_values_per_point: usize,
+}
+
+impl<T: Encodable + Numericable> MmapNumericIndex<T> {
+    pub(super) fn new(db: Arc<RwLock<DB>>, field: &str, path: &Path) -> Self {
+        let store_cf_name = NumericIndex::<T>::storage_cf_name(field);
+        let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
+        Self {
+            path: path.to_owned(),
+            storage: None,
+            deleted_pairs: Default::default(),
+            deleted_pairs_count: 0,
+            deleted_points: Default::default(),
+            db_wrapper,
+            histogram: Histogram::new(HISTOGRAM_MAX_BUCKET_SIZE, HISTOGRAM_PRECISION),
+            points_count: 0,
+            max_values_per_point: 1,
+        }
+    }
+
+    pub(super) fn get_db_wrapper(&self) -> &DatabaseColumnWrapper {
+        &self.db_wrapper
+    }
+
+    fn point_values_range(&self, idx: PointOffsetType) -> Option<Range<usize>> {
+        let offsets = self.storage.as_ref()?.point_offsets.as_slice();
+        let idx = idx as usize;
+        if idx + 1 >= offsets.len() {
+            return None;
+        }
+        if self.deleted_points[idx] {
+            return Some(0..0);
+        }
+        Some(offsets[idx] as usize..offsets[idx + 1] as usize)
+    }
+
+    pub(super) fn get_values(&self, idx: PointOffsetType) -> Option<&[T]> {
+        let range = self.point_values_range(idx)?;
+        Some(&self.storage.as_ref()?.point_values.as_slice()[range])
+    }
+
+    pub(super) fn get_values_count(&self) -> usize {
+        self.storage
+            .as_ref()
+            .map_or(0, |storage| storage.pairs_len() - self.deleted_pairs_count)
+    }
+
+    /// Values with their points in the given range, ordered by value
+    pub(super) fn orderable_values_range(
+        &self,
+        start_bound: Bound<NumericIndexKey<T>>,
+        end_bound: Bound<NumericIndexKey<T>>,
+    ) -> impl DoubleEndedIterator<Item = (T, PointOffsetType)> + '_ {
+        let (range, pair_values, pair_points) = match &self.storage {
+            Some(storage) => (
+                storage.find_start_index(&start_bound)..storage.find_end_index(&end_bound),
+                storage.pair_values.as_slice(),
+                storage.pair_points.as_slice(),
+            ),
+            None => (0..0, &[][..], &[][..]),
+        };
+        range
+            .filter(move |&index| !self.deleted_pairs[index])
+            .map(move |index| (pair_values[index], pair_points[index]))
+    }
+
+    pub(super) fn load(&mut self) -> OperationResult<bool> {
+        // Load data from db into `MutableNumericIndex` first and write it into memory mapped files
+        let mut mutable = MutableNumericIndex::<T> {
+            map: Default::default(),
+            db_wrapper: self.db_wrapper.clone(),
+            histogram: Histogram::new(HISTOGRAM_MAX_BUCKET_SIZE, HISTOGRAM_PRECISION),
+            points_count: 0,
+            max_values_per_point: 0,
+            point_to_values: Default::default(),
+        };
+        if !mutable.load()? {
+            return Ok(false);
+        }
+        let MutableNumericIndex {
+            map,
+            histogram,
+            points_count,
+            max_values_per_point,
+            point_to_values,
+            ..
+        } = mutable;
+
+        create_dir_all(&self.path)?;
+        let storage = MmapNumericStorage {
+            pair_values: MmapArray::create(
+                &self.path.join(PAIR_VALUES_FILE),
+                map.keys().map(|key| T::decode_key(key).1),
+            )?,
+            pair_points: MmapArray::create(
+                &self.path.join(PAIR_POINTS_FILE),
+                map.values().copied(),
+            )?,
+            point_offsets: MmapArray::create(
+                &self.path.join(POINT_OFFSETS_FILE),
+                std::iter::once(0).chain(point_to_values.iter().scan(0u32, |offset, values| {
+                    *offset += values.len() as u32;
+                    Some(*offset)
+                })),
+            )?,
+            point_values: MmapArray::create(
+                &self.path.join(POINT_VALUES_FILE),
+                point_to_values.iter().flatten().copied(),
+            )?,
+        };
+
+        self.deleted_pairs = BitVec::repeat(false, storage.pairs_len());
+        self.deleted_pairs_count = 0;
+        self.deleted_points = BitVec::repeat(false, point_to_values.len());
+        self.storage = Some(storage);
+        self.histogram = histogram;
+        self.points_count = points_count;
+        self.max_values_per_point = max_values_per_point;
+
+        Ok(true)
+    }
+
+    /// Remove memory mapped files of the index
+    pub(super) fn remove_files(&mut self) -> OperationResult<()> {
+        self.storage = None;
+        if self.path.exists() {
+            remove_dir_all(&self.path)?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
+        let Some(removed_values_range) = self.point_values_range(idx) else {
+            return Ok(());
+        };
+        self.deleted_points.set(idx as usize, true);
+
+        if !removed_values_range.is_empty() {
+            self.points_count -= 1;
+        }
+
+        let Some(storage) = &self.storage else {
+            return Ok(());
+        };
+        for &value in &storage.point_values.as_slice()[removed_values_range] {
+            let key = NumericIndexKey::new(value, idx);
+            let index = storage.partition_point(|pair| pair < &key);
+            if index < storage.pairs_len()
+                && storage.get_pair(index) == key
+                && !self.deleted_pairs[index]
+            {
+                self.deleted_pairs.set(index, true);
+                self.deleted_pairs_count += 1;
+
+                let deleted_pairs = &self.deleted_pairs;
+                self.histogram.remove(
+                    &key.into(),
+                    |point| Self::get_histogram_left_neighbor(storage, deleted_pairs, point),
+                    |point| Self::get_histogram_right_neighbor(storage, deleted_pairs, point),
+                );
+            }
+
+            // update db
+            let encoded = value.encode_key(idx);
+            self.db_wrapper.remove(encoded)?;
+        }
+
+        Ok(())
+    }
+
+    fn get_histogram_left_neighbor(
+        storage: &MmapNumericStorage<T>,
+        deleted_pairs: &BitVec,
+        point: &Point<T>,
+    ) -> Option<Point<T>> {
+        let key: NumericIndexKey<T> = point.clone().into();
+        let end = storage.find_end_index(&Bound::Excluded(key));
+        (0..end)
+            .rev()
+            .find(|&index| !deleted_pairs[index])
+            .map(|index| storage.get_pair(index).into())
+    }
+
+    fn get_histogram_right_neighbor(
+        storage: &MmapNumericStorage<T>,
+        deleted_pairs: &BitVec,
+        point: &Point<T>,
+    ) -> Option<Point<T>> {
+        let key: NumericIndexKey<T> = point.clone().into();
+        let start = storage.find_start_index(&Bound::Excluded(key));
+        (start..storage.pairs_len())
+            .find(|&index| !deleted_pairs[index])
+            .map(|index| storage.get_pair(index).into())
+    }
+}
@@ -1,4 +1,5 @@
 mod immutable_numeric_index;
+mod mmap_numeric_index;
 mod mutable_numeric_index;
 
 #[cfg(test)]
@@ -7,9 +8,11 @@ mod tests;
 use std::cmp::{max, min};
 use std::ops::Bound;
 use std::ops::Bound::{Excluded, Included, Unbounded};
+use std::path::Path;
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
+use mmap_numeric_index::MmapNumericIndex;
 use mutable_numeric_index::MutableNumericIndex;
 use parking_lot::RwLock;
 use rocksdb::DB;
@@ -30,7 +33,9 @@ use crate::index::key_encoding::{
     encode_i64_key_ascending,
 };
 use crate::telemetry::PayloadIndexTelemetry;
-use crate::types::{FieldCondition, FloatPayloadType, IntPayloadType, PayloadKeyType, Range};
+use crate::types::{
+    FieldCondition, FloatPayloadType, IntPayloadType, PayloadKeyType, Range, RangeInterface,
+};
 
 const HISTOGRAM_MAX_BUCKET_SIZE: usize = 10_000;
 const HISTOGRAM_PRECISION: f64 = 0.01;
@@ -83,6 +88,7 @@ impl Encodable for FloatPayloadType {
 pub enum NumericIndex<T: Encodable + Numericable> {
     Mutable(MutableNumericIndex<T>),
     Immutable(ImmutableNumericIndex<T>),
+    Mmap(MmapNumericIndex<T>),
 }
 
 impl<T: Encodable + Numericable> NumericIndex<T> {
@@ -94,10 +100,16 @@ impl<T: Encodable + Numericable> NumericIndex<T> {
         }
     }
 
+    /// Create immutable index, which keeps sorted values in memory mapped files under `path`
+    pub fn new_mmap(db: Arc<RwLock<DB>>, field: &str, path: &Path) -> Self {
+        NumericIndex::Mmap(MmapNumericIndex::new(db, field, path))
+    }
+
     fn get_db_wrapper(&self) -> &DatabaseColumnWrapper {
         match self {
             NumericIndex::Mutable(index) => index.get_db_wrapper(),
             NumericIndex::Immutable(index) => index.get_db_wrapper(),
+            NumericIndex::Mmap(index) => index.get_db_wrapper(),
         }
     }
 
@@ -105,6 +117,7 @@ impl<T: Encodable + Numericable> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => &index.histogram,
             NumericIndex::Immutable(index) => &index.histogram,
+            NumericIndex::Mmap(index) => &index.histogram,
         }
     }
 
@@ -112,6 +125,7 @@ impl<T: Encodable + Numericable> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => index.points_count,
             NumericIndex::Immutable(index) => index.points_count,
+            NumericIndex::Mmap(index) => index.points_count,
         }
     }
 
@@ -119,6 +133,7 @@ impl<T: Encodable + Numericable> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => index.get_values_count(),
             NumericIndex::Immutable(index) => index.get_values_count(),
+            NumericIndex::Mmap(index) => index.get_values_count(),
         }
     }
 
@@ -134,6 +149,7 @@ impl<T: Encodable + Numericable> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => index.load(),
             NumericIndex::Immutable(index) => index.load(),
+            NumericIndex::Mmap(index) => index.load(),
         }
     }
 
@@ -145,6 +161,7 @@ impl<T: Encodable + Numericable> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => index.remove_point(idx),
             NumericIndex::Immutable(index) => index.remove_point(idx),
+            NumericIndex::Mmap(index) => index.remove_point(idx),
         }
     }
 
@@ -152,6 +169,7 @@ impl<T: Encodable + Numericable> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => index.get_values(idx),
             NumericIndex::Immutable(index) => index.get_values(idx),
+            NumericIndex::Mmap(index) => index.get_values(idx),
         }
     }
 
@@ -164,10 +182,11 @@ impl<T: Encodable + Numericable> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => index.max_values_per_point,
             NumericIndex::Immutable(index) => index.max_values_per_point,
+            NumericIndex::Mmap(index) => index.max_values_per_point,
         }
     }
 
-    fn range_cardinality(&self, range: &Range) -> CardinalityEstimation {
+    pub fn range_cardinality(&self, range: &Range) -> CardinalityEstimation {
         let max_values_per_point = self.max_values_per_point();
         if max_values_per_point == 0 {
             return CardinalityEstimation::exact(0);
@@ -246,34 +265,21 @@ impl<T: Encodable + Numericable> NumericIndex<T> {
             .map(|x| x.is_empty())
             .unwrap_or(true)
     }
-}
-
-impl<T: Encodable + Numericable> PayloadFieldIndex for NumericIndex<T> {
-    fn count_indexed_points(&self) -> usize {
-        self.get_points_count()
-    }
-
-    fn load(&mut self) -> OperationResult<bool> {
-        NumericIndex::load(self)
-    }
-
-    fn clear(self) -> OperationResult<()> {
-        self.get_db_wrapper().recreate_column_family()
-    }
 
-    fn flusher(&self) -> Flusher {
-        NumericIndex::flusher(self)
+    /// Get iterator over points with values in the given `range`
+    pub fn range_filter(
+        &self,
+        cond_range: &Range,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        Box::new(self.stream_range(cond_range).map(|(_, idx)| idx))
     }
 
-    fn filter(
+    /// Get iterator over values and their points in the given `range`, ordered by value.
+    /// Points with multiple values in the range are yielded once per value.
+    pub fn stream_range(
         &self,
-        condition: &FieldCondition,
-    ) -> OperationResult<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
-        let cond_range = condition
-            .range
-            .as_ref()
-            .ok_or_else(|| OperationError::service_error("failed to get condition range"))?;
-
+        cond_range: &Range,
+    ) -> Box<dyn DoubleEndedIterator<Item = (T, PointOffsetType)> + '_> {
         let start_bound = match cond_range {
             Range { gt: Some(gt), .. } => {
                 let v: T = T::from_f64(*gt);
@@ -301,10 +307,10 @@ impl<T: Encodable + Numericable> PayloadFieldIndex for NumericIndex<T> {
         // map.range
         // Panics if range start > end. Panics if range start == end and both bounds are Excluded.
         if !check_boundaries(&start_bound, &end_bound) {
-            return Ok(Box::new(vec![].into_iter()));
+            return Box::new(vec![].into_iter());
         }
 
-        Ok(match self {
+        match self {
             NumericIndex::Mutable(index) => {
                 let start_bound = match start_bound {
                     Included(k) => Included(k.encode()),
@@ -316,27 +322,66 @@ impl<T: Encodable + Numericable> PayloadFieldIndex for NumericIndex<T> {
                     Excluded(k) => Excluded(k.encode()),
                     Unbounded => Unbounded,
                 };
-                Box::new(index.values_range(start_bound, end_bound))
+                Box::new(index.orderable_values_range(start_bound, end_bound))
             }
-            NumericIndex::Immutable(index) => Box::new(index.values_range(start_bound, end_bound)),
-        })
+            NumericIndex::Immutable(index) => {
+                Box::new(index.orderable_values_range(start_bound, end_bound))
+            }
+            NumericIndex::Mmap(index) => {
+                Box::new(index.orderable_values_range(start_bound, end_bound))
+            }
+        }
+    }
+}
+
+impl<T: Encodable + Numericable> PayloadFieldIndex for NumericIndex<T> {
+    fn count_indexed_points(&self) -> usize {
+        self.get_points_count()
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        NumericIndex::load(self)
+    }
+
+    fn clear(mut self) -> OperationResult<()> {
+        if let NumericIndex::Mmap(index) = &mut self {
+            index.remove_files()?;
+        }
+        self.get_db_wrapper().recreate_column_family()
+    }
+
+    fn flusher(&self) -> Flusher {
+        NumericIndex::flusher(self)
+    }
+
+    fn filter(
+        &self,
+        condition: &FieldCondition,
+    ) -> OperationResult<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
+        match &condition.range {
+            Some(RangeInterface::Float(cond_range)) => Ok(self.range_filter(cond_range)),
+            _ => Err(OperationError::service_error(
+                "failed to get condition range",
+            )),
+        }
     }
 
     fn estimate_cardinality(
         &self,
         condition: &FieldCondition,
     ) -> OperationResult<CardinalityEstimation> {
-        condition
-            .range
-            .as_ref()
-            .map(|range| {
+        match &condition.range {
+            Some(RangeInterface::Float(range)) => {
                 let mut cardinality = self.range_cardinality(range);
                 cardinality
                     .primary_clauses
                     .push(PrimaryCondition::Condition(condition.clone()));
-                cardinality
-            })
-            .ok_or_else(|| OperationError::service_error("failed to estimate cardinality"))
+                Ok(cardinality)
+            }
+            _ => Err(OperationError::service_error(
+                "failed to estimate cardinality",
+            )),
+        }
     }
 
     fn payload_blocks(
@@ -418,9 +463,9 @@ impl ValueIndexer<IntPayloadType> for NumericIndex<IntPayloadType> {
     ) -> OperationResult<()> {
         match self {
             NumericIndex::Mutable(index) => index.add_many_to_list(id, values),
-            NumericIndex::Immutable(_) => Err(OperationError::service_error(
-                "Can't add values to immutable numeric index",
-            )),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => Err(
+                OperationError::service_error("Can't add values to immutable numeric index"),
+            ),
         }
     }
 
@@ -444,9 +489,9 @@ impl ValueIndexer<FloatPayloadType> for NumericIndex<FloatPayloadType> {
     ) -> OperationResult<()> {
         match self {
             NumericIndex::Mutable(index) => index.add_many_to_list(id, values),
-            NumericIndex::Immutable(_) => Err(OperationError::service_error(
-                "Can't add values to immutable numeric index",
-            )),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => Err(
+                OperationError::service_error("Can't add values to immutable numeric index"),
+            ),
         }
     }
 
@@ -47,12 +47,15 @@ impl<T: Encodable + Numericable> MutableNumericIndex<T> {
         self.map.len()
     }
 
-    pub fn values_range(
+    /// Values with their points in the given range of encoded keys, ordered by value
+    pub fn orderable_values_range(
         &self,
         start_bound: Bound<Vec<u8>>,
         end_bound: Bound<Vec<u8>>,
-    ) -> impl Iterator<Item = PointOffsetType> + '_ {
-        self.map.range((start_bound, end_bound)).map(|(_, v)| *v)
+    ) -> impl DoubleEndedIterator<Item = (T, PointOffsetType)> + '_ {
+        self.map
+            .range((start_bound, end_bound))
+            .map(|(key, v)| (T::decode_key(key).1, *v))
     }
 
     fn add_value(&mut self, id: PointOffsetType, value: T) -> OperationResult<()> {
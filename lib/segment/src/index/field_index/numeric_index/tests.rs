@@ -35,7 +35,9 @@ fn random_index(
             NumericIndex::Mutable(index) => index
                 .add_many_to_list(i as PointOffsetType, values)
                 .unwrap(),
-            NumericIndex::Immutable(_) => unreachable!("index is mutable"),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => {
+                unreachable!("index is mutable")
+            }
         }
     }
 
@@ -213,7 +215,9 @@ fn test_payload_blocks_small(#[case] immutable: bool) {
             NumericIndex::Mutable(index) => index
                 .add_many_to_list(idx as PointOffsetType + 1, values)
                 .unwrap(),
-            NumericIndex::Immutable(_) => unreachable!("index is mutable"),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => {
+                unreachable!("index is mutable")
+            }
         });
 
     index.flusher()().unwrap();
@@ -259,7 +263,9 @@ fn test_numeric_index_load_from_disk(#[case] immutable: bool) {
             NumericIndex::Mutable(index) => index
                 .add_many_to_list(idx as PointOffsetType + 1, values)
                 .unwrap(),
-            NumericIndex::Immutable(_) => unreachable!("index is mutable"),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => {
+                unreachable!("index is mutable")
+            }
         });
 
     index.flusher()().unwrap();
@@ -305,7 +311,9 @@ fn test_numeric_index(#[case] immutable: bool) {
             NumericIndex::Mutable(index) => index
                 .add_many_to_list(idx as PointOffsetType + 1, values)
                 .unwrap(),
-            NumericIndex::Immutable(_) => unreachable!("index is mutable"),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => {
+                unreachable!("index is mutable")
+            }
         });
 
     index.flusher()().unwrap();
@@ -384,7 +392,7 @@ fn test_cond<T: Encodable + Numericable + PartialOrd + Clone>(
     let condition = FieldCondition {
         key: "".to_string(),
         r#match: None,
-        range: Some(rng),
+        range: Some(RangeInterface::Float(rng)),
         geo_bounding_box: None,
         geo_radius: None,
         values_count: None,
@@ -423,3 +431,29 @@ fn test_empty_cardinality(#[case] immutable: bool) {
         },
     );
 }
+
+#[rstest]
+#[case(true)]
+#[case(false)]
+fn test_stream_range(#[case] immutable: bool) {
+    let (_temp_dir, index) = random_index(1000, 2, immutable);
+    let range = Range {
+        lt: None,
+        gt: None,
+        gte: Some(20.0),
+        lte: Some(40.0),
+    };
+
+    let values = index.stream_range(&range).collect_vec();
+    assert!(!values.is_empty());
+    assert!(values.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+    assert!(values
+        .iter()
+        .all(|(value, _)| (20.0..=40.0).contains(value)));
+    for (value, idx) in &values {
+        assert!(index.get_values(*idx).unwrap().contains(value));
+    }
+
+    let reversed = index.stream_range(&range).rev().collect_vec();
+    assert_eq!(reversed, values.into_iter().rev().collect_vec());
+}
@@ -0,0 +1,180 @@
+use std::sync::Arc;
+
+use bitvec::vec::BitVec;
+use common::types::PointOffsetType;
+use parking_lot::Mutex;
+
+use crate::payload_storage::FilterContext;
+use crate::types::Filter;
+
+/// Maximal number of filters, cached for a single segment
+const FILTER_CACHE_CAPACITY: usize = 16;
+
+/// Number of uses of a filter, after which its matching points are evaluated and cached
+const FILTER_CACHE_MIN_USES: usize = 3;
+
+struct FilterCacheEntry {
+    filter: Filter,
+    uses: usize,
+    points: Option<Arc<BitVec>>,
+}
+
+#[derive(Default)]
+struct FilterCacheState {
+    /// Least recently used entries go first
+    entries: Vec<FilterCacheEntry>,
+    /// Incremented on each invalidation, so that outdated evaluations are not stored
+    generation: u64,
+}
+
+/// LRU cache of points, matching repeatedly used filters
+///
+/// Points of a filter are evaluated only after it is used a few times, so one-off filters
+/// don't pay for checking every point of the segment.
+/// The cache must be cleared on any change of points in the segment.
+#[derive(Default)]
+pub struct FilterCache {
+    state: Mutex<FilterCacheState>,
+}
+
+impl FilterCache {
+    /// Get points, matching the filter, if the filter is used often enough to be cached
+    ///
+    /// * `evaluate` - builds a bitmap of all points, matching the filter
+    pub fn get_or_evaluate(
+        &self,
+        filter: &Filter,
+        evaluate: impl FnOnce() -> BitVec,
+    ) -> Option<Arc<BitVec>> {
+        let generation = {
+            let mut guard = self.state.lock();
+            let state = &mut *guard;
+
+            match state
+                .entries
+                .iter()
+                .position(|entry| &entry.filter == filter)
+            {
+                Some(position) => {
+                    let entry = state.entries.remove(position);
+                    state.entries.push(entry);
+                }
+                None => {
+                    if state.entries.len() >= FILTER_CACHE_CAPACITY {
+                        state.entries.remove(0);
+                    }
+                    state.entries.push(FilterCacheEntry {
+                        filter: filter.clone(),
+                        uses: 0,
+                        points: None,
+                    });
+                }
+            }
+
+            let entry = state.entries.last_mut().expect("entry is just inserted");
+            entry.uses += 1;
+            if let Some(points) = &entry.points {
+                return Some(points.clone());
+            }
+            if entry.uses < FILTER_CACHE_MIN_USES {
+                return None;
+            }
+            state.generation
+        };
+
+        // Evaluate without holding the lock, so that searches with other filters are not blocked
+        let points = Arc::new(evaluate());
+
+        let mut state = self.state.lock();
+        if state.generation == generation {
+            if let Some(entry) = state
+                .entries
+                .iter_mut()
+                .find(|entry| &entry.filter == filter)
+            {
+                entry.points = Some(points.clone());
+            }
+        }
+        Some(points)
+    }
+
+    /// Drop all cached filters, should be called before points of the segment are changed
+    pub fn clear(&self) {
+        let mut state = self.state.lock();
+        state.entries.clear();
+        state.generation += 1;
+    }
+}
+
+/// Checks points against a cached bitmap instead of evaluating conditions of the filter
+pub struct CachedFilterContext {
+    points: Arc<BitVec>,
+}
+
+impl CachedFilterContext {
+    pub fn new(points: Arc<BitVec>) -> Self {
+        Self { points }
+    }
+}
+
+impl FilterContext for CachedFilterContext {
+    fn check(&self, point_id: PointOffsetType) -> bool {
+        self.points
+            .get(point_id as usize)
+            .map_or(false, |matches| *matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitvec::bitvec;
+
+    use super::*;
+    use crate::types::{Condition, FieldCondition, Match, ValueVariants};
+
+    fn filter(value: i64) -> Filter {
+        Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "number",
+            Match::new_value(ValueVariants::Integer(value)),
+        )))
+    }
+
+    #[test]
+    fn test_filter_cache() {
+        let cache = FilterCache::default();
+        let hot_filter = filter(0);
+        let no_evaluation = || -> BitVec { panic!("filter should not be evaluated") };
+
+        let warm_up = |cache: &FilterCache| {
+            // Filter is evaluated only after it is used a few times
+            for _ in 1..FILTER_CACHE_MIN_USES {
+                assert!(cache.get_or_evaluate(&hot_filter, no_evaluation).is_none());
+            }
+            cache
+                .get_or_evaluate(&hot_filter, || bitvec![0, 1, 1])
+                .unwrap()
+        };
+
+        let context = CachedFilterContext::new(warm_up(&cache));
+        assert!(!context.check(0));
+        assert!(context.check(1));
+        assert!(!context.check(10));
+
+        // Evaluated points are reused
+        assert!(cache.get_or_evaluate(&hot_filter, no_evaluation).is_some());
+
+        // Uses are counted from scratch after invalidation
+        cache.clear();
+        warm_up(&cache);
+
+        // Recently used filters are kept, least recently used ones are evicted
+        for value in 1..FILTER_CACHE_CAPACITY as i64 {
+            cache.get_or_evaluate(&filter(value), no_evaluation);
+        }
+        assert!(cache.get_or_evaluate(&hot_filter, no_evaluation).is_some());
+        for value in 1..=FILTER_CACHE_CAPACITY as i64 {
+            cache.get_or_evaluate(&filter(value), no_evaluation);
+        }
+        assert!(cache.get_or_evaluate(&hot_filter, no_evaluation).is_none());
+    }
+}
@@ -1,13 +1,15 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use io::file_operations::{atomic_save_json, read_json};
 use serde::{Deserialize, Serialize};
 
 use crate::common::operation_error::OperationResult;
+use crate::types::PayloadKeyType;
 
 pub const HNSW_INDEX_CONFIG_FILE: &str = "hnsw_config.json";
 
-#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct HnswGraphConfig {
     pub m: usize,
     /// Requested M
@@ -27,18 +29,51 @@ pub struct HnswGraphConfig {
     pub payload_m: Option<usize>,
     #[serde(default)]
     pub payload_m0: Option<usize>,
+    /// Custom payload M for specific payload fields, overrides `payload_m`
+    #[serde(default)]
+    pub payload_m_overrides: BTreeMap<PayloadKeyType, usize>,
     #[serde(default)]
     pub indexed_vector_count: Option<usize>,
+    /// Seed for random level assignment, makes index building deterministic
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Extend candidates with their neighbours before the heuristic selection of links
+    #[serde(default)]
+    pub extend_candidates: bool,
+    /// Fill up links with candidates, discarded by the heuristic
+    #[serde(default)]
+    pub keep_pruned_connections: bool,
+    /// Target recall for the calibration of `ef` after the index is built
+    #[serde(default)]
+    pub target_recall: Option<f64>,
+    /// Multiplier of the level generation factor
+    #[serde(default)]
+    pub level_factor_multiplier: Option<f64>,
+    /// Number of neighbours to consider during the index building on each level
+    #[serde(default)]
+    pub ef_construct_levels: Option<Vec<usize>>,
+    /// Refine links of every point after the graph is built
+    #[serde(default)]
+    pub refine: bool,
 }
 
 impl HnswGraphConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         m: usize,
         ef_construct: usize,
         full_scan_threshold: usize,
         max_indexing_threads: usize,
         payload_m: Option<usize>,
+        payload_m_overrides: BTreeMap<PayloadKeyType, usize>,
         indexed_vector_count: usize,
+        seed: Option<u64>,
+        extend_candidates: bool,
+        keep_pruned_connections: bool,
+        target_recall: Option<f64>,
+        level_factor_multiplier: Option<f64>,
+        ef_construct_levels: Option<Vec<usize>>,
+        refine: bool,
     ) -> Self {
         HnswGraphConfig {
             m,
@@ -49,10 +84,35 @@ impl HnswGraphConfig {
             max_indexing_threads,
             payload_m,
             payload_m0: payload_m.map(|v| v * 2),
+            payload_m_overrides,
             indexed_vector_count: Some(indexed_vector_count),
+            seed,
+            extend_candidates,
+            keep_pruned_connections,
+            target_recall,
+            level_factor_multiplier,
+            ef_construct_levels,
+            refine,
         }
     }
 
+    /// Check if links of a graph, built with `other` config, are the same as built with this one
+    ///
+    /// Params used only for searching and `ef` calibration are ignored.
+    pub fn same_graph_params(&self, other: &Self) -> bool {
+        self.m == other.m
+            && self.m0 == other.m0
+            && self.ef_construct == other.ef_construct
+            && self.payload_m == other.payload_m
+            && self.payload_m0 == other.payload_m0
+            && self.payload_m_overrides == other.payload_m_overrides
+            && self.extend_candidates == other.extend_candidates
+            && self.keep_pruned_connections == other.keep_pruned_connections
+            && self.level_factor_multiplier == other.level_factor_multiplier
+            && self.ef_construct_levels == other.ef_construct_levels
+            && self.refine == other.refine
+    }
+
     pub fn get_config_path(path: &Path) -> PathBuf {
         path.join(HNSW_INDEX_CONFIG_FILE)
     }
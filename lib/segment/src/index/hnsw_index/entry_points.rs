@@ -1,9 +1,18 @@
 use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
 use common::types::PointOffsetType;
+use io::file_operations::{atomic_save_json, read_json};
 use serde::{Deserialize, Serialize};
 
+use crate::common::operation_error::OperationResult;
+use crate::index::hnsw_index::graph_layers::REMOVED_POINT;
+use crate::types::{Condition, FieldCondition, Filter, Match, PayloadKeyType, ValueVariants};
+
+pub const HNSW_PAYLOAD_ENTRY_POINTS_FILE: &str = "payload_entry_points.json";
+
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
 pub struct EntryPoint {
     pub point_id: PointOffsetType,
@@ -37,6 +46,52 @@ impl EntryPoints {
             extra_entry_points: FixedLengthPriorityQueue::new(extra_entry_points),
         }
     }
+    /// Number of primary entry points, one per disconnected part of the graph
+    pub fn len(&self) -> usize {
+        self.entry_points.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &EntryPoint> {
+        self.entry_points.iter()
+    }
+
+    /// Renumber entry points, dropping the ones mapped to `REMOVED_POINT`
+    pub fn remap(&mut self, old_to_new: &[PointOffsetType]) {
+        let remap_entry = |entry: &EntryPoint| {
+            let point_id = *old_to_new.get(entry.point_id as usize)?;
+            (point_id != REMOVED_POINT).then_some(EntryPoint {
+                point_id,
+                level: entry.level,
+            })
+        };
+
+        self.entry_points = self.entry_points.iter().filter_map(remap_entry).collect();
+
+        let mut extra_entry_points =
+            FixedLengthPriorityQueue::new(self.extra_entry_points.capacity());
+        for entry in self.extra_entry_points.iter().filter_map(remap_entry) {
+            extra_entry_points.push(entry);
+        }
+        self.extra_entry_points = extra_entry_points;
+    }
+
+    /// Drop entry points, for which `keep` returns `false`
+    pub fn retain<F>(&mut self, keep: F)
+    where
+        F: Fn(PointOffsetType) -> bool,
+    {
+        self.entry_points.retain(|entry| keep(entry.point_id));
+
+        let mut extra_entry_points =
+            FixedLengthPriorityQueue::new(self.extra_entry_points.capacity());
+        for entry in self.extra_entry_points.iter() {
+            if keep(entry.point_id) {
+                extra_entry_points.push(entry.clone());
+            }
+        }
+        self.extra_entry_points = extra_entry_points;
+    }
+
     pub fn merge_from_other(&mut self, mut other: EntryPoints) {
         self.entry_points.append(&mut other.entry_points);
         // Do not merge `extra_entry_points` to prevent duplications
@@ -111,6 +166,104 @@ impl EntryPoints {
     }
 }
 
+/// Entry points of payload-based subgraphs, keyed by the matched payload value
+///
+/// Allows filtered search to start inside the subgraph of the matching payload block,
+/// e.g. of a particular tenant, instead of the first entry point which satisfies the filter.
+#[derive(Debug, Default)]
+pub struct PayloadEntryPoints {
+    entry_points: HashMap<PayloadKeyType, HashMap<ValueVariants, PointOffsetType>>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct PayloadEntryPoint {
+    key: PayloadKeyType,
+    value: ValueVariants,
+    point_id: PointOffsetType,
+}
+
+impl PayloadEntryPoints {
+    pub fn get_path(path: &Path) -> PathBuf {
+        path.join(HNSW_PAYLOAD_ENTRY_POINTS_FILE)
+    }
+
+    pub fn load(path: &Path) -> OperationResult<Self> {
+        let mut payload_entry_points = Self::default();
+        let entries: Vec<PayloadEntryPoint> = read_json(path)?;
+        for entry in entries {
+            payload_entry_points
+                .entry_points
+                .entry(entry.key)
+                .or_default()
+                .insert(entry.value, entry.point_id);
+        }
+        Ok(payload_entry_points)
+    }
+
+    pub fn save(&self, path: &Path) -> OperationResult<()> {
+        let entries: Vec<_> = self
+            .entry_points
+            .iter()
+            .flat_map(|(key, values)| {
+                values.iter().map(|(value, &point_id)| PayloadEntryPoint {
+                    key: key.clone(),
+                    value: value.clone(),
+                    point_id,
+                })
+            })
+            .collect();
+        Ok(atomic_save_json(path, &entries)?)
+    }
+
+    /// Remember entry point of the subgraph, built for the payload block `condition`
+    ///
+    /// Only blocks of exact value matches are remembered, other conditions can't be
+    /// looked up by filter.
+    pub fn insert(&mut self, condition: &FieldCondition, point_id: PointOffsetType) {
+        if let Some(Match::Value(match_value)) = &condition.r#match {
+            self.entry_points
+                .entry(condition.key.clone())
+                .or_default()
+                .insert(match_value.value.clone(), point_id);
+        }
+    }
+
+    /// Entry points of payload blocks, which match one of the `must` conditions of the filter
+    pub fn get_for_filter(&self, filter: &Filter) -> Vec<PointOffsetType> {
+        let Some(must) = &filter.must else {
+            return vec![];
+        };
+        must.iter()
+            .filter_map(|condition| match condition {
+                Condition::Field(FieldCondition {
+                    key,
+                    r#match: Some(Match::Value(match_value)),
+                    ..
+                }) => self.entry_points.get(key)?.get(&match_value.value).copied(),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Renumber entry points, dropping the ones mapped to `REMOVED_POINT`
+    pub fn remap(&mut self, old_to_new: &[PointOffsetType]) {
+        for values in self.entry_points.values_mut() {
+            values.retain(|_, point_id| match old_to_new.get(*point_id as usize) {
+                Some(&new_id) if new_id != REMOVED_POINT => {
+                    *point_id = new_id;
+                    true
+                }
+                _ => false,
+            });
+        }
+        self.entry_points.retain(|_, values| !values.is_empty());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entry_points.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rand::Rng;
@@ -141,4 +294,54 @@ mod tests {
         assert_eq!(points.entry_points.len(), 5);
         assert_eq!(points.extra_entry_points.len(), 10);
     }
+
+    #[test]
+    fn test_payload_entry_points() {
+        let mut points = PayloadEntryPoints::default();
+        assert!(points.is_empty());
+
+        points.insert(
+            &FieldCondition::new_match("color", "red".to_owned().into()),
+            1,
+        );
+        points.insert(
+            &FieldCondition::new_match("color", "blue".to_owned().into()),
+            2,
+        );
+        points.insert(&FieldCondition::new_match("count", 5.into()), 3);
+        assert!(!points.is_empty());
+
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "color",
+            "blue".to_owned().into(),
+        )));
+        assert_eq!(points.get_for_filter(&filter), vec![2]);
+
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "color",
+            "green".to_owned().into(),
+        )));
+        assert!(points.get_for_filter(&filter).is_empty());
+
+        let dir = tempfile::Builder::new()
+            .prefix("entry_points")
+            .tempdir()
+            .unwrap();
+        let path = PayloadEntryPoints::get_path(dir.path());
+        points.save(&path).unwrap();
+        let loaded = PayloadEntryPoints::load(&path).unwrap();
+        assert_eq!(loaded.entry_points, points.entry_points);
+
+        points.remap(&[REMOVED_POINT, REMOVED_POINT, 0, 1]);
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "color",
+            "blue".to_owned().into(),
+        )));
+        assert_eq!(points.get_for_filter(&filter), vec![0]);
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "count",
+            5.into(),
+        )));
+        assert_eq!(points.get_for_filter(&filter), vec![1]);
+    }
 }
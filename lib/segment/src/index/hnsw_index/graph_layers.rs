@@ -1,22 +1,24 @@
 use std::cmp::max;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
 
 use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
-use common::types::{PointOffsetType, ScoredPointOffset};
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
 use io::file_operations::{atomic_save_bin, read_bin, FileStorageError};
 use itertools::Itertools;
 use memory::mmap_ops;
 use serde::{Deserialize, Serialize};
 
 use super::entry_points::EntryPoint;
-use super::graph_links::{GraphLinks, GraphLinksMmap};
-use crate::common::operation_error::OperationResult;
+use super::graph_links::{GraphLinks, GraphLinksCompressed, GraphLinksMmap, GraphLinksRam};
+use crate::common::operation_error::{check_process_stopped, OperationResult};
 use crate::common::utils::rev_range;
 use crate::index::hnsw_index::entry_points::EntryPoints;
 use crate::index::hnsw_index::graph_links::GraphLinksConverter;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::index::hnsw_index::search_context::SearchContext;
 use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
+use crate::telemetry::{HnswGraphLayerTelemetry, HnswGraphTelemetry};
 
 pub type LinkContainer = Vec<PointOffsetType>;
 pub type LinkContainerRef<'a> = &'a [PointOffsetType];
@@ -24,6 +26,13 @@ pub type LayersContainer = Vec<LinkContainer>;
 
 pub const HNSW_GRAPH_FILE: &str = "graph.bin";
 pub const HNSW_LINKS_FILE: &str = "links.bin";
+pub const HNSW_COMPRESSED_LINKS_FILE: &str = "links_compressed.bin";
+
+/// Marks points, which are dropped by `GraphLayers::remap_points`
+pub const REMOVED_POINT: PointOffsetType = PointOffsetType::MAX;
+
+/// Number of candidates to process between checks of the cancellation flag
+const CANCELLATION_CHECK_INTERVAL: usize = 32;
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct GraphLayersBackwardCompatibility {
@@ -35,6 +44,37 @@ pub struct GraphLayersBackwardCompatibility {
     pub(super) entry_points: EntryPoints,
 }
 
+/// Problems found by `GraphLayers::check_graph`
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GraphCheckReport {
+    /// Links to the point itself, to non-existing points or to points absent on the level
+    pub invalid_links: usize,
+    /// Links, which are present more than once in the same link list
+    pub duplicate_links: usize,
+    /// Link lists longer than `m` (`m0` on level 0)
+    ///
+    /// Not an error by itself: links of payload-based subgraphs are merged on top of the main graph.
+    pub oversized_link_lists: usize,
+    /// Links without the backward link
+    ///
+    /// Not an error by itself: neighbour selection heuristic makes the graph directed.
+    pub one_way_links: usize,
+    /// Entry points, which do not exist or whose level does not match the level of the point
+    pub invalid_entry_points: usize,
+    /// Points, which can't be reached from any entry point on level 0
+    pub unreachable_points: Vec<PointOffsetType>,
+}
+
+impl GraphCheckReport {
+    /// Whether the graph has no structural errors
+    pub fn is_ok(&self) -> bool {
+        self.invalid_links == 0
+            && self.duplicate_links == 0
+            && self.invalid_entry_points == 0
+            && self.unreachable_points.is_empty()
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct GraphLayers<TGraphLinks: GraphLinks> {
     pub(super) m: usize,
@@ -60,21 +100,30 @@ pub trait GraphLayersBase {
     fn get_m(&self, level: usize) -> usize;
 
     /// Greedy search for closest points within a single graph layer
+    ///
+    /// Returns an error if `is_stopped` is set during the search.
     fn _search_on_level(
         &self,
         searcher: &mut SearchContext,
         level: usize,
         visited_list: &mut VisitedListHandle,
         points_scorer: &mut FilteredScorer,
-    ) {
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<()> {
         let limit = self.get_m(level);
         let mut points_ids: Vec<PointOffsetType> = Vec::with_capacity(2 * limit);
 
+        let mut processed = 0;
         while let Some(candidate) = searcher.candidates.pop() {
             if candidate.score < searcher.lower_bound() {
                 break;
             }
 
+            processed += 1;
+            if processed % CANCELLATION_CHECK_INTERVAL == 0 {
+                check_process_stopped(is_stopped)?;
+            }
+
             points_ids.clear();
             self.links_map(candidate.idx, level, |link| {
                 if !visited_list.check(link) {
@@ -88,21 +137,103 @@ pub trait GraphLayersBase {
                 visited_list.check_and_update_visited(score_point.idx);
             });
         }
+        Ok(())
+    }
+
+    /// Same as `_search_on_level`, but does not stop at neighbours rejected by the filter:
+    /// neighbours of rejected points are considered as well (ACORN-style two-hop expansion).
+    ///
+    /// Keeps the search connected in parts of the graph where most points are filtered out,
+    /// at the cost of additional filter checks.
+    fn _search_on_level_acorn(
+        &self,
+        searcher: &mut SearchContext,
+        level: usize,
+        visited_list: &mut VisitedListHandle,
+        points_scorer: &mut FilteredScorer,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<()> {
+        let limit = self.get_m(level);
+        let mut points_ids: Vec<PointOffsetType> = Vec::with_capacity(2 * limit);
+        let mut rejected_ids: Vec<PointOffsetType> = Vec::with_capacity(limit);
+
+        let mut processed = 0;
+        while let Some(candidate) = searcher.candidates.pop() {
+            if candidate.score < searcher.lower_bound() {
+                break;
+            }
+
+            processed += 1;
+            if processed % CANCELLATION_CHECK_INTERVAL == 0 {
+                check_process_stopped(is_stopped)?;
+            }
+
+            points_ids.clear();
+            rejected_ids.clear();
+            self.links_map(candidate.idx, level, |link| {
+                if !visited_list.check_and_update_visited(link) {
+                    if points_scorer.check_vector(link) {
+                        points_ids.push(link);
+                    } else {
+                        rejected_ids.push(link);
+                    }
+                }
+            });
+
+            for &rejected_id in &rejected_ids {
+                self.links_map(rejected_id, level, |link| {
+                    // Rejected points of the second hop are not marked as visited,
+                    // so they can still be expanded as a neighbour of another candidate
+                    if !visited_list.check(link) && points_scorer.check_vector(link) {
+                        visited_list.check_and_update_visited(link);
+                        points_ids.push(link);
+                    }
+                });
+            }
+
+            // All points are already filtered and marked as visited, so score them without limit
+            let scores = points_scorer.score_points(&mut points_ids, 0);
+            scores.iter().copied().for_each(|score_point| {
+                searcher.process_candidate(score_point);
+            });
+        }
+        Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn search_on_level(
         &self,
         level_entry: ScoredPointOffset,
         level: usize,
         ef: usize,
         points_scorer: &mut FilteredScorer,
-    ) -> FixedLengthPriorityQueue<ScoredPointOffset> {
+        acorn: bool,
+        score_threshold: Option<ScoreType>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<FixedLengthPriorityQueue<ScoredPointOffset>> {
         let mut visited_list = self.get_visited_list_from_pool();
         visited_list.check_and_update_visited(level_entry.idx);
-        let mut search_context = SearchContext::new(level_entry, ef);
-
-        self._search_on_level(&mut search_context, level, &mut visited_list, points_scorer);
-        search_context.nearest
+        let mut search_context =
+            SearchContext::with_score_threshold(level_entry, ef, score_threshold);
+
+        if acorn {
+            self._search_on_level_acorn(
+                &mut search_context,
+                level,
+                &mut visited_list,
+                points_scorer,
+                is_stopped,
+            )?;
+        } else {
+            self._search_on_level(
+                &mut search_context,
+                level,
+                &mut visited_list,
+                points_scorer,
+                is_stopped,
+            )?;
+        }
+        Ok(search_context.nearest)
     }
 
     /// Greedy searches for entry point of level `target_level`.
@@ -113,13 +244,15 @@ pub trait GraphLayersBase {
         top_level: usize,
         target_level: usize,
         points_scorer: &mut FilteredScorer,
-    ) -> ScoredPointOffset {
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<ScoredPointOffset> {
         let mut links: Vec<PointOffsetType> = Vec::with_capacity(2 * self.get_m(0));
 
         let mut current_point = ScoredPointOffset {
             idx: entry_point,
             score: points_scorer.score_point(entry_point),
         };
+        let mut processed = 0;
         for level in rev_range(top_level, target_level) {
             let limit = self.get_m(level);
 
@@ -127,6 +260,11 @@ pub trait GraphLayersBase {
             while changed {
                 changed = false;
 
+                processed += 1;
+                if processed % CANCELLATION_CHECK_INTERVAL == 0 {
+                    check_process_stopped(is_stopped)?;
+                }
+
                 links.clear();
                 self.links_map(current_point.idx, level, |link| {
                     links.push(link);
@@ -141,7 +279,38 @@ pub trait GraphLayersBase {
                 });
             }
         }
-        current_point
+        Ok(current_point)
+    }
+
+    /// Search for `top` closest points, descending from `entry_point` to level 0
+    #[allow(clippy::too_many_arguments)]
+    fn search_from_entry(
+        &self,
+        entry_point: EntryPoint,
+        top: usize,
+        ef: usize,
+        points_scorer: &mut FilteredScorer,
+        acorn: bool,
+        score_threshold: Option<ScoreType>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPointOffset>> {
+        let zero_level_entry = self.search_entry(
+            entry_point.point_id,
+            entry_point.level,
+            0,
+            points_scorer,
+            is_stopped,
+        )?;
+        let nearest = self.search_on_level(
+            zero_level_entry,
+            0,
+            max(top, ef),
+            points_scorer,
+            acorn,
+            score_threshold,
+            is_stopped,
+        )?;
+        Ok(nearest.top_with_threshold().take(top).collect_vec())
     }
 }
 
@@ -150,13 +319,11 @@ impl<TGraphLinks: GraphLinks> GraphLayersBase for GraphLayers<TGraphLinks> {
         self.visited_pool.get(self.links.num_points())
     }
 
-    fn links_map<F>(&self, point_id: PointOffsetType, level: usize, mut f: F)
+    fn links_map<F>(&self, point_id: PointOffsetType, level: usize, f: F)
     where
         F: FnMut(PointOffsetType),
     {
-        for link in self.links.links(point_id, level) {
-            f(*link);
-        }
+        self.links.links_map(point_id, level, f)
     }
 
     fn get_m(&self, level: usize) -> usize {
@@ -202,24 +369,56 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
     }
 
     pub fn search(
+        &self,
+        top: usize,
+        ef: usize,
+        points_scorer: FilteredScorer,
+        custom_entry_points: Option<&[PointOffsetType]>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPointOffset>> {
+        self.search_with_options(
+            top,
+            ef,
+            points_scorer,
+            custom_entry_points,
+            false,
+            None,
+            is_stopped,
+        )
+    }
+
+    /// Same as `search`, with additional options:
+    ///
+    /// - `acorn` - use ACORN-style two-hop expansion on level 0,
+    ///   which improves recall of searches with restrictive filters
+    /// - `score_threshold` - ignore points with lower score and stop the search,
+    ///   once no better candidates are left
+    ///
+    /// Returns an error if `is_stopped` is set during the search.
+    #[allow(clippy::too_many_arguments)]
+    pub fn search_with_options(
         &self,
         top: usize,
         ef: usize,
         mut points_scorer: FilteredScorer,
         custom_entry_points: Option<&[PointOffsetType]>,
-    ) -> Vec<ScoredPointOffset> {
+        acorn: bool,
+        score_threshold: Option<ScoreType>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPointOffset>> {
         let Some(entry_point) = self.get_entry_point(&points_scorer, custom_entry_points) else {
-            return Vec::default();
+            return Ok(Vec::default());
         };
 
-        let zero_level_entry = self.search_entry(
-            entry_point.point_id,
-            entry_point.level,
-            0,
+        self.search_from_entry(
+            entry_point,
+            top,
+            ef,
             &mut points_scorer,
-        );
-        let nearest = self.search_on_level(zero_level_entry, 0, max(top, ef), &mut points_scorer);
-        nearest.into_iter().take(top).collect_vec()
+            acorn,
+            score_threshold,
+            is_stopped,
+        )
     }
 
     pub fn get_path(path: &Path) -> PathBuf {
@@ -227,12 +426,217 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
     }
 
     pub fn get_links_path(path: &Path) -> PathBuf {
-        path.join(HNSW_LINKS_FILE)
+        path.join(TGraphLinks::LINKS_FILE)
     }
 
     pub fn num_points(&self) -> usize {
         self.links.num_points()
     }
+
+    /// Collect structural statistics of the graph
+    ///
+    /// Points for which `is_deleted` returns `true` are skipped.
+    /// Iterates over all links, so it is linear in the size of the graph.
+    pub fn get_statistics<F>(&self, is_deleted: F) -> HnswGraphTelemetry
+    where
+        F: Fn(PointOffsetType) -> bool,
+    {
+        let num_points = self.num_points();
+        let mut layers: Vec<HnswGraphLayerTelemetry> = vec![];
+        let mut degree_sums: Vec<usize> = vec![];
+        let mut has_incoming = vec![false; num_points];
+
+        for entry in self.entry_points.iter() {
+            if let Some(flag) = has_incoming.get_mut(entry.point_id as usize) {
+                *flag = true;
+            }
+        }
+
+        for point_id in 0..num_points as PointOffsetType {
+            if is_deleted(point_id) {
+                continue;
+            }
+            let point_level = self.point_level(point_id);
+            if layers.len() <= point_level {
+                layers.resize_with(point_level + 1, Default::default);
+                degree_sums.resize(point_level + 1, 0);
+            }
+            for level in 0..=point_level {
+                let mut degree = 0;
+                self.links.links_map(point_id, level, |link| {
+                    degree += 1;
+                    if level == 0 {
+                        has_incoming[link as usize] = true;
+                    }
+                });
+                let layer = &mut layers[level];
+                layer.points += 1;
+                layer.max_out_degree = max(layer.max_out_degree, degree);
+                degree_sums[level] += degree;
+            }
+        }
+
+        for (layer, degree_sum) in layers.iter_mut().zip(degree_sums) {
+            if layer.points > 0 {
+                layer.avg_out_degree = degree_sum as f64 / layer.points as f64;
+            }
+        }
+
+        let orphaned_points = has_incoming
+            .iter()
+            .enumerate()
+            .filter(|&(point_id, &has_incoming)| {
+                !has_incoming && !is_deleted(point_id as PointOffsetType)
+            })
+            .count();
+
+        HnswGraphTelemetry {
+            layers,
+            orphaned_points,
+            entry_points: self.entry_points.len(),
+        }
+    }
+
+    /// Validate structure of the graph
+    ///
+    /// Checks link bounds, levels and symmetry of links, consistency of entry points
+    /// and reachability of all points from entry points on level 0.
+    /// Points for which `is_deleted` returns `true` are neither checked nor traversed.
+    pub fn check_graph<F>(&self, is_deleted: F) -> GraphCheckReport
+    where
+        F: Fn(PointOffsetType) -> bool,
+    {
+        let num_points = self.num_points();
+        let mut report = GraphCheckReport::default();
+        let is_valid_link = |point_id: PointOffsetType, link: PointOffsetType, level: usize| {
+            link != point_id && (link as usize) < num_points && self.point_level(link) >= level
+        };
+
+        let mut links = vec![];
+        for point_id in 0..num_points as PointOffsetType {
+            if is_deleted(point_id) {
+                continue;
+            }
+            for level in 0..=self.point_level(point_id) {
+                links.clear();
+                self.links
+                    .links_map(point_id, level, |link| links.push(link));
+
+                if links.len() > self.get_m(level) {
+                    report.oversized_link_lists += 1;
+                }
+                report.duplicate_links += links.len() - links.iter().unique().count();
+
+                for &link in &links {
+                    if !is_valid_link(point_id, link, level) {
+                        report.invalid_links += 1;
+                        continue;
+                    }
+                    if is_deleted(link) {
+                        continue;
+                    }
+                    let mut has_backward_link = false;
+                    self.links.links_map(link, level, |backward_link| {
+                        has_backward_link |= backward_link == point_id;
+                    });
+                    if !has_backward_link {
+                        report.one_way_links += 1;
+                    }
+                }
+            }
+        }
+
+        let mut reachable = vec![false; num_points];
+        let mut stack = vec![];
+        for entry in self.entry_points.iter() {
+            let point_id = entry.point_id;
+            if (point_id as usize) >= num_points || self.point_level(point_id) != entry.level {
+                report.invalid_entry_points += 1;
+                continue;
+            }
+            if !is_deleted(point_id) && !reachable[point_id as usize] {
+                reachable[point_id as usize] = true;
+                stack.push(point_id);
+            }
+        }
+        while let Some(point_id) = stack.pop() {
+            self.links.links_map(point_id, 0, |link| {
+                if is_valid_link(point_id, link, 0)
+                    && !reachable[link as usize]
+                    && !is_deleted(link)
+                {
+                    reachable[link as usize] = true;
+                    stack.push(link);
+                }
+            });
+        }
+
+        report.unreachable_points = (0..num_points as PointOffsetType)
+            .filter(|&point_id| !reachable[point_id as usize] && !is_deleted(point_id))
+            .collect();
+
+        report
+    }
+
+    /// Renumber points of the graph without rebuilding it, in a single pass over all links
+    ///
+    /// `old_to_new[old_id]` is the new id of the point, new ids must be unique.
+    /// Points mapped to `REMOVED_POINT` are dropped together with the links pointing to them.
+    pub fn remap_points(&mut self, old_to_new: &[PointOffsetType]) -> OperationResult<()> {
+        let links_converter = GraphLinksConverter::new(self.remap_links_layers(old_to_new));
+        self.links = TGraphLinks::from_converter(links_converter)?;
+        self.entry_points.remap(old_to_new);
+        Ok(())
+    }
+
+    /// Renumbered copy of the graph with links in memory, see `remap_points`
+    pub fn remapped_copy(
+        &self,
+        old_to_new: &[PointOffsetType],
+    ) -> OperationResult<GraphLayers<GraphLinksRam>> {
+        let links_converter = GraphLinksConverter::new(self.remap_links_layers(old_to_new));
+        let mut entry_points = self.entry_points.clone();
+        entry_points.remap(old_to_new);
+        Ok(GraphLayers {
+            m: self.m,
+            m0: self.m0,
+            ef_construct: self.ef_construct,
+            links: GraphLinksRam::from_converter(links_converter)?,
+            entry_points,
+            visited_pool: VisitedPool::new(),
+        })
+    }
+
+    fn remap_links_layers(&self, old_to_new: &[PointOffsetType]) -> Vec<LayersContainer> {
+        let remap = |point_id: PointOffsetType| {
+            old_to_new
+                .get(point_id as usize)
+                .copied()
+                .filter(|&new_id| new_id != REMOVED_POINT)
+        };
+
+        let num_points = (0..self.num_points() as PointOffsetType)
+            .filter_map(remap)
+            .map(|new_id| new_id as usize + 1)
+            .max()
+            .unwrap_or(0);
+        let mut links_layers: Vec<LayersContainer> = vec![vec![vec![]]; num_points];
+
+        for old_id in 0..self.num_points() as PointOffsetType {
+            let Some(new_id) = remap(old_id) else {
+                continue;
+            };
+            links_layers[new_id as usize] = (0..=self.point_level(old_id))
+                .map(|level| {
+                    let mut links = vec![];
+                    self.links
+                        .links_map(old_id, level, |link| links.extend(remap(link)));
+                    links
+                })
+                .collect();
+        }
+        links_layers
+    }
 }
 
 impl<TGraphLinks> GraphLayers<TGraphLinks>
@@ -260,7 +664,7 @@ where
                     log::debug!("Converting legacy graph to new format");
 
                     let mut converter = GraphLinksConverter::new(legacy.links_layers);
-                    converter.save_as(links_path)?;
+                    TGraphLinks::save_converter(&mut converter, links_path)?;
 
                     let links = TGraphLinks::from_converter(converter)?;
                     let slf = Self {
@@ -291,6 +695,12 @@ impl GraphLayers<GraphLinksMmap> {
     }
 }
 
+impl GraphLayers<GraphLinksCompressed> {
+    pub fn prefault_mmap_pages(&self, path: &Path) -> Option<mmap_ops::PrefaultMmapPages> {
+        self.links.prefault_mmap_pages(path)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs::File;
@@ -302,12 +712,15 @@ mod tests {
     use tempfile::Builder;
 
     use super::*;
+    use crate::common::operation_error::OperationError;
     use crate::data_types::vectors::VectorElementType;
     use crate::fixtures::index_fixtures::{
         random_vector, FakeFilterContext, TestRawScorerProducer,
     };
-    use crate::index::hnsw_index::graph_links::GraphLinksRam;
-    use crate::index::hnsw_index::tests::create_graph_layer_fixture;
+    use crate::index::hnsw_index::tests::{
+        create_graph_layer_builder_fixture, create_graph_layer_fixture,
+    };
+    use crate::payload_storage::FilterContext;
     use crate::spaces::metric::Metric;
     use crate::spaces::simple::{CosineMetric, DotProductMetric};
 
@@ -321,7 +734,7 @@ mod tests {
         let raw_scorer = vector_storage.get_raw_scorer(query.to_owned()).unwrap();
         let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
         let ef = 16;
-        graph.search(top, ef, scorer, None)
+        graph.search(top, ef, scorer, None, &false.into()).unwrap()
     }
 
     const M: usize = 8;
@@ -361,15 +774,20 @@ mod tests {
         let raw_scorer = vector_holder.get_raw_scorer(added_vector).unwrap();
         let mut scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
 
-        let nearest_on_level = graph_layers.search_on_level(
-            ScoredPointOffset {
-                idx: 0,
-                score: scorer.score_point(0),
-            },
-            0,
-            32,
-            &mut scorer,
-        );
+        let nearest_on_level = graph_layers
+            .search_on_level(
+                ScoredPointOffset {
+                    idx: 0,
+                    score: scorer.score_point(0),
+                },
+                0,
+                32,
+                &mut scorer,
+                false,
+                None,
+                &false.into(),
+            )
+            .unwrap();
 
         assert_eq!(nearest_on_level.len(), graph_links[0][0].len() + 1);
 
@@ -382,6 +800,192 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_search_on_level_acorn() {
+        struct EvenFilterContext {}
+
+        impl FilterContext for EvenFilterContext {
+            fn check(&self, point_id: PointOffsetType) -> bool {
+                point_id % 2 == 0
+            }
+        }
+
+        let dim = 8;
+        let m = 8;
+        let num_vectors = 7;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let vector_holder =
+            TestRawScorerProducer::<DotProductMetric>::new(dim, num_vectors, &mut rng);
+
+        // Chain of points, where every second point is rejected by the filter
+        let graph_links = (0..num_vectors as PointOffsetType)
+            .map(|point_id| {
+                let mut links = vec![];
+                if point_id > 0 {
+                    links.push(point_id - 1);
+                }
+                if point_id + 1 < num_vectors as PointOffsetType {
+                    links.push(point_id + 1);
+                }
+                vec![links]
+            })
+            .collect_vec();
+
+        let graph_layers = GraphLayers {
+            m,
+            m0: 2 * m,
+            ef_construct: 32,
+            links: GraphLinksRam::from_converter(GraphLinksConverter::new(graph_links)).unwrap(),
+            entry_points: EntryPoints::new(1),
+            visited_pool: VisitedPool::new(),
+        };
+
+        let filter_context = EvenFilterContext {};
+        let query = random_vector(&mut rng, dim);
+        let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+        let mut scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&filter_context));
+        let level_entry = ScoredPointOffset {
+            idx: 0,
+            score: scorer.score_point(0),
+        };
+
+        // Regular search can't get past the first rejected point
+        let nearest = graph_layers
+            .search_on_level(level_entry, 0, 32, &mut scorer, false, None, &false.into())
+            .unwrap();
+        assert_eq!(nearest.len(), 1);
+
+        // Two-hop expansion jumps over rejected points and reaches all accepted ones
+        let nearest = graph_layers
+            .search_on_level(level_entry, 0, 32, &mut scorer, true, None, &false.into())
+            .unwrap();
+        let found = nearest.iter().map(|point| point.idx).sorted().collect_vec();
+        assert_eq!(found, vec![0, 2, 4, 6]);
+    }
+
+    #[test]
+    fn test_search_with_score_threshold() {
+        let num_vectors = 1000;
+        let dim = 8;
+        let top = 20;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let (vector_holder, graph_layers) = create_graph_layer_fixture::<CosineMetric, _>(
+            num_vectors,
+            M,
+            dim,
+            true,
+            &mut rng,
+            None,
+        );
+
+        let fake_filter_context = FakeFilterContext {};
+        let query = random_vector(&mut rng, dim);
+        let search = |score_threshold| {
+            let raw_scorer = vector_holder.get_raw_scorer(query.clone()).unwrap();
+            let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+            graph_layers
+                .search_with_options(top, 64, scorer, None, false, score_threshold, &false.into())
+                .unwrap()
+        };
+
+        let result = search(None);
+        let score_threshold = result[top / 2].score;
+
+        let thresholded_result = search(Some(score_threshold));
+        assert!(thresholded_result.len() <= top / 2 + 1);
+        assert!(thresholded_result
+            .iter()
+            .all(|scored| scored.score >= score_threshold));
+    }
+
+    #[test]
+    fn test_search_cancelled() {
+        let num_vectors = 1000;
+        let dim = 8;
+        let top = 20;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let (vector_holder, graph_layers) = create_graph_layer_fixture::<CosineMetric, _>(
+            num_vectors,
+            M,
+            dim,
+            true,
+            &mut rng,
+            None,
+        );
+
+        let fake_filter_context = FakeFilterContext {};
+        let query = random_vector(&mut rng, dim);
+        let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+        let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+
+        let result = graph_layers.search(top, 128, scorer, None, &true.into());
+        assert!(matches!(result, Err(OperationError::Cancelled { .. })));
+    }
+
+    #[test]
+    fn test_remap_points() {
+        let num_vectors = 100;
+        let dim = 8;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        type M = CosineMetric;
+
+        let (_, mut graph_layers) =
+            create_graph_layer_fixture::<M, _>(num_vectors, 8, dim, true, &mut rng, None);
+
+        // Drop every 10th point and reverse the order of the rest
+        let mut next_id = (num_vectors - num_vectors / 10) as PointOffsetType;
+        let old_to_new = (0..num_vectors as PointOffsetType)
+            .map(|old_id| {
+                if old_id % 10 == 0 {
+                    REMOVED_POINT
+                } else {
+                    next_id -= 1;
+                    next_id
+                }
+            })
+            .collect_vec();
+
+        let expected_links = (0..num_vectors as PointOffsetType)
+            .filter(|old_id| old_id % 10 != 0)
+            .map(|old_id| {
+                let links = (0..=graph_layers.point_level(old_id))
+                    .map(|level| {
+                        graph_layers
+                            .links
+                            .links_vec(old_id, level)
+                            .into_iter()
+                            .map(|link| old_to_new[link as usize])
+                            .filter(|&link| link != REMOVED_POINT)
+                            .collect_vec()
+                    })
+                    .collect_vec();
+                (old_to_new[old_id as usize], links)
+            })
+            .collect_vec();
+
+        graph_layers.remap_points(&old_to_new).unwrap();
+
+        assert_eq!(graph_layers.num_points(), num_vectors - num_vectors / 10);
+        for (new_id, links) in expected_links {
+            assert_eq!(graph_layers.point_level(new_id), links.len() - 1);
+            for (level, level_links) in links.iter().enumerate() {
+                assert_eq!(&graph_layers.links.links_vec(new_id, level), level_links);
+            }
+        }
+
+        let report = graph_layers.check_graph(|_| false);
+        assert_eq!(report.invalid_links, 0);
+        assert_eq!(report.invalid_entry_points, 0);
+    }
+
     #[test]
     fn test_save_and_load() {
         let num_vectors = 100;
@@ -415,6 +1019,73 @@ mod tests {
         assert_eq!(res1, res2)
     }
 
+    #[test]
+    fn test_save_and_load_compressed() {
+        let num_vectors = 100;
+        let dim = 8;
+        let top = 5;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let dir = Builder::new().prefix("graph_dir").tempdir().unwrap();
+        let links_path = GraphLayers::<GraphLinksCompressed>::get_links_path(dir.path());
+        let (vector_holder, graph_layers_builder) = create_graph_layer_builder_fixture::<
+            CosineMetric,
+            _,
+        >(num_vectors, M, dim, false, &mut rng);
+        let graph_layers = graph_layers_builder
+            .into_graph_layers::<GraphLinksCompressed>(Some(&links_path))
+            .unwrap();
+
+        let query = random_vector(&mut rng, dim);
+
+        let res1 = search_in_graph(&query, top, &vector_holder, &graph_layers);
+
+        let path = GraphLayers::<GraphLinksCompressed>::get_path(dir.path());
+        graph_layers.save(&path).unwrap();
+
+        let graph2 = GraphLayers::<GraphLinksCompressed>::load(&path, &links_path).unwrap();
+
+        let res2 = search_in_graph(&query, top, &vector_holder, &graph2);
+
+        assert_eq!(res1, res2)
+    }
+
+    #[test]
+    fn test_graph_statistics() {
+        let num_vectors = 1000;
+        let dim = 8;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let (_vector_holder, graph_layers) = create_graph_layer_fixture::<CosineMetric, _>(
+            num_vectors,
+            M,
+            dim,
+            true,
+            &mut rng,
+            None,
+        );
+
+        let stats = graph_layers.get_statistics(|_| false);
+
+        assert_eq!(stats.layers[0].points, num_vectors);
+        assert_eq!(stats.entry_points, 1);
+        assert!(stats.orphaned_points < num_vectors / 100);
+        assert!(stats.layers.len() > 1);
+        for (level, layer) in stats.layers.iter().enumerate() {
+            assert!(layer.points > 0);
+            assert!(layer.max_out_degree <= graph_layers.get_m(level));
+            assert!(layer.avg_out_degree <= layer.max_out_degree as f64);
+        }
+        for pair in stats.layers.windows(2) {
+            assert!(pair[0].points >= pair[1].points);
+        }
+
+        let stats_with_deleted = graph_layers.get_statistics(|point_id| point_id % 10 == 0);
+        assert_eq!(stats_with_deleted.layers[0].points, num_vectors - 100);
+    }
+
     #[test]
     fn test_add_points() {
         let num_vectors = 1000;
@@ -441,7 +1112,7 @@ mod tests {
         assert_eq!(main_entry.level, num_levels);
 
         let total_links_0 = (0..num_vectors)
-            .map(|i| graph_layers.links.links(i as PointOffsetType, 0).len())
+            .map(|i| graph_layers.links.links_vec(i as PointOffsetType, 0).len())
             .sum::<usize>();
 
         eprintln!("total_links_0 = {total_links_0:#?}");
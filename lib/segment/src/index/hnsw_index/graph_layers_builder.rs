@@ -1,27 +1,56 @@
 use std::cmp::{max, min};
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::path::Path;
-use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::{AtomicBool, AtomicUsize};
 
 use bitvec::prelude::BitVec;
 use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
 use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
+use io::file_operations::{atomic_save_bin, read_bin};
+use itertools::Itertools;
 use parking_lot::{Mutex, MutexGuard, RwLock};
 use rand::distributions::Uniform;
 use rand::Rng;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use super::graph_links::GraphLinks;
+use crate::common::build_progress::BuildProgress;
 use crate::common::operation_error::OperationResult;
-use crate::index::hnsw_index::entry_points::EntryPoints;
+use crate::common::version::StorageVersion;
+use crate::index::hnsw_index::entry_points::{EntryPoint, EntryPoints};
 use crate::index::hnsw_index::graph_layers::{GraphLayers, GraphLayersBase, LinkContainer};
 use crate::index::hnsw_index::graph_links::GraphLinksConverter;
+use crate::index::hnsw_index::links_spill::{LinksReadGuard, LinksSpill, LinksWriteGuard};
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::index::hnsw_index::search_context::SearchContext;
 use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
+use crate::segment::SegmentVersion;
+use crate::vector_storage::RawScorer;
 
 pub type LockedLinkContainer = RwLock<LinkContainer>;
 pub type LockedLayersContainer = Vec<LockedLinkContainer>;
 
+/// Number of points of a batch, linked one after another by a single thread
+const LINK_BATCH_CHUNK_SIZE: usize = 32;
+
+/// Snapshot of `GraphLayersBuilder` state, used to resume an interrupted build
+#[derive(Deserialize, Serialize, Debug)]
+struct GraphLayersBuilderCheckpoint {
+    // Version of the storage, which saved the checkpoint
+    version: String,
+    // Identifies points and vectors, the graph is built for
+    fingerprint: u64,
+    m: usize,
+    m0: usize,
+    ef_construct: usize,
+    max_level: usize,
+    links_layers: Vec<Vec<LinkContainer>>,
+    entry_points: EntryPoints,
+    // Points, which are already linked into the graph
+    ready_points: Vec<PointOffsetType>,
+}
+
 /// Same as `GraphLayers`,  but allows to build in parallel
 /// Convertible to `GraphLayers`
 pub struct GraphLayersBuilder {
@@ -29,12 +58,24 @@ pub struct GraphLayersBuilder {
     m: usize,
     m0: usize,
     ef_construct: usize,
+    // Custom `ef_construct` for each level, starting from level 0
+    ef_construct_levels: Vec<usize>,
     // Factor of level probability
     level_factor: f64,
     // Exclude points according to "not closer than base" heuristic?
     use_heuristic: bool,
+    // Extend candidates with their neighbours before the heuristic selection?
+    extend_candidates: bool,
+    // Fill up links with candidates, discarded by the heuristic?
+    keep_pruned_connections: bool,
     links_layers: Vec<LockedLayersContainer>,
+    // Level 0 links, kept on disk instead of `links_layers`
+    links_spill: Option<LinksSpill>,
+    // Scores of level 0 links against their point, in the same order as links
+    link_scores: Option<Vec<Mutex<Vec<ScoredPointOffset>>>>,
     entry_points: Mutex<EntryPoints>,
+    // Progress of the build, counts linked points
+    progress: Option<BuildProgress>,
 
     // Fields used on construction phase only
     visited_pool: VisitedPool,
@@ -52,7 +93,7 @@ impl GraphLayersBase for GraphLayersBuilder {
     where
         F: FnMut(PointOffsetType),
     {
-        let links = self.links_layers[point_id as usize][level].read();
+        let links = self.read_links(point_id, level);
         let ready_list = self.ready_list.read();
         for link in links.iter() {
             if ready_list[*link as usize] {
@@ -75,19 +116,27 @@ impl GraphLayersBuilder {
         self.entry_points.lock()
     }
 
+    fn get_ef_construct(&self, level: usize) -> usize {
+        self.ef_construct_levels
+            .get(level)
+            .or(self.ef_construct_levels.last())
+            .map_or(self.ef_construct, |&ef| max(ef, 1))
+    }
+
     pub fn into_graph_layers<TGraphLinks: GraphLinks>(
         self,
         path: Option<&Path>,
     ) -> OperationResult<GraphLayers<TGraphLinks>> {
+        // Spilled level 0 links are not loaded back into memory, but read by the converter
         let unlocker_links_layers = self
             .links_layers
             .into_iter()
-            .map(|l| l.into_iter().map(|l| l.into_inner()).collect())
+            .map(|layers| layers.into_iter().map(|links| links.into_inner()).collect())
             .collect();
-
-        let mut links_converter = GraphLinksConverter::new(unlocker_links_layers);
+        let mut links_converter =
+            GraphLinksConverter::new_with_spill(unlocker_links_layers, self.links_spill);
         if let Some(path) = path {
-            links_converter.save_as(path)?;
+            TGraphLinks::save_converter(&mut links_converter, path)?;
         }
 
         let links = TGraphLinks::from_converter(links_converter)?;
@@ -127,10 +176,16 @@ impl GraphLayersBuilder {
             m,
             m0,
             ef_construct,
+            ef_construct_levels: Vec::new(),
             level_factor: 1.0 / (max(m, 2) as f64).ln(),
             use_heuristic,
+            extend_candidates: false,
+            keep_pruned_connections: false,
             links_layers,
+            links_spill: None,
+            link_scores: None,
             entry_points: Mutex::new(EntryPoints::new(entry_points_num)),
+            progress: None,
             visited_pool: VisitedPool::new(),
             ready_list,
         }
@@ -155,6 +210,313 @@ impl GraphLayersBuilder {
         )
     }
 
+    /// Create builder, pre-filled with the links of an already built `graph`
+    ///
+    /// All points of the existing graph are considered to be already linked,
+    /// so new points can be inserted into it with `link_new_point`.
+    pub fn new_from_graph<TGraphLinks: GraphLinks>(
+        graph: &GraphLayers<TGraphLinks>,
+        num_vectors: usize, // Total number of points, including the ones to be inserted
+        use_heuristic: bool,
+    ) -> Self {
+        let graph_points = graph.num_points();
+        let mut builder = Self::new_with_params(
+            max(num_vectors, graph_points),
+            graph.m,
+            graph.m0,
+            graph.ef_construct,
+            1,
+            use_heuristic,
+            false,
+        );
+
+        let mut max_level = 0;
+        for point_id in 0..graph_points as PointOffsetType {
+            let point_level = graph.point_level(point_id);
+            max_level = max(max_level, point_level);
+            builder.links_layers[point_id as usize] = (0..=point_level)
+                .map(|level| RwLock::new(graph.links.links_vec(point_id, level)))
+                .collect();
+        }
+
+        builder.max_level = AtomicUsize::new(max_level);
+        builder.entry_points = Mutex::new(graph.entry_points.clone());
+        builder.ready_list.write()[..graph_points].fill(true);
+        builder
+    }
+
+    /// Mark point as not linked, so that it is inserted by `link_new_point` again
+    ///
+    /// The point must not have any links.
+    pub fn set_unlinked(&self, point_id: PointOffsetType) {
+        self.ready_list.write().set(point_id as usize, false);
+    }
+
+    /// Configure variations of the neighbour selection heuristic from the original HNSW paper
+    ///
+    /// - `extend_candidates` - also consider neighbours of the found candidates
+    /// - `keep_pruned_connections` - fill up links with candidates, discarded by the heuristic
+    ///
+    /// Only has effect if the heuristic is used.
+    pub fn set_heuristic_params(&mut self, extend_candidates: bool, keep_pruned_connections: bool) {
+        self.extend_candidates = extend_candidates;
+        self.keep_pruned_connections = keep_pruned_connections;
+    }
+
+    /// Scale the level generation factor `1/ln(m)`, to flatten or deepen the hierarchy
+    pub fn set_level_factor_multiplier(&mut self, multiplier: f64) {
+        self.level_factor *= multiplier;
+    }
+
+    /// Use a custom `ef_construct` on each level, starting from level 0
+    ///
+    /// Levels above the end of the list use its last value. Empty list resets to `ef_construct`.
+    pub fn set_ef_construct_levels(&mut self, ef_construct_levels: Vec<usize>) {
+        self.ef_construct_levels = ef_construct_levels;
+    }
+
+    /// Keep level 0 links in a temporary mmap file at `path`, instead of memory
+    ///
+    /// Allows to build graphs, which do not fit into memory, at the cost of build speed.
+    /// The file is removed, once the builder is dropped or converted into `GraphLayers`.
+    pub fn spill_links(&mut self, path: &Path) -> OperationResult<()> {
+        let links_spill = LinksSpill::create(path, self.num_points(), self.m0)?;
+        Self::store_spilled_links(&links_spill, &mut self.links_layers);
+        self.links_spill = Some(links_spill);
+        Ok(())
+    }
+
+    /// Cache scores of level 0 links, so that existing links are not scored again on every insertion
+    ///
+    /// Trades memory for build speed. Cached scores are matched with links by point id and
+    /// only contain scores computed with `score_internal`, so the built graph is the same.
+    pub fn cache_link_scores(&mut self) {
+        self.link_scores = Some(
+            std::iter::repeat_with(|| Mutex::new(vec![]))
+                .take(self.num_points())
+                .collect(),
+        );
+    }
+
+    /// Report every point, linked with `link_new_point`, to `progress`
+    pub fn set_progress(&mut self, progress: BuildProgress) {
+        self.progress = Some(progress);
+    }
+
+    /// Score links of the point against it, reusing cached scores if possible
+    ///
+    /// Caller must hold the lock of the point links.
+    fn score_links<F>(
+        &self,
+        point_id: PointOffsetType,
+        level: usize,
+        links: &[PointOffsetType],
+        mut score_internal: F,
+    ) -> Vec<ScoredPointOffset>
+    where
+        F: FnMut(PointOffsetType, PointOffsetType) -> ScoreType,
+    {
+        let cached_scores = self.cached_link_scores(point_id, level);
+        links
+            .iter()
+            .enumerate()
+            .map(|(i, &link)| {
+                let cached_score = cached_scores
+                    .as_ref()
+                    .and_then(|scores| scores.get(i))
+                    .filter(|scored| scored.idx == link)
+                    .map(|scored| scored.score);
+                ScoredPointOffset {
+                    idx: link,
+                    score: cached_score.unwrap_or_else(|| score_internal(link, point_id)),
+                }
+            })
+            .collect()
+    }
+
+    /// Remember scores of the new links of the point
+    ///
+    /// Caller must hold the write lock of the point links.
+    fn set_link_scores(
+        &self,
+        point_id: PointOffsetType,
+        level: usize,
+        scored_links: &[ScoredPointOffset],
+    ) {
+        if let Some(mut cached_scores) = self.cached_link_scores(point_id, level) {
+            cached_scores.clear();
+            cached_scores.extend_from_slice(scored_links);
+        }
+    }
+
+    fn cached_link_scores(
+        &self,
+        point_id: PointOffsetType,
+        level: usize,
+    ) -> Option<MutexGuard<Vec<ScoredPointOffset>>> {
+        if level != 0 {
+            return None;
+        }
+        let scores = self.link_scores.as_ref()?.get(point_id as usize)?;
+        Some(scores.lock())
+    }
+
+    fn store_spilled_links(links_spill: &LinksSpill, links_layers: &mut [LockedLayersContainer]) {
+        for (point_id, layers) in links_layers.iter_mut().enumerate() {
+            if let Some(links) = layers.first_mut() {
+                // Safety: links are borrowed mutably, so there are no concurrent accesses
+                unsafe { links_spill.store(point_id as PointOffsetType, links.get_mut()) };
+            }
+        }
+    }
+
+    fn read_links(&self, point_id: PointOffsetType, level: usize) -> LinksReadGuard {
+        let links = self.links_layers[point_id as usize][level].read();
+        let links_spill = self
+            .links_spill
+            .as_ref()
+            .filter(|spill| level == 0 && spill.contains(point_id));
+        if let Some(links_spill) = links_spill {
+            // Safety: read lock of the point links is held by the guard
+            if let Some(spilled_links) = unsafe { links_spill.get(point_id) } {
+                return LinksReadGuard::Spilled {
+                    _lock: links,
+                    links: spilled_links,
+                };
+            }
+        }
+        LinksReadGuard::Ram(links)
+    }
+
+    fn write_links(&self, point_id: PointOffsetType, level: usize) -> LinksWriteGuard {
+        let links = self.links_layers[point_id as usize][level].write();
+        let links_spill = self
+            .links_spill
+            .as_ref()
+            .filter(|spill| level == 0 && spill.contains(point_id));
+        LinksWriteGuard::new(links, links_spill.map(|spill| (spill, point_id)))
+    }
+
+    /// Save a snapshot of the graph built so far, including levels of not yet linked points
+    ///
+    /// `fingerprint` must identify the points and vectors, the graph is built for.
+    /// Must not run concurrently with `link_new_point`, otherwise the snapshot may contain
+    /// partially linked points.
+    pub fn save_checkpoint(&self, path: &Path, fingerprint: u64) -> OperationResult<()> {
+        let checkpoint = GraphLayersBuilderCheckpoint {
+            version: SegmentVersion::current(),
+            fingerprint,
+            m: self.m,
+            m0: self.m0,
+            ef_construct: self.ef_construct,
+            max_level: self.max_level.load(std::sync::atomic::Ordering::Relaxed),
+            links_layers: (0..self.num_points() as PointOffsetType)
+                .map(|point_id| {
+                    (0..self.links_layers[point_id as usize].len())
+                        .map(|level| self.read_links(point_id, level).to_vec())
+                        .collect()
+                })
+                .collect(),
+            entry_points: self.entry_points.lock().clone(),
+            ready_points: self
+                .ready_list
+                .read()
+                .iter_ones()
+                .map(|point_id| point_id as PointOffsetType)
+                .collect(),
+        };
+        Ok(atomic_save_bin(path, &checkpoint)?)
+    }
+
+    /// Restore a snapshot, saved with `save_checkpoint`
+    ///
+    /// Returns `false` and keeps the builder unchanged if there is no checkpoint, or if it was
+    /// made by another storage version, for other points or for a graph with other parameters.
+    pub fn load_checkpoint(&mut self, path: &Path, fingerprint: u64) -> OperationResult<bool> {
+        if !path.exists() {
+            return Ok(false);
+        }
+        let checkpoint: GraphLayersBuilderCheckpoint = read_bin(path)?;
+        if checkpoint.version != SegmentVersion::current()
+            || checkpoint.fingerprint != fingerprint
+            || checkpoint.m != self.m
+            || checkpoint.m0 != self.m0
+            || checkpoint.ef_construct != self.ef_construct
+            || checkpoint.links_layers.len() != self.num_points()
+        {
+            return Ok(false);
+        }
+
+        self.max_level = AtomicUsize::new(checkpoint.max_level);
+        self.links_layers = checkpoint
+            .links_layers
+            .into_iter()
+            .map(|layers| layers.into_iter().map(RwLock::new).collect())
+            .collect();
+        if let Some(links_spill) = &self.links_spill {
+            Self::store_spilled_links(links_spill, &mut self.links_layers);
+        }
+        self.entry_points = Mutex::new(checkpoint.entry_points);
+        let mut ready_list = self.ready_list.write();
+        ready_list.fill(false);
+        for point_id in checkpoint.ready_points {
+            ready_list.set(point_id as usize, true);
+        }
+        Ok(true)
+    }
+
+    /// Check if the point is already linked into the graph
+    pub fn is_ready(&self, point_id: PointOffsetType) -> bool {
+        self.ready_list.read()[point_id as usize]
+    }
+
+    /// Search the graph without converting it into `GraphLayers`, see `GraphLayers::search_with_options`
+    ///
+    /// Only points, which are already linked, are found.
+    #[allow(clippy::too_many_arguments)]
+    pub fn search_with_options(
+        &self,
+        top: usize,
+        ef: usize,
+        mut points_scorer: FilteredScorer,
+        custom_entry_points: Option<&[PointOffsetType]>,
+        acorn: bool,
+        score_threshold: Option<ScoreType>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPointOffset>> {
+        let entry_point = custom_entry_points
+            .and_then(|custom_entry_points| {
+                custom_entry_points
+                    .iter()
+                    .filter(|&&point_id| {
+                        self.is_ready(point_id) && points_scorer.check_vector(point_id)
+                    })
+                    .map(|&point_id| EntryPoint {
+                        point_id,
+                        level: self.get_point_level(point_id),
+                    })
+                    .max_by_key(|ep| ep.level)
+            })
+            .or_else(|| {
+                self.entry_points
+                    .lock()
+                    .get_entry_point(|point_id| points_scorer.check_vector(point_id))
+            });
+        let Some(entry_point) = entry_point else {
+            return Ok(Vec::default());
+        };
+
+        self.search_from_entry(
+            entry_point,
+            top,
+            ef,
+            &mut points_scorer,
+            acorn,
+            score_threshold,
+            is_stopped,
+        )
+    }
+
     pub fn merge_from_other(&mut self, other: GraphLayersBuilder) {
         self.max_level = AtomicUsize::new(max(
             self.max_level.load(std::sync::atomic::Ordering::Relaxed),
@@ -166,23 +528,23 @@ impl GraphLayersBuilder {
                 .resize_with(other.links_layers.len(), Vec::new);
         }
         for (point_id, layers) in other.links_layers.into_iter().enumerate() {
-            let current_layers = &mut self.links_layers[point_id];
+            let point_id = point_id as PointOffsetType;
             for (level, other_links) in layers.into_iter().enumerate() {
+                let current_layers = &mut self.links_layers[point_id as usize];
                 if current_layers.len() <= level {
-                    current_layers.push(other_links);
-                } else {
-                    let other_links = other_links.into_inner();
-                    visited_list.next_iteration();
-                    let mut current_links = current_layers[level].write();
-                    current_links.iter().copied().for_each(|x| {
-                        visited_list.check_and_update_visited(x);
-                    });
-                    for other_link in other_links
-                        .into_iter()
-                        .filter(|x| !visited_list.check_and_update_visited(*x))
-                    {
-                        current_links.push(other_link);
-                    }
+                    current_layers.push(RwLock::new(vec![]));
+                }
+                let other_links = other_links.into_inner();
+                visited_list.next_iteration();
+                let mut current_links = self.write_links(point_id, level);
+                current_links.iter().copied().for_each(|x| {
+                    visited_list.check_and_update_visited(x);
+                });
+                for other_link in other_links
+                    .into_iter()
+                    .filter(|x| !visited_list.check_and_update_visited(*x))
+                {
+                    current_links.push(other_link);
                 }
             }
         }
@@ -191,7 +553,7 @@ impl GraphLayersBuilder {
             .merge_from_other(other.entry_points.into_inner());
     }
 
-    fn num_points(&self) -> usize {
+    pub fn num_points(&self) -> usize {
         self.links_layers.len()
     }
 
@@ -210,11 +572,17 @@ impl GraphLayersBuilder {
         self.links_layers[point_id as usize].len() - 1
     }
 
+    /// Set the level of a point, growing the builder if the point is beyond its current size
+    ///
+    /// Points, added in between, get an empty level 0 and are not ready.
     pub fn set_levels(&mut self, point_id: PointOffsetType, level: usize) {
         if self.links_layers.len() <= point_id as usize {
             while self.links_layers.len() <= point_id as usize {
-                self.links_layers.push(vec![]);
+                self.links_layers.push(vec![RwLock::new(vec![])]);
             }
+            self.ready_list
+                .get_mut()
+                .resize(self.links_layers.len(), false);
         }
         let point_layers = &mut self.links_layers[point_id as usize];
         while point_layers.len() <= level {
@@ -256,32 +624,65 @@ impl GraphLayersBuilder {
     }
 
     /// <https://github.com/nmslib/hnswlib/issues/99>
+    ///
+    /// If `keep_pruned` is set, the result is filled up to `m` with discarded candidates
     fn select_candidate_with_heuristic_from_sorted<F>(
         candidates: impl Iterator<Item = ScoredPointOffset>,
         m: usize,
-        mut score_internal: F,
+        keep_pruned: bool,
+        score_internal: F,
     ) -> Vec<PointOffsetType>
     where
         F: FnMut(PointOffsetType, PointOffsetType) -> ScoreType,
     {
-        let mut result_list = Vec::with_capacity(m);
+        Self::select_scored_candidate_with_heuristic_from_sorted(
+            candidates,
+            m,
+            keep_pruned,
+            score_internal,
+        )
+        .into_iter()
+        .map(|scored| scored.idx)
+        .collect()
+    }
+
+    /// Same as `select_candidate_with_heuristic_from_sorted`, but keeps scores of the selected candidates
+    fn select_scored_candidate_with_heuristic_from_sorted<F>(
+        candidates: impl Iterator<Item = ScoredPointOffset>,
+        m: usize,
+        keep_pruned: bool,
+        mut score_internal: F,
+    ) -> Vec<ScoredPointOffset>
+    where
+        F: FnMut(PointOffsetType, PointOffsetType) -> ScoreType,
+    {
+        let mut result_list: Vec<ScoredPointOffset> = Vec::with_capacity(m);
+        let mut pruned_list = vec![];
         for current_closest in candidates {
             if result_list.len() >= m {
                 break;
             }
             let mut is_good = true;
-            for &selected_point in &result_list {
-                let dist_to_already_selected = score_internal(current_closest.idx, selected_point);
+            for selected_point in &result_list {
+                let dist_to_already_selected =
+                    score_internal(current_closest.idx, selected_point.idx);
                 if dist_to_already_selected > current_closest.score {
                     is_good = false;
                     break;
                 }
             }
             if is_good {
-                result_list.push(current_closest.idx);
+                result_list.push(current_closest);
+            } else if keep_pruned {
+                pruned_list.push(current_closest);
             }
         }
 
+        if keep_pruned {
+            let lacking = m.saturating_sub(result_list.len());
+            result_list.extend(pruned_list.into_iter().take(lacking));
+        }
+
         result_list
     }
 
@@ -289,16 +690,74 @@ impl GraphLayersBuilder {
     fn select_candidates_with_heuristic<F>(
         candidates: FixedLengthPriorityQueue<ScoredPointOffset>,
         m: usize,
+        keep_pruned: bool,
         score_internal: F,
     ) -> Vec<PointOffsetType>
     where
         F: FnMut(PointOffsetType, PointOffsetType) -> ScoreType,
     {
         let closest_iter = candidates.into_iter();
-        Self::select_candidate_with_heuristic_from_sorted(closest_iter, m, score_internal)
+        Self::select_candidate_with_heuristic_from_sorted(
+            closest_iter,
+            m,
+            keep_pruned,
+            score_internal,
+        )
+    }
+
+    /// Insert a new point into the graph
+    ///
+    /// Returns an error if `is_stopped` is set while searching for neighbours.
+    /// In this case the point is not marked as ready, and the builder must be discarded.
+    pub fn link_new_point(
+        &self,
+        point_id: PointOffsetType,
+        points_scorer: FilteredScorer,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<()> {
+        let mut visited_list = self.get_visited_list_from_pool();
+        self.link_new_point_with_visited(point_id, points_scorer, &mut visited_list, is_stopped)
+    }
+
+    /// Link a batch of new points, same as calling `link_new_point` for each of them
+    ///
+    /// The batch is split into chunks, which are linked in parallel in the current rayon pool.
+    /// Points of a chunk are linked one after another by a single thread, reusing its visited list.
+    /// Raw scorer for each point is created with `raw_scorer`.
+    pub fn link_new_points<'a, F>(
+        &self,
+        points: &[PointOffsetType],
+        raw_scorer: F,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<()>
+    where
+        F: Fn(PointOffsetType) -> OperationResult<Box<dyn RawScorer + 'a>> + Sync,
+    {
+        points
+            .par_chunks(LINK_BATCH_CHUNK_SIZE)
+            .try_for_each(|chunk| {
+                let mut visited_list = self.get_visited_list_from_pool();
+                for &point_id in chunk {
+                    let raw_scorer = raw_scorer(point_id)?;
+                    let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), None);
+                    self.link_new_point_with_visited(
+                        point_id,
+                        points_scorer,
+                        &mut visited_list,
+                        is_stopped,
+                    )?;
+                }
+                Ok(())
+            })
     }
 
-    pub fn link_new_point(&self, point_id: PointOffsetType, mut points_scorer: FilteredScorer) {
+    fn link_new_point_with_visited(
+        &self,
+        point_id: PointOffsetType,
+        mut points_scorer: FilteredScorer,
+        visited_list: &mut VisitedListHandle,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<()> {
         // Check if there is an suitable entry point
         //   - entry point level if higher or equal
         //   - it satisfies filters
@@ -328,7 +787,8 @@ impl GraphLayersBuilder {
                         entry_point.level,
                         level,
                         &mut points_scorer,
-                    )
+                        is_stopped,
+                    )?
                 } else {
                     ScoredPointOffset {
                         idx: entry_point.point_id,
@@ -340,29 +800,52 @@ impl GraphLayersBuilder {
 
                 for curr_level in (0..=linking_level).rev() {
                     let level_m = self.get_m(curr_level);
-                    let mut visited_list = self.get_visited_list_from_pool();
+                    visited_list.next_iteration();
 
                     visited_list.check_and_update_visited(level_entry.idx);
 
-                    let mut search_context = SearchContext::new(level_entry, self.ef_construct);
+                    let mut search_context =
+                        SearchContext::new(level_entry, self.get_ef_construct(curr_level));
 
                     self._search_on_level(
                         &mut search_context,
                         curr_level,
-                        &mut visited_list,
+                        visited_list,
                         &mut points_scorer,
-                    );
+                        is_stopped,
+                    )?;
 
                     if let Some(the_nearest) = search_context.nearest.iter().max() {
                         level_entry = *the_nearest;
                     }
 
+                    if self.use_heuristic && self.extend_candidates {
+                        // Extend candidates with their own neighbours
+                        let found_points = search_context
+                            .nearest
+                            .iter()
+                            .map(|scored| scored.idx)
+                            .collect_vec();
+                        for found_point in found_points {
+                            self.links_map(found_point, curr_level, |link| {
+                                if link != point_id
+                                    && !visited_list.check_and_update_visited(link)
+                                    && points_scorer.check_vector(link)
+                                {
+                                    search_context.process_candidate(ScoredPointOffset {
+                                        idx: link,
+                                        score: points_scorer.score_point(link),
+                                    });
+                                }
+                            });
+                        }
+                    }
+
                     let scorer = |a, b| points_scorer.score_internal(a, b);
 
                     if self.use_heuristic {
                         let selected_nearest = {
-                            let mut existing_links =
-                                self.links_layers[point_id as usize][curr_level].write();
+                            let mut existing_links = self.write_links(point_id, curr_level);
                             {
                                 let ready_list = self.ready_list.read();
                                 for &existing_link in existing_links.iter() {
@@ -380,6 +863,7 @@ impl GraphLayersBuilder {
                             let selected_nearest = Self::select_candidates_with_heuristic(
                                 search_context.nearest,
                                 level_m,
+                                self.keep_pruned_connections,
                                 scorer,
                             );
                             existing_links.clone_from(&selected_nearest);
@@ -387,8 +871,7 @@ impl GraphLayersBuilder {
                         };
 
                         for &other_point in &selected_nearest {
-                            let mut other_point_links =
-                                self.links_layers[other_point as usize][curr_level].write();
+                            let mut other_point_links = self.write_links(other_point, curr_level);
                             if other_point_links.len() < level_m {
                                 // If linked point is lack of neighbours
                                 other_point_links.push(point_id);
@@ -398,31 +881,31 @@ impl GraphLayersBuilder {
                                     idx: point_id,
                                     score: scorer(point_id, other_point),
                                 });
-                                for other_point_link in
-                                    other_point_links.iter().take(level_m).copied()
-                                {
-                                    candidates.push(ScoredPointOffset {
-                                        idx: other_point_link,
-                                        score: scorer(other_point_link, other_point),
-                                    });
-                                }
+                                let existing_links = other_point_links.len().min(level_m);
+                                candidates.extend(self.score_links(
+                                    other_point,
+                                    curr_level,
+                                    &other_point_links[..existing_links],
+                                    scorer,
+                                ));
                                 let selected_candidates =
-                                    Self::select_candidate_with_heuristic_from_sorted(
+                                    Self::select_scored_candidate_with_heuristic_from_sorted(
                                         candidates.into_sorted_vec().into_iter().rev(),
                                         level_m,
+                                        self.keep_pruned_connections,
                                         scorer,
                                     );
                                 other_point_links.clear(); // this do not free memory, which is good
-                                for selected in selected_candidates.iter().copied() {
-                                    other_point_links.push(selected);
+                                for selected in selected_candidates.iter() {
+                                    other_point_links.push(selected.idx);
                                 }
+                                self.set_link_scores(other_point, curr_level, &selected_candidates);
                             }
                         }
                     } else {
                         for nearest_point in &search_context.nearest {
                             {
-                                let mut links =
-                                    self.links_layers[point_id as usize][curr_level].write();
+                                let mut links = self.write_links(point_id, curr_level);
                                 Self::connect_new_point(
                                     &mut links,
                                     nearest_point.idx,
@@ -433,9 +916,7 @@ impl GraphLayersBuilder {
                             }
 
                             {
-                                let mut links = self.links_layers[nearest_point.idx as usize]
-                                    [curr_level]
-                                    .write();
+                                let mut links = self.write_links(nearest_point.idx, curr_level);
                                 Self::connect_new_point(
                                     &mut links,
                                     point_id,
@@ -450,6 +931,280 @@ impl GraphLayersBuilder {
             }
         }
         self.ready_list.write().set(point_id as usize, true);
+        if let Some(progress) = &self.progress {
+            progress.inc(1);
+        }
+        Ok(())
+    }
+
+    /// Remove points from the graph and reconnect their neighbours with each other
+    ///
+    /// Links to the removed points are dropped from every point, which refers to them.
+    /// On each level, every such point and every neighbour of a removed point re-selects its links
+    /// from its remaining links and the links of the removed points it was connected to,
+    /// bounded by M of the level. Removed points are also dropped from entry points.
+    /// This keeps the graph connected, when it is reused for the next build, instead of leaving
+    /// dead ends.
+    pub fn heal_removed_points<F>(&self, points: &[PointOffsetType], score_internal: F)
+    where
+        F: FnMut(PointOffsetType, PointOffsetType) -> ScoreType,
+    {
+        self.heal_points(points, true, score_internal)
+    }
+
+    /// Same as `heal_removed_points`, but without scanning the whole graph for referring points
+    ///
+    /// Only neighbours of the removed points and their neighbours are checked for links to them,
+    /// which covers most of the referring points, as links are mostly mutual.
+    /// Links of the other ones are left in place and skipped on search, as removed points
+    /// are not ready anymore.
+    pub fn heal_removed_points_locally<F>(&self, points: &[PointOffsetType], score_internal: F)
+    where
+        F: FnMut(PointOffsetType, PointOffsetType) -> ScoreType,
+    {
+        self.heal_points(points, false, score_internal)
+    }
+
+    fn heal_points<F>(
+        &self,
+        points: &[PointOffsetType],
+        scan_all_points: bool,
+        mut score_internal: F,
+    ) where
+        F: FnMut(PointOffsetType, PointOffsetType) -> ScoreType,
+    {
+        let num_points = self.num_points();
+        let points = points
+            .iter()
+            .copied()
+            .filter(|&point_id| (point_id as usize) < num_points)
+            .unique()
+            .collect_vec();
+
+        let mut removed = BitVec::repeat(false, num_points);
+        {
+            let mut ready_list = self.ready_list.write();
+            for &point_id in &points {
+                removed.set(point_id as usize, true);
+                ready_list.set(point_id as usize, false);
+            }
+        }
+
+        let Some(max_removed_level) = points
+            .iter()
+            .map(|&point_id| self.get_point_level(point_id))
+            .max()
+        else {
+            return;
+        };
+
+        for level in 0..=max_removed_level {
+            let level_m = self.get_m(level);
+
+            let removed_links: HashMap<_, _> = points
+                .iter()
+                .filter(|&&point_id| self.get_point_level(point_id) >= level)
+                .map(|&point_id| {
+                    self.set_link_scores(point_id, level, &[]);
+                    (
+                        point_id,
+                        std::mem::take(&mut *self.write_links(point_id, level)),
+                    )
+                })
+                .collect();
+
+            // Points, linked with removed points, and the removed points they are linked with
+            let mut affected: HashMap<PointOffsetType, Vec<PointOffsetType>> = HashMap::new();
+            for (&removed_point, links) in &removed_links {
+                for &neighbour in links {
+                    if !removed[neighbour as usize] && self.get_point_level(neighbour) >= level {
+                        affected.entry(neighbour).or_default().push(removed_point);
+                    }
+                }
+            }
+            let referring_candidates: Vec<PointOffsetType> = if scan_all_points {
+                (0..num_points as PointOffsetType).collect()
+            } else {
+                let mut candidates = HashSet::new();
+                for &neighbour in affected.keys() {
+                    candidates.insert(neighbour);
+                    candidates.extend(self.read_links(neighbour, level).iter().copied());
+                }
+                candidates.into_iter().collect()
+            };
+            for point_id in referring_candidates {
+                if removed[point_id as usize] || self.get_point_level(point_id) < level {
+                    continue;
+                }
+                for &link in self.read_links(point_id, level).iter() {
+                    if removed[link as usize] {
+                        affected.entry(point_id).or_default().push(link);
+                    }
+                }
+            }
+
+            for (neighbour, removed_neighbours) in affected {
+                let mut neighbour_links = self.write_links(neighbour, level);
+
+                let mut candidates = {
+                    let ready_list = self.ready_list.read();
+                    let is_candidate =
+                        |link: PointOffsetType| link != neighbour && ready_list[link as usize];
+                    let mut candidates: Vec<_> = self
+                        .score_links(neighbour, level, &neighbour_links, &mut score_internal)
+                        .into_iter()
+                        .filter(|scored| is_candidate(scored.idx))
+                        .unique_by(|scored| scored.idx)
+                        .collect();
+                    let extra_links = removed_neighbours
+                        .iter()
+                        .unique()
+                        .flat_map(|removed_point| &removed_links[removed_point])
+                        .copied()
+                        .unique();
+                    for link in extra_links {
+                        if is_candidate(link) && !neighbour_links.contains(&link) {
+                            candidates.push(ScoredPointOffset {
+                                idx: link,
+                                score: score_internal(neighbour, link),
+                            });
+                        }
+                    }
+                    candidates
+                };
+                candidates.sort_unstable();
+                candidates.reverse();
+
+                let selected = if self.use_heuristic {
+                    Self::select_scored_candidate_with_heuristic_from_sorted(
+                        candidates.into_iter(),
+                        level_m,
+                        self.keep_pruned_connections,
+                        &mut score_internal,
+                    )
+                } else {
+                    candidates.into_iter().take(level_m).collect()
+                };
+                neighbour_links.clear();
+                neighbour_links.extend(selected.iter().map(|scored| scored.idx));
+                self.set_link_scores(neighbour, level, &selected);
+            }
+        }
+
+        let mut entry_points = self.entry_points.lock();
+        entry_points.retain(|point_id| !removed.get(point_id as usize).map_or(false, |bit| *bit));
+        if entry_points.iter().next().is_none() {
+            // The highest remaining point becomes the new entry point of the graph
+            let ready_list = self.ready_list.read();
+            let highest_point = (0..num_points as PointOffsetType)
+                .filter(|&point_id| ready_list[point_id as usize])
+                .max_by_key(|&point_id| self.get_point_level(point_id));
+            if let Some(point_id) = highest_point {
+                entry_points.new_point(point_id, self.get_point_level(point_id), |_| true);
+            }
+        }
+    }
+
+    /// Select links of an already linked point once more, against the built graph
+    ///
+    /// On each level, candidates are the current links of the point and links of its neighbours.
+    /// Only links of the point itself are replaced, so refinement of different points
+    /// can run concurrently.
+    pub fn refine_point<F>(&self, point_id: PointOffsetType, mut score_internal: F)
+    where
+        F: FnMut(PointOffsetType, PointOffsetType) -> ScoreType,
+    {
+        if !self.is_ready(point_id) {
+            return;
+        }
+
+        for level in 0..self.links_layers[point_id as usize].len() {
+            let level_m = self.get_m(level);
+            let links = self.read_links(point_id, level).to_vec();
+
+            let mut candidates = self.score_links(point_id, level, &links, &mut score_internal);
+            let mut seen: HashSet<_> = links.iter().copied().collect();
+            seen.insert(point_id);
+            for &neighbour in &links {
+                if self.links_layers[neighbour as usize].len() <= level {
+                    continue;
+                }
+                self.links_map(neighbour, level, |link| {
+                    if seen.insert(link) {
+                        candidates.push(ScoredPointOffset {
+                            idx: link,
+                            score: score_internal(point_id, link),
+                        });
+                    }
+                });
+            }
+            candidates.sort_unstable();
+            candidates.reverse();
+
+            let selected = if self.use_heuristic {
+                Self::select_scored_candidate_with_heuristic_from_sorted(
+                    candidates.into_iter(),
+                    level_m,
+                    self.keep_pruned_connections,
+                    &mut score_internal,
+                )
+            } else {
+                candidates.into_iter().take(level_m).collect()
+            };
+
+            let mut point_links = self.write_links(point_id, level);
+            point_links.clear();
+            point_links.extend(selected.iter().map(|scored| scored.idx));
+            self.set_link_scores(point_id, level, &selected);
+        }
+    }
+
+    /// Refine a batch of points in parallel in the current rayon pool, see `refine_point`
+    ///
+    /// Raw scorer for each point is created with `raw_scorer`.
+    pub fn refine_points<'a, F>(
+        &self,
+        points: &[PointOffsetType],
+        raw_scorer: F,
+    ) -> OperationResult<()>
+    where
+        F: Fn(PointOffsetType) -> OperationResult<Box<dyn RawScorer + 'a>> + Sync,
+    {
+        points.par_iter().try_for_each(|&point_id| {
+            let raw_scorer = raw_scorer(point_id)?;
+            self.refine_point(point_id, |a, b| raw_scorer.score_internal(a, b));
+            Ok(())
+        })
+    }
+
+    /// Link the point into the graph again, replacing its current links
+    ///
+    /// Used to repair points, which are not reachable from entry points.
+    /// If none of the selected neighbours keeps the backward link, the point is appended
+    /// to level 0 links of its closest neighbour, which may exceed `m0` for that neighbour.
+    pub fn relink_point(
+        &self,
+        point_id: PointOffsetType,
+        points_scorer: FilteredScorer,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<()> {
+        self.ready_list.write().set(point_id as usize, false);
+        for level in 0..self.links_layers[point_id as usize].len() {
+            self.write_links(point_id, level).clear();
+        }
+
+        self.link_new_point(point_id, points_scorer, is_stopped)?;
+
+        let neighbours = self.read_links(point_id, 0).to_vec();
+        let has_backward_link = neighbours
+            .iter()
+            .any(|&neighbour| self.read_links(neighbour, 0).contains(&point_id));
+        if !has_backward_link {
+            if let Some(&closest) = neighbours.first() {
+                self.write_links(closest, 0).push(point_id);
+            }
+        }
+        Ok(())
     }
 
     /// This function returns average number of links per node in HNSW graph
@@ -462,9 +1217,9 @@ impl GraphLayersBuilder {
     pub fn get_average_connectivity_on_level(&self, level: usize) -> f32 {
         let mut sum = 0;
         let mut count = 0;
-        for links in self.links_layers.iter() {
+        for (point_id, links) in self.links_layers.iter().enumerate() {
             if links.len() > level {
-                sum += links[level].read().len();
+                sum += self.read_links(point_id as PointOffsetType, level).len();
                 count += 1;
             }
         }
@@ -482,14 +1237,18 @@ mod tests {
     use rand::prelude::StdRng;
     use rand::seq::SliceRandom;
     use rand::SeedableRng;
+    use tempfile::Builder;
 
     use super::*;
     use crate::data_types::vectors::VectorElementType;
     use crate::fixtures::index_fixtures::{
         random_vector, FakeFilterContext, TestRawScorerProducer,
     };
-    use crate::index::hnsw_index::graph_links::GraphLinksRam;
-    use crate::index::hnsw_index::tests::create_graph_layer_fixture;
+    use crate::index::hnsw_index::graph_layers::HNSW_LINKS_FILE;
+    use crate::index::hnsw_index::graph_links::{GraphLinksMmap, GraphLinksRam};
+    use crate::index::hnsw_index::tests::{
+        create_graph_layer_builder_fixture, create_graph_layer_fixture,
+    };
     use crate::spaces::metric::Metric;
     use crate::spaces::simple::{CosineMetric, EuclidMetric};
 
@@ -539,7 +1298,9 @@ mod tests {
                     let raw_scorer = vector_holder.get_raw_scorer(added_vector).unwrap();
                     let scorer =
                         FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
-                    graph_layers.link_new_point(idx, scorer);
+                    graph_layers
+                        .link_new_point(idx, scorer, &false.into())
+                        .unwrap();
                 });
         });
 
@@ -580,7 +1341,9 @@ mod tests {
             let added_vector = vector_holder.vectors.get(idx).to_vec();
             let raw_scorer = vector_holder.get_raw_scorer(added_vector.clone()).unwrap();
             let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
-            graph_layers.link_new_point(idx, scorer);
+            graph_layers
+                .link_new_point(idx, scorer, &false.into())
+                .unwrap();
         }
 
         (vector_holder, graph_layers)
@@ -650,35 +1413,88 @@ mod tests {
         let raw_scorer = vector_holder.get_raw_scorer(query.clone()).unwrap();
         let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
         let ef = 16;
-        let graph_search = graph.search(top, ef, scorer, None);
+        let graph_search = graph.search(top, ef, scorer, None, &false.into()).unwrap();
 
         assert_eq!(reference_top.into_vec(), graph_search);
     }
 
+    #[cfg(not(windows))]
     #[test]
-    fn test_add_points() {
+    fn test_link_new_points_batch() {
         let num_vectors = 1000;
         let dim = 8;
+        let top = 5;
 
         let mut rng = StdRng::seed_from_u64(42);
-        let mut rng2 = StdRng::seed_from_u64(42);
-
         type M = CosineMetric;
 
-        let (vector_holder, graph_layers_builder) =
-            create_graph_layer::<M, _>(num_vectors, dim, false, &mut rng);
+        let vector_holder = TestRawScorerProducer::<M>::new(dim, num_vectors, &mut rng);
+        let mut graph_layers_builder =
+            GraphLayersBuilder::new(num_vectors, M, M * 2, 16, 10, false);
+        for idx in 0..(num_vectors as PointOffsetType) {
+            let level = graph_layers_builder.get_random_layer(&mut rng);
+            graph_layers_builder.set_levels(idx, level);
+        }
 
-        let (_vector_holder_orig, graph_layers_orig) =
-            create_graph_layer_fixture::<M, _>(num_vectors, M, dim, false, &mut rng2, None);
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+        let points = (0..num_vectors as PointOffsetType).collect_vec();
+        pool.install(|| {
+            graph_layers_builder.link_new_points(
+                &points,
+                |idx| vector_holder.get_raw_scorer(vector_holder.vectors.get(idx).to_vec()),
+                &false.into(),
+            )
+        })
+        .unwrap();
+        assert!(points.iter().all(|&idx| graph_layers_builder.is_ready(idx)));
 
-        // check is graph_layers_builder links are equal to graph_layers_orig
-        let orig_len = graph_layers_orig.links.num_points();
-        let builder_len = graph_layers_builder.links_layers.len();
+        let query = random_vector(&mut rng, dim);
+        let processed_query = M::preprocess(query.clone());
+        let mut reference_top = FixedLengthPriorityQueue::new(top);
+        for idx in 0..num_vectors as PointOffsetType {
+            reference_top.push(ScoredPointOffset {
+                idx,
+                score: M::similarity(vector_holder.vectors.get(idx), &processed_query),
+            });
+        }
+
+        let graph = graph_layers_builder
+            .into_graph_layers::<GraphLinksRam>(None)
+            .unwrap();
+        let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+        let scorer = FilteredScorer::new(raw_scorer.as_ref(), None);
+        let graph_search = graph.search(top, 16, scorer, None, &false.into()).unwrap();
+
+        assert_eq!(reference_top.into_vec(), graph_search);
+    }
+
+    #[test]
+    fn test_add_points() {
+        let num_vectors = 1000;
+        let dim = 8;
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut rng2 = StdRng::seed_from_u64(42);
+
+        type M = CosineMetric;
+
+        let (vector_holder, graph_layers_builder) =
+            create_graph_layer::<M, _>(num_vectors, dim, false, &mut rng);
+
+        let (_vector_holder_orig, graph_layers_orig) =
+            create_graph_layer_fixture::<M, _>(num_vectors, M, dim, false, &mut rng2, None);
+
+        // check is graph_layers_builder links are equal to graph_layers_orig
+        let orig_len = graph_layers_orig.links.num_points();
+        let builder_len = graph_layers_builder.links_layers.len();
 
         assert_eq!(orig_len, builder_len);
 
         for idx in 0..builder_len {
-            let links_orig = &graph_layers_orig.links.links(idx as PointOffsetType, 0);
+            let links_orig = &graph_layers_orig.links.links_vec(idx as PointOffsetType, 0);
             let links_builder = graph_layers_builder.links_layers[idx][0].read();
             let link_container_from_builder = links_builder.iter().copied().collect::<Vec<_>>();
             assert_eq!(links_orig, &link_container_from_builder);
@@ -733,7 +1549,577 @@ mod tests {
         let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
         let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
         let ef = 16;
-        let graph_search = graph.search(top, ef, scorer, None);
+        let graph_search = graph.search(top, ef, scorer, None, &false.into()).unwrap();
+
+        assert_eq!(reference_top.into_vec(), graph_search);
+    }
+
+    #[test]
+    fn test_insert_into_built_graph() {
+        let num_vectors = 1000;
+        let num_initial_vectors = 900;
+        let dim = 8;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        type M = CosineMetric;
+
+        let vector_holder = TestRawScorerProducer::<M>::new(dim, num_vectors, &mut rng);
+        let fake_filter_context = FakeFilterContext {};
+
+        let mut graph_layers_builder =
+            GraphLayersBuilder::new(num_initial_vectors, M, M * 2, 16, 10, true);
+        for idx in 0..(num_initial_vectors as PointOffsetType) {
+            let added_vector = vector_holder.vectors.get(idx).to_vec();
+            let raw_scorer = vector_holder.get_raw_scorer(added_vector).unwrap();
+            let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+            let level = graph_layers_builder.get_random_layer(&mut rng);
+            graph_layers_builder.set_levels(idx, level);
+            graph_layers_builder
+                .link_new_point(idx, scorer, &false.into())
+                .unwrap();
+        }
+        let graph = graph_layers_builder
+            .into_graph_layers::<GraphLinksRam>(None)
+            .unwrap();
+
+        let mut graph_layers_builder =
+            GraphLayersBuilder::new_from_graph(&graph, num_vectors, true);
+        for idx in 0..(num_initial_vectors as PointOffsetType) {
+            let links = graph_layers_builder.links_layers[idx as usize][0].read();
+            assert_eq!(graph.links.links_vec(idx, 0), *links);
+        }
+
+        for idx in (num_initial_vectors as PointOffsetType)..(num_vectors as PointOffsetType) {
+            let added_vector = vector_holder.vectors.get(idx).to_vec();
+            let raw_scorer = vector_holder.get_raw_scorer(added_vector).unwrap();
+            let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+            let level = graph_layers_builder.get_random_layer(&mut rng);
+            graph_layers_builder.set_levels(idx, level);
+            graph_layers_builder
+                .link_new_point(idx, scorer, &false.into())
+                .unwrap();
+        }
+        let graph = graph_layers_builder
+            .into_graph_layers::<GraphLinksRam>(None)
+            .unwrap();
+        assert_eq!(graph.num_points(), num_vectors);
+
+        let top = 5;
+        let query = random_vector(&mut rng, dim);
+        let processed_query = M::preprocess(query.clone());
+        let mut reference_top = FixedLengthPriorityQueue::new(top);
+        for idx in 0..vector_holder.vectors.len() as PointOffsetType {
+            let vec = &vector_holder.vectors.get(idx);
+            reference_top.push(ScoredPointOffset {
+                idx,
+                score: M::similarity(vec, &processed_query),
+            });
+        }
+
+        let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+        let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+        let graph_search = graph.search(top, 64, scorer, None, &false.into()).unwrap();
+
+        assert_eq!(reference_top.into_vec(), graph_search);
+    }
+
+    #[test]
+    fn test_heal_removed_points() {
+        let num_vectors = 1000;
+        let dim = 8;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        type M = CosineMetric;
+
+        let (vector_holder, graph_layers_builder) =
+            create_graph_layer::<M, _>(num_vectors, dim, true, &mut rng);
+
+        let removed = (0..num_vectors as PointOffsetType)
+            .step_by(10)
+            .collect_vec();
+        let former_neighbours = removed
+            .iter()
+            .flat_map(|&point_id| {
+                graph_layers_builder.links_layers[point_id as usize][0]
+                    .read()
+                    .clone()
+            })
+            .filter(|neighbour| neighbour % 10 != 0)
+            .collect_vec();
+
+        let raw_scorer = vector_holder
+            .get_raw_scorer(random_vector(&mut rng, dim))
+            .unwrap();
+        graph_layers_builder.heal_removed_points(&removed, |a, b| raw_scorer.score_internal(a, b));
+
+        for &point_id in &removed {
+            assert!(graph_layers_builder.links_layers[point_id as usize]
+                .iter()
+                .all(|links| links.read().is_empty()));
+        }
+
+        for neighbour in former_neighbours {
+            let links = graph_layers_builder.links_layers[neighbour as usize][0].read();
+            assert!(!links.is_empty());
+            assert!(!links.contains(&neighbour));
+        }
+
+        // No links and entry points refer to removed points anymore
+        for layers in &graph_layers_builder.links_layers {
+            for links in layers {
+                assert!(links.read().iter().all(|link| link % 10 != 0));
+            }
+        }
+        let entry_points = graph_layers_builder.get_entry_points();
+        assert!(entry_points.iter().next().is_some());
+        assert!(entry_points.iter().all(|entry| entry.point_id % 10 != 0));
+    }
+
+    #[test]
+    fn test_heal_removed_points_locally() {
+        let num_vectors = 1000;
+        let dim = 8;
+        let top = 5;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        type M = CosineMetric;
+
+        let (vector_holder, graph_layers_builder) =
+            create_graph_layer::<M, _>(num_vectors, dim, true, &mut rng);
+
+        let removed_point = 500;
+        let former_neighbours = graph_layers_builder.links_layers[removed_point as usize][0]
+            .read()
+            .clone();
+
+        let raw_scorer = vector_holder
+            .get_raw_scorer(random_vector(&mut rng, dim))
+            .unwrap();
+        graph_layers_builder
+            .heal_removed_points_locally(&[removed_point], |a, b| raw_scorer.score_internal(a, b));
+
+        assert!(!graph_layers_builder.is_ready(removed_point));
+        assert!(graph_layers_builder.links_layers[removed_point as usize]
+            .iter()
+            .all(|links| links.read().is_empty()));
+        for neighbour in former_neighbours {
+            let links = graph_layers_builder.links_layers[neighbour as usize][0].read();
+            assert!(!links.is_empty());
+            assert!(!links.contains(&removed_point));
+        }
+
+        // Search doesn't return the removed point, even if some links to it are left
+        let fake_filter_context = FakeFilterContext {};
+        let query = vector_holder.vectors.get(removed_point).to_vec();
+        let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+        let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+        let search_result = graph_layers_builder
+            .search_with_options(top, 64, scorer, None, false, None, &false.into())
+            .unwrap();
+        assert_eq!(search_result.len(), top);
+        assert!(search_result
+            .iter()
+            .all(|scored| scored.idx != removed_point));
+    }
+
+    #[test]
+    fn test_check_and_repair_graph() {
+        let num_vectors = 1000;
+        let dim = 8;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        type M = CosineMetric;
+
+        let (vector_holder, graph_layers_builder) =
+            create_graph_layer_builder_fixture::<M, _>(num_vectors, M, dim, true, &mut rng);
+
+        // Simulate corruption: drop all links to a point
+        let corrupted_point = (0..num_vectors as PointOffsetType)
+            .find(|&point_id| graph_layers_builder.get_point_level(point_id) == 0)
+            .unwrap();
+        for layers in &graph_layers_builder.links_layers {
+            for links in layers {
+                links.write().retain(|&link| link != corrupted_point);
+            }
+        }
+
+        let graph = graph_layers_builder
+            .into_graph_layers::<GraphLinksRam>(None)
+            .unwrap();
+        let report = graph.check_graph(|_| false);
+        assert!(!report.is_ok());
+        assert!(report.unreachable_points.contains(&corrupted_point));
+        assert_eq!(report.invalid_links, 0);
+        assert_eq!(report.duplicate_links, 0);
+        assert_eq!(report.invalid_entry_points, 0);
+
+        // Deleted points are not reported
+        let report_with_deleted = graph.check_graph(|point_id| point_id == corrupted_point);
+        assert!(!report_with_deleted
+            .unreachable_points
+            .contains(&corrupted_point));
+
+        let fake_filter_context = FakeFilterContext {};
+        let repaired_builder = GraphLayersBuilder::new_from_graph(&graph, num_vectors, true);
+        for &point_id in &report.unreachable_points {
+            let added_vector = vector_holder.vectors.get(point_id).to_vec();
+            let raw_scorer = vector_holder.get_raw_scorer(added_vector).unwrap();
+            let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+            repaired_builder
+                .relink_point(point_id, scorer, &false.into())
+                .unwrap();
+        }
+
+        let repaired_graph = repaired_builder
+            .into_graph_layers::<GraphLinksRam>(None)
+            .unwrap();
+        let report = repaired_graph.check_graph(|_| false);
+        assert!(!report.unreachable_points.contains(&corrupted_point));
+        assert_eq!(report.invalid_links, 0);
+        assert_eq!(report.duplicate_links, 0);
+    }
+
+    #[test]
+    fn test_cached_link_scores_build() {
+        let num_vectors = 1000;
+        let dim = 8;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        type M = CosineMetric;
+
+        let vector_holder = TestRawScorerProducer::<M>::new(dim, num_vectors, &mut rng);
+        let fake_filter_context = FakeFilterContext {};
+
+        let mut plain_builder = GraphLayersBuilder::new(num_vectors, M, M * 2, 16, 10, true);
+        let mut cached_builder = GraphLayersBuilder::new(num_vectors, M, M * 2, 16, 10, true);
+        cached_builder.cache_link_scores();
+
+        for idx in 0..num_vectors as PointOffsetType {
+            let level = plain_builder.get_random_layer(&mut rng);
+            plain_builder.set_levels(idx, level);
+            cached_builder.set_levels(idx, level);
+        }
+
+        for idx in 0..num_vectors as PointOffsetType {
+            for builder in [&plain_builder, &cached_builder] {
+                let added_vector = vector_holder.vectors.get(idx).to_vec();
+                let raw_scorer = vector_holder.get_raw_scorer(added_vector).unwrap();
+                let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+                builder.link_new_point(idx, scorer, &false.into()).unwrap();
+            }
+        }
+
+        // Cached scores are only reused, so the graph is the same
+        for idx in 0..num_vectors as PointOffsetType {
+            for level in 0..=plain_builder.get_point_level(idx) {
+                assert_eq!(
+                    *plain_builder.read_links(idx, level),
+                    *cached_builder.read_links(idx, level),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_spilled_links_build() {
+        let num_vectors = 1000;
+        let dim = 8;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        type M = CosineMetric;
+
+        let vector_holder = TestRawScorerProducer::<M>::new(dim, num_vectors, &mut rng);
+        let fake_filter_context = FakeFilterContext {};
+
+        let mut ram_builder = GraphLayersBuilder::new(num_vectors, M, M * 2, 16, 10, true);
+        let mut spilled_builder =
+            GraphLayersBuilder::new_with_params(num_vectors, M, M * 2, 16, 10, true, false);
+        let dir = Builder::new().prefix("graph_dir").tempdir().unwrap();
+        let spill_path = dir.path().join("links_spill.bin");
+        spilled_builder.spill_links(&spill_path).unwrap();
+
+        for idx in 0..num_vectors as PointOffsetType {
+            let level = ram_builder.get_random_layer(&mut rng);
+            ram_builder.set_levels(idx, level);
+            spilled_builder.set_levels(idx, level);
+        }
+
+        for idx in 0..num_vectors as PointOffsetType {
+            for builder in [&ram_builder, &spilled_builder] {
+                let added_vector = vector_holder.vectors.get(idx).to_vec();
+                let raw_scorer = vector_holder.get_raw_scorer(added_vector).unwrap();
+                let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+                builder.link_new_point(idx, scorer, &false.into()).unwrap();
+            }
+        }
+
+        // Spilled links are not kept in memory
+        assert!(spilled_builder
+            .links_layers
+            .iter()
+            .all(|layers| layers[0].read().is_empty()));
+
+        let ram_graph = ram_builder
+            .into_graph_layers::<GraphLinksRam>(None)
+            .unwrap();
+        // Spilled links are streamed from the spill file into the links file
+        let links_path = dir.path().join(HNSW_LINKS_FILE);
+        let spilled_graph = spilled_builder
+            .into_graph_layers::<GraphLinksMmap>(Some(&links_path))
+            .unwrap();
+        assert!(!spill_path.exists());
+
+        for idx in 0..num_vectors as PointOffsetType {
+            let level = ram_graph.point_level(idx);
+            assert_eq!(level, spilled_graph.point_level(idx));
+            for level in 0..=level {
+                assert_eq!(
+                    ram_graph.links.links_vec(idx, level),
+                    spilled_graph.links.links_vec(idx, level),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_level_factor_multiplier() {
+        let num_points = 10_000;
+        let mut rng = StdRng::seed_from_u64(42);
+        let total_levels = |multiplier: f64, rng: &mut StdRng| {
+            let mut builder = GraphLayersBuilder::new(0, M, M * 2, 16, 10, true);
+            builder.set_level_factor_multiplier(multiplier);
+            (0..num_points)
+                .map(|_| builder.get_random_layer(rng))
+                .sum::<usize>()
+        };
+
+        // Flat graph, all points on level 0
+        assert_eq!(total_levels(0.0, &mut rng), 0);
+
+        let default_levels = total_levels(1.0, &mut rng);
+        assert!(total_levels(0.5, &mut rng) < default_levels);
+        assert!(total_levels(2.0, &mut rng) > default_levels);
+    }
+
+    #[test]
+    fn test_ef_construct_levels() {
+        let mut builder = GraphLayersBuilder::new(0, M, M * 2, 16, 10, true);
+        assert_eq!(builder.get_ef_construct(0), 16);
+        assert_eq!(builder.get_ef_construct(3), 16);
+
+        builder.set_ef_construct_levels(vec![64, 0]);
+        assert_eq!(builder.get_ef_construct(0), 64);
+        // Zero is clamped, upper levels use the last value
+        assert_eq!(builder.get_ef_construct(1), 1);
+        assert_eq!(builder.get_ef_construct(5), 1);
+
+        builder.set_ef_construct_levels(vec![]);
+        assert_eq!(builder.get_ef_construct(0), 16);
+    }
+
+    #[test]
+    fn test_resume_from_checkpoint() {
+        let num_vectors = 1000;
+        let dim = 8;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        type M = CosineMetric;
+
+        let vector_holder = TestRawScorerProducer::<M>::new(dim, num_vectors, &mut rng);
+        let fake_filter_context = FakeFilterContext {};
+        let levels_builder = GraphLayersBuilder::new(0, M, M * 2, 16, 10, true);
+        let levels = (0..num_vectors)
+            .map(|_| levels_builder.get_random_layer(&mut rng))
+            .collect_vec();
+
+        let new_builder = || {
+            let mut builder = GraphLayersBuilder::new(num_vectors, M, M * 2, 16, 10, true);
+            for (idx, &level) in levels.iter().enumerate() {
+                builder.set_levels(idx as PointOffsetType, level);
+            }
+            builder
+        };
+        let link_points = |builder: &GraphLayersBuilder, points: &[PointOffsetType]| {
+            for &idx in points {
+                let added_vector = vector_holder.vectors.get(idx).to_vec();
+                let raw_scorer = vector_holder.get_raw_scorer(added_vector).unwrap();
+                let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+                builder.link_new_point(idx, scorer, &false.into()).unwrap();
+            }
+        };
+
+        let all_points = (0..num_vectors as PointOffsetType).collect_vec();
+        let (first_half, second_half) = all_points.split_at(num_vectors / 2);
+
+        let uninterrupted_builder = new_builder();
+        link_points(&uninterrupted_builder, &all_points);
+
+        let dir = Builder::new().prefix("graph_dir").tempdir().unwrap();
+        let checkpoint_path = dir.path().join("checkpoint.bin");
+        let fingerprint = 42;
+
+        let interrupted_builder = new_builder();
+        link_points(&interrupted_builder, first_half);
+        interrupted_builder
+            .save_checkpoint(&checkpoint_path, fingerprint)
+            .unwrap();
+
+        // Checkpoint of a graph with other parameters is ignored
+        let mut other_builder = GraphLayersBuilder::new(num_vectors, M * 2, M * 4, 16, 10, true);
+        assert!(!other_builder
+            .load_checkpoint(&checkpoint_path, fingerprint)
+            .unwrap());
+
+        // Checkpoint of other points is ignored
+        let mut other_builder = GraphLayersBuilder::new(num_vectors, M, M * 2, 16, 10, true);
+        assert!(!other_builder
+            .load_checkpoint(&checkpoint_path, fingerprint + 1)
+            .unwrap());
+
+        let mut resumed_builder = GraphLayersBuilder::new(num_vectors, M, M * 2, 16, 10, true);
+        assert!(resumed_builder
+            .load_checkpoint(&checkpoint_path, fingerprint)
+            .unwrap());
+        assert!(first_half.iter().all(|&idx| resumed_builder.is_ready(idx)));
+        assert!(!second_half.iter().any(|&idx| resumed_builder.is_ready(idx)));
+        link_points(&resumed_builder, second_half);
+
+        for idx in 0..num_vectors {
+            assert_eq!(
+                uninterrupted_builder.links_layers[idx].len(),
+                resumed_builder.links_layers[idx].len(),
+            );
+            for (expected, actual) in uninterrupted_builder.links_layers[idx]
+                .iter()
+                .zip(&resumed_builder.links_layers[idx])
+            {
+                assert_eq!(*expected.read(), *actual.read());
+            }
+        }
+    }
+
+    #[test]
+    fn test_heuristic_params() {
+        let num_vectors = 1000;
+        let dim = 8;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        type M = CosineMetric;
+
+        let vector_holder = TestRawScorerProducer::<M>::new(dim, num_vectors, &mut rng);
+        let fake_filter_context = FakeFilterContext {};
+
+        let mut graph_layers_builder =
+            GraphLayersBuilder::new(num_vectors, M, M * 2, 16, 10, false);
+        graph_layers_builder.set_heuristic_params(true, true);
+        for idx in 0..(num_vectors as PointOffsetType) {
+            let added_vector = vector_holder.vectors.get(idx).to_vec();
+            let raw_scorer = vector_holder.get_raw_scorer(added_vector).unwrap();
+            let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+            let level = graph_layers_builder.get_random_layer(&mut rng);
+            graph_layers_builder.set_levels(idx, level);
+            graph_layers_builder
+                .link_new_point(idx, scorer, &false.into())
+                .unwrap();
+        }
+
+        for idx in 0..num_vectors {
+            let links = graph_layers_builder.links_layers[idx][0].read();
+            assert!(!links.is_empty());
+            assert!(links.len() <= M * 2);
+            assert!(links.iter().all_unique());
+        }
+
+        let graph = graph_layers_builder
+            .into_graph_layers::<GraphLinksRam>(None)
+            .unwrap();
+
+        let top = 5;
+        let query = random_vector(&mut rng, dim);
+        let processed_query = M::preprocess(query.clone());
+        let mut reference_top = FixedLengthPriorityQueue::new(top);
+        for idx in 0..vector_holder.vectors.len() as PointOffsetType {
+            let vec = &vector_holder.vectors.get(idx);
+            reference_top.push(ScoredPointOffset {
+                idx,
+                score: M::similarity(vec, &processed_query),
+            });
+        }
+
+        let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+        let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+        let graph_search = graph.search(top, 64, scorer, None, &false.into()).unwrap();
+
+        assert_eq!(reference_top.into_vec(), graph_search);
+    }
+
+    #[test]
+    fn test_refine_points() {
+        let num_vectors = 1000;
+        let dim = 8;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        type M = CosineMetric;
+
+        let vector_holder = TestRawScorerProducer::<M>::new(dim, num_vectors, &mut rng);
+        let fake_filter_context = FakeFilterContext {};
+
+        let mut graph_layers_builder = GraphLayersBuilder::new(num_vectors, M, M * 2, 16, 10, true);
+        for idx in 0..(num_vectors as PointOffsetType) {
+            let added_vector = vector_holder.vectors.get(idx).to_vec();
+            let raw_scorer = vector_holder.get_raw_scorer(added_vector).unwrap();
+            let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+            let level = graph_layers_builder.get_random_layer(&mut rng);
+            graph_layers_builder.set_levels(idx, level);
+            graph_layers_builder
+                .link_new_point(idx, scorer, &false.into())
+                .unwrap();
+        }
+
+        let ids: Vec<_> = (0..num_vectors as PointOffsetType).collect();
+        graph_layers_builder
+            .refine_points(&ids, |point_id| {
+                let vector = vector_holder.vectors.get(point_id).to_vec();
+                vector_holder.get_raw_scorer(vector)
+            })
+            .unwrap();
+
+        for idx in 0..num_vectors {
+            let links = graph_layers_builder.links_layers[idx][0].read();
+            assert!(!links.is_empty());
+            assert!(links.len() <= M * 2);
+            assert!(links.iter().all_unique());
+            assert!(!links.contains(&(idx as PointOffsetType)));
+        }
+
+        let graph = graph_layers_builder
+            .into_graph_layers::<GraphLinksRam>(None)
+            .unwrap();
+
+        let top = 5;
+        let query = random_vector(&mut rng, dim);
+        let processed_query = M::preprocess(query.clone());
+        let mut reference_top = FixedLengthPriorityQueue::new(top);
+        for idx in 0..vector_holder.vectors.len() as PointOffsetType {
+            let vec = &vector_holder.vectors.get(idx);
+            reference_top.push(ScoredPointOffset {
+                idx,
+                score: M::similarity(vec, &processed_query),
+            });
+        }
+
+        let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+        let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+        let graph_search = graph.search(top, 64, scorer, None, &false.into()).unwrap();
 
         assert_eq!(reference_top.into_vec(), graph_search);
     }
@@ -759,7 +2145,9 @@ mod tests {
             let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
             let level = graph_layers_builder.get_random_layer(&mut rng);
             graph_layers_builder.set_levels(idx, level);
-            graph_layers_builder.link_new_point(idx, scorer);
+            graph_layers_builder
+                .link_new_point(idx, scorer, &false.into())
+                .unwrap();
         }
         let graph_layers = graph_layers_builder
             .into_graph_layers::<GraphLinksRam>(None)
@@ -776,12 +2164,12 @@ mod tests {
 
         let layers910 = graph_layers.links.point_level(910);
         let links910 = (0..layers910 + 1)
-            .map(|i| graph_layers.links.links(910, i).to_vec())
+            .map(|i| graph_layers.links.links_vec(910, i))
             .collect::<Vec<_>>();
         eprintln!("graph_layers.links_layers[910] = {links910:#?}",);
 
         let total_edges: usize = (0..NUM_VECTORS)
-            .map(|i| graph_layers.links.links(i as PointOffsetType, 0).len())
+            .map(|i| graph_layers.links.links_vec(i as PointOffsetType, 0).len())
             .sum();
         let avg_connectivity = total_edges as f64 / NUM_VECTORS as f64;
         eprintln!("avg_connectivity = {avg_connectivity:#?}");
@@ -821,6 +2209,7 @@ mod tests {
         let selected_candidates = GraphLayersBuilder::select_candidate_with_heuristic_from_sorted(
             sorted_candidates.into_iter(),
             M,
+            false,
             |a, b| scorer.score_internal(a, b),
         );
 
@@ -866,10 +2255,19 @@ mod tests {
             });
         }
 
-        let res = GraphLayersBuilder::select_candidates_with_heuristic(candidates, m, scorer);
+        let res = GraphLayersBuilder::select_candidates_with_heuristic(
+            candidates.clone(),
+            m,
+            false,
+            scorer,
+        );
 
         assert_eq!(&res, &vec![1, 3, 6]);
 
+        let res = GraphLayersBuilder::select_candidates_with_heuristic(candidates, m, true, scorer);
+
+        assert_eq!(&res, &vec![1, 3, 6, 2, 4, 5]);
+
         let mut rng = StdRng::seed_from_u64(42);
 
         let graph_layers_builder = GraphLayersBuilder::new(num_points, m, m, ef_construct, 1, true);
@@ -1,5 +1,6 @@
 use std::cmp::max;
-use std::fs::OpenOptions;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
 use std::mem::size_of;
 use std::ops::Range;
 use std::path::{Path, PathBuf};
@@ -11,6 +12,8 @@ use memory::{madvise, mmap_ops};
 
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::vector_utils::TrySetCapacityExact;
+use crate::index::hnsw_index::graph_layers::{HNSW_COMPRESSED_LINKS_FILE, HNSW_LINKS_FILE};
+use crate::index::hnsw_index::links_spill::LinksSpill;
 
 pub const MMAP_PANIC_MESSAGE: &str = "Mmap links are not loaded";
 
@@ -133,8 +136,75 @@ impl GraphLinksFileHeader {
     }
 }
 
+/*
+Compressed links use the same offsets layout, but links of each point on each level
+are stored sorted, delta-encoded and as varints. Offsets point to bytes of compressed links.
+
+header | level offsets | offsets | reindex | compressed links
+*/
+
+#[derive(Default)]
+struct GraphLinksCompressedFileHeader {
+    pub point_count: u64,
+    pub levels_count: u64,
+    pub total_links_bytes: u64,
+    pub total_offsets_len: u64,
+}
+
+impl GraphLinksCompressedFileHeader {
+    pub fn raw_size() -> usize {
+        size_of::<u64>() * 4
+    }
+
+    pub fn serialize_bytes_to(&self, raw_data: &mut [u8]) {
+        let byte_slice = &mut raw_data[0..Self::raw_size()];
+        let arr: &mut [u64] = mmap_ops::transmute_from_u8_to_mut_slice(byte_slice);
+        arr[0] = self.point_count;
+        arr[1] = self.levels_count;
+        arr[2] = self.total_links_bytes;
+        arr[3] = self.total_offsets_len;
+    }
+
+    pub fn deserialize_bytes_from(raw_data: &[u8]) -> GraphLinksCompressedFileHeader {
+        let byte_slice = &raw_data[0..Self::raw_size()];
+        let arr: &[u64] = mmap_ops::transmute_from_u8_to_slice(byte_slice);
+        GraphLinksCompressedFileHeader {
+            point_count: arr[0],
+            levels_count: arr[1],
+            total_links_bytes: arr[2],
+            total_offsets_len: arr[3],
+        }
+    }
+
+    pub fn get_data_size(&self) -> u64 {
+        self.get_links_range().end as u64
+    }
+
+    pub fn get_level_offsets_range(&self) -> Range<usize> {
+        let start = max(64, Self::raw_size());
+        start..start + self.levels_count as usize * size_of::<u64>()
+    }
+
+    pub fn get_offsets_range(&self) -> Range<usize> {
+        let start = self.get_level_offsets_range().end;
+        start..start + self.total_offsets_len as usize * size_of::<u64>()
+    }
+
+    pub fn get_reindex_range(&self) -> Range<usize> {
+        let start = self.get_offsets_range().end;
+        start..start + self.point_count as usize * size_of::<PointOffsetType>()
+    }
+
+    pub fn get_links_range(&self) -> Range<usize> {
+        let start = self.get_reindex_range().end;
+        start..start + self.total_links_bytes as usize
+    }
+}
+
 pub struct GraphLinksConverter {
     edges: Vec<Vec<Vec<PointOffsetType>>>,
+    /// Level 0 links, spilled by the graph builder, are read from it instead of `edges`
+    level0_spill: Option<LinksSpill>,
     reindex: Vec<PointOffsetType>,
     back_index: Vec<usize>,
     total_links_len: usize,
@@ -144,21 +214,34 @@ pub struct GraphLinksConverter {
 
 impl GraphLinksConverter {
     pub fn new(edges: Vec<Vec<Vec<PointOffsetType>>>) -> Self {
-        if edges.is_empty() {
-            return Self {
-                edges,
-                reindex: Vec::new(),
-                back_index: Vec::new(),
-                total_links_len: 0,
-                total_offsets_len: 1,
-                path: None,
-            };
+        Self::new_with_spill(edges, None)
+    }
+
+    /// Converter of `edges`, with level 0 links of the points in `level0_spill` read from the spill
+    ///
+    /// Spilled links are streamed into the links file from the spill file, so that the graph
+    /// does not have to be loaded into memory, when links are stored on disk.
+    pub fn new_with_spill(
+        edges: Vec<Vec<Vec<PointOffsetType>>>,
+        level0_spill: Option<LinksSpill>,
+    ) -> Self {
+        let mut converter = Self {
+            edges,
+            level0_spill,
+            reindex: Vec::new(),
+            back_index: Vec::new(),
+            total_links_len: 0,
+            total_offsets_len: 1,
+            path: None,
+        };
+        if converter.edges.is_empty() {
+            return converter;
         }
 
         // create map from index in `offsets` to point_id
-        let mut back_index: Vec<usize> = (0..edges.len()).collect();
+        let mut back_index: Vec<usize> = (0..converter.edges.len()).collect();
         // sort by max layer and use this map to build `Self.reindex`
-        back_index.sort_unstable_by_key(|&i| edges[i].len());
+        back_index.sort_unstable_by_key(|&i| converter.edges[i].len());
         back_index.reverse();
 
         // `reindex` is map from point id to index in `Self.offsets`
@@ -170,21 +253,29 @@ impl GraphLinksConverter {
         // estimate size of `links` and `offsets`
         let mut total_links_len = 0;
         let mut total_offsets_len = 1;
-        for point in edges.iter() {
-            for layer in point.iter() {
+        for (point_id, point) in converter.edges.iter().enumerate() {
+            total_links_len += converter.level0_links(point_id).len();
+            total_offsets_len += 1;
+            for layer in point.iter().skip(1) {
                 total_links_len += layer.len();
                 total_offsets_len += 1;
             }
         }
 
-        Self {
-            edges,
-            reindex,
-            back_index,
-            total_links_len,
-            total_offsets_len,
-            path: None,
-        }
+        converter.reindex = reindex;
+        converter.back_index = back_index;
+        converter.total_links_len = total_links_len;
+        converter.total_offsets_len = total_offsets_len;
+        converter
+    }
+
+    fn level0_links(&self, point_id: usize) -> &[PointOffsetType] {
+        self.level0_spill
+            .as_ref()
+            .filter(|spill| spill.contains(point_id as PointOffsetType))
+            // Safety: spill is owned by the converter, so there are no concurrent accesses
+            .and_then(|spill| unsafe { spill.get(point_id as PointOffsetType) })
+            .unwrap_or(&self.edges[point_id][0])
     }
 
     pub fn set_path(&mut self, path: PathBuf) {
@@ -284,6 +375,57 @@ impl GraphLinksConverter {
         Ok(())
     }
 
+    /// Compressed links of all levels, offsets of each point links and start offset of each level
+    fn compress(&self) -> (Vec<u8>, Vec<u64>, Vec<u64>) {
+        let levels_count = self.get_levels_count();
+        let mut links = Vec::new();
+        let mut offsets = Vec::with_capacity(self.total_offsets_len);
+        let mut level_offsets = Vec::with_capacity(levels_count);
+        offsets.push(0);
+        for level in 0..levels_count {
+            level_offsets.push(offsets.len() as u64 - 1);
+            self.iterate_level_points(level, |_, point_links| {
+                compress_links(point_links, &mut links);
+                offsets.push(links.len() as u64);
+            });
+        }
+        (links, offsets, level_offsets)
+    }
+
+    pub fn to_compressed_bytes(&self) -> Vec<u8> {
+        let (links, offsets, level_offsets) = self.compress();
+        let header = GraphLinksCompressedFileHeader {
+            point_count: self.reindex.len() as u64,
+            levels_count: level_offsets.len() as u64,
+            total_links_bytes: links.len() as u64,
+            total_offsets_len: offsets.len() as u64,
+        };
+
+        let mut bytes = vec![0; header.get_data_size() as usize];
+        header.serialize_bytes_to(&mut bytes);
+        bytes[header.get_level_offsets_range()]
+            .copy_from_slice(mmap_ops::transmute_to_u8_slice(&level_offsets));
+        bytes[header.get_offsets_range()]
+            .copy_from_slice(mmap_ops::transmute_to_u8_slice(&offsets));
+        bytes[header.get_reindex_range()]
+            .copy_from_slice(mmap_ops::transmute_to_u8_slice(&self.reindex));
+        bytes[header.get_links_range()].copy_from_slice(&links);
+        bytes
+    }
+
+    pub fn save_as_compressed(&mut self, path: &Path) -> OperationResult<()> {
+        self.path = Some(path.to_path_buf());
+        let temp_path = path.with_extension("tmp");
+        {
+            let mut file = File::create(temp_path.as_path())?;
+            file.write_all(&self.to_compressed_bytes())?;
+            file.sync_all()?;
+        }
+        std::fs::rename(temp_path, path)?;
+
+        Ok(())
+    }
+
     pub fn get_levels_count(&self) -> usize {
         if self.back_index.is_empty() {
             return 0;
@@ -294,11 +436,11 @@ impl GraphLinksConverter {
 
     pub fn iterate_level_points<F>(&self, level: usize, mut f: F)
     where
-        F: FnMut(usize, &Vec<PointOffsetType>),
+        F: FnMut(usize, &[PointOffsetType]),
     {
         let edges_len = self.edges.len();
         if level == 0 {
-            (0..edges_len).for_each(|point_id| f(point_id, &self.edges[point_id][0]));
+            (0..edges_len).for_each(|point_id| f(point_id, self.level0_links(point_id)));
         } else {
             for i in 0..edges_len {
                 let point_id = self.back_index[i];
@@ -312,37 +454,64 @@ impl GraphLinksConverter {
 }
 
 pub trait GraphLinks: Default {
+    /// Name of the file in the index directory, links are stored in
+    const LINKS_FILE: &'static str = HNSW_LINKS_FILE;
+
+    /// Whether links are read from disk on demand, rather than kept in memory
+    const ON_DISK: bool = false;
+
     fn load_from_file(path: &Path) -> OperationResult<Self>;
 
     fn from_converter(converter: GraphLinksConverter) -> OperationResult<Self>;
 
+    /// Save links of `converter` to `path` in the format, which can be loaded by this implementation
+    fn save_converter(converter: &mut GraphLinksConverter, path: &Path) -> OperationResult<()> {
+        converter.save_as(path)
+    }
+
+    fn num_points(&self) -> usize;
+
+    /// Returns the highest level this point is included in
+    fn point_level(&self, point_id: PointOffsetType) -> usize;
+
+    /// Call `f` for each link of the point on the given level
+    fn links_map<F>(&self, point_id: PointOffsetType, level: usize, f: F)
+    where
+        F: FnMut(PointOffsetType);
+
+    fn links_vec(&self, point_id: PointOffsetType, level: usize) -> Vec<PointOffsetType> {
+        let mut links = Vec::new();
+        self.links_map(point_id, level, |link| links.push(link));
+        links
+    }
+}
+
+/// Access to the offsets of the links layout, described above.
+///
+/// Shared by plain and compressed links. Ranges point to `PointOffsetType` items of plain links
+/// and to bytes of compressed links.
+pub trait GraphLinksLayout {
     fn offsets_len(&self) -> usize;
 
     fn levels_count(&self) -> usize;
 
-    fn get_links(&self, range: Range<usize>) -> &[PointOffsetType];
-
     fn get_links_range(&self, idx: usize) -> Range<usize>;
 
     fn get_level_offset(&self, level: usize) -> usize;
 
     fn reindex(&self, point_id: PointOffsetType) -> PointOffsetType;
 
-    fn num_points(&self) -> usize;
-
-    fn links(&self, point_id: PointOffsetType, level: usize) -> &[PointOffsetType] {
+    fn get_point_links_range(&self, point_id: PointOffsetType, level: usize) -> Range<usize> {
         if level == 0 {
-            let links_range = self.get_links_range(point_id as usize);
-            self.get_links(links_range)
+            self.get_links_range(point_id as usize)
         } else {
             let reindexed_point_id = self.reindex(point_id) as usize;
             let layer_offsets_start = self.get_level_offset(level);
-            let links_range = self.get_links_range(layer_offsets_start + reindexed_point_id);
-            self.get_links(links_range)
+            self.get_links_range(layer_offsets_start + reindexed_point_id)
         }
     }
 
-    fn point_level(&self, point_id: PointOffsetType) -> usize {
+    fn get_point_level(&self, point_id: PointOffsetType) -> usize {
         let reindexed_point_id = self.reindex(point_id) as usize;
         // level 0 is always present, start checking from level 1. Stop checking when level is incorrect
         for level in 1.. {
@@ -376,6 +545,15 @@ pub trait GraphLinks: Default {
     }
 }
 
+/// Plain links, which can be accessed as slices
+pub trait PlainGraphLinks: GraphLinksLayout {
+    fn get_links(&self, range: Range<usize>) -> &[PointOffsetType];
+
+    fn links(&self, point_id: PointOffsetType, level: usize) -> &[PointOffsetType] {
+        self.get_links(self.get_point_links_range(point_id, level))
+    }
+}
+
 #[derive(Default)]
 pub struct GraphLinksRam {
     // all flattened links of all levels
@@ -425,6 +603,36 @@ impl GraphLinksRam {
     }
 }
 
+impl GraphLinksLayout for GraphLinksRam {
+    fn offsets_len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    fn levels_count(&self) -> usize {
+        self.level_offsets.len()
+    }
+
+    fn get_links_range(&self, idx: usize) -> Range<usize> {
+        let start = self.offsets[idx];
+        let end = self.offsets[idx + 1];
+        start as usize..end as usize
+    }
+
+    fn get_level_offset(&self, level: usize) -> usize {
+        self.level_offsets[level] as usize
+    }
+
+    fn reindex(&self, point_id: PointOffsetType) -> PointOffsetType {
+        self.reindex[point_id as usize]
+    }
+}
+
+impl PlainGraphLinks for GraphLinksRam {
+    fn get_links(&self, range: Range<usize>) -> &[PointOffsetType] {
+        &self.links[range]
+    }
+}
+
 impl GraphLinks for GraphLinksRam {
     fn load_from_file(path: &Path) -> OperationResult<Self> {
         let file = OpenOptions::new()
@@ -446,34 +654,21 @@ impl GraphLinks for GraphLinksRam {
         Self::load_from_memory(&data)
     }
 
-    fn offsets_len(&self) -> usize {
-        self.offsets.len()
-    }
-
-    fn levels_count(&self) -> usize {
-        self.level_offsets.len()
-    }
-
-    fn get_links(&self, range: Range<usize>) -> &[PointOffsetType] {
-        &self.links[range]
-    }
-
-    fn get_links_range(&self, idx: usize) -> Range<usize> {
-        let start = self.offsets[idx];
-        let end = self.offsets[idx + 1];
-        start as usize..end as usize
-    }
-
-    fn get_level_offset(&self, level: usize) -> usize {
-        self.level_offsets[level] as usize
+    fn num_points(&self) -> usize {
+        self.reindex.len()
     }
 
-    fn reindex(&self, point_id: PointOffsetType) -> PointOffsetType {
-        self.reindex[point_id as usize]
+    fn point_level(&self, point_id: PointOffsetType) -> usize {
+        self.get_point_level(point_id)
     }
 
-    fn num_points(&self) -> usize {
-        self.reindex.len()
+    fn links_map<F>(&self, point_id: PointOffsetType, level: usize, mut f: F)
+    where
+        F: FnMut(PointOffsetType),
+    {
+        for link in self.links(point_id, level) {
+            f(*link);
+        }
     }
 }
 
@@ -514,7 +709,38 @@ impl GraphLinksMmap {
     }
 }
 
+impl GraphLinksLayout for GraphLinksMmap {
+    fn offsets_len(&self) -> usize {
+        self.header.get_offsets_range().len() / size_of::<u64>()
+    }
+
+    fn levels_count(&self) -> usize {
+        self.level_offsets.len()
+    }
+
+    fn get_links_range(&self, idx: usize) -> Range<usize> {
+        let offsets_slice = self.get_offsets_slice();
+        offsets_slice[idx] as usize..offsets_slice[idx + 1] as usize
+    }
+
+    fn get_level_offset(&self, level: usize) -> usize {
+        self.level_offsets[level] as usize
+    }
+
+    fn reindex(&self, point_id: PointOffsetType) -> PointOffsetType {
+        self.get_reindex_slice()[point_id as usize]
+    }
+}
+
+impl PlainGraphLinks for GraphLinksMmap {
+    fn get_links(&self, range: Range<usize>) -> &[PointOffsetType] {
+        &self.get_links_slice()[range]
+    }
+}
+
 impl GraphLinks for GraphLinksMmap {
+    const ON_DISK: bool = true;
+
     fn load_from_file(path: &Path) -> OperationResult<Self> {
         let file = OpenOptions::new()
             .read(true)
@@ -545,18 +771,68 @@ impl GraphLinks for GraphLinksMmap {
         }
     }
 
+    fn num_points(&self) -> usize {
+        self.header.point_count as usize
+    }
+
+    fn point_level(&self, point_id: PointOffsetType) -> usize {
+        self.get_point_level(point_id)
+    }
+
+    fn links_map<F>(&self, point_id: PointOffsetType, level: usize, mut f: F)
+    where
+        F: FnMut(PointOffsetType),
+    {
+        for link in self.links(point_id, level) {
+            f(*link);
+        }
+    }
+}
+
+/// Links, compressed to reduce the size of the graph on disk and in page cache.
+///
+/// Links of each point on each level are sorted, delta-encoded and stored as varints.
+/// Data is served directly from mmap, links are decoded on access.
+#[derive(Default)]
+pub struct GraphLinksCompressed {
+    mmap: Option<Arc<Mmap>>,
+    header: GraphLinksCompressedFileHeader,
+    level_offsets: Vec<u64>,
+}
+
+impl GraphLinksCompressed {
+    fn get_mmap(&self) -> &Mmap {
+        self.mmap.as_ref().expect(MMAP_PANIC_MESSAGE)
+    }
+
+    fn get_reindex_slice(&self) -> &[PointOffsetType] {
+        let reindex_byte_slice = &self.get_mmap()[self.header.get_reindex_range()];
+        mmap_ops::transmute_from_u8_to_slice(reindex_byte_slice)
+    }
+
+    fn get_offsets_slice(&self) -> &[u64] {
+        let offsets_byte_slice = &self.get_mmap()[self.header.get_offsets_range()];
+        mmap_ops::transmute_from_u8_to_slice(offsets_byte_slice)
+    }
+
+    fn get_links_bytes(&self) -> &[u8] {
+        &self.get_mmap()[self.header.get_links_range()]
+    }
+
+    pub fn prefault_mmap_pages(&self, path: &Path) -> Option<mmap_ops::PrefaultMmapPages> {
+        mmap_ops::PrefaultMmapPages::new(self.mmap.clone()?, Some(path)).into()
+    }
+}
+
+impl GraphLinksLayout for GraphLinksCompressed {
     fn offsets_len(&self) -> usize {
-        self.header.get_offsets_range().len() / size_of::<u64>()
+        self.header.total_offsets_len as usize
     }
 
     fn levels_count(&self) -> usize {
         self.level_offsets.len()
     }
 
-    fn get_links(&self, range: Range<usize>) -> &[PointOffsetType] {
-        &self.get_links_slice()[range]
-    }
-
     fn get_links_range(&self, idx: usize) -> Range<usize> {
         let offsets_slice = self.get_offsets_slice();
         offsets_slice[idx] as usize..offsets_slice[idx + 1] as usize
@@ -569,10 +845,101 @@ impl GraphLinks for GraphLinksMmap {
     fn reindex(&self, point_id: PointOffsetType) -> PointOffsetType {
         self.get_reindex_slice()[point_id as usize]
     }
+}
+
+impl GraphLinks for GraphLinksCompressed {
+    const LINKS_FILE: &'static str = HNSW_COMPRESSED_LINKS_FILE;
+    const ON_DISK: bool = true;
+
+    fn load_from_file(path: &Path) -> OperationResult<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(false)
+            .create(false)
+            .open(path)?;
+
+        let mmap = unsafe { Mmap::map(&file)? };
+        madvise::madvise(&mmap, madvise::get_global())?;
+
+        let header = GraphLinksCompressedFileHeader::deserialize_bytes_from(&mmap);
+        let level_offsets_byte_slice = &mmap[header.get_level_offsets_range()];
+        let level_offsets: &[u64] = mmap_ops::transmute_from_u8_to_slice(level_offsets_byte_slice);
+        let level_offsets = level_offsets.to_vec();
+
+        Ok(Self {
+            mmap: Some(Arc::new(mmap)),
+            header,
+            level_offsets,
+        })
+    }
+
+    fn from_converter(converter: GraphLinksConverter) -> OperationResult<Self> {
+        if let Some(path) = converter.path {
+            GraphLinksCompressed::load_from_file(&path)
+        } else {
+            Err(OperationError::service_error(
+                "HNSW links Data needs to be saved to file before it can be loaded as compressed",
+            ))
+        }
+    }
+
+    fn save_converter(converter: &mut GraphLinksConverter, path: &Path) -> OperationResult<()> {
+        converter.save_as_compressed(path)
+    }
 
     fn num_points(&self) -> usize {
         self.header.point_count as usize
     }
+
+    fn point_level(&self, point_id: PointOffsetType) -> usize {
+        self.get_point_level(point_id)
+    }
+
+    fn links_map<F>(&self, point_id: PointOffsetType, level: usize, f: F)
+    where
+        F: FnMut(PointOffsetType),
+    {
+        let links_range = self.get_point_links_range(point_id, level);
+        decompress_links(&self.get_links_bytes()[links_range], f);
+    }
+}
+
+/// Sort links, encode differences between consecutive links as varints and append to `output`
+fn compress_links(links: &[PointOffsetType], output: &mut Vec<u8>) {
+    let mut sorted_links = links.to_vec();
+    sorted_links.sort_unstable();
+
+    let mut previous = 0;
+    for link in sorted_links {
+        let mut delta = link - previous;
+        previous = link;
+        while delta >= 0x80 {
+            output.push(((delta as u8) & 0x7F) | 0x80);
+            delta >>= 7;
+        }
+        output.push(delta as u8);
+    }
+}
+
+/// Decode links, encoded by `compress_links`, calling `f` for each of them
+fn decompress_links<F>(data: &[u8], mut f: F)
+where
+    F: FnMut(PointOffsetType),
+{
+    let mut link: PointOffsetType = 0;
+    let mut delta: PointOffsetType = 0;
+    let mut shift = 0;
+    for &byte in data {
+        delta |= ((byte & 0x7F) as PointOffsetType) << shift;
+        if byte & 0x80 == 0 {
+            link += delta;
+            f(link);
+            delta = 0;
+            shift = 0;
+        } else {
+            shift += 7;
+        }
+    }
 }
 
 #[cfg(test)]
@@ -589,7 +956,7 @@ mod tests {
             let mut layers = Vec::new();
             let num_levels = links.point_level(i as PointOffsetType) + 1;
             for level in 0..num_levels {
-                let links = links.links(i as PointOffsetType, level).to_vec();
+                let links = links.links_vec(i as PointOffsetType, level);
                 layers.push(links);
             }
             result.push(layers);
@@ -704,4 +1071,36 @@ mod tests {
         test_save_load::<GraphLinksRam>(1000, 10);
         test_save_load::<GraphLinksMmap>(1000, 10);
     }
+
+    #[test]
+    fn test_graph_links_compressed() {
+        let path = Builder::new().prefix("graph_dir").tempdir().unwrap();
+        let links_file = path.path().join(GraphLinksCompressed::LINKS_FILE);
+
+        // no points
+        let links: Vec<Vec<Vec<PointOffsetType>>> = vec![];
+        let mut links_converter = GraphLinksConverter::new(links.clone());
+        GraphLinksCompressed::save_converter(&mut links_converter, &links_file).unwrap();
+        let cmp_links = to_vec(&GraphLinksCompressed::from_converter(links_converter).unwrap());
+        assert_eq!(links, cmp_links);
+
+        // large ids take multiple bytes
+        let mut links = random_links(1000, 10);
+        links[0][0] = vec![PointOffsetType::MAX, 0, 127, 128, 128, 16_384];
+
+        let mut links_converter = GraphLinksConverter::new(links.clone());
+        GraphLinksCompressed::save_converter(&mut links_converter, &links_file).unwrap();
+        let cmp_links = to_vec(&GraphLinksCompressed::load_from_file(&links_file).unwrap());
+
+        // compressed links are sorted
+        links
+            .iter_mut()
+            .flatten()
+            .for_each(|level_links| level_links.sort_unstable());
+        assert_eq!(links, cmp_links);
+
+        let plain_size = GraphLinksConverter::new(links.clone()).data_size();
+        let compressed_size = std::fs::metadata(&links_file).unwrap().len();
+        assert!(compressed_size < plain_size);
+    }
 }
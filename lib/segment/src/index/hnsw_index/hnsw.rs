@@ -1,19 +1,26 @@
+use std::collections::HashSet;
 use std::fs::create_dir_all;
+use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use atomic_refcell::AtomicRefCell;
+use bitvec::prelude::{BitSlice, BitVec};
 use common::types::{PointOffsetType, ScoredPointOffset};
 use log::debug;
 use memory::mmap_ops;
 use parking_lot::Mutex;
-use rand::thread_rng;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use rayon::prelude::*;
 use rayon::ThreadPool;
+use seahash::SeaHasher;
 
-use super::graph_links::{GraphLinks, GraphLinksMmap};
+use super::graph_links::{GraphLinks, GraphLinksCompressed, GraphLinksMmap, GraphLinksRam};
+use crate::common::build_progress::BuildProgress;
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
@@ -23,11 +30,14 @@ use crate::data_types::vectors::{QueryVector, Vector, VectorRef};
 use crate::id_tracker::{IdTracker, IdTrackerSS};
 use crate::index::hnsw_index::build_condition_checker::BuildConditionChecker;
 use crate::index::hnsw_index::config::HnswGraphConfig;
-use crate::index::hnsw_index::graph_layers::GraphLayers;
+use crate::index::hnsw_index::entry_points::PayloadEntryPoints;
+use crate::index::hnsw_index::graph_layers::{GraphCheckReport, GraphLayers, REMOVED_POINT};
 use crate::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
+use crate::index::hnsw_index::links_spill::HNSW_LINKS_SPILL_FILE;
 use crate::index::hnsw_index::max_rayon_threads;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::index::query_estimator::adjust_to_available_vectors;
+use crate::index::query_planner::{QueryPlanner, SearchPlan, SearchStrategy};
 use crate::index::sample_estimation::sample_check_cardinality;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
@@ -53,6 +63,23 @@ const SINGLE_THREADED_HNSW_BUILD_THRESHOLD: usize = 32;
 #[cfg(not(debug_assertions))]
 const SINGLE_THREADED_HNSW_BUILD_THRESHOLD: usize = 256;
 
+/// Number of points, inserted in parallel between checks for a build checkpoint.
+const HNSW_BUILD_CHUNK_SIZE: usize = 100_000;
+
+/// Minimal time between two build checkpoints.
+/// Saving a checkpoint copies the whole graph, so it should not happen too often.
+const HNSW_BUILD_CHECKPOINT_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Minimal fraction of points to index, which must be linked in the previous graph to reuse it.
+const HNSW_REUSE_GRAPH_MIN_RATIO: f64 = 0.9;
+
+/// Number of sampled queries, used to measure recall for `ef` calibration.
+const EF_CALIBRATION_QUERIES: usize = 32;
+/// Number of results per query, used to measure recall for `ef` calibration.
+const EF_CALIBRATION_TOP: usize = 10;
+/// Upper bound of calibrated `ef`, used if the target recall can't be reached.
+const EF_CALIBRATION_MAX_EF: usize = 1024;
+
 pub struct HNSWIndex<TGraphLinks: GraphLinks> {
     id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
     vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
@@ -61,7 +88,36 @@ pub struct HNSWIndex<TGraphLinks: GraphLinks> {
     config: HnswGraphConfig,
     path: PathBuf,
     graph: Option<GraphLayers<TGraphLinks>>,
+    payload_entry_points: PayloadEntryPoints,
     searches_telemetry: HNSWSearchesTelemetry,
+    /// Graph of the previous version of the segment, used as a starting point of the next build
+    previous_graph: Option<PreviousGraph>,
+    /// Directory to keep checkpoints of the build in, builds are not checkpointed if not set
+    build_checkpoint_dir: Option<PathBuf>,
+    /// Copy of `graph`, which points are linked into after the build, if the segment is appendable
+    ///
+    /// Created on the first update and searched instead of `graph` from then on.
+    /// It is not persisted: on open, points beyond `graph` are linked into it again.
+    mutable_graph: Option<GraphLayersBuilder>,
+}
+
+/// HNSW graph of another index, renumbered to point offsets of this one
+pub struct PreviousGraph {
+    graph: GraphLayers<GraphLinksRam>,
+    config: HnswGraphConfig,
+    /// Points, linked into the graph
+    linked: BitVec,
+}
+
+impl PreviousGraph {
+    pub fn num_linked(&self) -> usize {
+        self.linked.count_ones()
+    }
+
+    #[cfg(test)]
+    pub(super) fn graph(&self) -> &GraphLayers<GraphLinksRam> {
+        &self.graph
+    }
 }
 
 struct HNSWSearchesTelemetry {
@@ -99,7 +155,15 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 full_scan_threshold,
                 hnsw_config.max_indexing_threads,
                 hnsw_config.payload_m,
+                hnsw_config.payload_m_overrides.unwrap_or_default(),
                 available_vectors,
+                hnsw_config.seed,
+                hnsw_config.extend_candidates.unwrap_or_default(),
+                hnsw_config.keep_pruned_connections.unwrap_or_default(),
+                hnsw_config.target_recall,
+                hnsw_config.level_factor_multiplier,
+                hnsw_config.ef_construct_levels,
+                hnsw_config.refine.unwrap_or_default(),
             )
         };
 
@@ -110,7 +174,14 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         } else {
             None
         };
-        Ok(HNSWIndex {
+
+        let payload_entry_points_path = PayloadEntryPoints::get_path(path);
+        let payload_entry_points = if payload_entry_points_path.exists() {
+            PayloadEntryPoints::load(&payload_entry_points_path)?
+        } else {
+            PayloadEntryPoints::default()
+        };
+        let mut index = HNSWIndex {
             id_tracker,
             vector_storage,
             quantized_vectors,
@@ -118,6 +189,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             config,
             path: path.to_owned(),
             graph,
+            payload_entry_points,
             searches_telemetry: HNSWSearchesTelemetry {
                 unfiltered_hnsw: OperationDurationsAggregator::new(),
                 unfiltered_plain: OperationDurationsAggregator::new(),
@@ -126,7 +198,18 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 exact_filtered: OperationDurationsAggregator::new(),
                 exact_unfiltered: OperationDurationsAggregator::new(),
             },
-        })
+            previous_graph: None,
+            build_checkpoint_dir: None,
+            mutable_graph: None,
+        };
+
+        // Points beyond the graph were appended after the build
+        if let Some(graph) = &index.graph {
+            if graph.num_points() < index.vector_storage.borrow().total_vector_count() {
+                index.mutable_graph = Some(index.new_mutable_graph(graph)?);
+            }
+        }
+        Ok(index)
     }
 
     #[cfg(test)]
@@ -134,6 +217,278 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         self.graph.as_ref()
     }
 
+    /// Copy of the built graph, renumbered for another index with `old_to_new` offsets
+    ///
+    /// See `GraphLayers::remap_points` for the format of `old_to_new`.
+    /// Points, which are not moved to the other index, are healed out of the copy first,
+    /// so that their neighbours stay connected with each other.
+    pub fn previous_graph(
+        &self,
+        old_to_new: &[PointOffsetType],
+        stopped: &AtomicBool,
+    ) -> OperationResult<Option<PreviousGraph>> {
+        let Some(graph) = &self.graph else {
+            return Ok(None);
+        };
+        if self.config.m == 0 {
+            return Ok(None);
+        }
+
+        let removed_points: Vec<_> = (0..graph.num_points() as PointOffsetType)
+            .filter(|&point_id| {
+                old_to_new
+                    .get(point_id as usize)
+                    .map_or(true, |&new_id| new_id == REMOVED_POINT)
+            })
+            .collect();
+        let remapped = if removed_points.is_empty() {
+            graph.remapped_copy(old_to_new)?
+        } else {
+            self.healed_graph(graph, &removed_points, stopped)?
+                .remapped_copy(old_to_new)?
+        };
+
+        let mut linked = BitVec::repeat(false, remapped.num_points());
+        for &new_id in old_to_new.iter().take(graph.num_points()) {
+            if new_id != REMOVED_POINT {
+                linked.set(new_id as usize, true);
+            }
+        }
+
+        Ok(Some(PreviousGraph {
+            graph: remapped,
+            config: self.config.clone(),
+            linked,
+        }))
+    }
+
+    /// Copy of the graph without `points`, with their neighbours reconnected with each other
+    fn healed_graph(
+        &self,
+        graph: &GraphLayers<TGraphLinks>,
+        points: &[PointOffsetType],
+        stopped: &AtomicBool,
+    ) -> OperationResult<GraphLayers<GraphLinksRam>> {
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let quantized_vectors = self.quantized_vectors.borrow();
+
+        let mut graph_layers_builder =
+            GraphLayersBuilder::new_from_graph(graph, graph.num_points(), HNSW_USE_HEURISTIC);
+        graph_layers_builder.set_heuristic_params(
+            self.config.extend_candidates,
+            self.config.keep_pruned_connections,
+        );
+
+        check_process_stopped(stopped)?;
+        // Only internal scores are used, so any stored vector works as a query.
+        // Vectors of deleted points are kept in the storage.
+        let vector = vector_storage.get_vector(points[0]);
+        let vector = vector.as_vec_ref().into();
+        let raw_scorer = match quantized_vectors.as_ref() {
+            Some(quantized_storage) => quantized_storage.raw_scorer(
+                vector,
+                id_tracker.deleted_point_bitslice(),
+                vector_storage.deleted_vector_bitslice(),
+                stopped,
+            ),
+            None => new_raw_scorer(vector, &vector_storage, id_tracker.deleted_point_bitslice()),
+        }?;
+        graph_layers_builder.heal_removed_points(points, |a, b| raw_scorer.score_internal(a, b));
+        check_process_stopped(stopped)?;
+
+        debug!("healed {} removed points of HNSW graph", points.len());
+        graph_layers_builder.into_graph_layers(None)
+    }
+
+    /// Start the next build from `previous_graph`, instead of linking all points from scratch
+    ///
+    /// The graph is only used if it was built with the same params and most of the points,
+    /// which are not deleted, are linked in it. Only the rest of the points are inserted then.
+    pub fn set_previous_graph(&mut self, previous_graph: PreviousGraph) {
+        self.previous_graph = Some(previous_graph);
+    }
+
+    /// Periodically save the state of the next build into `dir`, to resume it after a restart
+    ///
+    /// The directory must outlive the index, e.g. if the index is built in a temporary segment,
+    /// which is removed on restart.
+    pub fn set_build_checkpoint_dir(&mut self, dir: PathBuf) {
+        self.build_checkpoint_dir = Some(dir);
+    }
+
+    /// Path of the build checkpoint in `dir` and fingerprint of the points to index
+    ///
+    /// The fingerprint covers the named vector, ids and versions of all points, so that
+    /// a checkpoint is only resumed by a build of exactly the same data, e.g. by the same
+    /// optimization, restarted after a crash.
+    fn build_checkpoint(
+        &self,
+        dir: &Path,
+        id_tracker: &IdTrackerSS,
+        vector_storage: &VectorStorageEnum,
+    ) -> (PathBuf, u64) {
+        let mut hasher = SeaHasher::new();
+        self.path.file_name().hash(&mut hasher);
+        vector_storage.vector_dim().hash(&mut hasher);
+        vector_storage.total_vector_count().hash(&mut hasher);
+        for point_id in id_tracker.iter_ids_excluding(vector_storage.deleted_vector_bitslice()) {
+            point_id.hash(&mut hasher);
+            id_tracker.external_id(point_id).hash(&mut hasher);
+            id_tracker.internal_version(point_id).hash(&mut hasher);
+        }
+        let fingerprint = hasher.finish();
+        (dir.join(format!("{fingerprint:016x}.bin")), fingerprint)
+    }
+
+    /// Prepare the previous graph to be extended, if it is worth reusing
+    ///
+    /// Deleted points are dropped from the graph.
+    /// Returns the graph and `old_to_new` mapping of its points, with unlinked ones removed.
+    fn reusable_graph(
+        &self,
+        previous_graph: PreviousGraph,
+        deleted_bitslice: &BitSlice,
+        available_vector_count: usize,
+    ) -> OperationResult<Option<(GraphLayers<GraphLinksRam>, Vec<PointOffsetType>)>> {
+        let PreviousGraph {
+            mut graph,
+            config,
+            linked,
+        } = previous_graph;
+        if !self.config.same_graph_params(&config) {
+            return Ok(None);
+        }
+
+        let old_to_new = linked
+            .iter()
+            .enumerate()
+            .map(|(point_id, linked)| {
+                let deleted = deleted_bitslice
+                    .get(point_id)
+                    .map_or(true, |deleted| *deleted);
+                if *linked && !deleted {
+                    point_id as PointOffsetType
+                } else {
+                    REMOVED_POINT
+                }
+            })
+            .collect::<Vec<_>>();
+        let reused_points = old_to_new
+            .iter()
+            .filter(|&&point_id| point_id != REMOVED_POINT)
+            .count();
+        if (reused_points as f64) < available_vector_count as f64 * HNSW_REUSE_GRAPH_MIN_RATIO {
+            return Ok(None);
+        }
+
+        graph.remap_points(&old_to_new)?;
+        Ok(Some((graph, old_to_new)))
+    }
+
+    /// Copy `graph` into a builder, heal deleted points out of it and link appended points into it
+    fn new_mutable_graph(
+        &self,
+        graph: &GraphLayers<TGraphLinks>,
+    ) -> OperationResult<GraphLayersBuilder> {
+        let mut graph_layers_builder =
+            GraphLayersBuilder::new_from_graph(graph, graph.num_points(), HNSW_USE_HEURISTIC);
+        graph_layers_builder.set_heuristic_params(
+            self.config.extend_candidates,
+            self.config.keep_pruned_connections,
+        );
+        if let Some(multiplier) = self.config.level_factor_multiplier {
+            graph_layers_builder.set_level_factor_multiplier(multiplier);
+        }
+        if let Some(ef_construct_levels) = &self.config.ef_construct_levels {
+            graph_layers_builder.set_ef_construct_levels(ef_construct_levels.clone());
+        }
+
+        let deleted_points: Vec<_> = (0..graph.num_points() as PointOffsetType)
+            .filter(|&point_id| self.is_deleted_point(point_id))
+            .collect();
+        self.heal_points(&graph_layers_builder, &deleted_points, true)?;
+
+        let total_vector_count = self.vector_storage.borrow().total_vector_count();
+        let appended_points: Vec<_> = (graph.num_points()..total_vector_count)
+            .map(|point_id| point_id as PointOffsetType)
+            .filter(|&point_id| !self.is_deleted_point(point_id))
+            .collect();
+        for &point_id in &appended_points {
+            self.link_point(&mut graph_layers_builder, point_id)?;
+        }
+        if !appended_points.is_empty() {
+            debug!(
+                "linked {} appended points into HNSW graph",
+                appended_points.len()
+            );
+        }
+        Ok(graph_layers_builder)
+    }
+
+    /// Remove deleted `points` from `graph_layers_builder`, reconnecting their neighbours
+    ///
+    /// See `GraphLayersBuilder::heal_removed_points` and `heal_removed_points_locally`.
+    fn heal_points(
+        &self,
+        graph_layers_builder: &GraphLayersBuilder,
+        points: &[PointOffsetType],
+        scan_all_points: bool,
+    ) -> OperationResult<()> {
+        let Some(&first_point) = points.first() else {
+            return Ok(());
+        };
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+
+        // Only internal scores are used, so any stored vector works as a query.
+        // Vectors of deleted points are kept in the storage.
+        let vector = vector_storage.get_vector(first_point);
+        let vector = vector.as_vec_ref().into();
+        let raw_scorer =
+            new_raw_scorer(vector, &vector_storage, id_tracker.deleted_point_bitslice())?;
+        let score_internal = |a, b| raw_scorer.score_internal(a, b);
+        if scan_all_points {
+            graph_layers_builder.heal_removed_points(points, score_internal);
+        } else {
+            graph_layers_builder.heal_removed_points_locally(points, score_internal);
+        }
+        Ok(())
+    }
+
+    /// Link a point into `graph_layers_builder`, replacing its links if it is linked already
+    fn link_point(
+        &self,
+        graph_layers_builder: &mut GraphLayersBuilder,
+        point_id: PointOffsetType,
+    ) -> OperationResult<()> {
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        // Linking is a part of the update operation, it is not cancelled
+        let stopped = AtomicBool::new(false);
+
+        let vector = vector_storage.get_vector(point_id);
+        let vector = vector.as_vec_ref().into();
+        let raw_scorer =
+            new_raw_scorer(vector, &vector_storage, id_tracker.deleted_point_bitslice())?;
+        let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), None);
+
+        let is_linked = (point_id as usize) < graph_layers_builder.num_points()
+            && graph_layers_builder.is_ready(point_id);
+        if is_linked {
+            return graph_layers_builder.relink_point(point_id, points_scorer, &stopped);
+        }
+
+        // Levels of appended points don't depend on the order of updates, if the seed is set
+        let mut rng = match self.config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed.wrapping_add(u64::from(point_id))),
+            None => StdRng::from_entropy(),
+        };
+        let level = graph_layers_builder.get_random_layer(&mut rng);
+        graph_layers_builder.set_levels(point_id, level);
+        graph_layers_builder.link_new_point(point_id, points_scorer, &stopped)
+    }
+
     pub fn get_quantized_vectors(&self) -> Arc<AtomicRefCell<Option<QuantizedVectors>>> {
         self.quantized_vectors.clone()
     }
@@ -146,7 +501,9 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
     fn save_graph(&self) -> OperationResult<()> {
         let graph_path = GraphLayers::<TGraphLinks>::get_path(&self.path);
         if let Some(graph) = &self.graph {
-            graph.save(&graph_path)
+            graph.save(&graph_path)?;
+            self.payload_entry_points
+                .save(&PayloadEntryPoints::get_path(&self.path))
         } else {
             Ok(())
         }
@@ -223,8 +580,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             let points_scorer =
                 FilteredScorer::new(raw_scorer.as_ref(), Some(&block_condition_checker));
 
-            graph_layers_builder.link_new_point(block_point_id, points_scorer);
-            Ok::<_, OperationError>(())
+            graph_layers_builder.link_new_point(block_point_id, points_scorer, stopped)
         };
 
         let first_points = points_to_index
@@ -249,6 +605,116 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         Ok(())
     }
 
+    fn is_deleted_point(&self, point_id: PointOffsetType) -> bool {
+        self.id_tracker.borrow().is_deleted_point(point_id)
+            || self.vector_storage.borrow().is_deleted_vector(point_id)
+    }
+
+    /// Validate structure of the built graph, `None` if the graph is not built
+    pub fn check_graph(&self) -> Option<GraphCheckReport> {
+        let graph = self.graph.as_ref()?;
+        Some(graph.check_graph(|point_id| self.is_deleted_point(point_id)))
+    }
+
+    /// Re-link points, which are not reachable from entry points of the graph
+    ///
+    /// Useful after crashes or partial disk corruption.
+    /// Returns the result of the check made before the repair, `None` if the graph is not built.
+    pub fn repair_graph(
+        &mut self,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Option<GraphCheckReport>> {
+        let Some(report) = self.check_graph() else {
+            return Ok(None);
+        };
+        if report.unreachable_points.is_empty() {
+            return Ok(Some(report));
+        }
+        if self.config.m == 0 {
+            // Without the main graph, points outside of payload blocks are expected to be unlinked
+            return Err(OperationError::service_error(
+                "Cannot repair HNSW graph without main graph (m = 0)",
+            ));
+        }
+        let Some(graph) = &self.graph else {
+            return Ok(None);
+        };
+
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let quantized_vectors = self.quantized_vectors.borrow();
+
+        let mut graph_layers_builder =
+            GraphLayersBuilder::new_from_graph(graph, graph.num_points(), HNSW_USE_HEURISTIC);
+        graph_layers_builder.set_heuristic_params(
+            self.config.extend_candidates,
+            self.config.keep_pruned_connections,
+        );
+
+        for &point_id in &report.unreachable_points {
+            check_process_stopped(stopped)?;
+            let vector = vector_storage.get_vector(point_id);
+            let vector = vector.as_vec_ref().into();
+            let raw_scorer = match quantized_vectors.as_ref() {
+                Some(quantized_storage) => quantized_storage.raw_scorer(
+                    vector,
+                    id_tracker.deleted_point_bitslice(),
+                    vector_storage.deleted_vector_bitslice(),
+                    stopped,
+                ),
+                None => {
+                    new_raw_scorer(vector, &vector_storage, id_tracker.deleted_point_bitslice())
+                }
+            }?;
+            let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), None);
+            graph_layers_builder.relink_point(point_id, points_scorer, stopped)?;
+        }
+
+        let graph_links_path = GraphLayers::<TGraphLinks>::get_links_path(&self.path);
+        let graph = graph_layers_builder.into_graph_layers(Some(&graph_links_path))?;
+        // Appended points are linked into the copy of the repaired graph again
+        if self.mutable_graph.is_some() {
+            self.mutable_graph = Some(self.new_mutable_graph(&graph)?);
+        }
+        self.graph = Some(graph);
+
+        debug!(
+            "relinked {} unreachable points of HNSW graph",
+            report.unreachable_points.len()
+        );
+        self.save()?;
+        Ok(Some(report))
+    }
+
+    /// Unlink a deleted point from the graph, reconnecting its neighbours with each other
+    ///
+    /// Only the neighbourhood of the point is repaired,
+    /// see `GraphLayersBuilder::heal_removed_points_locally`.
+    /// Like in `update_vector`, the built graph is not modified: the point is removed from its copy.
+    pub fn remove_vector(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        let Some(graph) = &self.graph else {
+            return Ok(());
+        };
+        if self.config.m == 0 || self.quantized_vectors.borrow().is_some() {
+            // Not appendable, the point is healed when the graph is reused by the next build
+            return Ok(());
+        }
+
+        let Some(mutable_graph) = self.mutable_graph.take() else {
+            // Deleted points, including this one, are healed on creation
+            self.mutable_graph = Some(self.new_mutable_graph(graph)?);
+            return Ok(());
+        };
+        let is_linked = (id as usize) < mutable_graph.num_points() && mutable_graph.is_ready(id);
+        let result = if is_linked {
+            self.heal_points(&mutable_graph, &[id], false)
+        } else {
+            Ok(())
+        };
+        self.mutable_graph = Some(mutable_graph);
+        result
+    }
+
     fn search_with_graph(
         &self,
         vector: &QueryVector,
@@ -256,6 +722,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         top: usize,
         params: Option<&SearchParams>,
         custom_entry_points: Option<&[PointOffsetType]>,
+        filter_bitmap: Option<&BitSlice>,
         is_stopped: &AtomicBool,
     ) -> OperationResult<Vec<ScoredPointOffset>> {
         let ef = params
@@ -277,17 +744,57 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         )?;
         let oversampled_top = Self::get_oversampled_top(quantized_vectors.as_ref(), params, top);
 
-        let filter_context = filter.map(|f| payload_index.filter_context(f));
-        let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), filter_context.as_deref());
+        let filter_context = filter
+            .filter(|_| filter_bitmap.is_none())
+            .map(|f| payload_index.filter_context(f));
+        let points_scorer = match filter_bitmap {
+            Some(filter_bitmap) => {
+                FilteredScorer::new_with_bitmap(raw_scorer.as_ref(), filter_bitmap)
+            }
+            None => FilteredScorer::new(raw_scorer.as_ref(), filter_context.as_deref()),
+        };
 
-        match &self.graph {
-            Some(graph) => {
-                let search_result =
-                    graph.search(oversampled_top, ef, points_scorer, custom_entry_points);
-                self.postprocess_search_result(search_result, vector, params, top, is_stopped)
+        // Start filtered search inside the subgraph of the matching payload block, if known
+        let payload_entry_points = match (custom_entry_points, filter) {
+            (None, Some(filter)) if !self.payload_entry_points.is_empty() => {
+                self.payload_entry_points.get_for_filter(filter)
             }
-            None => Ok(Default::default()),
-        }
+            _ => vec![],
+        };
+        let custom_entry_points = custom_entry_points.or_else(|| {
+            (!payload_entry_points.is_empty()).then_some(payload_entry_points.as_slice())
+        });
+
+        // Two-hop expansion only makes a difference if some points are rejected by the filter
+        let acorn = filter.is_some() && params.map(|params| params.acorn).unwrap_or(false);
+
+        // Quantized scores are approximate, so they can't be compared with the threshold
+        let score_threshold = params
+            .and_then(|params| params.score_threshold)
+            .filter(|_| !Self::is_quantized_search(quantized_vectors.as_ref(), params));
+
+        let search_result = match (&self.mutable_graph, &self.graph) {
+            (Some(mutable_graph), _) => mutable_graph.search_with_options(
+                oversampled_top,
+                ef,
+                points_scorer,
+                custom_entry_points,
+                acorn,
+                score_threshold,
+                is_stopped,
+            )?,
+            (None, Some(graph)) => graph.search_with_options(
+                oversampled_top,
+                ef,
+                points_scorer,
+                custom_entry_points,
+                acorn,
+                score_threshold,
+                is_stopped,
+            )?,
+            (None, None) => return Ok(Default::default()),
+        };
+        self.postprocess_search_result(search_result, vector, params, top, is_stopped)
     }
 
     fn search_vectors_with_graph(
@@ -298,6 +805,14 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         params: Option<&SearchParams>,
         is_stopped: &AtomicBool,
     ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        // share materialized filter for all query vectors
+        let ef = params
+            .and_then(|params| params.hnsw_ef)
+            .unwrap_or(self.config.ef);
+        let filter_bitmap =
+            filter.and_then(|filter| self.materialize_filter(filter, vectors.len() * ef));
+        let filter_bitmap = filter_bitmap.as_deref();
+
         vectors
             .iter()
             .map(|&vector| match vector {
@@ -306,13 +821,83 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                     filter,
                     top,
                     params,
+                    filter_bitmap,
+                    is_stopped,
+                ),
+                other => self.search_with_graph(
+                    other,
+                    filter,
+                    top,
+                    params,
+                    None,
+                    filter_bitmap,
                     is_stopped,
                 ),
-                other => self.search_with_graph(other, filter, top, params, None, is_stopped),
             })
             .collect()
     }
 
+    /// Evaluate the filter into a bitmap of matching points, if it is expected to be cheaper
+    /// than checking the filter for every point visited by the graph search
+    ///
+    /// Only filters, which could be resolved with the payload index, are materialized.
+    /// The graph search is expected to check about `m0` neighbours of each of `visits` points.
+    fn materialize_filter(&self, filter: &Filter, visits: usize) -> Option<BitVec> {
+        let payload_index = self.payload_index.borrow();
+        let cardinality = payload_index.estimate_cardinality(filter);
+        let expected_checks = visits.saturating_mul(self.config.m0);
+        if cardinality.primary_clauses.is_empty() || cardinality.exp > expected_checks {
+            return None;
+        }
+        let num_points = self.id_tracker.borrow().total_point_count();
+        Some(FilteredScorer::materialize_filter(
+            payload_index.query_points(filter),
+            num_points,
+        ))
+    }
+
+    /// Choose the strategy of the search with the given filter and params
+    ///
+    /// Exact search never uses the graph.
+    pub fn search_plan(
+        &self,
+        filter: Option<&Filter>,
+        params: Option<&SearchParams>,
+    ) -> SearchPlan {
+        let exact = params.map(|params| params.exact).unwrap_or(false);
+        let planner = QueryPlanner::new(self.config.full_scan_threshold);
+        let use_graph = !exact && self.graph.is_some();
+
+        let vector_storage = self.vector_storage.borrow();
+        let available_vector_count = vector_storage.available_vector_count();
+        let Some(filter) = filter else {
+            return planner.plan_unfiltered(available_vector_count, use_graph);
+        };
+
+        let payload_index = self.payload_index.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        let query_cardinality = adjust_to_available_vectors(
+            payload_index.estimate_cardinality(filter),
+            available_vector_count,
+            id_tracker.available_point_count(),
+        );
+        planner.plan(
+            &query_cardinality,
+            available_vector_count,
+            use_graph,
+            |threshold| {
+                // Fast cardinality estimation is not enough, do sample estimation of cardinality
+                let filter_context = payload_index.filter_context(filter);
+                sample_check_cardinality(
+                    id_tracker.sample_ids(Some(vector_storage.deleted_vector_bitslice())),
+                    |idx| filter_context.check(idx),
+                    threshold,
+                    available_vector_count, // Check cardinality among available vectors
+                )
+            },
+        )
+    }
+
     fn search_plain(
         &self,
         vector: &QueryVector,
@@ -345,13 +930,27 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         &self,
         vectors: &[&QueryVector],
         filter: &Filter,
+        strategy: SearchStrategy,
         top: usize,
         params: Option<&SearchParams>,
         is_stopped: &AtomicBool,
     ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
         let payload_index = self.payload_index.borrow();
         // share filtered points for all query vectors
-        let filtered_points = payload_index.query_points(filter);
+        let filtered_points = match strategy {
+            SearchStrategy::FullScan => {
+                let id_tracker = self.id_tracker.borrow();
+                let vector_storage = self.vector_storage.borrow();
+                let filter_context = payload_index.filter_context(filter);
+                id_tracker
+                    .iter_ids_excluding(vector_storage.deleted_vector_bitslice())
+                    .filter(|&idx| filter_context.check(idx))
+                    .collect()
+            }
+            SearchStrategy::PayloadIndex | SearchStrategy::Graph => {
+                payload_index.query_points(filter)
+            }
+        };
         vectors
             .iter()
             .map(|vector| self.search_plain(vector, &filtered_points, top, params, is_stopped))
@@ -364,6 +963,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         filter: Option<&Filter>,
         top: usize,
         params: Option<&SearchParams>,
+        filter_bitmap: Option<&BitSlice>,
         is_stopped: &AtomicBool,
     ) -> OperationResult<Vec<ScoredPointOffset>> {
         // Stage 1: Find best entry points using Context search
@@ -378,6 +978,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 DISCOVERY_ENTRY_POINT_COUNT,
                 params,
                 None,
+                filter_bitmap,
                 is_stopped,
             )
             .map(|search_result| search_result.iter().map(|x| x.idx).collect())?;
@@ -391,6 +992,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             top,
             params,
             Some(&custom_entry_points),
+            filter_bitmap,
             is_stopped,
         )
     }
@@ -483,8 +1085,8 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 is_stopped,
             )?;
 
-            let mut ids_iterator = search_result.iter().map(|x| x.idx);
-            let mut re_scored = raw_scorer.score_points_unfiltered(&mut ids_iterator);
+            let ids: Vec<_> = search_result.iter().map(|x| x.idx).collect();
+            let mut re_scored = raw_scorer.rescore_points(&ids);
 
             re_scored.sort_unstable();
             re_scored.reverse();
@@ -495,6 +1097,91 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         postprocess_result.truncate(top);
         Ok(postprocess_result)
     }
+
+    /// Find the smallest `ef`, for which graph search reaches `target_recall`
+    ///
+    /// Recall is measured against exact search, using a sample of indexed vectors as queries.
+    /// The query point itself is excluded from both exact and graph results, as it is always
+    /// found by the graph search and would inflate the measured recall.
+    fn calibrate_ef(&self, target_recall: f64, stopped: &AtomicBool) -> OperationResult<usize> {
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+
+        let queries: Vec<(PointOffsetType, QueryVector)> = id_tracker
+            .sample_ids(Some(vector_storage.deleted_vector_bitslice()))
+            .take(EF_CALIBRATION_QUERIES)
+            .map(|point_id| {
+                let vector = vector_storage.get_vector(point_id);
+                (point_id, vector.as_vec_ref().into())
+            })
+            .collect();
+
+        let ground_truth = queries
+            .iter()
+            .map(|(query_id, query)| {
+                new_stoppable_raw_scorer(
+                    query.to_owned(),
+                    &vector_storage,
+                    id_tracker.deleted_point_bitslice(),
+                    stopped,
+                )
+                .map(|scorer| {
+                    scorer
+                        .peek_top_all(EF_CALIBRATION_TOP + 1)
+                        .into_iter()
+                        .map(|scored| scored.idx)
+                        .filter(|idx| idx != query_id)
+                        .take(EF_CALIBRATION_TOP)
+                        .collect::<HashSet<_>>()
+                })
+            })
+            .collect::<OperationResult<Vec<_>>>()?;
+
+        let expected_count: usize = ground_truth.iter().map(|ids| ids.len()).sum();
+        if expected_count == 0 {
+            return Ok(self.config.ef);
+        }
+
+        let measure_recall = |ef: usize| -> OperationResult<f64> {
+            let params = SearchParams {
+                hnsw_ef: Some(ef),
+                ..Default::default()
+            };
+            let mut found_count = 0;
+            for ((query_id, query), expected_ids) in queries.iter().zip(&ground_truth) {
+                check_process_stopped(stopped)?;
+                let search_result = self.search_with_graph(
+                    query,
+                    None,
+                    EF_CALIBRATION_TOP + 1,
+                    Some(&params),
+                    None,
+                    None,
+                    stopped,
+                )?;
+                found_count += search_result
+                    .iter()
+                    .filter(|scored| scored.idx != *query_id)
+                    .take(EF_CALIBRATION_TOP)
+                    .filter(|scored| expected_ids.contains(&scored.idx))
+                    .count();
+            }
+            Ok(found_count as f64 / expected_count as f64)
+        };
+
+        // Recall grows with `ef`, so look for the smallest sufficient value with binary search
+        let mut low = EF_CALIBRATION_TOP;
+        let mut high = EF_CALIBRATION_MAX_EF;
+        while low < high {
+            let mid = (low + high) / 2;
+            if measure_recall(mid)? >= target_recall {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        Ok(low)
+    }
 }
 
 impl HNSWIndex<GraphLinksMmap> {
@@ -503,6 +1190,12 @@ impl HNSWIndex<GraphLinksMmap> {
     }
 }
 
+impl HNSWIndex<GraphLinksCompressed> {
+    pub fn prefault_mmap_pages(&self) -> Option<mmap_ops::PrefaultMmapPages> {
+        self.graph.as_ref()?.prefault_mmap_pages(&self.path)
+    }
+}
+
 impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
     fn search(
         &self,
@@ -513,160 +1206,198 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
         is_stopped: &AtomicBool,
     ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
         let exact = params.map(|params| params.exact).unwrap_or(false);
-        match filter {
-            None => {
+        // depending on the amount of filtered-out points the optimal strategy could be
+        // - to retrieve possible points and score them after
+        // - to use HNSW index with filtering condition
+        let plan = self.search_plan(filter, params);
+        match (filter, plan.strategy) {
+            (None, SearchStrategy::Graph) => {
+                let _timer = ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_hnsw);
+                self.search_vectors_with_graph(vectors, None, top, params, is_stopped)
+            }
+            (None, _) => {
                 let id_tracker = self.id_tracker.borrow();
                 let vector_storage = self.vector_storage.borrow();
 
-                // Determine whether to do a plain or graph search, and pick search timer aggregator
                 // Because an HNSW graph is built, we'd normally always assume to search the graph.
                 // But because a lot of points may be deleted in this graph, it may just be faster
                 // to do a plain search instead.
-                let plain_search = exact
-                    || vector_storage.available_vector_count() < self.config.full_scan_threshold;
-
-                // Do plain or graph search
-                if plain_search {
-                    let _timer = ScopeDurationMeasurer::new(if exact {
-                        &self.searches_telemetry.exact_unfiltered
-                    } else {
-                        &self.searches_telemetry.unfiltered_plain
-                    });
-                    vectors
-                        .iter()
-                        .map(|&vector| {
-                            new_stoppable_raw_scorer(
-                                vector.to_owned(),
-                                &vector_storage,
-                                id_tracker.deleted_point_bitslice(),
-                                is_stopped,
-                            )
-                            .map(|scorer| scorer.peek_top_all(top))
-                        })
-                        .collect()
+                let _timer = ScopeDurationMeasurer::new(if exact {
+                    &self.searches_telemetry.exact_unfiltered
                 } else {
-                    let _timer =
-                        ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_hnsw);
-                    self.search_vectors_with_graph(vectors, None, top, params, is_stopped)
-                }
+                    &self.searches_telemetry.unfiltered_plain
+                });
+                vectors
+                    .iter()
+                    .map(|&vector| {
+                        new_stoppable_raw_scorer(
+                            vector.to_owned(),
+                            &vector_storage,
+                            id_tracker.deleted_point_bitslice(),
+                            is_stopped,
+                        )
+                        .map(|scorer| scorer.peek_top_all(top))
+                    })
+                    .collect()
             }
-            Some(query_filter) => {
-                // depending on the amount of filtered-out points the optimal strategy could be
-                // - to retrieve possible points and score them after
-                // - to use HNSW index with filtering condition
-
-                // if exact search is requested, we should not use HNSW index
-                if exact {
-                    let exact_params = params.map(|params| {
-                        let mut params = *params;
-                        params.quantization = Some(QuantizationSearchParams {
-                            ignore: true,
-                            rescore: Some(false),
-                            oversampling: None,
-                        }); // disable quantization for exact search
-                        params
-                    });
-                    let _timer =
-                        ScopeDurationMeasurer::new(&self.searches_telemetry.exact_filtered);
-                    return self.search_vectors_plain(
-                        vectors,
-                        query_filter,
-                        top,
-                        exact_params.as_ref(),
-                        is_stopped,
-                    );
-                }
-
-                let payload_index = self.payload_index.borrow();
-                let vector_storage = self.vector_storage.borrow();
-                let id_tracker = self.id_tracker.borrow();
-                let available_vector_count = vector_storage.available_vector_count();
-                let query_point_cardinality = payload_index.estimate_cardinality(query_filter);
-                let query_cardinality = adjust_to_available_vectors(
-                    query_point_cardinality,
-                    available_vector_count,
-                    id_tracker.available_point_count(),
-                );
-
-                if query_cardinality.max < self.config.full_scan_threshold {
-                    // if cardinality is small - use plain index
-                    let _timer =
-                        ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
-                    return self.search_vectors_plain(
-                        vectors,
-                        query_filter,
-                        top,
-                        params,
-                        is_stopped,
-                    );
-                }
-
-                if query_cardinality.min > self.config.full_scan_threshold {
-                    // if cardinality is high enough - use HNSW index
-                    let _timer =
-                        ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
-                    return self
-                        .search_vectors_with_graph(vectors, filter, top, params, is_stopped);
-                }
-
-                let filter_context = payload_index.filter_context(query_filter);
-
-                // Fast cardinality estimation is not enough, do sample estimation of cardinality
-                let id_tracker = self.id_tracker.borrow();
-                if sample_check_cardinality(
-                    id_tracker.sample_ids(Some(vector_storage.deleted_vector_bitslice())),
-                    |idx| filter_context.check(idx),
-                    self.config.full_scan_threshold,
-                    available_vector_count, // Check cardinality among available vectors
-                ) {
-                    // if cardinality is high enough - use HNSW index
-                    let _timer =
-                        ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
-                    self.search_vectors_with_graph(vectors, filter, top, params, is_stopped)
-                } else {
-                    // if cardinality is small - use plain index
-                    let _timer =
-                        ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
-                    self.search_vectors_plain(vectors, query_filter, top, params, is_stopped)
-                }
+            (Some(_), SearchStrategy::Graph) => {
+                let _timer = ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
+                self.search_vectors_with_graph(vectors, filter, top, params, is_stopped)
+            }
+            (Some(query_filter), strategy) if exact => {
+                let exact_params = params.map(|params| {
+                    let mut params = *params;
+                    params.quantization = Some(QuantizationSearchParams {
+                        ignore: true,
+                        rescore: Some(false),
+                        oversampling: None,
+                    }); // disable quantization for exact search
+                    params
+                });
+                let _timer = ScopeDurationMeasurer::new(&self.searches_telemetry.exact_filtered);
+                self.search_vectors_plain(
+                    vectors,
+                    query_filter,
+                    strategy,
+                    top,
+                    exact_params.as_ref(),
+                    is_stopped,
+                )
+            }
+            (Some(query_filter), strategy) => {
+                // if cardinality is small - use plain index
+                let _timer = ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
+                self.search_vectors_plain(vectors, query_filter, strategy, top, params, is_stopped)
             }
         }
     }
 
     fn build_index(&mut self, stopped: &AtomicBool) -> OperationResult<()> {
+        self.build_index_with_progress(stopped, &BuildProgress::default())
+    }
+
+    fn build_index_with_progress(
+        &mut self,
+        stopped: &AtomicBool,
+        progress: &BuildProgress,
+    ) -> OperationResult<()> {
+        let previous_graph = self.previous_graph.take();
+
         // Build main index graph
         let id_tracker = self.id_tracker.borrow();
         let vector_storage = self.vector_storage.borrow();
         let quantized_vectors = self.quantized_vectors.borrow();
-        let mut rng = thread_rng();
+        let mut rng = match self.config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
 
         let total_vector_count = vector_storage.total_vector_count();
         let deleted_bitslice = vector_storage.deleted_vector_bitslice();
 
+        // If both vectors and graph are on disk, the graph is not expected to fit into memory
+        // either, so level 0 links are kept in a temporary file during the build
+        let spill_links = TGraphLinks::ON_DISK && vector_storage.is_on_disk();
+
+        // Spilled links are not supported by a builder, created from an existing graph
+        let reusable_graph = match previous_graph {
+            Some(previous_graph) if !spill_links => self.reusable_graph(
+                previous_graph,
+                deleted_bitslice,
+                vector_storage.available_vector_count(),
+            )?,
+            _ => None,
+        };
+
         debug!("building HNSW for {} vectors", total_vector_count);
         let indexing_threshold = self.config.full_scan_threshold;
-        let mut graph_layers_builder = GraphLayersBuilder::new(
-            total_vector_count,
-            self.config.m,
-            self.config.m0,
-            self.config.ef_construct,
-            (total_vector_count
-                .checked_div(indexing_threshold)
-                .unwrap_or(0)
-                * 10)
-                .max(1),
-            HNSW_USE_HEURISTIC,
+        let mut graph_layers_builder = if let Some((graph, old_to_new)) = &reusable_graph {
+            debug!(
+                "reusing previous HNSW graph of {} points",
+                graph.num_points()
+            );
+            let graph_layers_builder =
+                GraphLayersBuilder::new_from_graph(graph, total_vector_count, HNSW_USE_HEURISTIC);
+            // Points, which are not linked in the graph, are inserted as new ones
+            for (point_id, &new_id) in old_to_new.iter().enumerate().take(graph.num_points()) {
+                if new_id == REMOVED_POINT {
+                    graph_layers_builder.set_unlinked(point_id as PointOffsetType);
+                }
+            }
+            graph_layers_builder
+        } else {
+            GraphLayersBuilder::new_with_params(
+                total_vector_count,
+                self.config.m,
+                self.config.m0,
+                self.config.ef_construct,
+                (total_vector_count
+                    .checked_div(indexing_threshold)
+                    .unwrap_or(0)
+                    * 10)
+                    .max(1),
+                HNSW_USE_HEURISTIC,
+                !spill_links,
+            )
+        };
+        drop(reusable_graph);
+        graph_layers_builder.set_heuristic_params(
+            self.config.extend_candidates,
+            self.config.keep_pruned_connections,
         );
+        if let Some(multiplier) = self.config.level_factor_multiplier {
+            graph_layers_builder.set_level_factor_multiplier(multiplier);
+        }
+        if let Some(ef_construct_levels) = &self.config.ef_construct_levels {
+            graph_layers_builder.set_ef_construct_levels(ef_construct_levels.clone());
+        }
+        if spill_links {
+            debug!("spilling HNSW level 0 links to disk");
+            graph_layers_builder.spill_links(&self.path.join(HNSW_LINKS_SPILL_FILE))?;
+        } else {
+            // Links are kept in memory anyway, so spend some more of it to avoid re-scoring them
+            graph_layers_builder.cache_link_scores();
+        }
 
+        // Seeded build must insert points in a deterministic order, so it uses a single thread
+        let num_threads = if self.config.seed.is_some() {
+            1
+        } else {
+            max_rayon_threads(self.config.max_indexing_threads)
+        };
         let pool = rayon::ThreadPoolBuilder::new()
             .thread_name(|idx| format!("hnsw-build-{idx}"))
-            .num_threads(max_rayon_threads(self.config.max_indexing_threads))
+            .num_threads(num_threads)
             .build()?;
 
-        for vector_id in id_tracker.iter_ids_excluding(deleted_bitslice) {
-            check_process_stopped(stopped)?;
-            let level = graph_layers_builder.get_random_layer(&mut rng);
-            graph_layers_builder.set_levels(vector_id, level);
+        let checkpoint = self
+            .build_checkpoint_dir
+            .as_deref()
+            .map(|dir| self.build_checkpoint(dir, &id_tracker, &vector_storage));
+        let resumed = match &checkpoint {
+            Some((checkpoint_path, fingerprint)) => graph_layers_builder
+                .load_checkpoint(checkpoint_path, *fingerprint)
+                .unwrap_or_else(|err| {
+                    log::warn!(
+                        "failed to load HNSW build checkpoint, building from scratch: {err}"
+                    );
+                    false
+                }),
+            None => false,
+        };
+
+        if resumed {
+            debug!("resuming HNSW build from checkpoint");
+        } else {
+            for vector_id in id_tracker.iter_ids_excluding(deleted_bitslice) {
+                check_process_stopped(stopped)?;
+                // Points of the reused graph keep their levels
+                if graph_layers_builder.is_ready(vector_id) {
+                    continue;
+                }
+                let level = graph_layers_builder.get_random_layer(&mut rng);
+                graph_layers_builder.set_levels(vector_id, level);
+            }
         }
 
         let mut indexed_vectors = 0;
@@ -682,11 +1413,24 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
 
             indexed_vectors = ids.len() + first_few_ids.len();
 
-            let insert_point = |vector_id| {
+            // Points, linked before the checkpoint, are already in the graph
+            let first_few_ids: Vec<_> = first_few_ids
+                .into_iter()
+                .filter(|&vector_id| !graph_layers_builder.is_ready(vector_id))
+                .collect();
+            let ids: Vec<_> = ids
+                .into_iter()
+                .filter(|&vector_id| !graph_layers_builder.is_ready(vector_id))
+                .collect();
+
+            progress.start(first_few_ids.len() + ids.len());
+            graph_layers_builder.set_progress(progress.clone());
+
+            let raw_scorer = |vector_id| {
                 check_process_stopped(stopped)?;
                 let vector = vector_storage.get_vector(vector_id);
                 let vector = vector.as_vec_ref().into();
-                let raw_scorer = if let Some(quantized_storage) = quantized_vectors.as_ref() {
+                if let Some(quantized_storage) = quantized_vectors.as_ref() {
                     quantized_storage.raw_scorer(
                         vector,
                         id_tracker.deleted_point_bitslice(),
@@ -695,19 +1439,37 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     )
                 } else {
                     new_raw_scorer(vector, &vector_storage, id_tracker.deleted_point_bitslice())
-                }?;
-                let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), None);
-
-                graph_layers_builder.link_new_point(vector_id, points_scorer);
-                Ok::<_, OperationError>(())
+                }
             };
 
             for vector_id in first_few_ids {
-                insert_point(vector_id)?;
+                let raw_scorer = raw_scorer(vector_id)?;
+                let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), None);
+                graph_layers_builder.link_new_point(vector_id, points_scorer, stopped)?;
             }
 
-            if !ids.is_empty() {
-                pool.install(|| ids.into_par_iter().try_for_each(insert_point))?;
+            // Insert points in chunks, to periodically save a checkpoint in between
+            let mut last_checkpoint = Instant::now();
+            for chunk in ids.chunks(HNSW_BUILD_CHUNK_SIZE) {
+                pool.install(|| graph_layers_builder.link_new_points(chunk, &raw_scorer, stopped))?;
+
+                let Some((checkpoint_path, fingerprint)) = &checkpoint else {
+                    continue;
+                };
+                if last_checkpoint.elapsed() >= HNSW_BUILD_CHECKPOINT_INTERVAL {
+                    debug!("saving HNSW build checkpoint");
+                    if let Some(dir) = checkpoint_path.parent() {
+                        create_dir_all(dir)?;
+                    }
+                    graph_layers_builder.save_checkpoint(checkpoint_path, *fingerprint)?;
+                    last_checkpoint = Instant::now();
+                }
+            }
+
+            if self.config.refine {
+                debug!("refining main graph");
+                let ids: Vec<_> = id_tracker.iter_ids_excluding(deleted_bitslice).collect();
+                pool.install(|| graph_layers_builder.refine_points(&ids, &raw_scorer))?;
             }
 
             debug!("finish main graph");
@@ -721,8 +1483,10 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
 
         let payload_index = self.payload_index.borrow();
         let payload_m = self.config.payload_m.unwrap_or(self.config.m);
+        let mut payload_entry_points = PayloadEntryPoints::default();
+        let payload_m0 = self.config.payload_m0.unwrap_or(self.config.m0);
 
-        if payload_m > 0 {
+        if payload_m > 0 || self.config.payload_m_overrides.values().any(|&m| m > 0) {
             // Calculate true average number of links per vertex in the HNSW graph
             // to better estimate percolation threshold
             let average_links_per_0_level =
@@ -730,6 +1494,15 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
             let average_links_per_0_level_int = (average_links_per_0_level as usize).max(1);
 
             for (field, _) in payload_index.indexed_fields() {
+                let (field_m, field_m0) = match self.config.payload_m_overrides.get(&field) {
+                    Some(&field_m) => (field_m, field_m * 2),
+                    None => (payload_m, payload_m0),
+                };
+                if field_m == 0 {
+                    debug!("skip building additional index for field {}", &field);
+                    continue;
+                }
+
                 debug!("building additional index for field {}", &field);
 
                 // It is expected, that graph will become disconnected less than
@@ -752,20 +1525,30 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     // ToDo: reuse graph layer for same payload
                     let mut additional_graph = GraphLayersBuilder::new_with_params(
                         total_vector_count,
-                        payload_m,
-                        self.config.payload_m0.unwrap_or(self.config.m0),
+                        field_m,
+                        field_m0,
                         self.config.ef_construct,
                         1,
                         HNSW_USE_HEURISTIC,
                         false,
                     );
+                    additional_graph.set_heuristic_params(
+                        self.config.extend_candidates,
+                        self.config.keep_pruned_connections,
+                    );
                     self.build_filtered_graph(
                         &pool,
                         stopped,
                         &mut additional_graph,
-                        payload_block.condition,
+                        payload_block.condition.clone(),
                         &mut block_filter_list,
                     )?;
+                    let block_entry_point = additional_graph
+                        .get_entry_points()
+                        .get_entry_point(|_| true);
+                    if let Some(entry_point) = block_entry_point {
+                        payload_entry_points.insert(&payload_block.condition, entry_point.point_id);
+                    }
                     graph_layers_builder.merge_from_other(additional_graph);
                 }
             }
@@ -780,6 +1563,8 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
         }
 
         self.config.indexed_vector_count.replace(indexed_vectors);
+        self.payload_entry_points = payload_entry_points;
+        self.mutable_graph = None;
 
         let graph_links_path = GraphLayers::<TGraphLinks>::get_links_path(&self.path);
         self.graph = Some(graph_layers_builder.into_graph_layers(Some(&graph_links_path))?);
@@ -789,13 +1574,31 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
             let graph = self.graph.as_ref().unwrap();
             for (idx, deleted) in deleted_bitslice.iter().enumerate() {
                 if *deleted {
-                    debug_assert!(graph.links.links(idx as PointOffsetType, 0).is_empty());
+                    debug_assert!(graph.links.links_vec(idx as PointOffsetType, 0).is_empty());
                 }
             }
         }
 
         debug!("finish additional payload field indexing");
-        self.save()
+
+        if let Some(target_recall) = self.config.target_recall {
+            if self.config.m > 0 {
+                self.config.ef = self.calibrate_ef(target_recall, stopped)?;
+                debug!(
+                    "calibrated HNSW ef to {} for target recall {target_recall}",
+                    self.config.ef,
+                );
+            }
+        }
+
+        self.save()?;
+
+        if let Some((checkpoint_path, _)) = &checkpoint {
+            if checkpoint_path.exists() {
+                std::fs::remove_file(checkpoint_path)?;
+            }
+        }
+        Ok(())
     }
 
     fn get_telemetry_data(&self) -> VectorIndexSearchesTelemetry {
@@ -811,6 +1614,16 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
             filtered_sparse: Default::default(),
             unfiltered_exact: tm.exact_unfiltered.lock().get_statistics(),
             unfiltered_sparse: Default::default(),
+            graph: self.graph.as_ref().map(|graph| {
+                let vector_storage = self.vector_storage.borrow();
+                let deleted_bitslice = vector_storage.deleted_vector_bitslice();
+                graph.get_statistics(|point_id| {
+                    deleted_bitslice
+                        .get(point_id as usize)
+                        .map(|x| *x)
+                        .unwrap_or(false)
+                })
+            }),
         }
     }
 
@@ -819,6 +1632,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
             vec![
                 GraphLayers::<TGraphLinks>::get_path(&self.path),
                 GraphLayers::<TGraphLinks>::get_links_path(&self.path),
+                PayloadEntryPoints::get_path(&self.path),
             ]
         } else {
             vec![]
@@ -826,14 +1640,43 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
     }
 
     fn indexed_vector_count(&self) -> usize {
-        self.config
+        let built_vector_count = self
+            .config
             .indexed_vector_count
             // If indexed vector count is unknown, fall back to number of points
             .or_else(|| self.graph.as_ref().map(|graph| graph.num_points()))
-            .unwrap_or(0)
+            .unwrap_or(0);
+        let appended_vector_count = match (&self.mutable_graph, &self.graph) {
+            (Some(mutable_graph), Some(graph)) => (graph.num_points()..mutable_graph.num_points())
+                .filter(|&point_id| mutable_graph.is_ready(point_id as PointOffsetType))
+                .count(),
+            _ => 0,
+        };
+        built_vector_count + appended_vector_count
     }
 
-    fn update_vector(&mut self, _id: PointOffsetType, _vector: VectorRef) -> OperationResult<()> {
-        Err(OperationError::service_error("Cannot update HNSW index"))
+    fn update_vector(&mut self, id: PointOffsetType, _vector: VectorRef) -> OperationResult<()> {
+        let Some(graph) = &self.graph else {
+            // Points are searched without the graph until it is built
+            return Ok(());
+        };
+        if self.quantized_vectors.borrow().is_some() {
+            return Err(OperationError::service_error(
+                "Cannot link points into HNSW graph with quantized vectors",
+            ));
+        }
+
+        let mut mutable_graph = match self.mutable_graph.take() {
+            Some(mutable_graph) => mutable_graph,
+            None => self.new_mutable_graph(graph)?,
+        };
+        // Placeholders of missing named vectors are deleted right away, no need to link them
+        let result = if self.vector_storage.borrow().is_deleted_vector(id) {
+            Ok(())
+        } else {
+            self.link_point(&mut mutable_graph, id)
+        };
+        self.mutable_graph = Some(mutable_graph);
+        result
     }
 }
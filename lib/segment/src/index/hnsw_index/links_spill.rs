@@ -0,0 +1,205 @@
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+use memmap2::MmapMut;
+use memory::mmap_ops::{create_and_ensure_length, open_write_mmap};
+use parking_lot::{RwLockReadGuard, RwLockWriteGuard};
+
+use crate::common::operation_error::OperationResult;
+use crate::index::hnsw_index::graph_layers::LinkContainer;
+
+pub const HNSW_LINKS_SPILL_FILE: &str = "links_spill.bin";
+
+/// Length of a slot, which links did not fit into and are kept in memory instead
+const OVERFLOW_LEN: PointOffsetType = PointOffsetType::MAX;
+
+/// Level 0 links of `GraphLayersBuilder`, spilled into a temporary mmap file
+///
+/// Every point has a fixed slot of `capacity` links, prefixed with the number of links.
+/// Longer link lists, e.g. after merging of payload subgraphs, stay in memory.
+/// Pages of the file are cached by the OS, so only the working set of the build occupies RAM.
+///
+/// Slots are not synchronized by the spill itself, access to a slot must be guarded
+/// by the lock of level 0 links of the point.
+pub struct LinksSpill {
+    path: PathBuf,
+    mmap: Option<MmapMut>,
+    data: *mut PointOffsetType,
+    num_points: usize,
+    capacity: usize,
+}
+
+// Access to the slots is synchronized by locks of the link containers
+unsafe impl Send for LinksSpill {}
+unsafe impl Sync for LinksSpill {}
+
+impl LinksSpill {
+    pub fn create(path: &Path, num_points: usize, capacity: usize) -> OperationResult<Self> {
+        if path.exists() {
+            // Leftover of an interrupted build
+            std::fs::remove_file(path)?;
+        }
+        let slots_len = num_points.max(1) * (capacity + 1);
+        create_and_ensure_length(path, slots_len * std::mem::size_of::<PointOffsetType>())?;
+        let mut mmap = open_write_mmap(path)?;
+        let data = mmap.as_mut_ptr() as *mut PointOffsetType;
+        Ok(Self {
+            path: path.to_owned(),
+            mmap: Some(mmap),
+            data,
+            num_points,
+            capacity,
+        })
+    }
+
+    pub fn contains(&self, point_id: PointOffsetType) -> bool {
+        (point_id as usize) < self.num_points
+    }
+
+    fn slot(&self, point_id: PointOffsetType) -> *mut PointOffsetType {
+        debug_assert!(self.contains(point_id));
+        // Safety: slot of a contained point is within the mapped file
+        unsafe { self.data.add(point_id as usize * (self.capacity + 1)) }
+    }
+
+    /// Links of the point, `None` if they did not fit into the slot and are kept in memory
+    ///
+    /// # Safety
+    ///
+    /// Caller must hold a lock of level 0 links of the point for the lifetime of the result.
+    pub unsafe fn get(&self, point_id: PointOffsetType) -> Option<&[PointOffsetType]> {
+        let slot = self.slot(point_id);
+        let len = *slot;
+        if len == OVERFLOW_LEN {
+            return None;
+        }
+        Some(std::slice::from_raw_parts(slot.add(1), len as usize))
+    }
+
+    /// Move links of the point into its slot, releasing their memory
+    ///
+    /// Links, which do not fit into the slot, are left in `links`.
+    ///
+    /// # Safety
+    ///
+    /// Caller must hold the write lock of level 0 links of the point.
+    pub unsafe fn store(&self, point_id: PointOffsetType, links: &mut LinkContainer) {
+        let slot = self.slot(point_id);
+        if links.len() > self.capacity {
+            *slot = OVERFLOW_LEN;
+            return;
+        }
+        std::ptr::copy_nonoverlapping(links.as_ptr(), slot.add(1), links.len());
+        *slot = links.len() as PointOffsetType;
+        *links = LinkContainer::new();
+    }
+}
+
+impl Drop for LinksSpill {
+    fn drop(&mut self) {
+        self.mmap.take();
+        if let Err(err) = std::fs::remove_file(&self.path) {
+            log::warn!(
+                "failed to remove HNSW links spill file {:?}: {err}",
+                self.path
+            );
+        }
+    }
+}
+
+/// Read access to links of a point, either kept in memory or spilled
+pub enum LinksReadGuard<'a> {
+    Ram(RwLockReadGuard<'a, LinkContainer>),
+    Spilled {
+        _lock: RwLockReadGuard<'a, LinkContainer>,
+        links: &'a [PointOffsetType],
+    },
+}
+
+impl Deref for LinksReadGuard<'_> {
+    type Target = [PointOffsetType];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            LinksReadGuard::Ram(links) => links,
+            LinksReadGuard::Spilled { links, .. } => links,
+        }
+    }
+}
+
+/// Write access to links of a point
+///
+/// Spilled links are loaded into memory and stored back into the spill, once the guard is dropped.
+pub struct LinksWriteGuard<'a> {
+    links: RwLockWriteGuard<'a, LinkContainer>,
+    spill: Option<(&'a LinksSpill, PointOffsetType)>,
+}
+
+impl<'a> LinksWriteGuard<'a> {
+    pub fn new(
+        mut links: RwLockWriteGuard<'a, LinkContainer>,
+        spill: Option<(&'a LinksSpill, PointOffsetType)>,
+    ) -> Self {
+        if let Some((spill, point_id)) = spill {
+            // Safety: write lock of the point links is held
+            if let Some(spilled_links) = unsafe { spill.get(point_id) } {
+                links.clear();
+                links.extend_from_slice(spilled_links);
+            }
+        }
+        Self { links, spill }
+    }
+}
+
+impl Deref for LinksWriteGuard<'_> {
+    type Target = LinkContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.links
+    }
+}
+
+impl DerefMut for LinksWriteGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.links
+    }
+}
+
+impl Drop for LinksWriteGuard<'_> {
+    fn drop(&mut self) {
+        if let Some((spill, point_id)) = self.spill {
+            // Safety: write lock of the point links is held until the end of the drop
+            unsafe { spill.store(point_id, &mut self.links) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_links_spill() {
+        let dir = Builder::new().prefix("links_spill").tempdir().unwrap();
+        let path = dir.path().join(HNSW_LINKS_SPILL_FILE);
+        let spill = LinksSpill::create(&path, 3, 4).unwrap();
+
+        let mut links = vec![1, 2, 3];
+        unsafe { spill.store(0, &mut links) };
+        assert!(links.is_empty());
+        assert_eq!(unsafe { spill.get(0) }, Some([1, 2, 3].as_slice()));
+        assert_eq!(unsafe { spill.get(1) }, Some([].as_slice()));
+
+        // Links, which do not fit, stay in memory
+        let mut links = vec![0, 1, 2, 3, 4];
+        unsafe { spill.store(2, &mut links) };
+        assert_eq!(links, vec![0, 1, 2, 3, 4]);
+        assert_eq!(unsafe { spill.get(2) }, None);
+
+        drop(spill);
+        assert!(!path.exists());
+    }
+}
@@ -6,8 +6,9 @@ pub mod graph_layers;
 pub mod graph_layers_builder;
 pub mod graph_links;
 pub mod hnsw;
+mod links_spill;
 pub mod point_scorer;
-mod search_context;
+pub(crate) mod search_context;
 
 #[cfg(test)]
 mod tests;
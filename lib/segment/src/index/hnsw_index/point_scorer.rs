@@ -1,3 +1,4 @@
+use bitvec::prelude::{BitSlice, BitVec};
 use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
 
 use crate::payload_storage::FilterContext;
@@ -6,6 +7,8 @@ use crate::vector_storage::RawScorer;
 pub struct FilteredScorer<'a> {
     pub raw_scorer: &'a dyn RawScorer,
     pub filter_context: Option<&'a dyn FilterContext>,
+    /// Pre-materialized filter, takes precedence over `filter_context`
+    filter_bitmap: Option<&'a BitSlice>,
     points_buffer: Vec<ScoredPointOffset>,
 }
 
@@ -17,11 +20,51 @@ impl<'a> FilteredScorer<'a> {
         FilteredScorer {
             raw_scorer,
             filter_context,
+            filter_bitmap: None,
             points_buffer: Vec::new(),
         }
     }
 
+    /// Create scorer, which checks the filter with lookups into a bitmap of matching points
+    ///
+    /// See [`FilteredScorer::materialize_filter`].
+    pub fn new_with_bitmap(raw_scorer: &'a dyn RawScorer, filter_bitmap: &'a BitSlice) -> Self {
+        FilteredScorer {
+            raw_scorer,
+            filter_context: None,
+            filter_bitmap: Some(filter_bitmap),
+            points_buffer: Vec::new(),
+        }
+    }
+
+    /// Evaluate the filter once for the given matching points, so that it could be shared
+    /// by all searches of a batch
+    ///
+    /// Points outside of the bitmap are considered not matching.
+    pub fn materialize_filter(
+        matching_points: impl IntoIterator<Item = PointOffsetType>,
+        num_points: usize,
+    ) -> BitVec {
+        let mut bitmap = BitVec::repeat(false, num_points);
+        for point_id in matching_points {
+            if let Some(mut bit) = bitmap.get_mut(point_id as usize) {
+                *bit = true;
+            }
+        }
+        bitmap
+    }
+
+    fn check_bitmap(bitmap: &BitSlice, point_id: PointOffsetType) -> bool {
+        bitmap
+            .get(point_id as usize)
+            .map(|bit| *bit)
+            .unwrap_or(false)
+    }
+
     pub fn check_vector(&self, point_id: PointOffsetType) -> bool {
+        if let Some(bitmap) = self.filter_bitmap {
+            return Self::check_bitmap(bitmap, point_id) && self.raw_scorer.check_vector(point_id);
+        }
         match self.filter_context {
             None => self.raw_scorer.check_vector(point_id),
             Some(f) => f.check(point_id) && self.raw_scorer.check_vector(point_id),
@@ -44,9 +87,19 @@ impl<'a> FilteredScorer<'a> {
         limit: usize,
     ) -> &[ScoredPointOffset] {
         // apply filter and store filtered ids to source slice memory
-        let filtered_point_ids = match self.filter_context {
-            None => point_ids,
-            Some(f) => {
+        let filtered_point_ids = match (self.filter_bitmap, self.filter_context) {
+            (Some(bitmap), _) => {
+                let len = point_ids.len();
+                let mut filtered_len = 0;
+                for i in 0..len {
+                    let point_id = point_ids[i];
+                    point_ids[filtered_len] = point_id;
+                    filtered_len += Self::check_bitmap(bitmap, point_id) as usize;
+                }
+                &point_ids[0..filtered_len]
+            }
+            (None, None) => point_ids,
+            (None, Some(f)) => {
                 let len = point_ids.len();
                 let mut filtered_len = 0;
                 for i in 0..len {
@@ -80,3 +133,39 @@ impl<'a> FilteredScorer<'a> {
         self.raw_scorer.score_internal(point_a, point_b)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::fixtures::index_fixtures::{random_vector, TestRawScorerProducer};
+    use crate::spaces::simple::DotProductMetric;
+
+    #[test]
+    fn test_bitmap_filtered_scorer() {
+        let num_vectors = 100;
+        let dim = 8;
+        let mut rng = StdRng::seed_from_u64(42);
+        let vector_holder =
+            TestRawScorerProducer::<DotProductMetric>::new(dim, num_vectors, &mut rng);
+        let raw_scorer = vector_holder
+            .get_raw_scorer(random_vector(&mut rng, dim))
+            .unwrap();
+
+        let matching = (0..num_vectors as PointOffsetType).filter(|idx| idx % 3 == 0);
+        let bitmap = FilteredScorer::materialize_filter(matching, num_vectors);
+        let mut scorer = FilteredScorer::new_with_bitmap(raw_scorer.as_ref(), &bitmap);
+
+        assert!(scorer.check_vector(3));
+        assert!(!scorer.check_vector(4));
+        // Points beyond the bitmap never match
+        assert!(!scorer.check_vector(num_vectors as PointOffsetType + 3));
+
+        let mut point_ids: Vec<_> = (0..num_vectors as PointOffsetType).collect();
+        let scored = scorer.score_points(&mut point_ids, 0);
+        assert_eq!(scored.len(), 34);
+        assert!(scored.iter().all(|scored| scored.idx % 3 == 0));
+    }
+}
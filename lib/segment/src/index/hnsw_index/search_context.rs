@@ -7,7 +7,7 @@ use num_traits::float::FloatCore;
 
 /// Structure that holds context of the search
 pub struct SearchContext {
-    /// Overall nearest points found so far
+    /// Overall nearest points found so far, points lower than the score threshold are rejected
     pub nearest: FixedLengthPriorityQueue<ScoredPointOffset>,
     /// Current candidates to process
     pub candidates: BinaryHeap<ScoredPointOffset>,
@@ -15,7 +15,16 @@ pub struct SearchContext {
 
 impl SearchContext {
     pub fn new(entry_point: ScoredPointOffset, ef: usize) -> Self {
-        let mut nearest = FixedLengthPriorityQueue::new(ef);
+        Self::with_score_threshold(entry_point, ef, None)
+    }
+
+    pub fn with_score_threshold(
+        entry_point: ScoredPointOffset,
+        ef: usize,
+        score_threshold: Option<ScoreType>,
+    ) -> Self {
+        let threshold = score_threshold.map(|score| ScoredPointOffset { idx: 0, score });
+        let mut nearest = FixedLengthPriorityQueue::with_threshold(ef, threshold);
         nearest.push(entry_point);
         SearchContext {
             nearest,
@@ -23,10 +32,15 @@ impl SearchContext {
         }
     }
 
+    /// Candidates with lower score are not worth expanding
     pub fn lower_bound(&self) -> ScoreType {
-        match self.nearest.top() {
+        let worst_of_the_best = match self.nearest.top() {
             None => ScoreType::min_value(),
             Some(worst_of_the_best) => worst_of_the_best.score,
+        };
+        match self.nearest.threshold() {
+            Some(threshold) => worst_of_the_best.max(threshold.score),
+            None => worst_of_the_best,
         }
     }
 
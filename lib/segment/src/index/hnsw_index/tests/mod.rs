@@ -1,5 +1,7 @@
 mod test_compact_graph_layer;
+mod test_deterministic_build;
 mod test_graph_connectivity;
+mod test_graph_updates;
 
 use std::path::Path;
 
@@ -44,7 +46,9 @@ where
         let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
         let level = graph_layers_builder.get_random_layer(rng);
         graph_layers_builder.set_levels(idx, level);
-        graph_layers_builder.link_new_point(idx, scorer);
+        graph_layers_builder
+            .link_new_point(idx, scorer, &false.into())
+            .unwrap();
     }
     (vector_holder, graph_layers_builder)
 }
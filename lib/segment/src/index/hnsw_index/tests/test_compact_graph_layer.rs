@@ -27,14 +27,27 @@ fn search_in_builder(
         Some(ep) => ep,
     };
 
-    let zero_level_entry = builder.search_entry(
-        entry_point.point_id,
-        entry_point.level,
-        0,
-        &mut points_scorer,
-    );
+    let zero_level_entry = builder
+        .search_entry(
+            entry_point.point_id,
+            entry_point.level,
+            0,
+            &mut points_scorer,
+            &false.into(),
+        )
+        .unwrap();
 
-    let nearest = builder.search_on_level(zero_level_entry, 0, max(top, ef), &mut points_scorer);
+    let nearest = builder
+        .search_on_level(
+            zero_level_entry,
+            0,
+            max(top, ef),
+            &mut points_scorer,
+            false,
+            None,
+            &false.into(),
+        )
+        .unwrap();
     nearest.into_iter().take(top).collect_vec()
 }
 
@@ -75,7 +88,9 @@ fn test_compact_graph_layers() {
         .map(|query| {
             let raw_scorer = vector_holder.get_raw_scorer(query.clone()).unwrap();
             let scorer = FilteredScorer::new(raw_scorer.as_ref(), None);
-            graph_layers.search(top, ef, scorer, None)
+            graph_layers
+                .search(top, ef, scorer, None, &false.into())
+                .unwrap()
         })
         .collect_vec();
 
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+
+use common::types::PointOffsetType;
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+use tempfile::Builder;
+
+use crate::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
+use crate::entry::entry_point::SegmentEntry;
+use crate::fixtures::index_fixtures::random_vector;
+use crate::index::hnsw_index::graph_links::{GraphLinks, GraphLinksRam};
+use crate::index::hnsw_index::hnsw::HNSWIndex;
+use crate::index::VectorIndex;
+use crate::segment_constructor::build_segment;
+use crate::types::{
+    Distance, HnswConfig, Indexes, SegmentConfig, SeqNumberType, VectorDataConfig,
+    VectorStorageType,
+};
+
+#[test]
+fn test_seeded_build_is_deterministic() {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 16;
+    let num_vectors: u64 = 1_000;
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Cosine,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
+            },
+        )]),
+        payload_storage_type: Default::default(),
+        sparse_vector_data: Default::default(),
+        payload_compression: None,
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    for n in 0..num_vectors {
+        let idx = n.into();
+        let vector = random_vector(&mut rnd, dim);
+
+        segment
+            .upsert_point(n as SeqNumberType, idx, only_default_vector(&vector))
+            .unwrap();
+    }
+
+    let hnsw_config = HnswConfig {
+        m: 8,
+        ef_construct: 32,
+        full_scan_threshold: 10_000,
+        max_indexing_threads: 4,
+        on_disk: Some(false),
+        payload_m: None,
+        payload_m_overrides: None,
+        seed: Some(42),
+        extend_candidates: None,
+        keep_pruned_connections: None,
+        compress_links: None,
+        target_recall: None,
+        level_factor_multiplier: None,
+        ef_construct_levels: None,
+        refine: None,
+        appendable: None,
+    };
+
+    let build_links = || {
+        let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+        let mut hnsw_index = HNSWIndex::<GraphLinksRam>::open(
+            hnsw_dir.path(),
+            segment.id_tracker.clone(),
+            segment.vector_data[DEFAULT_VECTOR_NAME]
+                .vector_storage
+                .clone(),
+            Default::default(),
+            segment.payload_index.clone(),
+            hnsw_config.clone(),
+        )
+        .unwrap();
+        hnsw_index.build_index(&stopped).unwrap();
+
+        let graph = hnsw_index.graph().unwrap();
+        (0..num_vectors as PointOffsetType)
+            .map(|point_id| {
+                (0..=graph.point_level(point_id))
+                    .map(|level| graph.links.links_vec(point_id, level))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(build_links(), build_links());
+}
@@ -42,10 +42,15 @@ fn test_graph_connectivity() {
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Plain {},
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
             },
         )]),
         payload_storage_type: Default::default(),
         sparse_vector_data: Default::default(),
+        payload_compression: None,
     };
 
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
@@ -67,6 +72,16 @@ fn test_graph_connectivity() {
         max_indexing_threads: 4,
         on_disk: Some(false),
         payload_m: None,
+        payload_m_overrides: None,
+        seed: None,
+        extend_candidates: None,
+        keep_pruned_connections: None,
+        compress_links: None,
+        target_recall: None,
+        level_factor_multiplier: None,
+        ef_construct_levels: None,
+        refine: None,
+        appendable: None,
     };
 
     let mut hnsw_index = HNSWIndex::<GraphLinksRam>::open(
@@ -88,9 +103,9 @@ fn test_graph_connectivity() {
     let mut reverse_links = vec![vec![]; num_vectors as usize];
 
     for point_id in 0..num_vectors {
-        let links = graph.links.links(point_id as PointOffsetType, 0);
+        let links = graph.links.links_vec(point_id as PointOffsetType, 0);
         for link in links {
-            reverse_links[*link as usize].push(point_id);
+            reverse_links[link as usize].push(point_id);
         }
     }
 
@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use tempfile::Builder;
+
+use crate::data_types::vectors::{only_default_vector, QueryVector, DEFAULT_VECTOR_NAME};
+use crate::entry::entry_point::SegmentEntry;
+use crate::fixtures::index_fixtures::random_vector;
+use crate::index::hnsw_index::graph_layers::{GraphLayers, REMOVED_POINT};
+use crate::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
+use crate::index::hnsw_index::graph_links::{GraphLinks, GraphLinksRam};
+use crate::index::hnsw_index::hnsw::HNSWIndex;
+use crate::index::VectorIndex;
+use crate::segment::Segment;
+use crate::segment_constructor::build_segment;
+use crate::types::{
+    Distance, HnswConfig, Indexes, SegmentConfig, SeqNumberType, VectorDataConfig,
+    VectorStorageType,
+};
+
+const DIM: usize = 16;
+const NUM_VECTORS: u64 = 200;
+
+fn hnsw_config() -> HnswConfig {
+    HnswConfig {
+        m: 8,
+        ef_construct: 64,
+        // Always search with the graph
+        full_scan_threshold: 0,
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+        payload_m_overrides: None,
+        seed: Some(42),
+        extend_candidates: None,
+        keep_pruned_connections: None,
+        compress_links: None,
+        target_recall: None,
+        level_factor_multiplier: None,
+        ef_construct_levels: None,
+        refine: None,
+        appendable: None,
+    }
+}
+
+fn open_hnsw_index(segment: &Segment, hnsw_dir: &Path) -> HNSWIndex<GraphLinksRam> {
+    HNSWIndex::<GraphLinksRam>::open(
+        hnsw_dir,
+        segment.id_tracker.clone(),
+        segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        Default::default(),
+        segment.payload_index.clone(),
+        hnsw_config(),
+    )
+    .unwrap()
+}
+
+fn build_indexed_segment(
+    segment_dir: &Path,
+    hnsw_dir: &Path,
+    rnd: &mut StdRng,
+) -> (Segment, HNSWIndex<GraphLinksRam>) {
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: DIM,
+                distance: Distance::Euclid,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
+            },
+        )]),
+        payload_storage_type: Default::default(),
+        sparse_vector_data: Default::default(),
+        payload_compression: None,
+    };
+
+    let mut segment = build_segment(segment_dir, &config, true).unwrap();
+    for n in 0..NUM_VECTORS {
+        let vector = random_vector(rnd, DIM);
+        segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+
+    let mut hnsw_index = open_hnsw_index(&segment, hnsw_dir);
+    hnsw_index.build_index(&AtomicBool::new(false)).unwrap();
+
+    (segment, hnsw_index)
+}
+
+#[test]
+fn test_search_points_appended_to_built_graph() {
+    let stopped = AtomicBool::new(false);
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let segment_dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+    let (mut segment, mut hnsw_index) =
+        build_indexed_segment(segment_dir.path(), hnsw_dir.path(), &mut rnd);
+
+    // Segment writes the new vector into the storage, shared with the index
+    let vector = random_vector(&mut rnd, DIM);
+    segment
+        .upsert_point(
+            NUM_VECTORS,
+            NUM_VECTORS.into(),
+            only_default_vector(&vector),
+        )
+        .unwrap();
+    let new_offset = NUM_VECTORS as u32;
+
+    hnsw_index
+        .update_vector(new_offset, vector.as_slice().into())
+        .unwrap();
+    // New points are linked into a copy of the built graph
+    assert_eq!(
+        hnsw_index.graph().unwrap().num_points(),
+        NUM_VECTORS as usize
+    );
+    assert_eq!(hnsw_index.indexed_vector_count(), NUM_VECTORS as usize + 1);
+
+    let query: QueryVector = vector.into();
+    let result = hnsw_index
+        .search(&[&query], None, 1, None, &stopped)
+        .unwrap();
+    assert_eq!(result[0][0].idx, new_offset);
+
+    // Appended points are linked again on open
+    drop(hnsw_index);
+    let mut hnsw_index = open_hnsw_index(&segment, hnsw_dir.path());
+    assert_eq!(hnsw_index.indexed_vector_count(), NUM_VECTORS as usize + 1);
+    let result = hnsw_index
+        .search(&[&query], None, 1, None, &stopped)
+        .unwrap();
+    assert_eq!(result[0][0].idx, new_offset);
+
+    // Points, which are already linked, are re-linked with the updated vector
+    let updated_vector = random_vector(&mut rnd, DIM);
+    segment
+        .upsert_point(
+            NUM_VECTORS + 1,
+            0.into(),
+            only_default_vector(&updated_vector),
+        )
+        .unwrap();
+    hnsw_index
+        .update_vector(0, updated_vector.as_slice().into())
+        .unwrap();
+    let updated_query: QueryVector = updated_vector.into();
+    let result = hnsw_index
+        .search(&[&updated_query], None, 1, None, &stopped)
+        .unwrap();
+    assert_eq!(result[0][0].idx, 0);
+
+    hnsw_index.build_index(&stopped).unwrap();
+    assert_eq!(
+        hnsw_index.graph().unwrap().num_points(),
+        NUM_VECTORS as usize + 1
+    );
+    assert_eq!(hnsw_index.indexed_vector_count(), NUM_VECTORS as usize + 1);
+}
+
+#[test]
+fn test_remove_deleted_points_from_graph() {
+    let stopped = AtomicBool::new(false);
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let segment_dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+    let (mut segment, mut hnsw_index) =
+        build_indexed_segment(segment_dir.path(), hnsw_dir.path(), &mut rnd);
+
+    let is_deleted = |point_id: u32| point_id % 10 == 0;
+    for point_id in (0..NUM_VECTORS).filter(|&point_id| is_deleted(point_id as u32)) {
+        segment
+            .delete_point(NUM_VECTORS + point_id, point_id.into())
+            .unwrap();
+        hnsw_index.remove_vector(point_id as u32).unwrap();
+    }
+
+    // Neighbours of deleted points stay reachable
+    for point_id in (0..NUM_VECTORS as u32).filter(|&point_id| !is_deleted(point_id)) {
+        let query: QueryVector = segment
+            .vector(DEFAULT_VECTOR_NAME, u64::from(point_id).into())
+            .unwrap()
+            .unwrap()
+            .into();
+        let result = hnsw_index
+            .search(&[&query], None, 1, None, &stopped)
+            .unwrap();
+        assert_eq!(result[0][0].idx, point_id);
+    }
+
+    // The built graph is not modified
+    let graph = hnsw_index.graph().unwrap();
+    assert!(!graph.links.links_vec(0, 0).is_empty());
+}
+
+#[test]
+fn test_heal_deleted_points_of_previous_graph() {
+    let stopped = AtomicBool::new(false);
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let segment_dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+    let (mut segment, hnsw_index) =
+        build_indexed_segment(segment_dir.path(), hnsw_dir.path(), &mut rnd);
+
+    let is_deleted = |point_id: u32| point_id % 10 == 0;
+    for point_id in (0..NUM_VECTORS).filter(|&point_id| is_deleted(point_id as u32)) {
+        segment
+            .delete_point(NUM_VECTORS + point_id, point_id.into())
+            .unwrap();
+    }
+
+    // Deleted points are not moved into the new segment, the rest is renumbered
+    let mut next_id = 0;
+    let old_to_new: Vec<_> = (0..NUM_VECTORS as u32)
+        .map(|point_id| {
+            if is_deleted(point_id) {
+                REMOVED_POINT
+            } else {
+                next_id += 1;
+                next_id - 1
+            }
+        })
+        .collect();
+
+    let previous_graph = hnsw_index
+        .previous_graph(&old_to_new, &stopped)
+        .unwrap()
+        .unwrap();
+    assert_eq!(previous_graph.num_linked(), next_id as usize);
+
+    let graph = previous_graph.graph();
+    assert_eq!(graph.num_points(), next_id as usize);
+    let report = graph.check_graph(|_| false);
+    assert_eq!(report.invalid_links, 0);
+    assert_eq!(report.invalid_entry_points, 0);
+
+    // The old graph is not modified
+    let graph = hnsw_index.graph().unwrap();
+    assert_eq!(graph.num_points(), NUM_VECTORS as usize);
+    assert!(!graph.links.links_vec(0, 0).is_empty());
+}
+
+#[test]
+fn test_repair_unreachable_points() {
+    let stopped = AtomicBool::new(false);
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let segment_dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+    let (segment, hnsw_index) =
+        build_indexed_segment(segment_dir.path(), hnsw_dir.path(), &mut rnd);
+    drop(hnsw_index);
+
+    // Simulate a crash, which left a live point without incoming links in the stored graph
+    let graph_path = GraphLayers::<GraphLinksRam>::get_path(hnsw_dir.path());
+    let graph_links_path = GraphLayers::<GraphLinksRam>::get_links_path(hnsw_dir.path());
+    let graph = GraphLayers::<GraphLinksRam>::load(&graph_path, &graph_links_path).unwrap();
+    let unlinked_point = (0..NUM_VECTORS as u32)
+        .find(|&point_id| graph.point_level(point_id) == 0)
+        .unwrap();
+    let graph_layers_builder =
+        GraphLayersBuilder::new_from_graph(&graph, NUM_VECTORS as usize, true);
+    graph_layers_builder.heal_removed_points(&[unlinked_point], |_, _| 0.0);
+    let graph = graph_layers_builder
+        .into_graph_layers::<GraphLinksRam>(Some(&graph_links_path))
+        .unwrap();
+    graph.save(&graph_path).unwrap();
+    let report = graph.check_graph(|_| false);
+    assert!(report.unreachable_points.contains(&unlinked_point));
+
+    // Opening the index doesn't modify the graph
+    let mut hnsw_index = open_hnsw_index(&segment, hnsw_dir.path());
+    let report = hnsw_index.check_graph().unwrap();
+    assert!(report.unreachable_points.contains(&unlinked_point));
+
+    let report = hnsw_index.repair_graph(&stopped).unwrap().unwrap();
+    assert!(report.unreachable_points.contains(&unlinked_point));
+    let report = hnsw_index.check_graph().unwrap();
+    assert!(report.unreachable_points.is_empty());
+
+    // Repaired graph is saved
+    drop(hnsw_index);
+    let hnsw_index = open_hnsw_index(&segment, hnsw_dir.path());
+    let report = hnsw_index.check_graph().unwrap();
+    assert!(report.unreachable_points.is_empty());
+
+    let query: QueryVector = segment
+        .vector(DEFAULT_VECTOR_NAME, (unlinked_point as u64).into())
+        .unwrap()
+        .unwrap()
+        .into();
+    let result = hnsw_index
+        .search(&[&query], None, 1, None, &stopped)
+        .unwrap();
+    assert_eq!(result[0][0].idx, unlinked_point);
+}
@@ -0,0 +1,38 @@
+use std::path::{Path, PathBuf};
+
+use io::file_operations::{atomic_save_json, read_json};
+use serde::{Deserialize, Serialize};
+
+use crate::common::operation_error::OperationResult;
+
+pub const IVF_INDEX_CONFIG_FILE: &str = "ivf_config.json";
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct IvfIndexConfig {
+    /// Number of inverted lists, 0 for automatic selection
+    pub num_lists: usize,
+    /// Number of dimensions of a product quantization subvector
+    pub pq_subvector_dim: usize,
+    /// Number of lists to scan during the search
+    pub nprobe: usize,
+    /// We prefer a full scan search upto (excluding) this number of vectors.
+    ///
+    /// Note: this is number of vectors, not KiloBytes.
+    pub full_scan_threshold: usize,
+    #[serde(default)]
+    pub indexed_vector_count: Option<usize>,
+}
+
+impl IvfIndexConfig {
+    pub fn get_config_path(path: &Path) -> PathBuf {
+        path.join(IVF_INDEX_CONFIG_FILE)
+    }
+
+    pub fn load(path: &Path) -> OperationResult<Self> {
+        Ok(read_json(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> OperationResult<()> {
+        Ok(atomic_save_json(path, self)?)
+    }
+}
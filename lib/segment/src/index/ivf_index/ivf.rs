@@ -0,0 +1,371 @@
+use std::fs::create_dir_all;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use common::types::{PointOffsetType, ScoredPointOffset};
+use log::debug;
+use parking_lot::Mutex;
+use rand::thread_rng;
+
+use super::config::IvfIndexConfig;
+use super::ivf_storage::IvfStorage;
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::operation_time_statistics::{
+    OperationDurationsAggregator, ScopeDurationMeasurer,
+};
+use crate::common::BYTES_IN_KB;
+use crate::data_types::vectors::{QueryVector, Vector, VectorElementType, VectorRef};
+use crate::id_tracker::{IdTracker, IdTrackerSS};
+use crate::index::query_estimator::adjust_to_available_vectors;
+use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::index::{PayloadIndex, VectorIndex};
+use crate::payload_storage::FilterContext;
+use crate::telemetry::VectorIndexSearchesTelemetry;
+use crate::types::{Filter, IvfConfig, SearchParams, VECTOR_ELEMENT_SIZE};
+use crate::vector_storage::{new_stoppable_raw_scorer, VectorStorage, VectorStorageEnum};
+
+/// Number of approximate candidates per requested result, which are rescored with original vectors
+const IVF_RESCORE_FACTOR: usize = 4;
+
+/// Vector index based on inverted lists of product-quantized vectors (IVF-PQ)
+///
+/// Only the centroids, codebooks and one byte per `pq_subvector_dim` dimensions of each vector
+/// are kept in memory, so the index is much smaller than HNSW graph.
+/// Search scans the lists nearest to the query and rescores the best candidates with original vectors.
+///
+/// The index is intended for large segments, which are rarely updated.
+/// Only nearest neighbour queries are served by the index, other queries are done with full scan.
+pub struct IvfIndex {
+    id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+    vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+    payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    config: IvfIndexConfig,
+    path: PathBuf,
+    storage: Option<IvfStorage>,
+    searches_telemetry: IvfSearchesTelemetry,
+}
+
+struct IvfSearchesTelemetry {
+    unfiltered_plain: Arc<Mutex<OperationDurationsAggregator>>,
+    unfiltered_ivf: Arc<Mutex<OperationDurationsAggregator>>,
+    small_cardinality: Arc<Mutex<OperationDurationsAggregator>>,
+    large_cardinality: Arc<Mutex<OperationDurationsAggregator>>,
+    exact_filtered: Arc<Mutex<OperationDurationsAggregator>>,
+    exact_unfiltered: Arc<Mutex<OperationDurationsAggregator>>,
+}
+
+impl IvfIndex {
+    pub fn open(
+        path: &Path,
+        id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+        vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+        payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+        ivf_config: IvfConfig,
+    ) -> OperationResult<Self> {
+        create_dir_all(path)?;
+
+        let config_path = IvfIndexConfig::get_config_path(path);
+        let config = if config_path.exists() {
+            IvfIndexConfig::load(&config_path)?
+        } else {
+            let vector_storage = vector_storage.borrow();
+            let full_scan_threshold = ivf_config.full_scan_threshold.saturating_mul(BYTES_IN_KB)
+                / (vector_storage.vector_dim() * VECTOR_ELEMENT_SIZE);
+
+            IvfIndexConfig {
+                num_lists: ivf_config.num_lists,
+                pq_subvector_dim: ivf_config.pq_subvector_dim,
+                nprobe: ivf_config.nprobe,
+                full_scan_threshold,
+                indexed_vector_count: None,
+            }
+        };
+
+        let storage_path = IvfStorage::get_path(path);
+        let storage = if storage_path.exists() {
+            Some(IvfStorage::load(&storage_path)?)
+        } else {
+            None
+        };
+
+        Ok(IvfIndex {
+            id_tracker,
+            vector_storage,
+            payload_index,
+            config,
+            path: path.to_owned(),
+            storage,
+            searches_telemetry: IvfSearchesTelemetry {
+                unfiltered_plain: OperationDurationsAggregator::new(),
+                unfiltered_ivf: OperationDurationsAggregator::new(),
+                small_cardinality: OperationDurationsAggregator::new(),
+                large_cardinality: OperationDurationsAggregator::new(),
+                exact_filtered: OperationDurationsAggregator::new(),
+                exact_unfiltered: OperationDurationsAggregator::new(),
+            },
+        })
+    }
+
+    fn save_config(&self) -> OperationResult<()> {
+        let config_path = IvfIndexConfig::get_config_path(&self.path);
+        self.config.save(&config_path)
+    }
+
+    fn search_with_ivf(
+        &self,
+        storage: &IvfStorage,
+        query: &[VectorElementType],
+        filter_context: Option<&dyn FilterContext>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPointOffset>> {
+        let nprobe = params
+            .and_then(|params| params.ivf_nprobe)
+            .unwrap_or(self.config.nprobe);
+
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let raw_scorer = new_stoppable_raw_scorer(
+            query.to_vec().into(),
+            &vector_storage,
+            id_tracker.deleted_point_bitslice(),
+            is_stopped,
+        )?;
+
+        let preprocessed_query = storage.distance().preprocess_vector(query.to_vec());
+        let candidates = storage.search(
+            &preprocessed_query,
+            nprobe,
+            top.saturating_mul(IVF_RESCORE_FACTOR),
+            |point_id| {
+                raw_scorer.check_vector(point_id)
+                    && filter_context.map_or(true, |filter| filter.check(point_id))
+            },
+        );
+
+        // Approximate scores only select candidates, results are scored with original vectors
+        Ok(raw_scorer.peek_top_iter(&mut candidates.iter().map(|scored| scored.idx), top))
+    }
+
+    fn search_vectors_with_ivf(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        let Some(storage) = &self.storage else {
+            return Ok(vec![vec![]; vectors.len()]);
+        };
+
+        let queries: Option<Vec<_>> = vectors
+            .iter()
+            .map(|vector| match vector {
+                QueryVector::Nearest(Vector::Dense(query)) => Some(query.as_slice()),
+                _ => None,
+            })
+            .collect();
+        let Some(queries) = queries else {
+            // Lists are selected by a single query vector, other queries are not supported
+            return self.search_vectors_plain(vectors, filter, top, is_stopped);
+        };
+
+        let payload_index = self.payload_index.borrow();
+        let filter_context = filter.map(|filter| payload_index.filter_context(filter));
+        queries
+            .into_iter()
+            .map(|query| {
+                self.search_with_ivf(
+                    storage,
+                    query,
+                    filter_context.as_deref(),
+                    top,
+                    params,
+                    is_stopped,
+                )
+            })
+            .collect()
+    }
+
+    fn search_vectors_plain(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        // share filtered points for all query vectors
+        let filtered_points = filter.map(|filter| self.payload_index.borrow().query_points(filter));
+        vectors
+            .iter()
+            .map(|&vector| {
+                let raw_scorer = new_stoppable_raw_scorer(
+                    vector.to_owned(),
+                    &vector_storage,
+                    id_tracker.deleted_point_bitslice(),
+                    is_stopped,
+                )?;
+                Ok(match &filtered_points {
+                    Some(points) => raw_scorer.peek_top_iter(&mut points.iter().copied(), top),
+                    None => raw_scorer.peek_top_all(top),
+                })
+            })
+            .collect()
+    }
+}
+
+impl VectorIndex for IvfIndex {
+    fn search(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        let exact = params.map(|params| params.exact).unwrap_or(false);
+        match filter {
+            None => {
+                let available_vector_count = self.vector_storage.borrow().available_vector_count();
+                if exact {
+                    let _timer =
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.exact_unfiltered);
+                    self.search_vectors_plain(vectors, None, top, is_stopped)
+                } else if available_vector_count < self.config.full_scan_threshold {
+                    let _timer =
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_plain);
+                    self.search_vectors_plain(vectors, None, top, is_stopped)
+                } else {
+                    let _timer =
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_ivf);
+                    self.search_vectors_with_ivf(vectors, None, top, params, is_stopped)
+                }
+            }
+            Some(query_filter) => {
+                if exact {
+                    let _timer =
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.exact_filtered);
+                    return self.search_vectors_plain(vectors, filter, top, is_stopped);
+                }
+
+                let query_cardinality = {
+                    let payload_index = self.payload_index.borrow();
+                    let vector_storage = self.vector_storage.borrow();
+                    let id_tracker = self.id_tracker.borrow();
+                    adjust_to_available_vectors(
+                        payload_index.estimate_cardinality(query_filter),
+                        vector_storage.available_vector_count(),
+                        id_tracker.available_point_count(),
+                    )
+                };
+
+                // Scanned lists only contain enough matching points if a large share of points matches
+                if query_cardinality.exp < self.config.full_scan_threshold {
+                    let _timer =
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
+                    self.search_vectors_plain(vectors, filter, top, is_stopped)
+                } else {
+                    let _timer =
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
+                    self.search_vectors_with_ivf(vectors, filter, top, params, is_stopped)
+                }
+            }
+        }
+    }
+
+    fn build_index(&mut self, stopped: &AtomicBool) -> OperationResult<()> {
+        let ids: Vec<_> = {
+            let id_tracker = self.id_tracker.borrow();
+            let vector_storage = self.vector_storage.borrow();
+            id_tracker
+                .iter_ids_excluding(vector_storage.deleted_vector_bitslice())
+                .collect()
+        };
+        self.config.indexed_vector_count.replace(ids.len());
+
+        let storage_path = IvfStorage::get_path(&self.path);
+        if ids.is_empty() {
+            debug!("skip building IVF index without vectors");
+            self.storage = None;
+            if storage_path.exists() {
+                std::fs::remove_file(&storage_path)?;
+            }
+            return self.save_config();
+        }
+
+        let num_lists = match self.config.num_lists {
+            0 => (ids.len() as f64).sqrt() as usize,
+            num_lists => num_lists,
+        };
+
+        let storage = {
+            let vector_storage = self.vector_storage.borrow();
+            IvfStorage::build(
+                &ids,
+                |point_id| {
+                    let vector = vector_storage.get_vector(point_id);
+                    let vector: &[VectorElementType] = vector.as_vec_ref().try_into()?;
+                    // Storage may keep original vectors, lists are built from preprocessed ones
+                    Ok(vector_storage.distance().preprocess_vector(vector.to_vec()))
+                },
+                vector_storage.vector_dim(),
+                vector_storage.distance(),
+                num_lists,
+                self.config.pq_subvector_dim,
+                &mut thread_rng(),
+                stopped,
+            )?
+        };
+        debug!(
+            "finish building IVF index of {} points in {num_lists} lists",
+            storage.num_points(),
+        );
+
+        storage.save(&storage_path)?;
+        self.storage = Some(storage);
+        self.save_config()
+    }
+
+    fn get_telemetry_data(&self) -> VectorIndexSearchesTelemetry {
+        let tm = &self.searches_telemetry;
+        VectorIndexSearchesTelemetry {
+            index_name: None,
+            unfiltered_plain: tm.unfiltered_plain.lock().get_statistics(),
+            filtered_plain: Default::default(),
+            unfiltered_hnsw: tm.unfiltered_ivf.lock().get_statistics(),
+            filtered_small_cardinality: tm.small_cardinality.lock().get_statistics(),
+            filtered_large_cardinality: tm.large_cardinality.lock().get_statistics(),
+            filtered_exact: tm.exact_filtered.lock().get_statistics(),
+            filtered_sparse: Default::default(),
+            unfiltered_exact: tm.exact_unfiltered.lock().get_statistics(),
+            unfiltered_sparse: Default::default(),
+            graph: None,
+        }
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        if self.storage.is_some() {
+            vec![IvfStorage::get_path(&self.path)]
+        } else {
+            vec![]
+        }
+    }
+
+    fn indexed_vector_count(&self) -> usize {
+        self.config
+            .indexed_vector_count
+            // If indexed vector count is unknown, fall back to number of points
+            .or_else(|| self.storage.as_ref().map(|storage| storage.num_points()))
+            .unwrap_or(0)
+    }
+
+    fn update_vector(&mut self, _id: PointOffsetType, _vector: VectorRef) -> OperationResult<()> {
+        Err(OperationError::service_error("Cannot update IVF index"))
+    }
+}
@@ -0,0 +1,330 @@
+use std::cmp::min;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
+use io::file_operations::{atomic_save_bin, read_bin};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::kmeans::{kmeans, nearest_centroid};
+use crate::common::operation_error::{check_process_stopped, OperationResult};
+use crate::data_types::vectors::{DenseVector, VectorElementType};
+use crate::types::Distance;
+
+pub const IVF_INDEX_FILE: &str = "ivf_index.bin";
+
+/// Number of centroids of a product quantization subspace, so that a code fits into a byte
+const PQ_CENTROIDS: usize = 256;
+
+/// Number of training vectors per centroid, used to cluster the sample of vectors
+const TRAINING_VECTORS_PER_CENTROID: usize = 32;
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct InvertedList {
+    ids: Vec<PointOffsetType>,
+    /// Product quantization codes of residuals, one byte per subspace for each point
+    codes: Vec<u8>,
+}
+
+/// Inverted lists of product-quantized vectors
+///
+/// Vectors are clustered with k-means, each cluster forms an inverted list.
+/// Residuals of vectors against the centroid of their list are encoded with product quantization:
+/// every subvector of `subvector_dim` dimensions is replaced by the index of the nearest
+/// codebook entry of its subspace.
+///
+/// Scores are approximated with lookup tables, computed for each scanned list,
+/// so they should be used to preselect candidates only.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct IvfStorage {
+    dim: usize,
+    distance: Distance,
+    subvector_dim: usize,
+    /// Centroids of the lists, `dim` values each
+    centroids: Vec<VectorElementType>,
+    /// Codebook entries of each subspace, one after another
+    codebooks: Vec<Vec<VectorElementType>>,
+    lists: Vec<InvertedList>,
+}
+
+impl IvfStorage {
+    pub fn get_path(path: &Path) -> PathBuf {
+        path.join(IVF_INDEX_FILE)
+    }
+
+    pub fn load(path: &Path) -> OperationResult<Self> {
+        Ok(read_bin(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> OperationResult<()> {
+        Ok(atomic_save_bin(path, self)?)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn build<F>(
+        ids: &[PointOffsetType],
+        get_vector: F,
+        dim: usize,
+        distance: Distance,
+        num_lists: usize,
+        subvector_dim: usize,
+        rng: &mut impl Rng,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Self>
+    where
+        F: Fn(PointOffsetType) -> OperationResult<DenseVector> + Sync,
+    {
+        let num_lists = num_lists.clamp(1, ids.len().max(1));
+        let subvector_dim = subvector_dim.clamp(1, dim);
+
+        // Train centroids and codebooks on a sample of vectors
+        let sample_size = min(
+            ids.len(),
+            num_lists.max(PQ_CENTROIDS) * TRAINING_VECTORS_PER_CENTROID,
+        );
+        let mut training_data = Vec::with_capacity(sample_size * dim);
+        for &point_id in ids.choose_multiple(rng, sample_size) {
+            training_data.extend(get_vector(point_id)?);
+        }
+
+        let centroids = kmeans(&training_data, dim, num_lists, rng, stopped)?;
+
+        training_data
+            .par_chunks_exact_mut(dim)
+            .for_each(|vector| subtract_nearest_centroid(&centroids, vector));
+
+        let mut codebooks = Vec::new();
+        for subspace in (0..dim).step_by(subvector_dim) {
+            let subspace = subspace..min(subspace + subvector_dim, dim);
+            let subspace_data: Vec<_> = training_data
+                .chunks_exact(dim)
+                .flat_map(|residual| residual[subspace.clone()].iter().copied())
+                .collect();
+            let num_entries = min(PQ_CENTROIDS, sample_size);
+            codebooks.push(kmeans(
+                &subspace_data,
+                subspace.len(),
+                num_entries,
+                rng,
+                stopped,
+            )?);
+        }
+        check_process_stopped(stopped)?;
+
+        let mut storage = Self {
+            dim,
+            distance,
+            subvector_dim,
+            centroids,
+            codebooks,
+            lists: Vec::new(),
+        };
+
+        let encoded: Vec<(usize, Vec<u8>)> = ids
+            .par_iter()
+            .map(|&point_id| {
+                check_process_stopped(stopped)?;
+                let mut vector = get_vector(point_id)?;
+                let list = subtract_nearest_centroid(&storage.centroids, &mut vector);
+                Ok((list, storage.encode(&vector)))
+            })
+            .collect::<OperationResult<_>>()?;
+
+        storage.lists = (0..num_lists).map(|_| InvertedList::default()).collect();
+        for (&point_id, (list, codes)) in ids.iter().zip(encoded) {
+            let list = &mut storage.lists[list];
+            list.ids.push(point_id);
+            list.codes.extend(codes);
+        }
+
+        Ok(storage)
+    }
+
+    pub fn num_points(&self) -> usize {
+        self.lists.iter().map(|list| list.ids.len()).sum()
+    }
+
+    pub fn distance(&self) -> Distance {
+        self.distance
+    }
+
+    fn subspaces(&self) -> impl Iterator<Item = std::ops::Range<usize>> + '_ {
+        (0..self.dim)
+            .step_by(self.subvector_dim)
+            .map(|start| start..min(start + self.subvector_dim, self.dim))
+    }
+
+    /// Product quantization codes of the residual
+    fn encode(&self, residual: &[VectorElementType]) -> Vec<u8> {
+        self.subspaces()
+            .zip(&self.codebooks)
+            .map(|(subspace, codebook)| nearest_centroid(codebook, &residual[subspace]) as u8)
+            .collect()
+    }
+
+    /// Approximate search of the preprocessed query in `nprobe` nearest lists
+    ///
+    /// Only points accepted by `check` are scored.
+    pub fn search(
+        &self,
+        query: &[VectorElementType],
+        nprobe: usize,
+        top: usize,
+        mut check: impl FnMut(PointOffsetType) -> bool,
+    ) -> Vec<ScoredPointOffset> {
+        let mut nearest_lists = FixedLengthPriorityQueue::new(nprobe.max(1));
+        for (list, centroid) in self.centroids.chunks_exact(self.dim).enumerate() {
+            nearest_lists.push(ScoredPointOffset {
+                idx: list as PointOffsetType,
+                score: self.similarity(query, centroid.iter().copied()),
+            });
+        }
+
+        let num_subspaces = self.codebooks.len();
+        let mut lookup_table = vec![0.0; num_subspaces * PQ_CENTROIDS];
+        let mut result = FixedLengthPriorityQueue::new(top.max(1));
+        for nearest_list in nearest_lists.into_vec() {
+            let list_idx = nearest_list.idx as usize;
+            let list = &self.lists[list_idx];
+            if list.ids.is_empty() {
+                continue;
+            }
+
+            // Score of every codebook entry, added to the centroid of the list
+            let centroid = &self.centroids[list_idx * self.dim..(list_idx + 1) * self.dim];
+            for (subspace_idx, (subspace, codebook)) in
+                self.subspaces().zip(&self.codebooks).enumerate()
+            {
+                let table = &mut lookup_table[subspace_idx * PQ_CENTROIDS..];
+                for (entry, entry_score) in codebook.chunks_exact(subspace.len()).zip(table) {
+                    let reconstructed = centroid[subspace.clone()]
+                        .iter()
+                        .zip(entry)
+                        .map(|(centroid, entry)| centroid + entry);
+                    *entry_score = self.similarity(&query[subspace.clone()], reconstructed);
+                }
+            }
+
+            for (&point_id, codes) in list.ids.iter().zip(list.codes.chunks_exact(num_subspaces)) {
+                if !check(point_id) {
+                    continue;
+                }
+                let score = codes
+                    .iter()
+                    .enumerate()
+                    .map(|(subspace_idx, &code)| {
+                        lookup_table[subspace_idx * PQ_CENTROIDS + code as usize]
+                    })
+                    .sum();
+                result.push(ScoredPointOffset {
+                    idx: point_id,
+                    score,
+                });
+            }
+        }
+        result.into_vec()
+    }
+
+    /// Similarity, which sums up over subspaces and orders vectors like the distance does
+    fn similarity(
+        &self,
+        query: &[VectorElementType],
+        vector: impl Iterator<Item = VectorElementType>,
+    ) -> ScoreType {
+        let pairs = query.iter().zip(vector);
+        match self.distance {
+            Distance::Cosine | Distance::Dot => pairs.map(|(q, v)| q * v).sum(),
+            Distance::Euclid => -pairs.map(|(q, v)| (q - v) * (q - v)).sum::<ScoreType>(),
+            Distance::Manhattan => -pairs.map(|(q, v)| (q - v).abs()).sum::<ScoreType>(),
+            Distance::Hamming => {
+                -(pairs
+                    .map(|(q, v)| ((q.round() as u8) ^ (v.round() as u8)).count_ones())
+                    .sum::<u32>() as ScoreType)
+            }
+        }
+    }
+}
+
+/// Replace the vector with its residual against the nearest centroid, return the centroid index
+fn subtract_nearest_centroid(
+    centroids: &[VectorElementType],
+    vector: &mut [VectorElementType],
+) -> usize {
+    let dim = vector.len();
+    let nearest = nearest_centroid(centroids, vector);
+    vector
+        .iter_mut()
+        .zip(&centroids[nearest * dim..(nearest + 1) * dim])
+        .for_each(|(value, centroid)| *value -= centroid);
+    nearest
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::fixtures::index_fixtures::random_vector;
+    use crate::index::ivf_index::kmeans::squared_distance;
+
+    #[test]
+    fn test_build_and_search_ivf_storage() {
+        let num_vectors = 2000;
+        let dim = 16;
+        let mut rng = StdRng::seed_from_u64(42);
+        let vectors: Vec<DenseVector> = (0..num_vectors)
+            .map(|_| random_vector(&mut rng, dim))
+            .collect();
+        let ids: Vec<PointOffsetType> = (0..num_vectors as PointOffsetType).collect();
+
+        let storage = IvfStorage::build(
+            &ids,
+            |point_id| Ok(vectors[point_id as usize].clone()),
+            dim,
+            Distance::Euclid,
+            20,
+            2,
+            &mut rng,
+            &AtomicBool::new(false),
+        )
+        .unwrap();
+        assert_eq!(storage.num_points(), num_vectors);
+
+        let dir = Builder::new().prefix("ivf_storage").tempdir().unwrap();
+        let path = IvfStorage::get_path(dir.path());
+        storage.save(&path).unwrap();
+        let storage = IvfStorage::load(&path).unwrap();
+
+        let num_queries = 20;
+        let mut found = 0;
+        for _ in 0..num_queries {
+            let query = random_vector(&mut rng, dim);
+            let nearest = ids
+                .iter()
+                .min_by(|&&a, &&b| {
+                    let a = squared_distance(&query, &vectors[a as usize]);
+                    let b = squared_distance(&query, &vectors[b as usize]);
+                    a.total_cmp(&b)
+                })
+                .copied()
+                .unwrap();
+
+            let candidates = storage.search(&query, 20, 50, |_| true);
+            assert_eq!(candidates.len(), 50);
+            if candidates.iter().any(|scored| scored.idx == nearest) {
+                found += 1;
+            }
+
+            // Odd points are excluded
+            let filtered = storage.search(&query, 20, 50, |point_id| point_id % 2 == 0);
+            assert!(filtered.iter().all(|scored| scored.idx % 2 == 0));
+        }
+        assert!(found >= num_queries * 8 / 10, "found {found}/{num_queries}");
+    }
+}
@@ -0,0 +1,83 @@
+use std::sync::atomic::AtomicBool;
+
+use rand::seq::index::sample;
+use rand::Rng;
+use rayon::prelude::*;
+
+use crate::common::operation_error::{check_process_stopped, OperationResult};
+use crate::data_types::vectors::VectorElementType;
+
+/// Number of iterations of k-means clustering
+const KMEANS_ITERATIONS: usize = 10;
+
+pub fn squared_distance(a: &[VectorElementType], b: &[VectorElementType]) -> VectorElementType {
+    a.iter().zip(b).map(|(a, b)| (a - b) * (a - b)).sum()
+}
+
+/// Index of the centroid nearest to the vector
+///
+/// `centroids` contain vectors of `vector.len()` dimensions one after another.
+pub fn nearest_centroid(centroids: &[VectorElementType], vector: &[VectorElementType]) -> usize {
+    centroids
+        .chunks_exact(vector.len())
+        .map(|centroid| squared_distance(centroid, vector))
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(idx, _)| idx)
+        .unwrap_or(0)
+}
+
+/// Cluster vectors with Lloyd's k-means algorithm
+///
+/// `data` contains vectors of `dim` dimensions one after another, result contains
+/// `k` centroids in the same layout. `k` must not exceed the number of vectors.
+/// Clusters, which became empty, are restarted from a random vector.
+pub fn kmeans(
+    data: &[VectorElementType],
+    dim: usize,
+    k: usize,
+    rng: &mut impl Rng,
+    stopped: &AtomicBool,
+) -> OperationResult<Vec<VectorElementType>> {
+    let num_vectors = data.len() / dim;
+    debug_assert!(k > 0 && k <= num_vectors);
+    let vector = |idx: usize| &data[idx * dim..(idx + 1) * dim];
+
+    let mut centroids: Vec<VectorElementType> = sample(rng, num_vectors, k)
+        .into_iter()
+        .flat_map(|idx| vector(idx).iter().copied())
+        .collect();
+
+    for _ in 0..KMEANS_ITERATIONS {
+        check_process_stopped(stopped)?;
+
+        let assignments: Vec<usize> = data
+            .par_chunks_exact(dim)
+            .map(|vector| nearest_centroid(&centroids, vector))
+            .collect();
+
+        let mut sums = vec![0.0; k * dim];
+        let mut counts = vec![0usize; k];
+        for (idx, &cluster) in assignments.iter().enumerate() {
+            counts[cluster] += 1;
+            sums[cluster * dim..(cluster + 1) * dim]
+                .iter_mut()
+                .zip(vector(idx))
+                .for_each(|(sum, value)| *sum += value);
+        }
+
+        for (cluster, count) in counts.into_iter().enumerate() {
+            let centroid = &mut centroids[cluster * dim..(cluster + 1) * dim];
+            if count == 0 {
+                centroid.copy_from_slice(vector(rng.gen_range(0..num_vectors)));
+                continue;
+            }
+            centroid
+                .iter_mut()
+                .zip(&sums[cluster * dim..(cluster + 1) * dim])
+                .for_each(|(value, sum)| *value = sum / count as VectorElementType);
+        }
+    }
+
+    Ok(centroids)
+}
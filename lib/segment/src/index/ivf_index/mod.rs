@@ -0,0 +1,4 @@
+mod config;
+pub mod ivf;
+pub mod ivf_storage;
+mod kmeans;
@@ -1,15 +1,19 @@
 pub mod field_index;
+mod filter_cache;
 pub mod hnsw_index;
+pub mod ivf_index;
 mod key_encoding;
 mod payload_config;
 mod payload_index_base;
 pub mod plain_payload_index;
 pub mod query_estimator;
 mod query_optimization;
+pub mod query_planner;
 mod sample_estimation;
 pub mod sparse_index;
 mod struct_filter_context;
 pub mod struct_payload_index;
+pub mod vamana_index;
 pub mod vector_index_base;
 mod visited_pool;
 
@@ -3,12 +3,12 @@
 //! Filter query is used e.g. for determining how would be faster to process the query:
 //! - use vector index or payload index first
 
-use std::cmp::{max, min};
+use std::cmp::{max, min, Reverse};
 
 use itertools::Itertools;
 
 use crate::index::field_index::{CardinalityEstimation, PrimaryCondition};
-use crate::types::{Condition, Filter};
+use crate::types::{Condition, Filter, MinShould};
 
 /// Re-estimate cardinality based on number of available vectors
 /// Assuming that deleted vectors are not correlated with the filter
@@ -160,6 +160,81 @@ pub fn combine_must_estimations(
     }
 }
 
+/// Estimate number of points, which match at least `min_count` of conditions with `estimations`
+///
+/// Expected value assumes that conditions are independent.
+pub fn combine_min_should_estimations(
+    estimations: &[CardinalityEstimation],
+    min_count: usize,
+    total: usize,
+) -> CardinalityEstimation {
+    if min_count == 0 {
+        return CardinalityEstimation::exact(total);
+    }
+    if min_count > estimations.len() {
+        return CardinalityEstimation::exact(0);
+    }
+    if min_count == 1 {
+        return combine_should_estimations(estimations, total);
+    }
+
+    // Each matched point matches at least one of any `len - min_count + 1` conditions,
+    // so the least probable of them are enough to select all candidates
+    let mut by_exp = estimations.iter().collect_vec();
+    by_exp.sort_by_key(|x| x.exp);
+    let candidates = &by_exp[..estimations.len() - min_count + 1];
+    let clauses = if candidates.iter().any(|x| x.primary_clauses.is_empty()) {
+        vec![]
+    } else {
+        candidates
+            .iter()
+            .flat_map(|x| x.primary_clauses.iter().cloned())
+            .collect()
+    };
+
+    // Points, which match all `min_count` conditions with the largest minimums
+    let mut mins = estimations.iter().map(|x| x.min).collect_vec();
+    mins.sort_unstable_by_key(|&x| Reverse(x));
+    let min_estimation = mins[..min_count].iter().fold(total as i64, |acc, &x| {
+        max(0, acc + (x as i64) - (total as i64))
+    }) as usize;
+
+    // Each matched point is counted at least `min_count` times in the sum of maximums
+    let max_estimation = min(
+        estimations.iter().map(|x| x.max).sum::<usize>() / min_count,
+        total,
+    );
+
+    // Probability distribution of the number of matched conditions, truncated at `min_count`
+    let mut matched_prob = vec![0.0; min_count];
+    matched_prob[0] = 1.0;
+    for estimation in estimations {
+        let hit_prob = if total == 0 {
+            0.0
+        } else {
+            estimation.exp as f64 / total as f64
+        };
+        for matched in (0..min_count).rev() {
+            let prev = if matched == 0 {
+                0.0
+            } else {
+                matched_prob[matched - 1]
+            };
+            matched_prob[matched] = matched_prob[matched] * (1.0 - hit_prob) + prev * hit_prob;
+        }
+    }
+    let element_hit_prob = 1.0 - matched_prob.iter().sum::<f64>();
+    let exp_estimation =
+        ((element_hit_prob * total as f64).round() as usize).clamp(min_estimation, max_estimation);
+
+    CardinalityEstimation {
+        primary_clauses: clauses,
+        min: min_estimation,
+        exp: exp_estimation,
+        max: max_estimation,
+    }
+}
+
 fn estimate_condition<F>(
     estimator: &F,
     condition: &Condition,
@@ -196,6 +271,19 @@ where
             }
         }
     }
+    match &filter.min_should {
+        None => {}
+        Some(MinShould {
+            conditions,
+            min_count,
+        }) => {
+            if !conditions.is_empty() {
+                filter_estimations.push(estimate_min_should(
+                    estimator, conditions, *min_count, total,
+                ));
+            }
+        }
+    }
     match &filter.must_not {
         None => {}
         Some(conditions) => {
@@ -221,6 +309,20 @@ where
     combine_should_estimations(&should_estimations, total)
 }
 
+fn estimate_min_should<F>(
+    estimator: &F,
+    conditions: &[Condition],
+    min_count: usize,
+    total: usize,
+) -> CardinalityEstimation
+where
+    F: Fn(&Condition) -> CardinalityEstimation,
+{
+    let estimate = |x| estimate_condition(estimator, x, total);
+    let min_should_estimations = conditions.iter().map(estimate).collect_vec();
+    combine_min_should_estimations(&min_should_estimations, min_count, total)
+}
+
 fn estimate_must<F>(estimator: &F, conditions: &[Condition], total: usize) -> CardinalityEstimation
 where
     F: Fn(&Condition) -> CardinalityEstimation,
@@ -327,6 +429,13 @@ mod tests {
                 exp: TOTAL / 2,
                 max: TOTAL,
             },
+            Condition::HasVector(_) => CardinalityEstimation::unknown(TOTAL),
+            Condition::HasIdRange(has_id_range) => CardinalityEstimation {
+                primary_clauses: vec![PrimaryCondition::IdRange(has_id_range.has_id_range)],
+                min: 0,
+                exp: TOTAL / 2,
+                max: TOTAL,
+            },
         }
     }
 
@@ -342,6 +451,7 @@ mod tests {
     fn must_estimation_query_test() {
         let query = Filter {
             should: None,
+            min_should: None,
             must: Some(vec![
                 test_condition("color".to_owned()),
                 test_condition("size".to_owned()),
@@ -368,6 +478,7 @@ mod tests {
                 test_condition("color".to_owned()),
                 test_condition("size".to_owned()),
             ]),
+            min_should: None,
             must: None,
             must_not: None,
         };
@@ -387,6 +498,7 @@ mod tests {
                 test_condition("size".to_owned()),
                 test_condition("un-indexed".to_owned()),
             ]),
+            min_should: None,
             must: None,
             must_not: None,
         };
@@ -405,6 +517,7 @@ mod tests {
             should: Some(vec![
                 Condition::Filter(Filter {
                     should: None,
+                    min_should: None,
                     must: Some(vec![
                         test_condition("color".to_owned()),
                         test_condition("size".to_owned()),
@@ -413,6 +526,7 @@ mod tests {
                 }),
                 Condition::Filter(Filter {
                     should: None,
+                    min_should: None,
                     must: Some(vec![
                         test_condition("price".to_owned()),
                         test_condition("size".to_owned()),
@@ -420,6 +534,7 @@ mod tests {
                     must_not: None,
                 }),
             ]),
+            min_should: None,
             must: None,
             must_not: Some(vec![Condition::HasId(HasIdCondition {
                 has_id: HashSet::from_iter([1, 2, 3, 4, 5].into_iter().map(|x| x.into())),
@@ -437,6 +552,7 @@ mod tests {
     fn another_complex_estimation_query_test() {
         let query = Filter {
             should: None,
+            min_should: None,
             must: Some(vec![
                 Condition::Filter(Filter {
                     must: None,
@@ -444,6 +560,7 @@ mod tests {
                         test_condition("color".to_owned()),
                         test_condition("size".to_owned()),
                     ]),
+                    min_should: None,
                     must_not: None,
                 }),
                 Condition::Filter(Filter {
@@ -452,6 +569,7 @@ mod tests {
                         test_condition("price".to_owned()),
                         test_condition("size".to_owned()),
                     ]),
+                    min_should: None,
                     must_not: None,
                 }),
             ]),
@@ -473,6 +591,46 @@ mod tests {
         assert!(estimation.min <= estimation.exp);
     }
 
+    #[test]
+    fn min_should_estimation_query_test() {
+        let query = Filter {
+            should: None,
+            min_should: Some(MinShould {
+                conditions: vec![
+                    test_condition("color".to_owned()),
+                    test_condition("size".to_owned()),
+                    test_condition("price".to_owned()),
+                ],
+                min_count: 2,
+            }),
+            must: None,
+            must_not: None,
+        };
+
+        let estimation = estimate_filter(&test_estimator, &query, TOTAL);
+        // Two least probable conditions cover all candidates
+        assert_eq!(estimation.primary_clauses.len(), 2);
+        estimation.primary_clauses.iter().for_each(|x| match x {
+            PrimaryCondition::Condition(field) => {
+                assert!(["price".to_owned(), "size".to_owned()].contains(&field.key))
+            }
+            _ => panic!("Should not go here"),
+        });
+        assert_eq!(estimation.max, 210);
+        assert!(estimation.exp <= estimation.max);
+        assert!(estimation.min <= estimation.exp);
+
+        let should_estimation = combine_min_should_estimations(
+            &[
+                test_estimator(&test_condition("color".to_owned())),
+                test_estimator(&test_condition("size".to_owned())),
+            ],
+            1,
+            TOTAL,
+        );
+        assert_eq!(should_estimation.exp, 280);
+    }
+
     #[test]
     fn test_combine_must_estimations() {
         let estimations = vec![CardinalityEstimation {
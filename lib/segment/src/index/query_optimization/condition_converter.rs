@@ -1,21 +1,23 @@
 use std::collections::HashSet;
 
+use bitvec::vec::BitVec;
 use common::types::PointOffsetType;
 use serde_json::Value;
 
-use crate::common::utils::IndexesMap;
+use crate::common::utils::{IndexesMap, VectorStoragesMap};
 use crate::id_tracker::IdTrackerSS;
+use crate::index::field_index::null_index::NullIndex;
 use crate::index::field_index::FieldIndex;
 use crate::index::query_optimization::optimized_filter::ConditionCheckerFn;
 use crate::index::query_optimization::payload_provider::PayloadProvider;
 use crate::payload_storage::query_checker::{
-    check_field_condition, check_is_empty_condition, check_is_null_condition, check_payload,
-    select_nested_indexes,
+    check_field_condition, check_has_vector, check_is_empty_condition, check_is_null_condition,
+    check_payload, select_nested_indexes,
 };
 use crate::types::{
-    AnyVariants, Condition, FieldCondition, FloatPayloadType, GeoBoundingBox, GeoPolygon,
-    GeoRadius, Match, MatchAny, MatchExcept, MatchText, MatchValue, OwnedPayloadRef,
-    PayloadContainer, Range, ValueVariants,
+    parse_uuid, AnyVariants, Condition, DatetimeRange, FieldCondition, FloatPayloadType,
+    GeoBoundingBox, GeoPolygon, GeoRadius, Match, MatchAny, MatchExcept, MatchPhrase, MatchPrefix,
+    MatchText, MatchValue, OwnedPayloadRef, PayloadContainer, Range, RangeInterface, ValueVariants,
 };
 
 pub fn condition_converter<'a>(
@@ -23,46 +25,69 @@ pub fn condition_converter<'a>(
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     id_tracker: &IdTrackerSS,
+    vector_storages: &VectorStoragesMap,
 ) -> ConditionCheckerFn<'a> {
     match condition {
-        Condition::Field(field_condition) => field_indexes
-            .get(&field_condition.key)
-            .and_then(|indexes| {
-                indexes
-                    .iter()
-                    .find_map(|index| field_condition_index(index, field_condition))
-            })
-            .unwrap_or_else(|| {
-                Box::new(move |point_id| {
-                    payload_provider.with_payload(point_id, |payload| {
-                        check_field_condition(field_condition, &payload, field_indexes)
-                    })
-                })
-            }),
-        // We can use index for `is_empty` condition effectively only when it is not empty.
-        // If the index says it is "empty", we still need to check the payload.
-        Condition::IsEmpty(is_empty) => {
-            let first_field_index = field_indexes
-                .get(&is_empty.is_empty.key)
-                .and_then(|indexes| indexes.first());
-
-            let fallback = Box::new(move |point_id| {
+        Condition::Field(field_condition) => {
+            let indexes = field_indexes.get(&field_condition.key);
+            let payload_checker = move |point_id| {
                 payload_provider.with_payload(point_id, |payload| {
-                    check_is_empty_condition(is_empty, &payload)
+                    check_field_condition(field_condition, &payload, field_indexes)
                 })
-            });
+            };
 
-            match first_field_index {
-                Some(index) => get_is_empty_checker(index, fallback),
-                None => fallback,
+            // Number of values is tracked by null index, unless the field has multiple payload values
+            if let (Some(values_count), Some(null_index)) = (
+                &field_condition.values_count,
+                indexes.and_then(|indexes| NullIndex::find(indexes)),
+            ) {
+                return Box::new(move |point_id| {
+                    if null_index.values_is_multiple(point_id) {
+                        payload_checker(point_id)
+                    } else {
+                        null_index
+                            .get_values_count(point_id)
+                            .map_or(false, |count| values_count.check_values_count(count))
+                    }
+                });
+            }
+
+            indexes
+                .and_then(|indexes| {
+                    indexes
+                        .iter()
+                        .find_map(|index| field_condition_index(index, field_condition))
+                })
+                .unwrap_or_else(|| Box::new(payload_checker))
+        }
+        // Indexed fields track empty and null values, so payload is only checked for non-indexed fields
+        Condition::IsEmpty(is_empty) => {
+            let null_index = field_indexes
+                .get(&is_empty.is_empty.key)
+                .and_then(|indexes| NullIndex::find(indexes));
+            match null_index {
+                Some(null_index) => Box::new(move |point_id| null_index.values_is_empty(point_id)),
+                None => Box::new(move |point_id| {
+                    payload_provider.with_payload(point_id, |payload| {
+                        check_is_empty_condition(is_empty, &payload)
+                    })
+                }),
             }
         }
 
-        Condition::IsNull(is_null) => Box::new(move |point_id| {
-            payload_provider.with_payload(point_id, |payload| {
-                check_is_null_condition(is_null, &payload)
-            })
-        }),
+        Condition::IsNull(is_null) => {
+            let null_index = field_indexes
+                .get(&is_null.is_null.key)
+                .and_then(|indexes| NullIndex::find(indexes));
+            match null_index {
+                Some(null_index) => Box::new(move |point_id| null_index.values_is_null(point_id)),
+                None => Box::new(move |point_id| {
+                    payload_provider.with_payload(point_id, |payload| {
+                        check_is_null_condition(is_null, &payload)
+                    })
+                }),
+            }
+        }
         // ToDo: It might be possible to make this condition faster by using `VisitedPool` instead of HashSet
         Condition::HasId(has_id) => {
             let segment_ids: HashSet<_> = has_id
@@ -72,6 +97,26 @@ pub fn condition_converter<'a>(
                 .collect();
             Box::new(move |point_id| segment_ids.contains(&point_id))
         }
+        Condition::HasIdRange(has_id_range) => {
+            // Bitmap of internal ids doesn't depend on the size of the range
+            let mut segment_ids = BitVec::repeat(false, id_tracker.total_point_count());
+            for (_, point_id) in id_tracker.iter_range(has_id_range.has_id_range) {
+                segment_ids.set(point_id as usize, true);
+            }
+            Box::new(move |point_id| {
+                segment_ids
+                    .get(point_id as usize)
+                    .map_or(false, |matches| *matches)
+            })
+        }
+        Condition::HasVector(has_vector) => {
+            match vector_storages.get(&has_vector.has_vector).cloned() {
+                Some(vector_storage) => {
+                    Box::new(move |point_id| check_has_vector(&vector_storage.borrow(), point_id))
+                }
+                None => Box::new(|_| false),
+            }
+        }
         Condition::Nested(nested) => {
             // Select indexes for nested fields. Trim nested part from key, so
             // that nested condition can address fields without nested part.
@@ -99,24 +144,26 @@ pub fn condition_converter<'a>(
                 payload_provider.with_payload(point_id, |payload| {
                     let field_values = payload.get_value(&nested_path).values();
 
-                    for value in field_values {
-                        if let Value::Object(object) = value {
-                            let get_payload = || OwnedPayloadRef::from(object);
-                            if check_payload(
-                                Box::new(get_payload),
-                                // None because has_id in nested is not supported. So retrieving
-                                // IDs through the tracker would always return None.
-                                None,
-                                &nested.nested.filter,
-                                point_id,
-                                &nested_indexes,
-                            ) {
-                                // If at least one nested object matches, return true
-                                return true;
-                            }
-                        }
-                    }
-                    false
+                    let matches = field_values.into_iter().filter_map(|value| match value {
+                        Value::Object(object) => Some(object),
+                        _ => None,
+                    });
+                    let matches = matches.map(|object| {
+                        let get_payload = || OwnedPayloadRef::from(object);
+                        check_payload(
+                            Box::new(get_payload),
+                            // None because has_id in nested is not supported. So retrieving
+                            // IDs through the tracker would always return None.
+                            None,
+                            // Same for has_vector, vectors are assigned to points, not to nested objects
+                            &VectorStoragesMap::new(),
+                            &nested.nested.filter,
+                            point_id,
+                            &nested_indexes,
+                        )
+                    });
+                    // By default at least one nested object has to match
+                    nested.check_matches(matches)
                 })
             })
         }
@@ -221,7 +268,14 @@ pub fn get_geo_bounding_box_checkers(
     }
 }
 
-pub fn get_range_checkers(index: &FieldIndex, range: Range) -> Option<ConditionCheckerFn> {
+pub fn get_range_checkers(index: &FieldIndex, range: RangeInterface) -> Option<ConditionCheckerFn> {
+    match range {
+        RangeInterface::Float(range) => get_float_range_checkers(index, range),
+        RangeInterface::DateTime(range) => get_datetime_range_checkers(index, range),
+    }
+}
+
+pub fn get_float_range_checkers(index: &FieldIndex, range: Range) -> Option<ConditionCheckerFn> {
     match index {
         FieldIndex::IntIndex(num_index) => Some(Box::new(move |point_id: PointOffsetType| {
             num_index.get_values(point_id).map_or(false, |values| {
@@ -240,6 +294,27 @@ pub fn get_range_checkers(index: &FieldIndex, range: Range) -> Option<ConditionC
     }
 }
 
+pub fn get_datetime_range_checkers(
+    index: &FieldIndex,
+    range: DatetimeRange,
+) -> Option<ConditionCheckerFn> {
+    match index {
+        FieldIndex::DatetimeIndex(datetime_index) => {
+            // Index stores timestamps in microseconds
+            let range = range.to_timestamp_range();
+            Some(Box::new(move |point_id: PointOffsetType| {
+                datetime_index.get_values(point_id).map_or(false, |values| {
+                    values
+                        .iter()
+                        .copied()
+                        .any(|i| range.check_range(i as FloatPayloadType))
+                })
+            }))
+        }
+        _ => None,
+    }
+}
+
 pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<ConditionCheckerFn> {
     match cond_match {
         Match::Value(MatchValue {
@@ -247,16 +322,20 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
         }) => match (value_variant, index) {
             (ValueVariants::Keyword(keyword), FieldIndex::KeywordIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index
-                        .get_values(point_id)
-                        .map_or(false, |values| values.iter().any(|k| k == &keyword))
+                    index.check_values_any(point_id, |k| k == &keyword)
                 }))
             }
             (ValueVariants::Integer(value), FieldIndex::IntMapIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index
-                        .get_values(point_id)
-                        .map_or(false, |values| values.iter().any(|i| i == &value))
+                    index.check_values_any(point_id, |i| i == &value)
+                }))
+            }
+            (ValueVariants::Keyword(keyword), FieldIndex::UuidIndex(index)) => {
+                let uuid = parse_uuid(&keyword);
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    uuid.map_or(false, |uuid| {
+                        index.check_values_any(point_id, |u| u == &uuid)
+                    })
                 }))
             }
             (ValueVariants::Bool(is_true), FieldIndex::BinaryIndex(index)) => {
@@ -270,13 +349,42 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
             }
             _ => None,
         },
-        Match::Text(MatchText { text }) => match index {
-            FieldIndex::FullTextIndex(full_text_index) => {
+        Match::Text(MatchText { text, analyzer }) => match index {
+            FieldIndex::FullTextIndex(full_text_index)
+                if full_text_index.has_analyzer(analyzer.as_deref()) =>
+            {
                 let parsed_query = full_text_index.parse_query(&text);
                 Some(Box::new(move |point_id: PointOffsetType| {
                     full_text_index
                         .get_doc(point_id)
-                        .map_or(false, |doc| parsed_query.check_match(doc))
+                        .map_or(false, |doc| parsed_query.check_match(&doc))
+                }))
+            }
+            _ => None,
+        },
+        Match::Phrase(MatchPhrase { phrase, analyzer }) => match index {
+            FieldIndex::FullTextIndex(full_text_index)
+                if full_text_index.has_analyzer(analyzer.as_deref()) =>
+            {
+                let parsed_query = full_text_index.parse_phrase_query(&phrase);
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    full_text_index
+                        .get_doc(point_id)
+                        .map_or(false, |doc| parsed_query.check_match(&doc))
+                }))
+            }
+            _ => None,
+        },
+        Match::Prefix(MatchPrefix { prefix }) => match index {
+            FieldIndex::KeywordIndex(index) => Some(Box::new(move |point_id: PointOffsetType| {
+                index.check_values_any(point_id, |k| k.starts_with(prefix.as_str()))
+            })),
+            FieldIndex::FullTextIndex(full_text_index) if full_text_index.has_analyzer(None) => {
+                let parsed_query = full_text_index.parse_prefix_query(&prefix);
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    full_text_index
+                        .get_doc(point_id)
+                        .map_or(false, |doc| parsed_query.check_match(&doc))
                 }))
             }
             _ => None,
@@ -284,18 +392,20 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
         Match::Any(MatchAny { any }) => match (any, index) {
             (AnyVariants::Keywords(list), FieldIndex::KeywordIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index.get_values(point_id).map_or(false, |values| {
-                        values
-                            .iter()
-                            .any(|k| list.iter().any(|s| s.as_str() == k.as_ref()))
+                    index.check_values_any(point_id, |k| {
+                        list.iter().any(|s| s.as_str() == k.as_ref())
                     })
                 }))
             }
             (AnyVariants::Integers(list), FieldIndex::IntMapIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index
-                        .get_values(point_id)
-                        .map_or(false, |values| values.iter().any(|i| list.contains(i)))
+                    index.check_values_any(point_id, |i| list.contains(i))
+                }))
+            }
+            (AnyVariants::Keywords(list), FieldIndex::UuidIndex(index)) => {
+                let uuids: HashSet<_> = list.iter().filter_map(|s| parse_uuid(s)).collect();
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    index.check_values_any(point_id, |u| uuids.contains(u))
                 }))
             }
             _ => None,
@@ -303,18 +413,14 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
         Match::Except(MatchExcept { except }) => match (except, index) {
             (AnyVariants::Keywords(list), FieldIndex::KeywordIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index.get_values(point_id).map_or(false, |values| {
-                        values
-                            .iter()
-                            .any(|k| !list.iter().any(|s| s.as_str() == k.as_ref()))
+                    index.check_values_any(point_id, |k| {
+                        !list.iter().any(|s| s.as_str() == k.as_ref())
                     })
                 }))
             }
             (AnyVariants::Integers(list), FieldIndex::IntMapIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index
-                        .get_values(point_id)
-                        .map_or(false, |values| values.iter().any(|i| !list.contains(i)))
+                    index.check_values_any(point_id, |i| !list.contains(i))
                 }))
             }
             (_, index) => Some(Box::new(|point_id: PointOffsetType| {
@@ -324,19 +430,3 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
         },
     }
 }
-
-/// Get a checker that checks if the field is empty
-///
-/// * `index` - index to check first
-/// * `fallback` - Check if it is empty using plain payload
-#[inline]
-fn get_is_empty_checker<'a>(
-    index: &'a FieldIndex,
-    fallback: ConditionCheckerFn<'a>,
-) -> ConditionCheckerFn<'a> {
-    Box::new(move |point_id: PointOffsetType| {
-        // Counting on the short-circuit of the `&&` operator
-        // Only check the fallback if the index seems to be empty
-        index.values_is_empty(point_id) && fallback(point_id)
-    })
-}
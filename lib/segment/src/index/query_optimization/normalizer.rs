@@ -0,0 +1,262 @@
+//! Rewrites filters into equivalent ones, which are cheaper to evaluate
+//!
+//! Rewrites:
+//!
+//! * Nested filters are merged into the parent clauses, where it doesn't change the result
+//! * Negations of nested filters are pushed inward, e.g. `not (a or b)` becomes `not a and not b`
+//! * Duplicated conditions of the same clause are removed
+//!
+//! Flattened conditions of all nesting levels are then ordered together by their estimated
+//! cardinality in the optimizer, so that the most selective ones are checked first.
+
+use crate::types::{Condition, Filter};
+
+#[derive(Debug, PartialEq)]
+pub enum NormalizedCondition<'a> {
+    Condition(&'a Condition),
+    /// Nested filter, which can't be merged into the parent one
+    Filter(NormalizedFilter<'a>),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct NormalizedMinShould<'a> {
+    pub conditions: Vec<NormalizedCondition<'a>>,
+    pub min_count: usize,
+}
+
+/// Filter without redundant nesting, empty clauses are ignored the same way as absent ones
+#[derive(Debug, Default, PartialEq)]
+pub struct NormalizedFilter<'a> {
+    /// At least one of those conditions should match
+    pub should: Vec<NormalizedCondition<'a>>,
+    /// At least minimum amount of given conditions should match
+    pub min_should: Option<NormalizedMinShould<'a>>,
+    /// All conditions must match
+    pub must: Vec<NormalizedCondition<'a>>,
+    /// All conditions must NOT match
+    pub must_not: Vec<NormalizedCondition<'a>>,
+}
+
+impl<'a> NormalizedFilter<'a> {
+    fn has_only_must(&self) -> bool {
+        self.should.is_empty() && self.min_should.is_none()
+    }
+
+    fn has_only_should(&self) -> bool {
+        !self.should.is_empty()
+            && self.min_should.is_none()
+            && self.must.is_empty()
+            && self.must_not.is_empty()
+    }
+
+    fn add_must(&mut self, condition: NormalizedCondition<'a>) {
+        match condition {
+            // a and (b and not c) = a and b and not c
+            NormalizedCondition::Filter(nested) if nested.has_only_must() => {
+                nested.must.into_iter().for_each(|c| self.add_must(c));
+                nested
+                    .must_not
+                    .into_iter()
+                    .for_each(|c| self.add_must_not(c));
+            }
+            condition => push_unique(&mut self.must, condition),
+        }
+    }
+
+    fn add_should(&mut self, condition: NormalizedCondition<'a>) {
+        match condition {
+            // a or (b or c) = a or b or c
+            NormalizedCondition::Filter(nested) if nested.has_only_should() => {
+                nested.should.into_iter().for_each(|c| self.add_should(c));
+            }
+            condition => push_unique(&mut self.should, condition),
+        }
+    }
+
+    fn add_must_not(&mut self, condition: NormalizedCondition<'a>) {
+        match condition {
+            // not (a or b) = not a and not b
+            NormalizedCondition::Filter(nested) if nested.has_only_should() => {
+                nested.should.into_iter().for_each(|c| self.add_must_not(c));
+            }
+            // not (not a) = a
+            NormalizedCondition::Filter(mut nested)
+                if nested.has_only_must()
+                    && nested.must.is_empty()
+                    && nested.must_not.len() == 1 =>
+            {
+                self.add_must(nested.must_not.pop().expect("length is checked"));
+            }
+            condition => push_unique(&mut self.must_not, condition),
+        }
+    }
+
+    /// Convert into a condition, unwrapping the filter if it consists of a single condition
+    fn into_condition(mut self) -> NormalizedCondition<'a> {
+        if self.has_only_must() && self.must.len() == 1 && self.must_not.is_empty() {
+            self.must.pop().expect("length is checked")
+        } else {
+            NormalizedCondition::Filter(self)
+        }
+    }
+}
+
+fn push_unique<'a>(
+    conditions: &mut Vec<NormalizedCondition<'a>>,
+    condition: NormalizedCondition<'a>,
+) {
+    if !conditions.contains(&condition) {
+        conditions.push(condition);
+    }
+}
+
+fn normalize_condition(condition: &Condition) -> NormalizedCondition {
+    match condition {
+        Condition::Filter(filter) => normalize_filter(filter).into_condition(),
+        _ => NormalizedCondition::Condition(condition),
+    }
+}
+
+/// Rewrite the filter into an equivalent one without redundant nesting and duplicated conditions
+pub fn normalize_filter(filter: &Filter) -> NormalizedFilter {
+    let mut normalized = NormalizedFilter::default();
+
+    for condition in filter.must.iter().flatten() {
+        normalized.add_must(normalize_condition(condition));
+    }
+    for condition in filter.must_not.iter().flatten() {
+        normalized.add_must_not(normalize_condition(condition));
+    }
+    for condition in filter.should.iter().flatten() {
+        normalized.add_should(normalize_condition(condition));
+    }
+    // Conditions are counted, so duplicates are preserved
+    normalized.min_should = filter
+        .min_should
+        .as_ref()
+        .filter(|min_should| !min_should.conditions.is_empty())
+        .map(|min_should| NormalizedMinShould {
+            conditions: min_should
+                .conditions
+                .iter()
+                .map(normalize_condition)
+                .collect(),
+            min_count: min_should.min_count,
+        });
+
+    // Single alternative must match
+    if normalized.should.len() == 1 {
+        let condition = normalized.should.pop().expect("length is checked");
+        normalized.add_must(condition);
+    }
+
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{FieldCondition, Match, ValueVariants};
+
+    fn condition(value: i64) -> Condition {
+        Condition::Field(FieldCondition::new_match(
+            "number",
+            Match::new_value(ValueVariants::Integer(value)),
+        ))
+    }
+
+    fn filter(should: Vec<Condition>, must: Vec<Condition>, must_not: Vec<Condition>) -> Filter {
+        Filter {
+            should: Some(should),
+            min_should: None,
+            must: Some(must),
+            must_not: Some(must_not),
+        }
+    }
+
+    #[test]
+    fn test_flatten_nested_filters() {
+        let (a, b, c, d) = (condition(1), condition(2), condition(3), condition(4));
+
+        // a and (b and (c and not d)) and (a or b or (c or d))
+        let nested = filter(
+            vec![],
+            vec![
+                a.clone(),
+                Condition::Filter(filter(
+                    vec![],
+                    vec![
+                        b.clone(),
+                        Condition::Filter(filter(vec![], vec![c.clone()], vec![d.clone()])),
+                    ],
+                    vec![],
+                )),
+                Condition::Filter(filter(
+                    vec![
+                        a.clone(),
+                        b.clone(),
+                        Condition::Filter(Filter::new_should(c.clone())),
+                        Condition::Filter(filter(vec![c.clone(), d.clone()], vec![], vec![])),
+                    ],
+                    vec![],
+                    vec![],
+                )),
+            ],
+            vec![],
+        );
+
+        let mut normalized = normalize_filter(&nested);
+        match normalized.must.pop() {
+            Some(NormalizedCondition::Filter(should)) => {
+                assert!(should.has_only_should());
+                assert_eq!(
+                    should.should,
+                    [&a, &b, &c, &d].map(NormalizedCondition::Condition),
+                );
+            }
+            condition => panic!("unexpected condition {condition:?}"),
+        }
+        assert_eq!(
+            normalized.must,
+            [&a, &b, &c].map(NormalizedCondition::Condition)
+        );
+        assert_eq!(normalized.must_not, [NormalizedCondition::Condition(&d)]);
+        assert!(normalized.should.is_empty());
+    }
+
+    #[test]
+    fn test_push_negations_inward() {
+        let (a, b, c) = (condition(1), condition(2), condition(3));
+
+        // not (a or b) and not (not c) and not a
+        let nested = filter(
+            vec![],
+            vec![],
+            vec![
+                Condition::Filter(filter(vec![a.clone(), b.clone()], vec![], vec![])),
+                Condition::Filter(Filter::new_must_not(c.clone())),
+                a.clone(),
+            ],
+        );
+
+        let normalized = normalize_filter(&nested);
+        assert_eq!(normalized.must, [NormalizedCondition::Condition(&c)]);
+        assert_eq!(
+            normalized.must_not,
+            [&a, &b].map(NormalizedCondition::Condition)
+        );
+        assert!(normalized.should.is_empty());
+
+        // not (a and b) can't be expressed without nesting
+        let nested = Filter::new_must_not(Condition::Filter(filter(
+            vec![],
+            vec![a.clone(), b.clone()],
+            vec![],
+        )));
+        let normalized = normalize_filter(&nested);
+        assert!(matches!(
+            &normalized.must_not[..],
+            [NormalizedCondition::Filter(_)]
+        ));
+    }
+}
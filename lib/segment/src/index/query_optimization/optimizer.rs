@@ -2,14 +2,20 @@ use std::cmp::Reverse;
 
 use itertools::Itertools;
 
-use crate::common::utils::IndexesMap;
+use crate::common::utils::{IndexesMap, VectorStoragesMap};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::CardinalityEstimation;
 use crate::index::query_estimator::{
-    combine_must_estimations, combine_should_estimations, invert_estimation,
+    combine_min_should_estimations, combine_must_estimations, combine_should_estimations,
+    invert_estimation,
 };
 use crate::index::query_optimization::condition_converter::condition_converter;
-use crate::index::query_optimization::optimized_filter::{OptimizedCondition, OptimizedFilter};
+use crate::index::query_optimization::normalizer::{
+    normalize_filter, NormalizedCondition, NormalizedFilter,
+};
+use crate::index::query_optimization::optimized_filter::{
+    OptimizedCondition, OptimizedFilter, OptimizedMinShould,
+};
 use crate::index::query_optimization::payload_provider::PayloadProvider;
 use crate::types::{Condition, Filter};
 
@@ -17,16 +23,16 @@ use crate::types::{Condition, Filter};
 ///
 /// Optimizations:
 ///
+/// * Flatten nested filters and remove duplicated conditions, see [`normalize_filter`]
 /// * Convert each condition into a checker function
 /// * Use column index, avoid reading Payload, if possible
 /// * Re-order operations using estimated cardinalities
 ///
-/// ToDo: Add optimizations between clauses
-///
 /// # Arguments
 ///
 /// * `filter` - original filter
 /// * `id_tracker` - used for converting collection-level ids into segment-level offsets of HasId condition
+/// * `vector_storages` - used for checking HasVector condition
 /// * `estimator` - function to estimate cardinality of individual conditions
 /// * `total` - total number of points in segment (used for cardinality estimation)
 ///
@@ -36,6 +42,30 @@ use crate::types::{Condition, Filter};
 pub fn optimize_filter<'a, F>(
     filter: &'a Filter,
     id_tracker: &IdTrackerSS,
+    vector_storages: &VectorStoragesMap,
+    field_indexes: &'a IndexesMap,
+    payload_provider: PayloadProvider,
+    estimator: &F,
+    total: usize,
+) -> (OptimizedFilter<'a>, CardinalityEstimation)
+where
+    F: Fn(&Condition) -> CardinalityEstimation,
+{
+    optimize_normalized_filter(
+        normalize_filter(filter),
+        id_tracker,
+        vector_storages,
+        field_indexes,
+        payload_provider,
+        estimator,
+        total,
+    )
+}
+
+fn optimize_normalized_filter<'a, F>(
+    filter: NormalizedFilter<'a>,
+    id_tracker: &IdTrackerSS,
+    vector_storages: &VectorStoragesMap,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
@@ -47,11 +77,13 @@ where
     let mut filter_estimations: Vec<CardinalityEstimation> = vec![];
 
     let optimized_filter = OptimizedFilter {
-        should: filter.should.as_ref().and_then(|conditions| {
+        should: {
+            let conditions = filter.should;
             if !conditions.is_empty() {
                 let (optimized_conditions, estimation) = optimize_should(
                     conditions,
                     id_tracker,
+                    vector_storages,
                     field_indexes,
                     payload_provider.clone(),
                     estimator,
@@ -62,12 +94,31 @@ where
             } else {
                 None
             }
+        },
+        min_should: filter.min_should.map(|min_should| {
+            let (optimized_conditions, estimation) = optimize_min_should(
+                min_should.conditions,
+                min_should.min_count,
+                id_tracker,
+                vector_storages,
+                field_indexes,
+                payload_provider.clone(),
+                estimator,
+                total,
+            );
+            filter_estimations.push(estimation);
+            OptimizedMinShould {
+                conditions: optimized_conditions,
+                min_count: min_should.min_count,
+            }
         }),
-        must: filter.must.as_ref().and_then(|conditions| {
+        must: {
+            let conditions = filter.must;
             if !conditions.is_empty() {
                 let (optimized_conditions, estimation) = optimize_must(
                     conditions,
                     id_tracker,
+                    vector_storages,
                     field_indexes,
                     payload_provider.clone(),
                     estimator,
@@ -78,12 +129,14 @@ where
             } else {
                 None
             }
-        }),
-        must_not: filter.must_not.as_ref().and_then(|conditions| {
+        },
+        must_not: {
+            let conditions = filter.must_not;
             if !conditions.is_empty() {
                 let (optimized_conditions, estimation) = optimize_must_not(
                     conditions,
                     id_tracker,
+                    vector_storages,
                     field_indexes,
                     payload_provider.clone(),
                     estimator,
@@ -94,7 +147,7 @@ where
             } else {
                 None
             }
-        }),
+        },
     };
 
     (
@@ -104,8 +157,9 @@ where
 }
 
 fn convert_conditions<'a, F>(
-    conditions: &'a [Condition],
+    conditions: Vec<NormalizedCondition<'a>>,
     id_tracker: &IdTrackerSS,
+    vector_storages: &VectorStoragesMap,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
@@ -115,12 +169,13 @@ where
     F: Fn(&Condition) -> CardinalityEstimation,
 {
     conditions
-        .iter()
+        .into_iter()
         .map(|condition| match condition {
-            Condition::Filter(filter) => {
-                let (optimized_filter, estimation) = optimize_filter(
+            NormalizedCondition::Filter(filter) => {
+                let (optimized_filter, estimation) = optimize_normalized_filter(
                     filter,
                     id_tracker,
+                    vector_storages,
                     field_indexes,
                     payload_provider.clone(),
                     estimator,
@@ -128,13 +183,14 @@ where
                 );
                 (OptimizedCondition::Filter(optimized_filter), estimation)
             }
-            _ => {
+            NormalizedCondition::Condition(condition) => {
                 let estimation = estimator(condition);
                 let condition_checker = condition_converter(
                     condition,
                     field_indexes,
                     payload_provider.clone(),
                     id_tracker,
+                    vector_storages,
                 );
                 (OptimizedCondition::Checker(condition_checker), estimation)
             }
@@ -143,8 +199,9 @@ where
 }
 
 fn optimize_should<'a, F>(
-    conditions: &'a [Condition],
+    conditions: Vec<NormalizedCondition<'a>>,
     id_tracker: &IdTrackerSS,
+    vector_storages: &VectorStoragesMap,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
@@ -156,6 +213,7 @@ where
     let mut converted = convert_conditions(
         conditions,
         id_tracker,
+        vector_storages,
         field_indexes,
         payload_provider,
         estimator,
@@ -168,9 +226,42 @@ where
     (conditions, combine_should_estimations(&estimations, total))
 }
 
+fn optimize_min_should<'a, F>(
+    conditions: Vec<NormalizedCondition<'a>>,
+    min_count: usize,
+    id_tracker: &IdTrackerSS,
+    vector_storages: &VectorStoragesMap,
+    field_indexes: &'a IndexesMap,
+    payload_provider: PayloadProvider,
+    estimator: &F,
+    total: usize,
+) -> (Vec<OptimizedCondition<'a>>, CardinalityEstimation)
+where
+    F: Fn(&Condition) -> CardinalityEstimation,
+{
+    let mut converted = convert_conditions(
+        conditions,
+        id_tracker,
+        vector_storages,
+        field_indexes,
+        payload_provider,
+        estimator,
+        total,
+    );
+    // More probable conditions first, so that `min_count` matches are found sooner
+    converted.sort_by_key(|(_, estimation)| Reverse(estimation.exp));
+    let (conditions, estimations): (Vec<_>, Vec<_>) = converted.into_iter().unzip();
+
+    (
+        conditions,
+        combine_min_should_estimations(&estimations, min_count, total),
+    )
+}
+
 fn optimize_must<'a, F>(
-    conditions: &'a [Condition],
+    conditions: Vec<NormalizedCondition<'a>>,
     id_tracker: &IdTrackerSS,
+    vector_storages: &VectorStoragesMap,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
@@ -182,6 +273,7 @@ where
     let mut converted = convert_conditions(
         conditions,
         id_tracker,
+        vector_storages,
         field_indexes,
         payload_provider,
         estimator,
@@ -195,8 +287,9 @@ where
 }
 
 fn optimize_must_not<'a, F>(
-    conditions: &'a [Condition],
+    conditions: Vec<NormalizedCondition<'a>>,
     id_tracker: &IdTrackerSS,
+    vector_storages: &VectorStoragesMap,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
@@ -208,6 +301,7 @@ where
     let mut converted = convert_conditions(
         conditions,
         id_tracker,
+        vector_storages,
         field_indexes,
         payload_provider,
         estimator,
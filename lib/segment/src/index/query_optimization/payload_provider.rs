@@ -50,6 +50,10 @@ impl PayloadProvider {
                 .read_payload(point_id)
                 .unwrap_or_else(|err| panic!("Payload storage is corrupted: {err}"))
                 .map(|x| x.into()),
+            PayloadStorageEnum::MmapPayloadStorage(s) => s
+                .read_payload(point_id)
+                .unwrap_or_else(|err| panic!("Payload storage is corrupted: {err}"))
+                .map(|x| x.into()),
         };
 
         let payload = if let Some(payload_ptr) = payload_ptr_opt {
@@ -0,0 +1,204 @@
+//! Chooses how the search is executed in a segment
+//!
+//! Search could be done in one of the following ways:
+//! - traverse the HNSW graph, checking the filter for every visited point
+//! - retrieve points, matching the filter, with payload indexes and score all of them
+//! - check the filter for every point of the segment and score the matching ones
+//!
+//! Costs of the strategies are measured in the number of scored vectors. The number of points,
+//! matching the filter, is estimated by payload indexes, e.g. numeric indexes use their histograms.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::index::field_index::CardinalityEstimation;
+
+/// Number of filter checks, which cost about as much as scoring a single vector
+const FILTER_CHECKS_PER_SCORE: usize = 10;
+
+/// Way of executing the search in a segment
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchStrategy {
+    /// Traverse the HNSW graph, checking the filter for visited points
+    Graph,
+    /// Score points, retrieved from payload indexes
+    PayloadIndex,
+    /// Check the filter for every point and score the matching ones
+    FullScan,
+}
+
+/// Strategy of the search in a segment, together with the estimations it is chosen by
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchPlan {
+    pub strategy: SearchStrategy,
+    /// Expected number of points, which match the filter
+    pub estimated_cardinality: usize,
+    /// Estimated cost of the graph search, if the graph could be used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graph_cost: Option<usize>,
+    /// Estimated cost of scoring points, retrieved from payload indexes, if the filter could use them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_index_cost: Option<usize>,
+    /// Estimated cost of checking the filter for every point
+    pub full_scan_cost: usize,
+}
+
+pub struct QueryPlanner {
+    /// Number of scored vectors, which is expected to be enough for the graph search.
+    /// If less points match the filter, it is cheaper to score all of them.
+    full_scan_threshold: usize,
+}
+
+impl QueryPlanner {
+    pub fn new(full_scan_threshold: usize) -> Self {
+        Self {
+            full_scan_threshold,
+        }
+    }
+
+    /// Choose the strategy of the search without filter
+    pub fn plan_unfiltered(&self, available_vectors: usize, use_graph: bool) -> SearchPlan {
+        let graph_cost = use_graph.then_some(self.full_scan_threshold);
+        let strategy = match graph_cost {
+            Some(cost) if available_vectors >= cost => SearchStrategy::Graph,
+            _ => SearchStrategy::FullScan,
+        };
+        SearchPlan {
+            strategy,
+            estimated_cardinality: available_vectors,
+            graph_cost,
+            payload_index_cost: None,
+            full_scan_cost: available_vectors,
+        }
+    }
+
+    /// Choose the strategy of the filtered search
+    ///
+    /// * `cardinality` - estimation of the number of points, matching the filter,
+    ///   adjusted to the number of available vectors
+    /// * `use_graph` - whether the graph could be used, e.g. it is not if exact search is requested
+    /// * `sample_exceeds` - checks a sample of points to tell if the number of matching points
+    ///   exceeds the given one, called only if the estimation is not precise enough
+    pub fn plan(
+        &self,
+        cardinality: &CardinalityEstimation,
+        available_vectors: usize,
+        use_graph: bool,
+        sample_exceeds: impl FnOnce(usize) -> bool,
+    ) -> SearchPlan {
+        let full_scan_cost = available_vectors / FILTER_CHECKS_PER_SCORE + cardinality.exp;
+        // Payload indexes select a superset of matching points, the filter is checked for each
+        let payload_index_cost = (!cardinality.primary_clauses.is_empty())
+            .then(|| cardinality.max / FILTER_CHECKS_PER_SCORE + cardinality.exp);
+        let graph_cost = use_graph.then_some(self.full_scan_threshold);
+
+        let use_graph = match graph_cost {
+            None => false,
+            // Scoring all matching points is cheaper even in the worst case
+            Some(cost) if cardinality.max < cost => false,
+            // Graph search is cheaper even in the best case
+            Some(cost) if cardinality.min > cost => true,
+            Some(cost) => sample_exceeds(cost),
+        };
+
+        let strategy = if use_graph {
+            SearchStrategy::Graph
+        } else {
+            match payload_index_cost {
+                Some(cost) if cost <= full_scan_cost => SearchStrategy::PayloadIndex,
+                _ => SearchStrategy::FullScan,
+            }
+        };
+
+        SearchPlan {
+            strategy,
+            estimated_cardinality: cardinality.exp,
+            graph_cost,
+            payload_index_cost,
+            full_scan_cost,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::field_index::PrimaryCondition;
+
+    fn estimation(min: usize, exp: usize, max: usize, indexed: bool) -> CardinalityEstimation {
+        CardinalityEstimation {
+            primary_clauses: if indexed {
+                vec![PrimaryCondition::Ids(Default::default())]
+            } else {
+                vec![]
+            },
+            min,
+            exp,
+            max,
+        }
+    }
+
+    #[test]
+    fn test_search_plan() {
+        let planner = QueryPlanner::new(1_000);
+        let no_sample = |_: usize| -> bool { panic!("estimation is precise enough") };
+
+        // Few matching points are retrieved with payload indexes, if the filter could use them
+        let plan = planner.plan(&estimation(10, 50, 100, true), 100_000, true, no_sample);
+        assert_eq!(plan.strategy, SearchStrategy::PayloadIndex);
+        assert_eq!(plan.estimated_cardinality, 50);
+        assert_eq!(plan.graph_cost, Some(1_000));
+        assert!(plan.payload_index_cost < Some(plan.full_scan_cost));
+
+        let plan = planner.plan(&estimation(10, 50, 100, false), 100_000, true, no_sample);
+        assert_eq!(plan.strategy, SearchStrategy::FullScan);
+        assert_eq!(plan.payload_index_cost, None);
+
+        // Many matching points are searched with the graph
+        let plan = planner.plan(
+            &estimation(5_000, 10_000, 20_000, true),
+            100_000,
+            true,
+            no_sample,
+        );
+        assert_eq!(plan.strategy, SearchStrategy::Graph);
+
+        // Graph is not used, if it can't be
+        let plan = planner.plan(
+            &estimation(5_000, 10_000, 20_000, true),
+            100_000,
+            false,
+            no_sample,
+        );
+        assert_eq!(plan.strategy, SearchStrategy::PayloadIndex);
+        assert_eq!(plan.graph_cost, None);
+
+        // Imprecise estimation is refined with a sample
+        for sample_result in [false, true] {
+            let plan = planner.plan(&estimation(100, 1_000, 10_000, true), 100_000, true, |n| {
+                assert_eq!(n, 1_000);
+                sample_result
+            });
+            assert_eq!(plan.strategy == SearchStrategy::Graph, sample_result);
+        }
+    }
+
+    #[test]
+    fn test_unfiltered_search_plan() {
+        let planner = QueryPlanner::new(1_000);
+        assert_eq!(
+            planner.plan_unfiltered(100, true).strategy,
+            SearchStrategy::FullScan
+        );
+        assert_eq!(
+            planner.plan_unfiltered(10_000, true).strategy,
+            SearchStrategy::Graph
+        );
+        assert_eq!(
+            planner.plan_unfiltered(10_000, false).strategy,
+            SearchStrategy::FullScan
+        );
+    }
+}
@@ -3,9 +3,11 @@ use std::path::{Path, PathBuf};
 use io::file_operations::{atomic_save_json, read_json};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use sparse::common::types::WeightType;
 
 use crate::common::anonymize::Anonymize;
 use crate::common::operation_error::OperationResult;
+use crate::types::VectorStorageDatatype;
 
 pub const SPARSE_INDEX_CONFIG_FILE: &str = "sparse_index_config.json";
 
@@ -31,6 +33,9 @@ pub struct SparseIndexConfig {
     pub full_scan_threshold: Option<usize>,
     /// Type of sparse index
     pub index_type: SparseIndexType,
+    /// Type of weights in the index stored on disk. If none - `float32` is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datatype: Option<VectorStorageDatatype>,
 }
 
 impl Anonymize for SparseIndexConfig {
@@ -38,6 +43,7 @@ impl Anonymize for SparseIndexConfig {
         SparseIndexConfig {
             full_scan_threshold: self.full_scan_threshold,
             index_type: self.index_type,
+            datatype: self.datatype,
         }
     }
 }
@@ -47,6 +53,16 @@ impl SparseIndexConfig {
         SparseIndexConfig {
             full_scan_threshold,
             index_type,
+            datatype: None,
+        }
+    }
+
+    /// Type, in which posting list weights are stored on disk
+    pub fn weight_type(&self) -> WeightType {
+        match self.datatype.unwrap_or_default() {
+            VectorStorageDatatype::Float32 => WeightType::Float32,
+            VectorStorageDatatype::Float16 => WeightType::Float16,
+            VectorStorageDatatype::Uint8 => WeightType::Uint8,
         }
     }
 
@@ -8,6 +8,7 @@ use atomic_refcell::AtomicRefCell;
 use common::types::{PointOffsetType, ScoredPointOffset};
 use itertools::Itertools;
 use sparse::common::sparse_vector::SparseVector;
+use sparse::common::types::WeightType;
 use sparse::index::inverted_index::inverted_index_ram::InvertedIndexRam;
 use sparse::index::inverted_index::InvertedIndex;
 use sparse::index::search_context::SearchContext;
@@ -62,6 +63,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
                 id_tracker.clone(),
                 vector_storage.clone(),
                 path,
+                config.weight_type(),
                 &AtomicBool::new(false),
             )?;
             (config, inverted_index, indices_tracker)
@@ -74,7 +76,11 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
             (loaded_config, inverted_index, indices_tracker)
         } else {
             // Inverted index and config are not presented - initialize empty inverted index
-            let inverted_index = TInvertedIndex::from_ram_index(InvertedIndexRam::empty(), path)?;
+            let inverted_index = TInvertedIndex::from_ram_index_with_weight_type(
+                InvertedIndexRam::empty(),
+                path,
+                config.weight_type(),
+            )?;
             let indices_tracker = Default::default();
             (config, inverted_index, indices_tracker)
         };
@@ -103,6 +109,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
         vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
         path: &Path,
+        weight_type: WeightType,
         stopped: &AtomicBool,
     ) -> OperationResult<(TInvertedIndex, IndicesTracker)> {
         let borrowed_vector_storage = vector_storage.borrow();
@@ -140,7 +147,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         // so we set the indexed vector count to the number of points we have seen
         ram_index.vector_count = index_point_count;
         Ok((
-            TInvertedIndex::from_ram_index(ram_index, path)?,
+            TInvertedIndex::from_ram_index_with_weight_type(ram_index, path, weight_type)?,
             indices_tracker,
         ))
     }
@@ -367,6 +374,7 @@ impl<TInvertedIndex: InvertedIndex> VectorIndex for SparseVectorIndex<TInvertedI
             self.id_tracker.clone(),
             self.vector_storage.clone(),
             &self.path,
+            self.config.weight_type(),
             stopped,
         )?;
 
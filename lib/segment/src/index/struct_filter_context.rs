@@ -1,6 +1,6 @@
 use common::types::PointOffsetType;
 
-use crate::common::utils::IndexesMap;
+use crate::common::utils::{IndexesMap, VectorStoragesMap};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::CardinalityEstimation;
 use crate::index::query_optimization::optimized_filter::{check_optimized_filter, OptimizedFilter};
@@ -17,6 +17,7 @@ impl<'a> StructFilterContext<'a> {
     pub fn new<F>(
         filter: &'a Filter,
         id_tracker: &IdTrackerSS,
+        vector_storages: &VectorStoragesMap,
         payload_provider: PayloadProvider,
         field_indexes: &'a IndexesMap,
         estimator: &F,
@@ -28,6 +29,7 @@ impl<'a> StructFilterContext<'a> {
         let (optimized_filter, _) = optimize_filter(
             filter,
             id_tracker,
+            vector_storages,
             field_indexes,
             payload_provider,
             estimator,
@@ -1,12 +1,17 @@
+use std::cmp::Reverse;
 use std::collections::{HashMap, HashSet};
 use std::fs::create_dir_all;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
+use bitvec::vec::BitVec;
+use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
 use common::types::PointOffsetType;
 use log::debug;
+use ordered_float::OrderedFloat;
 use parking_lot::RwLock;
 use rocksdb::DB;
 use schemars::_serde_json::Value;
@@ -14,13 +19,17 @@ use schemars::_serde_json::Value;
 use crate::common::arc_atomic_ref_cell_iterator::ArcAtomicRefCellIterator;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
-use crate::common::utils::{IndexesMap, JsonPathPayload, MultiValue};
+use crate::common::utils::{IndexesMap, JsonPathPayload, MultiValue, VectorStoragesMap};
 use crate::common::Flusher;
+use crate::data_types::facets::FacetValue;
+use crate::data_types::order_by::{Direction, OrderBy, OrderValue};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::index_selector::index_selector;
+use crate::index::field_index::null_index::NullIndex;
 use crate::index::field_index::{
     CardinalityEstimation, FieldIndex, PayloadBlockCondition, PrimaryCondition,
 };
+use crate::index::filter_cache::{CachedFilterContext, FilterCache};
 use crate::index::payload_config::PayloadConfig;
 use crate::index::query_estimator::estimate_filter;
 use crate::index::query_optimization::payload_provider::PayloadProvider;
@@ -31,19 +40,25 @@ use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::{FilterContext, PayloadStorage};
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    infer_collection_value_type, infer_value_type, Condition, FieldCondition, Filter,
+    infer_collection_value_type, infer_value_type, Condition, FieldCondition, Filter, GeoPoint,
     IsEmptyCondition, IsNullCondition, Payload, PayloadContainer, PayloadField, PayloadFieldSchema,
     PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType,
 };
+use crate::vector_storage::VectorStorage;
 
 pub const PAYLOAD_FIELD_INDEX_PATH: &str = "fields";
 
+/// Number of points selected by the first pass of ordering by distance
+const DISTANCE_STREAM_BATCH: usize = 128;
+
 /// `PayloadIndex` implementation, which actually uses index structures for providing faster search
 pub struct StructPayloadIndex {
     /// Payload storage
     payload: Arc<AtomicRefCell<PayloadStorageEnum>>,
     /// Used for `has_id` condition and estimating cardinality
     id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+    /// Used for `has_vector` condition and estimating cardinality
+    vector_storages: VectorStoragesMap,
     /// Indexes, associated with fields
     pub field_indexes: IndexesMap,
     config: PayloadConfig,
@@ -51,6 +66,8 @@ pub struct StructPayloadIndex {
     path: PathBuf,
     /// Used to select unique point ids
     visited_pool: VisitedPool,
+    /// Points, matching frequently used filters
+    filter_cache: FilterCache,
     db: Arc<RwLock<DB>>,
 }
 
@@ -115,7 +132,13 @@ impl StructPayloadIndex {
         payload_schema: PayloadFieldSchema,
         is_appendable: bool,
     ) -> OperationResult<Vec<FieldIndex>> {
-        let mut indexes = index_selector(field, &payload_schema, self.db.clone(), is_appendable);
+        let mut indexes = index_selector(
+            field,
+            &payload_schema,
+            self.db.clone(),
+            is_appendable,
+            &self.path,
+        );
 
         let mut is_loaded = true;
         for ref mut index in indexes.iter_mut() {
@@ -136,6 +159,7 @@ impl StructPayloadIndex {
     pub fn open(
         payload: Arc<AtomicRefCell<PayloadStorageEnum>>,
         id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+        vector_storages: VectorStoragesMap,
         path: &Path,
         is_appendable: bool,
     ) -> OperationResult<Self> {
@@ -153,10 +177,12 @@ impl StructPayloadIndex {
         let mut index = StructPayloadIndex {
             payload,
             id_tracker,
+            vector_storages,
             field_indexes: Default::default(),
             config,
             path: path.to_owned(),
             visited_pool: Default::default(),
+            filter_cache: Default::default(),
             db,
         };
 
@@ -176,7 +202,8 @@ impl StructPayloadIndex {
         payload_schema: PayloadFieldSchema,
     ) -> OperationResult<Vec<FieldIndex>> {
         let payload_storage = self.payload.borrow();
-        let mut field_indexes = index_selector(field, &payload_schema, self.db.clone(), true);
+        let mut field_indexes =
+            index_selector(field, &payload_schema, self.db.clone(), true, &self.path);
         for index in &field_indexes {
             index.recreate()?;
         }
@@ -208,6 +235,188 @@ impl StructPayloadIndex {
         self.id_tracker.borrow().available_point_count()
     }
 
+    /// Count points of each value of the field, which must be indexed with a keyword or integer index
+    pub fn facet(
+        &self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HashMap<FacetValue, usize>> {
+        let facet_index = self
+            .field_indexes
+            .get(key)
+            .into_iter()
+            .flatten()
+            .find(|index| {
+                matches!(
+                    index,
+                    FieldIndex::KeywordIndex(_) | FieldIndex::IntMapIndex(_)
+                )
+            })
+            .ok_or_else(|| OperationError::ValidationError {
+                description: format!("Facet requires keyword or integer index on field `{key}`"),
+            })?;
+
+        let points = filter.map(|filter| self.query_points(filter));
+        let points = points.as_deref();
+
+        let counts = match facet_index {
+            FieldIndex::KeywordIndex(index) => index
+                .count_per_value(points, is_stopped)?
+                .into_iter()
+                .map(|(value, count)| (FacetValue::Keyword(value.to_string()), count))
+                .collect(),
+            FieldIndex::IntMapIndex(index) => index
+                .count_per_value(points, is_stopped)?
+                .into_iter()
+                .map(|(value, count)| (FacetValue::Int(*value), count))
+                .collect(),
+            _ => unreachable!("index is selected by type"),
+        };
+        Ok(counts)
+    }
+
+    /// Iterate over values of the field with their points in the order of `order_by`.
+    /// The field must be indexed with an integer, float or datetime index,
+    /// or with a geo index for ordering by distance.
+    ///
+    /// Points with multiple values are yielded once per value.
+    pub fn stream_ordered(
+        &self,
+        order_by: &OrderBy,
+    ) -> OperationResult<Box<dyn Iterator<Item = (OrderValue, PointOffsetType)> + '_>> {
+        if let Some(origin) = &order_by.distance_from {
+            return self.stream_by_distance(order_by, origin);
+        }
+
+        let key = &order_by.key;
+        let order_index = self
+            .field_indexes
+            .get(key)
+            .into_iter()
+            .flatten()
+            .find(|index| {
+                matches!(
+                    index,
+                    FieldIndex::IntIndex(_)
+                        | FieldIndex::FloatIndex(_)
+                        | FieldIndex::DatetimeIndex(_)
+                )
+            })
+            .ok_or_else(|| OperationError::ValidationError {
+                description: format!(
+                    "Ordering requires integer, float or datetime index on field `{key}`"
+                ),
+            })?;
+
+        let range = order_by.value_range();
+        let stream: Box<dyn DoubleEndedIterator<Item = (OrderValue, PointOffsetType)> + '_> =
+            match order_index {
+                FieldIndex::IntIndex(index) => Box::new(
+                    index
+                        .stream_range(&range)
+                        .map(|(value, idx)| (value as OrderValue, idx)),
+                ),
+                FieldIndex::FloatIndex(index) => index.stream_range(&range),
+                FieldIndex::DatetimeIndex(index) => Box::new(
+                    index
+                        .stream_range(&range)
+                        .map(|(value, idx)| (value as OrderValue, idx)),
+                ),
+                _ => unreachable!("index is selected by type"),
+            };
+
+        match order_by.direction() {
+            Direction::Asc => Ok(stream),
+            Direction::Desc => Ok(Box::new(stream.rev())),
+        }
+    }
+
+    /// Iterate over distances from `origin` to geo points of the field, in the order of `order_by`
+    ///
+    /// Geo index has no order by distance. Instead of sorting distances to all indexed points,
+    /// each pass over the index selects the next batch of points with a bounded heap.
+    /// Batches are doubled with each pass, so reading the first pages is cheap.
+    fn stream_by_distance(
+        &self,
+        order_by: &OrderBy,
+        origin: &GeoPoint,
+    ) -> OperationResult<Box<dyn Iterator<Item = (OrderValue, PointOffsetType)> + '_>> {
+        let key = &order_by.key;
+        let geo_index = self
+            .field_indexes
+            .get(key)
+            .into_iter()
+            .flatten()
+            .find_map(|index| match index {
+                FieldIndex::GeoIndex(index) => Some(index),
+                _ => None,
+            })
+            .ok_or_else(|| OperationError::ValidationError {
+                description: format!("Ordering by distance requires geo index on field `{key}`"),
+            })?;
+
+        let origin = origin.clone();
+        let range = order_by.value_range();
+        let direction = order_by.direction();
+
+        // Queue keeps the largest keys, so keys are ordered in reverse of the stream.
+        // Ties are broken by the lower point offset.
+        type Key = (OrderedFloat<OrderValue>, Reverse<PointOffsetType>);
+        let to_key = move |distance: OrderValue, idx: PointOffsetType| {
+            let value = match direction {
+                Direction::Asc => -distance,
+                Direction::Desc => distance,
+            };
+            (OrderedFloat(value), Reverse(idx))
+        };
+        let from_key = move |(OrderedFloat(value), Reverse(idx)): Key| {
+            let distance = match direction {
+                Direction::Asc => -value,
+                Direction::Desc => value,
+            };
+            (distance, idx)
+        };
+
+        let mut batch_size = DISTANCE_STREAM_BATCH;
+        let mut last_key: Option<Key> = None;
+        let mut is_exhausted = false;
+        let mut batch = Vec::new().into_iter();
+
+        Ok(Box::new(std::iter::from_fn(move || {
+            if let Some(key) = batch.next() {
+                return Some(from_key(key));
+            }
+            if is_exhausted {
+                return None;
+            }
+
+            let mut queue = FixedLengthPriorityQueue::new(batch_size);
+            let id_tracker = self.id_tracker.borrow();
+            for idx in id_tracker.iter_ids() {
+                for point in geo_index.get_values(idx).into_iter().flatten() {
+                    let distance = origin.distance(point);
+                    if !range.check_range(distance) {
+                        continue;
+                    }
+                    // Skip points of the previous batches
+                    let key = to_key(distance, idx);
+                    if last_key.map_or(true, |last_key| key < last_key) {
+                        queue.push(key);
+                    }
+                }
+            }
+
+            is_exhausted = queue.len() < batch_size;
+            batch_size = batch_size.saturating_mul(2);
+
+            let keys = queue.into_vec();
+            last_key = keys.last().copied();
+            batch = keys.into_iter();
+            batch.next().map(from_key)
+        })))
+    }
+
     fn struct_filtered_context<'a>(&'a self, filter: &'a Filter) -> StructFilterContext<'a> {
         let estimator = |condition: &Condition| self.condition_cardinality(condition, None);
         let id_tracker = self.id_tracker.borrow();
@@ -215,6 +424,7 @@ impl StructPayloadIndex {
         StructFilterContext::new(
             filter,
             id_tracker.deref(),
+            &self.vector_storages,
             payload_provider,
             &self.field_indexes,
             &estimator,
@@ -222,6 +432,26 @@ impl StructPayloadIndex {
         )
     }
 
+    /// Points, matching the filter, if it is used often enough to be cached
+    fn cached_filter_points(&self, filter: &Filter) -> Option<Arc<BitVec>> {
+        self.filter_cache.get_or_evaluate(filter, || {
+            let id_tracker = self.id_tracker.borrow();
+            let struct_filtered_context = self.struct_filtered_context(filter);
+            let mut points = BitVec::repeat(false, id_tracker.total_point_count());
+            for point_id in id_tracker.iter_ids() {
+                if struct_filtered_context.check(point_id) {
+                    points.set(point_id as usize, true);
+                }
+            }
+            points
+        })
+    }
+
+    /// Drop cached points of filters, must be called before points of the segment are changed
+    pub fn clear_filter_cache(&self) {
+        self.filter_cache.clear();
+    }
+
     fn condition_cardinality(
         &self,
         condition: &Condition,
@@ -232,7 +462,20 @@ impl StructPayloadIndex {
             Condition::Nested(nested) => {
                 // propagate complete nested path in case of multiple nested layers
                 let full_path = JsonPathPayload::extend_or_new(nested_path, &nested.array_key());
-                self.estimate_nested_cardinality(nested.filter(), &full_path)
+                let estimation = self.estimate_nested_cardinality(nested.filter(), &full_path);
+                match &nested.nested.count {
+                    None => estimation,
+                    // Points without matching nested objects also match,
+                    // so they can't be retrieved from the indexes of the nested filter
+                    Some(count) if count.check_values_count(0) => {
+                        CardinalityEstimation::unknown(self.available_point_count())
+                    }
+                    // Only some of the points with matching nested objects match the count
+                    Some(_) => CardinalityEstimation {
+                        min: 0,
+                        ..estimation
+                    },
+                }
             }
             Condition::IsEmpty(IsEmptyCondition { is_empty: field }) => {
                 let available_points = self.available_point_count();
@@ -241,6 +484,14 @@ impl StructPayloadIndex {
 
                 let mut indexed_points = 0;
                 if let Some(field_indexes) = self.field_indexes.get(&full_path) {
+                    if let Some(null_index) = NullIndex::find(field_indexes) {
+                        return CardinalityEstimation::exact(
+                            available_points.saturating_sub(null_index.has_values_count()),
+                        )
+                        .with_primary_clause(PrimaryCondition::IsEmpty(IsEmptyCondition {
+                            is_empty: PayloadField { key: full_path },
+                        }));
+                    }
                     for index in field_indexes {
                         indexed_points = indexed_points.max(index.count_indexed_points())
                     }
@@ -270,6 +521,12 @@ impl StructPayloadIndex {
 
                 let mut indexed_points = 0;
                 if let Some(field_indexes) = self.field_indexes.get(&full_path) {
+                    if let Some(null_index) = NullIndex::find(field_indexes) {
+                        return CardinalityEstimation::exact(null_index.is_null_count())
+                            .with_primary_clause(PrimaryCondition::IsNull(IsNullCondition {
+                                is_null: PayloadField { key: full_path },
+                            }));
+                    }
                     for index in field_indexes {
                         indexed_points = indexed_points.max(index.count_indexed_points())
                     }
@@ -307,6 +564,25 @@ impl StructPayloadIndex {
                     max: num_ids,
                 }
             }
+            Condition::HasIdRange(has_id_range) => {
+                let range = has_id_range.has_id_range;
+                let num_ids = self.id_tracker.borrow().iter_range(range).count();
+                CardinalityEstimation {
+                    primary_clauses: vec![PrimaryCondition::IdRange(range)],
+                    min: num_ids,
+                    exp: num_ids,
+                    max: num_ids,
+                }
+            }
+            Condition::HasVector(has_vector) => {
+                let available_vectors = self
+                    .vector_storages
+                    .get(&has_vector.has_vector)
+                    .map_or(0, |vector_storage| {
+                        vector_storage.borrow().available_vector_count()
+                    });
+                CardinalityEstimation::exact(available_vectors)
+            }
             Condition::Field(field_condition) => self
                 .estimate_field_condition(field_condition, nested_path)
                 .unwrap_or_else(|| CardinalityEstimation::unknown(self.available_point_count())),
@@ -356,6 +632,8 @@ impl PayloadIndex for StructPayloadIndex {
         }
         self.build_and_save(field, payload_schema)?;
         self.save_config()?;
+        // Indexed conditions may match differently than the plain payload check
+        self.clear_filter_cache();
 
         Ok(())
     }
@@ -371,6 +649,7 @@ impl PayloadIndex for StructPayloadIndex {
         }
 
         self.save_config()?;
+        self.clear_filter_cache();
         Ok(())
     }
 
@@ -392,6 +671,13 @@ impl PayloadIndex for StructPayloadIndex {
     }
 
     fn query_points(&self, query: &Filter) -> Vec<PointOffsetType> {
+        if let Some(points) = self.cached_filter_points(query) {
+            return points
+                .iter_ones()
+                .map(|point_id| point_id as PointOffsetType)
+                .collect();
+        }
+
         // Assume query is already estimated to be small enough so we can iterate over all matched ids
 
         let query_cardinality = self.estimate_cardinality(query);
@@ -429,8 +715,22 @@ impl PayloadIndex for StructPayloadIndex {
                             )
                         }
                         PrimaryCondition::Ids(ids) => Box::new(ids.iter().copied()),
+                        PrimaryCondition::IdRange(range) => Box::new(
+                            points_iterator_ref
+                                .iter_range(*range)
+                                .map(|(_, point_id)| point_id),
+                        ),
                         PrimaryCondition::IsEmpty(_) => points_iterator_ref.iter_ids(), /* there are no fast index for IsEmpty */
-                        PrimaryCondition::IsNull(_) => points_iterator_ref.iter_ids(),  /* no fast index for IsNull too */
+                        PrimaryCondition::IsNull(IsNullCondition { is_null: field }) => {
+                            let null_index = self
+                                .field_indexes
+                                .get(&field.key)
+                                .and_then(|indexes| NullIndex::find(indexes));
+                            match null_index {
+                                None => points_iterator_ref.iter_ids(),
+                                Some(null_index) => Box::new(null_index.iter_is_null()),
+                            }
+                        }
                     }
                 })
                 .filter(|&id| !visited_list.check_and_update_visited(id))
@@ -454,7 +754,10 @@ impl PayloadIndex for StructPayloadIndex {
     }
 
     fn filter_context<'a>(&'a self, filter: &'a Filter) -> Box<dyn FilterContext + 'a> {
-        Box::new(self.struct_filtered_context(filter))
+        match self.cached_filter_points(filter) {
+            Some(points) => Box::new(CachedFilterContext::new(points)),
+            None => Box::new(self.struct_filtered_context(filter)),
+        }
     }
 
     fn payload_blocks(
@@ -550,6 +853,8 @@ impl PayloadIndex for StructPayloadIndex {
     }
 
     fn files(&self) -> Vec<PathBuf> {
-        vec![self.config_path()]
+        let mut files = vec![self.config_path()];
+        files.extend(self.payload.borrow().files());
+        files
     }
 }
@@ -0,0 +1,38 @@
+use std::path::{Path, PathBuf};
+
+use io::file_operations::{atomic_save_json, read_json};
+use serde::{Deserialize, Serialize};
+
+use crate::common::operation_error::OperationResult;
+
+pub const VAMANA_INDEX_CONFIG_FILE: &str = "vamana_config.json";
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct VamanaGraphConfig {
+    /// Maximal number of links per point
+    pub max_degree: usize,
+    /// Number of candidates to keep during the index building
+    pub build_list_size: usize,
+    /// Number of candidates to keep during the search
+    pub search_list_size: usize,
+    /// We prefer a full scan search upto (excluding) this number of vectors.
+    ///
+    /// Note: this is number of vectors, not KiloBytes.
+    pub full_scan_threshold: usize,
+    #[serde(default)]
+    pub indexed_vector_count: Option<usize>,
+}
+
+impl VamanaGraphConfig {
+    pub fn get_config_path(path: &Path) -> PathBuf {
+        path.join(VAMANA_INDEX_CONFIG_FILE)
+    }
+
+    pub fn load(path: &Path) -> OperationResult<Self> {
+        Ok(read_json(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> OperationResult<()> {
+        Ok(atomic_save_json(path, self)?)
+    }
+}
@@ -0,0 +1,481 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::mem::size_of;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
+use memmap2::Mmap;
+use memory::mmap_ops;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
+use crate::index::hnsw_index::search_context::SearchContext;
+use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
+use crate::vector_storage::RawScorer;
+
+pub const VAMANA_GRAPH_FILE: &str = "vamana_graph.bin";
+
+/*
+Layout of the graph file, all numbers are little endian:
+
+header:  num_points: u64 | max_degree: u64 | medoid: u64
+slots:   len: u32 | links: [u32; max_degree]    <- one slot per point
+
+Every point has a slot of the same size, so links of a point are read from disk
+with a single random access, without any offsets table.
+*/
+
+const HEADER_SIZE: usize = 3 * size_of::<u64>();
+
+/// Access to links of a Vamana graph, shared by the graph builder and the on-disk graph
+pub trait VamanaLinks {
+    fn num_points(&self) -> usize;
+
+    fn links(&self, point_id: PointOffsetType) -> &[PointOffsetType];
+
+    fn get_visited_list_from_pool(&self) -> VisitedListHandle;
+
+    /// Greedy search of the closest points to the query of `raw_scorer`, starting from `entry_point`
+    ///
+    /// Keeps up to `list_size` closest candidates, `on_scored` is called for every scored point.
+    /// Returns the closest candidates and the expanded points, in the order of expansion.
+    fn greedy_search(
+        &self,
+        entry_point: PointOffsetType,
+        list_size: usize,
+        raw_scorer: &dyn RawScorer,
+        mut on_scored: impl FnMut(ScoredPointOffset),
+    ) -> (
+        FixedLengthPriorityQueue<ScoredPointOffset>,
+        Vec<ScoredPointOffset>,
+    ) {
+        let mut visited_list = self.get_visited_list_from_pool();
+        visited_list.check_and_update_visited(entry_point);
+
+        let entry = ScoredPointOffset {
+            idx: entry_point,
+            score: raw_scorer.score_point(entry_point),
+        };
+        on_scored(entry);
+
+        let mut search_context = SearchContext::new(entry, list_size);
+        let mut expanded = Vec::new();
+        let mut points_ids: Vec<PointOffsetType> = Vec::new();
+        let mut scores: Vec<ScoredPointOffset> = Vec::new();
+
+        while let Some(candidate) = search_context.candidates.pop() {
+            if candidate.score < search_context.lower_bound() {
+                break;
+            }
+            expanded.push(candidate);
+
+            points_ids.clear();
+            points_ids.extend(
+                self.links(candidate.idx)
+                    .iter()
+                    .copied()
+                    .filter(|&link| (link as usize) < self.num_points())
+                    .filter(|&link| !visited_list.check_and_update_visited(link)),
+            );
+
+            scores.resize(points_ids.len(), ScoredPointOffset::default());
+            let count = raw_scorer.score_points(&points_ids, &mut scores);
+            for &score_point in &scores[..count] {
+                on_scored(score_point);
+                search_context.process_candidate(score_point);
+            }
+        }
+
+        (search_context.nearest, expanded)
+    }
+}
+
+/// Single-layer Vamana graph, served from disk
+pub struct VamanaGraph {
+    mmap: Arc<Mmap>,
+    num_points: usize,
+    max_degree: usize,
+    medoid: PointOffsetType,
+    visited_pool: VisitedPool,
+}
+
+impl VamanaGraph {
+    pub fn get_path(path: &Path) -> PathBuf {
+        path.join(VAMANA_GRAPH_FILE)
+    }
+
+    pub fn load(path: &Path) -> OperationResult<Self> {
+        let mmap = mmap_ops::open_read_mmap(path)?;
+
+        let read_u64 = |index: usize| -> OperationResult<u64> {
+            let offset = index * size_of::<u64>();
+            let bytes = mmap
+                .get(offset..offset + size_of::<u64>())
+                .ok_or_else(|| OperationError::service_error("Vamana graph header is corrupted"))?;
+            Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+        };
+        let num_points = read_u64(0)? as usize;
+        let max_degree = read_u64(1)? as usize;
+        let medoid = read_u64(2)? as PointOffsetType;
+
+        let corrupted = || {
+            OperationError::service_error(format!(
+                "Vamana graph file {} is corrupted",
+                path.display(),
+            ))
+        };
+
+        let expected_len = slot_size(max_degree)
+            .checked_mul(num_points)
+            .and_then(|slots_len| slots_len.checked_add(HEADER_SIZE));
+        if expected_len != Some(mmap.len()) || (num_points > 0 && medoid as usize >= num_points) {
+            return Err(corrupted());
+        }
+
+        // Slots are not validated here, to not read the whole graph on load.
+        // Corrupted links counts and links are skipped when the slot is read.
+        Ok(Self {
+            mmap: Arc::new(mmap),
+            num_points,
+            max_degree,
+            medoid,
+            visited_pool: VisitedPool::new(),
+        })
+    }
+
+    /// Links count of the point, followed by `max_degree` links
+    fn slot(&self, point_id: PointOffsetType) -> &[PointOffsetType] {
+        let offset = HEADER_SIZE + point_id as usize * slot_size(self.max_degree);
+        mmap_ops::transmute_from_u8_to_slice(
+            &self.mmap[offset..offset + slot_size(self.max_degree)],
+        )
+    }
+
+    /// Entry point of all searches, the point closest to the center of the data
+    pub fn medoid(&self) -> PointOffsetType {
+        self.medoid
+    }
+
+    pub fn prefault_mmap_pages(&self, path: &Path) -> mmap_ops::PrefaultMmapPages {
+        mmap_ops::PrefaultMmapPages::new(self.mmap.clone(), Some(path))
+    }
+}
+
+impl VamanaLinks for VamanaGraph {
+    fn num_points(&self) -> usize {
+        self.num_points
+    }
+
+    fn links(&self, point_id: PointOffsetType) -> &[PointOffsetType] {
+        let slot = self.slot(point_id);
+        let len = (slot[0] as usize).min(self.max_degree);
+        &slot[1..=len]
+    }
+
+    fn get_visited_list_from_pool(&self) -> VisitedListHandle {
+        self.visited_pool.get(self.num_points)
+    }
+}
+
+fn slot_size(max_degree: usize) -> usize {
+    (max_degree + 1) * size_of::<PointOffsetType>()
+}
+
+/// Builds Vamana graph in memory
+///
+/// The graph is built in two passes over all points: the first one with `alpha = 1`
+/// produces a sparse graph, the second one with `alpha > 1` adds long-range links,
+/// which shorten the search paths.
+pub struct VamanaGraphBuilder {
+    links: Vec<Vec<PointOffsetType>>,
+    max_degree: usize,
+    build_list_size: usize,
+    medoid: PointOffsetType,
+    visited_pool: VisitedPool,
+}
+
+impl VamanaGraphBuilder {
+    pub fn new(
+        num_points: usize,
+        max_degree: usize,
+        build_list_size: usize,
+        medoid: PointOffsetType,
+    ) -> Self {
+        Self {
+            links: vec![Vec::new(); num_points],
+            max_degree,
+            build_list_size,
+            medoid,
+            visited_pool: VisitedPool::new(),
+        }
+    }
+
+    /// Connect each of the given points with up to `max_degree` random other points
+    pub fn init_random_links<R: Rng + ?Sized>(&mut self, ids: &[PointOffsetType], rng: &mut R) {
+        for &point_id in ids {
+            let links: Vec<_> = ids
+                .choose_multiple(rng, self.max_degree + 1)
+                .copied()
+                .filter(|&link| link != point_id)
+                .take(self.max_degree)
+                .collect();
+            self.links[point_id as usize] = links;
+        }
+    }
+
+    /// Relink all given points in random order
+    ///
+    /// `scorer_for` creates a scorer with the vector of the given point as a query.
+    pub fn link_points<'a, R, F>(
+        &mut self,
+        ids: &[PointOffsetType],
+        alpha: ScoreType,
+        rng: &mut R,
+        scorer_for: F,
+        stopped: &AtomicBool,
+    ) -> OperationResult<()>
+    where
+        R: Rng + ?Sized,
+        F: Fn(PointOffsetType) -> OperationResult<Box<dyn RawScorer + 'a>>,
+    {
+        let mut ids = ids.to_vec();
+        ids.shuffle(rng);
+
+        for point_id in ids {
+            check_process_stopped(stopped)?;
+            let raw_scorer = scorer_for(point_id)?;
+
+            let (_, mut candidates) = self.greedy_search(
+                self.medoid,
+                self.build_list_size,
+                raw_scorer.as_ref(),
+                |_| {},
+            );
+            candidates.extend(self.links[point_id as usize].iter().map(|&link| {
+                ScoredPointOffset {
+                    idx: link,
+                    score: raw_scorer.score_point(link),
+                }
+            }));
+            let links = self.robust_prune(point_id, candidates, alpha, raw_scorer.as_ref());
+
+            for &link in &links {
+                let link_links = &self.links[link as usize];
+                if link_links.contains(&point_id) {
+                    continue;
+                }
+                if link_links.len() < self.max_degree {
+                    self.links[link as usize].push(point_id);
+                    continue;
+                }
+                let link_candidates = link_links
+                    .iter()
+                    .copied()
+                    .chain(std::iter::once(point_id))
+                    .map(|other| ScoredPointOffset {
+                        idx: other,
+                        score: raw_scorer.score_internal(link, other),
+                    })
+                    .collect();
+                self.links[link as usize] =
+                    self.robust_prune(link, link_candidates, alpha, raw_scorer.as_ref());
+            }
+            self.links[point_id as usize] = links;
+        }
+        Ok(())
+    }
+
+    /// Select up to `max_degree` links of the point among the candidates
+    ///
+    /// Candidate is skipped, if it is much closer to an already selected link than to the point.
+    /// Larger `alpha` requires a larger margin, so fewer candidates are skipped.
+    /// Scores of the candidates are relative to the point.
+    fn robust_prune(
+        &self,
+        point_id: PointOffsetType,
+        mut candidates: Vec<ScoredPointOffset>,
+        alpha: ScoreType,
+        raw_scorer: &dyn RawScorer,
+    ) -> Vec<PointOffsetType> {
+        candidates.retain(|candidate| candidate.idx != point_id);
+        candidates.sort_unstable_by_key(|candidate| candidate.idx);
+        candidates.dedup_by_key(|candidate| candidate.idx);
+        candidates.sort_unstable_by(|a, b| b.cmp(a));
+
+        let mut selected: Vec<PointOffsetType> = Vec::with_capacity(self.max_degree);
+        for candidate in candidates {
+            if selected.len() >= self.max_degree {
+                break;
+            }
+            let is_dominated = selected.iter().any(|&selected_id| {
+                let selected_score = raw_scorer.score_internal(selected_id, candidate.idx);
+                selected_score - candidate.score >= (alpha - 1.0) * selected_score.abs()
+            });
+            if !is_dominated {
+                selected.push(candidate.idx);
+            }
+        }
+        selected
+    }
+
+    pub fn save(&self, path: &Path) -> OperationResult<()> {
+        let temp_path = path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&temp_path)?);
+        writer.write_all(&(self.links.len() as u64).to_le_bytes())?;
+        writer.write_all(&(self.max_degree as u64).to_le_bytes())?;
+        writer.write_all(&(self.medoid as u64).to_le_bytes())?;
+
+        let mut slot = vec![0 as PointOffsetType; self.max_degree + 1];
+        for links in &self.links {
+            slot.fill(0);
+            slot[0] = links.len() as PointOffsetType;
+            slot[1..=links.len()].copy_from_slice(links);
+            writer.write_all(mmap_ops::transmute_to_u8_slice(&slot))?;
+        }
+
+        writer.flush()?;
+        writer
+            .into_inner()
+            .map_err(|err| err.into_error())?
+            .sync_all()?;
+        std::fs::rename(temp_path, path)?;
+        Ok(())
+    }
+}
+
+impl VamanaLinks for VamanaGraphBuilder {
+    fn num_points(&self) -> usize {
+        self.links.len()
+    }
+
+    fn links(&self, point_id: PointOffsetType) -> &[PointOffsetType] {
+        &self.links[point_id as usize]
+    }
+
+    fn get_visited_list_from_pool(&self) -> VisitedListHandle {
+        self.visited_pool.get(self.links.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::fixtures::index_fixtures::{random_vector, TestRawScorerProducer};
+    use crate::spaces::simple::CosineMetric;
+
+    #[test]
+    fn test_build_and_search_vamana_graph() {
+        let num_vectors = 1000;
+        let dim = 16;
+        let max_degree = 16;
+        let top = 10;
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let vector_holder = TestRawScorerProducer::<CosineMetric>::new(dim, num_vectors, &mut rng);
+        let ids: Vec<PointOffsetType> = (0..num_vectors as PointOffsetType).collect();
+
+        let mut builder = VamanaGraphBuilder::new(num_vectors, max_degree, 64, 0);
+        builder.init_random_links(&ids, &mut rng);
+        let scorer_for = |point_id: PointOffsetType| {
+            let query = vector_holder.vectors.get(point_id).to_vec();
+            vector_holder.get_raw_scorer(query)
+        };
+        let stopped = AtomicBool::new(false);
+        for alpha in [1.0, 1.2] {
+            builder
+                .link_points(&ids, alpha, &mut rng, scorer_for, &stopped)
+                .unwrap();
+        }
+        assert!(builder.links.iter().all(|links| links.len() <= max_degree));
+
+        let dir = Builder::new().prefix("vamana_graph").tempdir().unwrap();
+        let path = VamanaGraph::get_path(dir.path());
+        builder.save(&path).unwrap();
+        let graph = VamanaGraph::load(&path).unwrap();
+
+        assert_eq!(graph.num_points(), num_vectors);
+        for point_id in [0, 17, 999] {
+            assert_eq!(graph.links(point_id), builder.links(point_id));
+        }
+
+        let attempts = 20;
+        let mut found = 0;
+        for _ in 0..attempts {
+            let query = random_vector(&mut rng, dim);
+            let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+            let (nearest, _) = graph.greedy_search(graph.medoid(), 64, raw_scorer.as_ref(), |_| {});
+            let result: Vec<_> = nearest.into_vec().into_iter().take(top).collect();
+            let reference = raw_scorer.peek_top_iter(&mut ids.iter().copied(), top);
+            found += result
+                .iter()
+                .filter(|scored| reference.iter().any(|x| x.idx == scored.idx))
+                .count();
+        }
+        let recall = found as f64 / (attempts * top) as f64;
+        assert!(recall >= 0.8, "recall {recall} is too low");
+    }
+
+    #[test]
+    fn test_load_corrupted_vamana_graph() {
+        let num_points = 10;
+        let max_degree = 4;
+
+        let mut builder = VamanaGraphBuilder::new(num_points, max_degree, 8, 0);
+        for point_id in 0..num_points as PointOffsetType {
+            builder.links[point_id as usize] = vec![(point_id + 1) % num_points as PointOffsetType];
+        }
+
+        let dir = Builder::new().prefix("vamana_graph").tempdir().unwrap();
+        let path = VamanaGraph::get_path(dir.path());
+        builder.save(&path).unwrap();
+        assert!(!path.with_extension("tmp").exists());
+        assert!(VamanaGraph::load(&path).is_ok());
+
+        let valid_bytes = std::fs::read(&path).unwrap();
+        let slot_offset = |point_id: usize| HEADER_SIZE + point_id * slot_size(max_degree);
+        let write_u32 = |bytes: &mut Vec<u8>, offset: usize, value: u32| {
+            bytes[offset..offset + size_of::<u32>()].copy_from_slice(&value.to_le_bytes());
+        };
+
+        // Corrupted slots are only noticed when they are read and don't break the search
+        let mut rng = StdRng::seed_from_u64(42);
+        let vector_holder = TestRawScorerProducer::<CosineMetric>::new(4, num_points, &mut rng);
+        let search = |graph: &VamanaGraph| {
+            let raw_scorer = vector_holder.get_raw_scorer(vec![1.0; 4]).unwrap();
+            graph.greedy_search(graph.medoid(), 8, raw_scorer.as_ref(), |_| {});
+        };
+
+        // Links count is larger than the slot
+        let mut bytes = valid_bytes.clone();
+        write_u32(&mut bytes, slot_offset(3), max_degree as u32 + 1);
+        std::fs::write(&path, bytes).unwrap();
+        let graph = VamanaGraph::load(&path).unwrap();
+        assert_eq!(graph.links(3).len(), max_degree);
+        search(&graph);
+        drop(graph);
+
+        // Link points outside of the graph
+        let mut bytes = valid_bytes.clone();
+        write_u32(
+            &mut bytes,
+            slot_offset(3) + size_of::<u32>(),
+            num_points as u32,
+        );
+        std::fs::write(&path, bytes).unwrap();
+        let graph = VamanaGraph::load(&path).unwrap();
+        search(&graph);
+        drop(graph);
+
+        // File is truncated
+        std::fs::write(&path, &valid_bytes[..valid_bytes.len() - 1]).unwrap();
+        assert!(VamanaGraph::load(&path).is_err());
+    }
+}
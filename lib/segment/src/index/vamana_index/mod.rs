@@ -0,0 +1,3 @@
+mod config;
+pub mod graph;
+pub mod vamana;
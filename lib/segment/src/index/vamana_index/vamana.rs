@@ -0,0 +1,423 @@
+use std::cmp::max;
+use std::fs::create_dir_all;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
+use log::debug;
+use memory::mmap_ops;
+use parking_lot::Mutex;
+use rand::thread_rng;
+
+use super::config::VamanaGraphConfig;
+use super::graph::{VamanaGraph, VamanaGraphBuilder, VamanaLinks};
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::operation_time_statistics::{
+    OperationDurationsAggregator, ScopeDurationMeasurer,
+};
+use crate::common::BYTES_IN_KB;
+use crate::data_types::vectors::{DenseVector, QueryVector, VectorElementType, VectorRef};
+use crate::id_tracker::{IdTracker, IdTrackerSS};
+use crate::index::query_estimator::adjust_to_available_vectors;
+use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::index::{PayloadIndex, VectorIndex};
+use crate::payload_storage::FilterContext;
+use crate::telemetry::VectorIndexSearchesTelemetry;
+use crate::types::{Filter, SearchParams, VamanaConfig, VECTOR_ELEMENT_SIZE};
+use crate::vector_storage::{new_stoppable_raw_scorer, VectorStorage, VectorStorageEnum};
+
+/// Pruning parameter of the second build pass, controls the share of long-range links.
+const VAMANA_ALPHA: ScoreType = 1.2;
+
+/// Number of sampled vectors, used to find the center of the data.
+const MEDOID_SAMPLE_SIZE: usize = 1000;
+
+/// Vector index based on a single-layer Vamana graph, as used by DiskANN
+///
+/// Links are stored in fixed-size slots of a file, which is served with mmap,
+/// so together with mmap vector storage the index doesn't need to fit into RAM.
+/// All searches start from the medoid of the data and use greedy beam search.
+///
+/// Unlike HNSW, the graph has no additional payload-based links: filtered searches traverse
+/// the graph without the filter and only check it for the found points. If this finds less than
+/// `top` matching points, the search falls back to scoring all matching points.
+/// Quantized vectors are not used by the index.
+pub struct VamanaIndex {
+    id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+    vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+    payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    config: VamanaGraphConfig,
+    path: PathBuf,
+    graph: Option<VamanaGraph>,
+    searches_telemetry: VamanaSearchesTelemetry,
+}
+
+struct VamanaSearchesTelemetry {
+    unfiltered_plain: Arc<Mutex<OperationDurationsAggregator>>,
+    unfiltered_graph: Arc<Mutex<OperationDurationsAggregator>>,
+    small_cardinality: Arc<Mutex<OperationDurationsAggregator>>,
+    large_cardinality: Arc<Mutex<OperationDurationsAggregator>>,
+    exact_filtered: Arc<Mutex<OperationDurationsAggregator>>,
+    exact_unfiltered: Arc<Mutex<OperationDurationsAggregator>>,
+}
+
+impl VamanaIndex {
+    pub fn open(
+        path: &Path,
+        id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+        vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+        payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+        vamana_config: VamanaConfig,
+    ) -> OperationResult<Self> {
+        create_dir_all(path)?;
+
+        let config_path = VamanaGraphConfig::get_config_path(path);
+        let config = if config_path.exists() {
+            VamanaGraphConfig::load(&config_path)?
+        } else {
+            let vector_storage = vector_storage.borrow();
+            let full_scan_threshold = vamana_config
+                .full_scan_threshold
+                .saturating_mul(BYTES_IN_KB)
+                / (vector_storage.vector_dim() * VECTOR_ELEMENT_SIZE);
+
+            VamanaGraphConfig {
+                max_degree: vamana_config.max_degree,
+                build_list_size: vamana_config.build_list_size,
+                search_list_size: vamana_config.search_list_size,
+                full_scan_threshold,
+                indexed_vector_count: None,
+            }
+        };
+
+        let graph_path = VamanaGraph::get_path(path);
+        let graph = if graph_path.exists() {
+            Some(VamanaGraph::load(&graph_path)?)
+        } else {
+            None
+        };
+
+        Ok(VamanaIndex {
+            id_tracker,
+            vector_storage,
+            payload_index,
+            config,
+            path: path.to_owned(),
+            graph,
+            searches_telemetry: VamanaSearchesTelemetry {
+                unfiltered_plain: OperationDurationsAggregator::new(),
+                unfiltered_graph: OperationDurationsAggregator::new(),
+                small_cardinality: OperationDurationsAggregator::new(),
+                large_cardinality: OperationDurationsAggregator::new(),
+                exact_filtered: OperationDurationsAggregator::new(),
+                exact_unfiltered: OperationDurationsAggregator::new(),
+            },
+        })
+    }
+
+    fn save_config(&self) -> OperationResult<()> {
+        let config_path = VamanaGraphConfig::get_config_path(&self.path);
+        self.config.save(&config_path)
+    }
+
+    pub fn prefault_mmap_pages(&self) -> Option<mmap_ops::PrefaultMmapPages> {
+        let graph = self.graph.as_ref()?;
+        Some(graph.prefault_mmap_pages(&VamanaGraph::get_path(&self.path)))
+    }
+
+    /// Find the point closest to the centroid of a sample of the given points
+    fn find_medoid(
+        &self,
+        ids: &[PointOffsetType],
+        stopped: &AtomicBool,
+    ) -> OperationResult<PointOffsetType> {
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+
+        let step = max(1, ids.len() / MEDOID_SAMPLE_SIZE);
+        let mut centroid: DenseVector = vec![0.0; vector_storage.vector_dim()];
+        let mut sample_size = 0;
+        for &point_id in ids.iter().step_by(step) {
+            let vector = vector_storage.get_vector(point_id);
+            let vector: &[VectorElementType] = vector.as_vec_ref().try_into()?;
+            centroid
+                .iter_mut()
+                .zip(vector)
+                .for_each(|(sum, value)| *sum += value);
+            sample_size += 1;
+        }
+        centroid
+            .iter_mut()
+            .for_each(|sum| *sum /= sample_size.max(1) as VectorElementType);
+
+        let raw_scorer = new_stoppable_raw_scorer(
+            centroid.into(),
+            &vector_storage,
+            id_tracker.deleted_point_bitslice(),
+            stopped,
+        )?;
+        raw_scorer
+            .peek_top_iter(&mut ids.iter().copied(), 1)
+            .first()
+            .map(|scored| scored.idx)
+            .ok_or_else(|| OperationError::service_error("No points to find medoid"))
+    }
+
+    fn search_with_graph(
+        &self,
+        graph: &VamanaGraph,
+        vector: &QueryVector,
+        filter_context: Option<&dyn FilterContext>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPointOffset>> {
+        let list_size = params
+            .and_then(|params| params.hnsw_ef)
+            .unwrap_or(self.config.search_list_size);
+
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let raw_scorer = new_stoppable_raw_scorer(
+            vector.to_owned(),
+            &vector_storage,
+            id_tracker.deleted_point_bitslice(),
+            is_stopped,
+        )?;
+
+        let mut result = FixedLengthPriorityQueue::new(top);
+        graph.greedy_search(
+            graph.medoid(),
+            max(top, list_size),
+            raw_scorer.as_ref(),
+            |scored| {
+                let is_matching = raw_scorer.check_vector(scored.idx)
+                    && filter_context.map_or(true, |filter| filter.check(scored.idx));
+                if is_matching {
+                    result.push(scored);
+                }
+            },
+        );
+        Ok(result.into_vec())
+    }
+
+    fn search_vectors_with_graph(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        let Some(graph) = &self.graph else {
+            return Ok(vec![vec![]; vectors.len()]);
+        };
+        let payload_index = self.payload_index.borrow();
+        let filter_context = filter.map(|filter| payload_index.filter_context(filter));
+        vectors
+            .iter()
+            .map(|&vector| {
+                let result = self.search_with_graph(
+                    graph,
+                    vector,
+                    filter_context.as_deref(),
+                    top,
+                    params,
+                    is_stopped,
+                )?;
+                // Matching points may be outside of the traversed part of the graph
+                if filter.is_some() && result.len() < top {
+                    let mut exact_result =
+                        self.search_vectors_plain(&[vector], filter, top, is_stopped)?;
+                    return Ok(exact_result.pop().unwrap_or_default());
+                }
+                Ok(result)
+            })
+            .collect()
+    }
+
+    fn search_vectors_plain(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        // share filtered points for all query vectors
+        let filtered_points = filter.map(|filter| self.payload_index.borrow().query_points(filter));
+        vectors
+            .iter()
+            .map(|&vector| {
+                let raw_scorer = new_stoppable_raw_scorer(
+                    vector.to_owned(),
+                    &vector_storage,
+                    id_tracker.deleted_point_bitslice(),
+                    is_stopped,
+                )?;
+                Ok(match &filtered_points {
+                    Some(points) => raw_scorer.peek_top_iter(&mut points.iter().copied(), top),
+                    None => raw_scorer.peek_top_all(top),
+                })
+            })
+            .collect()
+    }
+}
+
+impl VectorIndex for VamanaIndex {
+    fn search(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        let exact = params.map(|params| params.exact).unwrap_or(false);
+        match filter {
+            None => {
+                let available_vector_count = self.vector_storage.borrow().available_vector_count();
+                if exact {
+                    let _timer =
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.exact_unfiltered);
+                    self.search_vectors_plain(vectors, None, top, is_stopped)
+                } else if available_vector_count < self.config.full_scan_threshold {
+                    let _timer =
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_plain);
+                    self.search_vectors_plain(vectors, None, top, is_stopped)
+                } else {
+                    let _timer =
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_graph);
+                    self.search_vectors_with_graph(vectors, None, top, params, is_stopped)
+                }
+            }
+            Some(query_filter) => {
+                if exact {
+                    let _timer =
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.exact_filtered);
+                    return self.search_vectors_plain(vectors, filter, top, is_stopped);
+                }
+
+                let query_cardinality = {
+                    let payload_index = self.payload_index.borrow();
+                    let vector_storage = self.vector_storage.borrow();
+                    let id_tracker = self.id_tracker.borrow();
+                    adjust_to_available_vectors(
+                        payload_index.estimate_cardinality(query_filter),
+                        vector_storage.available_vector_count(),
+                        id_tracker.available_point_count(),
+                    )
+                };
+
+                // The graph is traversed without the filter, so it only finds enough matching
+                // points if a large share of points matches. Otherwise the graph search
+                // falls back to the plain search.
+                if query_cardinality.exp < self.config.full_scan_threshold {
+                    let _timer =
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
+                    self.search_vectors_plain(vectors, filter, top, is_stopped)
+                } else {
+                    let _timer =
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
+                    self.search_vectors_with_graph(vectors, filter, top, params, is_stopped)
+                }
+            }
+        }
+    }
+
+    fn build_index(&mut self, stopped: &AtomicBool) -> OperationResult<()> {
+        let ids: Vec<_> = {
+            let id_tracker = self.id_tracker.borrow();
+            let vector_storage = self.vector_storage.borrow();
+            id_tracker
+                .iter_ids_excluding(vector_storage.deleted_vector_bitslice())
+                .collect()
+        };
+        self.config.indexed_vector_count.replace(ids.len());
+
+        if ids.is_empty() {
+            debug!("skip building Vamana graph without vectors");
+            self.graph = None;
+            return self.save_config();
+        }
+
+        let medoid = self.find_medoid(&ids, stopped)?;
+
+        let graph_path = VamanaGraph::get_path(&self.path);
+        {
+            let id_tracker = self.id_tracker.borrow();
+            let vector_storage = self.vector_storage.borrow();
+
+            let mut builder = VamanaGraphBuilder::new(
+                vector_storage.total_vector_count(),
+                self.config.max_degree,
+                self.config.build_list_size,
+                medoid,
+            );
+            let mut rng = thread_rng();
+            builder.init_random_links(&ids, &mut rng);
+
+            let scorer_for = |point_id: PointOffsetType| {
+                let vector = vector_storage.get_vector(point_id);
+                new_stoppable_raw_scorer(
+                    vector.as_vec_ref().into(),
+                    &vector_storage,
+                    id_tracker.deleted_point_bitslice(),
+                    stopped,
+                )
+            };
+            for alpha in [1.0, VAMANA_ALPHA] {
+                builder.link_points(&ids, alpha, &mut rng, scorer_for, stopped)?;
+                debug!("finish Vamana build pass with alpha {alpha}");
+            }
+
+            // Release the mmap of the previous graph before overwriting its file
+            self.graph = None;
+            builder.save(&graph_path)?;
+        }
+
+        self.graph = Some(VamanaGraph::load(&graph_path)?);
+        self.save_config()
+    }
+
+    fn get_telemetry_data(&self) -> VectorIndexSearchesTelemetry {
+        let tm = &self.searches_telemetry;
+        VectorIndexSearchesTelemetry {
+            index_name: None,
+            unfiltered_plain: tm.unfiltered_plain.lock().get_statistics(),
+            filtered_plain: Default::default(),
+            unfiltered_hnsw: tm.unfiltered_graph.lock().get_statistics(),
+            filtered_small_cardinality: tm.small_cardinality.lock().get_statistics(),
+            filtered_large_cardinality: tm.large_cardinality.lock().get_statistics(),
+            filtered_exact: tm.exact_filtered.lock().get_statistics(),
+            filtered_sparse: Default::default(),
+            unfiltered_exact: tm.exact_unfiltered.lock().get_statistics(),
+            unfiltered_sparse: Default::default(),
+            graph: None,
+        }
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        if self.graph.is_some() {
+            vec![VamanaGraph::get_path(&self.path)]
+        } else {
+            vec![]
+        }
+    }
+
+    fn indexed_vector_count(&self) -> usize {
+        self.config
+            .indexed_vector_count
+            // If indexed vector count is unknown, fall back to number of points
+            .or_else(|| self.graph.as_ref().map(|graph| graph.num_points()))
+            .unwrap_or(0)
+    }
+
+    fn update_vector(&mut self, _id: PointOffsetType, _vector: VectorRef) -> OperationResult<()> {
+        Err(OperationError::service_error("Cannot update Vamana index"))
+    }
+}
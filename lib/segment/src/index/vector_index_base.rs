@@ -5,12 +5,17 @@ use common::types::{PointOffsetType, ScoredPointOffset};
 use sparse::index::inverted_index::inverted_index_mmap::InvertedIndexMmap;
 use sparse::index::inverted_index::inverted_index_ram::InvertedIndexRam;
 
-use super::hnsw_index::graph_links::{GraphLinksMmap, GraphLinksRam};
-use super::hnsw_index::hnsw::HNSWIndex;
+use super::hnsw_index::graph_layers::GraphCheckReport;
+use super::hnsw_index::graph_links::{GraphLinksCompressed, GraphLinksMmap, GraphLinksRam};
+use super::hnsw_index::hnsw::{HNSWIndex, PreviousGraph};
+use super::ivf_index::ivf::IvfIndex;
 use super::plain_payload_index::PlainIndex;
 use super::sparse_index::sparse_vector_index::SparseVectorIndex;
+use super::vamana_index::vamana::VamanaIndex;
+use crate::common::build_progress::BuildProgress;
 use crate::common::operation_error::OperationResult;
 use crate::data_types::vectors::{QueryVector, VectorRef};
+use crate::index::query_planner::SearchPlan;
 use crate::telemetry::VectorIndexSearchesTelemetry;
 use crate::types::{Filter, SearchParams};
 
@@ -29,6 +34,17 @@ pub trait VectorIndex {
     /// Force internal index rebuild.
     fn build_index(&mut self, stopped: &AtomicBool) -> OperationResult<()>;
 
+    /// Force internal index rebuild, reporting how far along it is to `progress`
+    ///
+    /// Indexes, which can't estimate their progress, don't report anything.
+    fn build_index_with_progress(
+        &mut self,
+        stopped: &AtomicBool,
+        _progress: &BuildProgress,
+    ) -> OperationResult<()> {
+        self.build_index(stopped)
+    }
+
     fn get_telemetry_data(&self) -> VectorIndexSearchesTelemetry;
 
     fn files(&self) -> Vec<PathBuf>;
@@ -44,8 +60,11 @@ pub enum VectorIndexEnum {
     Plain(PlainIndex),
     HnswRam(HNSWIndex<GraphLinksRam>),
     HnswMmap(HNSWIndex<GraphLinksMmap>),
+    HnswCompressed(HNSWIndex<GraphLinksCompressed>),
     SparseRam(SparseVectorIndex<InvertedIndexRam>),
     SparseMmap(SparseVectorIndex<InvertedIndexMmap>),
+    Vamana(VamanaIndex),
+    Ivf(IvfIndex),
 }
 
 impl VectorIndexEnum {
@@ -54,8 +73,117 @@ impl VectorIndexEnum {
             Self::Plain(_) => false,
             Self::HnswRam(_) => true,
             Self::HnswMmap(_) => true,
+            Self::HnswCompressed(_) => true,
             Self::SparseRam(_) => true,
             Self::SparseMmap(_) => true,
+            Self::Vamana(_) => true,
+            Self::Ivf(_) => true,
+        }
+    }
+
+    /// Copy of the built HNSW graph, renumbered with `old_to_new` offsets for another segment
+    pub fn previous_graph(
+        &self,
+        old_to_new: &[PointOffsetType],
+        stopped: &AtomicBool,
+    ) -> OperationResult<Option<PreviousGraph>> {
+        match self {
+            Self::HnswRam(index) => index.previous_graph(old_to_new, stopped),
+            Self::HnswMmap(index) => index.previous_graph(old_to_new, stopped),
+            Self::HnswCompressed(index) => index.previous_graph(old_to_new, stopped),
+            Self::Plain(_)
+            | Self::SparseRam(_)
+            | Self::SparseMmap(_)
+            | Self::Vamana(_)
+            | Self::Ivf(_) => Ok(None),
+        }
+    }
+
+    /// Check the HNSW graph and re-link points, which are not reachable from its entry points
+    ///
+    /// Returns the result of the check made before the repair, `None` for indexes without a graph.
+    pub fn repair_graph(
+        &mut self,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Option<GraphCheckReport>> {
+        match self {
+            Self::HnswRam(index) => index.repair_graph(stopped),
+            Self::HnswMmap(index) => index.repair_graph(stopped),
+            Self::HnswCompressed(index) => index.repair_graph(stopped),
+            Self::Plain(_)
+            | Self::SparseRam(_)
+            | Self::SparseMmap(_)
+            | Self::Vamana(_)
+            | Self::Ivf(_) => Ok(None),
+        }
+    }
+
+    /// Unlink a deleted vector from the HNSW graph of an appendable segment, see `update_vector`
+    pub fn remove_vector(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        match self {
+            Self::HnswRam(index) => index.remove_vector(id),
+            Self::HnswMmap(index) => index.remove_vector(id),
+            Self::HnswCompressed(index) => index.remove_vector(id),
+            Self::Plain(_)
+            | Self::SparseRam(_)
+            | Self::SparseMmap(_)
+            | Self::Vamana(_)
+            | Self::Ivf(_) => Ok(()),
+        }
+    }
+
+    /// Checkpoint the next build of the index into `dir`, if the index supports it
+    pub fn set_build_checkpoint_dir(&mut self, dir: PathBuf) {
+        match self {
+            Self::HnswRam(index) => index.set_build_checkpoint_dir(dir),
+            Self::HnswMmap(index) => index.set_build_checkpoint_dir(dir),
+            Self::HnswCompressed(index) => index.set_build_checkpoint_dir(dir),
+            Self::Plain(_)
+            | Self::SparseRam(_)
+            | Self::SparseMmap(_)
+            | Self::Vamana(_)
+            | Self::Ivf(_) => {}
+        }
+    }
+
+    /// Whether the index can start its build from a `PreviousGraph`
+    pub fn accepts_previous_graph(&self) -> bool {
+        matches!(
+            self,
+            Self::HnswRam(_) | Self::HnswMmap(_) | Self::HnswCompressed(_)
+        )
+    }
+
+    /// Strategy, which the search with the given filter and params would use.
+    /// `None` for indexes, which don't choose the strategy with the query planner.
+    pub fn search_plan(
+        &self,
+        filter: Option<&Filter>,
+        params: Option<&SearchParams>,
+    ) -> Option<SearchPlan> {
+        match self {
+            Self::HnswRam(index) => Some(index.search_plan(filter, params)),
+            Self::HnswMmap(index) => Some(index.search_plan(filter, params)),
+            Self::HnswCompressed(index) => Some(index.search_plan(filter, params)),
+            Self::Plain(_)
+            | Self::SparseRam(_)
+            | Self::SparseMmap(_)
+            | Self::Vamana(_)
+            | Self::Ivf(_) => None,
+        }
+    }
+
+    /// Start the next build from `previous_graph`, ignored by non-HNSW indexes
+    pub fn set_previous_graph(&mut self, previous_graph: PreviousGraph) {
+        match self {
+            Self::HnswRam(index) => index.set_previous_graph(previous_graph),
+            Self::HnswMmap(index) => index.set_previous_graph(previous_graph),
+            Self::HnswCompressed(index) => index.set_previous_graph(previous_graph),
+            Self::Plain(_)
+            | Self::SparseRam(_)
+            | Self::SparseMmap(_)
+            | Self::Vamana(_)
+            | Self::Ivf(_) => {}
         }
     }
 }
@@ -77,12 +205,19 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::HnswMmap(index) => {
                 index.search(vectors, filter, top, params, is_stopped)
             }
+            VectorIndexEnum::HnswCompressed(index) => {
+                index.search(vectors, filter, top, params, is_stopped)
+            }
             VectorIndexEnum::SparseRam(index) => {
                 index.search(vectors, filter, top, params, is_stopped)
             }
             VectorIndexEnum::SparseMmap(index) => {
                 index.search(vectors, filter, top, params, is_stopped)
             }
+            VectorIndexEnum::Vamana(index) => {
+                index.search(vectors, filter, top, params, is_stopped)
+            }
+            VectorIndexEnum::Ivf(index) => index.search(vectors, filter, top, params, is_stopped),
         }
     }
 
@@ -91,8 +226,32 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::Plain(index) => index.build_index(stopped),
             VectorIndexEnum::HnswRam(index) => index.build_index(stopped),
             VectorIndexEnum::HnswMmap(index) => index.build_index(stopped),
+            VectorIndexEnum::HnswCompressed(index) => index.build_index(stopped),
             VectorIndexEnum::SparseRam(index) => index.build_index(stopped),
             VectorIndexEnum::SparseMmap(index) => index.build_index(stopped),
+            VectorIndexEnum::Vamana(index) => index.build_index(stopped),
+            VectorIndexEnum::Ivf(index) => index.build_index(stopped),
+        }
+    }
+
+    fn build_index_with_progress(
+        &mut self,
+        stopped: &AtomicBool,
+        progress: &BuildProgress,
+    ) -> OperationResult<()> {
+        match self {
+            VectorIndexEnum::Plain(index) => index.build_index_with_progress(stopped, progress),
+            VectorIndexEnum::HnswRam(index) => index.build_index_with_progress(stopped, progress),
+            VectorIndexEnum::HnswMmap(index) => index.build_index_with_progress(stopped, progress),
+            VectorIndexEnum::HnswCompressed(index) => {
+                index.build_index_with_progress(stopped, progress)
+            }
+            VectorIndexEnum::SparseRam(index) => index.build_index_with_progress(stopped, progress),
+            VectorIndexEnum::SparseMmap(index) => {
+                index.build_index_with_progress(stopped, progress)
+            }
+            VectorIndexEnum::Vamana(index) => index.build_index_with_progress(stopped, progress),
+            VectorIndexEnum::Ivf(index) => index.build_index_with_progress(stopped, progress),
         }
     }
 
@@ -101,8 +260,11 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::Plain(index) => index.get_telemetry_data(),
             VectorIndexEnum::HnswRam(index) => index.get_telemetry_data(),
             VectorIndexEnum::HnswMmap(index) => index.get_telemetry_data(),
+            VectorIndexEnum::HnswCompressed(index) => index.get_telemetry_data(),
             VectorIndexEnum::SparseRam(index) => index.get_telemetry_data(),
             VectorIndexEnum::SparseMmap(index) => index.get_telemetry_data(),
+            VectorIndexEnum::Vamana(index) => index.get_telemetry_data(),
+            VectorIndexEnum::Ivf(index) => index.get_telemetry_data(),
         }
     }
 
@@ -111,8 +273,11 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::Plain(index) => index.files(),
             VectorIndexEnum::HnswRam(index) => index.files(),
             VectorIndexEnum::HnswMmap(index) => index.files(),
+            VectorIndexEnum::HnswCompressed(index) => index.files(),
             VectorIndexEnum::SparseRam(index) => index.files(),
             VectorIndexEnum::SparseMmap(index) => index.files(),
+            VectorIndexEnum::Vamana(index) => index.files(),
+            VectorIndexEnum::Ivf(index) => index.files(),
         }
     }
 
@@ -121,8 +286,11 @@ impl VectorIndex for VectorIndexEnum {
             Self::Plain(index) => index.indexed_vector_count(),
             Self::HnswRam(index) => index.indexed_vector_count(),
             Self::HnswMmap(index) => index.indexed_vector_count(),
+            Self::HnswCompressed(index) => index.indexed_vector_count(),
             Self::SparseRam(index) => index.indexed_vector_count(),
             Self::SparseMmap(index) => index.indexed_vector_count(),
+            Self::Vamana(index) => index.indexed_vector_count(),
+            Self::Ivf(index) => index.indexed_vector_count(),
         }
     }
 
@@ -131,8 +299,11 @@ impl VectorIndex for VectorIndexEnum {
             Self::Plain(index) => index.update_vector(id, vector),
             Self::HnswRam(index) => index.update_vector(id, vector),
             Self::HnswMmap(index) => index.update_vector(id, vector),
+            Self::HnswCompressed(index) => index.update_vector(id, vector),
             Self::SparseRam(index) => index.update_vector(id, vector),
             Self::SparseMmap(index) => index.update_vector(id, vector),
+            Self::Vamana(index) => index.update_vector(id, vector),
+            Self::Ivf(index) => index.update_vector(id, vector),
         }
     }
 }
@@ -3,8 +3,9 @@
 use serde_json::Value;
 
 use crate::types::{
-    AnyVariants, FieldCondition, GeoBoundingBox, GeoPoint, GeoPolygon, GeoRadius, Match, MatchAny,
-    MatchExcept, MatchText, MatchValue, Range, ValueVariants, ValuesCount,
+    parse_datetime, AnyVariants, DatetimeRange, FieldCondition, GeoBoundingBox, GeoPoint,
+    GeoPolygon, GeoRadius, Match, MatchAny, MatchExcept, MatchPhrase, MatchPrefix, MatchText,
+    MatchValue, Range, RangeInterface, ValueVariants, ValuesCount,
 };
 
 pub trait ValueChecker {
@@ -80,10 +81,18 @@ impl ValueChecker for Match {
                 }
                 _ => false,
             },
-            Match::Text(MatchText { text }) => match payload {
+            Match::Text(MatchText { text, .. }) => match payload {
                 Value::String(stored) => stored.contains(text),
                 _ => false,
             },
+            Match::Phrase(MatchPhrase { phrase, .. }) => match payload {
+                Value::String(stored) => stored.contains(phrase),
+                _ => false,
+            },
+            Match::Prefix(MatchPrefix { prefix }) => match payload {
+                Value::String(stored) => stored.starts_with(prefix),
+                _ => false,
+            },
             Match::Any(MatchAny { any }) => match (payload, any) {
                 (Value::String(stored), AnyVariants::Keywords(list)) => list.contains(stored),
                 (Value::Number(stored), AnyVariants::Integers(list)) => stored
@@ -121,6 +130,26 @@ impl ValueChecker for Range {
     }
 }
 
+impl ValueChecker for DatetimeRange {
+    fn check_match(&self, payload: &Value) -> bool {
+        match payload {
+            Value::String(stored) => parse_datetime(stored)
+                .map(|datetime| self.check_range(datetime))
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+}
+
+impl ValueChecker for RangeInterface {
+    fn check_match(&self, payload: &Value) -> bool {
+        match self {
+            RangeInterface::Float(range) => range.check_match(payload),
+            RangeInterface::DateTime(range) => range.check_match(payload),
+        }
+    }
+}
+
 impl ValueChecker for GeoBoundingBox {
     fn check_match(&self, payload: &Value) -> bool {
         match payload {
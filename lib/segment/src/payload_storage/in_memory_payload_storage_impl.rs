@@ -63,7 +63,7 @@ mod tests {
     use serde_json::json;
 
     use super::*;
-    use crate::common::utils::IndexesMap;
+    use crate::common::utils::{IndexesMap, VectorStoragesMap};
     use crate::fixtures::payload_context_fixture::FixtureIdTracker;
     use crate::payload_storage::query_checker::check_payload;
     use crate::types::{Condition, FieldCondition, Filter, OwnedPayloadRef};
@@ -88,6 +88,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: Some(vec![
                 Condition::Field(FieldCondition::new_match("age".to_string(), 43.into())),
                 Condition::Field(FieldCondition::new_match(
@@ -112,6 +113,7 @@ mod tests {
                 payload.borrow().as_ref().cloned().unwrap()
             }),
             Some(&id_tracker),
+            &VectorStoragesMap::new(),
             &query,
             0,
             &IndexesMap::new(),
@@ -0,0 +1,555 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::mem::size_of;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use common::types::PointOffsetType;
+use memmap2::Mmap;
+use memory::mmap_ops::open_read_mmap;
+use parking_lot::Mutex;
+use serde_json::Value;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::Flusher;
+use crate::payload_storage::payload_record::{deserialize_payload, serialize_payload};
+use crate::payload_storage::PayloadStorage;
+use crate::types::{Payload, PayloadCompression, PayloadKeyTypeRef};
+
+const PAYLOAD_LOG_FILE: &str = "payload.log";
+
+/// Record header: point offset, length of the serialized payload and CRC32 of the record,
+/// all little endian `u32`
+const RECORD_HEADER_SIZE: usize = 3 * size_of::<u32>();
+
+/// Part of the header, which is covered by the checksum together with the payload
+const CHECKSUMMED_HEADER_SIZE: usize = 2 * size_of::<u32>();
+
+/// Length value, which marks a record of the removed payload
+const REMOVED_RECORD_LENGTH: u32 = u32::MAX;
+
+/// Records, written after the last mapping of the log, are kept in memory until the log is
+/// re-mapped. Limits the memory used for them.
+#[cfg(debug_assertions)]
+const MAX_UNMAPPED_TAIL_SIZE: usize = 1024; // 1Kb
+#[cfg(not(debug_assertions))]
+const MAX_UNMAPPED_TAIL_SIZE: usize = 32 * 1024 * 1024; // 32Mb
+
+/// Smaller logs are not compacted, even if most of their records are outdated
+#[cfg(debug_assertions)]
+const MIN_COMPACTION_LOG_SIZE: usize = 4 * 1024; // 4Kb
+#[cfg(not(debug_assertions))]
+const MIN_COMPACTION_LOG_SIZE: usize = 64 * 1024 * 1024; // 64Mb
+
+/// Position of the serialized payload in the log
+#[derive(Debug, Clone, Copy)]
+struct RecordPosition {
+    offset: usize,
+    length: usize,
+}
+
+/// Persistent implementation of `PayloadStorage`, which does not depend on RocksDB.
+///
+/// Every change of the payload is appended to the log file as a new record, the log is read via
+/// memory mapping. Position of the latest record of each point is kept in memory and is restored
+/// by scanning the log on load. Records are checksummed, the log is truncated at the first invalid
+/// record, e.g. a torn or zero-filled tail after a crash.
+///
+/// Once outdated records take more than half of the log, the log is rewritten with the latest
+/// records only, so it stays within twice the size of the current payload.
+pub struct MmapPayloadStorage {
+    log_path: PathBuf,
+    /// Log file, shared with flushers, because compaction replaces it
+    file: Arc<Mutex<File>>,
+    /// Mapping of the first `mapped_length` bytes of the log
+    mmap: Option<Mmap>,
+    mapped_length: usize,
+    /// Records, appended after the log was mapped
+    tail: Vec<u8>,
+    positions: Vec<Option<RecordPosition>>,
+    /// Size of the records in `positions`, including headers
+    live_size: usize,
+    compression: Option<PayloadCompression>,
+}
+
+impl MmapPayloadStorage {
+    pub fn open(path: &Path, compression: Option<PayloadCompression>) -> OperationResult<Self> {
+        fs::create_dir_all(path)?;
+        let log_path = path.join(PAYLOAD_LOG_FILE);
+        let compaction_path = log_path.with_extension("tmp");
+        if compaction_path.exists() {
+            // Leftover of an interrupted compaction, the log itself is not affected
+            fs::remove_file(&compaction_path)?;
+        }
+        let file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&log_path)?;
+
+        let mut storage = MmapPayloadStorage {
+            log_path,
+            file: Arc::new(Mutex::new(file)),
+            mmap: None,
+            mapped_length: 0,
+            tail: vec![],
+            positions: vec![],
+            live_size: 0,
+            compression,
+        };
+        storage.remap()?;
+
+        let valid_length = storage.load_positions();
+        if valid_length < storage.mapped_length {
+            // Record was not written completely or is corrupted, drop it and everything after it
+            log::warn!(
+                "Truncating invalid record of payload log {:?} at {valid_length}",
+                storage.log_path,
+            );
+            storage.mmap = None;
+            storage.file.lock().set_len(valid_length as u64)?;
+            storage.remap()?;
+        }
+
+        Ok(storage)
+    }
+
+    /// Map the whole log file, records appended so far become readable from the mapping
+    fn remap(&mut self) -> OperationResult<()> {
+        let length = self.file.lock().metadata()?.len() as usize;
+        self.mmap = if length > 0 {
+            Some(open_read_mmap(&self.log_path)?)
+        } else {
+            None
+        };
+        self.mapped_length = length;
+        self.tail.clear();
+        Ok(())
+    }
+
+    /// Restore positions of the latest records from the mapped log.
+    ///
+    /// Returns length of the log, which consists of complete records with valid checksums.
+    fn load_positions(&mut self) -> usize {
+        let Some(mmap) = self.mmap.take() else {
+            return 0;
+        };
+
+        let mut offset = 0;
+        while offset + RECORD_HEADER_SIZE <= mmap.len() {
+            let header = &mmap[offset..offset + RECORD_HEADER_SIZE];
+            let (point_id, length, checksum) = parse_header(header);
+            let data_offset = offset + RECORD_HEADER_SIZE;
+
+            let data_length = if length == REMOVED_RECORD_LENGTH {
+                0
+            } else {
+                length as usize
+            };
+            let Some(data) = mmap.get(data_offset..data_offset + data_length) else {
+                break;
+            };
+            if record_checksum(&header[..CHECKSUMMED_HEADER_SIZE], data) != checksum {
+                break;
+            }
+
+            let position = (length != REMOVED_RECORD_LENGTH).then_some(RecordPosition {
+                offset: data_offset,
+                length: data_length,
+            });
+            self.set_position(point_id, position);
+
+            offset = data_offset + data_length;
+        }
+
+        self.mmap = Some(mmap);
+        offset
+    }
+
+    fn set_position(&mut self, point_id: PointOffsetType, position: Option<RecordPosition>) {
+        let point_id = point_id as usize;
+        if point_id >= self.positions.len() {
+            self.positions.resize(point_id + 1, None);
+        }
+        let record_size = |position: RecordPosition| RECORD_HEADER_SIZE + position.length;
+        if let Some(previous) = self.positions[point_id] {
+            self.live_size -= record_size(previous);
+        }
+        if let Some(position) = position {
+            self.live_size += record_size(position);
+        }
+        self.positions[point_id] = position;
+    }
+
+    fn log_length(&self) -> usize {
+        self.mapped_length + self.tail.len()
+    }
+
+    fn record_data(&self, position: RecordPosition) -> &[u8] {
+        let RecordPosition { offset, length } = position;
+        if offset < self.mapped_length {
+            let mmap = self
+                .mmap
+                .as_ref()
+                .expect("log is mapped if its mapped length is not zero");
+            &mmap[offset..offset + length]
+        } else {
+            let offset = offset - self.mapped_length;
+            &self.tail[offset..offset + length]
+        }
+    }
+
+    /// Append record to the log. Payload of `None` removes the payload of the point.
+    fn append_record(
+        &mut self,
+        point_id: PointOffsetType,
+        data: Option<&[u8]>,
+    ) -> OperationResult<()> {
+        let record = encode_record(point_id, data)?;
+        self.file.lock().write_all(&record)?;
+
+        let position = data.map(|data| RecordPosition {
+            offset: self.log_length() + RECORD_HEADER_SIZE,
+            length: data.len(),
+        });
+        self.tail.extend_from_slice(&record);
+        self.set_position(point_id, position);
+
+        let log_length = self.log_length();
+        if log_length >= MIN_COMPACTION_LOG_SIZE && log_length - self.live_size > self.live_size {
+            self.compact()?;
+        } else if self.tail.len() > MAX_UNMAPPED_TAIL_SIZE {
+            self.remap()?;
+        }
+        Ok(())
+    }
+
+    /// Rewrite the log with the latest records of the points only
+    ///
+    /// The compacted log is written next to the log and replaces it atomically.
+    fn compact(&mut self) -> OperationResult<()> {
+        let compaction_path = self.log_path.with_extension("tmp");
+        let mut positions = Vec::with_capacity(self.positions.len());
+        let mut offset = 0;
+        {
+            let mut writer = BufWriter::new(File::create(&compaction_path)?);
+            for (point_id, position) in self.positions.iter().enumerate() {
+                let Some(position) = *position else {
+                    positions.push(None);
+                    continue;
+                };
+                let record = encode_record(
+                    point_id as PointOffsetType,
+                    Some(self.record_data(position)),
+                )?;
+                writer.write_all(&record)?;
+                positions.push(Some(RecordPosition {
+                    offset: offset + RECORD_HEADER_SIZE,
+                    length: position.length,
+                }));
+                offset += record.len();
+            }
+            writer.flush()?;
+            writer.get_ref().sync_all()?;
+        }
+
+        log::debug!(
+            "Compacted payload log {:?} from {} to {offset} bytes",
+            self.log_path,
+            self.log_length(),
+        );
+
+        self.mmap = None;
+        fs::rename(&compaction_path, &self.log_path)?;
+        // Rename is only durable once the directory is synced,
+        // otherwise the outdated log could be restored after a crash
+        #[cfg(unix)]
+        if let Some(dir) = self.log_path.parent() {
+            File::open(dir)?.sync_all()?;
+        }
+        *self.file.lock() = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .open(&self.log_path)?;
+        self.positions = positions;
+        self.live_size = offset;
+        self.remap()
+    }
+
+    pub fn update_storage(
+        &mut self,
+        point_id: PointOffsetType,
+        payload: &Payload,
+    ) -> OperationResult<()> {
+        let data = serialize_payload(payload, self.compression)?;
+        self.append_record(point_id, Some(&data))
+    }
+
+    pub fn read_payload(&self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
+        match self.positions.get(point_id as usize).copied().flatten() {
+            Some(position) => deserialize_payload(self.record_data(position)).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub fn iter<F>(&self, mut callback: F) -> OperationResult<()>
+    where
+        F: FnMut(PointOffsetType, &Payload) -> OperationResult<bool>,
+    {
+        for (point_id, position) in self.positions.iter().enumerate() {
+            let Some(position) = position else {
+                continue;
+            };
+            let payload = deserialize_payload(self.record_data(*position))?;
+            if !callback(point_id as PointOffsetType, &payload)? {
+                return Ok(());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        vec![self.log_path.clone()]
+    }
+}
+
+/// Serialize a record of the point, payload of `None` marks the removed payload
+fn encode_record(point_id: PointOffsetType, data: Option<&[u8]>) -> OperationResult<Vec<u8>> {
+    let length = match data {
+        Some(data) if data.len() >= REMOVED_RECORD_LENGTH as usize => {
+            return Err(OperationError::service_error(format!(
+                "Payload of point {point_id} is too large: {} bytes",
+                data.len(),
+            )));
+        }
+        Some(data) => data.len() as u32,
+        None => REMOVED_RECORD_LENGTH,
+    };
+    let data = data.unwrap_or_default();
+
+    let mut record = Vec::with_capacity(RECORD_HEADER_SIZE + data.len());
+    record.extend_from_slice(&point_id.to_le_bytes());
+    record.extend_from_slice(&length.to_le_bytes());
+    let checksum = record_checksum(&record, data);
+    record.extend_from_slice(&checksum.to_le_bytes());
+    record.extend_from_slice(data);
+    Ok(record)
+}
+
+fn record_checksum(checksummed_header: &[u8], data: &[u8]) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(checksummed_header);
+    hasher.update(data);
+    hasher.finalize()
+}
+
+fn parse_header(header: &[u8]) -> (PointOffsetType, u32, u32) {
+    let read_u32 = |index: usize| {
+        let offset = index * size_of::<u32>();
+        u32::from_le_bytes(
+            header[offset..offset + size_of::<u32>()]
+                .try_into()
+                .unwrap(),
+        )
+    };
+    (read_u32(0) as PointOffsetType, read_u32(1), read_u32(2))
+}
+
+impl PayloadStorage for MmapPayloadStorage {
+    fn assign_all(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
+        self.update_storage(point_id, payload)
+    }
+
+    fn assign(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
+        match self.read_payload(point_id)? {
+            Some(mut point_payload) => {
+                point_payload.merge(payload);
+                self.update_storage(point_id, &point_payload)
+            }
+            None => self.update_storage(point_id, payload),
+        }
+    }
+
+    fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
+        Ok(self.read_payload(point_id)?.unwrap_or_default())
+    }
+
+    fn delete(
+        &mut self,
+        point_id: PointOffsetType,
+        key: PayloadKeyTypeRef,
+    ) -> OperationResult<Vec<Value>> {
+        match self.read_payload(point_id)? {
+            Some(mut payload) => {
+                let res = payload.remove(key);
+                if !res.is_empty() {
+                    self.update_storage(point_id, &payload)?;
+                }
+                Ok(res)
+            }
+            None => Ok(vec![]),
+        }
+    }
+
+    fn drop(&mut self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
+        let payload = self.read_payload(point_id)?;
+        if payload.is_some() {
+            self.append_record(point_id, None)?;
+        }
+        Ok(payload)
+    }
+
+    fn wipe(&mut self) -> OperationResult<()> {
+        self.mmap = None;
+        self.file.lock().set_len(0)?;
+        self.positions.clear();
+        self.live_size = 0;
+        self.remap()
+    }
+
+    fn flusher(&self) -> Flusher {
+        let file = self.file.clone();
+        Box::new(move || {
+            // Resolve the file on flush, the log could be compacted since the flusher was created.
+            // Records of the replaced log were synced by the compaction.
+            let file = file.lock().try_clone()?;
+            file.sync_data()?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::max;
+
+    use serde_json::json;
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_mmap_payload_storage_reload() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+
+        let payload =
+            |value: usize| Payload::from(json!({ "value": value, "text": "a".repeat(value) }));
+
+        {
+            let mut storage = MmapPayloadStorage::open(dir.path(), None).unwrap();
+            for point_id in 0..100 {
+                storage
+                    .assign(point_id, &payload(point_id as usize))
+                    .unwrap();
+            }
+            storage.assign_all(10, &payload(1000)).unwrap();
+            storage.drop(20).unwrap();
+            storage
+                .assign(30, &Payload::from(json!({ "extra": true })))
+                .unwrap();
+            storage.delete(40, "text").unwrap();
+            storage.flusher()().unwrap();
+        }
+
+        // Simulate incomplete write of the last record
+        {
+            let mut file = OpenOptions::new()
+                .append(true)
+                .open(dir.path().join(PAYLOAD_LOG_FILE))
+                .unwrap();
+            let data = serialize_payload(&payload(0), None).unwrap();
+            let record = encode_record(50, Some(&data)).unwrap();
+            file.write_all(&record[..RECORD_HEADER_SIZE + 1]).unwrap();
+        }
+
+        let mut storage = MmapPayloadStorage::open(dir.path(), None).unwrap();
+        assert_eq!(storage.payload(10).unwrap(), payload(1000));
+        assert_eq!(storage.payload(20).unwrap(), Payload::default());
+        assert_eq!(storage.payload(30).unwrap().0["extra"], json!(true));
+        assert_eq!(
+            storage.payload(40).unwrap(),
+            Payload::from(json!({ "value": 40 }))
+        );
+        assert_eq!(storage.payload(50).unwrap(), payload(50));
+
+        let mut count = 0;
+        storage
+            .iter(|_, _| {
+                count += 1;
+                Ok(true)
+            })
+            .unwrap();
+        assert_eq!(count, 99);
+
+        storage.wipe().unwrap();
+        assert_eq!(storage.payload(10).unwrap(), Payload::default());
+        storage.assign(10, &payload(10)).unwrap();
+        assert_eq!(storage.payload(10).unwrap(), payload(10));
+    }
+
+    #[test]
+    fn test_mmap_payload_storage_zero_filled_tail() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let log_path = dir.path().join(PAYLOAD_LOG_FILE);
+        let payload = Payload::from(json!({ "value": 1 }));
+
+        let valid_length = {
+            let mut storage = MmapPayloadStorage::open(dir.path(), None).unwrap();
+            storage.assign(1, &payload).unwrap();
+            storage.flusher()().unwrap();
+            storage.log_length()
+        };
+
+        // File system may extend the file with zeros on crash, before the data is written
+        {
+            let mut file = OpenOptions::new().append(true).open(&log_path).unwrap();
+            file.write_all(&[0; 4 * RECORD_HEADER_SIZE]).unwrap();
+        }
+
+        let storage = MmapPayloadStorage::open(dir.path(), None).unwrap();
+        assert_eq!(storage.payload(0).unwrap(), Payload::default());
+        assert_eq!(storage.payload(1).unwrap(), payload);
+        assert_eq!(
+            fs::metadata(&log_path).unwrap().len() as usize,
+            valid_length
+        );
+    }
+
+    #[test]
+    fn test_mmap_payload_storage_compaction() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let log_path = dir.path().join(PAYLOAD_LOG_FILE);
+        let num_points = 10;
+
+        let payload = |point_id: PointOffsetType, version: usize| {
+            Payload::from(json!({ "point": point_id, "version": version }))
+        };
+
+        let mut storage = MmapPayloadStorage::open(dir.path(), None).unwrap();
+        // Flusher, created before compaction, syncs the compacted log
+        let flusher = storage.flusher();
+        let mut max_log_length = 0;
+        for version in 0..100 {
+            for point_id in 0..num_points {
+                storage
+                    .assign_all(point_id, &payload(point_id, version))
+                    .unwrap();
+            }
+            storage.drop(0).unwrap();
+            max_log_length = max_log_length.max(storage.log_length());
+        }
+
+        // Outdated records are removed, so the log doesn't grow with every update
+        assert!(max_log_length < 2 * MIN_COMPACTION_LOG_SIZE);
+        assert!(storage.log_length() <= max(MIN_COMPACTION_LOG_SIZE, 2 * storage.live_size));
+        flusher().unwrap();
+        drop(storage);
+        assert!(!log_path.with_extension("tmp").exists());
+
+        let storage = MmapPayloadStorage::open(dir.path(), None).unwrap();
+        assert_eq!(storage.payload(0).unwrap(), Payload::default());
+        for point_id in 1..num_points {
+            assert_eq!(storage.payload(point_id).unwrap(), payload(point_id, 99));
+        }
+    }
+}
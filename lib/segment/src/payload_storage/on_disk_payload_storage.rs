@@ -5,22 +5,30 @@ use parking_lot::RwLock;
 use rocksdb::DB;
 use serde_json::Value;
 
-use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::operation_error::OperationResult;
 use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_PAYLOAD_CF};
 use crate::common::Flusher;
+use crate::payload_storage::payload_record::{deserialize_payload, serialize_payload};
 use crate::payload_storage::PayloadStorage;
-use crate::types::{Payload, PayloadKeyTypeRef};
+use crate::types::{Payload, PayloadCompression, PayloadKeyTypeRef};
 
 /// On-disk implementation of `PayloadStorage`.
 /// Persists all changes to disk using `store`, does not keep payload in memory
 pub struct OnDiskPayloadStorage {
     db_wrapper: DatabaseColumnWrapper,
+    compression: Option<PayloadCompression>,
 }
 
 impl OnDiskPayloadStorage {
-    pub fn open(database: Arc<RwLock<DB>>) -> OperationResult<Self> {
+    pub fn open(
+        database: Arc<RwLock<DB>>,
+        compression: Option<PayloadCompression>,
+    ) -> OperationResult<Self> {
         let db_wrapper = DatabaseColumnWrapper::new(database, DB_PAYLOAD_CF);
-        Ok(OnDiskPayloadStorage { db_wrapper })
+        Ok(OnDiskPayloadStorage {
+            db_wrapper,
+            compression,
+        })
     }
 
     pub fn remove_from_storage(&self, point_id: PointOffsetType) -> OperationResult<()> {
@@ -35,16 +43,15 @@ impl OnDiskPayloadStorage {
     ) -> OperationResult<()> {
         self.db_wrapper.put(
             serde_cbor::to_vec(&point_id).unwrap(),
-            serde_cbor::to_vec(payload).unwrap(),
+            serialize_payload(payload, self.compression)?,
         )
     }
 
     pub fn read_payload(&self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
         let key = serde_cbor::to_vec(&point_id).unwrap();
         self.db_wrapper
-            .get_pinned(&key, |raw| serde_cbor::from_slice(raw))?
+            .get_pinned(&key, deserialize_payload)?
             .transpose()
-            .map_err(OperationError::from)
     }
 
     pub fn iter<F>(&self, mut callback: F) -> OperationResult<()>
@@ -52,10 +59,7 @@ impl OnDiskPayloadStorage {
         F: FnMut(PointOffsetType, &Payload) -> OperationResult<bool>,
     {
         for (key, val) in self.db_wrapper.lock_db().iter()? {
-            let do_continue = callback(
-                serde_cbor::from_slice(&key)?,
-                &serde_cbor::from_slice(&val)?,
-            )?;
+            let do_continue = callback(serde_cbor::from_slice(&key)?, &deserialize_payload(&val)?)?;
             if !do_continue {
                 return Ok(());
             }
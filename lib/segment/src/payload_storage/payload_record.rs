@@ -0,0 +1,56 @@
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::types::{Payload, PayloadCompression};
+
+/// Magic number, which starts every zstd frame.
+/// CBOR encoded payload always starts with a map header, so records are distinguishable.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+const ZSTD_LEVEL: i32 = 3;
+
+/// Serialize payload into a storage record, optionally compressed
+pub fn serialize_payload(
+    payload: &Payload,
+    compression: Option<PayloadCompression>,
+) -> OperationResult<Vec<u8>> {
+    let raw = serde_cbor::to_vec(payload)?;
+    match compression {
+        None => Ok(raw),
+        Some(PayloadCompression::Zstd) => Ok(zstd::encode_all(raw.as_slice(), ZSTD_LEVEL)?),
+    }
+}
+
+/// Deserialize payload from a storage record
+///
+/// Compression is detected for each record, so records written with different settings
+/// can be read from the same storage.
+pub fn deserialize_payload(record: &[u8]) -> OperationResult<Payload> {
+    if record.starts_with(&ZSTD_MAGIC) {
+        let raw = zstd::decode_all(record).map_err(|err| {
+            OperationError::service_error(format!("cannot decompress payload: {err}"))
+        })?;
+        Ok(serde_cbor::from_slice(&raw)?)
+    } else {
+        Ok(serde_cbor::from_slice(record)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_record_compression() {
+        let payload: Payload = serde_json::from_str(
+            r#"{"text": "lorem ipsum lorem ipsum lorem ipsum lorem ipsum", "count": 42}"#,
+        )
+        .unwrap();
+
+        let plain = serialize_payload(&payload, None).unwrap();
+        let compressed = serialize_payload(&payload, Some(PayloadCompression::Zstd)).unwrap();
+        assert!(!plain.starts_with(&ZSTD_MAGIC));
+        assert!(compressed.starts_with(&ZSTD_MAGIC));
+
+        assert_eq!(deserialize_payload(&plain).unwrap(), payload);
+        assert_eq!(deserialize_payload(&compressed).unwrap(), payload);
+    }
+}
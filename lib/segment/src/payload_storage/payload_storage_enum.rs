@@ -1,9 +1,12 @@
+use std::path::PathBuf;
+
 use common::types::PointOffsetType;
 use serde_json::Value;
 
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
 use crate::payload_storage::in_memory_payload_storage::InMemoryPayloadStorage;
+use crate::payload_storage::mmap_payload_storage::MmapPayloadStorage;
 use crate::payload_storage::on_disk_payload_storage::OnDiskPayloadStorage;
 use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
 use crate::payload_storage::PayloadStorage;
@@ -13,6 +16,7 @@ pub enum PayloadStorageEnum {
     InMemoryPayloadStorage(InMemoryPayloadStorage),
     SimplePayloadStorage(SimplePayloadStorage),
     OnDiskPayloadStorage(OnDiskPayloadStorage),
+    MmapPayloadStorage(MmapPayloadStorage),
 }
 
 impl From<InMemoryPayloadStorage> for PayloadStorageEnum {
@@ -33,6 +37,12 @@ impl From<OnDiskPayloadStorage> for PayloadStorageEnum {
     }
 }
 
+impl From<MmapPayloadStorage> for PayloadStorageEnum {
+    fn from(a: MmapPayloadStorage) -> Self {
+        PayloadStorageEnum::MmapPayloadStorage(a)
+    }
+}
+
 impl PayloadStorageEnum {
     pub fn iter<F>(&self, callback: F) -> OperationResult<()>
     where
@@ -42,6 +52,17 @@ impl PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.iter(callback),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.iter(callback),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.iter(callback),
+            PayloadStorageEnum::MmapPayloadStorage(s) => s.iter(callback),
+        }
+    }
+
+    /// Files of the storage, which are not part of the segment's RocksDB
+    pub fn files(&self) -> Vec<PathBuf> {
+        match self {
+            PayloadStorageEnum::InMemoryPayloadStorage(_) => vec![],
+            PayloadStorageEnum::SimplePayloadStorage(_) => vec![],
+            PayloadStorageEnum::OnDiskPayloadStorage(_) => vec![],
+            PayloadStorageEnum::MmapPayloadStorage(s) => s.files(),
         }
     }
 }
@@ -52,6 +73,7 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.assign(point_id, payload),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.assign(point_id, payload),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.assign(point_id, payload),
+            PayloadStorageEnum::MmapPayloadStorage(s) => s.assign(point_id, payload),
         }
     }
 
@@ -60,6 +82,7 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.payload(point_id),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.payload(point_id),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.payload(point_id),
+            PayloadStorageEnum::MmapPayloadStorage(s) => s.payload(point_id),
         }
     }
 
@@ -72,6 +95,7 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.delete(point_id, key),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.delete(point_id, key),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.delete(point_id, key),
+            PayloadStorageEnum::MmapPayloadStorage(s) => s.delete(point_id, key),
         }
     }
 
@@ -80,6 +104,7 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.drop(point_id),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.drop(point_id),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.drop(point_id),
+            PayloadStorageEnum::MmapPayloadStorage(s) => s.drop(point_id),
         }
     }
 
@@ -88,6 +113,7 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.wipe(),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.wipe(),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.wipe(),
+            PayloadStorageEnum::MmapPayloadStorage(s) => s.wipe(),
         }
     }
 
@@ -96,6 +122,7 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.flusher(),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.flusher(),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.flusher(),
+            PayloadStorageEnum::MmapPayloadStorage(s) => s.flusher(),
         }
     }
 }
@@ -106,14 +133,14 @@ mod tests {
 
     use super::*;
     use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
-    use crate::types::Payload;
+    use crate::types::{Payload, PayloadCompression};
 
     #[test]
     fn test_storage() {
         let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
         let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
 
-        let mut storage: PayloadStorageEnum = SimplePayloadStorage::open(db).unwrap().into();
+        let mut storage: PayloadStorageEnum = SimplePayloadStorage::open(db, None).unwrap().into();
         let payload: Payload = serde_json::from_str(r#"{"name": "John Doe"}"#).unwrap();
         storage.assign(100, &payload).unwrap();
         storage.wipe().unwrap();
@@ -132,7 +159,7 @@ mod tests {
 
         {
             let mut storage: PayloadStorageEnum =
-                SimplePayloadStorage::open(db.clone()).unwrap().into();
+                SimplePayloadStorage::open(db.clone(), None).unwrap().into();
             let payload: Payload = serde_json::from_str(
                 r#"{
                 "name": "John Doe",
@@ -163,7 +190,8 @@ mod tests {
         }
 
         {
-            let mut storage: PayloadStorageEnum = OnDiskPayloadStorage::open(db).unwrap().into();
+            let mut storage: PayloadStorageEnum =
+                OnDiskPayloadStorage::open(db, None).unwrap().into();
 
             let res = storage.payload(100).unwrap();
 
@@ -189,4 +217,36 @@ mod tests {
             eprintln!("res = {res:#?}");
         }
     }
+
+    #[test]
+    fn test_compressed_storage() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+
+        let payload: Payload = serde_json::from_str(r#"{"name": "John Doe", "age": 52}"#).unwrap();
+        let other_payload: Payload = serde_json::from_str(r#"{"name": "Jane Doe"}"#).unwrap();
+
+        {
+            let mut storage: PayloadStorageEnum =
+                SimplePayloadStorage::open(db.clone(), Some(PayloadCompression::Zstd))
+                    .unwrap()
+                    .into();
+            storage.assign_all(100, &payload).unwrap();
+        }
+
+        {
+            // Records with and without compression are readable from the same storage
+            let mut storage: PayloadStorageEnum =
+                OnDiskPayloadStorage::open(db.clone(), None).unwrap().into();
+            assert_eq!(storage.payload(100).unwrap(), payload);
+            storage.assign_all(101, &other_payload).unwrap();
+        }
+
+        let storage: PayloadStorageEnum =
+            SimplePayloadStorage::open(db, Some(PayloadCompression::Zstd))
+                .unwrap()
+                .into();
+        assert_eq!(storage.payload(100).unwrap(), payload);
+        assert_eq!(storage.payload(101).unwrap(), other_payload);
+    }
 }
@@ -6,16 +6,17 @@ use std::sync::Arc;
 use atomic_refcell::AtomicRefCell;
 use common::types::PointOffsetType;
 
-use crate::common::utils::IndexesMap;
+use crate::common::utils::{IndexesMap, VectorStoragesMap};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::FieldIndex;
 use crate::payload_storage::condition_checker::ValueChecker;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::ConditionChecker;
 use crate::types::{
-    Condition, FieldCondition, Filter, IsEmptyCondition, IsNullCondition, OwnedPayloadRef, Payload,
-    PayloadContainer, PayloadKeyType,
+    Condition, FieldCondition, Filter, IsEmptyCondition, IsNullCondition, MinShould,
+    OwnedPayloadRef, Payload, PayloadContainer, PayloadKeyType,
 };
+use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 
 fn check_condition<F>(checker: &F, condition: &Condition) -> bool
 where
@@ -32,6 +33,7 @@ where
     F: Fn(&Condition) -> bool,
 {
     check_should(checker, &filter.should)
+        && check_min_should(checker, &filter.min_should)
         && check_must(checker, &filter.must)
         && check_must_not(checker, &filter.must_not)
 }
@@ -47,6 +49,27 @@ where
     }
 }
 
+fn check_min_should<F>(checker: &F, min_should: &Option<MinShould>) -> bool
+where
+    F: Fn(&Condition) -> bool,
+{
+    let check = |x| check_condition(checker, x);
+    match min_should {
+        None => true,
+        Some(MinShould {
+            conditions,
+            min_count,
+        }) => {
+            conditions
+                .iter()
+                .filter(|x| check(x))
+                .take(*min_count)
+                .count()
+                == *min_count
+        }
+    }
+}
+
 fn check_must<F>(checker: &F, must: &Option<Vec<Condition>>) -> bool
 where
     F: Fn(&Condition) -> bool,
@@ -90,6 +113,7 @@ where
 pub fn check_payload<'a, R>(
     get_payload: Box<dyn Fn() -> OwnedPayloadRef<'a> + 'a>,
     id_tracker: Option<&IdTrackerSS>,
+    vector_storages: &VectorStoragesMap,
     query: &Filter,
     point_id: PointOffsetType,
     field_indexes: &HashMap<PayloadKeyType, R>,
@@ -106,23 +130,33 @@ where
         Condition::HasId(has_id) => id_tracker
             .and_then(|id_tracker| id_tracker.external_id(point_id))
             .map_or(false, |id| has_id.has_id.contains(&id)),
+        Condition::HasIdRange(has_id_range) => id_tracker
+            .and_then(|id_tracker| id_tracker.external_id(point_id))
+            .map_or(false, |id| has_id_range.has_id_range.contains(id)),
+        Condition::HasVector(has_vector) => vector_storages
+            .get(&has_vector.has_vector)
+            .map_or(false, |vector_storage| {
+                check_has_vector(&vector_storage.borrow(), point_id)
+            }),
         Condition::Nested(nested) => {
             let nested_path = nested.array_key();
             let nested_indexes = select_nested_indexes(&nested_path, field_indexes);
-            get_payload()
-                .get_value(&nested_path)
-                .values()
+            let payload = get_payload();
+            let values = payload.get_value(&nested_path).values();
+            let matches = values
                 .iter()
                 .filter_map(|value| value.as_object())
-                .any(|object| {
+                .map(|object| {
                     check_payload(
                         Box::new(|| OwnedPayloadRef::from(object)),
                         None,
+                        &VectorStoragesMap::new(),
                         &nested.nested.filter,
                         point_id,
                         &nested_indexes,
                     )
-                })
+                });
+            nested.check_matches(matches)
         }
         Condition::Filter(_) => unreachable!(),
     };
@@ -130,6 +164,12 @@ where
     check_filter(&checker, query)
 }
 
+/// Check if the point has a vector in the storage, which is not deleted
+pub fn check_has_vector(vector_storage: &VectorStorageEnum, point_id: PointOffsetType) -> bool {
+    (point_id as usize) < vector_storage.total_vector_count()
+        && !vector_storage.is_deleted_vector(point_id)
+}
+
 pub fn check_is_empty_condition(
     is_empty: &IsEmptyCondition,
     payload: &impl PayloadContainer,
@@ -187,6 +227,7 @@ where
 pub struct SimpleConditionChecker {
     payload_storage: Arc<AtomicRefCell<PayloadStorageEnum>>,
     id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+    vector_storages: VectorStoragesMap,
     empty_payload: Payload,
 }
 
@@ -194,10 +235,12 @@ impl SimpleConditionChecker {
     pub fn new(
         payload_storage: Arc<AtomicRefCell<PayloadStorageEnum>>,
         id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+        vector_storages: VectorStoragesMap,
     ) -> Self {
         SimpleConditionChecker {
             payload_storage,
             id_tracker,
+            vector_storages,
             empty_payload: Default::default(),
         }
     }
@@ -238,6 +281,11 @@ impl ConditionChecker for SimpleConditionChecker {
                                 .unwrap_or_else(|err| panic!("Payload storage is corrupted: {err}"))
                                 .map(|x| x.into())
                         }
+                        // Same as for `OnDiskPayloadStorage`, see above
+                        PayloadStorageEnum::MmapPayloadStorage(s) => s
+                            .read_payload(point_id)
+                            .unwrap_or_else(|err| panic!("Payload storage is corrupted: {err}"))
+                            .map(|x| x.into()),
                     };
 
                     payload_ref_cell
@@ -246,6 +294,7 @@ impl ConditionChecker for SimpleConditionChecker {
                 payload_ref_cell.borrow().as_ref().cloned().unwrap()
             }),
             Some(id_tracker.deref()),
+            &self.vector_storages,
             query,
             point_id,
             &IndexesMap::new(),
@@ -267,8 +316,10 @@ mod tests {
     use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
     use crate::payload_storage::PayloadStorage;
     use crate::types::{
-        FieldCondition, GeoBoundingBox, GeoPoint, PayloadField, Range, ValuesCount,
+        Distance, FieldCondition, GeoBoundingBox, GeoPoint, IdRange, PayloadField, Range,
+        ValuesCount,
     };
+    use crate::vector_storage::simple_dense_vector_storage::open_simple_vector_storage;
 
     #[test]
     fn test_condition_checker() {
@@ -293,7 +344,9 @@ mod tests {
         .into();
 
         let mut payload_storage: PayloadStorageEnum =
-            SimplePayloadStorage::open(db.clone()).unwrap().into();
+            SimplePayloadStorage::open(db.clone(), None).unwrap().into();
+        let vector_storage =
+            open_simple_vector_storage(db.clone(), DB_VECTOR_CF, 2, Distance::Dot).unwrap();
         let mut id_tracker = SimpleIdTracker::open(db).unwrap();
 
         id_tracker.set_link(0.into(), 0).unwrap();
@@ -301,10 +354,21 @@ mod tests {
         id_tracker.set_link(2.into(), 2).unwrap();
         id_tracker.set_link(10.into(), 10).unwrap();
         payload_storage.assign_all(0, &payload).unwrap();
+        {
+            let mut vector_storage = vector_storage.borrow_mut();
+            vector_storage
+                .insert_vector(0, [1.0, 0.0].as_slice().into())
+                .unwrap();
+            vector_storage
+                .insert_vector(1, [0.0, 1.0].as_slice().into())
+                .unwrap();
+            vector_storage.delete_vector(1).unwrap();
+        }
 
         let payload_checker = SimpleConditionChecker::new(
             Arc::new(AtomicRefCell::new(payload_storage)),
             Arc::new(AtomicRefCell::new(id_tracker)),
+            VectorStoragesMap::from([("image".to_string(), vector_storage)]),
         );
 
         let is_empty_condition = Filter::new_must(Condition::IsEmpty(IsEmptyCondition {
@@ -447,6 +511,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: Some(vec![match_red.clone()]),
             must_not: None,
         };
@@ -454,6 +519,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: Some(vec![match_blue.clone()]),
             must_not: None,
         };
@@ -461,6 +527,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![match_blue.clone()]),
         };
@@ -468,6 +535,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![match_red.clone()]),
         };
@@ -475,13 +543,37 @@ mod tests {
 
         let query = Filter {
             should: Some(vec![match_red.clone(), match_blue.clone()]),
+            min_should: None,
             must: Some(vec![with_delivery.clone(), in_berlin.clone()]),
             must_not: None,
         };
         assert!(payload_checker.check(0, &query));
 
+        let query = Filter {
+            should: None,
+            min_should: Some(MinShould {
+                conditions: vec![match_red.clone(), match_blue.clone(), in_berlin.clone()],
+                min_count: 2,
+            }),
+            must: None,
+            must_not: None,
+        };
+        assert!(payload_checker.check(0, &query));
+
+        let query = Filter {
+            should: None,
+            min_should: Some(MinShould {
+                conditions: vec![match_red.clone(), match_blue.clone(), in_moscow.clone()],
+                min_count: 2,
+            }),
+            must: None,
+            must_not: None,
+        };
+        assert!(!payload_checker.check(0, &query));
+
         let query = Filter {
             should: Some(vec![match_red.clone(), match_blue.clone()]),
+            min_should: None,
             must: Some(vec![with_delivery, in_moscow.clone()]),
             must_not: None,
         };
@@ -491,15 +583,18 @@ mod tests {
             should: Some(vec![
                 Condition::Filter(Filter {
                     should: None,
+                    min_should: None,
                     must: Some(vec![match_red.clone(), in_moscow.clone()]),
                     must_not: None,
                 }),
                 Condition::Filter(Filter {
                     should: None,
+                    min_should: None,
                     must: Some(vec![match_blue.clone(), in_berlin.clone()]),
                     must_not: None,
                 }),
             ]),
+            min_should: None,
             must: None,
             must_not: None,
         };
@@ -509,15 +604,18 @@ mod tests {
             should: Some(vec![
                 Condition::Filter(Filter {
                     should: None,
+                    min_should: None,
                     must: Some(vec![match_blue, in_moscow]),
                     must_not: None,
                 }),
                 Condition::Filter(Filter {
                     should: None,
+                    min_should: None,
                     must: Some(vec![match_red, in_berlin]),
                     must_not: None,
                 }),
             ]),
+            min_should: None,
             must: None,
             must_not: None,
         };
@@ -525,6 +623,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![with_bad_rating]),
         };
@@ -534,6 +633,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![Condition::HasId(ids.into())]),
         };
@@ -543,6 +643,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![Condition::HasId(ids.into())]),
         };
@@ -552,9 +653,29 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: Some(vec![Condition::HasId(ids.into())]),
             must_not: None,
         };
         assert!(payload_checker.check(2, &query));
+
+        let has_id_range = Filter::new_must(Condition::HasIdRange(
+            IdRange {
+                gte: Some(1.into()),
+                lt: Some(10.into()),
+            }
+            .into(),
+        ));
+        assert!(!payload_checker.check(0, &has_id_range));
+        assert!(payload_checker.check(2, &has_id_range));
+        assert!(!payload_checker.check(10, &has_id_range));
+
+        let has_vector = Filter::new_must(Condition::HasVector("image".to_string().into()));
+        assert!(payload_checker.check(0, &has_vector));
+        assert!(!payload_checker.check(1, &has_vector)); // vector is deleted
+        assert!(!payload_checker.check(2, &has_vector)); // vector is not inserted
+
+        let has_unknown_vector = Filter::new_must(Condition::HasVector("text".to_string().into()));
+        assert!(!payload_checker.check(0, &has_unknown_vector));
     }
 }
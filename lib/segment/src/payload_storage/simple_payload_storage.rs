@@ -7,17 +7,22 @@ use rocksdb::DB;
 
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_PAYLOAD_CF};
-use crate::types::Payload;
+use crate::payload_storage::payload_record::{deserialize_payload, serialize_payload};
+use crate::types::{Payload, PayloadCompression};
 
 /// In-memory implementation of `PayloadStorage`.
 /// Persists all changes to disk using `store`, but only uses this storage during the initial load
 pub struct SimplePayloadStorage {
     pub(crate) payload: HashMap<PointOffsetType, Payload>,
     pub(crate) db_wrapper: DatabaseColumnWrapper,
+    compression: Option<PayloadCompression>,
 }
 
 impl SimplePayloadStorage {
-    pub fn open(database: Arc<RwLock<DB>>) -> OperationResult<Self> {
+    pub fn open(
+        database: Arc<RwLock<DB>>,
+        compression: Option<PayloadCompression>,
+    ) -> OperationResult<Self> {
         let mut payload_map: HashMap<PointOffsetType, Payload> = Default::default();
 
         let db_wrapper = DatabaseColumnWrapper::new(database, DB_PAYLOAD_CF);
@@ -25,7 +30,7 @@ impl SimplePayloadStorage {
         for (key, val) in db_wrapper.lock_db().iter()? {
             let point_id: PointOffsetType = serde_cbor::from_slice(&key)
                 .map_err(|_| OperationError::service_error("cannot deserialize point id"))?;
-            let payload: Payload = serde_cbor::from_slice(&val)
+            let payload: Payload = deserialize_payload(&val)
                 .map_err(|_| OperationError::service_error("cannot deserialize payload"))?;
             payload_map.insert(point_id, payload);
         }
@@ -33,6 +38,7 @@ impl SimplePayloadStorage {
         Ok(SimplePayloadStorage {
             payload: payload_map,
             db_wrapper,
+            compression,
         })
     }
 
@@ -43,7 +49,7 @@ impl SimplePayloadStorage {
                 .remove(serde_cbor::to_vec(&point_id).unwrap()),
             Some(payload) => self.db_wrapper.put(
                 serde_cbor::to_vec(&point_id).unwrap(),
-                serde_cbor::to_vec(payload).unwrap(),
+                serialize_payload(payload, self.compression)?,
             ),
         }
     }
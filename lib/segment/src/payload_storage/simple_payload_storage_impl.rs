@@ -75,7 +75,7 @@ mod tests {
         let dir = Builder::new().prefix("db_dir").tempdir().unwrap();
         let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
 
-        let mut storage = SimplePayloadStorage::open(db).unwrap();
+        let mut storage = SimplePayloadStorage::open(db, None).unwrap();
         let payload: Payload = serde_json::from_str(r#"{"name": "John Doe"}"#).unwrap();
         storage.assign(100, &payload).unwrap();
         storage.wipe().unwrap();
@@ -114,7 +114,7 @@ mod tests {
         let payload: Payload = serde_json::from_str(data).unwrap();
         let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
         let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
-        let mut storage = SimplePayloadStorage::open(db).unwrap();
+        let mut storage = SimplePayloadStorage::open(db, None).unwrap();
         storage.assign(100, &payload).unwrap();
         let pload = storage.payload(100).unwrap();
         assert_eq!(pload, payload);
@@ -2,20 +2,21 @@ use std::cmp::max;
 use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
 
 use atomic_refcell::AtomicRefCell;
 use common::types::{PointOffsetType, ScoredPointOffset};
 use io::file_operations::{atomic_save_json, read_json};
-use memory::mmap_ops;
+use memory::{madvise, mmap_ops};
 use parking_lot::{Mutex, RwLock};
 use rocksdb::DB;
 use sparse::common::sparse_vector::SparseVector;
 use tar::Builder;
 use uuid::Uuid;
 
+use crate::common::checksums::{SegmentChecksums, CHECKSUMS_FILE};
 use crate::common::operation_error::OperationError::TypeInferenceError;
 use crate::common::operation_error::{
     get_service_error, OperationError, OperationResult, SegmentFailedState,
@@ -24,11 +25,15 @@ use crate::common::version::{StorageVersion, VERSION_FILE};
 use crate::common::{
     check_named_vectors, check_query_vectors, check_stopped, check_vector, check_vector_name,
 };
+use crate::data_types::facets::FacetValue;
 use crate::data_types::named_vectors::NamedVectors;
-use crate::data_types::vectors::{QueryVector, Vector};
+use crate::data_types::order_by::{OrderBy, OrderValue};
+use crate::data_types::vectors::{MultiDenseVector, QueryVector, Vector};
 use crate::entry::entry_point::SegmentEntry;
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::CardinalityEstimation;
+use crate::index::hnsw_index::graph_layers::GraphCheckReport;
+use crate::index::query_planner::SearchPlan;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
 use crate::spaces::tools::peek_top_smallest_iterable;
@@ -88,6 +93,13 @@ pub struct Segment {
     pub error_status: Option<SegmentFailedState>,
     pub database: Arc<RwLock<DB>>,
     pub flush_thread: Mutex<Option<JoinHandle<OperationResult<SeqNumberType>>>>,
+    /// Held while checksums of segment files are verified or updated
+    pub checksums_lock: Arc<Mutex<()>>,
+    /// Stops background verification of checksums once the segment is dropped
+    pub stop_checksums_verification: Arc<AtomicBool>,
+    /// Set if verification of checksums found corrupted files.
+    /// Corruption can't be recovered by re-applying operations, so all update operations are aborted
+    pub corruption_error: Arc<Mutex<Option<OperationError>>>,
 }
 
 pub struct VectorData {
@@ -100,16 +112,47 @@ impl VectorData {
     pub fn prefault_mmap_pages(&self) -> impl Iterator<Item = mmap_ops::PrefaultMmapPages> {
         let index_task = match &*self.vector_index.borrow() {
             VectorIndexEnum::HnswMmap(index) => index.prefault_mmap_pages(),
+            VectorIndexEnum::HnswCompressed(index) => index.prefault_mmap_pages(),
+            VectorIndexEnum::Vamana(index) => index.prefault_mmap_pages(),
             _ => None,
         };
 
         let storage_task = match &*self.vector_storage.borrow() {
             VectorStorageEnum::Memmap(storage) => storage.prefault_mmap_pages(),
+            VectorStorageEnum::MemmapHalf(storage) => storage.prefault_mmap_pages(),
+            VectorStorageEnum::MemmapByte(storage) => storage.prefault_mmap_pages(),
             _ => None,
         };
 
         index_task.into_iter().chain(storage_task)
     }
+
+    fn set_hot_tier_ratio(&self, ratio: Option<f32>) {
+        match &mut *self.vector_storage.borrow_mut() {
+            VectorStorageEnum::Memmap(storage) => storage.set_hot_tier_ratio(ratio),
+            VectorStorageEnum::MemmapHalf(storage) => storage.set_hot_tier_ratio(ratio),
+            VectorStorageEnum::MemmapByte(storage) => storage.set_hot_tier_ratio(ratio),
+            _ => {}
+        }
+    }
+
+    fn prepare_hot_tier(&self) -> bool {
+        match &*self.vector_storage.borrow() {
+            VectorStorageEnum::Memmap(storage) => storage.prepare_hot_tier(),
+            VectorStorageEnum::MemmapHalf(storage) => storage.prepare_hot_tier(),
+            VectorStorageEnum::MemmapByte(storage) => storage.prepare_hot_tier(),
+            _ => false,
+        }
+    }
+
+    fn apply_hot_tier(&self) {
+        match &mut *self.vector_storage.borrow_mut() {
+            VectorStorageEnum::Memmap(storage) => storage.apply_hot_tier(),
+            VectorStorageEnum::MemmapHalf(storage) => storage.apply_hot_tier(),
+            VectorStorageEnum::MemmapByte(storage) => storage.apply_hot_tier(),
+            _ => {}
+        }
+    }
 }
 
 impl Segment {
@@ -137,15 +180,21 @@ impl Segment {
             let vector = vectors.get(vector_name);
             match vector {
                 Some(vector) => {
-                    let mut vector_storage = vector_data.vector_storage.borrow_mut();
-                    vector_storage.insert_vector(internal_id, vector)?;
+                    vector_data
+                        .vector_storage
+                        .borrow_mut()
+                        .insert_vector(internal_id, vector)?;
                     let mut vector_index = vector_data.vector_index.borrow_mut();
                     vector_index.update_vector(internal_id, vector)?;
                 }
                 None => {
                     // No vector provided, so we remove it
-                    let mut vector_storage = vector_data.vector_storage.borrow_mut();
-                    vector_storage.delete_vector(internal_id)?;
+                    vector_data
+                        .vector_storage
+                        .borrow_mut()
+                        .delete_vector(internal_id)?;
+                    let mut vector_index = vector_data.vector_index.borrow_mut();
+                    vector_index.remove_vector(internal_id)?;
                 }
             }
         }
@@ -203,22 +252,35 @@ impl Segment {
         for (vector_name, vector_data) in self.vector_data.iter_mut() {
             let vector_opt = vectors.get(vector_name);
             let mut vector_storage = vector_data.vector_storage.borrow_mut();
-            let mut vector_index = vector_data.vector_index.borrow_mut();
             match vector_opt {
                 None => {
                     let dim = vector_storage.vector_dim();
                     let vector: Vector = match *vector_storage {
                         VectorStorageEnum::DenseSimple(_)
+                        | VectorStorageEnum::DenseSimpleHalf(_)
+                        | VectorStorageEnum::DenseSimpleByte(_)
                         | VectorStorageEnum::Memmap(_)
-                        | VectorStorageEnum::AppendableMemmap(_) => vec![1.0; dim].into(),
+                        | VectorStorageEnum::MemmapHalf(_)
+                        | VectorStorageEnum::MemmapByte(_)
+                        | VectorStorageEnum::AppendableMemmap(_)
+                        | VectorStorageEnum::AppendableMemmapHalf(_)
+                        | VectorStorageEnum::AppendableMemmapByte(_) => vec![1.0; dim].into(),
                         VectorStorageEnum::SparseSimple(_) => SparseVector::default().into(),
+                        VectorStorageEnum::MultiDenseSimple(_) => {
+                            MultiDenseVector::new(vec![1.0; dim], dim).into()
+                        }
                     };
                     vector_storage.insert_vector(new_index, vector.to_vec_ref())?;
                     vector_storage.delete_vector(new_index)?;
+                    // The index reads inserted vectors from the storage
+                    drop(vector_storage);
+                    let mut vector_index = vector_data.vector_index.borrow_mut();
                     vector_index.update_vector(new_index, vector.to_vec_ref())?;
                 }
                 Some(vec) => {
                     vector_storage.insert_vector(new_index, vec)?;
+                    drop(vector_storage);
+                    let mut vector_index = vector_data.vector_index.borrow_mut();
                     vector_index.update_vector(new_index, vec)?;
                 }
             }
@@ -252,6 +314,10 @@ impl Segment {
     where
         F: FnOnce(&mut Segment) -> OperationResult<(bool, Option<PointOffsetType>)>,
     {
+        if let Some(error) = self.corruption_error.lock().clone() {
+            return Err(error);
+        }
+
         if let Some(SegmentFailedState {
             version: failed_version,
             point_id: _failed_point_id,
@@ -338,6 +404,9 @@ impl Segment {
             }
         }
 
+        // Points are about to change, so cached matches of filters become outdated
+        self.payload_index.borrow().clear_filter_cache();
+
         let (applied, point_id) = operation(self)?;
 
         self.version = Some(max(op_num, self.version.unwrap_or(0)));
@@ -384,6 +453,20 @@ impl Segment {
         })
     }
 
+    /// Files which are not modified after the segment is built, except by explicit repairs.
+    /// Only meaningful for non-appendable segments.
+    fn immutable_files(&self) -> Vec<PathBuf> {
+        let mut files = vec![];
+        for vector_data in self.vector_data.values() {
+            files.extend(vector_data.vector_index.borrow().files());
+            files.extend(vector_data.vector_storage.borrow().immutable_files());
+            if let Some(quantized_vectors) = vector_data.quantized_vectors.borrow().as_ref() {
+                files.extend(quantized_vectors.files());
+            }
+        }
+        files
+    }
+
     /// Retrieve vector by internal ID
     ///
     /// Returns None if the vector does not exists or deleted
@@ -465,7 +548,11 @@ impl Segment {
 
     pub fn restore_snapshot(snapshot_path: &Path, segment_id: &str) -> OperationResult<()> {
         let segment_path = snapshot_path.parent().unwrap().join(segment_id);
+        Self::restore_snapshot_into(snapshot_path, &segment_path)
+    }
 
+    /// Unpack segment snapshot archive into `segment_path` and restore segment data from it
+    pub fn restore_snapshot_into(snapshot_path: &Path, segment_path: &Path) -> OperationResult<()> {
         let archive_file = File::open(snapshot_path).map_err(|err| {
             OperationError::service_error(format!(
                 "failed to open segment snapshot archive {snapshot_path:?}: {err}"
@@ -473,7 +560,7 @@ impl Segment {
         })?;
 
         tar::Archive::new(archive_file)
-            .unpack(&segment_path)
+            .unpack(segment_path)
             .map_err(|err| {
                 OperationError::service_error(format!(
                     "failed to unpack segment snapshot archive {snapshot_path:?}: {err}"
@@ -486,12 +573,12 @@ impl Segment {
             let db_backup_path = snapshot_path.join(DB_BACKUP_PATH);
             let payload_index_db_backup = snapshot_path.join(PAYLOAD_DB_BACKUP_PATH);
 
-            crate::rocksdb_backup::restore(&db_backup_path, &segment_path)?;
+            crate::rocksdb_backup::restore(&db_backup_path, segment_path)?;
 
             if payload_index_db_backup.is_dir() {
                 StructPayloadIndex::restore_database_snapshot(
                     &payload_index_db_backup,
-                    &segment_path,
+                    segment_path,
                 )?;
             }
 
@@ -504,7 +591,7 @@ impl Segment {
                 )));
             }
 
-            utils::fs::move_all(&files_path, &segment_path)?;
+            utils::fs::move_all(&files_path, segment_path)?;
 
             fs::remove_dir_all(&snapshot_path).map_err(|err| {
                 OperationError::service_error(format!(
@@ -707,6 +794,88 @@ impl Segment {
         Ok(())
     }
 
+    /// Check HNSW graph of the vector and re-link points, which are not reachable from its
+    /// entry points.
+    ///
+    /// Useful after crashes or partial disk corruption. Index files are rewritten, if any point
+    /// is re-linked. Returns the result of the check made before the repair, `None` if the
+    /// vector is not indexed with a graph.
+    pub fn repair_vector_index(
+        &mut self,
+        vector_name: &str,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Option<GraphCheckReport>> {
+        check_vector_name(vector_name, &self.segment_config)?;
+        let report = self.vector_data[vector_name]
+            .vector_index
+            .borrow_mut()
+            .repair_graph(stopped)?;
+        if let Some(report) = &report {
+            if !report.unreachable_points.is_empty() {
+                log::warn!(
+                    "HNSW graph of vector {vector_name} in segment {} had {} unreachable points, relinked them",
+                    self.current_path.display(),
+                    report.unreachable_points.len(),
+                );
+                // Graph files are rewritten by the repair
+                self.update_checksums()?;
+            }
+        }
+        Ok(report)
+    }
+
+    /// Verify checksums of segment files, see [`SegmentChecksums`]
+    ///
+    /// On mismatch the segment is marked as corrupted, see [`Segment::corruption_error`]
+    pub fn verify_checksums(&self) -> OperationResult<()> {
+        let _lock = self.checksums_lock.lock();
+        let result =
+            SegmentChecksums::verify(&self.current_path, &self.stop_checksums_verification);
+        if let Err(err @ OperationError::DataCorruption { .. }) = &result {
+            *self.corruption_error.lock() = Some(err.clone());
+        }
+        result
+    }
+
+    /// Verify checksums of segment files in background, see [`Segment::verify_checksums`]
+    ///
+    /// Reading all files takes a while for large segments, so it should not block segment load.
+    pub fn verify_checksums_in_background(&self) {
+        let path = self.current_path.clone();
+        let lock = self.checksums_lock.clone();
+        let stopped = self.stop_checksums_verification.clone();
+        let corruption_error = self.corruption_error.clone();
+
+        let _ = thread::Builder::new()
+            .name(format!("segment-{:?}-verify-checksums", self.current_path))
+            .spawn(move || {
+                let _lock = lock.lock();
+                match SegmentChecksums::verify(&path, &stopped) {
+                    Ok(()) | Err(OperationError::Cancelled { .. }) => {}
+                    Err(err @ OperationError::DataCorruption { .. }) => {
+                        log::error!("Segment {} is corrupted: {err}", path.display());
+                        *corruption_error.lock() = Some(err);
+                    }
+                    Err(err) => {
+                        log::error!(
+                            "Failed to verify checksums of segment {}: {err}",
+                            path.display()
+                        )
+                    }
+                }
+            });
+    }
+
+    /// Recompute saved checksums after files of the segment were rewritten in place
+    fn update_checksums(&self) -> OperationResult<()> {
+        let _lock = self.checksums_lock.lock();
+        if !SegmentChecksums::check_exists(&self.current_path) {
+            return Ok(());
+        }
+        SegmentChecksums::compute(&self.current_path, &self.immutable_files())?
+            .save(&self.current_path)
+    }
+
     pub fn available_vector_count(&self, vector_name: &str) -> OperationResult<usize> {
         check_vector_name(vector_name, &self.segment_config)?;
         Ok(self.vector_data[vector_name]
@@ -719,12 +888,31 @@ impl Segment {
         self.id_tracker.borrow().total_point_count()
     }
 
-    pub fn prefault_mmap_pages(&self) {
-        let tasks: Vec<_> = self
-            .vector_data
+    fn mmap_pages(&self) -> Vec<mmap_ops::PrefaultMmapPages> {
+        self.vector_data
             .values()
             .flat_map(|data| data.prefault_mmap_pages())
-            .collect();
+            .collect()
+    }
+
+    /// Memory-mapped files of the given vector, or of all vectors if `vector_name` is not specified
+    pub fn vector_mmap_pages(
+        &self,
+        vector_name: Option<&str>,
+    ) -> OperationResult<Vec<mmap_ops::PrefaultMmapPages>> {
+        match vector_name {
+            Some(vector_name) => {
+                check_vector_name(vector_name, &self.segment_config)?;
+                Ok(self.vector_data[vector_name]
+                    .prefault_mmap_pages()
+                    .collect())
+            }
+            None => Ok(self.mmap_pages()),
+        }
+    }
+
+    pub fn prefault_mmap_pages(&self) {
+        let tasks = self.mmap_pages();
 
         let _ = thread::Builder::new()
             .name(format!(
@@ -733,6 +921,55 @@ impl Segment {
             ))
             .spawn(move || tasks.iter().for_each(mmap_ops::PrefaultMmapPages::exec));
     }
+
+    /// Read memory-mapped files of the segment and lock them in RAM in background
+    pub fn lock_mmap_pages(&self) {
+        let tasks = self.mmap_pages();
+
+        let _ = thread::Builder::new()
+            .name(format!("segment-{:?}-lock-mmap-pages", self.current_path))
+            .spawn(move || tasks.iter().for_each(mmap_ops::PrefaultMmapPages::lock));
+    }
+
+    /// Keep the given fraction of the most frequently accessed vectors of memory-mapped storages
+    /// in RAM, `None` disables it
+    ///
+    /// Hot vectors are selected by [`Segment::prepare_hot_tier`] and [`Segment::apply_hot_tier`].
+    pub fn set_hot_tier_ratio(&self, ratio: Option<f32>) {
+        for vector_data in self.vector_data.values() {
+            vector_data.set_hot_tier_ratio(ratio);
+        }
+    }
+
+    /// Copy currently most frequently accessed vectors of memory-mapped storages into RAM
+    ///
+    /// Can run concurrently with searches, copied vectors are not used until
+    /// [`Segment::apply_hot_tier`] is called. Returns false if no storage has the hot tier enabled.
+    pub fn prepare_hot_tier(&self) -> bool {
+        self.vector_data
+            .values()
+            .fold(false, |prepared, vector_data| {
+                vector_data.prepare_hot_tier() || prepared
+            })
+    }
+
+    /// Start reading vectors copied by [`Segment::prepare_hot_tier`] from RAM
+    ///
+    /// Requires exclusive access to the segment, but only swaps prepared data.
+    pub fn apply_hot_tier(&mut self) {
+        for vector_data in self.vector_data.values() {
+            vector_data.apply_hot_tier();
+        }
+    }
+
+    /// Advise OS how memory-mapped files of the segment will be accessed
+    pub fn madvise_mmap_pages(&self, advice: madvise::Advice) {
+        for task in self.mmap_pages() {
+            if let Err(err) = task.madvise(advice) {
+                log::error!("Failed to advise {advice:?} for segment mmap: {err}");
+            }
+        }
+    }
 }
 
 /// This is a basic implementation of `SegmentEntry`,
@@ -807,6 +1044,20 @@ impl SegmentEntry for Segment {
         res
     }
 
+    fn search_plan(
+        &self,
+        vector_name: &str,
+        filter: Option<&Filter>,
+        params: Option<&SearchParams>,
+    ) -> OperationResult<Option<SearchPlan>> {
+        check_vector_name(vector_name, &self.segment_config)?;
+        let vector_data = &self.vector_data[vector_name];
+        Ok(vector_data
+            .vector_index
+            .borrow()
+            .search_plan(filter, params))
+    }
+
     fn upsert_point(
         &mut self,
         op_num: SeqNumberType,
@@ -815,7 +1066,7 @@ impl SegmentEntry for Segment {
     ) -> OperationResult<bool> {
         debug_assert!(self.is_appendable());
         check_named_vectors(&vectors, &self.segment_config)?;
-        vectors.preprocess(|name| self.segment_config.distance(name).unwrap());
+        vectors.preprocess(|name| self.segment_config.preprocessing_distance(name));
         let stored_internal_point = self.id_tracker.borrow().internal_id(point_id);
         self.handle_version_and_failure(op_num, stored_internal_point, |segment| {
             if let Some(existing_internal_id) = stored_internal_point {
@@ -843,6 +1094,15 @@ impl SegmentEntry for Segment {
                     segment.payload_index.borrow_mut().drop(internal_id)?;
                     segment.id_tracker.borrow_mut().drop(point_id)?;
 
+                    // Graphs of appendable segments are repaired right away, immutable ones
+                    // are healed when they are reused by the next build
+                    if segment.appendable_flag {
+                        for vector_data in segment.vector_data.values() {
+                            let mut vector_index = vector_data.vector_index.borrow_mut();
+                            vector_index.remove_vector(internal_id)?;
+                        }
+                    }
+
                     // Before, we propagated point deletions to also delete its vectors. This turns
                     // out to be problematic because this sometimes makes us loose vector data
                     // because we cannot control the order of segment flushes.
@@ -868,7 +1128,7 @@ impl SegmentEntry for Segment {
         mut vectors: NamedVectors,
     ) -> OperationResult<bool> {
         check_named_vectors(&vectors, &self.segment_config)?;
-        vectors.preprocess(|name| self.segment_config.distance(name).unwrap());
+        vectors.preprocess(|name| self.segment_config.preprocessing_distance(name));
         let internal_id = self.id_tracker.borrow().internal_id(point_id);
         match internal_id {
             None => Err(OperationError::PointIdError {
@@ -902,8 +1162,14 @@ impl SegmentEntry for Segment {
                             received_name: vector_name.to_string(),
                         },
                     )?;
-                    let mut vector_storage = vector_data.vector_storage.borrow_mut();
-                    let is_deleted = vector_storage.delete_vector(internal_id)?;
+                    let is_deleted = vector_data
+                        .vector_storage
+                        .borrow_mut()
+                        .delete_vector(internal_id)?;
+                    if is_deleted && segment.appendable_flag {
+                        let mut vector_index = vector_data.vector_index.borrow_mut();
+                        vector_index.remove_vector(internal_id)?;
+                    }
                     Ok((is_deleted, Some(internal_id)))
                 })
             }
@@ -1078,6 +1344,35 @@ impl SegmentEntry for Segment {
         }
     }
 
+    fn read_ordered_filtered<'a>(
+        &'a self,
+        limit: Option<usize>,
+        filter: Option<&'a Filter>,
+        order_by: &'a OrderBy,
+    ) -> OperationResult<Vec<(OrderValue, PointIdType)>> {
+        let payload_index = self.payload_index.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        let filter_context = filter.map(|filter| payload_index.filter_context(filter));
+
+        let mut visited = HashSet::new();
+        let points = payload_index
+            .stream_ordered(order_by)?
+            .filter(|(_, internal_id)| visited.insert(*internal_id))
+            .filter(|(_, internal_id)| {
+                filter_context
+                    .as_ref()
+                    .map_or(true, |context| context.check(*internal_id))
+            })
+            .filter_map(|(value, internal_id)| {
+                id_tracker
+                    .external_id(internal_id)
+                    .map(|external_id| (value, external_id))
+            })
+            .take(limit.unwrap_or(usize::MAX))
+            .collect();
+        Ok(points)
+    }
+
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType> {
         let id_tracker = self.id_tracker.borrow();
         let iterator = id_tracker.iter_from(from).map(|x| x.0);
@@ -1117,6 +1412,15 @@ impl SegmentEntry for Segment {
         }
     }
 
+    fn facet(
+        &self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HashMap<FacetValue, usize>> {
+        self.payload_index.borrow().facet(key, filter, is_stopped)
+    }
+
     fn segment_type(&self) -> SegmentType {
         self.segment_type
     }
@@ -1222,6 +1526,12 @@ impl SegmentEntry for Segment {
         let payload_index_flusher = self.payload_index.borrow().flusher();
         let id_tracker_versions_flusher = self.id_tracker.borrow().versions_flusher();
         let persisted_version = self.persisted_version.clone();
+        // Files of non-appendable segments are not modified after the segment is built,
+        // so their checksums only need to be saved once. Repairs update checksums themselves.
+        let checksum_files = (!self.appendable_flag
+            && !SegmentChecksums::check_exists(&current_path))
+        .then(|| self.immutable_files());
+        let checksums_lock = self.checksums_lock.clone();
 
         // Flush order is important:
         //
@@ -1295,6 +1605,16 @@ impl SegmentEntry for Segment {
             Self::save_state(&state, &current_path).map_err(|err| {
                 OperationError::service_error(format!("Failed to flush segment state: {err}"))
             })?;
+            if let Some(checksum_files) = checksum_files {
+                let _lock = checksums_lock.lock();
+                SegmentChecksums::compute(&current_path, &checksum_files)
+                    .and_then(|checksums| checksums.save(&current_path))
+                    .map_err(|err| {
+                        OperationError::service_error(format!(
+                            "Failed to save segment checksums: {err}"
+                        ))
+                    })?;
+            }
             *persisted_version.lock() = state.version;
 
             debug_assert!(state.version.is_some());
@@ -1374,6 +1694,13 @@ impl SegmentEntry for Segment {
     }
 
     fn check_error(&self) -> Option<SegmentFailedState> {
+        if let Some(error) = self.corruption_error.lock().clone() {
+            return Some(SegmentFailedState {
+                version: self.version(),
+                point_id: None,
+                error,
+            });
+        }
         self.error_status.clone()
     }
 
@@ -1398,6 +1725,14 @@ impl SegmentEntry for Segment {
             .vector_dim())
     }
 
+    fn available_vectors_size_in_bytes(&self, vector_name: &str) -> OperationResult<usize> {
+        check_vector_name(vector_name, &self.segment_config)?;
+        Ok(self.vector_data[vector_name]
+            .vector_storage
+            .borrow()
+            .available_size_in_bytes())
+    }
+
     fn vector_dims(&self) -> HashMap<String, usize> {
         self.vector_data
             .iter()
@@ -1519,6 +1854,11 @@ impl SegmentEntry for Segment {
             &files.join(SEGMENT_STATE_FILE),
         )?;
 
+        let checksums_path = SegmentChecksums::get_path(&self.current_path);
+        if checksums_path.exists() {
+            utils::tar::append_file(&mut builder, &checksums_path, &files.join(CHECKSUMS_FILE))?;
+        }
+
         utils::tar::append_file(
             &mut builder,
             &self.current_path.join(VERSION_FILE),
@@ -1565,6 +1905,10 @@ impl SegmentEntry for Segment {
 impl Drop for Segment {
     fn drop(&mut self) {
         let _lock = self.lock_flushing();
+        // Wait for verification of checksums, files may be removed right after the drop
+        self.stop_checksums_verification
+            .store(true, Ordering::Relaxed);
+        let _checksums_lock = self.checksums_lock.lock();
     }
 }
 
@@ -1576,7 +1920,10 @@ mod tests {
     use crate::common::operation_error::OperationError::PointIdError;
     use crate::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
     use crate::segment_constructor::{build_segment, load_segment};
-    use crate::types::{Distance, Indexes, SegmentConfig, VectorDataConfig, VectorStorageType};
+    use crate::types::{
+        Distance, Indexes, SegmentConfig, VectorDataConfig, VectorStorageDatatype,
+        VectorStorageType,
+    };
 
     // no longer valid since users are now allowed to store arbitrary json objects.
     // TODO(gvelo): add tests for invalid payload types on indexed fields.
@@ -1625,10 +1972,15 @@ mod tests {
                     storage_type: VectorStorageType::Memory,
                     index: Indexes::Plain {},
                     quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
+                    keep_original: None,
+                    dimension_weights: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -1698,10 +2050,15 @@ mod tests {
                     storage_type: VectorStorageType::Memory,
                     index: Indexes::Plain {},
                     quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
+                    keep_original: None,
+                    dimension_weights: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
 
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
@@ -1790,10 +2147,15 @@ mod tests {
                     storage_type: VectorStorageType::Memory,
                     index: Indexes::Plain {},
                     quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
+                    keep_original: None,
+                    dimension_weights: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
 
         let mut segment = build_segment(segment_base_dir.path(), &config, true).unwrap();
@@ -1882,10 +2244,15 @@ mod tests {
                     storage_type: VectorStorageType::Memory,
                     index: Indexes::Plain {},
                     quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
+                    keep_original: None,
+                    dimension_weights: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
 
         let mut segment = build_segment(segment_base_dir.path(), &config, true).unwrap();
@@ -1914,10 +2281,15 @@ mod tests {
                     storage_type: VectorStorageType::Memory,
                     index: Indexes::Plain {},
                     quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
+                    keep_original: None,
+                    dimension_weights: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -2009,10 +2381,15 @@ mod tests {
                     storage_type: VectorStorageType::Memory,
                     index: Indexes::Plain {},
                     quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
+                    keep_original: None,
+                    dimension_weights: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -2048,6 +2425,48 @@ mod tests {
         // assert_eq!(segment_info.num_vectors, 1);
     }
 
+    #[test]
+    fn test_available_vectors_size_in_bytes() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let dim = 4;
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance: Distance::Dot,
+                    storage_type: VectorStorageType::Memory,
+                    index: Indexes::Plain {},
+                    quantization_config: None,
+                    multivector_config: None,
+                    datatype: Some(VectorStorageDatatype::Float16),
+                    keep_original: None,
+                    dimension_weights: None,
+                },
+            )]),
+            sparse_vector_data: Default::default(),
+            payload_storage_type: Default::default(),
+            payload_compression: None,
+        };
+        let mut segment = build_segment(dir.path(), &config, true).unwrap();
+
+        for point_id in 0..3 {
+            segment
+                .upsert_point(
+                    100 + point_id,
+                    point_id.into(),
+                    only_default_vector(&[0.5; 4]),
+                )
+                .unwrap();
+        }
+
+        // Vectors are counted with the element size of the storage, 2 bytes for float16
+        let size = segment
+            .available_vectors_size_in_bytes(DEFAULT_VECTOR_NAME)
+            .unwrap();
+        assert_eq!(size, 3 * dim * 2);
+    }
+
     #[test]
     fn test_point_vector_count_multivec() {
         let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
@@ -2062,6 +2481,10 @@ mod tests {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
+                        keep_original: None,
+                        dimension_weights: None,
                     },
                 ),
                 (
@@ -2072,11 +2495,16 @@ mod tests {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
+                        keep_original: None,
+                        dimension_weights: None,
                     },
                 ),
             ]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -2168,6 +2596,10 @@ mod tests {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
+                        keep_original: None,
+                        dimension_weights: None,
                     },
                 ),
                 (
@@ -2178,11 +2610,16 @@ mod tests {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
+                        keep_original: None,
+                        dimension_weights: None,
                     },
                 ),
             ]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -2311,6 +2748,10 @@ mod tests {
                 .err()
                 .unwrap();
             segment.available_vector_count(wrong_name).err().unwrap();
+            segment
+                .available_vectors_size_in_bytes(wrong_name)
+                .err()
+                .unwrap();
             segment
                 .vector_by_offset(wrong_name, internal_id)
                 .err()
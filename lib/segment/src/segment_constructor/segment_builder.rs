@@ -4,14 +4,17 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 
 use super::get_vector_storage_path;
+use crate::common::build_progress::BuildProgress;
 use crate::common::error_logging::LogError;
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::entry::entry_point::SegmentEntry;
+use crate::index::hnsw_index::graph_layers::REMOVED_POINT;
+use crate::index::hnsw_index::hnsw::PreviousGraph;
 use crate::index::hnsw_index::max_rayon_threads;
 use crate::index::{PayloadIndex, VectorIndex};
 use crate::segment::Segment;
 use crate::segment_constructor::{build_segment, load_segment};
-use crate::types::{Indexes, PayloadFieldSchema, PayloadKeyType, SegmentConfig};
+use crate::types::{Distance, Indexes, PayloadFieldSchema, PayloadKeyType, SegmentConfig};
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::VectorStorage;
 
@@ -21,6 +24,12 @@ pub struct SegmentBuilder {
     pub destination_path: PathBuf,
     pub temp_path: PathBuf,
     pub indexed_fields: HashMap<PayloadKeyType, PayloadFieldSchema>,
+    /// Progress of building the vector index, which is currently being built
+    pub progress: BuildProgress,
+    /// Directory to checkpoint vector index builds in, it must survive restarts to be useful
+    pub build_checkpoint_dir: Option<PathBuf>,
+    /// Largest HNSW graph of the source segments per vector name, renumbered for the new segment
+    previous_graphs: HashMap<String, PreviousGraph>,
 }
 
 impl SegmentBuilder {
@@ -39,6 +48,9 @@ impl SegmentBuilder {
             destination_path,
             temp_path,
             indexed_fields: Default::default(),
+            progress: Default::default(),
+            build_checkpoint_dir: None,
+            previous_graphs: Default::default(),
         })
     }
 
@@ -118,6 +130,7 @@ impl SegmentBuilder {
             }
         }
 
+        let mut old_to_new = vec![];
         if let Some(new_internal_range) = new_internal_range {
             let internal_id_iter = new_internal_range.zip(other_id_tracker.iter_ids());
 
@@ -135,6 +148,11 @@ impl SegmentBuilder {
                     };
                 let other_version = other_id_tracker.internal_version(old_internal_id).unwrap();
 
+                if old_to_new.len() <= old_internal_id as usize {
+                    old_to_new.resize(old_internal_id as usize + 1, REMOVED_POINT);
+                }
+                old_to_new[old_internal_id as usize] = new_internal_id;
+
                 match id_tracker.internal_id(external_id) {
                     None => {
                         // New point, just insert
@@ -174,6 +192,30 @@ impl SegmentBuilder {
             }
         }
 
+        // Keep the largest graph of the source segments, so that it is extended instead of rebuilt
+        for (vector_name, vector_data) in &self_segment.vector_data {
+            if !vector_data.vector_index.borrow().accepts_previous_graph() {
+                continue;
+            }
+            let Some(other_vector_data) = other.vector_data.get(vector_name) else {
+                continue;
+            };
+            let Some(previous_graph) = other_vector_data
+                .vector_index
+                .borrow()
+                .previous_graph(&old_to_new, stopped)?
+            else {
+                continue;
+            };
+            let is_largest = self.previous_graphs.get(vector_name).map_or(true, |graph| {
+                graph.num_linked() < previous_graph.num_linked()
+            });
+            if is_largest {
+                self.previous_graphs
+                    .insert(vector_name.clone(), previous_graph);
+            }
+        }
+
         for (field, payload_schema) in other.payload_index.borrow().indexed_fields() {
             self.indexed_fields.insert(field, payload_schema);
         }
@@ -194,8 +236,15 @@ impl SegmentBuilder {
 
             Self::update_quantization(&mut segment, stopped)?;
 
-            for vector_data in segment.vector_data.values_mut() {
-                vector_data.vector_index.borrow_mut().build_index(stopped)?;
+            for (vector_name, vector_data) in segment.vector_data.iter_mut() {
+                let mut vector_index = vector_data.vector_index.borrow_mut();
+                if let Some(previous_graph) = self.previous_graphs.remove(vector_name) {
+                    vector_index.set_previous_graph(previous_graph);
+                }
+                if let Some(dir) = &self.build_checkpoint_dir {
+                    vector_index.set_build_checkpoint_dir(dir.clone());
+                }
+                vector_index.build_index_with_progress(stopped, &self.progress)?;
             }
 
             segment.flush(true)?;
@@ -221,6 +270,14 @@ impl SegmentBuilder {
 
         for (vector_name, vector_data) in &mut segment.vector_data {
             let max_threads = if let Some(config) = config.vector_data.get(vector_name) {
+                // binary vectors compared by Hamming distance are already compact
+                if config.distance == Distance::Hamming {
+                    continue;
+                }
+                // quantized scorers can't apply per-dimension weights
+                if config.dimension_weights.is_some() {
+                    continue;
+                }
                 match &config.index {
                     Indexes::Hnsw(hnsw) => max_rayon_threads(hnsw.max_indexing_threads),
                     _ => 1,
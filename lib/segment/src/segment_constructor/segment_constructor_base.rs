@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
@@ -17,28 +18,42 @@ use crate::common::version::StorageVersion;
 use crate::data_types::vectors::DEFAULT_VECTOR_NAME;
 use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
 use crate::id_tracker::IdTracker;
-use crate::index::hnsw_index::graph_links::{GraphLinksMmap, GraphLinksRam};
+use crate::index::hnsw_index::graph_links::{GraphLinksCompressed, GraphLinksMmap, GraphLinksRam};
 use crate::index::hnsw_index::hnsw::HNSWIndex;
+use crate::index::ivf_index::ivf::IvfIndex;
 use crate::index::plain_payload_index::PlainIndex;
 use crate::index::sparse_index::sparse_index_config::SparseIndexType;
 use crate::index::sparse_index::sparse_vector_index::SparseVectorIndex;
 use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::index::vamana_index::vamana::VamanaIndex;
 use crate::index::VectorIndexEnum;
+use crate::payload_storage::mmap_payload_storage::MmapPayloadStorage;
 use crate::payload_storage::on_disk_payload_storage::OnDiskPayloadStorage;
 use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
 use crate::segment::{Segment, SegmentVersion, VectorData, SEGMENT_STATE_FILE};
 use crate::types::{
     Distance, Indexes, PayloadStorageType, SegmentConfig, SegmentState, SegmentType, SeqNumberType,
-    VectorStorageType,
+    VectorStorageDatatype, VectorStorageType,
+};
+use crate::vector_storage::appendable_mmap_vector_storage::{
+    open_appendable_memmap_byte_vector_storage, open_appendable_memmap_half_vector_storage,
+    open_appendable_memmap_vector_storage, open_appendable_memmap_vector_storage_with_norms,
+};
+use crate::vector_storage::memmap_vector_storage::{
+    open_memmap_byte_vector_storage, open_memmap_half_vector_storage, open_memmap_vector_storage,
+    open_memmap_vector_storage_with_norms,
 };
-use crate::vector_storage::appendable_mmap_vector_storage::open_appendable_memmap_vector_storage;
-use crate::vector_storage::memmap_vector_storage::open_memmap_vector_storage;
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
-use crate::vector_storage::simple_dense_vector_storage::open_simple_vector_storage;
+use crate::vector_storage::simple_dense_vector_storage::{
+    open_simple_byte_vector_storage, open_simple_half_vector_storage, open_simple_vector_storage,
+    open_simple_vector_storage_with_norms,
+};
+use crate::vector_storage::simple_multi_dense_vector_storage::open_simple_multi_dense_vector_storage;
 use crate::vector_storage::simple_sparse_vector_storage::open_simple_sparse_vector_storage;
 use crate::vector_storage::VectorStorage;
 
 pub const PAYLOAD_INDEX_PATH: &str = "payload_index";
+pub const PAYLOAD_STORAGE_PATH: &str = "payload_storage";
 pub const VECTOR_STORAGE_PATH: &str = "vector_storage";
 pub const VECTOR_INDEX_PATH: &str = "vector_index";
 
@@ -85,8 +100,17 @@ fn create_segment(
         .map_err(|err| OperationError::service_error(format!("RocksDB open error: {err}")))?;
 
     let payload_storage = match config.payload_storage_type {
-        PayloadStorageType::InMemory => sp(SimplePayloadStorage::open(database.clone())?.into()),
-        PayloadStorageType::OnDisk => sp(OnDiskPayloadStorage::open(database.clone())?.into()),
+        PayloadStorageType::InMemory => {
+            sp(SimplePayloadStorage::open(database.clone(), config.payload_compression)?.into())
+        }
+        PayloadStorageType::OnDisk => {
+            sp(OnDiskPayloadStorage::open(database.clone(), config.payload_compression)?.into())
+        }
+        PayloadStorageType::Mmap => sp(MmapPayloadStorage::open(
+            &segment_path.join(PAYLOAD_STORAGE_PATH),
+            config.payload_compression,
+        )?
+        .into()),
     };
 
     let id_tracker = sp(SimpleIdTracker::open(database.clone())?);
@@ -103,45 +127,144 @@ fn create_segment(
         )
         .all(|v| v);
 
-    let payload_index_path = segment_path.join(PAYLOAD_INDEX_PATH);
-    let payload_index: Arc<AtomicRefCell<StructPayloadIndex>> = sp(StructPayloadIndex::open(
-        payload_storage,
-        id_tracker.clone(),
-        &payload_index_path,
-        appendable_flag,
-    )?);
-
-    let mut vector_data = HashMap::new();
+    let mut vector_storages = HashMap::new();
     for (vector_name, vector_config) in &config.vector_data {
         let vector_storage_path = get_vector_storage_path(segment_path, vector_name);
-        let vector_index_path = get_vector_index_path(segment_path, vector_name);
 
         // Select suitable vector storage type based on configuration
-        let vector_storage = match vector_config.storage_type {
-            // In memory
-            VectorStorageType::Memory => {
-                let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
-                open_simple_vector_storage(
-                    database.clone(),
-                    &db_column_name,
-                    vector_config.size,
-                    vector_config.distance,
-                )?
-            }
-            // Mmap on disk, not appendable
-            VectorStorageType::Mmap => open_memmap_vector_storage(
-                &vector_storage_path,
-                vector_config.size,
-                vector_config.distance,
-            )?,
-            // Chunked mmap on disk, appendable
-            VectorStorageType::ChunkedMmap => open_appendable_memmap_vector_storage(
-                &vector_storage_path,
+        let vector_storage = if let Some(multivector_config) = vector_config.multivector_config {
+            // Multivectors only have an in-memory storage, regardless of the storage type
+            let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
+            open_simple_multi_dense_vector_storage(
+                database.clone(),
+                &db_column_name,
                 vector_config.size,
                 vector_config.distance,
-            )?,
+                multivector_config,
+            )?
+        } else {
+            let datatype = vector_config.datatype.unwrap_or_default();
+            let keep_original = vector_config.keeps_original();
+            match (vector_config.storage_type, datatype) {
+                // Original cosine vectors with cached norms
+                (VectorStorageType::Memory, VectorStorageDatatype::Float32) if keep_original => {
+                    let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
+                    open_simple_vector_storage_with_norms(
+                        database.clone(),
+                        &db_column_name,
+                        vector_config.size,
+                    )?
+                }
+                (VectorStorageType::Mmap, VectorStorageDatatype::Float32) if keep_original => {
+                    open_memmap_vector_storage_with_norms(&vector_storage_path, vector_config.size)?
+                }
+                (VectorStorageType::ChunkedMmap, VectorStorageDatatype::Float32)
+                    if keep_original =>
+                {
+                    open_appendable_memmap_vector_storage_with_norms(
+                        &vector_storage_path,
+                        vector_config.size,
+                    )?
+                }
+                // In memory
+                (VectorStorageType::Memory, VectorStorageDatatype::Float32) => {
+                    let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
+                    open_simple_vector_storage(
+                        database.clone(),
+                        &db_column_name,
+                        vector_config.size,
+                        vector_config.distance,
+                    )?
+                }
+                (VectorStorageType::Memory, VectorStorageDatatype::Float16) => {
+                    let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
+                    open_simple_half_vector_storage(
+                        database.clone(),
+                        &db_column_name,
+                        vector_config.size,
+                        vector_config.distance,
+                    )?
+                }
+                (VectorStorageType::Memory, VectorStorageDatatype::Uint8) => {
+                    let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
+                    open_simple_byte_vector_storage(
+                        database.clone(),
+                        &db_column_name,
+                        vector_config.size,
+                        vector_config.distance,
+                    )?
+                }
+                // Mmap on disk, not appendable
+                (VectorStorageType::Mmap, VectorStorageDatatype::Float32) => {
+                    open_memmap_vector_storage(
+                        &vector_storage_path,
+                        vector_config.size,
+                        vector_config.distance,
+                    )?
+                }
+                (VectorStorageType::Mmap, VectorStorageDatatype::Float16) => {
+                    open_memmap_half_vector_storage(
+                        &vector_storage_path,
+                        vector_config.size,
+                        vector_config.distance,
+                    )?
+                }
+                (VectorStorageType::Mmap, VectorStorageDatatype::Uint8) => {
+                    open_memmap_byte_vector_storage(
+                        &vector_storage_path,
+                        vector_config.size,
+                        vector_config.distance,
+                    )?
+                }
+                // Chunked mmap on disk, appendable
+                (VectorStorageType::ChunkedMmap, VectorStorageDatatype::Float32) => {
+                    open_appendable_memmap_vector_storage(
+                        &vector_storage_path,
+                        vector_config.size,
+                        vector_config.distance,
+                    )?
+                }
+                (VectorStorageType::ChunkedMmap, VectorStorageDatatype::Float16) => {
+                    open_appendable_memmap_half_vector_storage(
+                        &vector_storage_path,
+                        vector_config.size,
+                        vector_config.distance,
+                    )?
+                }
+                (VectorStorageType::ChunkedMmap, VectorStorageDatatype::Uint8) => {
+                    open_appendable_memmap_byte_vector_storage(
+                        &vector_storage_path,
+                        vector_config.size,
+                        vector_config.distance,
+                    )?
+                }
+            }
         };
 
+        if let Some(weights) = &vector_config.dimension_weights {
+            vector_storage
+                .borrow_mut()
+                .set_dimension_weights(weights.clone())?;
+        }
+
+        // Warn when number of points between ID tracker and storage differs
+        let point_count = id_tracker.borrow().total_point_count();
+        let vector_count = vector_storage.borrow().total_vector_count();
+        if vector_count != point_count {
+            log::debug!(
+                "Mismatch of point and vector counts ({point_count} != {vector_count}, storage: {})",
+                vector_storage_path.display(),
+            );
+        }
+
+        vector_storages.insert(vector_name.to_owned(), vector_storage);
+    }
+
+    for vector_name in config.sparse_vector_data.keys() {
+        let vector_storage_path = get_vector_storage_path(segment_path, vector_name);
+        let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
+        let vector_storage = open_simple_sparse_vector_storage(database.clone(), &db_column_name)?;
+
         // Warn when number of points between ID tracker and storage differs
         let point_count = id_tracker.borrow().total_point_count();
         let vector_count = vector_storage.borrow().total_vector_count();
@@ -152,6 +275,24 @@ fn create_segment(
             );
         }
 
+        vector_storages.insert(vector_name.to_owned(), vector_storage);
+    }
+
+    let payload_index_path = segment_path.join(PAYLOAD_INDEX_PATH);
+    let payload_index: Arc<AtomicRefCell<StructPayloadIndex>> = sp(StructPayloadIndex::open(
+        payload_storage,
+        id_tracker.clone(),
+        vector_storages.clone(),
+        &payload_index_path,
+        appendable_flag,
+    )?);
+
+    let mut vector_data = HashMap::new();
+    for (vector_name, vector_config) in &config.vector_data {
+        let vector_storage_path = get_vector_storage_path(segment_path, vector_name);
+        let vector_index_path = get_vector_index_path(segment_path, vector_name);
+        let vector_storage = vector_storages[vector_name].clone();
+
         let quantized_vectors = sp(if config.quantization_config(vector_name).is_some() {
             let quantized_data_path = vector_storage_path;
             if QuantizedVectors::config_exists(&quantized_data_path) {
@@ -171,25 +312,52 @@ fn create_segment(
                 vector_storage.clone(),
                 payload_index.clone(),
             ))),
-            Indexes::Hnsw(vector_hnsw_config) => sp(if vector_hnsw_config.on_disk == Some(true) {
-                VectorIndexEnum::HnswMmap(HNSWIndex::<GraphLinksMmap>::open(
-                    &vector_index_path,
-                    id_tracker.clone(),
-                    vector_storage.clone(),
-                    quantized_vectors.clone(),
-                    payload_index.clone(),
-                    vector_hnsw_config.clone(),
-                )?)
-            } else {
-                VectorIndexEnum::HnswRam(HNSWIndex::<GraphLinksRam>::open(
+            Indexes::Hnsw(vector_hnsw_config) => {
+                sp(if vector_hnsw_config.compress_links == Some(true) {
+                    VectorIndexEnum::HnswCompressed(HNSWIndex::<GraphLinksCompressed>::open(
+                        &vector_index_path,
+                        id_tracker.clone(),
+                        vector_storage.clone(),
+                        quantized_vectors.clone(),
+                        payload_index.clone(),
+                        vector_hnsw_config.clone(),
+                    )?)
+                } else if vector_hnsw_config.on_disk == Some(true) {
+                    VectorIndexEnum::HnswMmap(HNSWIndex::<GraphLinksMmap>::open(
+                        &vector_index_path,
+                        id_tracker.clone(),
+                        vector_storage.clone(),
+                        quantized_vectors.clone(),
+                        payload_index.clone(),
+                        vector_hnsw_config.clone(),
+                    )?)
+                } else {
+                    VectorIndexEnum::HnswRam(HNSWIndex::<GraphLinksRam>::open(
+                        &vector_index_path,
+                        id_tracker.clone(),
+                        vector_storage.clone(),
+                        quantized_vectors.clone(),
+                        payload_index.clone(),
+                        vector_hnsw_config.clone(),
+                    )?)
+                })
+            }
+            Indexes::Vamana(vector_vamana_config) => {
+                sp(VectorIndexEnum::Vamana(VamanaIndex::open(
                     &vector_index_path,
                     id_tracker.clone(),
                     vector_storage.clone(),
-                    quantized_vectors.clone(),
                     payload_index.clone(),
-                    vector_hnsw_config.clone(),
-                )?)
-            }),
+                    vector_vamana_config.clone(),
+                )?))
+            }
+            Indexes::Ivf(vector_ivf_config) => sp(VectorIndexEnum::Ivf(IvfIndex::open(
+                &vector_index_path,
+                id_tracker.clone(),
+                vector_storage.clone(),
+                payload_index.clone(),
+                vector_ivf_config.clone(),
+            )?)),
         };
 
         vector_data.insert(
@@ -203,21 +371,8 @@ fn create_segment(
     }
 
     for (vector_name, sparse_vector_config) in &config.sparse_vector_data {
-        let vector_storage_path = get_vector_storage_path(segment_path, vector_name);
         let vector_index_path = get_vector_index_path(segment_path, vector_name);
-
-        let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
-        let vector_storage = open_simple_sparse_vector_storage(database.clone(), &db_column_name)?;
-
-        // Warn when number of points between ID tracker and storage differs
-        let point_count = id_tracker.borrow().total_point_count();
-        let vector_count = vector_storage.borrow().total_vector_count();
-        if vector_count != point_count {
-            log::debug!(
-                "Mismatch of point and vector counts ({point_count} != {vector_count}, storage: {})",
-                vector_storage_path.display(),
-            );
-        }
+        let vector_storage = vector_storages[vector_name].clone();
 
         let vector_index = match sparse_vector_config.index.index_type {
             SparseIndexType::Mmap => sp(VectorIndexEnum::SparseMmap(SparseVectorIndex::open(
@@ -267,6 +422,9 @@ fn create_segment(
         error_status: None,
         database,
         flush_thread: Mutex::new(None),
+        checksums_lock: Arc::new(Mutex::new(())),
+        stop_checksums_verification: Arc::new(AtomicBool::new(false)),
+        corruption_error: Arc::new(Mutex::new(None)),
     })
 }
 
@@ -29,10 +29,15 @@ pub fn build_simple_segment(
                     storage_type: VectorStorageType::Memory,
                     index: Indexes::Plain {},
                     quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
+                    keep_original: None,
+                    dimension_weights: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         },
         true,
     )
@@ -53,6 +58,10 @@ pub fn build_multivec_segment(
             storage_type: VectorStorageType::Memory,
             index: Indexes::Plain {},
             quantization_config: None,
+            multivector_config: None,
+            datatype: None,
+            keep_original: None,
+            dimension_weights: None,
         },
     );
     vectors_config.insert(
@@ -63,6 +72,10 @@ pub fn build_multivec_segment(
             storage_type: VectorStorageType::Memory,
             index: Indexes::Plain {},
             quantization_config: None,
+            multivector_config: None,
+            datatype: None,
+            keep_original: None,
+            dimension_weights: None,
         },
     );
 
@@ -72,6 +85,7 @@ pub fn build_multivec_segment(
             vector_data: vectors_config,
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         },
         true,
     )
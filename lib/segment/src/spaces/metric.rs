@@ -17,3 +17,18 @@ pub trait Metric {
     /// correct metric score for displaying
     fn postprocess(score: ScoreType) -> ScoreType;
 }
+
+/// Defines how to compare vectors, stored with element type `T`
+///
+/// Preprocessing is still done by [`Metric`] in full precision, before vectors are converted into `T`.
+pub trait ElementSimilarity<T>: Metric {
+    /// Greater the value - closer the vectors
+    fn element_similarity(v1: &[T], v2: &[T]) -> ScoreType;
+}
+
+impl<TMetric: Metric> ElementSimilarity<VectorElementType> for TMetric {
+    #[inline]
+    fn element_similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        TMetric::similarity(v1, v2)
+    }
+}
@@ -1,5 +1,7 @@
 pub mod metric;
 pub mod simple;
+pub mod simple_byte;
+pub mod simple_half;
 pub mod tools;
 
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
@@ -8,5 +10,14 @@ pub mod simple_sse;
 #[cfg(target_arch = "x86_64")]
 pub mod simple_avx;
 
+#[cfg(target_arch = "x86_64")]
+pub mod simple_half_avx;
+
+#[cfg(target_arch = "x86_64")]
+pub mod simple_byte_avx;
+
 #[cfg(target_arch = "aarch64")]
 pub mod simple_neon;
+
+#[cfg(target_arch = "aarch64")]
+pub mod simple_byte_neon;
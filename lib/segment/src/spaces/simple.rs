@@ -32,6 +32,9 @@ pub struct EuclidMetric;
 #[derive(Clone)]
 pub struct ManhattanMetric;
 
+#[derive(Clone)]
+pub struct HammingMetric;
+
 impl Metric for EuclidMetric {
     fn distance() -> Distance {
         Distance::Euclid
@@ -116,6 +119,24 @@ impl Metric for ManhattanMetric {
     }
 }
 
+impl Metric for HammingMetric {
+    fn distance() -> Distance {
+        Distance::Hamming
+    }
+
+    fn similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        hamming_similarity(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+
+    fn postprocess(score: ScoreType) -> ScoreType {
+        score.abs()
+    }
+}
+
 impl Metric for DotProductMetric {
     fn distance() -> Distance {
         Distance::Dot
@@ -239,6 +260,16 @@ pub fn manhattan_similarity(v1: &[VectorElementType], v2: &[VectorElementType])
         .sum::<ScoreType>()
 }
 
+/// Negative number of differing bits, each element holds 8 bits of a binary vector
+///
+/// Elements are converted into bytes the same way as they are stored with the `uint8` datatype.
+pub fn hamming_similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+    -(v1.iter()
+        .zip(v2)
+        .map(|(&a, &b)| ((a.round() as u8) ^ (b.round() as u8)).count_ones())
+        .sum::<u32>() as ScoreType)
+}
+
 pub fn cosine_preprocess(vector: DenseVector) -> DenseVector {
     let mut length: f32 = vector.iter().map(|x| x * x).sum();
     if length < f32::EPSILON {
@@ -248,10 +279,47 @@ pub fn cosine_preprocess(vector: DenseVector) -> DenseVector {
     vector.iter().map(|x| x / length).collect()
 }
 
+/// Length of the vector, by which it is divided in [`cosine_preprocess`]
+///
+/// Returns `1.0` for zero vectors, as those are not normalized.
+pub fn cosine_norm(vector: &[VectorElementType]) -> ScoreType {
+    let length = DotProductMetric::similarity(vector, vector);
+    if length < f32::EPSILON {
+        return 1.0;
+    }
+    length.sqrt()
+}
+
 pub fn dot_similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
     v1.iter().zip(v2).map(|(a, b)| a * b).sum()
 }
 
+/// [`euclid_similarity`] with each squared difference multiplied by the weight of its dimension
+pub fn weighted_euclid_similarity(
+    weights: &[VectorElementType],
+    v1: &[VectorElementType],
+    v2: &[VectorElementType],
+) -> ScoreType {
+    -weights
+        .iter()
+        .zip(v1.iter().zip(v2))
+        .map(|(w, (a, b))| w * (a - b).powi(2))
+        .sum::<ScoreType>()
+}
+
+/// [`dot_similarity`] with each product multiplied by the weight of its dimension
+pub fn weighted_dot_similarity(
+    weights: &[VectorElementType],
+    v1: &[VectorElementType],
+    v2: &[VectorElementType],
+) -> ScoreType {
+    weights
+        .iter()
+        .zip(v1.iter().zip(v2))
+        .map(|(w, (a, b))| w * a * b)
+        .sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,216 @@
+use common::types::ScoreType;
+
+use super::metric::ElementSimilarity;
+use super::simple::{CosineMetric, DotProductMetric, EuclidMetric, HammingMetric, ManhattanMetric};
+#[cfg(target_arch = "x86_64")]
+use super::simple_byte_avx::*;
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use super::simple_byte_neon::*;
+use crate::data_types::vectors::VectorElementTypeByte;
+
+#[cfg(target_arch = "x86_64")]
+const MIN_DIM_SIZE_AVX: usize = 32;
+
+#[cfg(target_arch = "x86_64")]
+fn is_byte_avx_available(dim: usize) -> bool {
+    is_x86_feature_detected!("avx2") && dim >= MIN_DIM_SIZE_AVX
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+const MIN_DIM_SIZE_NEON: usize = 16;
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+fn is_byte_neon_available(dim: usize) -> bool {
+    std::arch::is_aarch64_feature_detected!("neon") && dim >= MIN_DIM_SIZE_NEON
+}
+
+impl ElementSimilarity<VectorElementTypeByte> for EuclidMetric {
+    fn element_similarity(v1: &[VectorElementTypeByte], v2: &[VectorElementTypeByte]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_byte_avx_available(v1.len()) {
+                return unsafe { euclid_similarity_byte_avx2(v1, v2) };
+            }
+        }
+
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            if is_byte_neon_available(v1.len()) {
+                return unsafe { euclid_similarity_byte_neon(v1, v2) };
+            }
+        }
+
+        euclid_similarity_byte(v1, v2)
+    }
+}
+
+impl ElementSimilarity<VectorElementTypeByte> for ManhattanMetric {
+    fn element_similarity(v1: &[VectorElementTypeByte], v2: &[VectorElementTypeByte]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_byte_avx_available(v1.len()) {
+                return unsafe { manhattan_similarity_byte_avx2(v1, v2) };
+            }
+        }
+
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            if is_byte_neon_available(v1.len()) {
+                return unsafe { manhattan_similarity_byte_neon(v1, v2) };
+            }
+        }
+
+        manhattan_similarity_byte(v1, v2)
+    }
+}
+
+impl ElementSimilarity<VectorElementTypeByte> for DotProductMetric {
+    fn element_similarity(v1: &[VectorElementTypeByte], v2: &[VectorElementTypeByte]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_byte_avx_available(v1.len()) {
+                return unsafe { dot_similarity_byte_avx2(v1, v2) };
+            }
+        }
+
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            if is_byte_neon_available(v1.len()) {
+                return unsafe { dot_similarity_byte_neon(v1, v2) };
+            }
+        }
+
+        dot_similarity_byte(v1, v2)
+    }
+}
+
+impl ElementSimilarity<VectorElementTypeByte> for HammingMetric {
+    fn element_similarity(v1: &[VectorElementTypeByte], v2: &[VectorElementTypeByte]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_byte_avx_available(v1.len()) {
+                return unsafe { hamming_similarity_byte_avx2(v1, v2) };
+            }
+        }
+
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            if is_byte_neon_available(v1.len()) {
+                return unsafe { hamming_similarity_byte_neon(v1, v2) };
+            }
+        }
+
+        hamming_similarity_byte(v1, v2)
+    }
+}
+
+impl ElementSimilarity<VectorElementTypeByte> for CosineMetric {
+    fn element_similarity(v1: &[VectorElementTypeByte], v2: &[VectorElementTypeByte]) -> ScoreType {
+        // Byte storages can't hold normalized vectors and are not allowed with cosine distance,
+        // score as a dot product to stay consistent with other element types
+        DotProductMetric::element_similarity(v1, v2)
+    }
+}
+
+pub fn euclid_similarity_byte(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    -v1.iter()
+        .zip(v2)
+        .map(|(&a, &b)| {
+            let diff = i64::from(a) - i64::from(b);
+            diff * diff
+        })
+        .sum::<i64>() as ScoreType
+}
+
+pub fn manhattan_similarity_byte(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    -v1.iter()
+        .zip(v2)
+        .map(|(&a, &b)| i64::from(a.abs_diff(b)))
+        .sum::<i64>() as ScoreType
+}
+
+pub fn dot_similarity_byte(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    v1.iter()
+        .zip(v2)
+        .map(|(&a, &b)| i64::from(a) * i64::from(b))
+        .sum::<i64>() as ScoreType
+}
+
+/// Negative number of differing bits, compares 8 bytes at once using popcount
+pub fn hamming_similarity_byte(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let chunks1 = v1.chunks_exact(8);
+    let chunks2 = v2.chunks_exact(8);
+    let tail = chunks1
+        .remainder()
+        .iter()
+        .zip(chunks2.remainder())
+        .map(|(&a, &b)| (a ^ b).count_ones())
+        .sum::<u32>();
+    let count = chunks1
+        .zip(chunks2)
+        .map(|(a, b)| {
+            let a = u64::from_ne_bytes(a.try_into().unwrap());
+            let b = u64::from_ne_bytes(b.try_into().unwrap());
+            (a ^ b).count_ones()
+        })
+        .sum::<u32>();
+    -((count + tail) as ScoreType)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spaces::metric::Metric;
+
+    #[test]
+    fn test_byte_similarity_matches_full_precision() {
+        let v1: Vec<u8> = (0..100).map(|i| (i * 7 % 256) as u8).collect();
+        let v2: Vec<u8> = (0..100).map(|i| (i * 13 % 256) as u8).collect();
+        let f1: Vec<f32> = v1.iter().copied().map(f32::from).collect();
+        let f2: Vec<f32> = v2.iter().copied().map(f32::from).collect();
+
+        assert_eq!(
+            DotProductMetric::element_similarity(&v1, &v2),
+            DotProductMetric::similarity(&f1, &f2)
+        );
+        assert_eq!(
+            EuclidMetric::element_similarity(&v1, &v2),
+            EuclidMetric::similarity(&f1, &f2)
+        );
+        assert_eq!(
+            ManhattanMetric::element_similarity(&v1, &v2),
+            ManhattanMetric::similarity(&f1, &f2)
+        );
+        assert_eq!(
+            HammingMetric::element_similarity(&v1, &v2),
+            HammingMetric::similarity(&f1, &f2)
+        );
+    }
+
+    #[test]
+    fn test_hamming_similarity_byte() {
+        assert_eq!(
+            hamming_similarity_byte(&[0b1010_1010], &[0b0101_0101]),
+            -8.0
+        );
+        assert_eq!(hamming_similarity_byte(&[0xff; 11], &[0xff; 11]), 0.0);
+
+        let v1 = [0u8; 11];
+        let mut v2 = [0u8; 11];
+        v2[0] = 0b0000_0011;
+        v2[10] = 0b1000_0000;
+        assert_eq!(hamming_similarity_byte(&v1, &v2), -3.0);
+    }
+}
@@ -0,0 +1,200 @@
+use std::arch::x86_64::*;
+
+use common::types::ScoreType;
+
+use crate::data_types::vectors::VectorElementTypeByte;
+
+/// Sum of 32-bit lanes, widened to avoid overflow
+#[target_feature(enable = "avx2")]
+unsafe fn hsum256_epi32_avx2(x: __m256i) -> i64 {
+    let mut lanes = [0i32; 8];
+    _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, x);
+    lanes.iter().map(|&lane| i64::from(lane)).sum()
+}
+
+/// Sum of 64-bit lanes
+#[target_feature(enable = "avx2")]
+unsafe fn hsum256_epi64_avx2(x: __m256i) -> i64 {
+    let mut lanes = [0i64; 4];
+    _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, x);
+    lanes.iter().sum()
+}
+
+/// Loads 32 bytes and widens them into two vectors of 16-bit lanes
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn load_widen_epu8(ptr: *const VectorElementTypeByte) -> (__m256i, __m256i) {
+    let bytes = _mm256_loadu_si256(ptr as *const __m256i);
+    let low = _mm256_cvtepu8_epi16(_mm256_castsi256_si128(bytes));
+    let high = _mm256_cvtepu8_epi16(_mm256_extracti128_si256(bytes, 1));
+    (low, high)
+}
+
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn euclid_similarity_byte_avx2(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 32);
+    let mut ptr1: *const VectorElementTypeByte = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeByte = v2.as_ptr();
+    // Each lane accumulates at most 2 * 255^2 per iteration, which can't overflow for any supported dimension
+    let mut sum256_1: __m256i = _mm256_setzero_si256();
+    let mut sum256_2: __m256i = _mm256_setzero_si256();
+    let mut i: usize = 0;
+    while i < m {
+        let (low1, high1) = load_widen_epu8(ptr1);
+        let (low2, high2) = load_widen_epu8(ptr2);
+
+        let sub_low = _mm256_sub_epi16(low1, low2);
+        sum256_1 = _mm256_add_epi32(sum256_1, _mm256_madd_epi16(sub_low, sub_low));
+
+        let sub_high = _mm256_sub_epi16(high1, high2);
+        sum256_2 = _mm256_add_epi32(sum256_2, _mm256_madd_epi16(sub_high, sub_high));
+
+        ptr1 = ptr1.add(32);
+        ptr2 = ptr2.add(32);
+        i += 32;
+    }
+
+    let mut result = hsum256_epi32_avx2(sum256_1) + hsum256_epi32_avx2(sum256_2);
+    for i in 0..n - m {
+        let diff = i64::from(*ptr1.add(i)) - i64::from(*ptr2.add(i));
+        result += diff * diff;
+    }
+    -result as ScoreType
+}
+
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn manhattan_similarity_byte_avx2(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 32);
+    let mut ptr1: *const VectorElementTypeByte = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeByte = v2.as_ptr();
+    let mut sum256: __m256i = _mm256_setzero_si256();
+    let mut i: usize = 0;
+    while i < m {
+        // Sum of absolute differences of each 8 bytes, into 64-bit lanes
+        let bytes1 = _mm256_loadu_si256(ptr1 as *const __m256i);
+        let bytes2 = _mm256_loadu_si256(ptr2 as *const __m256i);
+        sum256 = _mm256_add_epi64(sum256, _mm256_sad_epu8(bytes1, bytes2));
+
+        ptr1 = ptr1.add(32);
+        ptr2 = ptr2.add(32);
+        i += 32;
+    }
+
+    let mut result = hsum256_epi64_avx2(sum256);
+    for i in 0..n - m {
+        result += i64::from((*ptr1.add(i)).abs_diff(*ptr2.add(i)));
+    }
+    -result as ScoreType
+}
+
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn dot_similarity_byte_avx2(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 32);
+    let mut ptr1: *const VectorElementTypeByte = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeByte = v2.as_ptr();
+    // Each lane accumulates at most 2 * 255^2 per iteration, which can't overflow for any supported dimension
+    let mut sum256_1: __m256i = _mm256_setzero_si256();
+    let mut sum256_2: __m256i = _mm256_setzero_si256();
+    let mut i: usize = 0;
+    while i < m {
+        let (low1, high1) = load_widen_epu8(ptr1);
+        let (low2, high2) = load_widen_epu8(ptr2);
+
+        sum256_1 = _mm256_add_epi32(sum256_1, _mm256_madd_epi16(low1, low2));
+        sum256_2 = _mm256_add_epi32(sum256_2, _mm256_madd_epi16(high1, high2));
+
+        ptr1 = ptr1.add(32);
+        ptr2 = ptr2.add(32);
+        i += 32;
+    }
+
+    let mut result = hsum256_epi32_avx2(sum256_1) + hsum256_epi32_avx2(sum256_2);
+    for i in 0..n - m {
+        result += i64::from(*ptr1.add(i)) * i64::from(*ptr2.add(i));
+    }
+    result as ScoreType
+}
+
+/// Counts differing bits with a lookup table of set bits for each 4-bit value
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn hamming_similarity_byte_avx2(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 32);
+    let mut ptr1: *const VectorElementTypeByte = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeByte = v2.as_ptr();
+    let lookup = _mm256_setr_epi8(
+        0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4, 0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3,
+        3, 4,
+    );
+    let low_mask = _mm256_set1_epi8(0x0f);
+    let mut sum256: __m256i = _mm256_setzero_si256();
+    let mut i: usize = 0;
+    while i < m {
+        let x = _mm256_xor_si256(
+            _mm256_loadu_si256(ptr1 as *const __m256i),
+            _mm256_loadu_si256(ptr2 as *const __m256i),
+        );
+        let low = _mm256_shuffle_epi8(lookup, _mm256_and_si256(x, low_mask));
+        let high = _mm256_shuffle_epi8(lookup, _mm256_and_si256(_mm256_srli_epi16(x, 4), low_mask));
+        // Sum up per-byte counts into 64-bit lanes
+        let counts = _mm256_add_epi8(low, high);
+        sum256 = _mm256_add_epi64(sum256, _mm256_sad_epu8(counts, _mm256_setzero_si256()));
+
+        ptr1 = ptr1.add(32);
+        ptr2 = ptr2.add(32);
+        i += 32;
+    }
+
+    let mut result = hsum256_epi64_avx2(sum256);
+    for i in 0..n - m {
+        result += i64::from((*ptr1.add(i) ^ *ptr2.add(i)).count_ones());
+    }
+    -result as ScoreType
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_spaces_byte_avx2() {
+        use super::*;
+        use crate::spaces::simple_byte::*;
+
+        if is_x86_feature_detected!("avx2") {
+            let v1: Vec<u8> = (0..1000).map(|i| (i * 31 % 256) as u8).collect();
+            let v2: Vec<u8> = (0..1000).map(|i| (i * 17 % 256) as u8).collect();
+
+            let euclid_simd = unsafe { euclid_similarity_byte_avx2(&v1, &v2) };
+            let euclid = euclid_similarity_byte(&v1, &v2);
+            assert_eq!(euclid_simd, euclid);
+
+            let manhattan_simd = unsafe { manhattan_similarity_byte_avx2(&v1, &v2) };
+            let manhattan = manhattan_similarity_byte(&v1, &v2);
+            assert_eq!(manhattan_simd, manhattan);
+
+            let dot_simd = unsafe { dot_similarity_byte_avx2(&v1, &v2) };
+            let dot = dot_similarity_byte(&v1, &v2);
+            assert_eq!(dot_simd, dot);
+
+            let hamming_simd = unsafe { hamming_similarity_byte_avx2(&v1, &v2) };
+            let hamming = hamming_similarity_byte(&v1, &v2);
+            assert_eq!(hamming_simd, hamming);
+        } else {
+            println!("avx2 test skipped");
+        }
+    }
+}
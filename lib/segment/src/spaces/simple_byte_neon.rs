@@ -0,0 +1,159 @@
+#[cfg(target_feature = "neon")]
+use std::arch::aarch64::*;
+
+#[cfg(target_feature = "neon")]
+use common::types::ScoreType;
+
+#[cfg(target_feature = "neon")]
+use crate::data_types::vectors::VectorElementTypeByte;
+
+#[cfg(target_feature = "neon")]
+pub(crate) unsafe fn euclid_similarity_byte_neon(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 16);
+    let mut ptr1: *const VectorElementTypeByte = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeByte = v2.as_ptr();
+    // Each lane accumulates at most 2 * 255^2 per iteration, which can't overflow for any supported dimension
+    let mut sum1 = vdupq_n_u32(0);
+    let mut sum2 = vdupq_n_u32(0);
+
+    let mut i: usize = 0;
+    while i < m {
+        let diff = vabdq_u8(vld1q_u8(ptr1), vld1q_u8(ptr2));
+        sum1 = vpadalq_u16(sum1, vmull_u8(vget_low_u8(diff), vget_low_u8(diff)));
+        sum2 = vpadalq_u16(sum2, vmull_high_u8(diff, diff));
+
+        ptr1 = ptr1.add(16);
+        ptr2 = ptr2.add(16);
+        i += 16;
+    }
+
+    let mut result = vaddlvq_u32(sum1) + vaddlvq_u32(sum2);
+    for i in 0..n - m {
+        result += u64::from((*ptr1.add(i)).abs_diff(*ptr2.add(i))).pow(2);
+    }
+    -(result as ScoreType)
+}
+
+#[cfg(target_feature = "neon")]
+pub(crate) unsafe fn manhattan_similarity_byte_neon(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 16);
+    let mut ptr1: *const VectorElementTypeByte = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeByte = v2.as_ptr();
+    let mut sum = vdupq_n_u32(0);
+
+    let mut i: usize = 0;
+    while i < m {
+        let diff = vabdq_u8(vld1q_u8(ptr1), vld1q_u8(ptr2));
+        sum = vpadalq_u16(sum, vpaddlq_u8(diff));
+
+        ptr1 = ptr1.add(16);
+        ptr2 = ptr2.add(16);
+        i += 16;
+    }
+
+    let mut result = vaddlvq_u32(sum);
+    for i in 0..n - m {
+        result += u64::from((*ptr1.add(i)).abs_diff(*ptr2.add(i)));
+    }
+    -(result as ScoreType)
+}
+
+#[cfg(target_feature = "neon")]
+pub(crate) unsafe fn dot_similarity_byte_neon(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 16);
+    let mut ptr1: *const VectorElementTypeByte = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeByte = v2.as_ptr();
+    let mut sum1 = vdupq_n_u32(0);
+    let mut sum2 = vdupq_n_u32(0);
+
+    let mut i: usize = 0;
+    while i < m {
+        let a = vld1q_u8(ptr1);
+        let b = vld1q_u8(ptr2);
+        sum1 = vpadalq_u16(sum1, vmull_u8(vget_low_u8(a), vget_low_u8(b)));
+        sum2 = vpadalq_u16(sum2, vmull_high_u8(a, b));
+
+        ptr1 = ptr1.add(16);
+        ptr2 = ptr2.add(16);
+        i += 16;
+    }
+
+    let mut result = vaddlvq_u32(sum1) + vaddlvq_u32(sum2);
+    for i in 0..n - m {
+        result += u64::from(*ptr1.add(i)) * u64::from(*ptr2.add(i));
+    }
+    result as ScoreType
+}
+
+#[cfg(target_feature = "neon")]
+pub(crate) unsafe fn hamming_similarity_byte_neon(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 16);
+    let mut ptr1: *const VectorElementTypeByte = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeByte = v2.as_ptr();
+    let mut sum = vdupq_n_u32(0);
+
+    let mut i: usize = 0;
+    while i < m {
+        let bits = vcntq_u8(veorq_u8(vld1q_u8(ptr1), vld1q_u8(ptr2)));
+        sum = vpadalq_u16(sum, vpaddlq_u8(bits));
+
+        ptr1 = ptr1.add(16);
+        ptr2 = ptr2.add(16);
+        i += 16;
+    }
+
+    let mut result = vaddlvq_u32(sum);
+    for i in 0..n - m {
+        result += u64::from((*ptr1.add(i) ^ *ptr2.add(i)).count_ones());
+    }
+    -(result as ScoreType)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(target_feature = "neon")]
+    #[test]
+    fn test_spaces_byte_neon() {
+        use super::*;
+        use crate::spaces::simple_byte::*;
+
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            let v1: Vec<u8> = (0..1000).map(|i| (i * 31 % 256) as u8).collect();
+            let v2: Vec<u8> = (0..1000).map(|i| (i * 17 % 256) as u8).collect();
+
+            let euclid_simd = unsafe { euclid_similarity_byte_neon(&v1, &v2) };
+            let euclid = euclid_similarity_byte(&v1, &v2);
+            assert_eq!(euclid_simd, euclid);
+
+            let manhattan_simd = unsafe { manhattan_similarity_byte_neon(&v1, &v2) };
+            let manhattan = manhattan_similarity_byte(&v1, &v2);
+            assert_eq!(manhattan_simd, manhattan);
+
+            let dot_simd = unsafe { dot_similarity_byte_neon(&v1, &v2) };
+            let dot = dot_similarity_byte(&v1, &v2);
+            assert_eq!(dot_simd, dot);
+
+            let hamming_simd = unsafe { hamming_similarity_byte_neon(&v1, &v2) };
+            let hamming = hamming_similarity_byte(&v1, &v2);
+            assert_eq!(hamming_simd, hamming);
+        } else {
+            println!("neon test skipped");
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use common::types::ScoreType;
+use half::slice::HalfFloatSliceExt;
+
+use super::metric::{ElementSimilarity, Metric};
+use super::simple::{CosineMetric, DotProductMetric, EuclidMetric, HammingMetric, ManhattanMetric};
+#[cfg(target_arch = "x86_64")]
+use super::simple_half_avx::*;
+use crate::data_types::vectors::{VectorElementType, VectorElementTypeHalf};
+
+#[cfg(target_arch = "x86_64")]
+const MIN_DIM_SIZE_AVX: usize = 32;
+
+/// Number of elements converted to full precision at once in the non-SIMD fallback
+const CONVERSION_CHUNK_SIZE: usize = 64;
+
+#[cfg(target_arch = "x86_64")]
+fn is_half_avx_available(dim: usize) -> bool {
+    is_x86_feature_detected!("avx")
+        && is_x86_feature_detected!("fma")
+        && is_x86_feature_detected!("f16c")
+        && dim >= MIN_DIM_SIZE_AVX
+}
+
+/// Computes the metric by converting vectors into full precision chunk by chunk.
+///
+/// Only valid for metrics, which are a sum over the individual dimensions.
+fn chunked_similarity<TMetric: Metric>(
+    v1: &[VectorElementTypeHalf],
+    v2: &[VectorElementTypeHalf],
+) -> ScoreType {
+    let mut buffer1 = [VectorElementType::default(); CONVERSION_CHUNK_SIZE];
+    let mut buffer2 = [VectorElementType::default(); CONVERSION_CHUNK_SIZE];
+    v1.chunks(CONVERSION_CHUNK_SIZE)
+        .zip(v2.chunks(CONVERSION_CHUNK_SIZE))
+        .map(|(chunk1, chunk2)| {
+            let len = chunk1.len();
+            chunk1.convert_to_f32_slice(&mut buffer1[..len]);
+            chunk2.convert_to_f32_slice(&mut buffer2[..len]);
+            TMetric::similarity(&buffer1[..len], &buffer2[..len])
+        })
+        .sum()
+}
+
+impl ElementSimilarity<VectorElementTypeHalf> for EuclidMetric {
+    fn element_similarity(v1: &[VectorElementTypeHalf], v2: &[VectorElementTypeHalf]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_half_avx_available(v1.len()) {
+                return unsafe { euclid_similarity_half_avx(v1, v2) };
+            }
+        }
+
+        chunked_similarity::<Self>(v1, v2)
+    }
+}
+
+impl ElementSimilarity<VectorElementTypeHalf> for ManhattanMetric {
+    fn element_similarity(v1: &[VectorElementTypeHalf], v2: &[VectorElementTypeHalf]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_half_avx_available(v1.len()) {
+                return unsafe { manhattan_similarity_half_avx(v1, v2) };
+            }
+        }
+
+        chunked_similarity::<Self>(v1, v2)
+    }
+}
+
+impl ElementSimilarity<VectorElementTypeHalf> for DotProductMetric {
+    fn element_similarity(v1: &[VectorElementTypeHalf], v2: &[VectorElementTypeHalf]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_half_avx_available(v1.len()) {
+                return unsafe { dot_similarity_half_avx(v1, v2) };
+            }
+        }
+
+        chunked_similarity::<Self>(v1, v2)
+    }
+}
+
+impl ElementSimilarity<VectorElementTypeHalf> for HammingMetric {
+    fn element_similarity(v1: &[VectorElementTypeHalf], v2: &[VectorElementTypeHalf]) -> ScoreType {
+        chunked_similarity::<Self>(v1, v2)
+    }
+}
+
+impl ElementSimilarity<VectorElementTypeHalf> for CosineMetric {
+    fn element_similarity(v1: &[VectorElementTypeHalf], v2: &[VectorElementTypeHalf]) -> ScoreType {
+        // Vectors are normalized before conversion, so cosine is a dot product
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_half_avx_available(v1.len()) {
+                return unsafe { dot_similarity_half_avx(v1, v2) };
+            }
+        }
+
+        chunked_similarity::<Self>(v1, v2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use half::f16;
+
+    use super::*;
+
+    #[test]
+    fn test_half_similarity_matches_full_precision() {
+        let v1: Vec<f32> = (0..100).map(|i| (i % 7) as f32 * 0.25).collect();
+        let v2: Vec<f32> = (0..100).map(|i| (i % 5) as f32 * 0.5).collect();
+        // All values are exactly representable in f16
+        let h1: Vec<f16> = v1.iter().copied().map(f16::from_f32).collect();
+        let h2: Vec<f16> = v2.iter().copied().map(f16::from_f32).collect();
+
+        assert_eq!(
+            DotProductMetric::element_similarity(&h1, &h2),
+            DotProductMetric::similarity(&v1, &v2)
+        );
+        assert_eq!(
+            EuclidMetric::element_similarity(&h1, &h2),
+            EuclidMetric::similarity(&v1, &v2)
+        );
+        assert_eq!(
+            ManhattanMetric::element_similarity(&h1, &h2),
+            ManhattanMetric::similarity(&v1, &v2)
+        );
+        assert_eq!(
+            chunked_similarity::<DotProductMetric>(&h1[..10], &h2[..10]),
+            DotProductMetric::similarity(&v1[..10], &v2[..10])
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use std::arch::x86_64::*;
+
+use common::types::ScoreType;
+
+use super::simple_avx::hsum256_ps_avx;
+use crate::data_types::vectors::VectorElementTypeHalf;
+
+/// Loads 8 half precision values and converts them into full precision
+#[inline]
+#[target_feature(enable = "avx")]
+#[target_feature(enable = "f16c")]
+unsafe fn load_half_ps(ptr: *const VectorElementTypeHalf) -> __m256 {
+    _mm256_cvtph_ps(_mm_loadu_si128(ptr as *const __m128i))
+}
+
+#[target_feature(enable = "avx")]
+#[target_feature(enable = "fma")]
+#[target_feature(enable = "f16c")]
+pub(crate) unsafe fn euclid_similarity_half_avx(
+    v1: &[VectorElementTypeHalf],
+    v2: &[VectorElementTypeHalf],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 32);
+    let mut ptr1: *const VectorElementTypeHalf = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeHalf = v2.as_ptr();
+    let mut sum256_1: __m256 = _mm256_setzero_ps();
+    let mut sum256_2: __m256 = _mm256_setzero_ps();
+    let mut sum256_3: __m256 = _mm256_setzero_ps();
+    let mut sum256_4: __m256 = _mm256_setzero_ps();
+    let mut i: usize = 0;
+    while i < m {
+        let sub256_1: __m256 = _mm256_sub_ps(load_half_ps(ptr1), load_half_ps(ptr2));
+        sum256_1 = _mm256_fmadd_ps(sub256_1, sub256_1, sum256_1);
+
+        let sub256_2: __m256 = _mm256_sub_ps(load_half_ps(ptr1.add(8)), load_half_ps(ptr2.add(8)));
+        sum256_2 = _mm256_fmadd_ps(sub256_2, sub256_2, sum256_2);
+
+        let sub256_3: __m256 =
+            _mm256_sub_ps(load_half_ps(ptr1.add(16)), load_half_ps(ptr2.add(16)));
+        sum256_3 = _mm256_fmadd_ps(sub256_3, sub256_3, sum256_3);
+
+        let sub256_4: __m256 =
+            _mm256_sub_ps(load_half_ps(ptr1.add(24)), load_half_ps(ptr2.add(24)));
+        sum256_4 = _mm256_fmadd_ps(sub256_4, sub256_4, sum256_4);
+
+        ptr1 = ptr1.add(32);
+        ptr2 = ptr2.add(32);
+        i += 32;
+    }
+
+    let mut result = hsum256_ps_avx(sum256_1)
+        + hsum256_ps_avx(sum256_2)
+        + hsum256_ps_avx(sum256_3)
+        + hsum256_ps_avx(sum256_4);
+    for i in 0..n - m {
+        result += ((*ptr1.add(i)).to_f32() - (*ptr2.add(i)).to_f32()).powi(2);
+    }
+    -result
+}
+
+#[target_feature(enable = "avx")]
+#[target_feature(enable = "fma")]
+#[target_feature(enable = "f16c")]
+pub(crate) unsafe fn manhattan_similarity_half_avx(
+    v1: &[VectorElementTypeHalf],
+    v2: &[VectorElementTypeHalf],
+) -> ScoreType {
+    let mask: __m256 = _mm256_set1_ps(-0.0f32); // 1 << 31 used to clear sign bit to mimic abs
+
+    let n = v1.len();
+    let m = n - (n % 32);
+    let mut ptr1: *const VectorElementTypeHalf = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeHalf = v2.as_ptr();
+    let mut sum256_1: __m256 = _mm256_setzero_ps();
+    let mut sum256_2: __m256 = _mm256_setzero_ps();
+    let mut sum256_3: __m256 = _mm256_setzero_ps();
+    let mut sum256_4: __m256 = _mm256_setzero_ps();
+    let mut i: usize = 0;
+    while i < m {
+        let sub256_1: __m256 = _mm256_sub_ps(load_half_ps(ptr1), load_half_ps(ptr2));
+        sum256_1 = _mm256_add_ps(_mm256_andnot_ps(mask, sub256_1), sum256_1);
+
+        let sub256_2: __m256 = _mm256_sub_ps(load_half_ps(ptr1.add(8)), load_half_ps(ptr2.add(8)));
+        sum256_2 = _mm256_add_ps(_mm256_andnot_ps(mask, sub256_2), sum256_2);
+
+        let sub256_3: __m256 =
+            _mm256_sub_ps(load_half_ps(ptr1.add(16)), load_half_ps(ptr2.add(16)));
+        sum256_3 = _mm256_add_ps(_mm256_andnot_ps(mask, sub256_3), sum256_3);
+
+        let sub256_4: __m256 =
+            _mm256_sub_ps(load_half_ps(ptr1.add(24)), load_half_ps(ptr2.add(24)));
+        sum256_4 = _mm256_add_ps(_mm256_andnot_ps(mask, sub256_4), sum256_4);
+
+        ptr1 = ptr1.add(32);
+        ptr2 = ptr2.add(32);
+        i += 32;
+    }
+
+    let mut result = hsum256_ps_avx(sum256_1)
+        + hsum256_ps_avx(sum256_2)
+        + hsum256_ps_avx(sum256_3)
+        + hsum256_ps_avx(sum256_4);
+    for i in 0..n - m {
+        result += ((*ptr1.add(i)).to_f32() - (*ptr2.add(i)).to_f32()).abs();
+    }
+    -result
+}
+
+#[target_feature(enable = "avx")]
+#[target_feature(enable = "fma")]
+#[target_feature(enable = "f16c")]
+pub(crate) unsafe fn dot_similarity_half_avx(
+    v1: &[VectorElementTypeHalf],
+    v2: &[VectorElementTypeHalf],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 32);
+    let mut ptr1: *const VectorElementTypeHalf = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeHalf = v2.as_ptr();
+    let mut sum256_1: __m256 = _mm256_setzero_ps();
+    let mut sum256_2: __m256 = _mm256_setzero_ps();
+    let mut sum256_3: __m256 = _mm256_setzero_ps();
+    let mut sum256_4: __m256 = _mm256_setzero_ps();
+    let mut i: usize = 0;
+    while i < m {
+        sum256_1 = _mm256_fmadd_ps(load_half_ps(ptr1), load_half_ps(ptr2), sum256_1);
+        sum256_2 = _mm256_fmadd_ps(
+            load_half_ps(ptr1.add(8)),
+            load_half_ps(ptr2.add(8)),
+            sum256_2,
+        );
+        sum256_3 = _mm256_fmadd_ps(
+            load_half_ps(ptr1.add(16)),
+            load_half_ps(ptr2.add(16)),
+            sum256_3,
+        );
+        sum256_4 = _mm256_fmadd_ps(
+            load_half_ps(ptr1.add(24)),
+            load_half_ps(ptr2.add(24)),
+            sum256_4,
+        );
+
+        ptr1 = ptr1.add(32);
+        ptr2 = ptr2.add(32);
+        i += 32;
+    }
+
+    let mut result = hsum256_ps_avx(sum256_1)
+        + hsum256_ps_avx(sum256_2)
+        + hsum256_ps_avx(sum256_3)
+        + hsum256_ps_avx(sum256_4);
+
+    for i in 0..n - m {
+        result += (*ptr1.add(i)).to_f32() * (*ptr2.add(i)).to_f32();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_spaces_half_avx() {
+        use half::f16;
+
+        use super::*;
+        use crate::spaces::simple::*;
+
+        if is_x86_feature_detected!("avx")
+            && is_x86_feature_detected!("fma")
+            && is_x86_feature_detected!("f16c")
+        {
+            let v1: Vec<f32> = (0..70).map(|i| (10 + i % 16) as f32).collect();
+            let v2: Vec<f32> = (0..70).map(|i| (40 + i % 22) as f32).collect();
+            let h1: Vec<f16> = v1.iter().copied().map(f16::from_f32).collect();
+            let h2: Vec<f16> = v2.iter().copied().map(f16::from_f32).collect();
+
+            let euclid_simd = unsafe { euclid_similarity_half_avx(&h1, &h2) };
+            let euclid = euclid_similarity(&v1, &v2);
+            assert_eq!(euclid_simd, euclid);
+
+            let manhattan_simd = unsafe { manhattan_similarity_half_avx(&h1, &h2) };
+            let manhattan = manhattan_similarity(&v1, &v2);
+            assert_eq!(manhattan_simd, manhattan);
+
+            let dot_simd = unsafe { dot_similarity_half_avx(&h1, &h2) };
+            let dot = dot_similarity(&v1, &v2);
+            assert_eq!(dot_simd, dot);
+        } else {
+            println!("avx f16c test skipped");
+        }
+    }
+}
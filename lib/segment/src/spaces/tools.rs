@@ -2,6 +2,28 @@ use std::cmp::Reverse;
 
 use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
 
+/// Size of a CPU cache line in bytes
+const CACHE_LINE_SIZE: usize = 64;
+
+/// Hint the CPU to load the vector into cache, so that it's ready by the time it is scored
+#[inline]
+pub fn prefetch_vector<T>(vector: &[T]) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        use std::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+
+        let ptr = vector.as_ptr() as *const i8;
+        let size = std::mem::size_of_val(vector);
+        for offset in (0..size).step_by(CACHE_LINE_SIZE) {
+            // Prefetch is a hint only, it never faults
+            unsafe { _mm_prefetch(ptr.add(offset), _MM_HINT_T0) };
+        }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    let _ = vector;
+}
+
 pub fn peek_top_smallest_iterable<I, E: Ord>(elements: I, top: usize) -> Vec<E>
 where
     I: IntoIterator<Item = E>,
@@ -70,6 +70,29 @@ pub struct VectorIndexSearchesTelemetry {
 
     #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
     pub unfiltered_exact: OperationDurationStatistics,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub graph: Option<HnswGraphTelemetry>,
+}
+
+/// Structural statistics of the HNSW graph, used to investigate recall issues
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, Default, PartialEq)]
+pub struct HnswGraphTelemetry {
+    /// Statistics per graph layer, starting from level 0
+    pub layers: Vec<HnswGraphLayerTelemetry>,
+    /// Number of non-deleted points which have no incoming links on level 0 and are not entry points
+    pub orphaned_points: usize,
+    /// Number of entry points of the graph
+    pub entry_points: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, Default, PartialEq)]
+pub struct HnswGraphLayerTelemetry {
+    /// Number of points present on this layer
+    pub points: usize,
+    pub avg_out_degree: f64,
+    pub max_out_degree: usize,
 }
 
 impl Anonymize for SegmentTelemetry {
@@ -126,6 +149,7 @@ impl Anonymize for SegmentConfig {
             vector_data: self.vector_data.anonymize(),
             sparse_vector_data: self.sparse_vector_data.anonymize(),
             payload_storage_type: self.payload_storage_type,
+            payload_compression: self.payload_compression,
         }
     }
 }
@@ -138,6 +162,10 @@ impl Anonymize for VectorDataConfig {
             storage_type: self.storage_type,
             index: self.index.clone(),
             quantization_config: None,
+            multivector_config: None,
+            datatype: None,
+            keep_original: None,
+            dimension_weights: None,
         }
     }
 }
@@ -163,6 +191,27 @@ impl Anonymize for VectorIndexSearchesTelemetry {
             filtered_exact: self.filtered_exact.anonymize(),
             filtered_sparse: self.filtered_sparse.anonymize(),
             unfiltered_exact: self.filtered_exact.anonymize(),
+            graph: self.graph.anonymize(),
+        }
+    }
+}
+
+impl Anonymize for HnswGraphTelemetry {
+    fn anonymize(&self) -> Self {
+        HnswGraphTelemetry {
+            layers: self.layers.anonymize(),
+            orphaned_points: self.orphaned_points.anonymize(),
+            entry_points: self.entry_points,
+        }
+    }
+}
+
+impl Anonymize for HnswGraphLayerTelemetry {
+    fn anonymize(&self) -> Self {
+        HnswGraphLayerTelemetry {
+            points: self.points.anonymize(),
+            avg_out_degree: self.avg_out_degree,
+            max_out_degree: self.max_out_degree,
         }
     }
 }
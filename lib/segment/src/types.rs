@@ -25,11 +25,14 @@ use crate::common::utils::{
     check_exclude_pattern, check_include_pattern, filter_json_values, get_value_from_json_map,
     get_value_from_json_map_opt, MultiValue,
 };
+use crate::data_types::index::{FloatIndexParams, IntegerIndexParams, KeywordIndexParams};
 use crate::data_types::text_index::TextIndexParams;
-use crate::data_types::vectors::{DenseVector, VectorElementType, VectorStruct};
+use crate::data_types::vectors::{DenseVector, MultiDenseVector, VectorElementType, VectorStruct};
 use crate::index::sparse_index::sparse_index_config::{SparseIndexConfig, SparseIndexType};
 use crate::spaces::metric::Metric;
-use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+use crate::spaces::simple::{
+    CosineMetric, DotProductMetric, EuclidMetric, HammingMetric, ManhattanMetric,
+};
 use crate::vector_storage::simple_sparse_vector_storage::SPARSE_VECTOR_DISTANCE;
 
 pub type PayloadKeyType = String;
@@ -41,6 +44,10 @@ pub type TagType = u64;
 pub type FloatPayloadType = f64;
 /// Type of integer point payload
 pub type IntPayloadType = i64;
+/// Type of datetime point payload, given as RFC 3339 string
+pub type DateTimePayloadType = chrono::DateTime<chrono::Utc>;
+/// Type of UUID point payload, stored as 128-bit integer
+pub type UuidIntType = u128;
 
 pub const VECTOR_ELEMENT_SIZE: usize = size_of::<VectorElementType>();
 
@@ -126,6 +133,9 @@ pub enum Distance {
     Dot,
     // <https://simple.wikipedia.org/wiki/Manhattan_distance>
     Manhattan,
+    // <https://en.wikipedia.org/wiki/Hamming_distance>
+    // Number of differing bits of binary vectors, packed by the client into `uint8` elements
+    Hamming,
 }
 
 impl Distance {
@@ -135,22 +145,47 @@ impl Distance {
             Distance::Euclid => EuclidMetric::preprocess(vector),
             Distance::Dot => DotProductMetric::preprocess(vector),
             Distance::Manhattan => ManhattanMetric::preprocess(vector),
+            Distance::Hamming => HammingMetric::preprocess(vector),
         }
     }
 
+    /// Preprocess each vector of the multivector separately
+    pub fn preprocess_multi_vector(&self, vector: MultiDenseVector) -> MultiDenseVector {
+        let dim = vector.dim;
+        let flattened_vectors = vector
+            .multi_vectors()
+            .flat_map(|vector| self.preprocess_vector(vector.to_vec()))
+            .collect();
+        MultiDenseVector::new(flattened_vectors, dim)
+    }
+
     pub fn postprocess_score(&self, score: ScoreType) -> ScoreType {
         match self {
             Distance::Cosine => CosineMetric::postprocess(score),
             Distance::Euclid => EuclidMetric::postprocess(score),
             Distance::Dot => DotProductMetric::postprocess(score),
             Distance::Manhattan => ManhattanMetric::postprocess(score),
+            Distance::Hamming => HammingMetric::postprocess(score),
         }
     }
 
     pub fn distance_order(&self) -> Order {
         match self {
             Distance::Cosine | Distance::Dot => Order::LargeBetter,
-            Distance::Euclid | Distance::Manhattan => Order::SmallBetter,
+            Distance::Euclid | Distance::Manhattan | Distance::Hamming => Order::SmallBetter,
+        }
+    }
+
+    /// Convert score threshold of the request into the internal scores, used by the index
+    ///
+    /// Internal scores are always "larger is better".
+    pub fn preprocess_score_threshold(&self, threshold: ScoreType) -> ScoreType {
+        match self {
+            Distance::Cosine | Distance::Dot => threshold,
+            // Internal score is the negative squared distance
+            Distance::Euclid => -(threshold * threshold),
+            // Internal score is the negative distance
+            Distance::Manhattan | Distance::Hamming => -threshold,
         }
     }
 
@@ -171,6 +206,7 @@ impl Distance {
             Distance::Euclid => EuclidMetric::similarity(v1, v2),
             Distance::Dot => DotProductMetric::similarity(v1, v2),
             Distance::Manhattan => ManhattanMetric::similarity(v1, v2),
+            Distance::Hamming => HammingMetric::similarity(v1, v2),
         }
     }
 }
@@ -249,12 +285,10 @@ impl PayloadIndexInfo {
                 params: None,
                 points: points_count,
             },
-            PayloadFieldSchema::FieldParams(schema_params) => match schema_params {
-                PayloadSchemaParams::Text(_) => PayloadIndexInfo {
-                    data_type: PayloadSchemaType::Text,
-                    params: Some(schema_params),
-                    points: points_count,
-                },
+            PayloadFieldSchema::FieldParams(schema_params) => PayloadIndexInfo {
+                data_type: schema_params.kind(),
+                params: Some(schema_params),
+                points: points_count,
             },
         }
     }
@@ -326,6 +360,10 @@ pub struct SearchParams {
     /// Size of the beam in a beam-search. Larger the value - more accurate the result, more time required for search.
     pub hnsw_ef: Option<usize>,
 
+    /// Params relevant to IVF index
+    /// Number of nearest inverted lists to scan. Larger the value - more accurate the result, more time required for search.
+    pub ivf_nprobe: Option<usize>,
+
     /// Search without approximation. If set to true, search may run long but with exact results.
     #[serde(default)]
     pub exact: bool,
@@ -340,6 +378,17 @@ pub struct SearchParams {
     /// guarantee that all uploaded vectors will be included in search results
     #[serde(default)]
     pub indexed_only: bool,
+
+    /// If enabled, filtered HNSW search also looks at the neighbours of points rejected by the filter.
+    /// Improves recall of searches with restrictive filters, at the cost of additional filter checks.
+    #[serde(default)]
+    pub acorn: bool,
+
+    /// Internal score threshold of the search, derived from `score_threshold` of the request.
+    /// Allows graph search to skip worse points and terminate early. Not a part of the API.
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub score_threshold: Option<ScoreType>,
 }
 
 /// Vector index configuration
@@ -353,6 +402,12 @@ pub enum Indexes {
     /// Use filterable HNSW index for approximate search. Is very fast even on a very huge collections,
     /// but require additional space to store index and additional time to build it.
     Hnsw(HnswConfig),
+    /// Use single-layer Vamana graph for approximate search. The graph is served from disk,
+    /// which makes it suitable for collections much larger than RAM.
+    Vamana(VamanaConfig),
+    /// Use inverted file index with product quantization for approximate search.
+    /// Requires little memory, which makes it suitable for very large collections, which are rarely updated.
+    Ivf(IvfConfig),
 }
 
 impl Indexes {
@@ -360,12 +415,14 @@ impl Indexes {
         match self {
             Indexes::Plain {} => false,
             Indexes::Hnsw(_) => true,
+            Indexes::Vamana(_) => true,
+            Indexes::Ivf(_) => true,
         }
     }
 }
 
 /// Config of HNSW index
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct HnswConfig {
     /// Number of edges per node in the index graph. Larger the value - more accurate the search, more space required.
@@ -388,8 +445,60 @@ pub struct HnswConfig {
     /// Custom M param for hnsw graph built for payload index. If not set, default M will be used.
     #[serde(default, skip_serializing_if = "Option::is_none")] // Better backward compatibility
     pub payload_m: Option<usize>,
+    /// Custom M param of payload-based HNSW links for specific payload fields.
+    /// Overrides `payload_m` for the listed fields, e.g. to reduce memory used by links of
+    /// high-cardinality fields. Value of 0 disables additional links for the field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_m_overrides: Option<BTreeMap<PayloadKeyType, usize>>,
+    /// Seed for the random level assignment of HNSW index building. If set, building the index
+    /// for the same data produces the same graph. Note: index building with a seed is
+    /// single-threaded, to keep the insertion order deterministic. Default: not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Extend the candidate list with neighbours of the candidates before selecting links
+    /// with the heuristic. Improves recall on highly clustered data, at the cost of slower
+    /// index building. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extend_candidates: Option<bool>,
+    /// Fill up the links of a node with candidates discarded by the heuristic, if it selected
+    /// less than M of them. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_pruned_connections: Option<bool>,
+    /// Store HNSW links compressed and serve them from disk via mmap. Links of each node are
+    /// sorted, delta-encoded and stored as varints. Reduces memory and disk usage of the index,
+    /// at the cost of slower search. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compress_links: Option<bool>,
+    /// Target recall of the search with default params. If set, `ef` used for searches without
+    /// explicit `hnsw_ef` is calibrated after the index is built: the smallest `ef` reaching
+    /// this recall on a sample of queries, compared to exact search. Default: not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub target_recall: Option<f64>,
+    /// Multiplier of the level generation factor `1/ln(m)`. Values below 1 flatten the hierarchy,
+    /// values above 1 make it deeper. Doesn't affect the number of links per node. Default: 1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0.0))]
+    pub level_factor_multiplier: Option<f64>,
+    /// Number of neighbours to consider during the index building on each graph level, starting
+    /// from level 0. Levels above the end of the list use its last value. Overrides
+    /// `ef_construct`. Default: not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ef_construct_levels: Option<Vec<usize>>,
+    /// Refine the graph after it is built: links of every point are selected once more from its
+    /// neighbours and their neighbours. Improves recall for the same `m`, at the cost of longer
+    /// index building. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refine: Option<bool>,
+    /// Link new points into the built graph on upsert, instead of writing them into a separate
+    /// unindexed segment. Only applies if both the graph and the vectors are kept in RAM
+    /// (`on_disk` and `compress_links` are not set) and vectors are not quantized. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub appendable: Option<bool>,
 }
 
+impl Eq for HnswConfig {}
+
 impl HnswConfig {
     /// Detect configuration mismatch against `other` that requires rebuilding
     ///
@@ -404,6 +513,20 @@ impl HnswConfig {
             || self.ef_construct != other.ef_construct
             || self.full_scan_threshold != other.full_scan_threshold
             || self.payload_m != other.payload_m
+            || self.payload_m_overrides != other.payload_m_overrides
+            || self.extend_candidates.unwrap_or_default()
+                != other.extend_candidates.unwrap_or_default()
+            || self.keep_pruned_connections.unwrap_or_default()
+                != other.keep_pruned_connections.unwrap_or_default()
+            || self.compress_links.unwrap_or_default() != other.compress_links.unwrap_or_default()
+            // Calibration of `ef` happens while building the index
+            || self.target_recall != other.target_recall
+            || self.level_factor_multiplier.unwrap_or(1.0)
+                != other.level_factor_multiplier.unwrap_or(1.0)
+            || self.ef_construct_levels != other.ef_construct_levels
+            || self.refine.unwrap_or_default() != other.refine.unwrap_or_default()
+            // Appendability is a part of the segment config
+            || self.appendable.unwrap_or_default() != other.appendable.unwrap_or_default()
             // Data on disk is the same, we have a unit test for that. We can eventually optimize
             // this to just reload the collection rather than optimizing it again as a whole just
             // to flip this flag
@@ -415,6 +538,119 @@ const fn default_max_indexing_threads() -> usize {
     0
 }
 
+/// Config of Vamana index
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct VamanaConfig {
+    /// Maximal number of edges per node in the index graph. Larger the value - more accurate the search, more space required.
+    #[serde(default = "default_vamana_max_degree")]
+    #[validate(range(min = 4))]
+    pub max_degree: usize,
+    /// Size of the candidate list during the index building. Larger the value - more accurate the search, more time required to build index.
+    #[serde(default = "default_vamana_list_size")]
+    #[validate(range(min = 4))]
+    pub build_list_size: usize,
+    /// Size of the candidate list during the search, if not set by search params.
+    #[serde(default = "default_vamana_list_size")]
+    #[validate(range(min = 1))]
+    pub search_list_size: usize,
+    /// Minimal size (in KiloBytes) of vectors to search with the graph.
+    /// Searches with fewer matching vectors are done with full scan.
+    #[serde(default = "default_vamana_full_scan_threshold")]
+    pub full_scan_threshold: usize,
+}
+
+impl Default for VamanaConfig {
+    fn default() -> Self {
+        VamanaConfig {
+            max_degree: default_vamana_max_degree(),
+            build_list_size: default_vamana_list_size(),
+            search_list_size: default_vamana_list_size(),
+            full_scan_threshold: default_vamana_full_scan_threshold(),
+        }
+    }
+}
+
+impl VamanaConfig {
+    /// Detect configuration mismatch against `other` that requires rebuilding
+    ///
+    /// `search_list_size` is only used at search time, so changing it doesn't require a rebuild.
+    pub fn mismatch_requires_rebuild(&self, other: &Self) -> bool {
+        self.max_degree != other.max_degree
+            || self.build_list_size != other.build_list_size
+            || self.full_scan_threshold != other.full_scan_threshold
+    }
+}
+
+const fn default_vamana_max_degree() -> usize {
+    64
+}
+
+const fn default_vamana_list_size() -> usize {
+    100
+}
+
+const fn default_vamana_full_scan_threshold() -> usize {
+    DEFAULT_FULL_SCAN_THRESHOLD
+}
+
+/// Config of IVF-PQ index
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct IvfConfig {
+    /// Number of inverted lists, vectors are clustered into. Larger the value - faster the search, more time required to build index.
+    /// If 0 - square root of the number of vectors is used.
+    #[serde(default)]
+    pub num_lists: usize,
+    /// Number of vector dimensions, encoded into a single byte by product quantization.
+    /// Larger the value - less memory required, less accurate the search.
+    #[serde(default = "default_ivf_pq_subvector_dim")]
+    #[validate(range(min = 1))]
+    pub pq_subvector_dim: usize,
+    /// Number of nearest inverted lists to scan during the search, if not set by search params.
+    #[serde(default = "default_ivf_nprobe")]
+    #[validate(range(min = 1))]
+    pub nprobe: usize,
+    /// Minimal size (in KiloBytes) of vectors to search with the index.
+    /// Searches with fewer matching vectors are done with full scan.
+    #[serde(default = "default_ivf_full_scan_threshold")]
+    pub full_scan_threshold: usize,
+}
+
+impl Default for IvfConfig {
+    fn default() -> Self {
+        IvfConfig {
+            num_lists: 0,
+            pq_subvector_dim: default_ivf_pq_subvector_dim(),
+            nprobe: default_ivf_nprobe(),
+            full_scan_threshold: default_ivf_full_scan_threshold(),
+        }
+    }
+}
+
+impl IvfConfig {
+    /// Detect configuration mismatch against `other` that requires rebuilding
+    ///
+    /// `nprobe` is only used at search time, so changing it doesn't require a rebuild.
+    pub fn mismatch_requires_rebuild(&self, other: &Self) -> bool {
+        self.num_lists != other.num_lists
+            || self.pq_subvector_dim != other.pq_subvector_dim
+            || self.full_scan_threshold != other.full_scan_threshold
+    }
+}
+
+const fn default_ivf_pq_subvector_dim() -> usize {
+    4
+}
+
+const fn default_ivf_nprobe() -> usize {
+    16
+}
+
+const fn default_ivf_full_scan_threshold() -> usize {
+    DEFAULT_FULL_SCAN_THRESHOLD
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum CompressionRatio {
@@ -570,6 +806,16 @@ impl Default for HnswConfig {
             max_indexing_threads: 0,
             on_disk: Some(false),
             payload_m: None,
+            payload_m_overrides: None,
+            seed: None,
+            extend_candidates: None,
+            keep_pruned_connections: None,
+            compress_links: None,
+            target_recall: None,
+            level_factor_multiplier: None,
+            ef_construct_levels: None,
+            refine: None,
+            appendable: None,
         }
     }
 }
@@ -606,14 +852,24 @@ pub enum PayloadStorageType {
     InMemory,
     // Store payload on disk only, read each time it is requested
     OnDisk,
+    // Store payload in append-only log without RocksDB, read it via memory mapping
+    Mmap,
 }
 
 impl PayloadStorageType {
     pub fn is_on_disk(&self) -> bool {
-        matches!(self, PayloadStorageType::OnDisk)
+        matches!(self, PayloadStorageType::OnDisk | PayloadStorageType::Mmap)
     }
 }
 
+/// Compression of payload records in the persistent payload storage
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadCompression {
+    /// Zstandard compression of each record
+    Zstd,
+}
+
 #[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct SegmentConfig {
@@ -624,6 +880,9 @@ pub struct SegmentConfig {
     pub sparse_vector_data: HashMap<String, SparseVectorDataConfig>,
     /// Defines payload storage type
     pub payload_storage_type: PayloadStorageType,
+    /// Compression of persisted payload records. If not set - records are not compressed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_compression: Option<PayloadCompression>,
 }
 
 impl SegmentConfig {
@@ -652,6 +911,14 @@ impl SegmentConfig {
         }
     }
 
+    /// Distance to preprocess vectors with before storing them, `None` if they are stored as is
+    pub fn preprocessing_distance(&self, vector_name: &str) -> Option<Distance> {
+        match self.vector_data.get(vector_name) {
+            Some(config) if config.keeps_original() => None,
+            _ => self.distance(vector_name),
+        }
+    }
+
     /// Check if any vector storages are indexed
     pub fn is_any_vector_indexed(&self) -> bool {
         self.vector_data
@@ -704,6 +971,24 @@ pub enum VectorStorageType {
     ChunkedMmap,
 }
 
+/// Element type, in which vectors are stored and scored
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Copy, Clone, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum VectorStorageDatatype {
+    /// Single-precision floating point, 4 bytes per element
+    #[default]
+    Float32,
+    /// Half-precision floating point, 2 bytes per element
+    ///
+    /// Halves memory usage, at the cost of precision of stored vectors.
+    Float16,
+    /// Unsigned 8-bit integer, 1 byte per element
+    ///
+    /// For embeddings, which are already quantized by the client into `0..=255` range.
+    /// Values are rounded and saturated on conversion. Not compatible with `Cosine` distance.
+    Uint8,
+}
+
 impl VectorStorageType {
     /// Whether this storage type is a mmap on disk
     pub fn is_on_disk(&self) -> bool {
@@ -728,16 +1013,37 @@ pub struct VectorDataConfig {
     pub index: Indexes,
     /// Vector specific quantization config that overrides collection config
     pub quantization_config: Option<QuantizationConfig>,
+    /// If set, each point stores multiple vectors of this size, compared as a whole
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multivector_config: Option<MultiVectorConfig>,
+    /// Element type of stored vectors. If none - `float32` is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datatype: Option<VectorStorageDatatype>,
+    /// If true, `Cosine` vectors are stored without normalization, together with their norms
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_original: Option<bool>,
+    /// Per-dimension weights, applied during scoring of `Euclid` and `Dot` distances
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimension_weights: Option<Vec<f32>>,
 }
 
 impl VectorDataConfig {
     /// Whether this vector data can be appended to
     ///
     /// This requires an index and storage type that both support appending.
+    /// HNSW index is appendable if enabled, with the main graph and vectors kept in RAM.
     pub fn is_appendable(&self) -> bool {
-        let is_index_appendable = match self.index {
+        let is_index_appendable = match &self.index {
             Indexes::Plain {} => true,
-            Indexes::Hnsw(_) => false,
+            Indexes::Hnsw(hnsw_config) => {
+                hnsw_config.appendable.unwrap_or_default()
+                    && hnsw_config.m > 0
+                    && !hnsw_config.on_disk.unwrap_or_default()
+                    && !hnsw_config.compress_links.unwrap_or_default()
+                    && self.quantization_config.is_none()
+            }
+            Indexes::Vamana(_) => false,
+            Indexes::Ivf(_) => false,
         };
         let is_storage_appendable = match self.storage_type {
             VectorStorageType::Memory => true,
@@ -746,6 +1052,34 @@ impl VectorDataConfig {
         };
         is_index_appendable && is_storage_appendable
     }
+
+    /// Whether `Cosine` vectors are stored un-normalized, with norms cached by the storage
+    ///
+    /// Only supported for single `float32` vectors, the option is ignored otherwise.
+    pub fn keeps_original(&self) -> bool {
+        self.keep_original == Some(true)
+            && self.distance == Distance::Cosine
+            && self.multivector_config.is_none()
+            && self.datatype.unwrap_or_default() == VectorStorageDatatype::Float32
+    }
+}
+
+/// Function to compare multivectors
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MultiVectorComparator {
+    /// Sum of similarities of each query vector to its most similar stored vector
+    #[default]
+    MaxSim,
+}
+
+/// Config of multivectors, where each point is a set of vectors of the same size
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct MultiVectorConfig {
+    /// Function to compare multivectors. Default: max_sim
+    #[serde(default)]
+    pub comparator: MultiVectorComparator,
 }
 
 /// Config of single sparse vector data storage
@@ -836,6 +1170,11 @@ impl GeoPoint {
         Self::validate(lon, lat)?;
         Ok(GeoPoint { lon, lat })
     }
+
+    /// Haversine distance to the other point in meters
+    pub fn distance(&self, other: &GeoPoint) -> f64 {
+        Point::new(self.lon, self.lat).haversine_distance(&Point::new(other.lon, other.lat))
+    }
 }
 
 impl TryFrom<GeoPointShadow> for GeoPoint {
@@ -886,6 +1225,26 @@ impl Payload {
         utils::remove_value_from_json_map(path, &mut self.0).values()
     }
 
+    /// Move value from `path` to `new_path`, replacing the existing value at `new_path`
+    ///
+    /// Paths consist of object keys only, array notation and wildcards are not supported.
+    /// Returns `false` if there is no value at `path`, or any of the paths has a wildcard.
+    pub fn rename(&mut self, path: &str, new_path: &str) -> bool {
+        // Wildcard may match multiple values, which can't be moved to a single key
+        if utils::is_wildcard_path(path) || utils::is_wildcard_path(new_path) {
+            return false;
+        }
+        let Some(value) = utils::remove_value_from_json_map(path, &mut self.0)
+            .values()
+            .into_iter()
+            .next()
+        else {
+            return false;
+        };
+        utils::set_value_to_json_map(new_path, &mut self.0, value);
+        true
+    }
+
     pub fn len(&self) -> usize {
         self.0.len()
     }
@@ -1059,15 +1418,42 @@ pub enum PayloadSchemaType {
     Geo,
     Text,
     Bool,
+    Datetime,
+    Uuid,
 }
 
 /// Payload type with parameters
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
 #[serde(untagged, rename_all = "snake_case")]
 pub enum PayloadSchemaParams {
+    Keyword(KeywordIndexParams),
+    Integer(IntegerIndexParams),
+    Float(FloatIndexParams),
     Text(TextIndexParams),
 }
 
+impl PayloadSchemaParams {
+    /// Type of the payload field, indexed with these parameters
+    pub fn kind(&self) -> PayloadSchemaType {
+        match self {
+            PayloadSchemaParams::Keyword(_) => PayloadSchemaType::Keyword,
+            PayloadSchemaParams::Integer(_) => PayloadSchemaType::Integer,
+            PayloadSchemaParams::Float(_) => PayloadSchemaType::Float,
+            PayloadSchemaParams::Text(_) => PayloadSchemaType::Text,
+        }
+    }
+
+    /// Whether the index is stored on disk instead of RAM
+    pub fn is_on_disk(&self) -> bool {
+        match self {
+            PayloadSchemaParams::Keyword(params) => params.on_disk.unwrap_or(false),
+            PayloadSchemaParams::Integer(params) => params.on_disk.unwrap_or(false),
+            PayloadSchemaParams::Float(params) => params.on_disk.unwrap_or(false),
+            PayloadSchemaParams::Text(params) => params.on_disk.unwrap_or(false),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
 #[serde(untagged, rename_all = "snake_case")]
 pub enum PayloadFieldSchema {
@@ -1075,6 +1461,24 @@ pub enum PayloadFieldSchema {
     FieldParams(PayloadSchemaParams),
 }
 
+impl PayloadFieldSchema {
+    /// Type of the payload field, covered by the index
+    pub fn kind(&self) -> PayloadSchemaType {
+        match self {
+            PayloadFieldSchema::FieldType(field_type) => *field_type,
+            PayloadFieldSchema::FieldParams(params) => params.kind(),
+        }
+    }
+
+    /// Whether the index of the field is stored on disk instead of RAM
+    pub fn is_on_disk(&self) -> bool {
+        match self {
+            PayloadFieldSchema::FieldType(_) => false,
+            PayloadFieldSchema::FieldParams(params) => params.is_on_disk(),
+        }
+    }
+}
+
 impl From<PayloadSchemaType> for PayloadFieldSchema {
     fn from(payload_schema_type: PayloadSchemaType) -> Self {
         PayloadFieldSchema::FieldType(payload_schema_type)
@@ -1086,9 +1490,9 @@ impl TryFrom<PayloadIndexInfo> for PayloadFieldSchema {
 
     fn try_from(index_info: PayloadIndexInfo) -> Result<Self, Self::Error> {
         match (index_info.data_type, index_info.params) {
-            (PayloadSchemaType::Text, Some(PayloadSchemaParams::Text(params))) => Ok(
-                PayloadFieldSchema::FieldParams(PayloadSchemaParams::Text(params)),
-            ),
+            (data_type, Some(params)) if params.kind() == data_type => {
+                Ok(PayloadFieldSchema::FieldParams(params))
+            }
             (data_type, Some(_)) => Err(format!(
                 "Payload field with type {data_type:?} has unexpected params"
             )),
@@ -1143,7 +1547,7 @@ where
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
 #[serde(untagged)]
 pub enum ValueVariants {
     Keyword(String),
@@ -1170,11 +1574,52 @@ pub struct MatchValue {
 #[serde(rename_all = "snake_case")]
 pub struct MatchText {
     pub text: String,
+    /// Name of the text index analyzer, which tokenizes the text.
+    /// If not set, the main configuration of the full-text index is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analyzer: Option<String>,
 }
 
 impl From<String> for MatchText {
     fn from(text: String) -> Self {
-        MatchText { text }
+        MatchText {
+            text,
+            analyzer: None,
+        }
+    }
+}
+
+/// Full-text match of the strings, which requires tokens of the phrase to follow one another.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchPhrase {
+    pub phrase: String,
+    /// Name of the text index analyzer, which tokenizes the phrase.
+    /// If not set, the main configuration of the full-text index is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analyzer: Option<String>,
+}
+
+impl From<String> for MatchPhrase {
+    fn from(phrase: String) -> Self {
+        MatchPhrase {
+            phrase,
+            analyzer: None,
+        }
+    }
+}
+
+/// Match of the strings, which start with the given prefix.
+/// For full-text index, any token of the string should start with the prefix.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchPrefix {
+    pub prefix: String,
+}
+
+impl From<String> for MatchPrefix {
+    fn from(prefix: String) -> Self {
+        MatchPrefix { prefix }
     }
 }
 
@@ -1198,6 +1643,8 @@ pub struct MatchExcept {
 pub enum MatchInterface {
     Value(MatchValue),
     Text(MatchText),
+    Phrase(MatchPhrase),
+    Prefix(MatchPrefix),
     Any(MatchAny),
     Except(MatchExcept),
 }
@@ -1208,6 +1655,8 @@ pub enum MatchInterface {
 pub enum Match {
     Value(MatchValue),
     Text(MatchText),
+    Phrase(MatchPhrase),
+    Prefix(MatchPrefix),
     Any(MatchAny),
     Except(MatchExcept),
 }
@@ -1219,7 +1668,7 @@ impl Match {
 
     #[cfg(test)]
     fn new_text(text: &str) -> Self {
-        Self::Text(MatchText { text: text.into() })
+        Self::Text(MatchText::from(text.to_owned()))
     }
 
     pub fn new_any(any: AnyVariants) -> Self {
@@ -1241,7 +1690,17 @@ impl From<MatchInterface> for Match {
     fn from(value: MatchInterface) -> Self {
         match value {
             MatchInterface::Value(value) => Self::Value(MatchValue { value: value.value }),
-            MatchInterface::Text(text) => Self::Text(MatchText { text: text.text }),
+            MatchInterface::Text(text) => Self::Text(MatchText {
+                text: text.text,
+                analyzer: text.analyzer,
+            }),
+            MatchInterface::Phrase(phrase) => Self::Phrase(MatchPhrase {
+                phrase: phrase.phrase,
+                analyzer: phrase.analyzer,
+            }),
+            MatchInterface::Prefix(prefix) => Self::Prefix(MatchPrefix {
+                prefix: prefix.prefix,
+            }),
             MatchInterface::Any(any) => Self::Any(MatchAny { any: any.any }),
             MatchInterface::Except(except) => Self::Except(MatchExcept {
                 except: except.except,
@@ -1337,6 +1796,157 @@ impl Range {
     }
 }
 
+/// Datetime range filter request
+///
+/// Bounds are RFC 3339 datetimes, or expressions relative to the current time of the server,
+/// like `now`, `now-7d` or `now-1d+12h`. Supported units are `s`, `m`, `h`, `d` and `w`.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Default, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct DatetimeRange {
+    /// point.key < range.lt
+    #[serde(default, deserialize_with = "deserialize_datetime_bound")]
+    #[schemars(with = "Option<String>")]
+    pub lt: Option<DateTimePayloadType>,
+    /// point.key > range.gt
+    #[serde(default, deserialize_with = "deserialize_datetime_bound")]
+    #[schemars(with = "Option<String>")]
+    pub gt: Option<DateTimePayloadType>,
+    /// point.key >= range.gte
+    #[serde(default, deserialize_with = "deserialize_datetime_bound")]
+    #[schemars(with = "Option<String>")]
+    pub gte: Option<DateTimePayloadType>,
+    /// point.key <= range.lte
+    #[serde(default, deserialize_with = "deserialize_datetime_bound")]
+    #[schemars(with = "Option<String>")]
+    pub lte: Option<DateTimePayloadType>,
+}
+
+/// Relative expressions are resolved once the request is received,
+/// so all segments and shards check the same absolute range
+fn deserialize_datetime_bound<'de, D>(
+    deserializer: D,
+) -> Result<Option<DateTimePayloadType>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let Some(value) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    value
+        .parse::<DateTimePayloadType>()
+        .ok()
+        .or_else(|| parse_relative_datetime(&value, chrono::Utc::now()))
+        .map(Some)
+        .ok_or_else(|| serde::de::Error::custom(format!("invalid datetime: {value}")))
+}
+
+/// Parse datetime expression relative to `now`, like `now-7d` or `now-1d+12h`
+pub fn parse_relative_datetime(
+    value: &str,
+    now: DateTimePayloadType,
+) -> Option<DateTimePayloadType> {
+    let mut rest = value.trim().strip_prefix("now")?;
+    let mut datetime = now;
+    while !rest.is_empty() {
+        let (is_add, offset) = if let Some(offset) = rest.strip_prefix('+') {
+            (true, offset)
+        } else {
+            (false, rest.strip_prefix('-')?)
+        };
+        let digits = offset
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(offset.len());
+        let amount: u64 = offset[..digits].parse().ok()?;
+        let mut unit = offset[digits..].chars();
+        let unit_seconds = match unit.next()? {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 24 * 60 * 60,
+            'w' => 7 * 24 * 60 * 60,
+            _ => return None,
+        };
+        let duration = chrono::Duration::from_std(std::time::Duration::from_secs(
+            amount.checked_mul(unit_seconds)?,
+        ))
+        .ok()?;
+        datetime = if is_add {
+            datetime.checked_add_signed(duration)?
+        } else {
+            datetime.checked_sub_signed(duration)?
+        };
+        rest = unit.as_str();
+    }
+    Some(datetime)
+}
+
+impl DatetimeRange {
+    pub fn check_range(&self, datetime: DateTimePayloadType) -> bool {
+        self.lt.map_or(true, |x| datetime < x)
+            && self.gt.map_or(true, |x| datetime > x)
+            && self.lte.map_or(true, |x| datetime <= x)
+            && self.gte.map_or(true, |x| datetime >= x)
+    }
+
+    /// Convert into a range over timestamps in microseconds, as stored in the datetime index
+    pub fn to_timestamp_range(&self) -> Range {
+        let to_timestamp = |datetime: DateTimePayloadType| datetime.timestamp_micros() as f64;
+        Range {
+            lt: self.lt.map(to_timestamp),
+            gt: self.gt.map(to_timestamp),
+            gte: self.gte.map(to_timestamp),
+            lte: self.lte.map(to_timestamp),
+        }
+    }
+
+    /// Convert from a range over timestamps in microseconds
+    pub fn from_timestamp_range(range: &Range) -> Self {
+        let from_timestamp = |timestamp: FloatPayloadType| {
+            chrono::NaiveDateTime::from_timestamp_micros(timestamp as i64).map(|datetime| {
+                DateTimePayloadType::from_naive_utc_and_offset(datetime, chrono::Utc)
+            })
+        };
+        DatetimeRange {
+            lt: range.lt.and_then(from_timestamp),
+            gt: range.gt.and_then(from_timestamp),
+            gte: range.gte.and_then(from_timestamp),
+            lte: range.lte.and_then(from_timestamp),
+        }
+    }
+}
+
+/// Parse RFC 3339 datetime string of a payload value
+pub fn parse_datetime(value: &str) -> Option<DateTimePayloadType> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|datetime| datetime.with_timezone(&chrono::Utc))
+}
+
+/// Parse UUID string in any of the supported formats into its 128-bit representation
+pub fn parse_uuid(value: &str) -> Option<UuidIntType> {
+    Uuid::parse_str(value).ok().map(|uuid| uuid.as_u128())
+}
+
+/// Range filter request, over numbers or datetimes
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum RangeInterface {
+    Float(Range),
+    DateTime(DatetimeRange),
+}
+
+impl From<Range> for RangeInterface {
+    fn from(range: Range) -> Self {
+        RangeInterface::Float(range)
+    }
+}
+
+impl From<DatetimeRange> for RangeInterface {
+    fn from(range: DatetimeRange) -> Self {
+        RangeInterface::DateTime(range)
+    }
+}
+
 /// Values count filter request
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -1352,13 +1962,20 @@ pub struct ValuesCount {
 }
 
 impl ValuesCount {
-    pub fn check_count(&self, value: &Value) -> bool {
-        let count = match value {
+    /// Number of values in a payload value: array length, 0 for null and 1 otherwise
+    pub fn count_values(value: &Value) -> usize {
+        match value {
             Value::Null => 0,
             Value::Array(array) => array.len(),
             _ => 1,
-        };
+        }
+    }
+
+    pub fn check_count(&self, value: &Value) -> bool {
+        self.check_values_count(Self::count_values(value))
+    }
 
+    pub fn check_values_count(&self, count: usize) -> bool {
         self.lt.map_or(true, |x| count < x)
             && self.gt.map_or(true, |x| count > x)
             && self.lte.map_or(true, |x| count <= x)
@@ -1401,8 +2018,7 @@ pub struct GeoRadius {
 
 impl GeoRadius {
     pub fn check_point(&self, point: &GeoPoint) -> bool {
-        let query_center = Point::new(self.center.lon, self.center.lat);
-        query_center.haversine_distance(&Point::new(point.lon, point.lat)) < self.radius
+        self.center.distance(point) < self.radius
     }
 }
 
@@ -1536,7 +2152,7 @@ pub struct FieldCondition {
     /// Check if point has field with a given value
     pub r#match: Option<Match>,
     /// Check if points value lies in a given range
-    pub range: Option<Range>,
+    pub range: Option<RangeInterface>,
     /// Check if points geo location lies in a given area
     pub geo_bounding_box: Option<GeoBoundingBox>,
     /// Check if geo point is within a given radius
@@ -1560,11 +2176,11 @@ impl FieldCondition {
         }
     }
 
-    pub fn new_range(key: impl Into<PayloadKeyType>, range: Range) -> Self {
+    pub fn new_range(key: impl Into<PayloadKeyType>, range: impl Into<RangeInterface>) -> Self {
         Self {
             key: key.into(),
             r#match: None,
-            range: Some(range),
+            range: Some(range.into()),
             geo_bounding_box: None,
             geo_radius: None,
             geo_polygon: None,
@@ -1690,12 +2306,57 @@ impl From<HashSet<PointIdType>> for HasIdCondition {
     }
 }
 
+/// Range of point IDs, numeric IDs are ordered before UUIDs
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IdRange {
+    /// Point ID greater than or equal
+    pub gte: Option<PointIdType>,
+    /// Point ID less than
+    pub lt: Option<PointIdType>,
+}
+
+impl IdRange {
+    pub fn contains(&self, id: PointIdType) -> bool {
+        self.gte.map_or(true, |gte| gte <= id) && self.lt.map_or(true, |lt| id < lt)
+    }
+}
+
+/// Select points with IDs in a given range, without listing all of them
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct HasIdRangeCondition {
+    pub has_id_range: IdRange,
+}
+
+impl From<IdRange> for HasIdRangeCondition {
+    fn from(range: IdRange) -> Self {
+        HasIdRangeCondition {
+            has_id_range: range,
+        }
+    }
+}
+
+/// Select points, which have the named vector assigned
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct HasVectorCondition {
+    pub has_vector: String,
+}
+
+impl From<String> for HasVectorCondition {
+    fn from(vector_name: String) -> Self {
+        HasVectorCondition {
+            has_vector: vector_name,
+        }
+    }
+}
+
 /// Select points with payload for a specified nested field
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Validate)]
 pub struct Nested {
     pub key: PayloadKeyType,
     #[validate]
     pub filter: Filter,
+    /// Number of nested objects, which must match the filter. Default is at least one.
+    pub count: Option<ValuesCount>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Validate)]
@@ -1728,6 +2389,15 @@ impl NestedCondition {
     pub fn filter(&self) -> &Filter {
         &self.nested.filter
     }
+
+    /// Check matches of the filter against nested objects, stops as soon as the result is known
+    pub fn check_matches(&self, matches: impl IntoIterator<Item = bool>) -> bool {
+        let mut matches = matches.into_iter().filter(|matches| *matches);
+        match &self.nested.count {
+            None => matches.next().is_some(),
+            Some(count) => count.check_values_count(matches.count()),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
@@ -1742,6 +2412,10 @@ pub enum Condition {
     IsNull(IsNullCondition),
     /// Check if points id is in a given set
     HasId(HasIdCondition),
+    /// Check if points id is in a given range
+    HasIdRange(HasIdRangeCondition),
+    /// Check if point has the named vector assigned
+    HasVector(HasVectorCondition),
     /// Nested filters
     Nested(NestedCondition),
     /// Nested filter
@@ -1754,6 +2428,7 @@ impl Condition {
             nested: Nested {
                 key: key.into(),
                 filter,
+                count: None,
             },
         })
     }
@@ -1763,7 +2438,10 @@ impl Condition {
 impl Validate for Condition {
     fn validate(&self) -> Result<(), ValidationErrors> {
         match self {
-            Condition::HasId(_) | Condition::IsEmpty(_) | Condition::IsNull(_) => Ok(()),
+            Condition::HasId(_)
+            | Condition::HasVector(_)
+            | Condition::IsEmpty(_)
+            | Condition::IsNull(_) => Ok(()),
             Condition::Field(field_condition) => field_condition.validate(),
             Condition::Nested(nested_condition) => nested_condition.validate(),
             Condition::Filter(filter) => filter.validate(),
@@ -1950,12 +2628,23 @@ pub struct WithPayload {
     pub payload_selector: Option<PayloadSelector>,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct MinShould {
+    #[validate]
+    pub conditions: Vec<Condition>,
+    pub min_count: usize,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Default)]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub struct Filter {
     /// At least one of those conditions should match
     #[validate]
     pub should: Option<Vec<Condition>>,
+    /// At least minimum amount of given conditions should match
+    #[validate]
+    pub min_should: Option<MinShould>,
     /// All conditions must match
     #[validate]
     pub must: Option<Vec<Condition>>,
@@ -1968,6 +2657,7 @@ impl Filter {
     pub fn new_should(condition: Condition) -> Self {
         Filter {
             should: Some(vec![condition]),
+            min_should: None,
             must: None,
             must_not: None,
         }
@@ -1976,6 +2666,7 @@ impl Filter {
     pub fn new_must(condition: Condition) -> Self {
         Filter {
             should: None,
+            min_should: None,
             must: Some(vec![condition]),
             must_not: None,
         }
@@ -1984,6 +2675,7 @@ impl Filter {
     pub fn new_must_not(condition: Condition) -> Self {
         Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![condition]),
         }
@@ -2001,9 +2693,24 @@ impl Filter {
                 }
             }
         };
+        // Only one `min_should` clause fits into the filter, the other one is nested into `must`
+        let (min_should, nested_min_should) = match (&self.min_should, &other.min_should) {
+            (Some(this), Some(other)) => (
+                Some(this.clone()),
+                Some(vec![Condition::Filter(Filter {
+                    min_should: Some(other.clone()),
+                    ..Default::default()
+                })]),
+            ),
+            (this, other) => (this.clone().or_else(|| other.clone()), None),
+        };
         Filter {
             should: merge_component(self.should.clone(), other.should.clone()),
-            must: merge_component(self.must.clone(), other.must.clone()),
+            min_should,
+            must: merge_component(
+                merge_component(self.must.clone(), other.must.clone()),
+                nested_min_should,
+            ),
             must_not: merge_component(self.must_not.clone(), other.must_not.clone()),
         }
     }
@@ -2201,6 +2908,7 @@ mod tests {
             ))]),
             must_not: None,
             should: None,
+            min_should: None,
         };
         let json = serde_json::to_string_pretty(&filter).unwrap();
         eprintln!("{json}")
@@ -2426,6 +3134,35 @@ mod tests {
         assert_eq!(c.is_null.key.as_str(), "Jason");
     }
 
+    #[test]
+    fn test_parse_relative_datetime() {
+        let now = parse_datetime("2024-03-10T12:00:00Z").unwrap();
+        let relative = |value: &str| parse_relative_datetime(value, now);
+
+        assert_eq!(relative("now"), Some(now));
+        assert_eq!(relative("now-7d"), parse_datetime("2024-03-03T12:00:00Z"));
+        assert_eq!(relative("now+90s"), parse_datetime("2024-03-10T12:01:30Z"));
+        assert_eq!(
+            relative("now-1w+2h"),
+            parse_datetime("2024-03-03T14:00:00Z")
+        );
+        assert_eq!(relative("now-1y"), None);
+        assert_eq!(relative("now-d"), None);
+        assert_eq!(relative("now*2d"), None);
+        assert_eq!(relative("2024-03-10T12:00:00Z"), None);
+        assert_eq!(relative("now-99999999999999w"), None);
+
+        let range: RangeInterface =
+            serde_json::from_str(r#"{"gte": "now-1h", "lt": "2024-03-10T12:00:00Z"}"#).unwrap();
+        let RangeInterface::DateTime(range) = range else {
+            panic!("datetime range expected");
+        };
+        assert!(range.gte.unwrap() > parse_datetime("2024-03-10T12:00:00Z").unwrap());
+        assert_eq!(range.lt, Some(now));
+
+        assert!(serde_json::from_str::<DatetimeRange>(r#"{"gte": "yesterday"}"#).is_err());
+    }
+
     #[test]
     fn test_parse_nested_filter_query() {
         let query = r#"
@@ -2564,6 +3301,33 @@ mod tests {
         assert_eq!(must.len(), 2);
     }
 
+    #[test]
+    fn test_nested_count_matches() {
+        let query = r#"
+        {
+            "nested": {
+                "key": "reviews",
+                "filter": { "must": [{ "key": "rating", "range": { "gte": 4 } }] },
+                "count": { "gte": 2 }
+            }
+        }
+        "#;
+
+        let Condition::Nested(nested) = serde_json::from_str(query).unwrap() else {
+            panic!("Nested condition expected");
+        };
+        assert!(nested.check_matches([true, false, true]));
+        assert!(!nested.check_matches([true, false, false]));
+
+        // By default at least one nested object has to match
+        let nested = NestedCondition::new(Nested {
+            count: None,
+            ..nested.nested
+        });
+        assert!(nested.check_matches([false, true]));
+        assert!(!nested.check_matches([]));
+    }
+
     #[test]
     fn test_geo_validation() {
         let query1 = r#"
@@ -2817,6 +3581,88 @@ mod tests {
         assert_eq!(payload, Default::default());
     }
 
+    #[test]
+    fn test_remove_wildcard_key() {
+        let mut payload: Payload = serde_json::from_str(
+            r#"
+        {
+            "a": 1,
+            "metadata": {
+                "tmp": {
+                    "x": 1,
+                    "y": [1, 2]
+                },
+                "source": {
+                    "tmp": "text",
+                    "name": "web"
+                },
+                "author": {
+                    "name": "John"
+                }
+            }
+        }
+        "#,
+        )
+        .unwrap();
+
+        let removed = payload.remove("metadata.*.name");
+        assert_eq!(removed.len(), 2);
+
+        let removed = payload.remove("metadata.tmp.*");
+        assert_eq!(removed.len(), 2);
+        assert_eq!(payload.get_value("metadata.tmp").values(), vec![&json!({})]);
+
+        let removed = payload.remove("metadata.*");
+        assert_eq!(removed.len(), 3);
+        assert_eq!(payload, Payload::from(json!({"a": 1, "metadata": {}})));
+
+        assert!(payload.remove("*.*").is_empty());
+        assert_eq!(payload.remove("*").len(), 2);
+        assert_eq!(payload, Default::default());
+    }
+
+    #[test]
+    fn test_rename_key() {
+        let mut payload: Payload = serde_json::from_str(
+            r#"
+        {
+            "a": 1,
+            "b": {
+                "c": 123,
+                "d": "text"
+            }
+        }
+        "#,
+        )
+        .unwrap();
+
+        assert!(payload.rename("b.c", "e.f"));
+        assert!(payload.rename("a", "b.a"));
+        assert!(!payload.rename("x", "y"));
+        assert!(!payload.rename("b.*", "x"));
+        assert!(!payload.rename("b.d", "x.*"));
+
+        let expected: Payload = serde_json::from_str(
+            r#"
+        {
+            "b": {
+                "a": 1,
+                "d": "text"
+            },
+            "e": {
+                "f": 123
+            }
+        }
+        "#,
+        )
+        .unwrap();
+        assert_eq!(payload, expected);
+
+        // Intermediate non-object value is replaced
+        assert!(payload.rename("e.f", "b.d.g"));
+        assert_eq!(payload.get_value("b.d.g").values(), vec![&Value::from(123)]);
+    }
+
     #[test]
     fn test_payload_parsing() {
         let ft = PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword);
@@ -2830,6 +3676,14 @@ mod tests {
         let query = r#""keyword""#;
         let field_type: PayloadSchemaType = serde_json::from_str(query).unwrap();
         eprintln!("field_type = {field_type:?}");
+
+        let query = r#"{"type": "integer", "on_disk": true}"#;
+        let field_schema: PayloadFieldSchema = serde_json::from_str(query).unwrap();
+        assert!(matches!(
+            field_schema,
+            PayloadFieldSchema::FieldParams(PayloadSchemaParams::Integer(_))
+        ));
+        assert!(field_schema.is_on_disk());
     }
 
     #[test]
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fs::create_dir_all;
 use std::ops::Range;
 use std::path::{Path, PathBuf};
@@ -6,26 +7,34 @@ use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
 use bitvec::prelude::BitSlice;
-use common::types::PointOffsetType;
+use common::types::{PointOffsetType, ScoreType};
 
 use super::DenseVectorStorage;
 use crate::common::operation_error::{check_process_stopped, OperationResult};
 use crate::common::Flusher;
 use crate::data_types::named_vectors::CowVector;
+use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{VectorElementType, VectorRef};
+use crate::spaces::simple::cosine_norm;
 use crate::types::Distance;
 use crate::vector_storage::chunked_mmap_vectors::ChunkedMmapVectors;
+use crate::vector_storage::common::get_async_scorer;
 use crate::vector_storage::dynamic_mmap_flags::DynamicMmapFlags;
 use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 
 const VECTORS_DIR_PATH: &str = "vectors";
 const DELETED_DIR_PATH: &str = "deleted";
+const NORMS_DIR_PATH: &str = "norms";
 
-pub struct AppendableMmapVectorStorage {
-    vectors: ChunkedMmapVectors,
+pub struct AppendableMmapVectorStorage<T: PrimitiveVectorElement = VectorElementType> {
+    vectors: ChunkedMmapVectors<T>,
     deleted: DynamicMmapFlags,
     distance: Distance,
     deleted_count: usize,
+    /// Norms of stored vectors, if vectors are kept un-normalized. Stored as vectors of dimension 1.
+    norms: Option<ChunkedMmapVectors<ScoreType>>,
+    /// Per-dimension weights applied during scoring, taken from the segment config
+    dimension_weights: Option<Vec<VectorElementType>>,
 }
 
 pub fn open_appendable_memmap_vector_storage(
@@ -33,12 +42,85 @@ pub fn open_appendable_memmap_vector_storage(
     dim: usize,
     distance: Distance,
 ) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    open_appendable_memmap_vector_storage_with_async_io(path, dim, distance, get_async_scorer())
+}
+
+pub fn open_appendable_memmap_vector_storage_with_async_io(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    with_async_io: bool,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage =
+        open_appendable_memmap_vector_storage_impl(path, dim, distance, with_async_io, false)?;
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::AppendableMemmap(Box::new(storage)),
+    )))
+}
+
+/// Open appendable mmap vector storage for `Cosine` distance, which keeps original vectors and caches their norms
+pub fn open_appendable_memmap_vector_storage_with_norms(
+    path: &Path,
+    dim: usize,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_appendable_memmap_vector_storage_impl(
+        path,
+        dim,
+        Distance::Cosine,
+        get_async_scorer(),
+        true,
+    )?;
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::AppendableMemmap(Box::new(storage)),
+    )))
+}
+
+/// Open appendable mmap vector storage, which keeps vectors in half precision
+///
+/// Async IO is not supported for half precision vectors.
+pub fn open_appendable_memmap_half_vector_storage(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_appendable_memmap_vector_storage_impl(path, dim, distance, false, false)?;
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::AppendableMemmapHalf(Box::new(storage)),
+    )))
+}
+
+/// Open appendable mmap vector storage, which keeps vectors as unsigned bytes
+///
+/// Async IO is not supported for byte vectors.
+pub fn open_appendable_memmap_byte_vector_storage(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_appendable_memmap_vector_storage_impl(path, dim, distance, false, false)?;
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::AppendableMemmapByte(Box::new(storage)),
+    )))
+}
+
+fn open_appendable_memmap_vector_storage_impl<T: PrimitiveVectorElement>(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    with_async_io: bool,
+    with_norms: bool,
+) -> OperationResult<AppendableMmapVectorStorage<T>> {
     create_dir_all(path)?;
 
     let vectors_path = path.join(VECTORS_DIR_PATH);
     let deleted_path = path.join(DELETED_DIR_PATH);
 
-    let vectors: ChunkedMmapVectors = ChunkedMmapVectors::open(&vectors_path, dim)?;
+    let vectors: ChunkedMmapVectors<T> =
+        ChunkedMmapVectors::open(&vectors_path, dim, true, with_async_io)?;
 
     let num_vectors = vectors.len();
 
@@ -52,19 +134,54 @@ pub fn open_appendable_memmap_vector_storage(
         }
     }
 
-    let storage = AppendableMmapVectorStorage {
+    let norms = if with_norms {
+        let mut norms: ChunkedMmapVectors<ScoreType> =
+            ChunkedMmapVectors::open(&path.join(NORMS_DIR_PATH), 1, false, false)?;
+        // Norms are written after vectors, compute the ones missing after an interrupted write
+        for key in norms.len()..num_vectors {
+            let key = key as PointOffsetType;
+            norms.insert(key, &[vector_norm(vectors.get(key))])?;
+        }
+        Some(norms)
+    } else {
+        None
+    };
+
+    Ok(AppendableMmapVectorStorage {
         vectors,
         deleted,
         distance,
         deleted_count,
-    };
+        norms,
+        dimension_weights: None,
+    })
+}
 
-    Ok(Arc::new(AtomicRefCell::new(
-        VectorStorageEnum::AppendableMemmap(Box::new(storage)),
-    )))
+fn vector_norm<T: PrimitiveVectorElement>(vector: &[T]) -> ScoreType {
+    cosine_norm(&T::slice_to_float_cow(Cow::Borrowed(vector)))
 }
 
-impl AppendableMmapVectorStorage {
+impl<T: PrimitiveVectorElement> AppendableMmapVectorStorage<T> {
+    /// Attach per-dimension weights from the vector config, they are not persisted
+    pub fn set_dimension_weights(&mut self, weights: Vec<VectorElementType>) {
+        self.dimension_weights = Some(weights);
+    }
+
+    pub fn get_chunked_mmap_vectors(&self) -> &ChunkedMmapVectors<T> {
+        &self.vectors
+    }
+
+    pub fn has_async_reader(&self) -> bool {
+        self.vectors.has_async_reader()
+    }
+
+    fn set_norm(&mut self, key: PointOffsetType, vector: &[T]) -> OperationResult<()> {
+        if let Some(norms) = &mut self.norms {
+            norms.insert(key, &[vector_norm(vector)])?;
+        }
+        Ok(())
+    }
+
     /// Set deleted flag for given key. Returns previous deleted state.
     #[inline]
     fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> OperationResult<bool> {
@@ -85,13 +202,33 @@ impl AppendableMmapVectorStorage {
     }
 }
 
-impl DenseVectorStorage for AppendableMmapVectorStorage {
-    fn get_dense(&self, key: PointOffsetType) -> &[VectorElementType] {
+impl<T: PrimitiveVectorElement> DenseVectorStorage<T> for AppendableMmapVectorStorage<T> {
+    fn get_dense(&self, key: PointOffsetType) -> &[T] {
         self.vectors.get(key)
     }
+
+    fn padded_dim(&self) -> Option<usize> {
+        self.vectors.padded_dim()
+    }
+
+    fn get_dense_padded(&self, key: PointOffsetType) -> &[T] {
+        self.vectors.get_padded(key)
+    }
+
+    fn has_norms(&self) -> bool {
+        self.norms.is_some()
+    }
+
+    fn get_norm(&self, key: PointOffsetType) -> ScoreType {
+        self.norms.as_ref().map_or(1.0, |norms| norms.get(key)[0])
+    }
+
+    fn dimension_weights(&self) -> Option<&[VectorElementType]> {
+        self.dimension_weights.as_deref()
+    }
 }
 
-impl VectorStorage for AppendableMmapVectorStorage {
+impl<T: PrimitiveVectorElement> VectorStorage for AppendableMmapVectorStorage<T> {
     fn vector_dim(&self) -> usize {
         self.vectors.dim()
     }
@@ -109,11 +246,14 @@ impl VectorStorage for AppendableMmapVectorStorage {
     }
 
     fn get_vector(&self, key: PointOffsetType) -> CowVector {
-        self.get_dense(key).into()
+        T::slice_to_float_cow(Cow::Borrowed(self.get_dense(key))).into()
     }
 
     fn insert_vector(&mut self, key: PointOffsetType, vector: VectorRef) -> OperationResult<()> {
-        self.vectors.insert(key, vector.try_into()?)?;
+        let vector: &[VectorElementType] = vector.try_into()?;
+        let vector = T::slice_from_float_cow(Cow::Borrowed(vector));
+        self.vectors.insert(key, &vector)?;
+        self.set_norm(key, &vector)?;
         self.set_deleted(key, false)?;
         Ok(())
     }
@@ -130,8 +270,10 @@ impl VectorStorage for AppendableMmapVectorStorage {
             // Do not perform preprocessing - vectors should be already processed
             let other_deleted = other.is_deleted_vector(point_id);
             let other_vector = other.get_vector(point_id);
-            let other_vector = other_vector.as_vec_ref().try_into()?;
-            let new_id = self.vectors.push(other_vector)?;
+            let other_vector: &[VectorElementType] = other_vector.as_vec_ref().try_into()?;
+            let other_vector = T::slice_from_float_cow(Cow::Borrowed(other_vector));
+            let new_id = self.vectors.push(&other_vector)?;
+            self.set_norm(new_id, &other_vector)?;
             self.set_deleted(new_id, other_deleted)?;
         }
         let end_index = self.vectors.len() as PointOffsetType;
@@ -142,9 +284,13 @@ impl VectorStorage for AppendableMmapVectorStorage {
         Box::new({
             let vectors_flusher = self.vectors.flusher();
             let deleted_flusher = self.deleted.flusher();
+            let norms_flusher = self.norms.as_ref().map(|norms| norms.flusher());
             move || {
                 vectors_flusher()?;
                 deleted_flusher()?;
+                if let Some(norms_flusher) = norms_flusher {
+                    norms_flusher()?;
+                }
                 Ok(())
             }
         })
@@ -153,6 +299,9 @@ impl VectorStorage for AppendableMmapVectorStorage {
     fn files(&self) -> Vec<PathBuf> {
         let mut files = self.vectors.files();
         files.extend(self.deleted.files());
+        if let Some(norms) = &self.norms {
+            files.extend(norms.files());
+        }
         files
     }
 
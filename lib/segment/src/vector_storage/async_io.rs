@@ -1,5 +1,5 @@
 use std::fs::File;
-use std::os::fd::AsRawFd;
+use std::os::fd::{AsRawFd, RawFd};
 
 use common::types::PointOffsetType;
 use io_uring::{opcode, types, IoUring};
@@ -48,27 +48,88 @@ impl BufferStore {
 }
 
 pub struct UringReader {
-    file: File,
+    /// Files with vector data. There is more than one file only for chunked storages.
+    files: Vec<File>,
+    /// Number of vectors stored in each file, if vectors are split into chunks
+    chunk_size_vectors: Option<usize>,
     buffers: BufferStore,
     io_uring: Option<IoUring>,
     raw_size: usize,
+    /// Distance in bytes between starts of consecutive vectors, larger than `raw_size` if vectors
+    /// are padded
+    stride_size: usize,
     header_size: usize,
 }
 
 impl UringReader {
-    pub fn new(file: File, raw_size: usize, header_size: usize) -> OperationResult<Self> {
+    pub fn new(
+        file: File,
+        raw_size: usize,
+        stride_size: usize,
+        header_size: usize,
+    ) -> OperationResult<Self> {
+        Self::with_files(vec![file], None, raw_size, stride_size, header_size)
+    }
+
+    /// Create reader for vectors split into chunk files of `chunk_size_vectors` vectors each
+    pub fn new_chunked(
+        files: Vec<File>,
+        chunk_size_vectors: usize,
+        raw_size: usize,
+        stride_size: usize,
+    ) -> OperationResult<Self> {
+        Self::with_files(files, Some(chunk_size_vectors), raw_size, stride_size, 0)
+    }
+
+    fn with_files(
+        files: Vec<File>,
+        chunk_size_vectors: Option<usize>,
+        raw_size: usize,
+        stride_size: usize,
+        header_size: usize,
+    ) -> OperationResult<Self> {
         let buffers = BufferStore::new(DISK_PARALLELISM, raw_size);
         let io_uring = IoUring::new(DISK_PARALLELISM as _)?;
 
         Ok(Self {
-            file,
+            files,
+            chunk_size_vectors,
             buffers,
             io_uring: Some(io_uring),
             raw_size,
+            stride_size,
             header_size,
         })
     }
 
+    /// Register a new chunk file, which was appended to the chunked storage
+    pub fn push_chunk(&mut self, file: File) {
+        debug_assert!(self.chunk_size_vectors.is_some());
+        self.files.push(file);
+    }
+
+    /// Returns file descriptor and byte offset of the vector with the given offset
+    fn vector_location(&self, point: PointOffsetType) -> OperationResult<(RawFd, usize)> {
+        let (file_idx, vector_idx) = match self.chunk_size_vectors {
+            Some(chunk_size_vectors) => (
+                point as usize / chunk_size_vectors,
+                point as usize % chunk_size_vectors,
+            ),
+            None => (0, point as usize),
+        };
+
+        let file = self.files.get(file_idx).ok_or_else(|| {
+            OperationError::service_error(format!(
+                "Point {point} is out of range of the io_uring reader"
+            ))
+        })?;
+
+        Ok((
+            file.as_raw_fd(),
+            self.header_size + self.stride_size * vector_idx,
+        ))
+    }
+
     /// Takes in iterator of point offsets, reads it, and yields a callback with the read data.
     pub fn read_stream(
         &mut self,
@@ -99,6 +160,8 @@ impl UringReader {
                     self.raw_size,
                 )?;
             }
+            let (fd, offset) = self.vector_location(point)?;
+
             // Assume there is at least one buffer available at this point
             let buffer_id = unused_buffer_ids.pop().unwrap();
 
@@ -108,18 +171,13 @@ impl UringReader {
             });
 
             let buffer = &mut self.buffers.buffers[buffer_id].buffer;
-            let offset = self.header_size + self.raw_size * point as usize;
 
             let user_data = buffer_id;
 
-            let read_e = opcode::Read::new(
-                types::Fd(self.file.as_raw_fd()),
-                buffer.as_mut_ptr(),
-                buffer.len() as _,
-            )
-            .offset(offset as _)
-            .build()
-            .user_data(user_data as _);
+            let read_e = opcode::Read::new(types::Fd(fd), buffer.as_mut_ptr(), buffer.len() as _)
+                .offset(offset as _)
+                .build()
+                .user_data(user_data as _);
 
             unsafe {
                 // self.io_uring.submission().push(&read_e).unwrap();
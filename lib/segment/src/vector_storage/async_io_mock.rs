@@ -8,7 +8,23 @@ pub struct UringReader;
 
 #[allow(dead_code)]
 impl UringReader {
-    pub fn new(_file: File, _raw_size: usize, _header_size: usize) -> OperationResult<Self> {
+    pub fn new(
+        _file: File,
+        _raw_size: usize,
+        _stride_size: usize,
+        _header_size: usize,
+    ) -> OperationResult<Self> {
         Ok(Self {})
     }
+
+    pub fn new_chunked(
+        _files: Vec<File>,
+        _chunk_size_vectors: usize,
+        _raw_size: usize,
+        _stride_size: usize,
+    ) -> OperationResult<Self> {
+        Ok(Self {})
+    }
+
+    pub fn push_chunk(&mut self, _file: File) {}
 }
@@ -12,17 +12,50 @@ use super::query_scorer::custom_query_scorer::CustomQueryScorer;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::vectors::{DenseVector, QueryVector, Vector, VectorElementType};
 use crate::spaces::metric::Metric;
-use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+use crate::spaces::simple::{
+    CosineMetric, DotProductMetric, EuclidMetric, HammingMetric, ManhattanMetric,
+};
 use crate::types::Distance;
+use crate::vector_storage::appendable_mmap_vector_storage::AppendableMmapVectorStorage;
 use crate::vector_storage::memmap_vector_storage::MemmapVectorStorage;
-use crate::vector_storage::mmap_vectors::MmapVectors;
 use crate::vector_storage::query_scorer::metric_query_scorer::MetricQueryScorer;
 use crate::vector_storage::query_scorer::QueryScorer;
-use crate::vector_storage::{RawScorer, VectorStorage as _, DEFAULT_STOPPED};
+use crate::vector_storage::{DenseVectorStorage, RawScorer, DEFAULT_STOPPED};
 
-pub fn new<'a>(
+/// On-disk vector storage, which can read vectors with asynchronous IO
+pub trait AsyncVectorStorage: DenseVectorStorage {
+    /// Reads vectors for the given ids and calls the callback for each vector as soon as it is read.
+    fn read_vectors_async(
+        &self,
+        points: impl Iterator<Item = PointOffsetType>,
+        callback: impl FnMut(usize, PointOffsetType, &[VectorElementType]),
+    ) -> OperationResult<()>;
+}
+
+impl AsyncVectorStorage for MemmapVectorStorage {
+    fn read_vectors_async(
+        &self,
+        points: impl Iterator<Item = PointOffsetType>,
+        callback: impl FnMut(usize, PointOffsetType, &[VectorElementType]),
+    ) -> OperationResult<()> {
+        self.get_mmap_vectors().read_vectors_async(points, callback)
+    }
+}
+
+impl AsyncVectorStorage for AppendableMmapVectorStorage {
+    fn read_vectors_async(
+        &self,
+        points: impl Iterator<Item = PointOffsetType>,
+        callback: impl FnMut(usize, PointOffsetType, &[VectorElementType]),
+    ) -> OperationResult<()> {
+        self.get_chunked_mmap_vectors()
+            .read_vectors_async(points, callback)
+    }
+}
+
+pub fn new<'a, TVectorStorage: AsyncVectorStorage>(
     query: QueryVector,
-    storage: &'a MemmapVectorStorage,
+    storage: &'a TVectorStorage,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
@@ -31,10 +64,14 @@ pub fn new<'a>(
         .build()
 }
 
-pub struct AsyncRawScorerImpl<'a, TQueryScorer: QueryScorer<[VectorElementType]>> {
+pub struct AsyncRawScorerImpl<
+    'a,
+    TQueryScorer: QueryScorer<[VectorElementType]>,
+    TVectorStorage: AsyncVectorStorage,
+> {
     points_count: PointOffsetType,
     query_scorer: TQueryScorer,
-    storage: &'a MmapVectors,
+    storage: &'a TVectorStorage,
     point_deleted: &'a BitSlice,
     vec_deleted: &'a BitSlice,
     /// This flag indicates that the search process is stopped externally,
@@ -42,14 +79,15 @@ pub struct AsyncRawScorerImpl<'a, TQueryScorer: QueryScorer<[VectorElementType]>
     pub is_stopped: &'a AtomicBool,
 }
 
-impl<'a, TQueryScorer> AsyncRawScorerImpl<'a, TQueryScorer>
+impl<'a, TQueryScorer, TVectorStorage> AsyncRawScorerImpl<'a, TQueryScorer, TVectorStorage>
 where
     TQueryScorer: QueryScorer<[VectorElementType]>,
+    TVectorStorage: AsyncVectorStorage,
 {
     fn new(
         points_count: PointOffsetType,
         query_scorer: TQueryScorer,
-        storage: &'a MmapVectors,
+        storage: &'a TVectorStorage,
         point_deleted: &'a BitSlice,
         vec_deleted: &'a BitSlice,
         is_stopped: &'a AtomicBool,
@@ -65,9 +103,11 @@ where
     }
 }
 
-impl<'a, TQueryScorer> RawScorer for AsyncRawScorerImpl<'a, TQueryScorer>
+impl<'a, TQueryScorer, TVectorStorage> RawScorer
+    for AsyncRawScorerImpl<'a, TQueryScorer, TVectorStorage>
 where
     TQueryScorer: QueryScorer<[VectorElementType]>,
+    TVectorStorage: AsyncVectorStorage,
 {
     fn score_points(&self, points: &[PointOffsetType], scores: &mut [ScoredPointOffset]) -> usize {
         if self.is_stopped.load(Ordering::Relaxed) {
@@ -206,20 +246,23 @@ where
     }
 }
 
-struct AsyncRawScorerBuilder<'a> {
+struct AsyncRawScorerBuilder<'a, TVectorStorage: AsyncVectorStorage> {
     points_count: PointOffsetType,
     query: QueryVector,
-    storage: &'a MemmapVectorStorage,
+    storage: &'a TVectorStorage,
     point_deleted: &'a BitSlice,
     vec_deleted: &'a BitSlice,
     distance: Distance,
     is_stopped: Option<&'a AtomicBool>,
 }
 
-impl<'a> AsyncRawScorerBuilder<'a> {
+impl<'a, TVectorStorage> AsyncRawScorerBuilder<'a, TVectorStorage>
+where
+    TVectorStorage: AsyncVectorStorage,
+{
     pub fn new(
         query: QueryVector,
-        storage: &'a MemmapVectorStorage,
+        storage: &'a TVectorStorage,
         point_deleted: &'a BitSlice,
     ) -> OperationResult<Self> {
         let points_count = storage.total_vector_count() as _;
@@ -246,6 +289,7 @@ impl<'a> AsyncRawScorerBuilder<'a> {
             Distance::Euclid => self._build_with_metric::<EuclidMetric>(),
             Distance::Dot => self._build_with_metric::<DotProductMetric>(),
             Distance::Manhattan => self._build_with_metric::<ManhattanMetric>(),
+            Distance::Hamming => self._build_with_metric::<HammingMetric>(),
         }
     }
 
@@ -270,11 +314,11 @@ impl<'a> AsyncRawScorerBuilder<'a> {
                 match vector {
                     Vector::Dense(dense_vector) => {
                         let query_scorer =
-                            MetricQueryScorer::<TMetric, _>::new(dense_vector, storage);
+                            MetricQueryScorer::<_, TMetric, _>::new(dense_vector, storage);
                         Ok(Box::new(AsyncRawScorerImpl::new(
                             points_count,
                             query_scorer,
-                            storage.get_mmap_vectors(),
+                            storage,
                             point_deleted,
                             vec_deleted,
                             is_stopped.unwrap_or(&DEFAULT_STOPPED),
@@ -283,15 +327,16 @@ impl<'a> AsyncRawScorerBuilder<'a> {
                     Vector::Sparse(_sparse_vector) => Err(OperationError::service_error(
                         "sparse vectors are not supported for async scorer",
                     )), // TODO(sparse) add support?
+                    Vector::MultiDense(_) => Err(OperationError::WrongMulti),
                 }
             }
             QueryVector::Recommend(reco_query) => {
                 let reco_query: RecoQuery<DenseVector> = reco_query.transform_into()?;
-                let query_scorer = CustomQueryScorer::<TMetric, _, _>::new(reco_query, storage);
+                let query_scorer = CustomQueryScorer::<_, TMetric, _, _>::new(reco_query, storage);
                 Ok(Box::new(AsyncRawScorerImpl::new(
                     points_count,
                     query_scorer,
-                    storage.get_mmap_vectors(),
+                    storage,
                     point_deleted,
                     vec_deleted,
                     is_stopped.unwrap_or(&DEFAULT_STOPPED),
@@ -301,11 +346,11 @@ impl<'a> AsyncRawScorerBuilder<'a> {
                 let discovery_query: DiscoveryQuery<DenseVector> =
                     discovery_query.transform_into()?;
                 let query_scorer =
-                    CustomQueryScorer::<TMetric, _, _>::new(discovery_query, storage);
+                    CustomQueryScorer::<_, TMetric, _, _>::new(discovery_query, storage);
                 Ok(Box::new(AsyncRawScorerImpl::new(
                     points_count,
                     query_scorer,
-                    storage.get_mmap_vectors(),
+                    storage,
                     point_deleted,
                     vec_deleted,
                     is_stopped.unwrap_or(&DEFAULT_STOPPED),
@@ -313,11 +358,12 @@ impl<'a> AsyncRawScorerBuilder<'a> {
             }
             QueryVector::Context(context_query) => {
                 let context_query: ContextQuery<DenseVector> = context_query.transform_into()?;
-                let query_scorer = CustomQueryScorer::<TMetric, _, _>::new(context_query, storage);
+                let query_scorer =
+                    CustomQueryScorer::<_, TMetric, _, _>::new(context_query, storage);
                 Ok(Box::new(AsyncRawScorerImpl::new(
                     points_count,
                     query_scorer,
-                    storage.get_mmap_vectors(),
+                    storage,
                     point_deleted,
                     vec_deleted,
                     is_stopped.unwrap_or(&DEFAULT_STOPPED),
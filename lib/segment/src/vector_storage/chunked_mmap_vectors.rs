@@ -1,18 +1,26 @@
 use std::cmp::max;
-use std::fs::{create_dir_all, OpenOptions};
+use std::fs::{create_dir_all, File, OpenOptions};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use common::types::PointOffsetType;
 use memmap2::MmapMut;
 use memory::mmap_ops::{create_and_ensure_length, open_write_mmap};
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 
 use crate::common::mmap_type::MmapType;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::Flusher;
+use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::VectorElementType;
+use crate::types::VectorStorageDatatype;
+#[cfg(target_os = "linux")]
+use crate::vector_storage::async_io::UringReader;
+#[cfg(not(target_os = "linux"))]
+use crate::vector_storage::async_io_mock::UringReader;
 use crate::vector_storage::chunked_utils::{chunk_name, create_chunk, read_mmaps, MmapChunk};
+use crate::vector_storage::common::padded_dim;
 
 #[cfg(debug_assertions)]
 const DEFAULT_CHUNK_SIZE: usize = 512 * 1024; // 512Kb
@@ -32,16 +40,28 @@ struct ChunkedMmapConfig {
     chunk_size_bytes: usize,
     chunk_size_vectors: usize,
     dim: usize,
+    /// Number of elements each vector occupies in a chunk, if vectors are stored padded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    padded_dim: Option<usize>,
 }
 
-pub struct ChunkedMmapVectors {
+impl ChunkedMmapConfig {
+    fn stride(&self) -> usize {
+        self.padded_dim.unwrap_or(self.dim)
+    }
+}
+
+pub struct ChunkedMmapVectors<T: PrimitiveVectorElement = VectorElementType> {
     config: ChunkedMmapConfig,
     status: MmapType<Status>,
-    chunks: Vec<MmapChunk>,
+    chunks: Vec<MmapChunk<T>>,
     directory: PathBuf,
+    /// Context for io_uring-base async IO, reads directly from chunk files
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    uring_reader: Mutex<Option<UringReader>>,
 }
 
-impl ChunkedMmapVectors {
+impl<T: PrimitiveVectorElement> ChunkedMmapVectors<T> {
     fn config_file(directory: &Path) -> PathBuf {
         directory.join(CONFIG_FILE_NAME)
     }
@@ -65,11 +85,16 @@ impl ChunkedMmapVectors {
         }
     }
 
-    fn ensure_config(directory: &Path, dim: usize) -> OperationResult<ChunkedMmapConfig> {
+    fn ensure_config(
+        directory: &Path,
+        dim: usize,
+        padded: bool,
+    ) -> OperationResult<ChunkedMmapConfig> {
         let config_file = Self::config_file(directory);
         if !config_file.exists() {
+            let padded_dim = padded.then(|| padded_dim::<T>(dim));
             let chunk_size_bytes = DEFAULT_CHUNK_SIZE;
-            let vector_size_bytes = dim * std::mem::size_of::<VectorElementType>();
+            let vector_size_bytes = padded_dim.unwrap_or(dim) * std::mem::size_of::<T>();
             let chunk_size_vectors = chunk_size_bytes / vector_size_bytes;
             let corrected_chunk_size_bytes = chunk_size_vectors * vector_size_bytes;
 
@@ -77,6 +102,7 @@ impl ChunkedMmapVectors {
                 chunk_size_bytes: corrected_chunk_size_bytes,
                 chunk_size_vectors,
                 dim,
+                padded_dim,
             };
             let mut file = OpenOptions::new()
                 .create(true)
@@ -101,33 +127,65 @@ impl ChunkedMmapVectors {
         }
     }
 
-    pub fn open(directory: &Path, dim: usize) -> OperationResult<Self> {
+    /// Open or create chunked vectors in the given directory
+    ///
+    /// If `padded` is set, newly created storages keep vectors padded to [`padded_dim`]. Existing
+    /// storages keep the layout they were created with.
+    pub fn open(
+        directory: &Path,
+        dim: usize,
+        padded: bool,
+        with_async_io: bool,
+    ) -> OperationResult<Self> {
         create_dir_all(directory)?;
         let status_mmap = Self::ensure_status_file(directory)?;
         let status = unsafe { MmapType::from(status_mmap) };
 
-        let config = Self::ensure_config(directory, dim)?;
+        let config = Self::ensure_config(directory, dim, padded)?;
         let chunks = read_mmaps(directory)?;
 
+        // Async IO reads vectors in full precision only
+        let uring_reader = if with_async_io && T::datatype() == VectorStorageDatatype::Float32 {
+            // Keep chunk file handles open for async IO
+            let files = (0..chunks.len())
+                .map(|chunk_idx| File::open(chunk_name(directory, chunk_idx)))
+                .collect::<Result<Vec<_>, _>>()?;
+            let raw_size = dim * std::mem::size_of::<T>();
+            let stride_size = config.stride() * std::mem::size_of::<T>();
+            Some(UringReader::new_chunked(
+                files,
+                config.chunk_size_vectors,
+                raw_size,
+                stride_size,
+            )?)
+        } else {
+            None
+        };
+
         let vectors = Self {
             status,
             config,
             chunks,
             directory: directory.to_owned(),
+            uring_reader: Mutex::new(uring_reader),
         };
         Ok(vectors)
     }
 
+    pub fn has_async_reader(&self) -> bool {
+        self.uring_reader.lock().is_some()
+    }
+
     #[inline]
     fn get_chunk_index(&self, key: usize) -> usize {
         key / self.config.chunk_size_vectors
     }
 
-    /// Returns the byte offset of the vector in the chunk
+    /// Returns the element offset of the vector in the chunk
     #[inline]
     fn get_chunk_offset(&self, key: usize) -> usize {
         let chunk_vector_idx = key % self.config.chunk_size_vectors;
-        chunk_vector_idx * self.config.dim
+        chunk_vector_idx * self.config.stride()
     }
 
     pub fn len(&self) -> usize {
@@ -138,22 +196,27 @@ impl ChunkedMmapVectors {
         self.config.dim
     }
 
+    /// Number of elements in vectors returned by [`ChunkedMmapVectors::get_padded`]
+    ///
+    /// `None` if vectors are not stored padded.
+    pub fn padded_dim(&self) -> Option<usize> {
+        self.config.padded_dim
+    }
+
     fn add_chunk(&mut self) -> OperationResult<()> {
-        let chunk = create_chunk(
-            &self.directory,
-            self.chunks.len(),
-            self.config.chunk_size_bytes,
-        )?;
+        let chunk_idx = self.chunks.len();
+        let chunk = create_chunk(&self.directory, chunk_idx, self.config.chunk_size_bytes)?;
+
+        if let Some(uring_reader) = self.uring_reader.get_mut().as_mut() {
+            let file = File::open(chunk_name(&self.directory, chunk_idx))?;
+            uring_reader.push_chunk(file);
+        }
 
         self.chunks.push(chunk);
         Ok(())
     }
 
-    pub fn insert(
-        &mut self,
-        key: PointOffsetType,
-        vector: &[VectorElementType],
-    ) -> OperationResult<()> {
+    pub fn insert(&mut self, key: PointOffsetType, vector: &[T]) -> OperationResult<()> {
         let key = key as usize;
         let chunk_idx = self.get_chunk_index(key);
         let chunk_offset = self.get_chunk_offset(key);
@@ -175,13 +238,13 @@ impl ChunkedMmapVectors {
         Ok(())
     }
 
-    pub fn push(&mut self, vector: &[VectorElementType]) -> OperationResult<PointOffsetType> {
+    pub fn push(&mut self, vector: &[T]) -> OperationResult<PointOffsetType> {
         let new_id = self.status.len as PointOffsetType;
         self.insert(new_id, vector)?;
         Ok(new_id)
     }
 
-    pub fn get<TKey>(&self, key: TKey) -> &[VectorElementType]
+    pub fn get<TKey>(&self, key: TKey) -> &[T]
     where
         TKey: num_traits::cast::AsPrimitive<usize>,
     {
@@ -192,6 +255,19 @@ impl ChunkedMmapVectors {
         &chunk[chunk_offset..chunk_offset + self.config.dim]
     }
 
+    /// Vector followed by its zero padding, same as [`ChunkedMmapVectors::get`] if vectors are
+    /// not stored padded
+    pub fn get_padded<TKey>(&self, key: TKey) -> &[T]
+    where
+        TKey: num_traits::cast::AsPrimitive<usize>,
+    {
+        let key: usize = key.as_();
+        let chunk_idx = self.get_chunk_index(key);
+        let chunk_offset = self.get_chunk_offset(key);
+        let chunk = &self.chunks[chunk_idx];
+        &chunk[chunk_offset..chunk_offset + self.config.stride()]
+    }
+
     pub fn flusher(&self) -> Flusher {
         Box::new({
             let status_flusher = self.status.flusher();
@@ -217,6 +293,53 @@ impl ChunkedMmapVectors {
     }
 }
 
+impl ChunkedMmapVectors<VectorElementType> {
+    #[cfg(target_os = "linux")]
+    fn process_points_uring(
+        &self,
+        points: impl Iterator<Item = PointOffsetType>,
+        callback: impl FnMut(usize, PointOffsetType, &[VectorElementType]),
+    ) -> OperationResult<()> {
+        self.uring_reader
+            .lock()
+            .as_mut()
+            .expect("io_uring reader should be initialized")
+            .read_stream(points, callback)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn process_points_simple(
+        &self,
+        points: impl Iterator<Item = PointOffsetType>,
+        mut callback: impl FnMut(usize, PointOffsetType, &[VectorElementType]),
+    ) -> OperationResult<()> {
+        for (idx, point) in points.enumerate() {
+            let vector = self.get(point);
+            callback(idx, point, vector);
+        }
+        Ok(())
+    }
+
+    /// Reads vectors for the given ids and calls the callback for each vector.
+    /// Tries to utilize asynchronous IO if possible.
+    /// In particular, uses io_uring on Linux and simple synchronous IO otherwise.
+    pub fn read_vectors_async(
+        &self,
+        points: impl Iterator<Item = PointOffsetType>,
+        callback: impl FnMut(usize, PointOffsetType, &[VectorElementType]),
+    ) -> OperationResult<()> {
+        #[cfg(target_os = "linux")]
+        {
+            self.process_points_uring(points, callback)
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            self.process_points_simple(points, callback)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rand::prelude::StdRng;
@@ -239,7 +362,7 @@ mod tests {
 
         {
             let mut chunked_mmap: ChunkedMmapVectors =
-                ChunkedMmapVectors::open(dir.path(), dim).unwrap();
+                ChunkedMmapVectors::open(dir.path(), dim, true, false).unwrap();
 
             for vec in &vectors {
                 chunked_mmap.push(vec).unwrap();
@@ -265,7 +388,7 @@ mod tests {
 
         {
             let chunked_mmap: ChunkedMmapVectors =
-                ChunkedMmapVectors::open(dir.path(), dim).unwrap();
+                ChunkedMmapVectors::open(dir.path(), dim, true, false).unwrap();
 
             assert!(
                 chunked_mmap.chunks.len() > 1,
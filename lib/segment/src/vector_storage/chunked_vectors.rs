@@ -2,12 +2,14 @@ use std::cmp::max;
 use std::collections::TryReserveError;
 use std::fs::File;
 use std::io::{Read, Write};
+use std::marker::PhantomData;
 use std::mem;
 use std::path::Path;
 
 use common::types::PointOffsetType;
 
 use crate::common::vector_utils::TrySetCapacityExact;
+use crate::vector_storage::common::{padded_dim, VECTOR_ALIGNMENT};
 
 // chunk size in bytes
 const CHUNK_SIZE: usize = 32 * 1024 * 1024;
@@ -15,28 +17,53 @@ const CHUNK_SIZE: usize = 32 * 1024 * 1024;
 // if dimension is too high, use this capacity
 const MIN_CHUNK_CAPACITY: usize = 16;
 
+/// Unit of chunk allocation, keeps the start of every chunk aligned to [`VECTOR_ALIGNMENT`]
+#[derive(Clone, Copy, Default)]
+#[repr(C, align(32))]
+struct AlignedBlock([u8; VECTOR_ALIGNMENT]);
+
+const _: () = assert!(mem::size_of::<AlignedBlock>() == VECTOR_ALIGNMENT);
+
 pub struct ChunkedVectors<T> {
     /// Vector's dimension.
-    ///
-    /// Each vector will consume `size_of::<T>() * dim` bytes.
     dim: usize,
+    /// Number of elements each vector occupies in a chunk.
+    ///
+    /// Each vector will consume `size_of::<T>() * stride` bytes. Equals `dim` unless vectors are
+    /// stored padded, see [`padded_dim`].
+    stride: usize,
     /// Number of stored vectors in all chunks.
     len: usize,
     /// Maximum number of vectors in each chunk.
     chunk_capacity: usize,
-    chunks: Vec<Vec<T>>,
+    chunks: Vec<Vec<AlignedBlock>>,
+    _phantom: PhantomData<T>,
 }
 
 impl<T: Copy + Clone + Default> ChunkedVectors<T> {
+    /// Vectors are stored tightly, one after another
     pub fn new(dim: usize) -> Self {
+        Self::with_stride(dim, dim)
+    }
+
+    /// Vectors are padded with zeroes to [`padded_dim`], every vector starts at an address
+    /// aligned to [`VECTOR_ALIGNMENT`]
+    pub fn new_padded(dim: usize) -> Self {
+        Self::with_stride(dim, padded_dim::<T>(dim))
+    }
+
+    fn with_stride(dim: usize, stride: usize) -> Self {
         assert_ne!(dim, 0, "The vector's dimension cannot be 0");
-        let vector_size = dim * mem::size_of::<T>();
+        debug_assert!(VECTOR_ALIGNMENT % mem::size_of::<T>() == 0);
+        let vector_size = stride * mem::size_of::<T>();
         let chunk_capacity = max(MIN_CHUNK_CAPACITY, CHUNK_SIZE / vector_size);
         Self {
             dim,
+            stride,
             len: 0,
             chunk_capacity,
             chunks: Vec::new(),
+            _phantom: PhantomData,
         }
     }
 
@@ -48,14 +75,27 @@ impl<T: Copy + Clone + Default> ChunkedVectors<T> {
         self.len == 0
     }
 
+    /// Number of elements returned by [`ChunkedVectors::get_padded`]
+    pub fn padded_dim(&self) -> usize {
+        self.stride
+    }
+
     pub fn get<TKey>(&self, key: TKey) -> &[T]
+    where
+        TKey: num_traits::cast::AsPrimitive<usize>,
+    {
+        &self.get_padded(key)[..self.dim]
+    }
+
+    /// Vector with its zero padding, see [`ChunkedVectors::new_padded`]
+    pub fn get_padded<TKey>(&self, key: TKey) -> &[T]
     where
         TKey: num_traits::cast::AsPrimitive<usize>,
     {
         let key: usize = key.as_();
-        let chunk_data = &self.chunks[key / self.chunk_capacity];
-        let idx = (key % self.chunk_capacity) * self.dim;
-        &chunk_data[idx..idx + self.dim]
+        let chunk_data = Self::chunk_elements(&self.chunks[key / self.chunk_capacity]);
+        let idx = (key % self.chunk_capacity) * self.stride;
+        &chunk_data[idx..idx + self.stride]
     }
 
     pub fn push(&mut self, vector: &[T]) -> Result<PointOffsetType, TryReserveError> {
@@ -71,33 +111,53 @@ impl<T: Copy + Clone + Default> ChunkedVectors<T> {
             .resize_with(self.len.div_ceil(self.chunk_capacity), Vec::new);
 
         let chunk_idx = key / self.chunk_capacity;
+        let idx = (key % self.chunk_capacity) * self.stride;
+        let required_blocks = self.blocks_for(idx + self.stride);
+        let full_chunk_blocks = self.blocks_for(self.chunk_capacity * self.stride);
         let chunk_data = &mut self.chunks[chunk_idx];
-        let idx = (key % self.chunk_capacity) * self.dim;
 
         // Grow the current chunk if needed to fit the new vector.
         //
         // All chunks are dynamically resized to fit their vectors in it.
-        // Chunks have a size of zero by default. It's grown with zeroes to fit new vectors.
+        // Chunks have a size of zero by default. It's grown with zeroes to fit new vectors, which
+        // also makes up the padding of padded vectors.
         //
         // The capacity for the first chunk is allocated normally to keep the memory footprint as
         // small as possible, see
         // <https://doc.rust-lang.org/std/vec/struct.Vec.html#capacity-and-reallocation>).
         // All other chunks allocate their capacity in full on first use to prevent expensive
         // reallocations when their data grows.
-        if chunk_data.len() < idx + self.dim {
+        if chunk_data.len() < required_blocks {
             // If the chunk is not the first one, allocate it fully on first use
             if chunk_idx != 0 {
-                let desired_capacity = self.chunk_capacity * self.dim;
-                chunk_data.try_set_capacity_exact(desired_capacity)?;
+                chunk_data.try_set_capacity_exact(full_chunk_blocks)?;
             }
-            chunk_data.resize_with(idx + self.dim, T::default);
+            chunk_data.resize(required_blocks, AlignedBlock::default());
         }
 
-        let data = &mut chunk_data[idx..idx + self.dim];
+        let data = &mut Self::chunk_elements_mut(chunk_data)[idx..idx + self.dim];
         data.copy_from_slice(vector);
 
         Ok(())
     }
+
+    /// Number of blocks to fit the given number of elements
+    fn blocks_for(&self, elements: usize) -> usize {
+        (elements * mem::size_of::<T>()).div_ceil(VECTOR_ALIGNMENT)
+    }
+
+    fn chunk_elements(chunk: &[AlignedBlock]) -> &[T] {
+        let len = chunk.len() * VECTOR_ALIGNMENT / mem::size_of::<T>();
+        // Safety: blocks are plain bytes, aligned at least as strictly as `T`, and `T` is a
+        // primitive vector element for which any bit pattern is valid
+        unsafe { std::slice::from_raw_parts(chunk.as_ptr().cast::<T>(), len) }
+    }
+
+    fn chunk_elements_mut(chunk: &mut [AlignedBlock]) -> &mut [T] {
+        let len = chunk.len() * VECTOR_ALIGNMENT / mem::size_of::<T>();
+        // Safety: see `chunk_elements`
+        unsafe { std::slice::from_raw_parts_mut(chunk.as_mut_ptr().cast::<T>(), len) }
+    }
 }
 
 impl quantization::EncodedStorage for ChunkedVectors<u8> {
@@ -152,20 +212,19 @@ impl quantization::EncodedStorage for ChunkedVectors<u8> {
     }
 }
 
-impl<T: Clone> TrySetCapacityExact for ChunkedVectors<T> {
+impl<T: Copy + Clone + Default> TrySetCapacityExact for ChunkedVectors<T> {
     fn try_set_capacity_exact(&mut self, capacity: usize) -> Result<(), TryReserveError> {
         let num_chunks = capacity.div_ceil(self.chunk_capacity);
         let last_chunk_idx = capacity / self.chunk_capacity;
         self.chunks.try_set_capacity_exact(num_chunks)?;
         self.chunks.resize_with(num_chunks, Vec::new);
         for chunk_idx in 0..num_chunks {
-            if chunk_idx == last_chunk_idx {
-                let desired_capacity = (capacity % self.chunk_capacity) * self.dim;
-                self.chunks[chunk_idx].try_set_capacity_exact(desired_capacity)?;
+            let desired_capacity = if chunk_idx == last_chunk_idx {
+                self.blocks_for((capacity % self.chunk_capacity) * self.stride)
             } else {
-                let desired_capacity = self.chunk_capacity * self.dim;
-                self.chunks[chunk_idx].try_set_capacity_exact(desired_capacity)?;
-            }
+                self.blocks_for(self.chunk_capacity * self.stride)
+            };
+            self.chunks[chunk_idx].try_set_capacity_exact(desired_capacity)?;
         }
         Ok(())
     }
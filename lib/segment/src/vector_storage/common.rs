@@ -1,3 +1,4 @@
+use std::mem;
 use std::sync::atomic::{AtomicBool, Ordering};
 
 static ASYNC_SCORER: AtomicBool = AtomicBool::new(false);
@@ -9,3 +10,17 @@ pub fn set_async_scorer(async_scorer: bool) {
 pub fn get_async_scorer() -> bool {
     ASYNC_SCORER.load(Ordering::Relaxed)
 }
+
+/// Alignment in bytes of vectors stored in padded layout
+///
+/// Matches the widest SIMD registers used by the distance kernels.
+pub const VECTOR_ALIGNMENT: usize = 32;
+
+/// Number of elements a vector of `dim` elements of type `T` occupies in padded layout
+///
+/// Vectors are padded with zeroes to a multiple of [`VECTOR_ALIGNMENT`] bytes, so that all vectors
+/// stored one after another start at an aligned address and kernels need no scalar tail.
+pub const fn padded_dim<T>(dim: usize) -> usize {
+    let lanes = VECTOR_ALIGNMENT / mem::size_of::<T>();
+    dim.div_ceil(lanes) * lanes
+}
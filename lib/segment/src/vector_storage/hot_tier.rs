@@ -0,0 +1,175 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use common::types::PointOffsetType;
+use parking_lot::Mutex;
+
+/// Position of a vector, which is not kept in RAM
+const COLD: u32 = u32::MAX;
+
+/// Hot vectors, stored contiguously in RAM
+struct HotVectors<T> {
+    /// Position of each vector in `data`, or `COLD` if the vector is only stored on disk
+    positions: Vec<u32>,
+    data: Vec<T>,
+}
+
+impl<T> HotVectors<T> {
+    fn empty(num_vectors: usize) -> Self {
+        Self {
+            positions: vec![COLD; num_vectors],
+            data: vec![],
+        }
+    }
+}
+
+/// RAM copy of the most frequently accessed vectors of a memory-mapped storage
+///
+/// Every read of a vector is counted. Hot vectors are selected in two steps, so readers are
+/// blocked only for a short time: [`HotTier::prepare`] copies the currently hottest vectors
+/// concurrently with readers, and [`HotTier::apply`] replaces the active hot vectors with them.
+pub struct HotTier<T> {
+    /// Fraction of vectors to keep in RAM
+    ratio: f32,
+    /// Number of reads of each vector, halved on every [`HotTier::prepare`]
+    access_counts: Vec<AtomicU32>,
+    active: HotVectors<T>,
+    pending: Mutex<Option<HotVectors<T>>>,
+}
+
+impl<T: Copy> HotTier<T> {
+    pub fn new(ratio: f32, num_vectors: usize) -> Self {
+        Self {
+            ratio,
+            access_counts: (0..num_vectors).map(|_| AtomicU32::new(0)).collect(),
+            active: HotVectors::empty(num_vectors),
+            pending: Mutex::new(None),
+        }
+    }
+
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    /// Count the access to the vector and return it, if it is kept in RAM
+    #[inline]
+    pub fn get(&self, key: PointOffsetType, dim: usize) -> Option<&[T]> {
+        self.access_counts
+            .get(key as usize)?
+            .fetch_add(1, Ordering::Relaxed);
+        let position = self.active.positions[key as usize];
+        if position == COLD {
+            return None;
+        }
+        let start = position as usize * dim;
+        Some(&self.active.data[start..start + dim])
+    }
+
+    /// Copy currently most frequently accessed vectors into RAM
+    ///
+    /// Vectors, which were never accessed since they were cold, are not promoted.
+    /// Access counts are halved, so old accesses gradually lose their weight.
+    /// Copied vectors are used by readers after [`HotTier::apply`].
+    pub fn prepare<'a>(&self, dim: usize, get_vector: impl Fn(PointOffsetType) -> &'a [T])
+    where
+        T: 'a,
+    {
+        let num_vectors = self.access_counts.len();
+        let hot_count =
+            ((num_vectors as f64 * self.ratio as f64).round() as usize).min(num_vectors);
+
+        let mut candidates: Vec<_> = self
+            .access_counts
+            .iter()
+            .enumerate()
+            .map(|(key, count)| {
+                let previous = count
+                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+                        Some(count / 2)
+                    })
+                    .unwrap_or_default();
+                (previous, key as PointOffsetType)
+            })
+            .filter(|(count, _)| *count > 0)
+            .collect();
+
+        if hot_count < candidates.len() {
+            candidates.select_nth_unstable_by(hot_count, |a, b| b.0.cmp(&a.0));
+            candidates.truncate(hot_count);
+        }
+
+        // Read vectors in the order of the file
+        let mut hot_keys: Vec<_> = candidates.into_iter().map(|(_, key)| key).collect();
+        hot_keys.sort_unstable();
+
+        let mut hot_vectors = HotVectors::empty(num_vectors);
+        hot_vectors.data.reserve_exact(hot_keys.len() * dim);
+        for (position, key) in hot_keys.into_iter().enumerate() {
+            hot_vectors.positions[key as usize] = position as u32;
+            hot_vectors.data.extend_from_slice(get_vector(key));
+        }
+
+        *self.pending.lock() = Some(hot_vectors);
+    }
+
+    /// Replace active hot vectors with the ones copied by [`HotTier::prepare`]
+    ///
+    /// Vectors, which are not hot anymore, are released from RAM.
+    pub fn apply(&mut self) {
+        if let Some(hot_vectors) = self.pending.get_mut().take() {
+            self.active = hot_vectors;
+        }
+    }
+
+    /// Number of vectors currently kept in RAM
+    pub fn hot_vector_count(&self, dim: usize) -> usize {
+        if dim == 0 {
+            0
+        } else {
+            self.active.data.len() / dim
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hot_tier_promotes_most_accessed_vectors() {
+        let dim = 2;
+        let num_vectors = 10;
+        let vectors: Vec<f32> = (0..num_vectors * dim).map(|i| i as f32).collect();
+        let get_vector = |key: PointOffsetType| {
+            let start = key as usize * dim;
+            &vectors[start..start + dim]
+        };
+
+        let mut hot_tier = HotTier::new(0.2, num_vectors);
+        assert!(hot_tier.get(0, dim).is_none());
+
+        for _ in 0..5 {
+            hot_tier.get(3, dim);
+            hot_tier.get(7, dim);
+        }
+        hot_tier.get(5, dim);
+
+        hot_tier.prepare(dim, get_vector);
+        // Prepared vectors are not used until applied
+        assert!(hot_tier.get(3, dim).is_none());
+
+        hot_tier.apply();
+        assert_eq!(hot_tier.hot_vector_count(dim), 2);
+        assert_eq!(hot_tier.get(3, dim), Some(get_vector(3)));
+        assert_eq!(hot_tier.get(7, dim), Some(get_vector(7)));
+        assert!(hot_tier.get(5, dim).is_none());
+
+        // Vector 5 becomes the most accessed one after counts decay
+        for _ in 0..20 {
+            hot_tier.get(5, dim);
+        }
+        hot_tier.prepare(dim, get_vector);
+        hot_tier.apply();
+        assert_eq!(hot_tier.get(5, dim), Some(get_vector(5)));
+        assert_eq!(hot_tier.hot_vector_count(dim), 2);
+    }
+}
@@ -1,5 +1,7 @@
-use std::fs::{create_dir_all, File, OpenOptions};
+use std::borrow::Cow;
+use std::fs::{self, create_dir_all, File, OpenOptions};
 use std::io::{self, Write};
+use std::mem::size_of;
 use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
@@ -7,14 +9,16 @@ use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
 use bitvec::prelude::BitSlice;
-use common::types::PointOffsetType;
+use common::types::{PointOffsetType, ScoreType};
 use memory::mmap_ops;
 
 use super::{DenseVectorStorage, VectorStorageEnum};
 use crate::common::operation_error::{check_process_stopped, OperationResult};
 use crate::common::Flusher;
 use crate::data_types::named_vectors::CowVector;
+use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{DenseVector, VectorElementType, VectorRef};
+use crate::spaces::simple::cosine_norm;
 use crate::types::Distance;
 use crate::vector_storage::common::get_async_scorer;
 use crate::vector_storage::mmap_vectors::MmapVectors;
@@ -22,6 +26,7 @@ use crate::vector_storage::VectorStorage;
 
 const VECTORS_PATH: &str = "matrix.dat";
 const DELETED_PATH: &str = "deleted.dat";
+const NORMS_PATH: &str = "norms.dat";
 
 /// Stores all vectors in mem-mapped file
 ///
@@ -29,11 +34,16 @@ const DELETED_PATH: &str = "deleted.dat";
 /// but possible to mark some vectors as removed
 ///
 /// Mem-mapped storage can only be constructed from another storage
-pub struct MemmapVectorStorage {
+pub struct MemmapVectorStorage<T: PrimitiveVectorElement = VectorElementType> {
     vectors_path: PathBuf,
     deleted_path: PathBuf,
-    mmap_store: Option<MmapVectors>,
+    mmap_store: Option<MmapVectors<T>>,
     distance: Distance,
+    /// Norms of stored vectors, if vectors are kept un-normalized. Persisted in `norms_path`.
+    norms: Option<Vec<ScoreType>>,
+    norms_path: PathBuf,
+    /// Per-dimension weights applied during scoring, taken from the segment config
+    dimension_weights: Option<Vec<VectorElementType>>,
 }
 
 pub fn open_memmap_vector_storage(
@@ -50,23 +60,123 @@ pub fn open_memmap_vector_storage_with_async_io(
     distance: Distance,
     with_async_io: bool,
 ) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_memmap_vector_storage_impl(path, dim, distance, with_async_io, false)?;
+
+    Ok(Arc::new(AtomicRefCell::new(VectorStorageEnum::Memmap(
+        Box::new(storage),
+    ))))
+}
+
+/// Open mem-mapped vector storage for `Cosine` distance, which keeps original vectors and caches their norms
+pub fn open_memmap_vector_storage_with_norms(
+    path: &Path,
+    dim: usize,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage =
+        open_memmap_vector_storage_impl(path, dim, Distance::Cosine, get_async_scorer(), true)?;
+
+    Ok(Arc::new(AtomicRefCell::new(VectorStorageEnum::Memmap(
+        Box::new(storage),
+    ))))
+}
+
+/// Open mem-mapped vector storage, which keeps vectors in half precision
+///
+/// Async IO is not supported for half precision vectors.
+pub fn open_memmap_half_vector_storage(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_memmap_vector_storage_impl(path, dim, distance, false, false)?;
+
+    Ok(Arc::new(AtomicRefCell::new(VectorStorageEnum::MemmapHalf(
+        Box::new(storage),
+    ))))
+}
+
+/// Open mem-mapped vector storage, which keeps vectors as unsigned bytes
+///
+/// Async IO is not supported for byte vectors.
+pub fn open_memmap_byte_vector_storage(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_memmap_vector_storage_impl(path, dim, distance, false, false)?;
+
+    Ok(Arc::new(AtomicRefCell::new(VectorStorageEnum::MemmapByte(
+        Box::new(storage),
+    ))))
+}
+
+fn open_memmap_vector_storage_impl<T: PrimitiveVectorElement>(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    with_async_io: bool,
+    with_norms: bool,
+) -> OperationResult<MemmapVectorStorage<T>> {
     create_dir_all(path)?;
 
     let vectors_path = path.join(VECTORS_PATH);
     let deleted_path = path.join(DELETED_PATH);
+    let norms_path = path.join(NORMS_PATH);
     let mmap_store = MmapVectors::open(&vectors_path, &deleted_path, dim, with_async_io)?;
 
-    Ok(Arc::new(AtomicRefCell::new(VectorStorageEnum::Memmap(
-        Box::new(MemmapVectorStorage {
-            vectors_path,
-            deleted_path,
-            mmap_store: Some(mmap_store),
-            distance,
-        }),
-    ))))
+    let norms = if with_norms {
+        let num_vectors = mmap_store.num_vectors;
+        let mut norms = if norms_path.exists() {
+            read_norms(&norms_path)?
+        } else {
+            vec![]
+        };
+        // Norms file is written after vectors, recompute it if it was not written completely
+        if norms.len() != num_vectors {
+            norms = (0..num_vectors as PointOffsetType)
+                .map(|key| vector_norm(mmap_store.get_vector(key)))
+                .collect();
+            write_norms(&norms_path, &norms)?;
+        }
+        Some(norms)
+    } else {
+        None
+    };
+
+    Ok(MemmapVectorStorage {
+        vectors_path,
+        deleted_path,
+        mmap_store: Some(mmap_store),
+        distance,
+        norms,
+        norms_path,
+        dimension_weights: None,
+    })
+}
+
+fn vector_norm<T: PrimitiveVectorElement>(vector: &[T]) -> ScoreType {
+    cosine_norm(&T::slice_to_float_cow(Cow::Borrowed(vector)))
+}
+
+fn read_norms(path: &Path) -> OperationResult<Vec<ScoreType>> {
+    let data = fs::read(path)?;
+    Ok(data
+        .chunks_exact(size_of::<ScoreType>())
+        .map(|bytes| ScoreType::from_ne_bytes(bytes.try_into().unwrap()))
+        .collect())
+}
+
+fn write_norms(path: &Path, norms: &[ScoreType]) -> OperationResult<()> {
+    fs::write(path, mmap_ops::transmute_to_u8_slice(norms))?;
+    Ok(())
 }
 
-impl MemmapVectorStorage {
+impl<T: PrimitiveVectorElement> MemmapVectorStorage<T> {
+    /// Attach per-dimension weights from the vector config, they are not persisted
+    pub fn set_dimension_weights(&mut self, weights: Vec<VectorElementType>) {
+        self.dimension_weights = Some(weights);
+    }
+
     pub fn prefault_mmap_pages(&self) -> Option<mmap_ops::PrefaultMmapPages> {
         Some(
             self.mmap_store
@@ -75,7 +185,30 @@ impl MemmapVectorStorage {
         )
     }
 
-    pub fn get_mmap_vectors(&self) -> &MmapVectors {
+    /// Keep the given fraction of the most frequently accessed vectors in RAM, `None` to disable
+    pub fn set_hot_tier_ratio(&mut self, ratio: Option<f32>) {
+        if let Some(mmap_store) = &mut self.mmap_store {
+            mmap_store.set_hot_tier_ratio(ratio);
+        }
+    }
+
+    /// Copy currently most frequently accessed vectors into RAM
+    ///
+    /// Returns false if the hot tier is disabled.
+    pub fn prepare_hot_tier(&self) -> bool {
+        self.mmap_store
+            .as_ref()
+            .map_or(false, MmapVectors::prepare_hot_tier)
+    }
+
+    /// Start reading prepared hot vectors from RAM
+    pub fn apply_hot_tier(&mut self) {
+        if let Some(mmap_store) = &mut self.mmap_store {
+            mmap_store.apply_hot_tier();
+        }
+    }
+
+    pub fn get_mmap_vectors(&self) -> &MmapVectors<T> {
         self.mmap_store.as_ref().unwrap()
     }
 
@@ -87,13 +220,41 @@ impl MemmapVectorStorage {
     }
 }
 
-impl DenseVectorStorage for MemmapVectorStorage {
-    fn get_dense(&self, key: PointOffsetType) -> &[VectorElementType] {
+impl<T: PrimitiveVectorElement> DenseVectorStorage<T> for MemmapVectorStorage<T> {
+    fn get_dense(&self, key: PointOffsetType) -> &[T] {
         self.mmap_store.as_ref().unwrap().get_vector(key)
     }
+
+    fn padded_dim(&self) -> Option<usize> {
+        self.mmap_store.as_ref().unwrap().padded_dim()
+    }
+
+    fn get_dense_padded(&self, key: PointOffsetType) -> &[T] {
+        let mmap_store = self.mmap_store.as_ref().unwrap();
+        if mmap_store.padded_dim().is_some() {
+            mmap_store.get_padded_vector(key)
+        } else {
+            mmap_store.get_vector(key)
+        }
+    }
+
+    fn has_norms(&self) -> bool {
+        self.norms.is_some()
+    }
+
+    fn get_norm(&self, key: PointOffsetType) -> ScoreType {
+        self.norms
+            .as_ref()
+            .and_then(|norms| norms.get(key as usize).copied())
+            .unwrap_or(1.0)
+    }
+
+    fn dimension_weights(&self) -> Option<&[VectorElementType]> {
+        self.dimension_weights.as_deref()
+    }
 }
 
-impl VectorStorage for MemmapVectorStorage {
+impl<T: PrimitiveVectorElement> VectorStorage for MemmapVectorStorage<T> {
     fn vector_dim(&self) -> usize {
         self.mmap_store.as_ref().unwrap().dim
     }
@@ -111,7 +272,7 @@ impl VectorStorage for MemmapVectorStorage {
     }
 
     fn get_vector(&self, key: PointOffsetType) -> CowVector {
-        self.get_dense(key).into()
+        T::slice_to_float_cow(Cow::Borrowed(self.get_dense(key))).into()
     }
 
     fn insert_vector(&mut self, _key: PointOffsetType, _vector: VectorRef) -> OperationResult<()> {
@@ -125,6 +286,7 @@ impl VectorStorage for MemmapVectorStorage {
         stopped: &AtomicBool,
     ) -> OperationResult<Range<PointOffsetType>> {
         let dim = self.vector_dim();
+        let padding = vec![0u8; self.mmap_store.as_ref().unwrap().padding_size()];
         let start_index = self.mmap_store.as_ref().unwrap().num_vectors as PointOffsetType;
         let mut end_index = start_index;
 
@@ -140,10 +302,16 @@ impl VectorStorage for MemmapVectorStorage {
         for id in other_ids {
             check_process_stopped(stopped)?;
             let vector: DenseVector = other.get_vector(id).try_into()?;
-            let raw_bites = mmap_ops::transmute_to_u8_slice(&vector);
+            let vector = T::slice_from_float_cow(Cow::Owned(vector));
+            let raw_bites = mmap_ops::transmute_to_u8_slice::<T>(&vector);
             vectors_file.write_all(raw_bites)?;
+            vectors_file.write_all(&padding)?;
             end_index += 1;
 
+            if let Some(norms) = &mut self.norms {
+                norms.push(vector_norm(&vector));
+            }
+
             // Remember deleted IDs so we can propagate deletions later
             if other.is_deleted_vector(id) {
                 deleted_ids.push((start_index + id) as PointOffsetType);
@@ -152,6 +320,10 @@ impl VectorStorage for MemmapVectorStorage {
         vectors_file.flush()?;
         drop(vectors_file);
 
+        if let Some(norms) = &self.norms {
+            write_norms(&self.norms_path, norms)?;
+        }
+
         // Load store with updated files
         self.mmap_store.replace(MmapVectors::open(
             &self.vectors_path,
@@ -181,7 +353,19 @@ impl VectorStorage for MemmapVectorStorage {
     }
 
     fn files(&self) -> Vec<PathBuf> {
-        vec![self.vectors_path.clone(), self.deleted_path.clone()]
+        let mut files = vec![self.vectors_path.clone(), self.deleted_path.clone()];
+        if self.norms.is_some() {
+            files.push(self.norms_path.clone());
+        }
+        files
+    }
+
+    fn immutable_files(&self) -> Vec<PathBuf> {
+        let mut files = vec![self.vectors_path.clone()];
+        if self.norms.is_some() {
+            files.push(self.norms_path.clone());
+        }
+        files
     }
 
     fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
@@ -225,6 +409,7 @@ mod tests {
     use crate::fixtures::payload_context_fixture::FixtureIdTracker;
     use crate::id_tracker::IdTracker;
     use crate::types::{PointIdType, QuantizationConfig, ScalarQuantizationConfig};
+    use crate::vector_storage::common::VECTOR_ALIGNMENT;
     use crate::vector_storage::new_raw_scorer;
     use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
     use crate::vector_storage::simple_dense_vector_storage::open_simple_vector_storage;
@@ -715,4 +900,140 @@ mod tests {
             assert!((orig - quant).abs() < 0.15);
         }
     }
+
+    #[test]
+    fn test_padded_vectors() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let points = [vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]];
+
+        let storage = open_memmap_vector_storage(dir.path(), 3, Distance::Dot).unwrap();
+        {
+            let dir2 = Builder::new().prefix("db_dir").tempdir().unwrap();
+            let db = open_db(dir2.path(), &[DB_VECTOR_CF]).unwrap();
+            let storage2 = open_simple_vector_storage(db, DB_VECTOR_CF, 3, Distance::Dot).unwrap();
+            for (i, point) in points.iter().enumerate() {
+                storage2
+                    .borrow_mut()
+                    .insert_vector(i as PointOffsetType, point.as_slice().into())
+                    .unwrap();
+            }
+            storage
+                .borrow_mut()
+                .update_from(&storage2.borrow(), &mut Box::new(0..2), &Default::default())
+                .unwrap();
+        }
+
+        let borrowed_storage = storage.borrow();
+        let VectorStorageEnum::Memmap(memmap_storage) = &*borrowed_storage else {
+            panic!("expected memmap storage");
+        };
+        assert_eq!(memmap_storage.padded_dim(), Some(8));
+        for (i, point) in points.iter().enumerate() {
+            let key = i as PointOffsetType;
+            let padded = memmap_storage.get_dense_padded(key);
+            assert_eq!(padded.len(), 8);
+            assert_eq!(padded.as_ptr() as usize % VECTOR_ALIGNMENT, 0);
+            assert_eq!(&padded[..3], point.as_slice());
+            assert!(padded[3..].iter().all(|&x| x == 0.0));
+            assert_eq!(memmap_storage.get_dense(key), point.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_legacy_unpadded_vectors() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let points = [
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+            vec![7.0, 8.0, 9.0],
+        ];
+
+        // File in the format used before vectors were stored padded
+        let mut legacy_data = b"data".to_vec();
+        for point in &points[..2] {
+            legacy_data.extend_from_slice(transmute_to_u8_slice(point.as_slice()));
+        }
+        fs::write(dir.path().join(VECTORS_PATH), legacy_data).unwrap();
+
+        let check_vectors = |storage: &VectorStorageEnum, count: usize| {
+            let VectorStorageEnum::Memmap(memmap_storage) = storage else {
+                panic!("expected memmap storage");
+            };
+            assert_eq!(memmap_storage.padded_dim(), None);
+            assert_eq!(memmap_storage.total_vector_count(), count);
+            for (i, point) in points[..count].iter().enumerate() {
+                let key = i as PointOffsetType;
+                assert_eq!(memmap_storage.get_dense(key), point.as_slice());
+            }
+        };
+
+        let storage = open_memmap_vector_storage(dir.path(), 3, Distance::Dot).unwrap();
+        check_vectors(&storage.borrow(), 2);
+
+        // Appended vectors keep the format of the existing file
+        {
+            let dir2 = Builder::new().prefix("db_dir").tempdir().unwrap();
+            let db = open_db(dir2.path(), &[DB_VECTOR_CF]).unwrap();
+            let storage2 = open_simple_vector_storage(db, DB_VECTOR_CF, 3, Distance::Dot).unwrap();
+            storage2
+                .borrow_mut()
+                .insert_vector(0, points[2].as_slice().into())
+                .unwrap();
+            storage
+                .borrow_mut()
+                .update_from(&storage2.borrow(), &mut Box::new(0..1), &Default::default())
+                .unwrap();
+        }
+        check_vectors(&storage.borrow(), 3);
+        drop(storage);
+
+        let storage = open_memmap_vector_storage(dir.path(), 3, Distance::Dot).unwrap();
+        check_vectors(&storage.borrow(), 3);
+    }
+
+    #[test]
+    fn test_weighted_scoring() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let points = [vec![0.0, 0.0], vec![3.0, 0.0], vec![0.0, 2.0]];
+        let id_tracker = FixtureIdTracker::new(points.len());
+
+        let storage = open_memmap_vector_storage(dir.path(), 2, Distance::Euclid).unwrap();
+        {
+            let dir2 = Builder::new().prefix("db_dir").tempdir().unwrap();
+            let db = open_db(dir2.path(), &[DB_VECTOR_CF]).unwrap();
+            let storage2 =
+                open_simple_vector_storage(db, DB_VECTOR_CF, 2, Distance::Euclid).unwrap();
+            for (i, point) in points.iter().enumerate() {
+                storage2
+                    .borrow_mut()
+                    .insert_vector(i as PointOffsetType, point.as_slice().into())
+                    .unwrap();
+            }
+            storage
+                .borrow_mut()
+                .update_from(&storage2.borrow(), &mut Box::new(0..3), &Default::default())
+                .unwrap();
+        }
+
+        // Second dimension weights 4 times more, so point 2 is further away than point 1
+        storage
+            .borrow_mut()
+            .set_dimension_weights(vec![1.0, 4.0])
+            .unwrap();
+
+        let borrowed_storage = storage.borrow();
+        let raw_scorer = new_raw_scorer(
+            QueryVector::from(vec![0.0, 0.0]),
+            &borrowed_storage,
+            id_tracker.deleted_point_bitslice(),
+        )
+        .unwrap();
+        let res = raw_scorer.peek_top_all(3);
+        assert_eq!(res.iter().map(|p| p.idx).collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!(
+            res.iter().map(|p| p.score).collect::<Vec<_>>(),
+            [0.0, -9.0, -16.0]
+        );
+        assert_eq!(raw_scorer.score_internal(1, 2), -25.0);
+    }
 }
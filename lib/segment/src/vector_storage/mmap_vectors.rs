@@ -1,6 +1,7 @@
 use std::fs::{File, OpenOptions};
 use std::io::Write;
-use std::mem::{self, size_of, transmute};
+use std::marker::PhantomData;
+use std::mem::{self, size_of};
 use std::path::Path;
 use std::sync::Arc;
 
@@ -14,20 +15,31 @@ use crate::common::error_logging::LogError;
 use crate::common::mmap_type::MmapBitSlice;
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
+use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::VectorElementType;
+use crate::types::VectorStorageDatatype;
 #[cfg(target_os = "linux")]
 use crate::vector_storage::async_io::UringReader;
 #[cfg(not(target_os = "linux"))]
 use crate::vector_storage::async_io_mock::UringReader;
+use crate::vector_storage::common::{padded_dim, VECTOR_ALIGNMENT};
+use crate::vector_storage::hot_tier::HotTier;
 
 const HEADER_SIZE: usize = 4;
+/// Header of vector files created before vectors were stored padded
 const VECTORS_HEADER: &[u8; HEADER_SIZE] = b"data";
+/// Header of vector files storing vectors padded, vector data starts at [`VECTOR_ALIGNMENT`]
+const PADDED_VECTORS_HEADER: &[u8; HEADER_SIZE] = b"dpad";
 const DELETED_HEADER: &[u8; HEADER_SIZE] = b"drop";
 
 /// Mem-mapped file
-pub struct MmapVectors {
+pub struct MmapVectors<T: PrimitiveVectorElement = VectorElementType> {
     pub dim: usize,
     pub num_vectors: usize,
+    /// Number of elements each vector occupies in the file, see [`padded_dim`]
+    stride: usize,
+    /// Byte offset of the first vector in the file
+    data_start: usize,
     /// Memory mapped file for vector data
     ///
     /// Has an exact size to fit a header and `num_vectors` of vectors.
@@ -39,9 +51,12 @@ pub struct MmapVectors {
     deleted: MmapBitSlice,
     /// Current number of deleted vectors.
     pub deleted_count: usize,
+    /// RAM copy of the most frequently accessed vectors, if enabled
+    hot_tier: Option<HotTier<T>>,
+    _phantom: PhantomData<T>,
 }
 
-impl MmapVectors {
+impl<T: PrimitiveVectorElement> MmapVectors<T> {
     pub fn open(
         vectors_path: &Path,
         deleted_path: &Path,
@@ -49,10 +64,18 @@ impl MmapVectors {
         with_async_io: bool,
     ) -> OperationResult<Self> {
         // Allocate/open vectors mmap
-        ensure_mmap_file_size(vectors_path, VECTORS_HEADER, None)
+        ensure_mmap_file_size(vectors_path, &padded_vectors_header(), None)
             .describe("Create mmap data file")?;
         let mmap = mmap_ops::open_read_mmap(vectors_path).describe("Open mmap for reading")?;
-        let num_vectors = (mmap.len() - HEADER_SIZE) / dim / size_of::<VectorElementType>();
+
+        // Files created before padding was introduced keep their vectors tightly packed
+        let (data_start, stride) = if mmap.starts_with(&PADDED_VECTORS_HEADER[..]) {
+            (VECTOR_ALIGNMENT, padded_dim::<T>(dim))
+        } else {
+            debug_assert!(mmap.starts_with(&VECTORS_HEADER[..]));
+            (HEADER_SIZE, dim)
+        };
+        let num_vectors = (mmap.len() - data_start) / stride / size_of::<T>();
 
         // Allocate/open deleted mmap
         let deleted_mmap_size = deleted_mmap_size(num_vectors);
@@ -71,11 +94,18 @@ impl MmapVectors {
         let deleted = MmapBitSlice::try_from(deleted_mmap, deleted_mmap_data_start())?;
         let deleted_count = deleted.count_ones();
 
-        let uring_reader = if with_async_io {
+        // Async IO reads vectors in full precision only
+        let uring_reader = if with_async_io && T::datatype() == VectorStorageDatatype::Float32 {
             // Keep file handle open for async IO
             let vectors_file = File::open(vectors_path)?;
-            let raw_size = dim * size_of::<VectorElementType>();
-            Some(UringReader::new(vectors_file, raw_size, HEADER_SIZE)?)
+            let raw_size = dim * size_of::<T>();
+            let stride_size = stride * size_of::<T>();
+            Some(UringReader::new(
+                vectors_file,
+                raw_size,
+                stride_size,
+                data_start,
+            )?)
         } else {
             None
         };
@@ -83,10 +113,14 @@ impl MmapVectors {
         Ok(MmapVectors {
             dim,
             num_vectors,
+            stride,
+            data_start,
             mmap: mmap.into(),
             uring_reader: Mutex::new(uring_reader),
             deleted,
             deleted_count,
+            hot_tier: None,
+            _phantom: PhantomData,
         })
     }
 
@@ -99,8 +133,8 @@ impl MmapVectors {
     }
 
     pub fn data_offset(&self, key: PointOffsetType) -> Option<usize> {
-        let vector_data_length = self.dim * size_of::<VectorElementType>();
-        let offset = (key as usize) * vector_data_length + HEADER_SIZE;
+        let vector_data_length = self.stride * size_of::<T>();
+        let offset = (key as usize) * vector_data_length + self.data_start;
         if key >= (self.num_vectors as PointOffsetType) {
             return None;
         }
@@ -108,21 +142,84 @@ impl MmapVectors {
     }
 
     pub fn raw_size(&self) -> usize {
-        self.dim * size_of::<VectorElementType>()
+        self.dim * size_of::<T>()
+    }
+
+    /// Number of zero bytes following each vector in the file
+    pub fn padding_size(&self) -> usize {
+        (self.stride - self.dim) * size_of::<T>()
+    }
+
+    /// Number of elements in vectors returned by [`MmapVectors::get_padded_vector`]
+    ///
+    /// `None` if the file was created before vectors were stored padded.
+    pub fn padded_dim(&self) -> Option<usize> {
+        (self.data_start == VECTOR_ALIGNMENT).then_some(self.stride)
     }
 
-    pub fn raw_vector_offset(&self, offset: usize) -> &[VectorElementType] {
+    pub fn raw_vector_offset(&self, offset: usize) -> &[T] {
         let byte_slice = &self.mmap[offset..(offset + self.raw_size())];
-        let arr: &[VectorElementType] = unsafe { transmute(byte_slice) };
-        &arr[0..self.dim]
+        unsafe { std::slice::from_raw_parts(byte_slice.as_ptr() as *const T, self.dim) }
     }
 
     /// Returns reference to vector data by key
-    pub fn get_vector(&self, key: PointOffsetType) -> &[VectorElementType] {
+    pub fn get_vector(&self, key: PointOffsetType) -> &[T] {
+        if let Some(vector) = self
+            .hot_tier
+            .as_ref()
+            .and_then(|hot_tier| hot_tier.get(key, self.dim))
+        {
+            return vector;
+        }
+        self.get_mmap_vector(key)
+    }
+
+    fn get_mmap_vector(&self, key: PointOffsetType) -> &[T] {
         let offset = self.data_offset(key).unwrap();
         self.raw_vector_offset(offset)
     }
 
+    /// Returns reference to vector data by key, followed by its zero padding
+    ///
+    /// Always reads from the mmap, vectors kept in the hot tier are not padded.
+    pub fn get_padded_vector(&self, key: PointOffsetType) -> &[T] {
+        let offset = self.data_offset(key).unwrap();
+        let byte_slice = &self.mmap[offset..(offset + self.stride * size_of::<T>())];
+        unsafe { std::slice::from_raw_parts(byte_slice.as_ptr() as *const T, self.stride) }
+    }
+
+    /// Keep the given fraction of the most frequently accessed vectors in RAM
+    ///
+    /// `None` disables the hot tier and releases vectors kept in RAM.
+    pub fn set_hot_tier_ratio(&mut self, ratio: Option<f32>) {
+        match ratio {
+            Some(ratio) => {
+                if self.hot_tier.as_ref().map(HotTier::ratio) != Some(ratio) {
+                    self.hot_tier = Some(HotTier::new(ratio, self.num_vectors));
+                }
+            }
+            None => self.hot_tier = None,
+        }
+    }
+
+    /// Copy currently most frequently accessed vectors into RAM, see [`HotTier::prepare`]
+    ///
+    /// Returns false if the hot tier is disabled.
+    pub fn prepare_hot_tier(&self) -> bool {
+        let Some(hot_tier) = &self.hot_tier else {
+            return false;
+        };
+        hot_tier.prepare(self.dim, |key| self.get_mmap_vector(key));
+        true
+    }
+
+    /// Start reading vectors copied by [`MmapVectors::prepare_hot_tier`] from RAM
+    pub fn apply_hot_tier(&mut self) {
+        if let Some(hot_tier) = &mut self.hot_tier {
+            hot_tier.apply();
+        }
+    }
+
     pub fn delete(&mut self, key: PointOffsetType) -> bool {
         if self.num_vectors <= key as usize {
             return false;
@@ -150,7 +247,9 @@ impl MmapVectors {
     pub fn prefault_mmap_pages(&self, path: &Path) -> mmap_ops::PrefaultMmapPages {
         mmap_ops::PrefaultMmapPages::new(self.mmap.clone(), Some(path))
     }
+}
 
+impl MmapVectors<VectorElementType> {
     #[cfg(target_os = "linux")]
     fn process_points_uring(
         &self,
@@ -197,6 +296,13 @@ impl MmapVectors {
     }
 }
 
+/// Header for newly created vector files, padded so that the first vector is aligned
+fn padded_vectors_header() -> [u8; VECTOR_ALIGNMENT] {
+    let mut header = [0; VECTOR_ALIGNMENT];
+    header[..HEADER_SIZE].copy_from_slice(PADDED_VECTORS_HEADER);
+    header
+}
+
 /// Ensure the given mmap file exists and is the given size
 ///
 /// # Arguments
@@ -5,11 +5,13 @@ mod chunked_mmap_vectors;
 mod chunked_utils;
 pub mod chunked_vectors;
 mod dynamic_mmap_flags;
+mod hot_tier;
 pub mod memmap_vector_storage;
 mod mmap_vectors;
 pub mod quantized;
 pub mod raw_scorer;
 pub mod simple_dense_vector_storage;
+pub mod simple_multi_dense_vector_storage;
 mod vector_storage_base;
 
 #[cfg(test)]
@@ -1,5 +1,7 @@
 mod quantized_custom_query_scorer;
 mod quantized_mmap_storage;
+mod quantized_multi_custom_query_scorer;
+mod quantized_multi_query_scorer;
 mod quantized_query_scorer;
 mod quantized_scorer_builder;
 pub mod quantized_vectors;
@@ -0,0 +1,85 @@
+use common::types::{PointOffsetType, ScoreType};
+
+use super::quantized_multi_query_scorer::score_encoded_multi;
+use super::quantized_vectors::MultivectorOffset;
+use crate::data_types::vectors::MultiDenseVector;
+use crate::types::{Distance, MultiVectorConfig};
+use crate::vector_storage::query::{Query, TransformInto};
+use crate::vector_storage::query_scorer::QueryScorer;
+
+pub struct QuantizedMultiCustomQueryScorer<
+    'a,
+    TEncodedQuery,
+    TEncodedVectors,
+    TQuery: Query<Vec<TEncodedQuery>>,
+> where
+    TEncodedVectors: quantization::EncodedVectors<TEncodedQuery>,
+{
+    query: TQuery,
+    quantized_storage: &'a TEncodedVectors,
+    offsets: &'a [MultivectorOffset],
+    multivector_config: MultiVectorConfig,
+    phantom: std::marker::PhantomData<TEncodedQuery>,
+}
+
+impl<'a, TEncodedQuery, TEncodedVectors, TQuery: Query<Vec<TEncodedQuery>>>
+    QuantizedMultiCustomQueryScorer<'a, TEncodedQuery, TEncodedVectors, TQuery>
+where
+    TEncodedVectors: quantization::EncodedVectors<TEncodedQuery>,
+{
+    pub fn new<TOriginalQuery>(
+        raw_query: TOriginalQuery,
+        quantized_storage: &'a TEncodedVectors,
+        offsets: &'a [MultivectorOffset],
+        multivector_config: MultiVectorConfig,
+        distance: Distance,
+    ) -> Self
+    where
+        TOriginalQuery: TransformInto<TQuery, MultiDenseVector, Vec<TEncodedQuery>>,
+    {
+        let query = raw_query
+            .transform(|vector| {
+                let vector = distance.preprocess_multi_vector(vector);
+                Ok(vector
+                    .multi_vectors()
+                    .map(|inner_vector| quantized_storage.encode_query(inner_vector))
+                    .collect())
+            })
+            .unwrap();
+
+        Self {
+            query,
+            quantized_storage,
+            offsets,
+            multivector_config,
+            phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<TEncodedQuery, TEncodedVectors, TQuery: Query<Vec<TEncodedQuery>>>
+    QueryScorer<MultiDenseVector>
+    for QuantizedMultiCustomQueryScorer<'_, TEncodedQuery, TEncodedVectors, TQuery>
+where
+    TEncodedVectors: quantization::EncodedVectors<TEncodedQuery>,
+{
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        let offset = &self.offsets[idx as usize];
+        self.query.score_by(|this| {
+            score_encoded_multi(
+                &self.multivector_config,
+                this,
+                self.quantized_storage,
+                offset,
+            )
+        })
+    }
+
+    fn score(&self, _v2: &MultiDenseVector) -> ScoreType {
+        unimplemented!("This method is not expected to be called for quantized scorer")
+    }
+
+    fn score_internal(&self, _point_a: PointOffsetType, _point_b: PointOffsetType) -> ScoreType {
+        unimplemented!("Custom scorer compares against multiple vectors, not just one")
+    }
+}
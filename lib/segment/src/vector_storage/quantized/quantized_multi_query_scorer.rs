@@ -0,0 +1,128 @@
+use common::types::{PointOffsetType, ScoreType};
+
+use super::quantized_vectors::MultivectorOffset;
+use crate::data_types::vectors::MultiDenseVector;
+use crate::types::{Distance, MultiVectorComparator, MultiVectorConfig};
+use crate::vector_storage::query_scorer::QueryScorer;
+
+pub struct QuantizedMultiQueryScorer<'a, TEncodedQuery, TEncodedVectors>
+where
+    TEncodedVectors: quantization::EncodedVectors<TEncodedQuery>,
+{
+    original_query: MultiDenseVector,
+    query: Vec<TEncodedQuery>,
+    quantized_data: &'a TEncodedVectors,
+    offsets: &'a [MultivectorOffset],
+    multivector_config: MultiVectorConfig,
+    distance: Distance,
+}
+
+/// Compare encoded query vectors against the inner vectors of a stored multivector
+pub(super) fn score_encoded_multi<TEncodedQuery, TEncodedVectors>(
+    multivector_config: &MultiVectorConfig,
+    query: &[TEncodedQuery],
+    quantized_data: &TEncodedVectors,
+    offset: &MultivectorOffset,
+) -> ScoreType
+where
+    TEncodedVectors: quantization::EncodedVectors<TEncodedQuery>,
+{
+    match multivector_config.comparator {
+        MultiVectorComparator::MaxSim => query
+            .iter()
+            .map(|inner_query| {
+                offset
+                    .inner_ids()
+                    .map(|inner_id| quantized_data.score_point(inner_query, inner_id))
+                    .fold(None, |max: Option<ScoreType>, score| {
+                        Some(max.map_or(score, |max| max.max(score)))
+                    })
+                    .unwrap_or_default()
+            })
+            .sum(),
+    }
+}
+
+impl<'a, TEncodedQuery, TEncodedVectors>
+    QuantizedMultiQueryScorer<'a, TEncodedQuery, TEncodedVectors>
+where
+    TEncodedVectors: quantization::EncodedVectors<TEncodedQuery>,
+{
+    pub fn new(
+        raw_query: MultiDenseVector,
+        quantized_data: &'a TEncodedVectors,
+        offsets: &'a [MultivectorOffset],
+        multivector_config: MultiVectorConfig,
+        distance: Distance,
+    ) -> Self {
+        let original_query = distance.preprocess_multi_vector(raw_query);
+        let query = original_query
+            .multi_vectors()
+            .map(|inner_vector| quantized_data.encode_query(inner_vector))
+            .collect();
+
+        Self {
+            original_query,
+            query,
+            quantized_data,
+            offsets,
+            multivector_config,
+            distance,
+        }
+    }
+}
+
+impl<TEncodedQuery, TEncodedVectors> QueryScorer<MultiDenseVector>
+    for QuantizedMultiQueryScorer<'_, TEncodedQuery, TEncodedVectors>
+where
+    TEncodedVectors: quantization::EncodedVectors<TEncodedQuery>,
+{
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        score_encoded_multi(
+            &self.multivector_config,
+            &self.query,
+            self.quantized_data,
+            &self.offsets[idx as usize],
+        )
+    }
+
+    fn score(&self, v2: &MultiDenseVector) -> ScoreType {
+        debug_assert!(
+            false,
+            "This method is not expected to be called for quantized scorer"
+        );
+        match self.multivector_config.comparator {
+            MultiVectorComparator::MaxSim => self
+                .original_query
+                .multi_vectors()
+                .map(|query| {
+                    v2.multi_vectors()
+                        .map(|other| self.distance.similarity(query, other))
+                        .fold(None, |max: Option<ScoreType>, score| {
+                            Some(max.map_or(score, |max| max.max(score)))
+                        })
+                        .unwrap_or_default()
+                })
+                .sum(),
+        }
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        let offset_a = &self.offsets[point_a as usize];
+        let offset_b = &self.offsets[point_b as usize];
+        match self.multivector_config.comparator {
+            MultiVectorComparator::MaxSim => offset_a
+                .inner_ids()
+                .map(|inner_a| {
+                    offset_b
+                        .inner_ids()
+                        .map(|inner_b| self.quantized_data.score_internal(inner_a, inner_b))
+                        .fold(None, |max: Option<ScoreType>, score| {
+                            Some(max.map_or(score, |max| max.max(score)))
+                        })
+                        .unwrap_or_default()
+                })
+                .sum(),
+        }
+    }
+}
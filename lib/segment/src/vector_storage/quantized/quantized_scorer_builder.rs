@@ -4,11 +4,13 @@ use bitvec::slice::BitSlice;
 use quantization::EncodedVectors;
 
 use super::quantized_custom_query_scorer::QuantizedCustomQueryScorer;
+use super::quantized_multi_custom_query_scorer::QuantizedMultiCustomQueryScorer;
+use super::quantized_multi_query_scorer::QuantizedMultiQueryScorer;
 use super::quantized_query_scorer::QuantizedQueryScorer;
-use super::quantized_vectors::QuantizedVectorStorage;
+use super::quantized_vectors::{MultivectorOffset, QuantizedVectorStorage};
 use crate::common::operation_error::OperationResult;
-use crate::data_types::vectors::{DenseVector, QueryVector};
-use crate::types::Distance;
+use crate::data_types::vectors::{DenseVector, MultiDenseVector, QueryVector};
+use crate::types::{Distance, MultiVectorConfig};
 use crate::vector_storage::query::context_query::ContextQuery;
 use crate::vector_storage::query::discovery_query::DiscoveryQuery;
 use crate::vector_storage::query::reco_query::RecoQuery;
@@ -17,6 +19,8 @@ use crate::vector_storage::{raw_scorer_from_query_scorer, RawScorer};
 
 pub(super) struct QuantizedScorerBuilder<'a> {
     quantized_storage: &'a QuantizedVectorStorage,
+    multivector_config: Option<&'a MultiVectorConfig>,
+    multivector_offsets: &'a [MultivectorOffset],
     query: QueryVector,
     point_deleted: &'a BitSlice,
     vec_deleted: &'a BitSlice,
@@ -25,8 +29,11 @@ pub(super) struct QuantizedScorerBuilder<'a> {
 }
 
 impl<'a> QuantizedScorerBuilder<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         quantized_storage: &'a QuantizedVectorStorage,
+        multivector_config: Option<&'a MultiVectorConfig>,
+        multivector_offsets: &'a [MultivectorOffset],
         query: QueryVector,
         point_deleted: &'a BitSlice,
         vec_deleted: &'a BitSlice,
@@ -35,6 +42,8 @@ impl<'a> QuantizedScorerBuilder<'a> {
     ) -> Self {
         Self {
             quantized_storage,
+            multivector_config,
+            multivector_offsets,
             query,
             point_deleted,
             vec_deleted,
@@ -59,8 +68,15 @@ impl<'a> QuantizedScorerBuilder<'a> {
         self,
         quantized_storage: &'a impl EncodedVectors<TEncodedQuery>,
     ) -> OperationResult<Box<dyn RawScorer + 'a>> {
+        if let Some(multivector_config) = self.multivector_config {
+            let multivector_config = *multivector_config;
+            return self.new_quantized_multi_scorer(quantized_storage, multivector_config);
+        }
+
         let Self {
             quantized_storage: _same_as_quantized_storage_in_args,
+            multivector_config: _,
+            multivector_offsets: _,
             query,
             point_deleted,
             vec_deleted,
@@ -95,4 +111,68 @@ impl<'a> QuantizedScorerBuilder<'a> {
             }
         }
     }
+    fn new_quantized_multi_scorer<TEncodedQuery: 'a>(
+        self,
+        quantized_storage: &'a impl EncodedVectors<TEncodedQuery>,
+        multivector_config: MultiVectorConfig,
+    ) -> OperationResult<Box<dyn RawScorer + 'a>> {
+        let Self {
+            quantized_storage: _same_as_quantized_storage_in_args,
+            multivector_config: _,
+            multivector_offsets,
+            query,
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+            distance,
+        } = self;
+
+        match query {
+            QueryVector::Nearest(vector) => {
+                let query_scorer = QuantizedMultiQueryScorer::new(
+                    vector.try_into()?,
+                    quantized_storage,
+                    multivector_offsets,
+                    multivector_config,
+                    *distance,
+                );
+                raw_scorer_from_query_scorer(query_scorer, point_deleted, vec_deleted, is_stopped)
+            }
+            QueryVector::Recommend(reco_query) => {
+                let reco_query: RecoQuery<MultiDenseVector> = reco_query.transform_into()?;
+                let query_scorer = QuantizedMultiCustomQueryScorer::new(
+                    reco_query,
+                    quantized_storage,
+                    multivector_offsets,
+                    multivector_config,
+                    *distance,
+                );
+                raw_scorer_from_query_scorer(query_scorer, point_deleted, vec_deleted, is_stopped)
+            }
+            QueryVector::Discovery(discovery_query) => {
+                let discovery_query: DiscoveryQuery<MultiDenseVector> =
+                    discovery_query.transform_into()?;
+                let query_scorer = QuantizedMultiCustomQueryScorer::new(
+                    discovery_query,
+                    quantized_storage,
+                    multivector_offsets,
+                    multivector_config,
+                    *distance,
+                );
+                raw_scorer_from_query_scorer(query_scorer, point_deleted, vec_deleted, is_stopped)
+            }
+            QueryVector::Context(context_query) => {
+                let context_query: ContextQuery<MultiDenseVector> =
+                    context_query.transform_into()?;
+                let query_scorer = QuantizedMultiCustomQueryScorer::new(
+                    context_query,
+                    quantized_storage,
+                    multivector_offsets,
+                    multivector_config,
+                    *distance,
+                );
+                raw_scorer_from_query_scorer(query_scorer, point_deleted, vec_deleted, is_stopped)
+            }
+        }
+    }
 }
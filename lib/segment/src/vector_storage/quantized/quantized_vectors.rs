@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 
@@ -9,27 +10,48 @@ use quantization::{EncodedVectors, EncodedVectorsPQ, EncodedVectorsU8};
 use serde::{Deserialize, Serialize};
 
 use super::quantized_scorer_builder::QuantizedScorerBuilder;
-use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::vector_utils::TrySetCapacityExact;
+use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{QueryVector, VectorElementType};
 use crate::types::{
-    BinaryQuantization, BinaryQuantizationConfig, CompressionRatio, Distance, ProductQuantization,
-    ProductQuantizationConfig, QuantizationConfig, ScalarQuantization, ScalarQuantizationConfig,
+    BinaryQuantization, BinaryQuantizationConfig, CompressionRatio, Distance, MultiVectorConfig,
+    ProductQuantization, ProductQuantizationConfig, QuantizationConfig, ScalarQuantization,
+    ScalarQuantizationConfig,
 };
 use crate::vector_storage::chunked_vectors::ChunkedVectors;
 use crate::vector_storage::quantized::quantized_mmap_storage::{
     QuantizedMmapStorage, QuantizedMmapStorageBuilder,
 };
-use crate::vector_storage::{DenseVectorStorage, RawScorer, VectorStorage, VectorStorageEnum};
+use crate::vector_storage::{
+    DenseVectorStorage, MultiVectorStorage, RawScorer, VectorStorage, VectorStorageEnum,
+};
 
 pub const QUANTIZED_CONFIG_PATH: &str = "quantized.config.json";
 pub const QUANTIZED_DATA_PATH: &str = "quantized.data";
 pub const QUANTIZED_META_PATH: &str = "quantized.meta.json";
+pub const QUANTIZED_OFFSETS_PATH: &str = "quantized.offsets.json";
 
 #[derive(Deserialize, Serialize, Clone)]
 pub struct QuantizedVectorsConfig {
     pub quantization_config: QuantizationConfig,
     pub vector_parameters: quantization::VectorParameters,
+    /// Set if the quantized vectors are inner vectors of multivectors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multivector_config: Option<MultiVectorConfig>,
+}
+
+/// Range of quantized inner vectors which belong to a single multivector point
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default)]
+pub struct MultivectorOffset {
+    pub start: PointOffsetType,
+    pub count: PointOffsetType,
+}
+
+impl MultivectorOffset {
+    pub fn inner_ids(&self) -> std::ops::Range<PointOffsetType> {
+        self.start..self.start + self.count
+    }
 }
 
 pub enum QuantizedVectorStorage {
@@ -44,6 +66,8 @@ pub enum QuantizedVectorStorage {
 pub struct QuantizedVectors {
     storage_impl: QuantizedVectorStorage,
     config: QuantizedVectorsConfig,
+    /// Inner vector ranges of each point, only for multivectors
+    multivector_offsets: Vec<MultivectorOffset>,
     path: PathBuf,
     distance: Distance,
 }
@@ -65,6 +89,8 @@ impl QuantizedVectors {
     ) -> OperationResult<Box<dyn RawScorer + 'a>> {
         QuantizedScorerBuilder::new(
             &self.storage_impl,
+            self.config.multivector_config.as_ref(),
+            &self.multivector_offsets,
             query,
             point_deleted,
             vec_deleted,
@@ -85,18 +111,29 @@ impl QuantizedVectors {
             QuantizedVectorStorage::BinaryRam(storage) => storage.save(&data_path, &meta_path)?,
             QuantizedVectorStorage::BinaryMmap(storage) => storage.save(&data_path, &meta_path)?,
         };
+        if self.config.multivector_config.is_some() {
+            atomic_save_json(
+                &path.join(QUANTIZED_OFFSETS_PATH),
+                &self.multivector_offsets,
+            )?;
+        }
         Ok(())
     }
 
     pub fn files(&self) -> Vec<PathBuf> {
-        vec![
+        let mut files = vec![
             // Config files
             self.path.join(QUANTIZED_CONFIG_PATH),
             // Storage file
             self.path.join(QUANTIZED_DATA_PATH),
             // Meta file
             self.path.join(QUANTIZED_META_PATH),
-        ]
+        ];
+        if self.config.multivector_config.is_some() {
+            // Multivector offsets file
+            files.push(self.path.join(QUANTIZED_OFFSETS_PATH));
+        }
+        files
     }
 
     pub fn create(
@@ -110,13 +147,50 @@ impl QuantizedVectors {
             VectorStorageEnum::DenseSimple(v) => {
                 Self::create_impl(v, quantization_config, path, max_threads, stopped)
             }
+            VectorStorageEnum::DenseSimpleHalf(v) => {
+                Self::create_converted_impl(v, quantization_config, path, max_threads, stopped)
+            }
+            VectorStorageEnum::DenseSimpleByte(v) => {
+                Self::create_converted_impl(v, quantization_config, path, max_threads, stopped)
+            }
             VectorStorageEnum::Memmap(v) => {
                 Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
+            VectorStorageEnum::MemmapHalf(v) => Self::create_converted_impl(
+                v.as_ref(),
+                quantization_config,
+                path,
+                max_threads,
+                stopped,
+            ),
+            VectorStorageEnum::MemmapByte(v) => Self::create_converted_impl(
+                v.as_ref(),
+                quantization_config,
+                path,
+                max_threads,
+                stopped,
+            ),
             VectorStorageEnum::AppendableMemmap(v) => {
                 Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
+            VectorStorageEnum::AppendableMemmapHalf(v) => Self::create_converted_impl(
+                v.as_ref(),
+                quantization_config,
+                path,
+                max_threads,
+                stopped,
+            ),
+            VectorStorageEnum::AppendableMemmapByte(v) => Self::create_converted_impl(
+                v.as_ref(),
+                quantization_config,
+                path,
+                max_threads,
+                stopped,
+            ),
             VectorStorageEnum::SparseSimple(_) => Err(OperationError::WrongSparse),
+            VectorStorageEnum::MultiDenseSimple(v) => {
+                Self::create_multi_impl(v, quantization_config, path, max_threads, stopped)
+            }
         }
     }
 
@@ -127,13 +201,147 @@ impl QuantizedVectors {
         max_threads: usize,
         stopped: &AtomicBool,
     ) -> OperationResult<Self> {
+        if vector_storage.has_norms() {
+            return Self::create_normalized_impl(
+                vector_storage,
+                quantization_config,
+                path,
+                max_threads,
+                stopped,
+            );
+        }
         let count = vector_storage.total_vector_count();
         let vectors = (0..count as PointOffsetType).map(|i| vector_storage.get_dense(i));
+        Self::create_from_vectors(
+            vectors,
+            count,
+            vector_storage,
+            quantization_config,
+            None,
+            vec![],
+            path,
+            max_threads,
+            stopped,
+        )
+    }
+
+    /// Quantized vectors are scored against normalized queries, so original vectors are normalized first
+    fn create_normalized_impl<TVectorStorage: DenseVectorStorage + Send + Sync>(
+        vector_storage: &TVectorStorage,
+        quantization_config: &QuantizationConfig,
+        path: &Path,
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Self> {
+        let count = vector_storage.total_vector_count();
+        let mut normalized = ChunkedVectors::<VectorElementType>::new(vector_storage.vector_dim());
+        for i in 0..count as PointOffsetType {
+            check_process_stopped(stopped)?;
+            let norm = vector_storage.get_norm(i);
+            let vector: Vec<_> = vector_storage
+                .get_dense(i)
+                .iter()
+                .map(|x| x / norm)
+                .collect();
+            normalized.push(&vector)?;
+        }
+        let vectors = (0..count as PointOffsetType).map(|i| normalized.get(i));
+        Self::create_from_vectors(
+            vectors,
+            count,
+            vector_storage,
+            quantization_config,
+            None,
+            vec![],
+            path,
+            max_threads,
+            stopped,
+        )
+    }
+
+    /// Quantization works with full precision vectors, so vectors of other element types are converted first
+    fn create_converted_impl<TElement, TVectorStorage>(
+        vector_storage: &TVectorStorage,
+        quantization_config: &QuantizationConfig,
+        path: &Path,
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Self>
+    where
+        TElement: PrimitiveVectorElement,
+        TVectorStorage: DenseVectorStorage<TElement> + Send + Sync,
+    {
+        let count = vector_storage.total_vector_count();
+        let mut converted = ChunkedVectors::<VectorElementType>::new(vector_storage.vector_dim());
+        for i in 0..count as PointOffsetType {
+            check_process_stopped(stopped)?;
+            let vector = TElement::slice_to_float_cow(Cow::Borrowed(vector_storage.get_dense(i)));
+            converted.push(&vector)?;
+        }
+        let vectors = (0..count as PointOffsetType).map(|i| converted.get(i));
+        Self::create_from_vectors(
+            vectors,
+            count,
+            vector_storage,
+            quantization_config,
+            None,
+            vec![],
+            path,
+            max_threads,
+            stopped,
+        )
+    }
+
+    /// Quantize inner vectors of all multivectors as a flat list and remember their ranges
+    fn create_multi_impl<TVectorStorage: MultiVectorStorage + Send + Sync>(
+        vector_storage: &TVectorStorage,
+        quantization_config: &QuantizationConfig,
+        path: &Path,
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Self> {
+        let points_count = vector_storage.total_vector_count() as PointOffsetType;
+        let mut multivector_offsets = Vec::with_capacity(points_count as usize);
+        let mut inner_count: PointOffsetType = 0;
+        for i in 0..points_count {
+            let count = vector_storage.get_multi(i).vectors_count() as PointOffsetType;
+            multivector_offsets.push(MultivectorOffset {
+                start: inner_count,
+                count,
+            });
+            inner_count += count;
+        }
+        let vectors = (0..points_count).flat_map(|i| vector_storage.get_multi(i).multi_vectors());
+        Self::create_from_vectors(
+            vectors,
+            inner_count as usize,
+            vector_storage,
+            quantization_config,
+            Some(*vector_storage.multivector_config()),
+            multivector_offsets,
+            path,
+            max_threads,
+            stopped,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_from_vectors<'a>(
+        vectors: impl Iterator<Item = &'a [VectorElementType]> + Clone + Send,
+        count: usize,
+        vector_storage: &impl VectorStorage,
+        quantization_config: &QuantizationConfig,
+        multivector_config: Option<MultiVectorConfig>,
+        multivector_offsets: Vec<MultivectorOffset>,
+        path: &Path,
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Self> {
         let on_disk_vector_storage = vector_storage.is_on_disk();
         let distance = vector_storage.distance();
         let dim = vector_storage.vector_dim();
 
-        let vector_parameters = Self::construct_vector_parameters(distance, dim, count);
+        let vector_parameters = Self::construct_vector_parameters(distance, dim, count)?;
 
         let quantized_storage = match quantization_config {
             QuantizationConfig::Scalar(ScalarQuantization {
@@ -172,11 +380,13 @@ impl QuantizedVectors {
         let quantized_vectors_config = QuantizedVectorsConfig {
             quantization_config: quantization_config.clone(),
             vector_parameters,
+            multivector_config,
         };
 
         let quantized_vectors = QuantizedVectors {
             storage_impl: quantized_storage,
             config: quantized_vectors_config,
+            multivector_offsets,
             path: path.to_path_buf(),
             distance,
         };
@@ -252,9 +462,16 @@ impl QuantizedVectors {
             }
         };
 
+        let multivector_offsets = if config.multivector_config.is_some() {
+            read_json(&path.join(QUANTIZED_OFFSETS_PATH))?
+        } else {
+            vec![]
+        };
+
         Ok(QuantizedVectors {
             storage_impl: quantized_store,
             config,
+            multivector_offsets,
             path: path.to_path_buf(),
             distance,
         })
@@ -389,8 +606,8 @@ impl QuantizedVectors {
         distance: Distance,
         dim: usize,
         count: usize,
-    ) -> quantization::VectorParameters {
-        quantization::VectorParameters {
+    ) -> OperationResult<quantization::VectorParameters> {
+        Ok(quantization::VectorParameters {
             dim,
             count,
             distance_type: match distance {
@@ -398,9 +615,15 @@ impl QuantizedVectors {
                 Distance::Euclid => quantization::DistanceType::L2,
                 Distance::Dot => quantization::DistanceType::Dot,
                 Distance::Manhattan => quantization::DistanceType::L1,
+                // Binary vectors are already as compact as quantized ones
+                Distance::Hamming => {
+                    return Err(OperationError::service_error(
+                        "Quantization is not supported with Hamming distance",
+                    ))
+                }
             },
             invert: distance == Distance::Euclid || distance == Distance::Manhattan,
-        }
+        })
     }
 
     fn get_bucket_size(compression: CompressionRatio) -> usize {
@@ -5,6 +5,7 @@ use crate::data_types::vectors::DenseVector;
 
 pub mod context_query;
 pub mod discovery_query;
+pub mod nearest_query;
 pub mod reco_query;
 
 pub trait TransformInto<Output, T = DenseVector, U = DenseVector> {
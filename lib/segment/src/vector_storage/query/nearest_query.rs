@@ -0,0 +1,15 @@
+use common::types::ScoreType;
+
+use super::Query;
+
+/// Plain nearest neighbour search, scores vectors by their similarity to a single example
+///
+/// Lets scorers, which are generic over [`Query`], also serve regular searches.
+#[derive(Debug, Clone)]
+pub struct NearestQuery<T>(pub T);
+
+impl<T> Query<T> for NearestQuery<T> {
+    fn score_by(&self, similarity: impl Fn(&T) -> ScoreType) -> ScoreType {
+        similarity(&self.0)
+    }
+}
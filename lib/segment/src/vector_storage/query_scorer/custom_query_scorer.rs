@@ -1,57 +1,95 @@
+use std::borrow::Cow;
 use std::marker::PhantomData;
 
 use common::types::{PointOffsetType, ScoreType};
 
-use crate::data_types::vectors::{DenseVector, VectorElementType};
-use crate::spaces::metric::Metric;
+use crate::data_types::primitive::PrimitiveVectorElement;
+use crate::data_types::vectors::DenseVector;
+use crate::spaces::metric::ElementSimilarity;
+use crate::spaces::simple::cosine_norm;
 use crate::vector_storage::query::{Query, TransformInto};
 use crate::vector_storage::query_scorer::QueryScorer;
 use crate::vector_storage::DenseVectorStorage;
 
 pub struct CustomQueryScorer<
     'a,
-    TMetric: Metric,
-    TVectorStorage: DenseVectorStorage,
-    TQuery: Query<DenseVector>,
+    TElement: PrimitiveVectorElement,
+    TMetric: ElementSimilarity<TElement>,
+    TVectorStorage: DenseVectorStorage<TElement>,
+    TQuery: Query<Vec<TElement>>,
 > {
     vector_storage: &'a TVectorStorage,
     query: TQuery,
+    /// Stored vectors are not normalized, similarities are divided by their norms
+    has_norms: bool,
     metric: PhantomData<TMetric>,
+    element: PhantomData<TElement>,
 }
 
 impl<
         'a,
-        TMetric: Metric,
-        TVectorStorage: DenseVectorStorage,
-        TQuery: Query<DenseVector> + TransformInto<TQuery>,
-    > CustomQueryScorer<'a, TMetric, TVectorStorage, TQuery>
+        TElement: PrimitiveVectorElement,
+        TMetric: ElementSimilarity<TElement>,
+        TVectorStorage: DenseVectorStorage<TElement>,
+        TQuery: Query<Vec<TElement>>,
+    > CustomQueryScorer<'a, TElement, TMetric, TVectorStorage, TQuery>
 {
-    pub fn new(query: TQuery, vector_storage: &'a TVectorStorage) -> Self {
+    /// Similarities of un-normalized stored vectors are divided by their `norm`, before being combined by the query
+    #[inline]
+    fn score_with_norm(&self, against: &[TElement], norm: ScoreType) -> ScoreType {
+        self.query
+            .score_by(|example| TMetric::element_similarity(example, against) / norm)
+    }
+
+    /// Creates scorer from a full precision query, which is preprocessed and converted into the storage element type
+    pub fn new<TInputQuery>(query: TInputQuery, vector_storage: &'a TVectorStorage) -> Self
+    where
+        TInputQuery: Query<DenseVector> + TransformInto<TQuery, DenseVector, Vec<TElement>>,
+    {
         let query = query
-            .transform(|vector| Ok(TMetric::preprocess(vector)))
+            .transform(|vector| {
+                let preprocessed = TMetric::preprocess(vector);
+                Ok(TElement::slice_from_float_cow(Cow::Owned(preprocessed)).into_owned())
+            })
             .unwrap();
 
         Self {
             query,
             vector_storage,
+            has_norms: vector_storage.has_norms(),
             metric: PhantomData,
+            element: PhantomData,
         }
     }
 }
 
-impl<'a, TMetric: Metric, TVectorStorage: DenseVectorStorage, TQuery: Query<DenseVector>>
-    QueryScorer<[VectorElementType]> for CustomQueryScorer<'a, TMetric, TVectorStorage, TQuery>
+impl<
+        'a,
+        TElement: PrimitiveVectorElement,
+        TMetric: ElementSimilarity<TElement>,
+        TVectorStorage: DenseVectorStorage<TElement>,
+        TQuery: Query<Vec<TElement>>,
+    > QueryScorer<[TElement]> for CustomQueryScorer<'a, TElement, TMetric, TVectorStorage, TQuery>
 {
     #[inline]
     fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
         let stored = self.vector_storage.get_dense(idx);
-        self.score(stored)
+        let norm = if self.has_norms {
+            self.vector_storage.get_norm(idx)
+        } else {
+            1.0
+        };
+        self.score_with_norm(stored, norm)
     }
 
     #[inline]
-    fn score(&self, against: &[VectorElementType]) -> ScoreType {
-        self.query
-            .score_by(|example| TMetric::similarity(example, against))
+    fn score(&self, against: &[TElement]) -> ScoreType {
+        let norm = if self.has_norms {
+            cosine_norm(&TElement::slice_to_float_cow(Cow::Borrowed(against)))
+        } else {
+            1.0
+        };
+        self.score_with_norm(against, norm)
     }
 
     fn score_internal(&self, _point_a: PointOffsetType, _point_b: PointOffsetType) -> ScoreType {
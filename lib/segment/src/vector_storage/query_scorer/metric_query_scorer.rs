@@ -1,46 +1,105 @@
+use std::borrow::Cow;
 use std::marker::PhantomData;
 
-use common::types::{PointOffsetType, ScoreType};
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
 
-use crate::data_types::vectors::{DenseVector, VectorElementType};
-use crate::spaces::metric::Metric;
+use crate::data_types::primitive::PrimitiveVectorElement;
+use crate::data_types::vectors::DenseVector;
+use crate::spaces::metric::ElementSimilarity;
+use crate::spaces::simple::cosine_norm;
+use crate::spaces::tools::prefetch_vector;
 use crate::vector_storage::query_scorer::QueryScorer;
 use crate::vector_storage::DenseVectorStorage;
 
-pub struct MetricQueryScorer<'a, TMetric: Metric, TVectorStorage: DenseVectorStorage> {
+pub struct MetricQueryScorer<
+    'a,
+    TElement: PrimitiveVectorElement,
+    TMetric: ElementSimilarity<TElement>,
+    TVectorStorage: DenseVectorStorage<TElement>,
+> {
     vector_storage: &'a TVectorStorage,
-    query: Vec<VectorElementType>,
+    query: Vec<TElement>,
+    /// Stored vectors are not normalized, scores are divided by their norms
+    has_norms: bool,
     metric: PhantomData<TMetric>,
 }
 
-impl<'a, TMetric: Metric, TVectorStorage: DenseVectorStorage>
-    MetricQueryScorer<'a, TMetric, TVectorStorage>
+impl<
+        'a,
+        TElement: PrimitiveVectorElement,
+        TMetric: ElementSimilarity<TElement>,
+        TVectorStorage: DenseVectorStorage<TElement>,
+    > MetricQueryScorer<'a, TElement, TMetric, TVectorStorage>
 {
     pub fn new(query: DenseVector, vector_storage: &'a TVectorStorage) -> Self {
+        let query = TMetric::preprocess(query);
         Self {
-            query: TMetric::preprocess(query),
+            query: TElement::slice_from_float_cow(Cow::Owned(query)).into_owned(),
             vector_storage,
+            has_norms: vector_storage.has_norms(),
             metric: PhantomData,
         }
     }
+
+    #[inline]
+    fn normalize_stored(&self, score: ScoreType, idx: PointOffsetType) -> ScoreType {
+        if self.has_norms {
+            score / self.vector_storage.get_norm(idx)
+        } else {
+            score
+        }
+    }
 }
 
-impl<'a, TMetric: Metric, TVectorStorage: DenseVectorStorage> QueryScorer<[VectorElementType]>
-    for MetricQueryScorer<'a, TMetric, TVectorStorage>
+impl<
+        'a,
+        TElement: PrimitiveVectorElement,
+        TMetric: ElementSimilarity<TElement>,
+        TVectorStorage: DenseVectorStorage<TElement>,
+    > QueryScorer<[TElement]> for MetricQueryScorer<'a, TElement, TMetric, TVectorStorage>
 {
     #[inline]
     fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
-        TMetric::similarity(&self.query, self.vector_storage.get_dense(idx))
+        let score = TMetric::element_similarity(&self.query, self.vector_storage.get_dense(idx));
+        self.normalize_stored(score, idx)
     }
 
     #[inline]
-    fn score(&self, v2: &[VectorElementType]) -> ScoreType {
-        TMetric::similarity(&self.query, v2)
+    fn score(&self, v2: &[TElement]) -> ScoreType {
+        let score = TMetric::element_similarity(&self.query, v2);
+        if self.has_norms {
+            score / cosine_norm(&TElement::slice_to_float_cow(Cow::Borrowed(v2)))
+        } else {
+            score
+        }
     }
 
     fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
         let v1 = self.vector_storage.get_dense(point_a);
         let v2 = self.vector_storage.get_dense(point_b);
-        TMetric::similarity(v1, v2)
+        let score = TMetric::element_similarity(v1, v2);
+        if self.has_norms {
+            score / (self.vector_storage.get_norm(point_a) * self.vector_storage.get_norm(point_b))
+        } else {
+            score
+        }
+    }
+
+    fn score_stored_batch(&self, scores: &mut [ScoredPointOffset]) {
+        let Some(first) = scores.first() else {
+            return;
+        };
+        let mut next_vector = self.vector_storage.get_dense(first.idx);
+        prefetch_vector(next_vector);
+        for i in 0..scores.len() {
+            let vector = next_vector;
+            // Request the next vector while the current one is being scored
+            if let Some(next) = scores.get(i + 1) {
+                next_vector = self.vector_storage.get_dense(next.idx);
+                prefetch_vector(next_vector);
+            }
+            let score = TMetric::element_similarity(&self.query, vector);
+            scores[i].score = self.normalize_stored(score, scores[i].idx);
+        }
     }
 }
@@ -1,8 +1,11 @@
-use common::types::{PointOffsetType, ScoreType};
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
 
 pub mod custom_query_scorer;
 pub mod metric_query_scorer;
+pub mod multi_custom_query_scorer;
+pub mod multi_metric_query_scorer;
 pub mod sparse_custom_query_scorer;
+pub mod weighted_query_scorer;
 
 pub trait QueryScorer<TVector: ?Sized> {
     fn score_stored(&self, idx: PointOffsetType) -> ScoreType;
@@ -10,4 +13,13 @@ pub trait QueryScorer<TVector: ?Sized> {
     fn score(&self, v2: &TVector) -> ScoreType;
 
     fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType;
+
+    /// Score stored vectors of all `scores[..].idx` at once, writing results into `scores[..].score`
+    ///
+    /// Implementations may override it to overlap memory loads of the next vectors with scoring.
+    fn score_stored_batch(&self, scores: &mut [ScoredPointOffset]) {
+        for scored in scores.iter_mut() {
+            scored.score = self.score_stored(scored.idx);
+        }
+    }
 }
@@ -0,0 +1,69 @@
+use std::marker::PhantomData;
+
+use common::types::{PointOffsetType, ScoreType};
+
+use crate::data_types::vectors::MultiDenseVector;
+use crate::spaces::metric::Metric;
+use crate::vector_storage::query::{Query, TransformInto};
+use crate::vector_storage::query_scorer::multi_metric_query_scorer::score_multi;
+use crate::vector_storage::query_scorer::QueryScorer;
+use crate::vector_storage::MultiVectorStorage;
+
+pub struct MultiCustomQueryScorer<
+    'a,
+    TMetric: Metric,
+    TVectorStorage: MultiVectorStorage,
+    TQuery: Query<MultiDenseVector>,
+> {
+    vector_storage: &'a TVectorStorage,
+    query: TQuery,
+    metric: PhantomData<TMetric>,
+}
+
+impl<
+        'a,
+        TMetric: Metric,
+        TVectorStorage: MultiVectorStorage,
+        TQuery: Query<MultiDenseVector> + TransformInto<TQuery, MultiDenseVector, MultiDenseVector>,
+    > MultiCustomQueryScorer<'a, TMetric, TVectorStorage, TQuery>
+{
+    pub fn new(query: TQuery, vector_storage: &'a TVectorStorage) -> Self {
+        let query = query
+            .transform(|vector| {
+                let dim = vector.dim;
+                let preprocessed = vector
+                    .multi_vectors()
+                    .flat_map(|vector| TMetric::preprocess(vector.to_vec()))
+                    .collect();
+                Ok(MultiDenseVector::new(preprocessed, dim))
+            })
+            .unwrap();
+
+        Self {
+            query,
+            vector_storage,
+            metric: PhantomData,
+        }
+    }
+}
+
+impl<'a, TMetric: Metric, TVectorStorage: MultiVectorStorage, TQuery: Query<MultiDenseVector>>
+    QueryScorer<MultiDenseVector> for MultiCustomQueryScorer<'a, TMetric, TVectorStorage, TQuery>
+{
+    #[inline]
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        let stored = self.vector_storage.get_multi(idx);
+        self.score(stored)
+    }
+
+    #[inline]
+    fn score(&self, against: &MultiDenseVector) -> ScoreType {
+        let multivector_config = self.vector_storage.multivector_config();
+        self.query
+            .score_by(|example| score_multi::<TMetric>(multivector_config, example, against))
+    }
+
+    fn score_internal(&self, _point_a: PointOffsetType, _point_b: PointOffsetType) -> ScoreType {
+        unimplemented!("Custom scorer can compare against multiple vectors, not just one")
+    }
+}
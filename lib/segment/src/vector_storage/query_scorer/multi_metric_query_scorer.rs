@@ -0,0 +1,87 @@
+use std::marker::PhantomData;
+
+use common::types::{PointOffsetType, ScoreType};
+
+use crate::data_types::vectors::MultiDenseVector;
+use crate::spaces::metric::Metric;
+use crate::types::{MultiVectorComparator, MultiVectorConfig};
+use crate::vector_storage::query_scorer::QueryScorer;
+use crate::vector_storage::MultiVectorStorage;
+
+pub struct MultiMetricQueryScorer<'a, TMetric: Metric, TVectorStorage: MultiVectorStorage> {
+    vector_storage: &'a TVectorStorage,
+    query: MultiDenseVector,
+    metric: PhantomData<TMetric>,
+}
+
+/// Compare two multivectors with the comparator from `multivector_config`
+pub fn score_multi<TMetric: Metric>(
+    multivector_config: &MultiVectorConfig,
+    multi_dense_a: &MultiDenseVector,
+    multi_dense_b: &MultiDenseVector,
+) -> ScoreType {
+    match multivector_config.comparator {
+        MultiVectorComparator::MaxSim => {
+            score_max_similarity::<TMetric>(multi_dense_a, multi_dense_b)
+        }
+    }
+}
+
+/// Sum over vectors of `multi_dense_a` of the best similarity with any vector of `multi_dense_b`
+fn score_max_similarity<TMetric: Metric>(
+    multi_dense_a: &MultiDenseVector,
+    multi_dense_b: &MultiDenseVector,
+) -> ScoreType {
+    let mut sum = 0.0;
+    for dense_a in multi_dense_a.multi_vectors() {
+        let mut max_sim = ScoreType::NEG_INFINITY;
+        for dense_b in multi_dense_b.multi_vectors() {
+            let sim = TMetric::similarity(dense_a, dense_b);
+            if sim > max_sim {
+                max_sim = sim;
+            }
+        }
+        // An empty stored multivector contributes nothing
+        if max_sim.is_finite() {
+            sum += max_sim;
+        }
+    }
+    sum
+}
+
+impl<'a, TMetric: Metric, TVectorStorage: MultiVectorStorage>
+    MultiMetricQueryScorer<'a, TMetric, TVectorStorage>
+{
+    pub fn new(query: MultiDenseVector, vector_storage: &'a TVectorStorage) -> Self {
+        let dim = query.dim;
+        let preprocessed = query
+            .multi_vectors()
+            .flat_map(|vector| TMetric::preprocess(vector.to_vec()))
+            .collect();
+        Self {
+            query: MultiDenseVector::new(preprocessed, dim),
+            vector_storage,
+            metric: PhantomData,
+        }
+    }
+}
+
+impl<'a, TMetric: Metric, TVectorStorage: MultiVectorStorage> QueryScorer<MultiDenseVector>
+    for MultiMetricQueryScorer<'a, TMetric, TVectorStorage>
+{
+    #[inline]
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        self.score(self.vector_storage.get_multi(idx))
+    }
+
+    #[inline]
+    fn score(&self, v2: &MultiDenseVector) -> ScoreType {
+        score_multi::<TMetric>(self.vector_storage.multivector_config(), &self.query, v2)
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        let v1 = self.vector_storage.get_multi(point_a);
+        let v2 = self.vector_storage.get_multi(point_b);
+        score_multi::<TMetric>(self.vector_storage.multivector_config(), v1, v2)
+    }
+}
@@ -0,0 +1,69 @@
+use common::types::{PointOffsetType, ScoreType};
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::data_types::vectors::{DenseVector, VectorElementType};
+use crate::spaces::simple::{weighted_dot_similarity, weighted_euclid_similarity};
+use crate::types::Distance;
+use crate::vector_storage::query::Query;
+use crate::vector_storage::query_scorer::QueryScorer;
+use crate::vector_storage::DenseVectorStorage;
+
+type WeightedSimilarity =
+    fn(&[VectorElementType], &[VectorElementType], &[VectorElementType]) -> ScoreType;
+
+/// Scores `float32` vectors, multiplying each term of the distance by the weight of its dimension
+///
+/// Only `Euclid` and `Dot` distances can be weighted per dimension.
+pub struct WeightedQueryScorer<'a, TVectorStorage: DenseVectorStorage, TQuery: Query<DenseVector>> {
+    vector_storage: &'a TVectorStorage,
+    query: TQuery,
+    weights: &'a [VectorElementType],
+    similarity: WeightedSimilarity,
+}
+
+impl<'a, TVectorStorage: DenseVectorStorage, TQuery: Query<DenseVector>>
+    WeightedQueryScorer<'a, TVectorStorage, TQuery>
+{
+    pub fn new(
+        query: TQuery,
+        weights: &'a [VectorElementType],
+        vector_storage: &'a TVectorStorage,
+    ) -> OperationResult<Self> {
+        let similarity: WeightedSimilarity = match vector_storage.distance() {
+            Distance::Euclid => weighted_euclid_similarity,
+            Distance::Dot => weighted_dot_similarity,
+            distance => {
+                return Err(OperationError::service_error(format!(
+                    "dimension weights are not supported for {distance:?} distance"
+                )))
+            }
+        };
+        Ok(Self {
+            vector_storage,
+            query,
+            weights,
+            similarity,
+        })
+    }
+}
+
+impl<'a, TVectorStorage: DenseVectorStorage, TQuery: Query<DenseVector>>
+    QueryScorer<[VectorElementType]> for WeightedQueryScorer<'a, TVectorStorage, TQuery>
+{
+    #[inline]
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        self.score(self.vector_storage.get_dense(idx))
+    }
+
+    #[inline]
+    fn score(&self, against: &[VectorElementType]) -> ScoreType {
+        self.query
+            .score_by(|example| (self.similarity)(self.weights, example, against))
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        let v1 = self.vector_storage.get_dense(point_a);
+        let v2 = self.vector_storage.get_dense(point_b);
+        (self.similarity)(self.weights, v1, v2)
+    }
+}
@@ -6,20 +6,30 @@ use sparse::common::sparse_vector::SparseVector;
 
 use super::query::context_query::ContextQuery;
 use super::query::discovery_query::DiscoveryQuery;
+use super::query::nearest_query::NearestQuery;
 use super::query::reco_query::RecoQuery;
 use super::query::TransformInto;
 use super::query_scorer::custom_query_scorer::CustomQueryScorer;
+use super::query_scorer::multi_custom_query_scorer::MultiCustomQueryScorer;
+use super::query_scorer::multi_metric_query_scorer::MultiMetricQueryScorer;
 use super::query_scorer::sparse_custom_query_scorer::SparseCustomQueryScorer;
-use super::{DenseVectorStorage, SparseVectorStorage, VectorStorageEnum};
+use super::query_scorer::weighted_query_scorer::WeightedQueryScorer;
+use super::{DenseVectorStorage, MultiVectorStorage, SparseVectorStorage, VectorStorageEnum};
 use crate::common::operation_error::{OperationError, OperationResult};
-use crate::data_types::vectors::{DenseVector, QueryVector};
-use crate::spaces::metric::Metric;
-use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+use crate::data_types::primitive::PrimitiveVectorElement;
+use crate::data_types::vectors::{DenseVector, MultiDenseVector, QueryVector, VectorElementType};
+use crate::spaces::metric::{ElementSimilarity, Metric};
+use crate::spaces::simple::{
+    CosineMetric, DotProductMetric, EuclidMetric, HammingMetric, ManhattanMetric,
+};
 use crate::spaces::tools::peek_top_largest_iterable;
 use crate::types::Distance;
 use crate::vector_storage::query_scorer::metric_query_scorer::MetricQueryScorer;
 use crate::vector_storage::query_scorer::QueryScorer;
 
+/// Number of points read from the vector storage at once by [`RawScorer::rescore_points`]
+const RESCORE_BATCH_SIZE: usize = 64;
+
 /// RawScorer composition:
 ///                                              Metric
 ///                                             ┌───────────────────┐
@@ -80,6 +90,33 @@ pub trait RawScorer {
     ) -> Vec<ScoredPointOffset>;
 
     fn peek_top_all(&self, top: usize) -> Vec<ScoredPointOffset>;
+
+    /// Score candidates found with quantized vectors again, with the vectors of this scorer
+    ///
+    /// Candidates are scored in storage order, in batches of [`RESCORE_BATCH_SIZE`], so that
+    /// on-disk storages are read sequentially instead of in rank order. Returned points keep the
+    /// order of `points`. Points are omitted if scoring was stopped.
+    fn rescore_points(&self, points: &[PointOffsetType]) -> Vec<ScoredPointOffset> {
+        let mut storage_order = points.to_vec();
+        storage_order.sort_unstable();
+
+        let mut scored = Vec::with_capacity(points.len());
+        for batch in storage_order.chunks(RESCORE_BATCH_SIZE) {
+            scored.extend(self.score_points_unfiltered(&mut batch.iter().copied()));
+        }
+
+        // Async storages may return scores in any order, restore the order of `points`
+        scored.sort_unstable_by_key(|scored| scored.idx);
+        points
+            .iter()
+            .filter_map(|&point_id| {
+                scored
+                    .binary_search_by_key(&point_id, |scored| scored.idx)
+                    .ok()
+                    .map(|pos| scored[pos])
+            })
+            .collect()
+    }
 }
 
 pub struct RawScorerImpl<'a, TVector: ?Sized, TQueryScorer>
@@ -108,14 +145,38 @@ pub fn new_stoppable_raw_scorer<'a>(
     is_stopped: &'a AtomicBool,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
     match vector_storage {
-        VectorStorageEnum::DenseSimple(vs) => raw_scorer_impl(query, vs, point_deleted, is_stopped),
+        VectorStorageEnum::DenseSimple(vs) => {
+            if let Some(weights) = vs.dimension_weights() {
+                return raw_weighted_scorer_impl(query, weights, vs, point_deleted, is_stopped);
+            }
+            raw_scorer_impl(query, vs, point_deleted, is_stopped)
+        }
+        VectorStorageEnum::DenseSimpleHalf(vs) => {
+            raw_scorer_impl(query, vs, point_deleted, is_stopped)
+        }
+        VectorStorageEnum::DenseSimpleByte(vs) => {
+            raw_scorer_impl(query, vs, point_deleted, is_stopped)
+        }
 
         VectorStorageEnum::Memmap(vs) => {
+            if let Some(weights) = vs.dimension_weights() {
+                return raw_weighted_scorer_impl(
+                    query,
+                    weights,
+                    vs.as_ref(),
+                    point_deleted,
+                    is_stopped,
+                );
+            }
             if vs.has_async_reader() {
                 #[cfg(target_os = "linux")]
                 {
-                    let scorer_result =
-                        super::async_raw_scorer::new(query.clone(), vs, point_deleted, is_stopped);
+                    let scorer_result = super::async_raw_scorer::new(
+                        query.clone(),
+                        vs.as_ref(),
+                        point_deleted,
+                        is_stopped,
+                    );
                     match scorer_result {
                         Ok(raw_scorer) => return Ok(raw_scorer),
                         Err(err) => log::error!("failed to initialize async raw scorer: {err}"),
@@ -128,13 +189,56 @@ pub fn new_stoppable_raw_scorer<'a>(
 
             raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
+        VectorStorageEnum::MemmapHalf(vs) => {
+            raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
+        VectorStorageEnum::MemmapByte(vs) => {
+            raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
 
         VectorStorageEnum::AppendableMemmap(vs) => {
+            if let Some(weights) = vs.dimension_weights() {
+                return raw_weighted_scorer_impl(
+                    query,
+                    weights,
+                    vs.as_ref(),
+                    point_deleted,
+                    is_stopped,
+                );
+            }
+            if vs.has_async_reader() {
+                #[cfg(target_os = "linux")]
+                {
+                    let scorer_result = super::async_raw_scorer::new(
+                        query.clone(),
+                        vs.as_ref(),
+                        point_deleted,
+                        is_stopped,
+                    );
+                    match scorer_result {
+                        Ok(raw_scorer) => return Ok(raw_scorer),
+                        Err(err) => log::error!("failed to initialize async raw scorer: {err}"),
+                    };
+                }
+
+                #[cfg(not(target_os = "linux"))]
+                log::warn!("async raw scorer is only supported on Linux");
+            }
+
+            raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
+        VectorStorageEnum::AppendableMemmapHalf(vs) => {
+            raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
+        VectorStorageEnum::AppendableMemmapByte(vs) => {
             raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
         VectorStorageEnum::SparseSimple(vs) => {
             raw_sparse_scorer_impl(query, vs, point_deleted, is_stopped)
         }
+        VectorStorageEnum::MultiDenseSimple(vs) => {
+            raw_multi_scorer_impl(query, vs, point_deleted, is_stopped)
+        }
     }
 }
 
@@ -189,32 +293,47 @@ pub fn new_raw_scorer<'a>(
     new_stoppable_raw_scorer(vector, vector_storage, point_deleted, &DEFAULT_STOPPED)
 }
 
-pub fn raw_scorer_impl<'a, TVectorStorage: DenseVectorStorage>(
+pub fn raw_scorer_impl<'a, TElement, TVectorStorage>(
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
-) -> OperationResult<Box<dyn RawScorer + 'a>> {
+) -> OperationResult<Box<dyn RawScorer + 'a>>
+where
+    TElement: PrimitiveVectorElement,
+    TVectorStorage: DenseVectorStorage<TElement>,
+    CosineMetric: ElementSimilarity<TElement>,
+    EuclidMetric: ElementSimilarity<TElement>,
+    DotProductMetric: ElementSimilarity<TElement>,
+    ManhattanMetric: ElementSimilarity<TElement>,
+    HammingMetric: ElementSimilarity<TElement>,
+{
     match vector_storage.distance() {
-        Distance::Cosine => new_scorer_with_metric::<CosineMetric, _>(
+        Distance::Cosine => new_scorer_with_metric::<_, CosineMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Euclid => new_scorer_with_metric::<_, EuclidMetric, _>(
             query,
             vector_storage,
             point_deleted,
             is_stopped,
         ),
-        Distance::Euclid => new_scorer_with_metric::<EuclidMetric, _>(
+        Distance::Dot => new_scorer_with_metric::<_, DotProductMetric, _>(
             query,
             vector_storage,
             point_deleted,
             is_stopped,
         ),
-        Distance::Dot => new_scorer_with_metric::<DotProductMetric, _>(
+        Distance::Manhattan => new_scorer_with_metric::<_, ManhattanMetric, _>(
             query,
             vector_storage,
             point_deleted,
             is_stopped,
         ),
-        Distance::Manhattan => new_scorer_with_metric::<ManhattanMetric, _>(
+        Distance::Hamming => new_scorer_with_metric::<_, HammingMetric, _>(
             query,
             vector_storage,
             point_deleted,
@@ -223,7 +342,12 @@ pub fn raw_scorer_impl<'a, TVectorStorage: DenseVectorStorage>(
     }
 }
 
-fn new_scorer_with_metric<'a, TMetric: Metric + 'a, TVectorStorage: DenseVectorStorage>(
+fn new_scorer_with_metric<
+    'a,
+    TElement: PrimitiveVectorElement,
+    TMetric: ElementSimilarity<TElement> + 'a,
+    TVectorStorage: DenseVectorStorage<TElement>,
+>(
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
     point_deleted: &'a BitSlice,
@@ -232,7 +356,7 @@ fn new_scorer_with_metric<'a, TMetric: Metric + 'a, TVectorStorage: DenseVectorS
     let vec_deleted = vector_storage.deleted_vector_bitslice();
     match query {
         QueryVector::Nearest(vector) => raw_scorer_from_query_scorer(
-            MetricQueryScorer::<TMetric, _>::new(vector.try_into()?, vector_storage),
+            MetricQueryScorer::<_, TMetric, _>::new(vector.try_into()?, vector_storage),
             point_deleted,
             vec_deleted,
             is_stopped,
@@ -240,7 +364,7 @@ fn new_scorer_with_metric<'a, TMetric: Metric + 'a, TVectorStorage: DenseVectorS
         QueryVector::Recommend(reco_query) => {
             let reco_query: RecoQuery<DenseVector> = reco_query.transform_into()?;
             raw_scorer_from_query_scorer(
-                CustomQueryScorer::<TMetric, _, _>::new(reco_query, vector_storage),
+                CustomQueryScorer::<_, TMetric, _, _>::new(reco_query, vector_storage),
                 point_deleted,
                 vec_deleted,
                 is_stopped,
@@ -249,7 +373,7 @@ fn new_scorer_with_metric<'a, TMetric: Metric + 'a, TVectorStorage: DenseVectorS
         QueryVector::Discovery(discovery_query) => {
             let discovery_query: DiscoveryQuery<DenseVector> = discovery_query.transform_into()?;
             raw_scorer_from_query_scorer(
-                CustomQueryScorer::<TMetric, _, _>::new(discovery_query, vector_storage),
+                CustomQueryScorer::<_, TMetric, _, _>::new(discovery_query, vector_storage),
                 point_deleted,
                 vec_deleted,
                 is_stopped,
@@ -258,7 +382,141 @@ fn new_scorer_with_metric<'a, TMetric: Metric + 'a, TVectorStorage: DenseVectorS
         QueryVector::Context(context_query) => {
             let context_query: ContextQuery<DenseVector> = context_query.transform_into()?;
             raw_scorer_from_query_scorer(
-                CustomQueryScorer::<TMetric, _, _>::new(context_query, vector_storage),
+                CustomQueryScorer::<_, TMetric, _, _>::new(context_query, vector_storage),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+    }
+}
+
+/// Raw scorer for `float32` vectors, which applies per-dimension `weights` to the distance
+pub fn raw_weighted_scorer_impl<'a, TVectorStorage: DenseVectorStorage>(
+    query: QueryVector,
+    weights: &'a [VectorElementType],
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    let vec_deleted = vector_storage.deleted_vector_bitslice();
+    match query {
+        QueryVector::Nearest(vector) => {
+            let vector: DenseVector = vector.try_into()?;
+            raw_scorer_from_query_scorer(
+                WeightedQueryScorer::new(NearestQuery(vector), weights, vector_storage)?,
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Recommend(reco_query) => {
+            let reco_query: RecoQuery<DenseVector> = reco_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                WeightedQueryScorer::new(reco_query, weights, vector_storage)?,
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Discovery(discovery_query) => {
+            let discovery_query: DiscoveryQuery<DenseVector> = discovery_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                WeightedQueryScorer::new(discovery_query, weights, vector_storage)?,
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Context(context_query) => {
+            let context_query: ContextQuery<DenseVector> = context_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                WeightedQueryScorer::new(context_query, weights, vector_storage)?,
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+    }
+}
+
+pub fn raw_multi_scorer_impl<'a, TVectorStorage: MultiVectorStorage>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    match vector_storage.distance() {
+        Distance::Cosine => new_multi_scorer_with_metric::<CosineMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Euclid => new_multi_scorer_with_metric::<EuclidMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Dot => new_multi_scorer_with_metric::<DotProductMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Manhattan => new_multi_scorer_with_metric::<ManhattanMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Hamming => new_multi_scorer_with_metric::<HammingMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+    }
+}
+
+fn new_multi_scorer_with_metric<'a, TMetric: Metric + 'a, TVectorStorage: MultiVectorStorage>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    let vec_deleted = vector_storage.deleted_vector_bitslice();
+    match query {
+        QueryVector::Nearest(vector) => raw_scorer_from_query_scorer(
+            MultiMetricQueryScorer::<TMetric, _>::new(vector.try_into()?, vector_storage),
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+        QueryVector::Recommend(reco_query) => {
+            let reco_query: RecoQuery<MultiDenseVector> = reco_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                MultiCustomQueryScorer::<TMetric, _, _>::new(reco_query, vector_storage),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Discovery(discovery_query) => {
+            let discovery_query: DiscoveryQuery<MultiDenseVector> =
+                discovery_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                MultiCustomQueryScorer::<TMetric, _, _>::new(discovery_query, vector_storage),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Context(context_query) => {
+            let context_query: ContextQuery<MultiDenseVector> = context_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                MultiCustomQueryScorer::<TMetric, _, _>::new(context_query, vector_storage),
                 point_deleted,
                 vec_deleted,
                 is_stopped,
@@ -302,14 +560,16 @@ where
             }
             scores[size] = ScoredPointOffset {
                 idx: point_id,
-                score: self.query_scorer.score_stored(point_id),
+                score: 0.0,
             };
 
             size += 1;
             if size == scores.len() {
-                return size;
+                break;
             }
         }
+        // Score all accepted points at once, so that vector loads can overlap with scoring
+        self.query_scorer.score_stored_batch(&mut scores[..size]);
         size
     }
 
@@ -320,13 +580,13 @@ where
         if self.is_stopped.load(Ordering::Relaxed) {
             return vec![];
         }
-        let mut scores = vec![];
-        for point_id in points {
-            scores.push(ScoredPointOffset {
+        let mut scores: Vec<_> = points
+            .map(|point_id| ScoredPointOffset {
                 idx: point_id,
-                score: self.query_scorer.score_stored(point_id),
-            });
-        }
+                score: 0.0,
+            })
+            .collect();
+        self.query_scorer.score_stored_batch(&mut scores);
         scores
     }
 
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::mem::size_of;
 use std::ops::Range;
 use std::sync::atomic::AtomicBool;
@@ -5,7 +6,7 @@ use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
 use bitvec::prelude::{BitSlice, BitVec};
-use common::types::PointOffsetType;
+use common::types::{PointOffsetType, ScoreType};
 use log::debug;
 use parking_lot::RwLock;
 use rocksdb::DB;
@@ -18,27 +19,33 @@ use crate::common::operation_error::{check_process_stopped, OperationError, Oper
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::common::Flusher;
 use crate::data_types::named_vectors::CowVector;
+use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{VectorElementType, VectorRef};
+use crate::spaces::simple::cosine_norm;
 use crate::types::Distance;
 use crate::vector_storage::bitvec::bitvec_set_deleted;
 
 /// In-memory vector storage with on-update persistence using `store`
-pub struct SimpleDenseVectorStorage {
+pub struct SimpleDenseVectorStorage<T: PrimitiveVectorElement = VectorElementType> {
     dim: usize,
     distance: Distance,
-    vectors: ChunkedVectors<VectorElementType>,
+    vectors: ChunkedVectors<T>,
     db_wrapper: DatabaseColumnWrapper,
-    update_buffer: StoredRecord,
+    update_buffer: StoredRecord<T>,
     /// BitVec for deleted flags. Grows dynamically upto last set flag.
     deleted: BitVec,
     /// Current number of deleted vectors.
     deleted_count: usize,
+    /// Norms of stored vectors, if vectors are kept un-normalized. Not persisted, computed on load.
+    norms: Option<Vec<ScoreType>>,
+    /// Per-dimension weights applied during scoring, taken from the segment config
+    dimension_weights: Option<Vec<VectorElementType>>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
-struct StoredRecord {
+struct StoredRecord<T> {
     pub deleted: bool,
-    pub vector: Vec<VectorElementType>,
+    pub vector: Vec<T>,
 }
 
 pub fn open_simple_vector_storage(
@@ -47,7 +54,82 @@ pub fn open_simple_vector_storage(
     dim: usize,
     distance: Distance,
 ) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
-    let mut vectors = ChunkedVectors::new(dim);
+    let storage = open_simple_dense_vector_storage_impl(
+        database,
+        database_column_name,
+        dim,
+        distance,
+        false,
+    )?;
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::DenseSimple(storage),
+    )))
+}
+
+/// Open in-memory vector storage for `Cosine` distance, which keeps original vectors and caches their norms
+pub fn open_simple_vector_storage_with_norms(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    dim: usize,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_simple_dense_vector_storage_impl(
+        database,
+        database_column_name,
+        dim,
+        Distance::Cosine,
+        true,
+    )?;
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::DenseSimple(storage),
+    )))
+}
+
+/// Open in-memory vector storage, which keeps vectors in half precision
+pub fn open_simple_half_vector_storage(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_simple_dense_vector_storage_impl(
+        database,
+        database_column_name,
+        dim,
+        distance,
+        false,
+    )?;
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::DenseSimpleHalf(storage),
+    )))
+}
+
+/// Open in-memory vector storage, which keeps vectors as unsigned bytes
+pub fn open_simple_byte_vector_storage(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_simple_dense_vector_storage_impl(
+        database,
+        database_column_name,
+        dim,
+        distance,
+        false,
+    )?;
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::DenseSimpleByte(storage),
+    )))
+}
+
+fn open_simple_dense_vector_storage_impl<T: PrimitiveVectorElement>(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    dim: usize,
+    distance: Distance,
+    with_norms: bool,
+) -> OperationResult<SimpleDenseVectorStorage<T>> {
+    let mut vectors = ChunkedVectors::new_padded(dim);
     let (mut deleted, mut deleted_count) = (BitVec::new(), 0);
 
     let db_wrapper = DatabaseColumnWrapper::new(database, database_column_name);
@@ -55,7 +137,7 @@ pub fn open_simple_vector_storage(
     for (key, value) in db_wrapper.lock_db().iter()? {
         let point_id: PointOffsetType = bincode::deserialize(&key)
             .map_err(|_| OperationError::service_error("cannot deserialize point id from db"))?;
-        let stored_record: StoredRecord = bincode::deserialize(&value)
+        let stored_record: StoredRecord<T> = bincode::deserialize(&value)
             .map_err(|_| OperationError::service_error("cannot deserialize record from db"))?;
 
         // Propagate deleted flag
@@ -66,29 +148,44 @@ pub fn open_simple_vector_storage(
         vectors.insert(point_id, &stored_record.vector)?;
     }
 
+    let norms = with_norms.then(|| {
+        (0..vectors.len() as PointOffsetType)
+            .map(|key| vector_norm(vectors.get(key)))
+            .collect()
+    });
+
     debug!("Segment vectors: {}", vectors.len());
     debug!(
         "Estimated segment size {} MB",
-        vectors.len() * dim * size_of::<VectorElementType>() / 1024 / 1024
+        vectors.len() * dim * size_of::<T>() / 1024 / 1024
     );
 
-    Ok(Arc::new(AtomicRefCell::new(
-        VectorStorageEnum::DenseSimple(SimpleDenseVectorStorage {
-            dim,
-            distance,
-            vectors,
-            db_wrapper,
-            update_buffer: StoredRecord {
-                deleted: false,
-                vector: vec![0.; dim],
-            },
-            deleted,
-            deleted_count,
-        }),
-    )))
+    Ok(SimpleDenseVectorStorage {
+        dim,
+        distance,
+        vectors,
+        db_wrapper,
+        update_buffer: StoredRecord {
+            deleted: false,
+            vector: vec![T::default(); dim],
+        },
+        deleted,
+        deleted_count,
+        norms,
+        dimension_weights: None,
+    })
+}
+
+fn vector_norm<T: PrimitiveVectorElement>(vector: &[T]) -> ScoreType {
+    cosine_norm(&T::slice_to_float_cow(Cow::Borrowed(vector)))
 }
 
-impl SimpleDenseVectorStorage {
+impl<T: PrimitiveVectorElement> SimpleDenseVectorStorage<T> {
+    /// Attach per-dimension weights from the vector config, they are not persisted
+    pub fn set_dimension_weights(&mut self, weights: Vec<VectorElementType>) {
+        self.dimension_weights = Some(weights);
+    }
+
     /// Set deleted flag for given key. Returns previous deleted state.
     #[inline]
     fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> bool {
@@ -106,11 +203,21 @@ impl SimpleDenseVectorStorage {
         was_deleted
     }
 
+    fn set_norm(&mut self, key: PointOffsetType, vector: &[T]) {
+        if let Some(norms) = &mut self.norms {
+            let key = key as usize;
+            if norms.len() <= key {
+                norms.resize(key + 1, 1.0);
+            }
+            norms[key] = vector_norm(vector);
+        }
+    }
+
     fn update_stored(
         &mut self,
         key: PointOffsetType,
         deleted: bool,
-        vector: Option<&[VectorElementType]>,
+        vector: Option<&[T]>,
     ) -> OperationResult<()> {
         // Write vector state to buffer record
         let record = &mut self.update_buffer;
@@ -129,13 +236,36 @@ impl SimpleDenseVectorStorage {
     }
 }
 
-impl DenseVectorStorage for SimpleDenseVectorStorage {
-    fn get_dense(&self, key: PointOffsetType) -> &[VectorElementType] {
+impl<T: PrimitiveVectorElement> DenseVectorStorage<T> for SimpleDenseVectorStorage<T> {
+    fn get_dense(&self, key: PointOffsetType) -> &[T] {
         self.vectors.get(key)
     }
+
+    fn padded_dim(&self) -> Option<usize> {
+        Some(self.vectors.padded_dim())
+    }
+
+    fn get_dense_padded(&self, key: PointOffsetType) -> &[T] {
+        self.vectors.get_padded(key)
+    }
+
+    fn has_norms(&self) -> bool {
+        self.norms.is_some()
+    }
+
+    fn get_norm(&self, key: PointOffsetType) -> ScoreType {
+        self.norms
+            .as_ref()
+            .and_then(|norms| norms.get(key as usize).copied())
+            .unwrap_or(1.0)
+    }
+
+    fn dimension_weights(&self) -> Option<&[VectorElementType]> {
+        self.dimension_weights.as_deref()
+    }
 }
 
-impl VectorStorage for SimpleDenseVectorStorage {
+impl<T: PrimitiveVectorElement> VectorStorage for SimpleDenseVectorStorage<T> {
     fn vector_dim(&self) -> usize {
         self.dim
     }
@@ -153,14 +283,16 @@ impl VectorStorage for SimpleDenseVectorStorage {
     }
 
     fn get_vector(&self, key: PointOffsetType) -> CowVector {
-        self.get_dense(key).into()
+        T::slice_to_float_cow(Cow::Borrowed(self.get_dense(key))).into()
     }
 
     fn insert_vector(&mut self, key: PointOffsetType, vector: VectorRef) -> OperationResult<()> {
-        let vector = vector.try_into()?;
-        self.vectors.insert(key, vector)?;
+        let vector: &[VectorElementType] = vector.try_into()?;
+        let vector = T::slice_from_float_cow(Cow::Borrowed(vector));
+        self.vectors.insert(key, &vector)?;
+        self.set_norm(key, &vector);
         self.set_deleted(key, false);
-        self.update_stored(key, false, Some(vector))?;
+        self.update_stored(key, false, Some(&vector))?;
         Ok(())
     }
 
@@ -175,11 +307,13 @@ impl VectorStorage for SimpleDenseVectorStorage {
             check_process_stopped(stopped)?;
             // Do not perform preprocessing - vectors should be already processed
             let other_vector = other.get_vector(point_id);
-            let other_vector = other_vector.as_vec_ref().try_into()?;
+            let other_vector: &[VectorElementType] = other_vector.as_vec_ref().try_into()?;
+            let other_vector = T::slice_from_float_cow(Cow::Borrowed(other_vector));
             let other_deleted = other.is_deleted_vector(point_id);
-            let new_id = self.vectors.push(other_vector)?;
+            let new_id = self.vectors.push(&other_vector)?;
+            self.set_norm(new_id, &other_vector);
             self.set_deleted(new_id, other_deleted);
-            self.update_stored(new_id, other_deleted, Some(other_vector))?;
+            self.update_stored(new_id, other_deleted, Some(&other_vector))?;
         }
         let end_index = self.vectors.len() as PointOffsetType;
         Ok(start_index..end_index)
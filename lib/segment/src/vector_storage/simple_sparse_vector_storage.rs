@@ -1,3 +1,4 @@
+use std::mem::size_of;
 use std::ops::Range;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
@@ -9,6 +10,7 @@ use parking_lot::RwLock;
 use rocksdb::DB;
 use serde::{Deserialize, Serialize};
 use sparse::common::sparse_vector::SparseVector;
+use sparse::common::types::{DimId, DimWeight};
 
 use super::SparseVectorStorage;
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
@@ -127,6 +129,11 @@ impl SimpleSparseVectorStorage {
         Ok(())
     }
 
+    /// Size of all not deleted sparse vectors in bytes, including indices and values
+    pub fn available_size_in_bytes(&self) -> usize {
+        self.total_sparse_size * (size_of::<DimId>() + size_of::<DimWeight>())
+    }
+
     /// Estimate average vector size based on total number of non-zero elements in all vectors.
     ///
     /// This is needed because the optimizer relies on the vector dimension * size_of_f32 * point_count to
@@ -11,6 +11,7 @@ use crate::data_types::vectors::QueryVector;
 use crate::fixtures::payload_context_fixture::FixtureIdTracker;
 use crate::id_tracker::IdTracker;
 use crate::types::Distance;
+use crate::vector_storage::appendable_mmap_vector_storage::open_appendable_memmap_vector_storage_with_async_io;
 use crate::vector_storage::memmap_vector_storage::open_memmap_vector_storage_with_async_io;
 use crate::vector_storage::simple_dense_vector_storage::open_simple_vector_storage;
 use crate::vector_storage::vector_storage_base::VectorStorage;
@@ -36,10 +37,35 @@ fn async_raw_scorer_dot() -> Result<()> {
     test_async_raw_scorer_defaults(Distance::Dot)
 }
 
+#[test]
+fn async_raw_scorer_appendable_cosine() -> Result<()> {
+    test_async_raw_scorer_appendable_defaults(Distance::Cosine)
+}
+
+#[test]
+fn async_raw_scorer_appendable_euclid() -> Result<()> {
+    test_async_raw_scorer_appendable_defaults(Distance::Euclid)
+}
+
+#[test]
+fn async_raw_scorer_appendable_manhattan() -> Result<()> {
+    test_async_raw_scorer_appendable_defaults(Distance::Manhattan)
+}
+
+#[test]
+fn async_raw_scorer_appendable_dot() -> Result<()> {
+    test_async_raw_scorer_appendable_defaults(Distance::Dot)
+}
+
 fn test_async_raw_scorer_defaults(distance: Distance) -> Result<()> {
     test_async_raw_scorer(6942, 128, distance, 1024, 128, 256)
 }
 
+fn test_async_raw_scorer_appendable_defaults(distance: Distance) -> Result<()> {
+    // Enough points to span multiple chunks
+    test_async_raw_scorer_appendable(6942, 128, distance, 4096, 512, 256)
+}
+
 fn test_async_raw_scorer(
     seed: u64,
     dim: usize,
@@ -83,6 +109,37 @@ fn test_async_raw_scorer(
 
     Ok(())
 }
+
+fn test_async_raw_scorer_appendable(
+    seed: u64,
+    dim: usize,
+    distance: Distance,
+    points: usize,
+    delete: usize,
+    score: usize,
+) -> Result<()> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+
+    let dir = tempfile::Builder::new()
+        .prefix("appendable-storage")
+        .tempdir()?;
+
+    let storage =
+        open_appendable_memmap_vector_storage_with_async_io(dir.path(), dim, distance, true)?;
+    let mut storage = storage.borrow_mut();
+
+    let mut id_tracker = FixtureIdTracker::new(points);
+
+    insert_random_vectors(&mut rng, &mut *storage, points)?;
+    delete_random_vectors(&mut rng, &mut *storage, &mut id_tracker, delete)?;
+
+    for _ in 0..score {
+        test_random_score(&mut rng, &storage, id_tracker.deleted_point_bitslice())?;
+    }
+
+    Ok(())
+}
+
 fn insert_random_vectors(
     rng: &mut impl rand::Rng,
     storage: &mut impl VectorStorage,
@@ -104,10 +161,14 @@ fn test_random_score(
     let raw_scorer = new_raw_scorer(query.clone(), storage, deleted_points).unwrap();
 
     let is_stopped = AtomicBool::new(false);
-    let async_raw_scorer = if let VectorStorageEnum::Memmap(storage) = storage {
-        async_raw_scorer::new(query, storage, deleted_points, &is_stopped)?
-    } else {
-        unreachable!();
+    let async_raw_scorer = match storage {
+        VectorStorageEnum::Memmap(storage) => {
+            async_raw_scorer::new(query, storage.as_ref(), deleted_points, &is_stopped)?
+        }
+        VectorStorageEnum::AppendableMemmap(storage) => {
+            async_raw_scorer::new(query, storage.as_ref(), deleted_points, &is_stopped)?
+        }
+        _ => unreachable!(),
     };
 
     let points = rng.gen_range(1..storage.total_vector_count());
@@ -118,5 +179,13 @@ fn test_random_score(
 
     assert_eq!(res, async_res);
 
+    // Rescoring reads in storage order, but keeps the order of given points
+    let rescored = raw_scorer.rescore_points(&points);
+    assert_eq!(
+        rescored.iter().map(|scored| scored.idx).collect_vec(),
+        points
+    );
+    assert_eq!(rescored, async_raw_scorer.rescore_points(&points));
+
     Ok(())
 }
@@ -3,4 +3,5 @@ mod async_raw_scorer;
 mod custom_query_scorer_equivalency;
 mod test_appendable_sparse_vector_storage;
 mod test_appendable_vector_storage;
+mod test_multi_dense_vector_storage;
 mod utils;
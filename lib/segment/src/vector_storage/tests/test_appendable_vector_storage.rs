@@ -10,10 +10,16 @@ use crate::data_types::vectors::QueryVector;
 use crate::fixtures::payload_context_fixture::FixtureIdTracker;
 use crate::id_tracker::{IdTracker, IdTrackerSS};
 use crate::types::{Distance, PointIdType, QuantizationConfig, ScalarQuantizationConfig};
-use crate::vector_storage::appendable_mmap_vector_storage::open_appendable_memmap_vector_storage;
+use crate::vector_storage::appendable_mmap_vector_storage::{
+    open_appendable_memmap_byte_vector_storage, open_appendable_memmap_half_vector_storage,
+    open_appendable_memmap_vector_storage, open_appendable_memmap_vector_storage_with_norms,
+};
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
-use crate::vector_storage::simple_dense_vector_storage::open_simple_vector_storage;
-use crate::vector_storage::{new_raw_scorer, VectorStorage, VectorStorageEnum};
+use crate::vector_storage::simple_dense_vector_storage::{
+    open_simple_byte_vector_storage, open_simple_half_vector_storage, open_simple_vector_storage,
+    open_simple_vector_storage_with_norms,
+};
+use crate::vector_storage::{new_raw_scorer, DenseVectorStorage, VectorStorage, VectorStorageEnum};
 
 fn do_test_delete_points(storage: Arc<AtomicRefCell<VectorStorageEnum>>) {
     let points = vec![
@@ -272,6 +278,72 @@ fn do_test_score_points(storage: Arc<AtomicRefCell<VectorStorageEnum>>) {
     assert!(!all_ids1.contains(&top_idx))
 }
 
+/// Storage with norms must return original vectors, but score them as normalized ones
+fn do_test_score_points_with_norms(storage: Arc<AtomicRefCell<VectorStorageEnum>>) {
+    let points = vec![
+        vec![2.0, 0.0, 2.0, 2.0],
+        vec![1.0, 0.0, 1.0, 0.0],
+        vec![3.0, 3.0, 3.0, 3.0],
+        vec![0.5, 0.5, 0.0, 0.5],
+        vec![0.0, 0.0, 0.0, 0.0],
+    ];
+    let id_tracker: Arc<AtomicRefCell<IdTrackerSS>> =
+        Arc::new(AtomicRefCell::new(FixtureIdTracker::new(points.len())));
+    let borrowed_id_tracker = id_tracker.borrow();
+    let mut borrowed_storage = storage.borrow_mut();
+
+    let dir = Builder::new().prefix("db_dir").tempdir().unwrap();
+    let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+    let normalized_storage =
+        open_simple_vector_storage(db, DB_VECTOR_CF, 4, Distance::Cosine).unwrap();
+    let mut borrowed_normalized_storage = normalized_storage.borrow_mut();
+
+    for (i, vec) in points.iter().enumerate() {
+        borrowed_storage
+            .insert_vector(i as PointOffsetType, vec.as_slice().into())
+            .unwrap();
+        let normalized = Distance::Cosine.preprocess_vector(vec.clone());
+        borrowed_normalized_storage
+            .insert_vector(i as PointOffsetType, normalized.as_slice().into())
+            .unwrap();
+    }
+
+    for (i, vec) in points.iter().enumerate() {
+        let stored: Vec<_> = borrowed_storage
+            .get_vector(i as PointOffsetType)
+            .to_owned()
+            .try_into()
+            .unwrap();
+        assert_eq!(&stored, vec);
+    }
+
+    let query: QueryVector = [0.0, 1.0, 1.1, 1.0].into();
+    let query_points = vec![0, 1, 2, 3, 4];
+
+    let mut scores = vec![ScoredPointOffset { idx: 0, score: 0. }; query_points.len()];
+    new_raw_scorer(
+        query.clone(),
+        &borrowed_storage,
+        borrowed_id_tracker.deleted_point_bitslice(),
+    )
+    .unwrap()
+    .score_points(&query_points, &mut scores);
+
+    let mut expected = vec![ScoredPointOffset { idx: 0, score: 0. }; query_points.len()];
+    new_raw_scorer(
+        query,
+        &borrowed_normalized_storage,
+        borrowed_id_tracker.deleted_point_bitslice(),
+    )
+    .unwrap()
+    .score_points(&query_points, &mut expected);
+
+    for (score, expected) in scores.iter().zip(&expected) {
+        assert_eq!(score.idx, expected.idx);
+        assert!((score.score - expected.score).abs() < 1e-5);
+    }
+}
+
 fn test_score_quantized_points(storage: Arc<AtomicRefCell<VectorStorageEnum>>) {
     let points = vec![
         vec![1.0, 0.0, 1.0, 1.0],
@@ -421,6 +493,24 @@ fn test_score_quantized_points_simple_vector_storages() {
     let _storage = open_simple_vector_storage(db, DB_VECTOR_CF, 4, Distance::Dot).unwrap();
 }
 
+#[test]
+fn test_score_points_in_simple_vector_storages_with_norms() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    {
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+        let storage = open_simple_vector_storage_with_norms(db, DB_VECTOR_CF, 4).unwrap();
+        do_test_score_points_with_norms(storage.clone());
+        storage.borrow().flusher()().unwrap();
+    }
+
+    let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+    let storage = open_simple_vector_storage_with_norms(db, DB_VECTOR_CF, 4).unwrap();
+    let VectorStorageEnum::DenseSimple(storage) = &*storage.borrow() else {
+        panic!("unexpected storage type");
+    };
+    assert_eq!(storage.get_norm(2), 6.0);
+}
+
 // ----------------------------------------------
 
 #[test]
@@ -460,6 +550,22 @@ fn test_score_points_in_appendable_memmap_vector_storages() {
     let _storage = open_appendable_memmap_vector_storage(dir.path(), 4, Distance::Dot).unwrap();
 }
 
+#[test]
+fn test_score_points_in_appendable_memmap_vector_storages_with_norms() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    {
+        let storage = open_appendable_memmap_vector_storage_with_norms(dir.path(), 4).unwrap();
+        do_test_score_points_with_norms(storage.clone());
+        storage.borrow().flusher()().unwrap();
+    }
+
+    let storage = open_appendable_memmap_vector_storage_with_norms(dir.path(), 4).unwrap();
+    let VectorStorageEnum::AppendableMemmap(storage) = &*storage.borrow() else {
+        panic!("unexpected storage type");
+    };
+    assert_eq!(storage.get_norm(2), 6.0);
+}
+
 #[test]
 fn test_score_quantized_points_appendable_memmap_vector_storages() {
     let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
@@ -471,3 +577,80 @@ fn test_score_quantized_points_appendable_memmap_vector_storages() {
 
     let _storage = open_appendable_memmap_vector_storage(dir.path(), 4, Distance::Dot).unwrap();
 }
+
+// ----------------------------------------------
+
+#[test]
+fn test_delete_points_in_simple_half_vector_storages() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    {
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+        let storage = open_simple_half_vector_storage(db, DB_VECTOR_CF, 4, Distance::Dot).unwrap();
+        do_test_delete_points(storage.clone());
+        storage.borrow().flusher()().unwrap();
+    }
+
+    let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+    let storage = open_simple_half_vector_storage(db, DB_VECTOR_CF, 4, Distance::Dot).unwrap();
+    assert_eq!(storage.borrow().total_vector_count(), 5);
+}
+
+#[test]
+fn test_score_points_in_simple_half_vector_storages() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+    let storage = open_simple_half_vector_storage(db, DB_VECTOR_CF, 4, Distance::Dot).unwrap();
+    do_test_score_points(storage);
+}
+
+#[test]
+fn test_update_from_delete_points_appendable_memmap_half_vector_storages() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    {
+        let storage =
+            open_appendable_memmap_half_vector_storage(dir.path(), 4, Distance::Dot).unwrap();
+        do_test_update_from_delete_points(storage.clone());
+        storage.borrow().flusher()().unwrap();
+    }
+
+    let storage = open_appendable_memmap_half_vector_storage(dir.path(), 4, Distance::Dot).unwrap();
+    assert_eq!(storage.borrow().total_vector_count(), 5);
+}
+
+#[test]
+fn test_score_quantized_points_appendable_memmap_half_vector_storages() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    let storage = open_appendable_memmap_half_vector_storage(dir.path(), 4, Distance::Dot).unwrap();
+    test_score_quantized_points(storage);
+}
+
+// ----------------------------------------------
+
+#[test]
+fn test_delete_points_in_simple_byte_vector_storages() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    {
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+        let storage = open_simple_byte_vector_storage(db, DB_VECTOR_CF, 4, Distance::Dot).unwrap();
+        do_test_delete_points(storage.clone());
+        storage.borrow().flusher()().unwrap();
+    }
+
+    let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+    let storage = open_simple_byte_vector_storage(db, DB_VECTOR_CF, 4, Distance::Dot).unwrap();
+    assert_eq!(storage.borrow().total_vector_count(), 5);
+}
+
+#[test]
+fn test_update_from_delete_points_appendable_memmap_byte_vector_storages() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    {
+        let storage =
+            open_appendable_memmap_byte_vector_storage(dir.path(), 4, Distance::Dot).unwrap();
+        do_test_update_from_delete_points(storage.clone());
+        storage.borrow().flusher()().unwrap();
+    }
+
+    let storage = open_appendable_memmap_byte_vector_storage(dir.path(), 4, Distance::Dot).unwrap();
+    assert_eq!(storage.borrow().total_vector_count(), 5);
+}
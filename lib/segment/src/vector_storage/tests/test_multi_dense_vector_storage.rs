@@ -0,0 +1,193 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use common::types::PointOffsetType;
+use tempfile::Builder;
+
+use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
+use crate::data_types::vectors::{MultiDenseVector, QueryVector};
+use crate::fixtures::payload_context_fixture::FixtureIdTracker;
+use crate::id_tracker::{IdTracker, IdTrackerSS};
+use crate::types::{Distance, MultiVectorConfig, QuantizationConfig, ScalarQuantizationConfig};
+use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
+use crate::vector_storage::simple_multi_dense_vector_storage::open_simple_multi_dense_vector_storage;
+use crate::vector_storage::{new_raw_scorer, VectorStorage, VectorStorageEnum};
+
+fn multi_points() -> Vec<MultiDenseVector> {
+    vec![
+        vec![vec![1.0, 0.0], vec![0.0, 1.0]],
+        vec![vec![1.0, 0.0], vec![0.0, 0.5]],
+        vec![vec![0.5, 0.5]],
+        vec![vec![0.0, 0.2]],
+    ]
+    .into_iter()
+    .map(|matrix| MultiDenseVector::try_from_matrix(matrix).unwrap())
+    .collect()
+}
+
+fn do_test_delete_points(storage: Arc<AtomicRefCell<VectorStorageEnum>>) {
+    let points = multi_points();
+    let id_tracker: Arc<AtomicRefCell<IdTrackerSS>> =
+        Arc::new(AtomicRefCell::new(FixtureIdTracker::new(points.len())));
+
+    let borrowed_id_tracker = id_tracker.borrow_mut();
+    let mut borrowed_storage = storage.borrow_mut();
+
+    for (i, vec) in points.iter().enumerate() {
+        borrowed_storage
+            .insert_vector(i as PointOffsetType, vec.into())
+            .unwrap();
+    }
+
+    // Check that all points are inserted
+    for (i, vec) in points.iter().enumerate() {
+        let stored_vec = borrowed_storage.get_vector(i as PointOffsetType);
+        let multi: &MultiDenseVector = stored_vec.as_vec_ref().try_into().unwrap();
+        assert_eq!(multi, vec);
+    }
+
+    // Query vectors sum up their best matches: 2.0, 1.5, 1.0 and 0.2
+    let query: QueryVector =
+        MultiDenseVector::try_from_matrix(vec![vec![1.0, 0.0], vec![0.0, 1.0]])
+            .unwrap()
+            .into();
+    let scorer = new_raw_scorer(
+        query.clone(),
+        &borrowed_storage,
+        borrowed_id_tracker.deleted_point_bitslice(),
+    )
+    .unwrap();
+    let closest = scorer.peek_top_iter(&mut [0, 1, 2, 3].iter().cloned(), 4);
+    assert_eq!(
+        closest.iter().map(|p| p.idx).collect::<Vec<_>>(),
+        vec![0, 1, 2, 3]
+    );
+    assert!((closest[1].score - 1.5).abs() < 1e-5);
+    assert!((scorer.score_internal(0, 1) - 1.5).abs() < 1e-5);
+    drop(scorer);
+
+    borrowed_storage.delete_vector(1).unwrap();
+    assert_eq!(borrowed_storage.deleted_vector_count(), 1);
+
+    let closest = new_raw_scorer(
+        query,
+        &borrowed_storage,
+        borrowed_id_tracker.deleted_point_bitslice(),
+    )
+    .unwrap()
+    .peek_top_iter(&mut [0, 1, 2, 3].iter().cloned(), 4);
+    assert_eq!(
+        closest.iter().map(|p| p.idx).collect::<Vec<_>>(),
+        vec![0, 2, 3],
+        "deleted multivector must not be scored"
+    );
+}
+
+fn do_test_score_quantized_points(storage: Arc<AtomicRefCell<VectorStorageEnum>>) {
+    let points = multi_points();
+    let id_tracker = Arc::new(AtomicRefCell::new(FixtureIdTracker::new(points.len())));
+    let mut borrowed_storage = storage.borrow_mut();
+    let borrowed_id_tracker = id_tracker.borrow_mut();
+
+    for (i, vec) in points.iter().enumerate() {
+        borrowed_storage
+            .insert_vector(i as PointOffsetType, vec.into())
+            .unwrap();
+    }
+
+    let config: QuantizationConfig = ScalarQuantizationConfig {
+        r#type: Default::default(),
+        quantile: None,
+        always_ram: None,
+    }
+    .into();
+
+    let dir = Builder::new()
+        .prefix("quantization_path")
+        .tempdir()
+        .unwrap();
+
+    let stopped = AtomicBool::new(false);
+    let quantized_vectors =
+        QuantizedVectors::create(&borrowed_storage, &config, dir.path(), 1, &stopped).unwrap();
+
+    let query: QueryVector =
+        MultiDenseVector::try_from_matrix(vec![vec![0.5, 0.5], vec![1.0, 0.0]])
+            .unwrap()
+            .into();
+
+    let files = quantized_vectors.files();
+    // test save-load
+    let quantized_vectors = QuantizedVectors::load(&borrowed_storage, dir.path()).unwrap();
+    assert_eq!(files, quantized_vectors.files());
+
+    let scorer_quant = quantized_vectors
+        .raw_scorer(
+            query.clone(),
+            borrowed_id_tracker.deleted_point_bitslice(),
+            borrowed_storage.deleted_vector_bitslice(),
+            &stopped,
+        )
+        .unwrap();
+    let scorer_orig = new_raw_scorer(
+        query,
+        &borrowed_storage,
+        borrowed_id_tracker.deleted_point_bitslice(),
+    )
+    .unwrap();
+    for i in 0..points.len() as PointOffsetType {
+        let quant = scorer_quant.score_point(i);
+        let orig = scorer_orig.score_point(i);
+        assert!((orig - quant).abs() < 0.15);
+
+        let quant = scorer_quant.score_internal(0, i);
+        let orig = scorer_orig.score_internal(0, i);
+        assert!((orig - quant).abs() < 0.15);
+    }
+}
+
+#[test]
+fn test_delete_points_in_simple_multi_dense_vector_storage() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    {
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+        let storage = open_simple_multi_dense_vector_storage(
+            db,
+            DB_VECTOR_CF,
+            2,
+            Distance::Dot,
+            MultiVectorConfig::default(),
+        )
+        .unwrap();
+        do_test_delete_points(storage.clone());
+        storage.borrow().flusher()().unwrap();
+    }
+
+    let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+    let storage = open_simple_multi_dense_vector_storage(
+        db,
+        DB_VECTOR_CF,
+        2,
+        Distance::Dot,
+        MultiVectorConfig::default(),
+    )
+    .unwrap();
+    assert_eq!(storage.borrow().total_vector_count(), 4);
+    assert_eq!(storage.borrow().deleted_vector_count(), 1);
+}
+
+#[test]
+fn test_score_quantized_points_simple_multi_dense_vector_storage() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+    let storage = open_simple_multi_dense_vector_storage(
+        db,
+        DB_VECTOR_CF,
+        2,
+        Distance::Dot,
+        MultiVectorConfig::default(),
+    )
+    .unwrap();
+    do_test_score_quantized_points(storage);
+}
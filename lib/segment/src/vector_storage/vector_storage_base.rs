@@ -1,19 +1,24 @@
+use std::mem::size_of;
 use std::ops::Range;
 use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 
 use bitvec::prelude::BitSlice;
-use common::types::PointOffsetType;
+use common::types::{PointOffsetType, ScoreType};
 use sparse::common::sparse_vector::SparseVector;
 
 use super::memmap_vector_storage::MemmapVectorStorage;
 use super::simple_dense_vector_storage::SimpleDenseVectorStorage;
-use crate::common::operation_error::OperationResult;
+use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::Flusher;
 use crate::data_types::named_vectors::CowVector;
-use crate::data_types::vectors::{VectorElementType, VectorRef};
-use crate::types::Distance;
+use crate::data_types::primitive::PrimitiveVectorElement;
+use crate::data_types::vectors::{
+    MultiDenseVector, VectorElementType, VectorElementTypeByte, VectorElementTypeHalf, VectorRef,
+};
+use crate::types::{Distance, MultiVectorConfig};
 use crate::vector_storage::appendable_mmap_vector_storage::AppendableMmapVectorStorage;
+use crate::vector_storage::simple_multi_dense_vector_storage::SimpleMultiDenseVectorStorage;
 use crate::vector_storage::simple_sparse_vector_storage::SimpleSparseVectorStorage;
 
 /// Trait for vector storage
@@ -65,6 +70,12 @@ pub trait VectorStorage {
 
     fn files(&self) -> Vec<PathBuf>;
 
+    /// Files which are never modified once the storage is built.
+    /// Checksums of these files are verified on segment load.
+    fn immutable_files(&self) -> Vec<PathBuf> {
+        vec![]
+    }
+
     /// Flag the vector by the given key as deleted
     ///
     /// Returns true if the vector was not deleted before and is now deleted
@@ -96,82 +107,223 @@ pub trait VectorStorage {
     fn deleted_vector_bitslice(&self) -> &BitSlice;
 }
 
-pub trait DenseVectorStorage: VectorStorage {
-    fn get_dense(&self, key: PointOffsetType) -> &[VectorElementType];
+pub trait DenseVectorStorage<T: PrimitiveVectorElement = VectorElementType>: VectorStorage {
+    fn get_dense(&self, key: PointOffsetType) -> &[T];
+
+    /// Number of elements in vectors returned by [`DenseVectorStorage::get_dense_padded`]
+    ///
+    /// `None` if the storage does not keep vectors in padded layout.
+    fn padded_dim(&self) -> Option<usize> {
+        None
+    }
+
+    /// Stored vector followed by its zero padding
+    ///
+    /// If [`DenseVectorStorage::padded_dim`] is set, the returned slice has exactly that length,
+    /// starts at an address aligned to [`VECTOR_ALIGNMENT`](super::common::VECTOR_ALIGNMENT) and
+    /// all elements past [`VectorStorage::vector_dim`] are zero. Otherwise it is the same as
+    /// [`DenseVectorStorage::get_dense`].
+    fn get_dense_padded(&self, key: PointOffsetType) -> &[T] {
+        self.get_dense(key)
+    }
+
+    /// Whether stored vectors are kept un-normalized, with their norms cached by the storage
+    ///
+    /// If so, scorers must divide similarities by [`DenseVectorStorage::get_norm`].
+    fn has_norms(&self) -> bool {
+        false
+    }
+
+    /// Cached norm of the stored vector, `1.0` if norms are not kept
+    fn get_norm(&self, _key: PointOffsetType) -> ScoreType {
+        1.0
+    }
+
+    /// Per-dimension weights, which scorers must apply to each term of the distance
+    fn dimension_weights(&self) -> Option<&[VectorElementType]> {
+        None
+    }
 }
 
 pub trait SparseVectorStorage: VectorStorage {
     fn get_sparse(&self, key: PointOffsetType) -> OperationResult<SparseVector>;
 }
 
+pub trait MultiVectorStorage: VectorStorage {
+    fn get_multi(&self, key: PointOffsetType) -> &MultiDenseVector;
+
+    fn multivector_config(&self) -> &MultiVectorConfig;
+}
+
 pub enum VectorStorageEnum {
     DenseSimple(SimpleDenseVectorStorage),
+    DenseSimpleHalf(SimpleDenseVectorStorage<VectorElementTypeHalf>),
+    DenseSimpleByte(SimpleDenseVectorStorage<VectorElementTypeByte>),
     Memmap(Box<MemmapVectorStorage>),
+    MemmapHalf(Box<MemmapVectorStorage<VectorElementTypeHalf>>),
+    MemmapByte(Box<MemmapVectorStorage<VectorElementTypeByte>>),
     AppendableMemmap(Box<AppendableMmapVectorStorage>),
+    AppendableMemmapHalf(Box<AppendableMmapVectorStorage<VectorElementTypeHalf>>),
+    AppendableMemmapByte(Box<AppendableMmapVectorStorage<VectorElementTypeByte>>),
     SparseSimple(SimpleSparseVectorStorage),
+    MultiDenseSimple(SimpleMultiDenseVectorStorage),
+}
+
+impl VectorStorageEnum {
+    /// Size of the available vectors in bytes, with the element type of the storage
+    ///
+    /// Estimates memory, required to copy the vectors into another segment.
+    pub fn available_size_in_bytes(&self) -> usize {
+        let dense_size =
+            |element_size: usize| self.available_vector_count() * self.vector_dim() * element_size;
+        match self {
+            VectorStorageEnum::DenseSimple(_)
+            | VectorStorageEnum::Memmap(_)
+            | VectorStorageEnum::AppendableMemmap(_) => dense_size(size_of::<VectorElementType>()),
+            VectorStorageEnum::DenseSimpleHalf(_)
+            | VectorStorageEnum::MemmapHalf(_)
+            | VectorStorageEnum::AppendableMemmapHalf(_) => {
+                dense_size(size_of::<VectorElementTypeHalf>())
+            }
+            VectorStorageEnum::DenseSimpleByte(_)
+            | VectorStorageEnum::MemmapByte(_)
+            | VectorStorageEnum::AppendableMemmapByte(_) => {
+                dense_size(size_of::<VectorElementTypeByte>())
+            }
+            VectorStorageEnum::SparseSimple(v) => v.available_size_in_bytes(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.available_size_in_bytes(),
+        }
+    }
+
+    /// Attach per-dimension weights, applied by scorers of `float32` dense vectors
+    pub fn set_dimension_weights(
+        &mut self,
+        weights: Vec<VectorElementType>,
+    ) -> OperationResult<()> {
+        match self {
+            VectorStorageEnum::DenseSimple(v) => v.set_dimension_weights(weights),
+            VectorStorageEnum::Memmap(v) => v.set_dimension_weights(weights),
+            VectorStorageEnum::AppendableMemmap(v) => v.set_dimension_weights(weights),
+            _ => {
+                return Err(OperationError::service_error(
+                    "dimension weights are only supported for float32 dense vectors",
+                ))
+            }
+        }
+        Ok(())
+    }
 }
 
 impl VectorStorage for VectorStorageEnum {
     fn vector_dim(&self) -> usize {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.vector_dim(),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.vector_dim(),
+            VectorStorageEnum::DenseSimpleByte(v) => v.vector_dim(),
             VectorStorageEnum::Memmap(v) => v.vector_dim(),
+            VectorStorageEnum::MemmapHalf(v) => v.vector_dim(),
+            VectorStorageEnum::MemmapByte(v) => v.vector_dim(),
             VectorStorageEnum::AppendableMemmap(v) => v.vector_dim(),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.vector_dim(),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.vector_dim(),
             VectorStorageEnum::SparseSimple(v) => v.vector_dim(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.vector_dim(),
         }
     }
 
     fn distance(&self) -> Distance {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.distance(),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.distance(),
+            VectorStorageEnum::DenseSimpleByte(v) => v.distance(),
             VectorStorageEnum::Memmap(v) => v.distance(),
+            VectorStorageEnum::MemmapHalf(v) => v.distance(),
+            VectorStorageEnum::MemmapByte(v) => v.distance(),
             VectorStorageEnum::AppendableMemmap(v) => v.distance(),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.distance(),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.distance(),
             VectorStorageEnum::SparseSimple(v) => v.distance(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.distance(),
         }
     }
 
     fn is_on_disk(&self) -> bool {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseSimpleByte(v) => v.is_on_disk(),
             VectorStorageEnum::Memmap(v) => v.is_on_disk(),
+            VectorStorageEnum::MemmapHalf(v) => v.is_on_disk(),
+            VectorStorageEnum::MemmapByte(v) => v.is_on_disk(),
             VectorStorageEnum::AppendableMemmap(v) => v.is_on_disk(),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.is_on_disk(),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.is_on_disk(),
             VectorStorageEnum::SparseSimple(v) => v.is_on_disk(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.is_on_disk(),
         }
     }
 
     fn total_vector_count(&self) -> usize {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseSimpleByte(v) => v.total_vector_count(),
             VectorStorageEnum::Memmap(v) => v.total_vector_count(),
+            VectorStorageEnum::MemmapHalf(v) => v.total_vector_count(),
+            VectorStorageEnum::MemmapByte(v) => v.total_vector_count(),
             VectorStorageEnum::AppendableMemmap(v) => v.total_vector_count(),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.total_vector_count(),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.total_vector_count(),
             VectorStorageEnum::SparseSimple(v) => v.total_vector_count(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.total_vector_count(),
         }
     }
 
     fn get_vector(&self, key: PointOffsetType) -> CowVector {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.get_vector(key),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.get_vector(key),
+            VectorStorageEnum::DenseSimpleByte(v) => v.get_vector(key),
             VectorStorageEnum::Memmap(v) => v.get_vector(key),
+            VectorStorageEnum::MemmapHalf(v) => v.get_vector(key),
+            VectorStorageEnum::MemmapByte(v) => v.get_vector(key),
             VectorStorageEnum::AppendableMemmap(v) => v.get_vector(key),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.get_vector(key),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.get_vector(key),
             VectorStorageEnum::SparseSimple(v) => v.get_vector(key),
+            VectorStorageEnum::MultiDenseSimple(v) => v.get_vector(key),
         }
     }
 
     fn get_vector_opt(&self, key: PointOffsetType) -> Option<CowVector> {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseSimpleByte(v) => v.get_vector_opt(key),
             VectorStorageEnum::Memmap(v) => v.get_vector_opt(key),
+            VectorStorageEnum::MemmapHalf(v) => v.get_vector_opt(key),
+            VectorStorageEnum::MemmapByte(v) => v.get_vector_opt(key),
             VectorStorageEnum::AppendableMemmap(v) => v.get_vector_opt(key),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.get_vector_opt(key),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.get_vector_opt(key),
             VectorStorageEnum::SparseSimple(v) => v.get_vector_opt(key),
+            VectorStorageEnum::MultiDenseSimple(v) => v.get_vector_opt(key),
         }
     }
 
     fn insert_vector(&mut self, key: PointOffsetType, vector: VectorRef) -> OperationResult<()> {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseSimpleByte(v) => v.insert_vector(key, vector),
             VectorStorageEnum::Memmap(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::MemmapHalf(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::MemmapByte(v) => v.insert_vector(key, vector),
             VectorStorageEnum::AppendableMemmap(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.insert_vector(key, vector),
             VectorStorageEnum::SparseSimple(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::MultiDenseSimple(v) => v.insert_vector(key, vector),
         }
     }
 
@@ -183,63 +335,128 @@ impl VectorStorage for VectorStorageEnum {
     ) -> OperationResult<Range<PointOffsetType>> {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::DenseSimpleByte(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::Memmap(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::MemmapHalf(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::MemmapByte(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::AppendableMemmap(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::SparseSimple(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::MultiDenseSimple(v) => v.update_from(other, other_ids, stopped),
         }
     }
 
     fn flusher(&self) -> Flusher {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.flusher(),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.flusher(),
+            VectorStorageEnum::DenseSimpleByte(v) => v.flusher(),
             VectorStorageEnum::Memmap(v) => v.flusher(),
+            VectorStorageEnum::MemmapHalf(v) => v.flusher(),
+            VectorStorageEnum::MemmapByte(v) => v.flusher(),
             VectorStorageEnum::AppendableMemmap(v) => v.flusher(),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.flusher(),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.flusher(),
             VectorStorageEnum::SparseSimple(v) => v.flusher(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.flusher(),
         }
     }
 
     fn files(&self) -> Vec<PathBuf> {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.files(),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.files(),
+            VectorStorageEnum::DenseSimpleByte(v) => v.files(),
             VectorStorageEnum::Memmap(v) => v.files(),
+            VectorStorageEnum::MemmapHalf(v) => v.files(),
+            VectorStorageEnum::MemmapByte(v) => v.files(),
             VectorStorageEnum::AppendableMemmap(v) => v.files(),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.files(),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.files(),
             VectorStorageEnum::SparseSimple(v) => v.files(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.files(),
+        }
+    }
+
+    fn immutable_files(&self) -> Vec<PathBuf> {
+        match self {
+            VectorStorageEnum::DenseSimple(v) => v.immutable_files(),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.immutable_files(),
+            VectorStorageEnum::DenseSimpleByte(v) => v.immutable_files(),
+            VectorStorageEnum::Memmap(v) => v.immutable_files(),
+            VectorStorageEnum::MemmapHalf(v) => v.immutable_files(),
+            VectorStorageEnum::MemmapByte(v) => v.immutable_files(),
+            VectorStorageEnum::AppendableMemmap(v) => v.immutable_files(),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.immutable_files(),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.immutable_files(),
+            VectorStorageEnum::SparseSimple(v) => v.immutable_files(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.immutable_files(),
         }
     }
 
     fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseSimpleByte(v) => v.delete_vector(key),
             VectorStorageEnum::Memmap(v) => v.delete_vector(key),
+            VectorStorageEnum::MemmapHalf(v) => v.delete_vector(key),
+            VectorStorageEnum::MemmapByte(v) => v.delete_vector(key),
             VectorStorageEnum::AppendableMemmap(v) => v.delete_vector(key),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.delete_vector(key),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.delete_vector(key),
             VectorStorageEnum::SparseSimple(v) => v.delete_vector(key),
+            VectorStorageEnum::MultiDenseSimple(v) => v.delete_vector(key),
         }
     }
 
     fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseSimpleByte(v) => v.is_deleted_vector(key),
             VectorStorageEnum::Memmap(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::MemmapHalf(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::MemmapByte(v) => v.is_deleted_vector(key),
             VectorStorageEnum::AppendableMemmap(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.is_deleted_vector(key),
             VectorStorageEnum::SparseSimple(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::MultiDenseSimple(v) => v.is_deleted_vector(key),
         }
     }
 
     fn deleted_vector_count(&self) -> usize {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseSimpleByte(v) => v.deleted_vector_count(),
             VectorStorageEnum::Memmap(v) => v.deleted_vector_count(),
+            VectorStorageEnum::MemmapHalf(v) => v.deleted_vector_count(),
+            VectorStorageEnum::MemmapByte(v) => v.deleted_vector_count(),
             VectorStorageEnum::AppendableMemmap(v) => v.deleted_vector_count(),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.deleted_vector_count(),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.deleted_vector_count(),
             VectorStorageEnum::SparseSimple(v) => v.deleted_vector_count(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.deleted_vector_count(),
         }
     }
 
     fn deleted_vector_bitslice(&self) -> &BitSlice {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseSimpleByte(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::Memmap(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::MemmapHalf(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::MemmapByte(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::AppendableMemmap(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::SparseSimple(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.deleted_vector_bitslice(),
         }
     }
 }
@@ -110,10 +110,15 @@ fn _test_filterable_hnsw(
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Plain {},
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
             },
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let int_key = "int";
@@ -143,6 +148,16 @@ fn _test_filterable_hnsw(
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        payload_m_overrides: None,
+        seed: None,
+        extend_candidates: None,
+        keep_pruned_connections: None,
+        compress_links: None,
+        target_recall: None,
+        level_factor_multiplier: None,
+        ef_construct_levels: None,
+        refine: None,
+        appendable: None,
     };
 
     let vector_storage = &segment.vector_data[DEFAULT_VECTOR_NAME].vector_storage;
@@ -120,6 +120,10 @@ pub fn build_segment_3(path: &Path) -> Segment {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
+                        keep_original: None,
+                        dimension_weights: None,
                     },
                 ),
                 (
@@ -130,6 +134,10 @@ pub fn build_segment_3(path: &Path) -> Segment {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
+                        keep_original: None,
+                        dimension_weights: None,
                     },
                 ),
                 (
@@ -140,11 +148,16 @@ pub fn build_segment_3(path: &Path) -> Segment {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
+                        keep_original: None,
+                        dimension_weights: None,
                     },
                 ),
             ]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         },
         true,
     )
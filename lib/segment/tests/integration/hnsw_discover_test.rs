@@ -72,10 +72,15 @@ fn hnsw_discover_precision() {
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Plain {},
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
             },
         )]),
         payload_storage_type: Default::default(),
         sparse_vector_data: Default::default(),
+        payload_compression: None,
     };
 
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
@@ -98,6 +103,16 @@ fn hnsw_discover_precision() {
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        payload_m_overrides: None,
+        seed: None,
+        extend_candidates: None,
+        keep_pruned_connections: None,
+        compress_links: None,
+        target_recall: None,
+        level_factor_multiplier: None,
+        ef_construct_levels: None,
+        refine: None,
+        appendable: None,
     };
 
     let vector_storage = &segment.vector_data[DEFAULT_VECTOR_NAME].vector_storage;
@@ -179,10 +194,15 @@ fn filtered_hnsw_discover_precision() {
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Plain {},
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
             },
         )]),
         payload_storage_type: Default::default(),
         sparse_vector_data: Default::default(),
+        payload_compression: None,
     };
 
     let keyword_key = "keyword";
@@ -212,6 +232,16 @@ fn filtered_hnsw_discover_precision() {
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        payload_m_overrides: None,
+        seed: None,
+        extend_candidates: None,
+        keep_pruned_connections: None,
+        compress_links: None,
+        target_recall: None,
+        level_factor_multiplier: None,
+        ef_construct_levels: None,
+        refine: None,
+        appendable: None,
     };
 
     let vector_storage = &segment.vector_data[DEFAULT_VECTOR_NAME].vector_storage;
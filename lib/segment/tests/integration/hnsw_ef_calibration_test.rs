@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::random_vector;
+use segment::index::hnsw_index::graph_links::GraphLinksRam;
+use segment::index::hnsw_index::hnsw::HNSWIndex;
+use segment::index::VectorIndex;
+use segment::segment::Segment;
+use segment::segment_constructor::build_segment;
+use segment::types::{
+    Distance, HnswConfig, Indexes, SegmentConfig, SeqNumberType, VectorDataConfig,
+    VectorStorageType,
+};
+use tempfile::Builder;
+
+fn build_calibrated_index(
+    segment: &Segment,
+    hnsw_dir: &Path,
+    target_recall: f64,
+) -> (HNSWIndex<GraphLinksRam>, usize) {
+    let hnsw_config = HnswConfig {
+        m: 4,
+        ef_construct: 16,
+        full_scan_threshold: 16,
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+        payload_m_overrides: None,
+        seed: None,
+        extend_candidates: None,
+        keep_pruned_connections: None,
+        compress_links: None,
+        target_recall: Some(target_recall),
+        level_factor_multiplier: None,
+        ef_construct_levels: None,
+        refine: None,
+        appendable: None,
+    };
+
+    let mut hnsw_index = HNSWIndex::<GraphLinksRam>::open(
+        hnsw_dir,
+        segment.id_tracker.clone(),
+        segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        segment.vector_data[DEFAULT_VECTOR_NAME]
+            .quantized_vectors
+            .clone(),
+        segment.payload_index.clone(),
+        hnsw_config,
+    )
+    .unwrap();
+
+    hnsw_index.build_index(&AtomicBool::new(false)).unwrap();
+
+    let config: serde_json::Value =
+        serde_json::from_reader(std::fs::File::open(hnsw_dir.join("hnsw_config.json")).unwrap())
+            .unwrap();
+    let ef = config["ef"].as_u64().unwrap() as usize;
+
+    (hnsw_index, ef)
+}
+
+#[test]
+fn test_hnsw_ef_calibration() {
+    let dim = 32;
+    let num_vectors: u64 = 2_000;
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Dot,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+        payload_compression: None,
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    for n in 0..num_vectors {
+        let vector = random_vector(&mut rnd, dim);
+        segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+
+    // Lowest possible ef satisfies any recall
+    let low_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+    let (_, low_ef) = build_calibrated_index(&segment, low_dir.path(), 0.0);
+    assert_eq!(low_ef, 10);
+
+    let high_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+    let (hnsw_index, high_ef) = build_calibrated_index(&segment, high_dir.path(), 1.0);
+    assert!(high_ef >= low_ef);
+
+    // Search with default params uses calibrated ef
+    let top = 10;
+    let attempts = 20;
+    let mut found = 0;
+    for _ in 0..attempts {
+        let query = random_vector(&mut rnd, dim).into();
+
+        let index_result = hnsw_index
+            .search(&[&query], None, top, None, &false.into())
+            .unwrap();
+        let plain_result = segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_index
+            .borrow()
+            .search(&[&query], None, top, None, &false.into())
+            .unwrap();
+
+        found += index_result[0]
+            .iter()
+            .filter(|scored| plain_result[0].iter().any(|x| x.idx == scored.idx))
+            .count();
+    }
+    let recall = found as f64 / (attempts * top) as f64;
+    assert!(
+        recall >= 0.9,
+        "recall {recall} is too low for calibrated ef {high_ef}"
+    );
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+
+use rand::prelude::StdRng;
+use rand::{Rng, SeedableRng};
+use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::vectors::{MultiDenseVector, QueryVector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::random_vector;
+use segment::index::hnsw_index::graph_links::GraphLinksRam;
+use segment::index::hnsw_index::hnsw::HNSWIndex;
+use segment::index::VectorIndex;
+use segment::segment_constructor::build_segment;
+use segment::types::{
+    Distance, HnswConfig, Indexes, MultiVectorConfig, SearchParams, SegmentConfig, SeqNumberType,
+    VectorDataConfig, VectorStorageType,
+};
+use tempfile::Builder;
+
+const MAX_VECTORS_PER_POINT: usize = 4;
+
+fn random_multi_vector<R: Rng + ?Sized>(rnd: &mut R, dim: usize) -> MultiDenseVector {
+    let count = rnd.gen_range(1..=MAX_VECTORS_PER_POINT);
+    let matrix = (0..count).map(|_| random_vector(rnd, dim)).collect();
+    MultiDenseVector::try_from_matrix(matrix).unwrap()
+}
+
+/// Checks that HNSW graph built with MaxSim scoring over multivectors finds the same top
+/// as the exhaustive search
+#[test]
+fn hnsw_multivector_precision() {
+    let stopped = AtomicBool::new(false);
+
+    let max_failures = 5; // out of 100
+    let dim = 8;
+    let m = 16;
+    let num_vectors: u64 = 2_000;
+    let ef = 64;
+    let ef_construct = 64;
+    let distance = Distance::Cosine;
+    let full_scan_threshold = 16; // KB
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: Some(MultiVectorConfig::default()),
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
+            },
+        )]),
+        payload_storage_type: Default::default(),
+        sparse_vector_data: Default::default(),
+        payload_compression: None,
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+
+    for n in 0..num_vectors {
+        let idx = n.into();
+        let vector = random_multi_vector(&mut rnd, dim);
+
+        segment
+            .upsert_point(
+                n as SeqNumberType,
+                idx,
+                NamedVectors::from_ref(DEFAULT_VECTOR_NAME, (&vector).into()),
+            )
+            .unwrap();
+    }
+
+    let hnsw_config = HnswConfig {
+        m,
+        ef_construct,
+        full_scan_threshold,
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+        payload_m_overrides: None,
+        seed: None,
+        extend_candidates: None,
+        keep_pruned_connections: None,
+        compress_links: None,
+        target_recall: None,
+        level_factor_multiplier: None,
+        ef_construct_levels: None,
+        refine: None,
+        appendable: None,
+    };
+
+    let vector_storage = &segment.vector_data[DEFAULT_VECTOR_NAME].vector_storage;
+    let quantized_vectors = &segment.vector_data[DEFAULT_VECTOR_NAME].quantized_vectors;
+    let mut hnsw_index = HNSWIndex::<GraphLinksRam>::open(
+        hnsw_dir.path(),
+        segment.id_tracker.clone(),
+        vector_storage.clone(),
+        quantized_vectors.clone(),
+        segment.payload_index.clone(),
+        hnsw_config,
+    )
+    .unwrap();
+
+    hnsw_index.build_index(&stopped).unwrap();
+
+    let top = 3;
+    let mut hits = 0;
+    let attempts = 100;
+    for _i in 0..attempts {
+        let query: QueryVector = random_multi_vector(&mut rnd, dim).into();
+
+        let index_result = hnsw_index
+            .search(
+                &[&query],
+                None,
+                top,
+                Some(&SearchParams {
+                    hnsw_ef: Some(ef),
+                    ..Default::default()
+                }),
+                &false.into(),
+            )
+            .unwrap();
+
+        let plain_result = segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_index
+            .borrow()
+            .search(&[&query], None, top, None, &false.into())
+            .unwrap();
+
+        if plain_result == index_result {
+            hits += 1;
+        }
+    }
+    eprintln!("hits = {hits:#?} out of {attempts}");
+    assert!(
+        attempts - hits <= max_failures,
+        "hits: {hits} of {attempts}"
+    ); // Not more than X% failures
+}
@@ -68,10 +68,15 @@ fn hnsw_quantized_search_test(
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Plain {},
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
             },
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
@@ -119,6 +124,16 @@ fn hnsw_quantized_search_test(
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        payload_m_overrides: None,
+        seed: None,
+        extend_candidates: None,
+        keep_pruned_connections: None,
+        compress_links: None,
+        target_recall: None,
+        level_factor_multiplier: None,
+        ef_construct_levels: None,
+        refine: None,
+        appendable: None,
     };
 
     let mut hnsw_index = HNSWIndex::<GraphLinksRam>::open(
@@ -412,6 +427,16 @@ fn test_build_hnsw_using_quantization() {
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        payload_m_overrides: None,
+        seed: None,
+        extend_candidates: None,
+        keep_pruned_connections: None,
+        compress_links: None,
+        target_recall: None,
+        level_factor_multiplier: None,
+        ef_construct_levels: None,
+        refine: None,
+        appendable: None,
     });
 
     let mut builder = SegmentBuilder::new(dir.path(), temp_dir.path(), &config).unwrap();
@@ -8,6 +8,8 @@ pub mod filtering_context_check;
 pub mod filtrable_hnsw_test;
 pub mod fixtures;
 pub mod hnsw_discover_test;
+pub mod hnsw_ef_calibration_test;
+pub mod hnsw_multivector_test;
 pub mod hnsw_quantized_search_test;
 pub mod nested_filtering_test;
 pub mod payload_index_test;
@@ -17,3 +19,4 @@ pub mod segment_tests;
 mod sparse_discover_test;
 mod sparse_vector_index_search_tests;
 pub mod utils;
+pub mod vamana_filtering_test;
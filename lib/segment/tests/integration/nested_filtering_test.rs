@@ -70,8 +70,14 @@ fn test_filtering_context_consistency() {
     let wrapped_payload_storage = Arc::new(AtomicRefCell::new(payload_storage.into()));
     let id_tracker = Arc::new(AtomicRefCell::new(FixtureIdTracker::new(NUM_POINTS)));
 
-    let mut index =
-        StructPayloadIndex::open(wrapped_payload_storage, id_tracker, dir.path(), true).unwrap();
+    let mut index = StructPayloadIndex::open(
+        wrapped_payload_storage,
+        id_tracker,
+        HashMap::new(),
+        dir.path(),
+        true,
+    )
+    .unwrap();
 
     index
         .set_indexed("f", PayloadSchemaType::Integer.into())
@@ -102,6 +108,7 @@ fn test_filtering_context_consistency() {
                     Condition::Field(FieldCondition::new_match("c", 1.into())),
                 ]),
                 should: None,
+                min_should: None,
                 must_not: Some(vec![Condition::Field(FieldCondition::new_range(
                     "d",
                     Range {
@@ -144,6 +151,7 @@ fn test_filtering_context_consistency() {
                     Condition::Field(FieldCondition::new_match("d", 0.into())),
                 ]),
                 should: None,
+                min_should: None,
                 must_not: None,
             },
         );
@@ -178,6 +186,7 @@ fn test_filtering_context_consistency() {
                     Condition::Field(FieldCondition::new_match("d", 0.into())),
                 ]),
                 should: None,
+                min_should: None,
                 must_not: None,
             },
         );
@@ -206,6 +215,7 @@ fn test_filtering_context_consistency() {
                     1.into(),
                 ))]),
                 should: None,
+                min_should: None,
                 must_not: None,
             },
         );
@@ -221,10 +231,12 @@ fn test_filtering_context_consistency() {
                             10.into(),
                         ))]),
                         should: None,
+                        min_should: None,
                         must_not: None,
                     },
                 )]),
                 should: None,
+                min_should: None,
                 must_not: None,
             },
         );
@@ -232,6 +244,7 @@ fn test_filtering_context_consistency() {
         let nested_filter_3 = Filter {
             must: Some(vec![nested_condition_3, nester_condition_3_1]),
             should: None,
+            min_should: None,
             must_not: None,
         };
 
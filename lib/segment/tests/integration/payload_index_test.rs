@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::Arc;
 
@@ -7,6 +7,7 @@ use common::types::PointOffsetType;
 use itertools::Itertools;
 use rand::prelude::StdRng;
 use rand::{Rng, SeedableRng};
+use segment::data_types::order_by::{Direction, OrderBy, StartFrom};
 use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
 use segment::entry::entry_point::SegmentEntry;
 use segment::fixtures::payload_context_fixture::FixtureIdTracker;
@@ -49,10 +50,15 @@ fn build_test_segments(path_struct: &Path, path_plain: &Path) -> (Segment, Segme
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Plain {},
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
             },
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let mut plain_segment = build_segment(path_plain, &config, true).unwrap();
@@ -154,10 +160,15 @@ fn build_test_segments_nested_payload(path_struct: &Path, path_plain: &Path) ->
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Plain {},
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
             },
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let mut plain_segment = build_segment(path_plain, &config, true).unwrap();
@@ -767,8 +778,14 @@ fn test_update_payload_index_type() {
     let wrapped_payload_storage = Arc::new(AtomicRefCell::new(payload_storage.into()));
     let id_tracker = Arc::new(AtomicRefCell::new(FixtureIdTracker::new(point_num)));
 
-    let mut index =
-        StructPayloadIndex::open(wrapped_payload_storage, id_tracker, dir.path(), true).unwrap();
+    let mut index = StructPayloadIndex::open(
+        wrapped_payload_storage,
+        id_tracker,
+        HashMap::new(),
+        dir.path(),
+        true,
+    )
+    .unwrap();
 
     // set field to Integer type
     index.set_indexed("field", Integer.into()).unwrap();
@@ -800,6 +817,55 @@ fn test_update_payload_index_type() {
     assert_eq!(field_index[1].count_indexed_points(), point_num);
 }
 
+#[test]
+fn test_filter_cache_invalidated_on_index_change() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    let mut payload_storage = InMemoryPayloadStorage::default();
+
+    let point_num = 5;
+    for idx in 0..point_num {
+        let payload: Payload = json!({ "text": "Hello World" }).into();
+        payload_storage
+            .assign(idx as PointOffsetType, &payload)
+            .unwrap();
+    }
+
+    let wrapped_payload_storage = Arc::new(AtomicRefCell::new(payload_storage.into()));
+    let id_tracker = Arc::new(AtomicRefCell::new(FixtureIdTracker::new(point_num)));
+
+    let mut index = StructPayloadIndex::open(
+        wrapped_payload_storage,
+        id_tracker,
+        HashMap::new(),
+        dir.path(),
+        true,
+    )
+    .unwrap();
+
+    // Plain check is case-sensitive, while the full-text index lowercases tokens
+    let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+        "text",
+        Match::Text("hello".to_string().into()),
+    )));
+
+    // Query repeatedly, so that the result gets cached
+    for _ in 0..4 {
+        assert!(index.query_points(&filter).is_empty());
+    }
+
+    index
+        .set_indexed("text", PayloadSchemaType::Text.into())
+        .unwrap();
+    for _ in 0..4 {
+        assert_eq!(index.query_points(&filter).len(), point_num);
+    }
+
+    index.drop_index("text").unwrap();
+    for _ in 0..4 {
+        assert!(index.query_points(&filter).is_empty());
+    }
+}
+
 #[test]
 fn test_any_matcher_cardinality_estimation() {
     let dir1 = Builder::new().prefix("segment1_dir").tempdir().unwrap();
@@ -850,3 +916,53 @@ fn test_any_matcher_cardinality_estimation() {
     assert!(exact <= estimation.max);
     assert!(exact >= estimation.min);
 }
+
+#[test]
+fn test_read_ordered_by_distance_pages() {
+    let dir1 = Builder::new().prefix("segment1_dir").tempdir().unwrap();
+    let dir2 = Builder::new().prefix("segment2_dir").tempdir().unwrap();
+    let (struct_segment, _) = build_test_segments(dir1.path(), dir2.path());
+
+    let page_size = 50;
+    let origin = GeoPoint {
+        lon: 12.5,
+        lat: 41.9,
+    };
+
+    for direction in [Direction::Asc, Direction::Desc] {
+        let order_by = |start_from| OrderBy {
+            key: GEO_KEY.to_string(),
+            direction: Some(direction),
+            start_from,
+            distance_from: Some(origin.clone()),
+        };
+
+        // Reading all points takes multiple passes over the geo index
+        let all_points = struct_segment
+            .read_ordered_filtered(None, None, &order_by(None))
+            .unwrap();
+        assert!(all_points.len() > 1000);
+        assert!(all_points
+            .windows(2)
+            .all(|pair| direction.cmp_values(pair[0].0, pair[1].0).is_le()));
+        assert_eq!(
+            all_points.iter().map(|(_, id)| id).unique().count(),
+            all_points.len(),
+        );
+
+        // Next page starts from the last distance of the previous one, points with multiple
+        // geo values are returned again with their other distances and are skipped
+        let mut paged_points = vec![];
+        let mut seen = HashSet::new();
+        let mut start_from = None;
+        while paged_points.len() < all_points.len() {
+            let page = struct_segment
+                .read_ordered_filtered(Some(page_size), None, &order_by(start_from))
+                .unwrap();
+            assert!(!page.is_empty());
+            start_from = Some(StartFrom::Float(page.last().unwrap().0));
+            paged_points.extend(page.into_iter().filter(|(_, id)| seen.insert(*id)));
+        }
+        assert_eq!(paged_points, all_points);
+    }
+}
@@ -4,12 +4,21 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use segment::common::checksums::SegmentChecksums;
 use segment::common::operation_error::OperationError;
 use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
 use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::random_vector;
 use segment::segment::Segment;
 use segment::segment_constructor::segment_builder::SegmentBuilder;
-use segment::types::{Indexes, SegmentConfig, VectorDataConfig, VectorStorageType};
+use segment::segment_constructor::{build_segment, load_segment};
+use segment::types::{
+    Distance, HnswConfig, Indexes, SearchParams, SegmentConfig, SeqNumberType, VectorDataConfig,
+    VectorStorageType,
+};
+use segment::vector_storage::VectorStorage;
 use tempfile::Builder;
 
 use crate::fixtures::segment::{build_segment_1, build_segment_2, empty_segment};
@@ -85,10 +94,15 @@ fn estimate_build_time(segment: &Segment, stop_delay_millis: u64) -> (u64, bool)
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Hnsw(Default::default()),
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
             },
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let mut builder = SegmentBuilder::new(dir.path(), temp_dir.path(), &segment_config).unwrap();
@@ -186,3 +200,321 @@ fn test_building_cancellation() {
         was_cancelled_later,
     );
 }
+
+#[test]
+fn test_rebuild_with_previous_graph() {
+    let dim = 16;
+    let num_vectors: u64 = 1_000;
+    let num_deleted: u64 = 50;
+    let stopped = AtomicBool::new(false);
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let segment_config = |index| SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Cosine,
+                storage_type: VectorStorageType::Memory,
+                index,
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+        payload_compression: None,
+    };
+    let hnsw_config = segment_config(Indexes::Hnsw(HnswConfig {
+        m: 8,
+        ef_construct: 32,
+        full_scan_threshold: 1,
+        ..Default::default()
+    }));
+
+    let mut plain_segment =
+        build_segment(dir.path(), &segment_config(Indexes::Plain {}), true).unwrap();
+    for n in 0..num_vectors {
+        let vector = random_vector(&mut rnd, dim);
+        plain_segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+
+    let mut builder = SegmentBuilder::new(dir.path(), temp_dir.path(), &hnsw_config).unwrap();
+    builder.update_from(&plain_segment, &stopped).unwrap();
+    let mut indexed_segment = builder.build(&stopped).unwrap();
+
+    for n in 0..num_deleted {
+        indexed_segment
+            .delete_point(num_vectors + n, (n * 7).into())
+            .unwrap();
+    }
+
+    // Most of the points are unchanged, so the graph of the indexed segment is extended
+    let mut builder = SegmentBuilder::new(dir.path(), temp_dir.path(), &hnsw_config).unwrap();
+    builder.update_from(&indexed_segment, &stopped).unwrap();
+    let rebuilt_segment = builder.build(&stopped).unwrap();
+
+    assert_eq!(
+        rebuilt_segment.available_point_count(),
+        (num_vectors - num_deleted) as usize,
+    );
+
+    for point_id in rebuilt_segment.iter_points().take(20) {
+        let vector = rebuilt_segment
+            .vector(DEFAULT_VECTOR_NAME, point_id)
+            .unwrap()
+            .unwrap();
+        let exact = rebuilt_segment
+            .search(
+                DEFAULT_VECTOR_NAME,
+                &vector.clone().into(),
+                &false.into(),
+                &false.into(),
+                None,
+                1,
+                Some(&SearchParams {
+                    exact: true,
+                    ..Default::default()
+                }),
+                &false.into(),
+            )
+            .unwrap();
+        let found = rebuilt_segment
+            .search(
+                DEFAULT_VECTOR_NAME,
+                &vector.into(),
+                &false.into(),
+                &false.into(),
+                None,
+                1,
+                Some(&SearchParams {
+                    hnsw_ef: Some(64),
+                    ..Default::default()
+                }),
+                &false.into(),
+            )
+            .unwrap();
+        assert_eq!(found[0].id, exact[0].id);
+    }
+}
+
+#[test]
+fn test_upsert_into_appendable_hnsw_segment() {
+    let dim = 16;
+    let num_vectors: u64 = 500;
+    let num_appended: u64 = 20;
+    let stopped = AtomicBool::new(false);
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let segment_config = |index| SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Euclid,
+                storage_type: VectorStorageType::Memory,
+                index,
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+        payload_compression: None,
+    };
+    let hnsw_config = segment_config(Indexes::Hnsw(HnswConfig {
+        m: 8,
+        ef_construct: 32,
+        // Always search with the graph
+        full_scan_threshold: 0,
+        appendable: Some(true),
+        ..Default::default()
+    }));
+
+    let mut plain_segment =
+        build_segment(dir.path(), &segment_config(Indexes::Plain {}), true).unwrap();
+    for n in 0..num_vectors {
+        let vector = random_vector(&mut rnd, dim);
+        plain_segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+
+    let mut builder = SegmentBuilder::new(dir.path(), temp_dir.path(), &hnsw_config).unwrap();
+    builder.update_from(&plain_segment, &stopped).unwrap();
+    let mut indexed_segment = builder.build(&stopped).unwrap();
+    assert!(indexed_segment.is_appendable());
+
+    let search_params = SearchParams {
+        hnsw_ef: Some(64),
+        ..Default::default()
+    };
+    let search_top = |segment: &Segment, vector: &[f32]| {
+        segment
+            .search(
+                DEFAULT_VECTOR_NAME,
+                &vector.to_vec().into(),
+                &false.into(),
+                &false.into(),
+                None,
+                1,
+                Some(&search_params),
+                &false.into(),
+            )
+            .unwrap()[0]
+            .id
+    };
+
+    let appended_vectors: Vec<_> = (0..num_appended)
+        .map(|_| random_vector(&mut rnd, dim))
+        .collect();
+    for (n, vector) in appended_vectors.iter().enumerate() {
+        let point_id = num_vectors + n as u64;
+        indexed_segment
+            .upsert_point(point_id, point_id.into(), only_default_vector(vector))
+            .unwrap();
+    }
+
+    // New points are linked into the graph, which is the only way to find them
+    let indexed_vector_count = indexed_segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_index
+        .borrow()
+        .indexed_vector_count();
+    assert_eq!(indexed_vector_count, (num_vectors + num_appended) as usize);
+    for (n, vector) in appended_vectors.iter().enumerate() {
+        let point_id = num_vectors + n as u64;
+        assert_eq!(search_top(&indexed_segment, vector), point_id.into());
+    }
+
+    // Vectors of existing points are re-linked in place
+    let updated_vector = random_vector(&mut rnd, dim);
+    indexed_segment
+        .upsert_point(
+            num_vectors + num_appended,
+            0.into(),
+            only_default_vector(&updated_vector),
+        )
+        .unwrap();
+    assert_eq!(search_top(&indexed_segment, &updated_vector), 0.into());
+
+    // Appended points are linked again, once the segment is loaded
+    indexed_segment.flush(true).unwrap();
+    let segment_path = indexed_segment.current_path.clone();
+    drop(indexed_segment);
+    let loaded_segment = load_segment(&segment_path).unwrap().unwrap();
+    for (n, vector) in appended_vectors.iter().enumerate() {
+        let point_id = num_vectors + n as u64;
+        assert_eq!(search_top(&loaded_segment, vector), point_id.into());
+    }
+}
+
+#[test]
+fn test_corrupted_segment_rejects_updates() {
+    let dim = 4;
+    let num_vectors: u64 = 100;
+    let stopped = AtomicBool::new(false);
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let segment_config = |storage_type| SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Dot,
+                storage_type,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+        payload_compression: None,
+    };
+
+    let mut plain_segment =
+        build_segment(dir.path(), &segment_config(VectorStorageType::Memory), true).unwrap();
+    for n in 0..num_vectors {
+        let vector = random_vector(&mut rnd, dim);
+        plain_segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+
+    let mut builder = SegmentBuilder::new(
+        dir.path(),
+        temp_dir.path(),
+        &segment_config(VectorStorageType::Mmap),
+    )
+    .unwrap();
+    builder.update_from(&plain_segment, &stopped).unwrap();
+    let mut segment = builder.build(&stopped).unwrap();
+    assert!(!segment.is_appendable());
+
+    // Checksums of immutable files are saved once the segment is built
+    assert!(SegmentChecksums::check_exists(&segment.current_path));
+    segment.verify_checksums().unwrap();
+    assert!(segment.check_error().is_none());
+
+    let vectors_path = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_storage
+        .borrow()
+        .immutable_files()[0]
+        .clone();
+    let segment_path = segment.current_path.clone();
+    drop(segment);
+
+    let mut data = std::fs::read(&vectors_path).unwrap();
+    let last = data.len() - 1;
+    data[last] ^= 0xFF;
+    std::fs::write(&vectors_path, data).unwrap();
+
+    let mut segment = load_segment(&segment_path).unwrap().unwrap();
+    segment.verify_checksums_in_background();
+
+    // Corruption is reported once the background verification is finished
+    let start = Instant::now();
+    let failed_state = loop {
+        if let Some(failed_state) = segment.check_error() {
+            break failed_state;
+        }
+        assert!(start.elapsed() < Duration::from_secs(10));
+        std::thread::sleep(Duration::from_millis(10));
+    };
+    assert!(matches!(
+        failed_state.error,
+        OperationError::DataCorruption { .. },
+    ));
+
+    let vector = random_vector(&mut rnd, dim);
+    let result = segment.upsert_point(
+        num_vectors + 1,
+        num_vectors.into(),
+        only_default_vector(&vector),
+    );
+    assert!(matches!(result, Err(OperationError::DataCorruption { .. })));
+    assert!(matches!(
+        segment.delete_point(num_vectors + 2, 0.into()),
+        Err(OperationError::DataCorruption { .. }),
+    ));
+}
@@ -46,6 +46,7 @@ fn test_point_exclusion() {
 
     let frt = Filter {
         should: None,
+        min_should: None,
         must: None,
         must_not: Some(vec![Condition::HasId(ids.into())]),
     };
@@ -105,6 +106,7 @@ fn test_named_vector_search() {
 
     let frt = Filter {
         should: None,
+        min_should: None,
         must: None,
         must_not: Some(vec![Condition::HasId(ids.into())]),
     };
@@ -263,9 +265,12 @@ fn test_update_named_vector() {
     // do exact search
     let search_params = SearchParams {
         hnsw_ef: None,
+        ivf_nprobe: None,
         exact: true,
         quantization: None,
         indexed_only: false,
+        acorn: false,
+        score_threshold: None,
     };
     let nearest_upsert = segment
         .search(
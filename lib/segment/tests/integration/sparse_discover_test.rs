@@ -120,10 +120,12 @@ fn sparse_index_discover_test() {
                 index: SparseIndexConfig {
                     full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
                     index_type: SparseIndexType::MutableRam,
+                    datatype: None,
                 },
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
     let dense_config = SegmentConfig {
         vector_data: HashMap::from([(
@@ -134,10 +136,15 @@ fn sparse_index_discover_test() {
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Plain {},
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
             },
         )]),
         payload_storage_type: Default::default(),
         sparse_vector_data: Default::default(),
+        payload_compression: None,
     };
 
     let mut sparse_segment = build_segment(dir.path(), &sparse_config, true).unwrap();
@@ -162,6 +169,7 @@ fn sparse_index_discover_test() {
         SparseIndexConfig {
             full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
             index_type: SparseIndexType::ImmutableRam,
+            datatype: None,
         },
         sparse_segment.id_tracker.clone(),
         vector_storage.clone(),
@@ -539,10 +539,12 @@ fn sparse_vector_index_persistence_test() {
                 index: SparseIndexConfig {
                     full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
                     index_type: SparseIndexType::MutableRam,
+                    datatype: None,
                 },
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -607,6 +609,7 @@ fn sparse_vector_index_persistence_test() {
         SparseIndexConfig {
             full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
             index_type: SparseIndexType::ImmutableRam,
+            datatype: None,
         },
         segment.id_tracker.clone(),
         segment.vector_data[SPARSE_VECTOR_NAME]
@@ -625,6 +628,7 @@ fn sparse_vector_index_persistence_test() {
         SparseIndexConfig {
             full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
             index_type: SparseIndexType::ImmutableRam,
+            datatype: None,
         },
         segment.id_tracker.clone(),
         segment.vector_data[SPARSE_VECTOR_NAME]
@@ -663,6 +667,7 @@ fn sparse_vector_index_persistence_test() {
             SparseIndexConfig {
                 full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
                 index_type: SparseIndexType::Mmap,
+                datatype: None,
             },
             segment.id_tracker.clone(),
             segment.vector_data[SPARSE_VECTOR_NAME]
@@ -681,6 +686,7 @@ fn sparse_vector_index_persistence_test() {
         SparseIndexConfig {
             full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
             index_type: SparseIndexType::Mmap,
+            datatype: None,
         },
         segment.id_tracker.clone(),
         segment.vector_data[SPARSE_VECTOR_NAME]
@@ -788,10 +794,12 @@ fn sparse_vector_test_large_index() {
                 index: SparseIndexConfig {
                     full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
                     index_type: SparseIndexType::MutableRam,
+                    datatype: None,
                 },
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
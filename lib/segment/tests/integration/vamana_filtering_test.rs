@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+use segment::data_types::vectors::{only_default_vector, QueryVector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::random_vector;
+use segment::index::vamana_index::vamana::VamanaIndex;
+use segment::index::VectorIndex;
+use segment::segment_constructor::build_segment;
+use segment::types::{
+    Condition, Distance, FieldCondition, Filter, Indexes, Payload, Range, SearchParams,
+    SegmentConfig, SeqNumberType, VamanaConfig, VectorDataConfig, VectorStorageType,
+};
+use serde_json::json;
+use tempfile::Builder;
+
+#[test]
+fn test_vamana_search_with_selective_filter() {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 8;
+    let num_vectors: u64 = 2_000;
+    let num_matching = 20;
+    let top = 10;
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let vamana_dir = Builder::new().prefix("vamana_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Cosine,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+                keep_original: None,
+                dimension_weights: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+        payload_compression: None,
+    };
+
+    let int_key = "int";
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    for n in 0..num_vectors {
+        let idx = n.into();
+        let vector = random_vector(&mut rnd, dim);
+        let payload: Payload = json!({ int_key: n }).into();
+
+        segment
+            .upsert_point(n as SeqNumberType, idx, only_default_vector(&vector))
+            .unwrap();
+        segment
+            .set_full_payload(n as SeqNumberType, idx, &payload)
+            .unwrap();
+    }
+
+    let vamana_config = VamanaConfig {
+        max_degree: 16,
+        build_list_size: 32,
+        search_list_size: 16,
+        // Always search with the graph, regardless of the filter cardinality
+        full_scan_threshold: 0,
+    };
+
+    let vector_storage = &segment.vector_data[DEFAULT_VECTOR_NAME].vector_storage;
+    let mut vamana_index = VamanaIndex::open(
+        vamana_dir.path(),
+        segment.id_tracker.clone(),
+        vector_storage.clone(),
+        segment.payload_index.clone(),
+        vamana_config,
+    )
+    .unwrap();
+    vamana_index.build_index(&stopped).unwrap();
+
+    // Only 1% of points match, which are unlikely to be met while traversing the graph
+    let filter = Filter::new_must(Condition::Field(FieldCondition::new_range(
+        int_key.to_owned(),
+        Range {
+            lt: Some(num_matching as f64),
+            ..Default::default()
+        },
+    )));
+    let exact_params = SearchParams {
+        exact: true,
+        ..Default::default()
+    };
+
+    for _ in 0..10 {
+        let query: QueryVector = random_vector(&mut rnd, dim).into();
+
+        let result = vamana_index
+            .search(&[&query], Some(&filter), top, None, &stopped)
+            .unwrap();
+        let exact_result = vamana_index
+            .search(&[&query], Some(&filter), top, Some(&exact_params), &stopped)
+            .unwrap();
+
+        assert_eq!(result[0].len(), top);
+        assert!(result[0]
+            .iter()
+            .all(|scored| exact_result[0].iter().any(|exact| exact.idx == scored.idx)));
+    }
+}
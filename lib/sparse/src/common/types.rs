@@ -1,2 +1,17 @@
+use serde::{Deserialize, Serialize};
+
 pub type DimId = u32;
 pub type DimWeight = f32;
+
+/// Type, in which weights of posting lists are stored on disk
+#[derive(Debug, Default, Deserialize, Serialize, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WeightType {
+    /// Full precision, posting lists are read without conversion
+    #[default]
+    Float32,
+    /// Half precision
+    Float16,
+    /// Linearly quantized into 256 levels between min and max weight of each posting list
+    Uint8,
+}
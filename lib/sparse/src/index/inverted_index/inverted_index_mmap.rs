@@ -1,8 +1,10 @@
+use std::borrow::Cow;
 use std::mem::size_of;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
+use half::f16;
 use io::file_operations::{atomic_save_json, read_json};
 use memmap2::{Mmap, MmapMut};
 use memory::madvise;
@@ -13,19 +15,25 @@ use memory::mmap_ops::{
 use serde::{Deserialize, Serialize};
 
 use crate::common::sparse_vector::SparseVector;
-use crate::common::types::DimId;
+use crate::common::types::{DimId, DimWeight, WeightType};
 use crate::index::inverted_index::inverted_index_ram::InvertedIndexRam;
 use crate::index::inverted_index::InvertedIndex;
-use crate::index::posting_list::{PostingElement, PostingListIterator};
+use crate::index::posting_list::{compute_max_next_weight, PostingElement, PostingListIterator};
 
 const POSTING_HEADER_SIZE: usize = size_of::<PostingListFileHeader>();
 const INDEX_FILE_NAME: &str = "inverted_index.data";
 const INDEX_CONFIG_FILE_NAME: &str = "inverted_index_config.json";
+/// Non-empty posting lists with `Uint8` weights start with their min weight and quantization step
+const UINT8_PARAMS_SIZE: usize = 2 * size_of::<DimWeight>();
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct InvertedIndexFileHeader {
     pub posting_count: usize, // number oof posting lists
     pub vector_count: usize,  // number of unique vectors indexed
+    /// Type of stored weights. Reduced precision posting lists don't store `max_next_weight`,
+    /// it is restored when the posting list is read
+    #[serde(default)]
+    pub weight_type: WeightType,
 }
 
 /// Inverted flatten index from dimension id to posting list
@@ -52,7 +60,7 @@ impl InvertedIndex for InvertedIndexMmap {
     }
 
     fn get(&self, id: &DimId) -> Option<PostingListIterator> {
-        self.get(id).map(PostingListIterator::new)
+        self.get(id).map(PostingListIterator::from_cow)
     }
 
     fn files(path: &Path) -> Vec<PathBuf> {
@@ -73,6 +81,14 @@ impl InvertedIndex for InvertedIndexMmap {
         Self::convert_and_save(&ram_index, path)
     }
 
+    fn from_ram_index_with_weight_type<P: AsRef<Path>>(
+        ram_index: InvertedIndexRam,
+        path: P,
+        weight_type: WeightType,
+    ) -> std::io::Result<Self> {
+        Self::convert_and_save_with_weight_type(&ram_index, path, weight_type)
+    }
+
     fn vector_count(&self) -> usize {
         self.file_header.vector_count
     }
@@ -94,7 +110,7 @@ impl InvertedIndexMmap {
         path.join(INDEX_CONFIG_FILE_NAME)
     }
 
-    pub fn get(&self, id: &DimId) -> Option<&[PostingElement]> {
+    pub fn get(&self, id: &DimId) -> Option<Cow<[PostingElement]>> {
         // check that the id is not out of bounds (posting_count includes the empty zeroth entry)
         if *id >= self.file_header.posting_count as DimId {
             return None;
@@ -105,15 +121,27 @@ impl InvertedIndexMmap {
         )
         .clone();
         let elements_bytes = &self.mmap[header.start_offset as usize..header.end_offset as usize];
-        Some(transmute_from_u8_to_slice(elements_bytes))
+        match self.file_header.weight_type {
+            WeightType::Float32 => Some(Cow::Borrowed(transmute_from_u8_to_slice(elements_bytes))),
+            weight_type => Some(Cow::Owned(decode_posting_list(elements_bytes, weight_type))),
+        }
     }
 
     pub fn convert_and_save<P: AsRef<Path>>(
         inverted_index_ram: &InvertedIndexRam,
         path: P,
+    ) -> std::io::Result<Self> {
+        Self::convert_and_save_with_weight_type(inverted_index_ram, path, WeightType::Float32)
+    }
+
+    pub fn convert_and_save_with_weight_type<P: AsRef<Path>>(
+        inverted_index_ram: &InvertedIndexRam,
+        path: P,
+        weight_type: WeightType,
     ) -> std::io::Result<Self> {
         let total_posting_headers_size = Self::total_posting_headers_size(inverted_index_ram);
-        let total_posting_elements_size = Self::total_posting_elements_size(inverted_index_ram);
+        let total_posting_elements_size =
+            Self::total_posting_elements_size(inverted_index_ram, weight_type);
 
         let file_length = total_posting_headers_size + total_posting_elements_size;
         let file_path = Self::index_file_path(path.as_ref());
@@ -123,8 +151,18 @@ impl InvertedIndexMmap {
         madvise::madvise(&mmap, madvise::Advice::Normal)?;
 
         // file index data
-        Self::save_posting_headers(&mut mmap, inverted_index_ram, total_posting_headers_size);
-        Self::save_posting_elements(&mut mmap, inverted_index_ram, total_posting_headers_size);
+        Self::save_posting_headers(
+            &mut mmap,
+            inverted_index_ram,
+            total_posting_headers_size,
+            weight_type,
+        );
+        Self::save_posting_elements(
+            &mut mmap,
+            inverted_index_ram,
+            total_posting_headers_size,
+            weight_type,
+        );
         if file_length > 0 {
             mmap.flush()?;
         }
@@ -137,6 +175,7 @@ impl InvertedIndexMmap {
         let file_header = InvertedIndexFileHeader {
             posting_count,
             vector_count,
+            weight_type,
         };
         let config_file_path = Self::index_config_file_path(path.as_ref());
         atomic_save_json(&config_file_path, &file_header)?;
@@ -168,10 +207,13 @@ impl InvertedIndexMmap {
         inverted_index_ram.postings.len() * POSTING_HEADER_SIZE
     }
 
-    fn total_posting_elements_size(inverted_index_ram: &InvertedIndexRam) -> usize {
+    fn total_posting_elements_size(
+        inverted_index_ram: &InvertedIndexRam,
+        weight_type: WeightType,
+    ) -> usize {
         let mut total_posting_elements_size = 0;
         for posting in &inverted_index_ram.postings {
-            total_posting_elements_size += posting.elements.len() * size_of::<PostingElement>();
+            total_posting_elements_size += posting_list_size(&posting.elements, weight_type);
         }
 
         total_posting_elements_size
@@ -181,10 +223,11 @@ impl InvertedIndexMmap {
         mmap: &mut MmapMut,
         inverted_index_ram: &InvertedIndexRam,
         total_posting_headers_size: usize,
+        weight_type: WeightType,
     ) {
         let mut elements_offset: usize = total_posting_headers_size;
         for (id, posting) in inverted_index_ram.postings.iter().enumerate() {
-            let posting_elements_size = posting.elements.len() * size_of::<PostingElement>();
+            let posting_elements_size = posting_list_size(&posting.elements, weight_type);
             let posting_header = PostingListFileHeader {
                 start_offset: elements_offset as u64,
                 end_offset: (elements_offset + posting_elements_size) as u64,
@@ -203,11 +246,19 @@ impl InvertedIndexMmap {
         mmap: &mut MmapMut,
         inverted_index_ram: &InvertedIndexRam,
         total_posting_headers_size: usize,
+        weight_type: WeightType,
     ) {
         let mut offset = total_posting_headers_size;
         for posting in &inverted_index_ram.postings {
             // save posting element
-            let posting_elements_bytes = transmute_to_u8_slice(&posting.elements);
+            let encoded;
+            let posting_elements_bytes = match weight_type {
+                WeightType::Float32 => transmute_to_u8_slice(&posting.elements),
+                weight_type => {
+                    encoded = encode_posting_list(&posting.elements, weight_type);
+                    encoded.as_slice()
+                }
+            };
             mmap[offset..offset + posting_elements_bytes.len()]
                 .copy_from_slice(posting_elements_bytes);
             offset += posting_elements_bytes.len();
@@ -215,6 +266,96 @@ impl InvertedIndexMmap {
     }
 }
 
+/// Size of a stored posting element. Reduced precision elements only keep record id and weight.
+fn element_size(weight_type: WeightType) -> usize {
+    match weight_type {
+        WeightType::Float32 => size_of::<PostingElement>(),
+        WeightType::Float16 => size_of::<PointOffsetType>() + size_of::<f16>(),
+        WeightType::Uint8 => size_of::<PointOffsetType>() + size_of::<u8>(),
+    }
+}
+
+fn posting_list_size(elements: &[PostingElement], weight_type: WeightType) -> usize {
+    let params_size = match weight_type {
+        WeightType::Uint8 if !elements.is_empty() => UINT8_PARAMS_SIZE,
+        _ => 0,
+    };
+    params_size + elements.len() * element_size(weight_type)
+}
+
+/// Encode posting list with reduced precision weights
+fn encode_posting_list(elements: &[PostingElement], weight_type: WeightType) -> Vec<u8> {
+    let mut data = Vec::with_capacity(posting_list_size(elements, weight_type));
+    match weight_type {
+        WeightType::Float32 => data.extend_from_slice(transmute_to_u8_slice(elements)),
+        WeightType::Float16 => {
+            for element in elements {
+                data.extend_from_slice(&element.record_id.to_ne_bytes());
+                data.extend_from_slice(&f16::from_f32(element.weight).to_ne_bytes());
+            }
+        }
+        WeightType::Uint8 => {
+            if elements.is_empty() {
+                return data;
+            }
+            let (min, max) = elements.iter().fold(
+                (DimWeight::INFINITY, DimWeight::NEG_INFINITY),
+                |(min, max), element| (min.min(element.weight), max.max(element.weight)),
+            );
+            let step = (max - min) / u8::MAX as DimWeight;
+            data.extend_from_slice(&min.to_ne_bytes());
+            data.extend_from_slice(&step.to_ne_bytes());
+            for element in elements {
+                let level = if step > 0.0 {
+                    ((element.weight - min) / step).round() as u8
+                } else {
+                    0
+                };
+                data.extend_from_slice(&element.record_id.to_ne_bytes());
+                data.push(level);
+            }
+        }
+    }
+    data
+}
+
+/// Decode posting list with reduced precision weights, restoring `max_next_weight` of elements
+fn decode_posting_list(data: &[u8], weight_type: WeightType) -> Vec<PostingElement> {
+    let read_id = |bytes: &[u8]| {
+        PointOffsetType::from_ne_bytes(bytes[..size_of::<PointOffsetType>()].try_into().unwrap())
+    };
+    let read_weight = |bytes: &[u8]| DimWeight::from_ne_bytes(bytes.try_into().unwrap());
+    let element_size = element_size(weight_type);
+
+    let mut elements: Vec<_> = match weight_type {
+        WeightType::Float32 => transmute_from_u8_to_slice(data).to_vec(),
+        WeightType::Float16 => data
+            .chunks_exact(element_size)
+            .map(|bytes| {
+                let weight =
+                    f16::from_ne_bytes(bytes[size_of::<PointOffsetType>()..].try_into().unwrap());
+                PostingElement::new(read_id(bytes), weight.to_f32())
+            })
+            .collect(),
+        WeightType::Uint8 => {
+            if data.len() < UINT8_PARAMS_SIZE {
+                return Vec::new();
+            }
+            let (params, data) = data.split_at(UINT8_PARAMS_SIZE);
+            let min = read_weight(&params[..size_of::<DimWeight>()]);
+            let step = read_weight(&params[size_of::<DimWeight>()..]);
+            data.chunks_exact(element_size)
+                .map(|bytes| {
+                    let level = bytes[size_of::<PointOffsetType>()];
+                    PostingElement::new(read_id(bytes), min + level as DimWeight * step)
+                })
+                .collect()
+        }
+    };
+    compute_max_next_weight(&mut elements);
+    elements
+}
+
 #[cfg(test)]
 mod tests {
     use tempfile::Builder;
@@ -288,4 +429,46 @@ mod tests {
         assert!(inverted_index_mmap.get(&7).is_none());
         assert!(inverted_index_mmap.get(&100).is_none());
     }
+
+    #[test]
+    fn test_inverted_index_mmap_reduced_precision() {
+        let inverted_index_ram = InvertedIndexBuilder::new()
+            .add(
+                1,
+                PostingList::from(vec![(1, 10.0), (2, 20.5), (3, 30.0), (4, 1.0), (5, 2.25)]),
+            )
+            .add(2, PostingList::from(vec![(1, 7.0), (2, 7.0)]))
+            .add(4, PostingList::from(vec![(3, 0.5)])) // skip 3
+            .build();
+
+        for (weight_type, tolerance) in [(WeightType::Float16, 0.05), (WeightType::Uint8, 0.1)] {
+            let tmp_dir_path = Builder::new().prefix("test_index_dir").tempdir().unwrap();
+            InvertedIndexMmap::convert_and_save_with_weight_type(
+                &inverted_index_ram,
+                &tmp_dir_path,
+                weight_type,
+            )
+            .unwrap();
+
+            let inverted_index_mmap = InvertedIndexMmap::load(&tmp_dir_path).unwrap();
+            assert_eq!(inverted_index_mmap.file_header.weight_type, weight_type);
+            assert!(inverted_index_mmap.get(&3).unwrap().is_empty());
+            assert!(inverted_index_mmap.get(&5).is_none());
+
+            for id in 0..inverted_index_ram.postings.len() as DimId {
+                let posting_list_ram = &inverted_index_ram.get(&id).unwrap().elements;
+                let posting_list_mmap = inverted_index_mmap.get(&id).unwrap();
+                assert_eq!(posting_list_ram.len(), posting_list_mmap.len());
+                for (ram, mmap) in posting_list_ram.iter().zip(posting_list_mmap.iter()) {
+                    assert_eq!(ram.record_id, mmap.record_id);
+                    assert!((ram.weight - mmap.weight).abs() <= tolerance);
+                    // max_next_weight is restored from the decoded weights, last one is infinite
+                    assert!(
+                        ram.max_next_weight == mmap.max_next_weight
+                            || (ram.max_next_weight - mmap.max_next_weight).abs() <= tolerance
+                    );
+                }
+            }
+        }
+    }
 }
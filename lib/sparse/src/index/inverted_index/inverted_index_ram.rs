@@ -37,7 +37,7 @@ impl InvertedIndex for InvertedIndexRam {
                 )
             })?;
             inverted_index.postings.push(PostingList {
-                elements: posting_list.to_owned(),
+                elements: posting_list.into_owned(),
             });
         }
 
@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 use common::types::PointOffsetType;
 
 use crate::common::sparse_vector::SparseVector;
-use crate::common::types::DimId;
+use crate::common::types::{DimId, WeightType};
 use crate::index::inverted_index::inverted_index_ram::InvertedIndexRam;
 use crate::index::posting_list::PostingListIterator;
 
@@ -32,6 +32,17 @@ pub trait InvertedIndex: Sized {
         path: P,
     ) -> std::io::Result<Self>;
 
+    /// Create inverted index from ram index, storing weights as `weight_type` if the index supports it
+    ///
+    /// Indexes, which keep posting lists in memory, use full precision weights.
+    fn from_ram_index_with_weight_type<P: AsRef<Path>>(
+        ram_index: InvertedIndexRam,
+        path: P,
+        _weight_type: WeightType,
+    ) -> std::io::Result<Self> {
+        Self::from_ram_index(ram_index, path)
+    }
+
     /// Number of indexed vectors
     fn vector_count(&self) -> usize;
 
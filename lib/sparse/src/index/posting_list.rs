@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::cmp::max;
 
 use common::types::PointOffsetType;
@@ -158,11 +159,7 @@ impl PostingBuilder {
         }
 
         // Calculate max_next_weight
-        let mut max_next_weight = f32::NEG_INFINITY;
-        for element in self.elements.iter_mut().rev() {
-            element.max_next_weight = max_next_weight;
-            max_next_weight = max_next_weight.max(element.weight);
-        }
+        compute_max_next_weight(&mut self.elements);
 
         PostingList {
             elements: self.elements,
@@ -170,18 +167,29 @@ impl PostingBuilder {
     }
 }
 
+/// Sets `max_next_weight` of each element from the weights of the following elements
+pub(crate) fn compute_max_next_weight(elements: &mut [PostingElement]) {
+    let mut max_next_weight = DEFAULT_MAX_NEXT_WEIGHT;
+    for element in elements.iter_mut().rev() {
+        element.max_next_weight = max_next_weight;
+        max_next_weight = max_next_weight.max(element.weight);
+    }
+}
+
 /// Iterator over posting list elements offering skipping abilities to avoid full iteration.
+///
+/// Elements are borrowed from the index, or owned if the index had to decode them.
 pub struct PostingListIterator<'a> {
-    pub elements: &'a [PostingElement],
+    pub elements: Cow<'a, [PostingElement]>,
     pub current_index: usize,
 }
 
 impl<'a> Iterator for PostingListIterator<'a> {
-    type Item = &'a PostingElement;
+    type Item = PostingElement;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.current_index < self.elements.len() {
-            let element = &self.elements[self.current_index];
+            let element = self.elements[self.current_index];
             self.current_index += 1;
             Some(element)
         } else {
@@ -192,6 +200,10 @@ impl<'a> Iterator for PostingListIterator<'a> {
 
 impl<'a> PostingListIterator<'a> {
     pub fn new(elements: &'a [PostingElement]) -> PostingListIterator<'a> {
+        Self::from_cow(Cow::Borrowed(elements))
+    }
+
+    pub fn from_cow(elements: Cow<'a, [PostingElement]>) -> PostingListIterator<'a> {
         PostingListIterator {
             elements,
             current_index: 0,
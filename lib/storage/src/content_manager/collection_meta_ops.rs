@@ -1,10 +1,13 @@
 use std::collections::BTreeMap;
 
-use collection::config::{CollectionConfig, ShardingMethod};
+use collection::config::{
+    CollectionConfig, MmapAdvice, MmapPrefault, PayloadStorageBackend, ShardingMethod,
+};
 use collection::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, QuantizationConfigDiff,
     WalConfigDiff,
 };
+use collection::operations::payload_schema::StrictPayloadSchema;
 use collection::operations::types::{
     SparseVectorParams, SparseVectorsConfig, VectorsConfig, VectorsConfigDiff,
 };
@@ -13,7 +16,9 @@ use collection::shards::shard::{PeerId, ShardId, ShardsPlacement};
 use collection::shards::transfer::{ShardTransfer, ShardTransferKey};
 use collection::shards::{replica_set, CollectionId};
 use schemars::JsonSchema;
-use segment::types::{PayloadFieldSchema, PayloadKeyType, QuantizationConfig, ShardKey};
+use segment::types::{
+    PayloadCompression, PayloadFieldSchema, PayloadKeyType, QuantizationConfig, ShardKey,
+};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
@@ -164,6 +169,32 @@ pub struct CreateCollection {
     /// Sparse vector data config.
     #[validate]
     pub sparse_vectors: Option<BTreeMap<String, SparseVectorParams>>,
+    /// Access pattern hint for memory-mapped files of the collection.
+    /// If none - value from service configuration file is used.
+    #[serde(default)]
+    pub mmap_advice: Option<MmapAdvice>,
+    /// How memory-mapped files of the collection are loaded into RAM when segments are loaded.
+    /// If none - files are read in advance only if vector data is expected to fit into RAM.
+    #[serde(default)]
+    pub mmap_prefault: Option<MmapPrefault>,
+    /// Percentage of the most frequently accessed vectors of memory-mapped storages to keep in RAM.
+    /// If none - vectors are only cached by the OS page cache.
+    #[serde(default)]
+    #[validate(range(min = 1, max = 100))]
+    pub mmap_hot_percent: Option<u32>,
+    /// Declared types of payload fields.
+    /// If set - upserts and payload updates with undeclared top-level keys or values of other types
+    /// are rejected.
+    #[serde(default)]
+    pub strict_payload_schema: Option<StrictPayloadSchema>,
+    /// Compression of payload records, persisted on disk.
+    /// If none - records are not compressed.
+    #[serde(default)]
+    pub payload_compression: Option<PayloadCompression>,
+    /// Backend of the persistent payload storage.
+    /// If none - payload is stored in RocksDB.
+    #[serde(default)]
+    pub payload_storage_backend: Option<PayloadStorageBackend>,
 }
 
 /// Operation for creating new collection and (optionally) specify index params
@@ -208,6 +239,7 @@ pub struct UpdateCollection {
     /// Custom params for Optimizers.  If none - it is left unchanged.
     /// This operation is blocking, it will only proceed once all current optimizations are complete
     #[serde(alias = "optimizer_config")]
+    #[validate]
     pub optimizers_config: Option<OptimizersConfigDiff>, // TODO: Allow updates for other configuration params as well
     /// Collection base params. If none - it is left unchanged.
     pub params: Option<CollectionParamsDiff>,
@@ -383,6 +415,12 @@ impl From<CollectionConfig> for CreateCollection {
             init_from: None,
             quantization_config: value.quantization_config,
             sparse_vectors: value.params.sparse_vectors,
+            mmap_advice: value.params.mmap_advice,
+            mmap_prefault: value.params.mmap_prefault,
+            mmap_hot_percent: value.params.mmap_hot_percent,
+            strict_payload_schema: value.params.strict_payload_schema,
+            payload_compression: value.params.payload_compression,
+            payload_storage_backend: value.params.payload_storage_backend,
         }
     }
 }
@@ -1,4 +1,8 @@
-use collection::operations::conversions::sharding_method_from_proto;
+use collection::operations::conversions::{
+    mmap_advice_from_proto, mmap_prefault_from_proto, payload_compression_from_proto,
+    payload_storage_backend_from_proto, sharding_method_from_proto,
+    strict_payload_schema_from_proto,
+};
 use collection::operations::types::SparseVectorsConfig;
 use tonic::Status;
 
@@ -36,10 +40,17 @@ impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
                 },
                 sparse_vectors: value
                     .sparse_vectors_config
-                    .map(|config| config.map.into_iter().map(|(k, v)| (k, v.into())).collect()),
+                    .map(|config| {
+                        config
+                            .map
+                            .into_iter()
+                            .map(|(k, v)| Ok((k, v.try_into()?)))
+                            .collect::<Result<_, Status>>()
+                    })
+                    .transpose()?,
                 hnsw_config: value.hnsw_config.map(|v| v.into()),
                 wal_config: value.wal_config.map(|v| v.into()),
-                optimizers_config: value.optimizers_config.map(|v| v.into()),
+                optimizers_config: value.optimizers_config.map(TryInto::try_into).transpose()?,
                 shard_number: value.shard_number,
                 on_disk_payload: value.on_disk_payload,
                 replication_factor: value.replication_factor,
@@ -55,6 +66,23 @@ impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
                     .sharding_method
                     .map(sharding_method_from_proto)
                     .transpose()?,
+                mmap_advice: value.mmap_advice.map(mmap_advice_from_proto).transpose()?,
+                mmap_prefault: value
+                    .mmap_prefault
+                    .map(mmap_prefault_from_proto)
+                    .transpose()?,
+                mmap_hot_percent: value.mmap_hot_percent,
+                strict_payload_schema: strict_payload_schema_from_proto(
+                    value.strict_payload_schema,
+                )?,
+                payload_compression: value
+                    .payload_compression
+                    .map(payload_compression_from_proto)
+                    .transpose()?,
+                payload_storage_backend: value
+                    .payload_storage_backend
+                    .map(payload_storage_backend_from_proto)
+                    .transpose()?,
             },
         )))
     }
@@ -74,16 +102,22 @@ impl TryFrom<api::grpc::qdrant::UpdateCollection> for CollectionMetaOperations {
                     .transpose()?,
                 hnsw_config: value.hnsw_config.map(Into::into),
                 params: value.params.map(TryInto::try_into).transpose()?,
-                optimizers_config: value.optimizers_config.map(Into::into),
+                optimizers_config: value.optimizers_config.map(TryInto::try_into).transpose()?,
                 quantization_config: value
                     .quantization_config
                     .map(TryInto::try_into)
                     .transpose()?,
-                sparse_vectors: value.sparse_vectors_config.map(|config| {
-                    SparseVectorsConfig(
-                        config.map.into_iter().map(|(k, v)| (k, v.into())).collect(),
-                    )
-                }),
+                sparse_vectors: value
+                    .sparse_vectors_config
+                    .map(|config| {
+                        config
+                            .map
+                            .into_iter()
+                            .map(|(k, v)| Ok((k, v.try_into()?)))
+                            .collect::<Result<_, Status>>()
+                            .map(SparseVectorsConfig)
+                    })
+                    .transpose()?,
             },
         )))
     }
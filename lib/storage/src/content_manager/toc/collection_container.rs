@@ -148,7 +148,10 @@ impl TableOfContent {
                         &snapshots_path,
                         &state.config,
                         self.storage_config
-                            .to_shared_storage_config(self.is_distributed())
+                            .to_shared_storage_config(
+                                self.is_distributed(),
+                                self.optimization_budget.clone(),
+                            )
                             .into(),
                         shard_distribution,
                         self.channel_service.clone(),
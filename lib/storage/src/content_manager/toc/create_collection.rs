@@ -46,6 +46,12 @@ impl TableOfContent {
             init_from,
             quantization_config,
             sparse_vectors,
+            mmap_advice,
+            mmap_prefault,
+            mmap_hot_percent,
+            strict_payload_schema,
+            payload_compression,
+            payload_storage_backend,
         } = operation;
 
         self.collections
@@ -125,6 +131,12 @@ impl TableOfContent {
                 },
             )?,
             read_fan_out_factor: None,
+            mmap_advice,
+            mmap_prefault,
+            mmap_hot_percent,
+            strict_payload_schema,
+            payload_compression,
+            payload_storage_backend,
         };
         let wal_config = match wal_config_diff {
             None => self.storage_config.wal.clone(),
@@ -148,7 +160,7 @@ impl TableOfContent {
 
         let storage_config = self
             .storage_config
-            .to_shared_storage_config(self.is_distributed())
+            .to_shared_storage_config(self.is_distributed(), self.optimization_budget.clone())
             .into();
 
         let collection_config = CollectionConfig {
@@ -20,6 +20,7 @@ use api::grpc::qdrant::qdrant_internal_client::QdrantInternalClient;
 use api::grpc::qdrant::WaitOnConsensusCommitRequest;
 use api::grpc::transport_channel_pool::AddTimeout;
 use collection::collection::{Collection, RequestShardTransfer};
+use collection::common::optimization_budget::OptimizationBudget;
 use collection::config::{default_replication_factor, CollectionConfig};
 use collection::operations::types::*;
 use collection::shards::channel_service::ChannelService;
@@ -77,6 +78,8 @@ pub struct TableOfContent {
     collection_create_lock: Mutex<()>,
     /// Dispatcher for shard transfer to access consensus.
     shard_transfer_dispatcher: parking_lot::Mutex<Option<ShardTransferDispatcher>>,
+    /// Resources, available to optimizations of all collections
+    optimization_budget: Arc<OptimizationBudget>,
 }
 
 impl TableOfContent {
@@ -103,6 +106,7 @@ impl TableOfContent {
             read_dir(&collections_path).expect("Can't read Collections directory");
         let mut collections: HashMap<String, Collection> = Default::default();
         let is_distributed = consensus_proposal_sender.is_some();
+        let optimization_budget = Arc::new(storage_config.optimization_budget());
         for entry in collection_paths {
             let collection_path = entry
                 .expect("Can't access of one of the collection files")
@@ -134,7 +138,7 @@ impl TableOfContent {
                 &collection_path,
                 &collection_snapshots_path,
                 storage_config
-                    .to_shared_storage_config(is_distributed)
+                    .to_shared_storage_config(is_distributed, optimization_budget.clone())
                     .into(),
                 channel_service.clone(),
                 Self::change_peer_state_callback(
@@ -194,6 +198,7 @@ impl TableOfContent {
             update_rate_limiter: rate_limiter,
             collection_create_lock: Default::default(),
             shard_transfer_dispatcher: Default::default(),
+            optimization_budget,
         }
     }
 
@@ -131,6 +131,34 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Count points with each value of a payload field.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we count
+    /// * `request` - [`FacetRequestInternal`]
+    /// * `shard_selection` - which local shard to use
+    /// * `timeout` - how long to wait for the response
+    ///
+    /// # Result
+    ///
+    /// Values of the field with the most points.
+    ///
+    pub async fn facet(
+        &self,
+        collection_name: &str,
+        request: FacetRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) -> Result<FacetResponse, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .facet(request, read_consistency, &shard_selection, timeout)
+            .await
+            .map_err(|err| err.into())
+    }
+
     /// Return specific points by IDs
     ///
     /// # Arguments
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 use std::num::NonZeroUsize;
+use std::sync::Arc;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
+use collection::common::optimization_budget::OptimizationBudget;
 use collection::config::WalConfig;
 use collection::operations::shared_storage_config::{
     SharedStorageConfig, DEFAULT_IO_SHARD_TRANSFER_LIMIT,
@@ -20,6 +22,8 @@ use validator::Validate;
 
 pub type PeerAddressById = HashMap<PeerId, Uri>;
 
+const BYTES_IN_MB: usize = 1024 * 1024;
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct PerformanceConfig {
     pub max_search_threads: usize,
@@ -33,6 +37,14 @@ pub struct PerformanceConfig {
     pub incoming_shard_transfers_limit: Option<usize>,
     #[serde(default = "default_io_shard_transfers_limit")]
     pub outgoing_shard_transfers_limit: Option<usize>,
+    /// Max total number of index building threads of concurrent optimizations across all collections.
+    /// If not set - not limited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optimization_threads_budget: Option<usize>,
+    /// Max total size (in megabytes) of segments under concurrent optimization across all collections.
+    /// If not set - not limited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optimization_memory_budget_mb: Option<usize>,
 }
 
 const fn default_max_optimization_threads() -> usize {
@@ -85,7 +97,21 @@ pub struct StorageConfig {
 }
 
 impl StorageConfig {
-    pub fn to_shared_storage_config(&self, is_distributed: bool) -> SharedStorageConfig {
+    /// Create the budget of optimizations, which must be shared by all collections of the node
+    pub fn optimization_budget(&self) -> OptimizationBudget {
+        OptimizationBudget::new(
+            self.performance.optimization_threads_budget,
+            self.performance
+                .optimization_memory_budget_mb
+                .map(|mb| mb.saturating_mul(BYTES_IN_MB)),
+        )
+    }
+
+    pub fn to_shared_storage_config(
+        &self,
+        is_distributed: bool,
+        optimization_budget: Arc<OptimizationBudget>,
+    ) -> SharedStorageConfig {
         SharedStorageConfig::new(
             self.update_queue_size,
             self.node_type,
@@ -98,6 +124,7 @@ impl StorageConfig {
             is_distributed,
             self.performance.incoming_shard_transfers_limit,
             self.performance.outgoing_shard_transfers_limit,
+            optimization_budget,
         )
     }
 }
@@ -2,7 +2,7 @@ use std::num::{NonZeroU64, NonZeroUsize};
 use std::sync::Arc;
 
 use collection::operations::types::VectorParams;
-use collection::optimizers_builder::OptimizersConfig;
+use collection::optimizers_builder::{MergePolicy, OptimizersConfig};
 use collection::shards::channel_service::ChannelService;
 use memory::madvise;
 use segment::types::Distance;
@@ -34,12 +34,14 @@ fn test_alias_operation() {
         optimizers: OptimizersConfig {
             deleted_threshold: 0.5,
             vacuum_min_vector_number: 100,
+            vacuum_enabled: true,
             default_segment_number: 2,
             max_segment_size: None,
             memmap_threshold: Some(100),
             indexing_threshold: Some(100),
             flush_interval_sec: 2,
             max_optimization_threads: 2,
+            merge_policy: MergePolicy::MaxSegmentSizeBound,
         },
         wal: Default::default(),
         performance: PerformanceConfig {
@@ -49,6 +51,8 @@ fn test_alias_operation() {
             search_timeout_sec: None,
             incoming_shard_transfers_limit: Some(1),
             outgoing_shard_transfers_limit: Some(1),
+            optimization_threads_budget: None,
+            optimization_memory_budget_mb: None,
         },
         hnsw_index: Default::default(),
         quantization: None,
@@ -95,6 +99,11 @@ fn test_alias_operation() {
                             hnsw_config: None,
                             quantization_config: None,
                             on_disk: None,
+                            index: None,
+                            multivector_config: None,
+                            datatype: None,
+                            keep_original: None,
+                            dimension_weights: None,
                         }
                         .into(),
                         sparse_vectors: None,
@@ -108,6 +117,12 @@ fn test_alias_operation() {
                         init_from: None,
                         quantization_config: None,
                         sharding_method: None,
+                        mmap_advice: None,
+                        mmap_prefault: None,
+                        mmap_hot_percent: None,
+                        strict_payload_schema: None,
+                        payload_compression: None,
+                        payload_storage_backend: None,
                     },
                 )),
                 None,
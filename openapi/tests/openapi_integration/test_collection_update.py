@@ -44,11 +44,43 @@ def test_collection_update():
             "optimizers_config": {
                 "default_segment_number": 6,
                 "indexing_threshold": 10000,
+                "merge_policy": "size_tiered",
+                "deleted_threshold": 0.5,
+                "vacuum_min_vector_number": 500,
+                "vacuum_enabled": False,
             },
         }
     )
     assert response.ok
 
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    optimizer_config = response.json()['result']['config']['optimizer_config']
+    assert optimizer_config['merge_policy'] == "size_tiered"
+    assert optimizer_config['default_segment_number'] == 6
+    assert optimizer_config['deleted_threshold'] == 0.5
+    assert optimizer_config['vacuum_min_vector_number'] == 500
+    assert not optimizer_config['vacuum_enabled']
+
+    # Thresholds are validated on update
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body={
+            "optimizers_config": {
+                "vacuum_min_vector_number": 10,
+            },
+        }
+    )
+    assert not response.ok
+    assert 'Validation error' in response.json()["status"]["error"]
+    assert 'optimizers_config.vacuum_min_vector_number' in response.json()["status"]["error"]
+
     response = request_with_validation(
         api='/collections/{collection_name}/points',
         method="PUT",
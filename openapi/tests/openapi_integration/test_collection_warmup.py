@@ -0,0 +1,33 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_warmup'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_collection_warmup():
+    response = request_with_validation(
+        api='/collections/{collection_name}/warmup',
+        method="POST",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert response.json()['result']
+
+
+def test_collection_warmup_unknown_vector():
+    response = request_with_validation(
+        api='/collections/{collection_name}/warmup',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'vector': 'unknown'},
+    )
+    assert response.status_code == 400
@@ -39,6 +39,7 @@ def test_exact_count_search():
     )
     assert response.ok
     assert response.json()['result']['count'] == 4
+    assert 'estimation' not in response.json()['result']
 
 
 def test_approx_count_search():
@@ -69,3 +70,7 @@ def test_approx_count_search():
     assert response.ok
     assert response.json()['result']['count'] < 10
     assert response.json()['result']['count'] > 0
+
+    estimation = response.json()['result']['estimation']
+    assert estimation['exp'] == response.json()['result']['count']
+    assert estimation['min'] <= estimation['exp'] <= estimation['max']
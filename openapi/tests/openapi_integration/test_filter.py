@@ -67,3 +67,47 @@ def test_just_key():
     error = response.json()["status"]["error"]
     assert "Validation error in JSON body" in error
     assert "At least one field condition must be specified" in error
+
+
+def test_min_should():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "limit": 10,
+            "filter": {
+                "min_should": {
+                    "conditions": [
+                        {"key": "city", "match": {"value": "Berlin"}},
+                        {"key": "city", "match": {"value": "London"}},
+                        {"key": "city", "match": {"value": "Moscow"}},
+                    ],
+                    "min_count": 2
+                }
+            }
+        }
+    )
+    assert response.ok
+
+    ids = [x['id'] for x in response.json()['result']['points']]
+    assert sorted(ids) == [2, 3, 4]
+
+
+def test_has_id_range():
+    def scroll_ids(has_id_range):
+        response = request_with_validation(
+            api='/collections/{collection_name}/points/scroll',
+            method="POST",
+            path_params={'collection_name': collection_name},
+            body={
+                "filter": {"must": [{"has_id_range": has_id_range}]},
+            }
+        )
+        assert response.ok
+        return sorted(point['id'] for point in response.json()['result']['points'])
+
+    assert scroll_ids({"gte": 2, "lt": 5}) == [2, 3, 4]
+    assert scroll_ids({"lt": 3}) == [1, 2]
+    assert scroll_ids({"gte": 7}) == [7, 8, 9, 10]
+    assert scroll_ids({"gte": 5, "lt": 5}) == []
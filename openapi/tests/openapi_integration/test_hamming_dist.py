@@ -0,0 +1,111 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_hamming'
+
+
+def basic_collection_setup(
+    collection_name='test_collection',
+    on_disk_vectors=False,
+):
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="DELETE",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 2,
+                "distance": "Hamming",
+                "datatype": "uint8",
+                "on_disk": on_disk_vectors,
+            }
+        }
+    )
+    assert response.ok
+
+    # Each element holds 8 bits of the binary vector
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {
+                    "id": 1,
+                    "vector": [255, 0],
+                },
+                {
+                    "id": 2,
+                    "vector": [15, 0],
+                },
+                {
+                    "id": 3,
+                    "vector": [0, 1],
+                },
+            ]
+        }
+    )
+    assert response.ok
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_search_with_threshold():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [255, 0],
+            "limit": 3
+        }
+    )
+    assert response.ok
+    result = response.json()['result']
+    assert [point['id'] for point in result] == [1, 2, 3]
+    assert [point['score'] for point in result] == [0.0, 4.0, 9.0]
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [255, 0],
+            "limit": 3,
+            "score_threshold": 5
+        }
+    )
+    assert response.ok
+    result = response.json()['result']
+    assert [point['id'] for point in result] == [1, 2]
+
+
+def test_hamming_requires_uint8():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name + '_float'},
+        body={
+            "vectors": {
+                "size": 2,
+                "distance": "Hamming",
+            }
+        }
+    )
+    assert not response.ok
+    assert response.status_code == 422
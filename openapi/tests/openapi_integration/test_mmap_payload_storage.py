@@ -0,0 +1,97 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_mmap_payload_storage'
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    drop_collection(collection_name=collection_name)
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 4,
+                "distance": "Dot",
+            },
+            "payload_storage_backend": "mmap",
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": [0.05, 0.61, 0.76, 0.74], "payload": {"city": "Berlin"}},
+                {"id": 2, "vector": [0.19, 0.81, 0.75, 0.11], "payload": {"city": "London"}},
+                {"id": 3, "vector": [0.36, 0.55, 0.47, 0.94], "payload": {"city": "Moscow"}},
+            ]
+        }
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def get_payload(point_id):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': point_id},
+    )
+    assert response.ok
+    return response.json()['result']['payload']
+
+
+def test_mmap_payload_storage():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert response.json()['result']['config']['params']['payload_storage_backend'] == "mmap"
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {"rating": 5},
+            "points": [1, 2]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload/clear',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": [3]}
+    )
+    assert response.ok
+
+    assert get_payload(1) == {"city": "Berlin", "rating": 5}
+    assert get_payload(2) == {"city": "London", "rating": 5}
+    assert get_payload(3) == {}
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "filter": {"must": [{"key": "city", "match": {"value": "London"}}]},
+        }
+    )
+    assert response.ok
+    assert [point['id'] for point in response.json()['result']['points']] == [2]
@@ -523,3 +523,61 @@ def test_nested_payload_indexing_operations():
     )
     assert response.ok
     assert len(response.json()['result']['payload_schema']) == 0
+
+
+def scroll_ids_by_cities_count(count):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "filter": {
+                "must": [
+                    {
+                        "nested": {
+                            "key": "country.cities",
+                            "filter": {
+                                "must": [
+                                    {
+                                        "key": "population",
+                                        "range": {
+                                            "gte": 1.5,
+                                        }
+                                    }
+                                ]
+                            },
+                            "count": count,
+                        }
+                    }
+                ]
+            },
+            "limit": 10
+        }
+    )
+    assert response.ok
+    return sorted(point['id'] for point in response.json()['result']['points'])
+
+
+def check_nested_count():
+    assert scroll_ids_by_cities_count({"gte": 3}) == [1, 4]
+    assert scroll_ids_by_cities_count({"gte": 2, "lte": 2}) == [2]
+    # Points without matching cities match as well
+    assert scroll_ids_by_cities_count({"lte": 1}) == [3, 5, 6]
+
+
+def test_nested_payload_count():
+    check_nested_count()
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": "country.cities[].population",
+            "field_schema": "float"
+        }
+    )
+    assert response.ok
+
+    check_nested_count()
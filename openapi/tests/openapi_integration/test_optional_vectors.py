@@ -496,6 +496,49 @@ def delete_vectors():
     assert "text" not in result["vector"]
 
 
+def test_has_vector_filter():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/vectors/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [2, 3],
+            "vector": ["text"]
+        }
+    )
+    assert response.ok
+
+    def count_with_vector(vector_name):
+        response = request_with_validation(
+            api='/collections/{collection_name}/points/count',
+            method="POST",
+            path_params={'collection_name': collection_name},
+            body={
+                "filter": {"must": [{"has_vector": vector_name}]},
+                "exact": True,
+            }
+        )
+        assert response.ok
+        return response.json()['result']['count']
+
+    total = count_with_vector("image")
+    assert total > 2
+    assert count_with_vector("text") == total - 2
+    assert count_with_vector("unknown") == 0
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "filter": {"must_not": [{"has_vector": "text"}]},
+        }
+    )
+    assert response.ok
+    assert sorted(point['id'] for point in response.json()['result']['points']) == [2, 3]
+
+
 def test_delete_all_vectors():
     response = request_with_validation(
         api='/collections/{collection_name}/points/vectors/delete',
@@ -0,0 +1,71 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_payload_compression'
+
+
+@pytest.fixture(autouse=True, params=[False, True])
+def setup(request):
+    drop_collection(collection_name=collection_name)
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 4,
+                "distance": "Dot",
+            },
+            "on_disk_payload": request.param,
+            "payload_compression": "zstd",
+        }
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_compressed_payload():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert response.json()['result']['config']['params']['payload_compression'] == "zstd"
+
+    payload = {"text": "lorem ipsum " * 100, "city": "Berlin", "count": 42}
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": [0.05, 0.61, 0.76, 0.74], "payload": payload},
+            ]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {"count": 43},
+            "points": [1]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 1},
+    )
+    assert response.ok
+    assert response.json()['result']['payload'] == {**payload, "count": 43}
@@ -275,3 +275,42 @@ def test_payload_operations():
     )
     assert response.ok
     assert len(response.json()['result']['points']) == 0
+
+
+def test_delete_payload_by_wildcard():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {
+                "city": "Berlin",
+                "metadata": {"tmp": {"a": 1, "b": [1, 2]}, "source": "web"},
+            },
+            "points": [6]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "keys": ["metadata.tmp.*"],
+            "points": [6]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 6},
+    )
+    assert response.ok
+    payload = response.json()['result']['payload']
+    assert payload['city'] == "Berlin"
+    assert payload['metadata'] == {"tmp": {}, "source": "web"}
@@ -0,0 +1,72 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_rebuild_index'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": "city",
+            "field_schema": "keyword"
+        }
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def count_city(city):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "filter": {"must": [{"key": "city", "match": {"value": city}}]},
+            "exact": True,
+        },
+    )
+    assert response.ok
+    return response.json()['result']['count']
+
+
+def test_rebuild_index():
+    berlin_count = count_city("Berlin")
+    assert berlin_count > 0
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index/{field_name}/rebuild',
+        method="POST",
+        path_params={'collection_name': collection_name, 'field_name': 'city'},
+        query_params={'wait': 'true'},
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert response.json()['result']['payload_schema']['city']['data_type'] == "keyword"
+
+    assert count_city("Berlin") == berlin_count
+
+
+def test_rebuild_not_indexed_field():
+    response = request_with_validation(
+        api='/collections/{collection_name}/index/{field_name}/rebuild',
+        method="POST",
+        path_params={'collection_name': collection_name, 'field_name': 'count'},
+        query_params={'wait': 'true'},
+    )
+    assert response.status_code == 400
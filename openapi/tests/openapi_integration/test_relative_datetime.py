@@ -0,0 +1,81 @@
+from datetime import datetime, timedelta, timezone
+
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_relative_datetime'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    drop_collection(collection_name=collection_name)
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 4,
+                "distance": "Dot",
+                "on_disk": on_disk_vectors,
+            },
+        }
+    )
+    assert response.ok
+
+    now = datetime.now(timezone.utc)
+    points = [
+        {"id": point_id, "vector": [0.1, 0.2, 0.3, 0.4], "payload": {"created": (now - age).isoformat()}}
+        for point_id, age in [(1, timedelta(hours=1)), (2, timedelta(days=3)), (3, timedelta(days=30))]
+    ]
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": points}
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def scroll_ids(datetime_range):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "filter": {"must": [{"key": "created", "range": datetime_range}]},
+        }
+    )
+    assert response.ok
+    return sorted(point['id'] for point in response.json()['result']['points'])
+
+
+def check_relative_ranges():
+    assert scroll_ids({"gte": "now-7d"}) == [1, 2]
+    assert scroll_ids({"gte": "now-1w", "lt": "now-2h"}) == [2]
+    assert scroll_ids({"lt": "now-7d"}) == [3]
+    assert scroll_ids({"gt": "now"}) == []
+
+
+def test_relative_datetime():
+    check_relative_ranges()
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": "created",
+            "field_schema": "datetime"
+        }
+    )
+    assert response.ok
+
+    check_relative_ranges()
+
@@ -0,0 +1,141 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_rename_payload'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    drop_collection(collection_name=collection_name)
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 4,
+                "distance": "Dot",
+                "on_disk": on_disk_vectors,
+            },
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": [0.05, 0.61, 0.76, 0.74], "payload": {"meta": {"town": "Berlin", "year": 2020}}},
+                {"id": 2, "vector": [0.19, 0.81, 0.75, 0.11], "payload": {"meta": {"town": "London"}}},
+                {"id": 3, "vector": [0.36, 0.55, 0.47, 0.94], "payload": {"city": "Moscow"}},
+            ]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": "city",
+            "field_schema": "keyword"
+        }
+    )
+    assert response.ok
+
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def rename_payload(body):
+    return request_with_validation(
+        api='/collections/{collection_name}/points/payload/rename',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body=body,
+    )
+
+
+def get_payload(point_id):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': point_id},
+    )
+    assert response.ok
+    return response.json()['result']['payload']
+
+
+def count_city(city):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "filter": {"must": [{"key": "city", "match": {"value": city}}]},
+            "exact": True,
+        },
+    )
+    assert response.ok
+    return response.json()['result']['count']
+
+
+def test_rename_nested_key_by_filter():
+    response = rename_payload({
+        "key": "meta.town",
+        "new_key": "city",
+        "filter": {"must": [{"key": "meta.town", "match": {"any": ["Berlin", "London"]}}]},
+    })
+    assert response.ok
+
+    assert get_payload(1) == {"meta": {"year": 2020}, "city": "Berlin"}
+    assert get_payload(2) == {"meta": {}, "city": "London"}
+    assert get_payload(3) == {"city": "Moscow"}
+
+    # Renamed values are indexed
+    assert count_city("Berlin") == 1
+    assert count_city("London") == 1
+
+
+def test_rename_key_by_ids():
+    response = rename_payload({
+        "key": "city",
+        "new_key": "location.city",
+        "points": [2, 3],
+    })
+    assert response.ok
+
+    assert get_payload(2) == {"meta": {"town": "London"}}
+    assert get_payload(3) == {"location": {"city": "Moscow"}}
+    assert count_city("Moscow") == 0
+
+
+def test_rename_invalid_keys():
+    response = rename_payload({
+        "key": "city",
+        "new_key": "city",
+        "points": [3],
+    })
+    assert response.status_code == 422
+
+    response = rename_payload({
+        "key": "tags[0]",
+        "new_key": "tag",
+        "points": [3],
+    })
+    assert response.status_code == 422
+
+    response = rename_payload({
+        "key": "meta.*",
+        "new_key": "city",
+        "points": [3],
+    })
+    assert response.status_code == 422
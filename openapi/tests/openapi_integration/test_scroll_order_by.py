@@ -0,0 +1,135 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_scroll_order_by'
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors):
+    drop_collection(collection_name=collection_name)
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 4,
+                "distance": "Dot",
+                "on_disk": on_disk_vectors,
+            },
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": [0.05, 0.61, 0.76, 0.74],
+                 "payload": {"price": 30, "location": {"lon": 13.40, "lat": 52.52}}},
+                {"id": 2, "vector": [0.19, 0.81, 0.75, 0.11],
+                 "payload": {"price": [10, 50], "location": {"lon": -0.13, "lat": 51.51}}},
+                {"id": 3, "vector": [0.36, 0.55, 0.47, 0.94],
+                 "payload": {"price": 20, "location": {"lon": 2.35, "lat": 48.86}}},
+                {"id": 4, "vector": [0.18, 0.01, 0.85, 0.80], "payload": {"price": 40}},
+                {"id": 5, "vector": [0.24, 0.18, 0.22, 0.44], "payload": {"color": "red"}},
+            ]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": "price",
+            "field_schema": "integer"
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": "location",
+            "field_schema": "geo"
+        }
+    )
+    assert response.ok
+
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def scroll(body):
+    return request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+
+
+def test_scroll_order_by_asc():
+    response = scroll({"order_by": {"key": "price"}, "limit": 10})
+    assert response.ok
+    points = response.json()['result']['points']
+    assert [(point['id'], point['order_value']) for point in points] == [
+        (2, 10), (3, 20), (1, 30), (4, 40),
+    ]
+    assert response.json()['result']['next_page_offset'] is None
+
+
+def test_scroll_order_by_desc_start_from():
+    response = scroll({
+        "order_by": {"key": "price", "direction": "desc", "start_from": 35},
+        "limit": 2,
+    })
+    assert response.ok
+    points = response.json()['result']['points']
+    assert [(point['id'], point['order_value']) for point in points] == [
+        (1, 30), (3, 20),
+    ]
+
+
+def test_scroll_order_by_with_offset():
+    response = scroll({"order_by": {"key": "price"}, "offset": 1})
+    assert response.status_code == 400
+
+
+def test_scroll_order_by_not_indexed():
+    response = scroll({"order_by": {"key": "color"}})
+    assert response.status_code == 400
+
+
+def test_scroll_order_by_distance():
+    # From Amsterdam: London is about 360 km away, Paris about 430 km and Berlin about 580 km
+    amsterdam = {"lon": 4.90, "lat": 52.37}
+    response = scroll({"order_by": {"key": "location", "distance_from": amsterdam}, "limit": 10})
+    assert response.ok
+    points = response.json()['result']['points']
+    assert [point['id'] for point in points] == [2, 3, 1]
+    distances = [point['order_value'] for point in points]
+    assert distances == sorted(distances)
+    assert 350_000 < distances[0] < 370_000
+
+    response = scroll({
+        "order_by": {"key": "location", "distance_from": amsterdam, "direction": "desc", "start_from": 500_000},
+    })
+    assert response.ok
+    assert [point['id'] for point in response.json()['result']['points']] == [3, 2]
+
+
+def test_scroll_order_by_distance_not_geo_indexed():
+    response = scroll({"order_by": {"key": "price", "distance_from": {"lon": 0.0, "lat": 0.0}}})
+    assert response.status_code == 400
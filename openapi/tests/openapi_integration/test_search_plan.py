@@ -0,0 +1,41 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_search_plan'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def search(query_params):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params=query_params,
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "filter": {"must": [{"key": "city", "match": {"value": "Berlin"}}]},
+            "limit": 3,
+        }
+    )
+    assert response.ok
+    return response.json()
+
+
+def test_search_plan():
+    response = search({})
+    assert 'debug' not in response
+
+    response = search({'debug': 'true'})
+    plans = response['debug']['search_plans']
+    assert len(plans) > 0
+    for plan in plans:
+        assert plan['strategy'] in ("graph", "payload_index", "full_scan")
+        assert plan['estimated_cardinality'] <= plan['full_scan_cost']
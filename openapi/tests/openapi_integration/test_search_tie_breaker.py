@@ -0,0 +1,97 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_search_tie_breaker'
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors):
+    drop_collection(collection_name=collection_name)
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 4,
+                "distance": "Dot",
+                "on_disk": on_disk_vectors,
+            },
+        }
+    )
+    assert response.ok
+
+    # Points 1-4 have the same vector, so their scores are equal
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": [0.1, 0.2, 0.3, 0.4], "payload": {"published": 2019}},
+                {"id": 2, "vector": [0.1, 0.2, 0.3, 0.4], "payload": {"published": 2023}},
+                {"id": 3, "vector": [0.1, 0.2, 0.3, 0.4], "payload": {"published": 2021}},
+                {"id": 4, "vector": [0.1, 0.2, 0.3, 0.4]},
+                {"id": 5, "vector": [0.9, 0.9, 0.9, 0.9], "payload": {"published": 2000}},
+            ]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": "published",
+            "field_schema": "integer"
+        }
+    )
+    assert response.ok
+
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def search(body):
+    return request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+
+
+def test_search_tie_breaker_desc():
+    response = search({
+        "vector": [1.0, 1.0, 1.0, 1.0],
+        "limit": 3,
+        "tie_breaker": {"key": "published", "direction": "desc"},
+    })
+    assert response.ok
+    assert [point['id'] for point in response.json()['result']] == [5, 2, 3]
+
+
+def test_search_tie_breaker_asc_with_offset():
+    response = search({
+        "vector": [1.0, 1.0, 1.0, 1.0],
+        "limit": 3,
+        "offset": 2,
+        "tie_breaker": {"key": "published"},
+    })
+    assert response.ok
+    # Point without the value goes last
+    assert [point['id'] for point in response.json()['result']] == [3, 2, 4]
+
+
+def test_search_tie_breaker_not_indexed():
+    response = search({
+        "vector": [1.0, 1.0, 1.0, 1.0],
+        "limit": 3,
+        "tie_breaker": {"key": "color"},
+    })
+    assert response.status_code == 400
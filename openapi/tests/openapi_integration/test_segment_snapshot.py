@@ -0,0 +1,134 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_segment_snapshot'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def list_segments():
+    response = request_with_validation(
+        api='/collections/{collection_name}/shards/{shard_id}/segments',
+        method="GET",
+        path_params={'shard_id': 0, 'collection_name': collection_name},
+    )
+    assert response.ok
+    return response.json()['result']
+
+
+def count_points():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"exact": True},
+    )
+    assert response.ok
+    return response.json()['result']['count']
+
+
+def test_segment_snapshot_operations():
+    segments = list_segments()
+    assert sum(segment['num_points'] for segment in segments) == 10
+
+    segment = max(segments, key=lambda segment: segment['num_points'])
+    assert segment['num_points'] > 0
+
+    # create snapshot of the segment
+    response = request_with_validation(
+        api='/collections/{collection_name}/shards/{shard_id}/segments/{segment_id}/snapshots',
+        method="POST",
+        path_params={'shard_id': 0, 'collection_name': collection_name, 'segment_id': segment['id']},
+        query_params={'wait': 'true'},
+    )
+    assert response.ok
+    snapshot_name = response.json()['result']['name']
+    assert segment['id'] in snapshot_name
+
+    # segment snapshot is listed together with shard snapshots
+    response = request_with_validation(
+        api='/collections/{collection_name}/shards/{shard_id}/snapshots',
+        method="GET",
+        path_params={'shard_id': 0, 'collection_name': collection_name},
+    )
+    assert response.ok
+    assert [snapshot['name'] for snapshot in response.json()['result']] == [snapshot_name]
+
+    # recover the segment from its snapshot
+    response = request_with_validation(
+        api='/collections/{collection_name}/shards/{shard_id}/segments/{segment_id}/snapshots/recover',
+        method="PUT",
+        path_params={'shard_id': 0, 'collection_name': collection_name, 'segment_id': segment['id']},
+        query_params={'wait': 'true'},
+        body={"location": snapshot_name},
+    )
+    assert response.ok
+    assert count_points() == 10
+
+    # recovered segment replaces the original one
+    recovered_segments = list_segments()
+    segment_ids = [recovered['id'] for recovered in recovered_segments]
+    assert len(segment_ids) == len(segments)
+    assert segment['id'] not in segment_ids
+
+    recovered = max(recovered_segments, key=lambda recovered: recovered['num_points'])
+    assert recovered['num_points'] == segment['num_points']
+
+    # delete all points
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": list(range(1, 11))},
+    )
+    assert response.ok
+    assert count_points() == 0
+
+    # snapshot is older than the segment now, deleted points must not come back
+    response = request_with_validation(
+        api='/collections/{collection_name}/shards/{shard_id}/segments/{segment_id}/snapshots/recover',
+        method="PUT",
+        path_params={'shard_id': 0, 'collection_name': collection_name, 'segment_id': recovered['id']},
+        query_params={'wait': 'true'},
+        body={"location": snapshot_name},
+    )
+    assert response.status_code == 400
+    assert count_points() == 0
+
+    # delete the snapshot
+    response = request_with_validation(
+        api='/collections/{collection_name}/shards/{shard_id}/snapshots/{snapshot_name}',
+        method="DELETE",
+        path_params={'shard_id': 0, 'collection_name': collection_name,
+                     'snapshot_name': snapshot_name},
+        query_params={'wait': 'true'},
+    )
+    assert response.ok
+
+
+def test_segment_snapshot_errors():
+    # Unknown segment
+    response = request_with_validation(
+        api='/collections/{collection_name}/shards/{shard_id}/segments/{segment_id}/snapshots',
+        method="POST",
+        path_params={'shard_id': 0, 'collection_name': collection_name, 'segment_id': 'whatever'},
+        query_params={'wait': 'true'},
+    )
+    assert response.status_code == 404
+
+    # Unknown snapshot
+    response = request_with_validation(
+        api='/collections/{collection_name}/shards/{shard_id}/segments/{segment_id}/snapshots/recover',
+        method="PUT",
+        path_params={'shard_id': 0, 'collection_name': collection_name, 'segment_id': 'whatever'},
+        body={"location": "whatever.snapshot"},
+    )
+    assert response.status_code == 404
@@ -0,0 +1,95 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_strict_payload_schema'
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    drop_collection(collection_name=collection_name)
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 4,
+                "distance": "Dot",
+            },
+            "strict_payload_schema": {
+                "city": "keyword",
+                "price": "float",
+            },
+        }
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert_point(payload):
+    return request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {
+                    "id": 1,
+                    "vector": [0.05, 0.61, 0.76, 0.74],
+                    "payload": payload,
+                }
+            ]
+        }
+    )
+
+
+def test_strict_payload_schema_config():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert response.json()['result']['config']['params']['strict_payload_schema'] == {
+        "city": "keyword",
+        "price": "float",
+    }
+
+
+def test_matching_payload():
+    response = upsert_point({"city": ["Berlin", "London"], "price": 10})
+    assert response.ok
+
+
+def test_wrong_type():
+    response = upsert_point({"city": "Berlin", "price": "cheap"})
+    assert response.status_code == 400
+    assert 'price: expected float' in response.json()['status']['error']
+
+
+def test_unexpected_key():
+    response = upsert_point({"city": "Berlin", "color": "red"})
+    assert response.status_code == 400
+    assert 'color: unexpected key' in response.json()['status']['error']
+
+
+def test_set_payload():
+    response = upsert_point({"city": "Berlin"})
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {"price": True},
+            "points": [1],
+        }
+    )
+    assert response.status_code == 400
+    assert 'price: expected float' in response.json()['status']['error']
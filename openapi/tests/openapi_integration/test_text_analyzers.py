@@ -0,0 +1,93 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_text_analyzers'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    drop_collection(collection_name=collection_name)
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 4,
+                "distance": "Dot",
+                "on_disk": on_disk_vectors,
+            },
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": [0.05, 0.61, 0.76, 0.74], "payload": {"title": "Database of vectors"}},
+                {"id": 2, "vector": [0.19, 0.81, 0.75, 0.11], "payload": {"title": "Vectorized data"}},
+                {"id": 3, "vector": [0.36, 0.55, 0.47, 0.94], "payload": {"title": "A base of data"}},
+            ]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": "title",
+            "field_schema": {
+                "type": "text",
+                "tokenizer": "word",
+                "analyzers": {
+                    "substring": {
+                        "tokenizer": "ngram",
+                    },
+                },
+            }
+        }
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def scroll_ids(text_match):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "filter": {"must": [{"key": "title", "match": text_match}]},
+        }
+    )
+    assert response.ok
+    return sorted(point['id'] for point in response.json()['result']['points'])
+
+
+def test_text_analyzers():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    params = response.json()['result']['payload_schema']['title']['params']
+    assert params['analyzers']['substring']['tokenizer'] == "ngram"
+
+    # Main configuration matches whole words
+    assert scroll_ids({"text": "base"}) == [3]
+    assert scroll_ids({"text": "vector"}) == []
+
+    # Analyzer with n-gram tokenizer matches substrings of words
+    assert scroll_ids({"text": "base", "analyzer": "substring"}) == [1, 3]
+    assert scroll_ids({"text": "vector", "analyzer": "substring"}) == [1, 2]
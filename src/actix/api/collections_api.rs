@@ -30,6 +30,12 @@ impl WaitTimeout {
     }
 }
 
+#[derive(Debug, Deserialize, Validate)]
+pub struct WarmupParams {
+    /// Only read data of this named vector
+    vector: Option<String>,
+}
+
 #[get("/collections")]
 async fn get_collections(toc: web::Data<TableOfContent>) -> impl Responder {
     let timing = Instant::now();
@@ -168,6 +174,18 @@ async fn update_collection_cluster(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/warmup")]
+async fn warmup_collection(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    Query(params): Query<WarmupParams>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response =
+        do_warmup_collection(toc.get_ref(), &collection.name, params.vector.as_deref()).await;
+    process_response(response, timing)
+}
+
 // Configure services
 pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
     cfg.service(get_collections)
@@ -179,7 +197,8 @@ pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
         .service(get_collection_aliases)
         .service(update_aliases)
         .service(get_cluster_info)
-        .service(update_collection_cluster);
+        .service(update_collection_cluster)
+        .service(warmup_collection);
 }
 
 #[cfg(test)]
@@ -3,23 +3,34 @@ use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    CoreSearchRequest, SearchGroupsRequest, SearchRequest, SearchRequestBatch,
+    CoreSearchRequest, SearchDebugInfo, SearchGroupsRequest, SearchRequest, SearchRequestBatch,
 };
+use serde::Deserialize;
+use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
+use validator::Validate;
 
 use super::read_params::ReadParams;
 use super::CollectionPath;
-use crate::actix::helpers::process_response;
+use crate::actix::helpers::{process_response, process_response_with_debug};
 use crate::common::points::{
-    do_core_search_points, do_search_batch_points, do_search_point_groups,
+    do_core_search_points, do_search_batch_points, do_search_plans, do_search_point_groups,
 };
 
+#[derive(Debug, Deserialize, Validate)]
+struct SearchDebugParams {
+    /// If true, include strategies chosen by the query planner into the response
+    #[serde(default)]
+    debug: bool,
+}
+
 #[post("/collections/{name}/points/search")]
 async fn search_points(
     toc: web::Data<TableOfContent>,
     collection: Path<CollectionPath>,
     request: Json<SearchRequest>,
     params: Query<ReadParams>,
+    debug_params: Query<SearchDebugParams>,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -33,17 +44,37 @@ async fn search_points(
         Some(shard_keys) => shard_keys.into(),
     };
 
-    let response = do_core_search_points(
-        toc.get_ref(),
-        &collection.name,
-        search_request.into(),
-        params.consistency,
-        shard_selection,
-        params.timeout(),
-    )
+    let core_request: CoreSearchRequest = search_request.into();
+
+    let response = async {
+        let debug = if debug_params.debug {
+            let search_plans = do_search_plans(
+                toc.get_ref(),
+                &collection.name,
+                &core_request,
+                &shard_selection,
+            )
+            .await?;
+            Some(SearchDebugInfo { search_plans })
+        } else {
+            None
+        };
+
+        let result = do_core_search_points(
+            toc.get_ref(),
+            &collection.name,
+            core_request,
+            params.consistency,
+            shard_selection,
+            params.timeout(),
+        )
+        .await?;
+
+        Ok::<_, StorageError>((result, debug))
+    }
     .await;
 
-    process_response(response, timing)
+    process_response_with_debug(response, timing)
 }
 
 #[post("/collections/{name}/points/search/batch")]
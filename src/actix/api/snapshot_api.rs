@@ -6,7 +6,7 @@ use actix_web::{delete, get, post, put, web, Responder, Result};
 use actix_web_validator as valid;
 use collection::common::file_utils::move_file;
 use collection::operations::snapshot_ops::{
-    ShardSnapshotRecover, SnapshotPriority, SnapshotRecover,
+    SegmentSnapshotRecover, ShardSnapshotRecover, SnapshotPriority, SnapshotRecover,
 };
 use collection::shards::shard::ShardId;
 use futures::{FutureExt as _, TryFutureExt as _};
@@ -419,6 +419,56 @@ async fn delete_shard_snapshot(
     helpers::time_or_accept(future, query.wait.unwrap_or(true)).await
 }
 
+#[get("/collections/{collection}/shards/{shard}/segments")]
+async fn list_shard_segments(
+    toc: web::Data<TableOfContent>,
+    path: web::Path<(String, ShardId)>,
+) -> impl Responder {
+    let (collection, shard) = path.into_inner();
+    let future = common::snapshots::list_shard_segments(toc.into_inner(), collection, shard)
+        .map_err(Into::into);
+
+    helpers::time(future).await
+}
+
+#[post("/collections/{collection}/shards/{shard}/segments/{segment}/snapshots")]
+async fn create_segment_snapshot(
+    toc: web::Data<TableOfContent>,
+    path: web::Path<(String, ShardId, String)>,
+    query: web::Query<SnapshottingParam>,
+) -> impl Responder {
+    let (collection, shard, segment) = path.into_inner();
+    let future =
+        common::snapshots::create_segment_snapshot(toc.into_inner(), collection, shard, segment)
+            .map_err(Into::into);
+
+    helpers::time_or_accept(future, query.wait.unwrap_or(true)).await
+}
+
+#[put("/collections/{collection}/shards/{shard}/segments/{segment}/snapshots/recover")]
+async fn recover_segment_snapshot(
+    toc: web::Data<TableOfContent>,
+    http_client: web::Data<HttpClient>,
+    path: web::Path<(String, ShardId, String)>,
+    query: web::Query<SnapshottingParam>,
+    web::Json(request): web::Json<SegmentSnapshotRecover>,
+) -> impl Responder {
+    let (collection, shard, segment) = path.into_inner();
+    let future = common::snapshots::recover_segment_snapshot(
+        toc.into_inner(),
+        collection,
+        shard,
+        segment,
+        request.location,
+        request.force,
+        http_client.as_ref().clone(),
+    )
+    .map_ok(|_| true)
+    .map_err(Into::into);
+
+    helpers::time_or_accept(future, query.wait.unwrap_or(true)).await
+}
+
 // Configure services
 pub fn config_snapshots_api(cfg: &mut web::ServiceConfig) {
     cfg.service(list_snapshots)
@@ -436,5 +486,8 @@ pub fn config_snapshots_api(cfg: &mut web::ServiceConfig) {
         .service(recover_shard_snapshot)
         .service(upload_shard_snapshot)
         .service(download_shard_snapshot)
-        .service(delete_shard_snapshot);
+        .service(delete_shard_snapshot)
+        .service(list_shard_segments)
+        .service(create_segment_snapshot)
+        .service(recover_segment_snapshot);
 }
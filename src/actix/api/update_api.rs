@@ -1,7 +1,7 @@
 use actix_web::rt::time::Instant;
 use actix_web::{delete, post, put, web, Responder};
 use actix_web_validator::{Json, Path, Query};
-use collection::operations::payload_ops::{DeletePayload, SetPayload};
+use collection::operations::payload_ops::{DeletePayload, RenamePayload, SetPayload};
 use collection::operations::point_ops::{PointInsertOperations, PointsSelector, WriteOrdering};
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::JsonSchema;
@@ -14,8 +14,8 @@ use super::CollectionPath;
 use crate::actix::helpers::process_response;
 use crate::common::points::{
     do_batch_update_points, do_clear_payload, do_create_index, do_delete_index, do_delete_payload,
-    do_delete_points, do_delete_vectors, do_overwrite_payload, do_set_payload, do_update_vectors,
-    do_upsert_points, CreateFieldIndex, UpdateOperations,
+    do_delete_points, do_delete_vectors, do_overwrite_payload, do_rebuild_index, do_rename_payload,
+    do_set_payload, do_update_vectors, do_upsert_points, CreateFieldIndex, UpdateOperations,
 };
 
 #[derive(Deserialize, Validate)]
@@ -199,6 +199,30 @@ async fn delete_payload(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/points/payload/rename")]
+async fn rename_payload(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    operation: Json<RenamePayload>,
+    params: Query<UpdateParam>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let operation = operation.into_inner();
+    let wait = params.wait.unwrap_or(false);
+    let ordering = params.ordering.unwrap_or_default();
+
+    let response = do_rename_payload(
+        toc.get_ref(),
+        &collection.name,
+        operation,
+        None,
+        wait,
+        ordering,
+    )
+    .await;
+    process_response(response, timing)
+}
+
 #[post("/collections/{name}/points/payload/clear")]
 async fn clear_payload(
     toc: web::Data<TableOfContent>,
@@ -293,6 +317,29 @@ async fn delete_field_index(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/index/{field_name}/rebuild")]
+async fn rebuild_field_index(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    field: Path<FieldPath>,
+    params: Query<UpdateParam>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let wait = params.wait.unwrap_or(false);
+    let ordering = params.ordering.unwrap_or_default();
+
+    let response = do_rebuild_index(
+        toc.get_ref(),
+        &collection.name,
+        field.name.clone(),
+        None,
+        wait,
+        ordering,
+    )
+    .await;
+    process_response(response, timing)
+}
+
 // Configure services
 pub fn config_update_api(cfg: &mut web::ServiceConfig) {
     cfg.service(upsert_points)
@@ -302,8 +349,10 @@ pub fn config_update_api(cfg: &mut web::ServiceConfig) {
         .service(set_payload)
         .service(overwrite_payload)
         .service(delete_payload)
+        .service(rename_payload)
         .service(clear_payload)
         .service(create_field_index)
         .service(delete_field_index)
+        .service(rebuild_field_index)
         .service(update_batch);
 }
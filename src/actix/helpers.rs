@@ -75,6 +75,36 @@ where
     }
 }
 
+#[derive(Serialize)]
+struct ApiResponseWithDebug<D, G> {
+    #[serde(flatten)]
+    response: ApiResponse<D>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    debug: Option<G>,
+}
+
+/// Same as `process_response`, but also includes optional debug info into successful response
+pub fn process_response_with_debug<D, G>(
+    response: Result<(D, Option<G>), StorageError>,
+    timing: Instant,
+) -> HttpResponse
+where
+    D: Serialize,
+    G: Serialize,
+{
+    match response {
+        Ok((res, debug)) => HttpResponse::Ok().json(ApiResponseWithDebug {
+            response: ApiResponse {
+                result: Some(res),
+                status: ApiStatus::Ok,
+                time: timing.elapsed().as_secs_f64(),
+            },
+            debug,
+        }),
+        Err(err) => process_response::<()>(Err(err), timing),
+    }
+}
+
 /// # Cancel safety
 ///
 /// Future must be cancel safe.
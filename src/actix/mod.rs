@@ -23,6 +23,7 @@ use crate::actix::api::cluster_api::config_cluster_api;
 use crate::actix::api::collections_api::config_collections_api;
 use crate::actix::api::count_api::count_points;
 use crate::actix::api::discovery_api::config_discovery_api;
+use crate::actix::api::facet_api::facet_points;
 use crate::actix::api::recommend_api::config_recommend_api;
 use crate::actix::api::retrieve_api::{get_point, get_points, scroll_points};
 use crate::actix::api::search_api::config_search_api;
@@ -151,7 +152,8 @@ pub fn init(
                 .service(get_point)
                 .service(get_points)
                 .service(scroll_points)
-                .service(count_points);
+                .service(count_points)
+                .service(facet_points);
 
             if web_ui_available {
                 app = app.service(
@@ -148,6 +148,16 @@ pub async fn do_get_collection_cluster(
     Ok(collection.cluster_info(toc.this_peer_id).await?)
 }
 
+pub async fn do_warmup_collection(
+    toc: &TableOfContent,
+    name: &str,
+    vector_name: Option<&str>,
+) -> Result<bool, StorageError> {
+    let collection = toc.get_collection(name).await?;
+    collection.warmup(vector_name).await?;
+    Ok(true)
+}
+
 pub async fn do_update_collection_cluster(
     dispatcher: &Dispatcher,
     collection_name: String,
@@ -3,7 +3,8 @@ use std::time::Duration;
 use collection::common::batching::batch_requests;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::payload_ops::{
-    DeletePayload, DeletePayloadOp, PayloadOps, SetPayload, SetPayloadOp,
+    DeletePayload, DeletePayloadOp, PayloadOps, RenamePayload, RenamePayloadOp, SetPayload,
+    SetPayloadOp,
 };
 use collection::operations::point_ops::{
     FilterSelector, PointIdsList, PointInsertOperations, PointOperations, PointsSelector,
@@ -13,9 +14,10 @@ use collection::operations::shard_key_selector::ShardKeySelector;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
     CoreSearchRequest, CoreSearchRequestBatch, CountRequestInternal, CountResult,
-    DiscoverRequestBatch, DiscoverRequestInternal, GroupsResult, PointRequestInternal,
-    RecommendGroupsRequestInternal, Record, ScrollRequestInternal, ScrollResult,
-    SearchGroupsRequestInternal, UpdateResult,
+    DiscoverRequestBatch, DiscoverRequestInternal, FacetRequestInternal, FacetResponse,
+    GroupsResult, PointRequestInternal, RecommendGroupsRequestInternal, Record,
+    ScrollRequestInternal, ScrollResult, SearchGroupsRequestInternal, SegmentSearchPlan,
+    UpdateResult,
 };
 use collection::operations::vector_ops::{
     DeleteVectors, UpdateVectors, UpdateVectorsOp, VectorOperations,
@@ -389,6 +391,42 @@ pub async fn do_delete_payload(
     .await
 }
 
+pub async fn do_rename_payload(
+    toc: &TableOfContent,
+    collection_name: &str,
+    operation: RenamePayload,
+    shard_selection: Option<ShardId>,
+    wait: bool,
+    ordering: WriteOrdering,
+) -> Result<UpdateResult, StorageError> {
+    let RenamePayload {
+        key,
+        new_key,
+        points,
+        filter,
+        shard_key,
+    } = operation;
+
+    let collection_operation =
+        CollectionUpdateOperations::PayloadOperation(PayloadOps::RenamePayload(RenamePayloadOp {
+            key,
+            new_key,
+            points,
+            filter,
+        }));
+
+    let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
+
+    toc.update(
+        collection_name,
+        collection_operation,
+        wait,
+        ordering,
+        shard_selector,
+    )
+    .await
+}
+
 pub async fn do_clear_payload(
     toc: &TableOfContent,
     collection_name: &str,
@@ -659,6 +697,42 @@ pub async fn do_delete_index(
     .await
 }
 
+/// Drop and build again the payload index of the field, without touching vector indexes
+pub async fn do_rebuild_index(
+    toc: &TableOfContent,
+    collection_name: &str,
+    index_name: String,
+    shard_selection: Option<ShardId>,
+    wait: bool,
+    ordering: WriteOrdering,
+) -> Result<UpdateResult, StorageError> {
+    // Internal requests come from the peer, which has already checked the index
+    if shard_selection.is_none() {
+        toc.get_collection(collection_name)
+            .await?
+            .check_payload_index_exists(&index_name)?;
+    }
+
+    let collection_operation = CollectionUpdateOperations::FieldIndexOperation(
+        FieldIndexOperations::RebuildIndex(index_name),
+    );
+
+    let shard_selector = if let Some(shard_selection) = shard_selection {
+        ShardSelectorInternal::ShardId(shard_selection)
+    } else {
+        ShardSelectorInternal::All
+    };
+
+    toc.update(
+        collection_name,
+        collection_operation,
+        wait,
+        ordering,
+        shard_selector,
+    )
+    .await
+}
+
 pub async fn do_core_search_points(
     toc: &TableOfContent,
     collection_name: &str,
@@ -684,6 +758,16 @@ pub async fn do_core_search_points(
         .ok_or_else(|| StorageError::service_error("Empty search result"))
 }
 
+pub async fn do_search_plans(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: &CoreSearchRequest,
+    shard_selection: &ShardSelectorInternal,
+) -> Result<Vec<SegmentSearchPlan>, StorageError> {
+    let collection = toc.get_collection(collection_name).await?;
+    Ok(collection.search_plans(request, shard_selection).await?)
+}
+
 pub async fn do_search_batch_points(
     toc: &TableOfContent,
     collection_name: &str,
@@ -836,6 +920,24 @@ pub async fn do_count_points(
         .await
 }
 
+pub async fn do_facet_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: FacetRequestInternal,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    timeout: Option<Duration>,
+) -> Result<FacetResponse, StorageError> {
+    toc.facet(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        timeout,
+    )
+    .await
+}
+
 pub async fn do_get_points(
     toc: &TableOfContent,
     collection_name: &str,
@@ -1,16 +1,17 @@
 use std::fmt;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use collection::collection::Collection;
 use collection::operations::snapshot_ops::{
-    ShardSnapshotLocation, SnapshotDescription, SnapshotPriority,
+    SegmentDescription, ShardSnapshotLocation, SnapshotDescription, SnapshotPriority,
 };
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::ShardId;
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::snapshots;
 use storage::content_manager::toc::TableOfContent;
+use tempfile::TempPath;
 
 use super::http_client::HttpClient;
 
@@ -87,32 +88,14 @@ pub async fn recover_shard_snapshot(
 
             let download_dir = toc.snapshots_download_tempdir()?;
 
-            let (snapshot_path, snapshot_temp_path) = match snapshot_location {
-                ShardSnapshotLocation::Url(url) => {
-                    if !matches!(url.scheme(), "http" | "https") {
-                        let description = format!(
-                            "Invalid snapshot URL {url}: URLs with {} scheme are not supported",
-                            url.scheme(),
-                        );
-
-                        return Err(StorageError::bad_input(description));
-                    }
-
-                    let client = client.client()?;
-
-                    let (snapshot_path, snapshot_temp_path) =
-                        snapshots::download::download_snapshot(&client, url, download_dir.path())
-                            .await?;
-
-                    (snapshot_path, snapshot_temp_path)
-                }
-
-                ShardSnapshotLocation::Path(path) => {
-                    let snapshot_path = collection.get_shard_snapshot_path(shard_id, path).await?;
-                    check_shard_snapshot_file_exists(&snapshot_path)?;
-                    (snapshot_path, None)
-                }
-            };
+            let (snapshot_path, snapshot_temp_path) = shard_snapshot_path_from_location(
+                &collection,
+                shard_id,
+                snapshot_location,
+                download_dir.path(),
+                &client,
+            )
+            .await?;
 
             Result::<_, StorageError>::Ok((
                 collection,
@@ -234,6 +217,124 @@ pub async fn recover_shard_snapshot_impl(
     Ok(())
 }
 
+/// # Cancel safety
+///
+/// This function is cancel safe.
+pub async fn list_shard_segments(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    shard_id: ShardId,
+) -> Result<Vec<SegmentDescription>, StorageError> {
+    let collection = toc.get_collection(&collection_name).await?;
+    let segments = collection.list_shard_segments(shard_id).await?;
+    Ok(segments)
+}
+
+/// # Cancel safety
+///
+/// This function is cancel safe.
+pub async fn create_segment_snapshot(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    shard_id: ShardId,
+    segment_id: String,
+) -> Result<SnapshotDescription, StorageError> {
+    let collection = toc.get_collection(&collection_name).await?;
+
+    let snapshot = collection
+        .create_segment_snapshot(
+            shard_id,
+            &segment_id,
+            &toc.optional_temp_or_snapshot_temp_path()?,
+        )
+        .await?;
+
+    Ok(snapshot)
+}
+
+/// Restore a single segment of the local shard, other segments of the shard are not affected
+///
+/// # Cancel safety
+///
+/// This function is cancel safe.
+pub async fn recover_segment_snapshot(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    shard_id: ShardId,
+    segment_id: String,
+    snapshot_location: ShardSnapshotLocation,
+    force: bool,
+    client: HttpClient,
+) -> Result<(), StorageError> {
+    // - `download_dir` handled by `tempfile` and would be deleted, if request is cancelled
+    //   - remote snapshot is downloaded into `download_dir` and would be deleted with it
+
+    let collection = toc.get_collection(&collection_name).await?;
+    collection.assert_shard_exists(shard_id).await?;
+
+    let download_dir = toc.snapshots_download_tempdir()?;
+
+    let (snapshot_path, snapshot_temp_path) = shard_snapshot_path_from_location(
+        &collection,
+        shard_id,
+        snapshot_location,
+        download_dir.path(),
+        &client,
+    )
+    .await?;
+
+    let result = collection
+        .restore_segment_snapshot(shard_id, &segment_id, &snapshot_path, force)
+        .await;
+
+    // Remove snapshot after recovery if downloaded
+    if let Some(path) = snapshot_temp_path {
+        if let Err(err) = path.close() {
+            log::error!("Failed to remove downloaded segment snapshot after recovery: {err}");
+        }
+    }
+
+    result?;
+
+    Ok(())
+}
+
+/// Get local path of the shard snapshot, remote snapshot is downloaded into `download_dir`
+///
+/// # Cancel safety
+///
+/// This function is cancel safe.
+async fn shard_snapshot_path_from_location(
+    collection: &Collection,
+    shard_id: ShardId,
+    snapshot_location: ShardSnapshotLocation,
+    download_dir: &Path,
+    client: &HttpClient,
+) -> Result<(PathBuf, Option<TempPath>), StorageError> {
+    match snapshot_location {
+        ShardSnapshotLocation::Url(url) => {
+            if !matches!(url.scheme(), "http" | "https") {
+                let description = format!(
+                    "Invalid snapshot URL {url}: URLs with {} scheme are not supported",
+                    url.scheme(),
+                );
+
+                return Err(StorageError::bad_input(description));
+            }
+
+            let client = client.client()?;
+
+            snapshots::download::download_snapshot(&client, url, download_dir).await
+        }
+
+        ShardSnapshotLocation::Path(path) => {
+            let snapshot_path = collection.get_shard_snapshot_path(shard_id, path).await?;
+            check_shard_snapshot_file_exists(&snapshot_path)?;
+            Ok((snapshot_path, None))
+        }
+    }
+}
+
 fn check_shard_snapshot_file_exists(snapshot_path: &Path) -> Result<(), StorageError> {
     let snapshot_path_display = snapshot_path.display();
     let snapshot_file_name = snapshot_path.file_name().and_then(|str| str.to_str());
@@ -1224,6 +1224,11 @@ mod tests {
                                 hnsw_config: None,
                                 quantization_config: None,
                                 on_disk: None,
+                                index: None,
+                                multivector_config: None,
+                                datatype: None,
+                                keep_original: None,
+                                dimension_weights: None,
                             }
                             .into(),
                             sparse_vectors: None,
@@ -1237,6 +1242,12 @@ mod tests {
                             init_from: None,
                             quantization_config: None,
                             sharding_method: None,
+                            mmap_advice: None,
+                            mmap_prefault: None,
+                            mmap_hot_percent: None,
+                            strict_payload_schema: None,
+                            payload_compression: None,
+                            payload_storage_backend: None,
                         },
                     )),
                     None,
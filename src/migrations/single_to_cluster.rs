@@ -54,6 +54,12 @@ pub async fn handle_existing_collections(
                 optimizers_config: Some(collection_state.config.optimizer_config.into()),
                 init_from: None,
                 quantization_config: collection_state.config.quantization_config,
+                mmap_advice: collection_state.config.params.mmap_advice,
+                mmap_prefault: collection_state.config.params.mmap_prefault,
+                mmap_hot_percent: collection_state.config.params.mmap_hot_percent,
+                strict_payload_schema: collection_state.config.params.strict_payload_schema,
+                payload_compression: collection_state.config.params.payload_compression,
+                payload_storage_backend: collection_state.config.params.payload_storage_backend,
             },
         );
 
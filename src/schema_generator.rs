@@ -1,17 +1,18 @@
 use api::grpc::models::CollectionsResponse;
 use collection::operations::cluster_ops::ClusterOperations;
 use collection::operations::consistency_params::ReadConsistency;
-use collection::operations::payload_ops::{DeletePayload, SetPayload};
+use collection::operations::payload_ops::{DeletePayload, RenamePayload, SetPayload};
 use collection::operations::point_ops::{PointInsertOperations, PointsSelector, WriteOrdering};
 use collection::operations::snapshot_ops::{
-    ShardSnapshotRecover, SnapshotDescription, SnapshotRecover,
+    SegmentDescription, SegmentSnapshotRecover, ShardSnapshotRecover, SnapshotDescription,
+    SnapshotRecover,
 };
 use collection::operations::types::{
     AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionsAliasesResponse,
-    CountRequest, CountResult, DiscoverRequest, DiscoverRequestBatch, GroupsResult, PointGroup,
-    PointRequest, RecommendGroupsRequest, RecommendRequest, RecommendRequestBatch, Record,
-    ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest, SearchRequestBatch,
-    UpdateResult,
+    CountRequest, CountResult, DiscoverRequest, DiscoverRequestBatch, FacetRequest, FacetResponse,
+    GroupsResult, PointGroup, PointRequest, RecommendGroupsRequest, RecommendRequest,
+    RecommendRequestBatch, Record, ScrollRequest, ScrollResult, SearchDebugInfo,
+    SearchGroupsRequest, SearchRequest, SearchRequestBatch, UpdateResult,
 };
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::gen::SchemaSettings;
@@ -78,6 +79,12 @@ struct AllDefinitions {
     b9: ShardSnapshotRecover,
     ba: DiscoverRequest,
     bb: DiscoverRequestBatch,
+    bc: FacetRequest,
+    bd: FacetResponse,
+    be: RenamePayload,
+    bf: SearchDebugInfo,
+    bg: SegmentDescription,
+    bh: SegmentSnapshotRecover,
 }
 
 fn save_schema<T: JsonSchema>() {
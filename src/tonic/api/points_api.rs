@@ -6,11 +6,11 @@ use api::grpc::qdrant::{
     ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
     DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors, DeletePoints,
     DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, GetPoints,
-    GetResponse, PointsOperationResponse, RecommendBatchPoints, RecommendBatchResponse,
-    RecommendGroupsResponse, RecommendPointGroups, RecommendPoints, RecommendResponse,
-    ScrollPoints, ScrollResponse, SearchBatchPoints, SearchBatchResponse, SearchGroupsResponse,
-    SearchPointGroups, SearchPoints, SearchResponse, SetPayloadPoints, UpdateBatchPoints,
-    UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
+    GetResponse, PointsOperationResponse, RebuildFieldIndexCollection, RecommendBatchPoints,
+    RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups, RecommendPoints,
+    RecommendResponse, RenamePayloadPoints, ScrollPoints, ScrollResponse, SearchBatchPoints,
+    SearchBatchResponse, SearchGroupsResponse, SearchPointGroups, SearchPoints, SearchResponse,
+    SetPayloadPoints, UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
 };
 use collection::operations::types::CoreSearchRequest;
 use storage::dispatcher::Dispatcher;
@@ -23,8 +23,8 @@ use super::points_common::{
 use super::validate;
 use crate::tonic::api::points_common::{
     clear_payload, convert_shard_selector_for_read, core_search_batch, count, create_field_index,
-    delete, delete_field_index, delete_payload, get, overwrite_payload, recommend, recommend_batch,
-    scroll, search, set_payload, upsert,
+    delete, delete_field_index, delete_payload, get, overwrite_payload, rebuild_field_index,
+    recommend, recommend_batch, rename_payload, scroll, search, set_payload, upsert,
 };
 
 pub struct PointsService {
@@ -100,6 +100,14 @@ impl Points for PointsService {
         delete_payload(self.dispatcher.as_ref(), request.into_inner(), None).await
     }
 
+    async fn rename_payload(
+        &self,
+        request: Request<RenamePayloadPoints>,
+    ) -> Result<Response<PointsOperationResponse>, Status> {
+        validate(request.get_ref())?;
+        rename_payload(self.dispatcher.as_ref(), request.into_inner(), None).await
+    }
+
     async fn clear_payload(
         &self,
         request: Request<ClearPayloadPoints>,
@@ -132,6 +140,14 @@ impl Points for PointsService {
         delete_field_index(self.dispatcher.as_ref(), request.into_inner(), None).await
     }
 
+    async fn rebuild_field_index(
+        &self,
+        request: Request<RebuildFieldIndexCollection>,
+    ) -> Result<Response<PointsOperationResponse>, Status> {
+        validate(request.get_ref())?;
+        rebuild_field_index(self.dispatcher.as_ref(), request.into_inner(), None).await
+    }
+
     async fn search(
         &self,
         request: Request<SearchPoints>,
@@ -1,32 +1,32 @@
 use std::time::{Duration, Instant};
 
 use api::grpc::conversions::proto_to_payloads;
-use api::grpc::qdrant::payload_index_params::IndexParams;
 use api::grpc::qdrant::points_update_operation::{ClearPayload, Operation, PointStructList};
 use api::grpc::qdrant::{
     points_update_operation, BatchResult, ClearPayloadPoints, CoreSearchPoints, CountPoints,
     CountResponse, CreateFieldIndexCollection, DeleteFieldIndexCollection, DeletePayloadPoints,
     DeletePointVectors, DeletePoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse,
-    FieldType, GetPoints, GetResponse, PayloadIndexParams, PointsOperationResponse, PointsSelector,
-    ReadConsistency as ReadConsistencyGrpc, RecommendBatchResponse, RecommendGroupsResponse,
-    RecommendPointGroups, RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse,
-    SearchBatchResponse, SearchGroupsResponse, SearchPointGroups, SearchPoints, SearchResponse,
-    SetPayloadPoints, SyncPoints, UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors,
-    UpsertPoints,
+    FacetCounts, FacetResponse, FieldType, GetPoints, GetResponse, PayloadIndexParams,
+    PointsOperationResponse, PointsSelector, ReadConsistency as ReadConsistencyGrpc,
+    RebuildFieldIndexCollection, RecommendBatchResponse, RecommendGroupsResponse,
+    RecommendPointGroups, RecommendPoints, RecommendResponse, RenamePayloadPoints, ScrollPoints,
+    ScrollResponse, SearchBatchResponse, SearchGroupsResponse, SearchPointGroups, SearchPoints,
+    SearchResponse, SetPayloadPoints, SyncPoints, UpdateBatchPoints, UpdateBatchResponse,
+    UpdatePointVectors, UpsertPoints,
 };
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::conversions::{
     try_discover_request_from_grpc, try_points_selector_from_grpc, write_ordering_from_proto,
 };
-use collection::operations::payload_ops::DeletePayload;
+use collection::operations::payload_ops::{DeletePayload, RenamePayload};
 use collection::operations::point_ops::{
     self, PointInsertOperations, PointOperations, PointSyncOperation, PointsList,
 };
 use collection::operations::shard_key_selector::ShardKeySelector;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    default_exact_count, CoreSearchRequest, CoreSearchRequestBatch, PointRequestInternal,
-    QueryEnum, RecommendExample, ScrollRequestInternal,
+    default_exact_count, default_facet_limit, CoreSearchRequest, CoreSearchRequestBatch,
+    PointRequestInternal, QueryEnum, RecommendExample, ScrollRequestInternal,
 };
 use collection::operations::vector_ops::{DeleteVectors, PointVectors, UpdateVectors};
 use collection::operations::CollectionUpdateOperations;
@@ -39,11 +39,14 @@ use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
 use tonic::{Response, Status};
 
+use super::validate;
+
 use crate::common::points::{
     do_clear_payload, do_core_search_points, do_count_points, do_create_index,
     do_create_index_internal, do_delete_index, do_delete_index_internal, do_delete_payload,
-    do_delete_points, do_delete_vectors, do_get_points, do_overwrite_payload, do_scroll_points,
-    do_search_batch_points, do_set_payload, do_update_vectors, do_upsert_points, CreateFieldIndex,
+    do_delete_points, do_delete_vectors, do_facet_points, do_get_points, do_overwrite_payload,
+    do_rebuild_index, do_rename_payload, do_scroll_points, do_search_batch_points, do_set_payload,
+    do_update_vectors, do_upsert_points, CreateFieldIndex,
 };
 
 fn extract_points_selector(
@@ -415,6 +418,47 @@ pub async fn delete_payload(
     Ok(Response::new(response))
 }
 
+pub async fn rename_payload(
+    toc: &TableOfContent,
+    rename_payload_points: RenamePayloadPoints,
+    shard_selection: Option<ShardId>,
+) -> Result<Response<PointsOperationResponse>, Status> {
+    let RenamePayloadPoints {
+        collection_name,
+        wait,
+        key,
+        new_key,
+        points_selector,
+        ordering,
+        shard_key_selector,
+    } = rename_payload_points;
+
+    let (points, filter) = extract_points_selector(points_selector)?;
+    let operation = RenamePayload {
+        key,
+        new_key,
+        points,
+        filter,
+        shard_key: shard_key_selector.map(ShardKeySelector::from),
+    };
+    validate(&operation)?;
+
+    let timing = Instant::now();
+    let result = do_rename_payload(
+        toc,
+        &collection_name,
+        operation,
+        shard_selection,
+        wait.unwrap_or(false),
+        write_ordering_from_proto(ordering)?,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    let response = points_operation_response(timing, result);
+    Ok(Response::new(response))
+}
+
 pub async fn clear_payload(
     toc: &TableOfContent,
     clear_payload_points: ClearPayloadPoints,
@@ -676,18 +720,24 @@ fn convert_field_type(
         (
             Some(v),
             Some(PayloadIndexParams {
-                index_params: Some(IndexParams::TextIndexParams(text_index_params)),
+                index_params: Some(index_params),
             }),
-        ) => match v {
-            FieldType::Text => Some(PayloadFieldSchema::FieldParams(PayloadSchemaParams::Text(
-                text_index_params.try_into()?,
-            ))),
-            _ => {
-                return Err(Status::invalid_argument(
-                    "field_type and field_index_params do not match",
-                ))
+        ) => {
+            let params = PayloadSchemaParams::try_from(index_params)?;
+            match (v, &params) {
+                (FieldType::Keyword, PayloadSchemaParams::Keyword(_))
+                | (FieldType::Integer, PayloadSchemaParams::Integer(_))
+                | (FieldType::Float, PayloadSchemaParams::Float(_))
+                | (FieldType::Text, PayloadSchemaParams::Text(_)) => {
+                    Some(PayloadFieldSchema::FieldParams(params))
+                }
+                _ => {
+                    return Err(Status::invalid_argument(
+                        "field_type and field_index_params do not match",
+                    ))
+                }
             }
-        },
+        }
         (Some(v), None | Some(PayloadIndexParams { index_params: None })) => match v {
             FieldType::Keyword => Some(PayloadSchemaType::Keyword.into()),
             FieldType::Integer => Some(PayloadSchemaType::Integer.into()),
@@ -695,6 +745,8 @@ fn convert_field_type(
             FieldType::Geo => Some(PayloadSchemaType::Geo.into()),
             FieldType::Text => Some(PayloadSchemaType::Text.into()),
             FieldType::Bool => Some(PayloadSchemaType::Bool.into()),
+            FieldType::Datetime => Some(PayloadSchemaType::Datetime.into()),
+            FieldType::Uuid => Some(PayloadSchemaType::Uuid.into()),
         },
         (None, Some(_)) => return Err(Status::invalid_argument("field type is missing")),
         (None, None) => None,
@@ -829,6 +881,34 @@ pub async fn delete_field_index_internal(
     Ok(Response::new(response))
 }
 
+pub async fn rebuild_field_index(
+    toc: &TableOfContent,
+    rebuild_field_index_collection: RebuildFieldIndexCollection,
+    shard_selection: Option<ShardId>,
+) -> Result<Response<PointsOperationResponse>, Status> {
+    let RebuildFieldIndexCollection {
+        collection_name,
+        wait,
+        field_name,
+        ordering,
+    } = rebuild_field_index_collection;
+
+    let timing = Instant::now();
+    let result = do_rebuild_index(
+        toc,
+        &collection_name,
+        field_name,
+        shard_selection,
+        wait.unwrap_or(false),
+        write_ordering_from_proto(ordering)?,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    let response = points_operation_response(timing, result);
+    Ok(Response::new(response))
+}
+
 pub async fn search(
     toc: &TableOfContent,
     search_points: SearchPoints,
@@ -849,6 +929,7 @@ pub async fn search(
         timeout,
         shard_key_selector,
         sparse_indices,
+        tie_breaker,
     } = search_points;
 
     let vector_struct =
@@ -869,6 +950,7 @@ pub async fn search(
                 .unwrap_or_default(),
         ),
         score_threshold,
+        tie_breaker: tie_breaker.map(|t| t.try_into()).transpose()?,
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
@@ -1267,6 +1349,7 @@ pub async fn scroll(
         with_vectors,
         read_consistency,
         shard_key_selector,
+        order_by,
     } = scroll_points;
 
     let scroll_request = ScrollRequestInternal {
@@ -1277,6 +1360,7 @@ pub async fn scroll(
         with_vector: with_vectors
             .map(|selector| selector.into())
             .unwrap_or_default(),
+        order_by: order_by.map(|o| o.try_into()).transpose()?,
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
@@ -1348,6 +1432,55 @@ pub async fn count(
     Ok(Response::new(response))
 }
 
+pub async fn facet(
+    toc: &TableOfContent,
+    facet_counts: FacetCounts,
+    shard_selection: Option<ShardId>,
+) -> Result<Response<FacetResponse>, Status> {
+    let FacetCounts {
+        collection_name,
+        key,
+        filter,
+        limit,
+        exact,
+        timeout,
+        read_consistency,
+        shard_key_selector,
+    } = facet_counts;
+
+    let facet_request = collection::operations::types::FacetRequestInternal {
+        key,
+        limit: limit.map_or_else(default_facet_limit, |limit| limit as usize),
+        filter: filter.map(|f| f.try_into()).transpose()?,
+        exact: exact.unwrap_or_default(),
+    };
+
+    let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+
+    let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
+
+    let timeout = timeout.map(Duration::from_secs);
+
+    let timing = Instant::now();
+    let facet_response = do_facet_points(
+        toc,
+        &collection_name,
+        facet_request,
+        read_consistency,
+        shard_selector,
+        timeout,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    let response = FacetResponse {
+        hits: facet_response.hits.into_iter().map(From::from).collect(),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
 pub async fn get(
     toc: &TableOfContent,
     get_points: GetPoints,
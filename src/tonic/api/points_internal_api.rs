@@ -5,11 +5,12 @@ use api::grpc::qdrant::points_internal_server::PointsInternal;
 use api::grpc::qdrant::{
     ClearPayloadPointsInternal, CoreSearchBatchPointsInternal, CountPointsInternal, CountResponse,
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollectionInternal,
-    DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, GetPointsInternal,
-    GetResponse, PointsOperationResponse, RecommendPointsInternal, RecommendResponse,
-    ScrollPointsInternal, ScrollResponse, SearchBatchPointsInternal, SearchBatchResponse,
-    SearchPointsInternal, SearchResponse, SetPayloadPointsInternal, SyncPointsInternal,
-    UpdateVectorsInternal, UpsertPointsInternal,
+    DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, FacetCountsInternal,
+    FacetResponse, GetPointsInternal, GetResponse, PointsOperationResponse,
+    RebuildFieldIndexCollectionInternal, RecommendPointsInternal, RecommendResponse,
+    RenamePayloadPointsInternal, ScrollPointsInternal, ScrollResponse, SearchBatchPointsInternal,
+    SearchBatchResponse, SearchPointsInternal, SearchResponse, SetPayloadPointsInternal,
+    SyncPointsInternal, UpdateVectorsInternal, UpsertPointsInternal,
 };
 use storage::content_manager::toc::TableOfContent;
 use tonic::{Request, Response, Status};
@@ -18,8 +19,8 @@ use super::points_common::core_search_list;
 use super::validate_and_log;
 use crate::tonic::api::points_common::{
     clear_payload, count, create_field_index_internal, delete, delete_field_index_internal,
-    delete_payload, delete_vectors, get, overwrite_payload, recommend, scroll, set_payload, sync,
-    update_vectors, upsert,
+    delete_payload, delete_vectors, facet, get, overwrite_payload, rebuild_field_index, recommend,
+    rename_payload, scroll, set_payload, sync, update_vectors, upsert,
 };
 
 /// This API is intended for P2P communication within a distributed deployment.
@@ -129,6 +130,22 @@ impl PointsInternal for PointsInternalService {
         delete_payload(self.toc.as_ref(), delete_payload_points, shard_id).await
     }
 
+    async fn rename_payload(
+        &self,
+        request: Request<RenamePayloadPointsInternal>,
+    ) -> Result<Response<PointsOperationResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let RenamePayloadPointsInternal {
+            rename_payload_points,
+            shard_id,
+        } = request.into_inner();
+
+        let rename_payload_points = rename_payload_points
+            .ok_or_else(|| Status::invalid_argument("RenamePayloadPoints is missing"))?;
+
+        rename_payload(self.toc.as_ref(), rename_payload_points, shard_id).await
+    }
+
     async fn clear_payload(
         &self,
         request: Request<ClearPayloadPointsInternal>,
@@ -179,6 +196,22 @@ impl PointsInternal for PointsInternalService {
             .await
     }
 
+    async fn rebuild_field_index(
+        &self,
+        request: Request<RebuildFieldIndexCollectionInternal>,
+    ) -> Result<Response<PointsOperationResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let RebuildFieldIndexCollectionInternal {
+            rebuild_field_index_collection,
+            shard_id,
+        } = request.into_inner();
+
+        let rebuild_field_index_collection = rebuild_field_index_collection
+            .ok_or_else(|| Status::invalid_argument("RebuildFieldIndexCollection is missing"))?;
+
+        rebuild_field_index(self.toc.as_ref(), rebuild_field_index_collection, shard_id).await
+    }
+
     async fn search(
         &self,
         _request: Request<SearchPointsInternal>,
@@ -300,6 +333,21 @@ impl PointsInternal for PointsInternalService {
         count(self.toc.as_ref(), count_points, shard_id).await
     }
 
+    async fn facet(
+        &self,
+        request: Request<FacetCountsInternal>,
+    ) -> Result<Response<FacetResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let FacetCountsInternal {
+            facet_counts,
+            shard_id,
+        } = request.into_inner();
+
+        let facet_counts =
+            facet_counts.ok_or_else(|| Status::invalid_argument("FacetCounts is missing"))?;
+        facet(self.toc.as_ref(), facet_counts, shard_id).await
+    }
+
     async fn sync(
         &self,
         request: Request<SyncPointsInternal>,